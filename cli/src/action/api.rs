@@ -14,27 +14,132 @@
 
 //! Provides convenient functions for sending REST API requests to a splinter node.
 
-use reqwest::blocking::Client;
+#[cfg(feature = "client-tls")]
+use std::fs;
+#[cfg(feature = "client-tls")]
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use openssl::{rand::rand_bytes, sha::sha256};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+#[cfg(feature = "client-tls")]
+use reqwest::{Certificate, Identity};
+use sawtooth_sdk::signing::secp256k1;
 use serde::Deserialize;
+use splinter::signing::{sawtooth, Signer};
+#[cfg(feature = "client-tls")]
+use splinter::tls::load_client_identity;
 
 use super::CliError;
 
+#[cfg(feature = "client-tls")]
+const SPLINTER_REST_API_CA_CERT_ENV: &str = "SPLINTER_REST_API_CA_CERT";
+#[cfg(feature = "client-tls")]
+const SPLINTER_REST_API_CLIENT_CERT_ENV: &str = "SPLINTER_REST_API_CLIENT_CERT";
+#[cfg(feature = "client-tls")]
+const SPLINTER_REST_API_CLIENT_KEY_ENV: &str = "SPLINTER_REST_API_CLIENT_KEY";
+
+/// The scheme of the `Authorization` header of signed requests
+const SIGNED_REQUEST_SCHEME: &str = "Cygnus";
+
 /// A wrapper around the Splinter REST API.
 pub struct SplinterRestClient<'a> {
     pub url: &'a str,
+    client: Client,
+    private_key: Option<String>,
 }
 
 impl<'a> SplinterRestClient<'a> {
     /// Constructs a new client for a Splinter node at the given URL.
-    pub fn new(url: &'a str) -> Self {
-        Self { url }
+    pub fn new(url: &'a str) -> Result<Self, CliError> {
+        Ok(Self {
+            url,
+            client: new_http_client()?,
+            private_key: None,
+        })
+    }
+
+    /// Signs the client's requests with the given hex-encoded secp256k1 private key, so that the
+    /// Splinter node identifies the caller by its public key.
+    pub fn with_signing_key(mut self, private_key: &str) -> Result<Self, CliError> {
+        secp256k1::Secp256k1PrivateKey::from_hex(private_key).map_err(|err| {
+            CliError::ActionError(format!("Invalid secp256k1 private key provided: {}", err))
+        })?;
+        self.private_key = Some(private_key.to_string());
+        Ok(self)
+    }
+
+    /// Gets the HTTP client for sending requests to the Splinter node.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Sends the request, adding a signed-request `Authorization` header if the client has a
+    /// signing key. The signature covers the request's method, path, a timestamp, a random nonce
+    /// and the SHA-256 digest of its body.
+    pub fn send(&self, request: RequestBuilder) -> Result<Response, String> {
+        let mut request = request.build().map_err(|err| err.to_string())?;
+
+        if let Some(private_key) = &self.private_key {
+            let url = request.url();
+            let path = match url.query() {
+                Some(query) => format!("{}?{}", url.path(), query),
+                None => url.path().to_string(),
+            };
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|err| err.to_string())?
+                .as_secs();
+            let mut nonce = [0u8; 16];
+            rand_bytes(&mut nonce).map_err(|err| format!("Failed to create nonce: {}", err))?;
+            let nonce = to_hex(&nonce);
+            let body_sha256 = to_hex(&sha256(
+                request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .unwrap_or(&[]),
+            ));
+            let message = format!(
+                "{}\n{}\n{}\n{}\n{}",
+                request.method(),
+                path,
+                timestamp,
+                nonce,
+                body_sha256
+            );
+
+            let signing_context = secp256k1::Secp256k1Context::new();
+            let private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+                .map_err(|err| format!("Invalid secp256k1 private key provided: {}", err))?;
+            let signer =
+                sawtooth::SawtoothSecp256k1RefSigner::new(&signing_context, private_key)
+                    .map_err(|err| format!("Failed to create signer from private key: {}", err))?;
+            let signature = signer
+                .sign(message.as_bytes())
+                .map_err(|err| format!("Failed to sign request: {}", err))?;
+
+            let authorization = format!(
+                "{} {}:{}:{}:{}:{}",
+                SIGNED_REQUEST_SCHEME,
+                to_hex(signer.public_key()),
+                timestamp,
+                nonce,
+                body_sha256,
+                to_hex(&signature)
+            );
+            request.headers_mut().insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&authorization).map_err(|err| err.to_string())?,
+            );
+        }
+
+        self.client.execute(request).map_err(|err| err.to_string())
     }
 
     /// Gets the Splinter node's status.
     pub fn get_node_status(&self) -> Result<NodeStatus, CliError> {
-        Client::new()
-            .get(&format!("{}/status", self.url))
-            .send()
+        self.send(self.client.get(&format!("{}/status", self.url)))
             .map_err(|err| CliError::ActionError(format!("Failed to fetch node ID: {}", err)))
             .and_then(|res| {
                 let status = res.status();
@@ -65,6 +170,72 @@ impl<'a> SplinterRestClient<'a> {
     }
 }
 
+/// Creates the HTTP client for sending requests to a Splinter REST API.
+///
+/// If the REST API is served over HTTPS with a certificate from a private certificate authority,
+/// the authority's certificate (*.pem file) may be set with the `SPLINTER_REST_API_CA_CERT`
+/// environment variable. If the REST API requires client certificates, the client certificate and
+/// its private key (*.pem files) are set with the `SPLINTER_REST_API_CLIENT_CERT` and
+/// `SPLINTER_REST_API_CLIENT_KEY` environment variables.
+#[cfg(feature = "client-tls")]
+pub fn new_http_client() -> Result<Client, CliError> {
+    let mut builder = Client::builder();
+
+    if let Ok(ca_cert) = std::env::var(SPLINTER_REST_API_CA_CERT_ENV) {
+        let certificate = Certificate::from_pem(&read_pem_file(&ca_cert)?).map_err(|err| {
+            CliError::EnvironmentError(format!("Invalid CA certificate {}: {}", ca_cert, err))
+        })?;
+        builder = builder.add_root_certificate(certificate);
+    }
+
+    match (
+        std::env::var(SPLINTER_REST_API_CLIENT_CERT_ENV),
+        std::env::var(SPLINTER_REST_API_CLIENT_KEY_ENV),
+    ) {
+        (Ok(cert), Ok(key)) => {
+            builder = builder.identity(load_identity(&cert, &key)?);
+        }
+        (Err(_), Err(_)) => (),
+        _ => {
+            return Err(CliError::EnvironmentError(format!(
+                "{} and {} must be set together",
+                SPLINTER_REST_API_CLIENT_CERT_ENV, SPLINTER_REST_API_CLIENT_KEY_ENV
+            )))
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|err| CliError::EnvironmentError(format!("Failed to create HTTP client: {}", err)))
+}
+
+/// Creates the HTTP client for sending requests to a Splinter REST API.
+#[cfg(not(feature = "client-tls"))]
+pub fn new_http_client() -> Result<Client, CliError> {
+    Ok(Client::new())
+}
+
+/// Loads a client identity from the given PEM certificate and private key files.
+#[cfg(feature = "client-tls")]
+fn load_identity(cert: &str, key: &str) -> Result<Identity, CliError> {
+    let pkcs12 = load_client_identity(Path::new(cert), Path::new(key)).map_err(|err| {
+        CliError::EnvironmentError(format!("Failed to load client identity: {}", err))
+    })?;
+
+    Identity::from_pkcs12_der(&pkcs12, "")
+        .map_err(|err| CliError::EnvironmentError(format!("Invalid client identity: {}", err)))
+}
+
+#[cfg(feature = "client-tls")]
+fn read_pem_file(path: &str) -> Result<Vec<u8>, CliError> {
+    fs::read(path)
+        .map_err(|err| CliError::EnvironmentError(format!("Failed to read {}: {}", path, err)))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
 #[derive(Deserialize)]
 pub struct ServerError {
     pub message: String,
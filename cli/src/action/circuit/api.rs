@@ -15,9 +15,12 @@
 use std::collections::BTreeMap;
 use std::fmt;
 
-use reqwest::{blocking::Client, header, StatusCode};
+use reqwest::{header, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::error::Result as JsonResult;
+#[cfg(feature = "proposal-validation")]
+use splinter::admin::messages::NodeValidation;
+use splinter::admin::messages::{SignerQuorum, VotingPolicy};
 use splinter::protocol::ADMIN_PROTOCOL_VERSION;
 
 use crate::action::api::{ServerError, SplinterRestClient};
@@ -28,37 +31,79 @@ const PAGING_LIMIT: &str = "1000";
 impl<'a> SplinterRestClient<'a> {
     /// Submits an admin payload to this client's Splinter node.
     pub fn submit_admin_payload(&self, payload: Vec<u8>) -> Result<(), CliError> {
-        Client::new()
-            .post(&format!("{}/admin/submit", self.url))
-            .header(header::CONTENT_TYPE, "octet-stream")
-            .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION)
-            .body(payload)
-            .send()
-            .map_err(|err| {
-                CliError::ActionError(format!("Failed to submit admin payload: {}", err))
-            })
-            .and_then(|res| {
-                let status = res.status();
-                if status.is_success() {
-                    Ok(())
-                } else {
-                    let message = res
-                        .json::<ServerError>()
-                        .map_err(|_| {
-                            CliError::ActionError(format!(
-                                "Admin payload submit request failed with status code '{}', but \
+        self.send(
+            self.client()
+                .post(&format!("{}/admin/submit", self.url))
+                .header(header::CONTENT_TYPE, "octet-stream")
+                .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION)
+                .body(payload),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to submit admin payload: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                Ok(())
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Admin payload submit request failed with status code '{}', but \
                                  error response was not valid",
-                                status
-                            ))
-                        })?
-                        .message;
-
-                    Err(CliError::ActionError(format!(
-                        "Failed to submit admin payload: {}",
-                        message
-                    )))
-                }
-            })
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to submit admin payload: {}",
+                    message
+                )))
+            }
+        })
+    }
+
+    /// Asks this client's Splinter node to validate an admin payload, on itself and on the
+    /// circuit's other members, without submitting it.
+    #[cfg(feature = "proposal-validation")]
+    pub fn validate_admin_payload(
+        &self,
+        payload: Vec<u8>,
+    ) -> Result<ProposalValidationSlice, CliError> {
+        self.send(
+            self.client()
+                .post(&format!("{}/admin/proposals/validate", self.url))
+                .header(header::CONTENT_TYPE, "octet-stream")
+                .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION)
+                .body(payload),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to validate admin payload: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<ProposalValidationSlice>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Admin payload validation request failed with status code '{}', \
+                                 but error response was not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to validate admin payload: {}",
+                    message
+                )))
+            }
+        })
     }
 
     pub fn list_circuits(&self, filter: Option<&str>) -> Result<CircuitListSlice, CliError> {
@@ -67,79 +112,82 @@ impl<'a> SplinterRestClient<'a> {
             request = format!("{}&filter={}", &request, &filter);
         }
 
-        Client::new()
-            .get(&request)
-            .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION)
-            .send()
-            .map_err(|err| CliError::ActionError(format!("Failed to list circuits: {}", err)))
-            .and_then(|res| {
-                let status = res.status();
-                if status.is_success() {
-                    res.json::<CircuitListSlice>().map_err(|_| {
-                        CliError::ActionError(
-                            "Request was successful, but received an invalid response".into(),
-                        )
-                    })
-                } else {
-                    let message = res
-                        .json::<ServerError>()
-                        .map_err(|_| {
-                            CliError::ActionError(format!(
-                                "Circuit list request failed with status code '{}', but error \
+        self.send(
+            self.client()
+                .get(&request)
+                .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to list circuits: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<CircuitListSlice>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Circuit list request failed with status code '{}', but error \
                                  response was not valid",
-                                status
-                            ))
-                        })?
-                        .message;
-
-                    Err(CliError::ActionError(format!(
-                        "Failed to list circuits: {}",
-                        message
-                    )))
-                }
-            })
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to list circuits: {}",
+                    message
+                )))
+            }
+        })
     }
 
     pub fn fetch_circuit(&self, circuit_id: &str) -> Result<Option<CircuitSlice>, CliError> {
-        Client::new()
-            .get(&format!("{}/admin/circuits/{}", self.url, circuit_id))
-            .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION)
-            .send()
-            .map_err(|err| CliError::ActionError(format!("Failed to fetch circuit: {}", err)))
-            .and_then(|res| {
-                let status = res.status();
-                if status.is_success() {
-                    res.json::<CircuitSlice>().map(Some).map_err(|_| {
-                        CliError::ActionError(
-                            "Request was successful, but received an invalid response".into(),
-                        )
-                    })
-                } else if status == StatusCode::NOT_FOUND {
-                    Ok(None)
-                } else {
-                    let message = res
-                        .json::<ServerError>()
-                        .map_err(|_| {
-                            CliError::ActionError(format!(
-                                "Circuit fetch request failed with status code '{}', but error \
+        self.send(
+            self.client()
+                .get(&format!("{}/admin/circuits/{}", self.url, circuit_id))
+                .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to fetch circuit: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<CircuitSlice>().map(Some).map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else if status == StatusCode::NOT_FOUND {
+                Ok(None)
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Circuit fetch request failed with status code '{}', but error \
                                  response was not valid",
-                                status
-                            ))
-                        })?
-                        .message;
-
-                    Err(CliError::ActionError(format!(
-                        "Failed to fetch circuit: {}",
-                        message
-                    )))
-                }
-            })
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to fetch circuit: {}",
+                    message
+                )))
+            }
+        })
     }
 
     pub fn list_proposals(
         &self,
         management_type_filter: Option<&str>,
         member_filter: Option<&str>,
+        #[cfg(feature = "circuit-history")] status_filter: Option<&str>,
     ) -> Result<ProposalListSlice, CliError> {
         let mut filters = vec![];
         if let Some(management_type) = management_type_filter {
@@ -148,79 +196,153 @@ impl<'a> SplinterRestClient<'a> {
         if let Some(member) = member_filter {
             filters.push(format!("member={}", member));
         }
+        #[cfg(feature = "circuit-history")]
+        {
+            if let Some(status) = status_filter {
+                filters.push(format!("status={}", status));
+            }
+        }
 
         let mut request = format!("{}/admin/proposals?limit={}", self.url, PAGING_LIMIT);
         if !filters.is_empty() {
             request.push_str(&format!("&{}", filters.join("&")));
         }
 
-        Client::new()
-            .get(&request)
-            .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION)
-            .send()
-            .map_err(|err| CliError::ActionError(format!("Failed to list proposals: {}", err)))
-            .and_then(|res| {
-                let status = res.status();
-                if status.is_success() {
-                    res.json::<ProposalListSlice>().map_err(|_| {
-                        CliError::ActionError(
-                            "Request was successful, but received an invalid response".into(),
-                        )
-                    })
-                } else {
-                    let message = res
-                        .json::<ServerError>()
-                        .map_err(|_| {
-                            CliError::ActionError(format!(
-                                "Proposal list request failed with status code '{}', but error \
+        self.send(
+            self.client()
+                .get(&request)
+                .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to list proposals: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<ProposalListSlice>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Proposal list request failed with status code '{}', but error \
                                  response was not valid",
-                                status
-                            ))
-                        })?
-                        .message;
-
-                    Err(CliError::ActionError(format!(
-                        "Failed to list proposals: {}",
-                        message
-                    )))
-                }
-            })
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to list proposals: {}",
+                    message
+                )))
+            }
+        })
     }
 
     pub fn fetch_proposal(&self, circuit_id: &str) -> Result<Option<ProposalSlice>, CliError> {
-        Client::new()
-            .get(&format!("{}/admin/proposals/{}", self.url, circuit_id))
-            .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION)
-            .send()
-            .map_err(|err| CliError::ActionError(format!("Failed to fetch proposal: {}", err)))
-            .and_then(|res| {
-                let status = res.status();
-                if status.is_success() {
-                    res.json::<ProposalSlice>().map(Some).map_err(|_| {
-                        CliError::ActionError(
-                            "Request was successful, but received an invalid response".into(),
-                        )
-                    })
-                } else if status == StatusCode::NOT_FOUND {
-                    Ok(None)
-                } else {
-                    let message = res
-                        .json::<ServerError>()
-                        .map_err(|_| {
-                            CliError::ActionError(format!(
-                                "Proposal fetch request failed with status code '{}', but error \
+        self.send(
+            self.client()
+                .get(&format!("{}/admin/proposals/{}", self.url, circuit_id))
+                .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to fetch proposal: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<ProposalSlice>().map(Some).map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else if status == StatusCode::NOT_FOUND {
+                Ok(None)
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Proposal fetch request failed with status code '{}', but error \
                                  response was not valid",
-                                status
-                            ))
-                        })?
-                        .message;
-
-                    Err(CliError::ActionError(format!(
-                        "Failed to fetch proposal: {}",
-                        message
-                    )))
-                }
-            })
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to fetch proposal: {}",
+                    message
+                )))
+            }
+        })
+    }
+
+    #[cfg(feature = "circuit-history")]
+    pub fn fetch_circuit_history(&self, circuit_id: &str) -> Result<CircuitHistorySlice, CliError> {
+        self.send(
+            self.client()
+                .get(&format!(
+                    "{}/admin/circuits/{}/history",
+                    self.url, circuit_id
+                ))
+                .header("SplinterProtocolVersion", ADMIN_PROTOCOL_VERSION),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to fetch circuit history: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<CircuitHistorySlice>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Circuit history request failed with status code '{}', but \
+                                 error response was not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to fetch circuit history: {}",
+                    message
+                )))
+            }
+        })
+    }
+}
+
+#[cfg(feature = "proposal-validation")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProposalValidationSlice {
+    pub valid: bool,
+    pub nodes: Vec<NodeValidation>,
+}
+
+#[cfg(feature = "proposal-validation")]
+impl fmt::Display for ProposalValidationSlice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut display_string = format!(
+            "Proposal validation: {}\n",
+            if self.valid { "valid" } else { "invalid" }
+        );
+
+        for node in &self.nodes {
+            display_string += &format!("    {}: {:?}", node.node_id, node.status);
+            if let Some(error) = &node.error {
+                display_string += &format!(" ({})", error);
+            }
+            display_string += "\n";
+        }
+
+        write!(f, "{}", display_string)
     }
 }
 
@@ -296,6 +418,24 @@ pub struct ProposalSlice {
     pub votes: Vec<VoteRecord>,
     pub requester: String,
     pub requester_node_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub voting_policy: VotingPolicy,
+    #[serde(default)]
+    pub signer_quorum: SignerQuorum,
+}
+
+impl ProposalSlice {
+    /// Returns the number of keys that must sign to accept on behalf of the given member.
+    fn required_signers(&self, node_id: &str) -> u32 {
+        self.signer_quorum
+            .node_signers
+            .iter()
+            .find(|node_signers| node_signers.node_id == node_id)
+            .map(|node_signers| node_signers.required_signers)
+            .unwrap_or(self.signer_quorum.required_signers)
+    }
 }
 
 impl fmt::Display for ProposalSlice {
@@ -305,20 +445,53 @@ impl fmt::Display for ProposalSlice {
             self.circuit_id, self.circuit.management_type
         );
 
+        if let Some(expires_at) = self.expires_at {
+            display_string += &format!("    Expires At: {} (seconds since epoch)\n", expires_at);
+        }
+
+        match &self.voting_policy {
+            VotingPolicy::Unanimous => (),
+            VotingPolicy::Threshold { threshold } => {
+                display_string += &format!("    Approval Threshold: {} members\n", threshold);
+            }
+            VotingPolicy::Weighted { weights, threshold } => {
+                display_string += &format!("    Approval Threshold: {} weight\n", threshold);
+                for node_weight in weights {
+                    display_string +=
+                        &format!("        {}: {}\n", node_weight.node_id, node_weight.weight);
+                }
+            }
+            VotingPolicy::RequiredApprovers { approvers } => {
+                display_string += &format!("    Required Approvers: {}\n", approvers.join(", "));
+            }
+        }
+
         for member in self.circuit.members.iter() {
             display_string += &format!("\n    {} ({:?})\n", member.node_id, member.endpoints);
             if member.node_id == self.requester_node_id {
                 display_string += &"        Vote: ACCEPT (implied as requester):\n".to_string();
                 display_string += &format!("            {}\n", self.requester);
             } else {
-                let mut vote_string = "        Vote: PENDING".to_string();
-                for vote in self.votes.iter() {
-                    if vote.voter_node_id == member.node_id {
-                        vote_string =
-                            format!("        Vote: ACCEPT\n             {}", vote.public_key)
-                    }
+                let required_signers = self.required_signers(&member.node_id);
+                if required_signers > 1 {
+                    display_string += &format!("        Required Signers: {}\n", required_signers);
+                }
+
+                let member_votes = self
+                    .votes
+                    .iter()
+                    .filter(|vote| vote.voter_node_id == member.node_id)
+                    .collect::<Vec<_>>();
+                if member_votes.is_empty() {
+                    display_string += "        Vote: PENDING\n";
+                }
+                for vote in member_votes {
+                    display_string += &format!(
+                        "        Vote: {}\n             {}\n",
+                        vote.vote.to_uppercase(),
+                        vote.public_key
+                    );
                 }
-                display_string += &format!("{}\n", vote_string);
             }
             for service in self.circuit.roster.iter() {
                 if service.allowed_nodes.contains(&member.node_id) {
@@ -389,6 +562,23 @@ pub struct VoteRecord {
     pub voter_node_id: String,
 }
 
+#[cfg(feature = "circuit-history")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CircuitHistorySlice {
+    pub data: Vec<AuditEntrySlice>,
+}
+
+#[cfg(feature = "circuit-history")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditEntrySlice {
+    pub event_type: String,
+    pub timestamp: u64,
+    pub circuit_hash: String,
+    pub public_key: Option<String>,
+    pub node_id: Option<String>,
+    pub vote: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Paging {
     pub current: String,
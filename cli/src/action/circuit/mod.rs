@@ -21,10 +21,13 @@ pub mod template;
 #[cfg(feature = "circuit-template")]
 use std::collections::HashMap;
 use std::fs::File;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::ArgMatches;
 use serde::Deserialize;
-use splinter::admin::messages::{CreateCircuit, SplinterService};
+use splinter::admin::messages::{
+    CreateCircuit, NodeSigners, NodeWeight, SignerQuorum, SplinterService, VotingPolicy,
+};
 
 use crate::error::CliError;
 #[cfg(feature = "circuit-template")]
@@ -32,7 +35,7 @@ use crate::template::CircuitTemplate;
 
 use super::api::SplinterRestClient;
 use super::{
-    msg_from_io_error, read_private_key, Action, DEFAULT_SPLINTER_REST_API_URL,
+    msg_from_io_error, print_table, read_private_key, Action, DEFAULT_SPLINTER_REST_API_URL,
     SPLINTER_REST_API_URL_ENV,
 };
 
@@ -40,6 +43,13 @@ use api::{CircuitServiceSlice, CircuitSlice};
 pub(crate) use builder::CreateCircuitMessageBuilder;
 use payload::make_signed_payload;
 
+struct CircuitCreate {
+    create_circuit: CreateCircuit,
+    expires_at: Option<u64>,
+    voting_policy: VotingPolicy,
+    signer_quorum: SignerQuorum,
+}
+
 pub struct CircuitProposeAction;
 
 impl Action for CircuitProposeAction {
@@ -156,35 +166,89 @@ impl Action for CircuitProposeAction {
             builder.set_comments(comments);
         }
 
+        let expires_at = match args.value_of("expires_in") {
+            Some(expires_in) => Some(parse_expires_in(expires_in)?),
+            None => None,
+        };
+
+        let voting_policy = parse_voting_policy(args)?;
+        let signer_quorum = parse_signer_quorum(args)?;
+
         let create_circuit = builder.build()?;
 
         let circuit_slice = CircuitSlice::from(&create_circuit);
 
-        if !args.is_present("dry_run") {
-            let url = args
-                .value_of("url")
-                .map(ToOwned::to_owned)
-                .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
-                .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
-            let key = args.value_of("key").unwrap_or("./splinter.priv");
+        let circuit_create = CircuitCreate {
+            create_circuit,
+            expires_at,
+            voting_policy,
+            signer_quorum,
+        };
 
-            let client = SplinterRestClient::new(&url);
-            let requester_node = client.get_node_status()?.node_id;
-            let private_key_hex = read_private_key(key)?;
+        if args.is_present("dry_run") {
+            info!("{}", circuit_slice);
 
-            let signed_payload =
-                make_signed_payload(&requester_node, &private_key_hex, create_circuit)?;
-            client.submit_admin_payload(signed_payload)?;
+            #[cfg(feature = "proposal-validation")]
+            validate_circuit_create(args, circuit_create)?;
 
-            info!("The circuit proposal was submited successfully");
+            return Ok(());
         }
 
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+        let key = args.value_of("key").unwrap_or("./splinter.priv");
+
+        let private_key_hex = read_private_key(key)?;
+        let client = SplinterRestClient::new(&url)?.with_signing_key(&private_key_hex)?;
+        let requester_node = client.get_node_status()?.node_id;
+
+        let signed_payload =
+            make_signed_payload(&requester_node, &private_key_hex, circuit_create)?;
+        client.submit_admin_payload(signed_payload)?;
+
+        info!("The circuit proposal was submited successfully");
         info!("{}", circuit_slice);
 
         Ok(())
     }
 }
 
+/// Asks the node and the other proposed members to validate the circuit proposal without
+/// submitting it, and displays each member's result. Returns an error if any member found the
+/// proposal invalid.
+#[cfg(feature = "proposal-validation")]
+fn validate_circuit_create(
+    args: &ArgMatches,
+    circuit_create: CircuitCreate,
+) -> Result<(), CliError> {
+    let url = args
+        .value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+    let key = args.value_of("key").unwrap_or("./splinter.priv");
+
+    let private_key_hex = read_private_key(key)?;
+    let client = SplinterRestClient::new(&url)?.with_signing_key(&private_key_hex)?;
+    let requester_node = client.get_node_status()?.node_id;
+
+    let signed_payload = make_signed_payload(&requester_node, &private_key_hex, circuit_create)?;
+    let validation = client.validate_admin_payload(signed_payload)?;
+
+    info!("{}", validation);
+
+    if validation.valid {
+        Ok(())
+    } else {
+        Err(CliError::ActionError(
+            "The circuit proposal is invalid on one or more members".into(),
+        ))
+    }
+}
+
 #[derive(Deserialize)]
 struct Node {
     #[serde(alias = "node_id")]
@@ -485,6 +549,143 @@ fn parse_service_type_argument(service_type: &str) -> Result<(String, String), C
     Ok((service_id, service_type))
 }
 
+/// Parses a duration of the form `<number>[s|m|h|d]`, such as `90m` or `7d`, and returns the Unix
+/// timestamp that is that far in the future. A number without a unit is a number of seconds.
+fn parse_expires_in(expires_in: &str) -> Result<u64, CliError> {
+    let (number, multiplier) = match expires_in.chars().last() {
+        Some('s') => (&expires_in[..expires_in.len() - 1], 1),
+        Some('m') => (&expires_in[..expires_in.len() - 1], 60),
+        Some('h') => (&expires_in[..expires_in.len() - 1], 60 * 60),
+        Some('d') => (&expires_in[..expires_in.len() - 1], 24 * 60 * 60),
+        _ => (expires_in, 1),
+    };
+
+    let seconds = number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .filter(|seconds| *seconds > 0)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Invalid expiry '{}': expected a positive number with an optional unit \
+                 (s, m, h or d)",
+                expires_in
+            ))
+        })?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| CliError::EnvironmentError(format!("Invalid system time: {}", err)))?;
+
+    Ok(now.as_secs() + seconds)
+}
+
+/// Builds the proposal's voting policy from the `--approval-threshold`, `--node-weight` and
+/// `--required-approver` arguments; the policy is unanimous if none of them are given.
+fn parse_voting_policy(args: &ArgMatches) -> Result<VotingPolicy, CliError> {
+    if let Some(approvers) = args.values_of("required_approver") {
+        return Ok(VotingPolicy::RequiredApprovers {
+            approvers: approvers.map(String::from).collect(),
+        });
+    }
+
+    let threshold = match args.value_of("approval_threshold") {
+        Some(threshold) => threshold.parse::<u32>().map_err(|_| {
+            CliError::ActionError(format!(
+                "Invalid approval threshold '{}': expected a positive integer",
+                threshold
+            ))
+        })?,
+        None => return Ok(VotingPolicy::Unanimous),
+    };
+
+    match args.values_of("node_weight") {
+        Some(node_weights) => Ok(VotingPolicy::Weighted {
+            weights: node_weights
+                .map(parse_node_weight_argument)
+                .collect::<Result<_, _>>()?,
+            threshold,
+        }),
+        None => Ok(VotingPolicy::Threshold { threshold }),
+    }
+}
+
+fn parse_node_weight_argument(node_weight: &str) -> Result<NodeWeight, CliError> {
+    let mut iter = node_weight.split("::");
+
+    let node_id = iter
+        .next()
+        .expect("str::split cannot return an empty iterator")
+        .to_string();
+    if node_id.is_empty() {
+        return Err(CliError::ActionError(
+            "Empty '--node-weight' argument detected".into(),
+        ));
+    }
+
+    let weight = iter
+        .next()
+        .ok_or_else(|| CliError::ActionError(format!("Missing weight for '{}'", node_id)))?;
+    let weight = weight.parse::<u32>().map_err(|_| {
+        CliError::ActionError(format!(
+            "Invalid weight '{}' for '{}': expected a positive integer",
+            weight, node_id
+        ))
+    })?;
+
+    Ok(NodeWeight { node_id, weight })
+}
+
+/// Builds the proposal's signer quorum from the `--required-signers` and `--node-required-signers`
+/// arguments; every member requires a single signer if neither is given.
+fn parse_signer_quorum(args: &ArgMatches) -> Result<SignerQuorum, CliError> {
+    let mut signer_quorum = SignerQuorum::default();
+
+    if let Some(required_signers) = args.value_of("required_signers") {
+        signer_quorum.required_signers = parse_required_signers(required_signers)?;
+    }
+
+    if let Some(node_required_signers) = args.values_of("node_required_signers") {
+        for node_signers in node_required_signers {
+            let mut iter = node_signers.split("::");
+
+            let node_id = iter
+                .next()
+                .expect("str::split cannot return an empty iterator")
+                .to_string();
+            if node_id.is_empty() {
+                return Err(CliError::ActionError(
+                    "Empty '--node-required-signers' argument detected".into(),
+                ));
+            }
+
+            let required_signers = iter.next().ok_or_else(|| {
+                CliError::ActionError(format!("Missing required signers for '{}'", node_id))
+            })?;
+
+            signer_quorum.node_signers.push(NodeSigners {
+                node_id,
+                required_signers: parse_required_signers(required_signers)?,
+            });
+        }
+    }
+
+    Ok(signer_quorum)
+}
+
+fn parse_required_signers(required_signers: &str) -> Result<u32, CliError> {
+    required_signers
+        .parse::<u32>()
+        .ok()
+        .filter(|required_signers| *required_signers > 0)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Invalid required signers '{}': expected a positive integer",
+                required_signers
+            ))
+        })
+}
+
 impl From<&CreateCircuit> for CircuitSlice {
     fn from(circuit: &CreateCircuit) -> Self {
         Self {
@@ -560,8 +761,8 @@ fn vote_on_circuit_proposal(
     circuit_id: &str,
     vote: Vote,
 ) -> Result<(), CliError> {
-    let client = SplinterRestClient::new(url);
     let private_key_hex = read_private_key(key)?;
+    let client = SplinterRestClient::new(url)?.with_signing_key(&private_key_hex)?;
 
     let requester_node = client.get_node_status()?.node_id;
     let proposal = client.fetch_proposal(circuit_id)?;
@@ -582,6 +783,53 @@ fn vote_on_circuit_proposal(
     }
 }
 
+struct CircuitWithdraw {
+    circuit_id: String,
+    circuit_hash: String,
+}
+
+pub struct CircuitProposalWithdrawAction;
+
+impl Action for CircuitProposalWithdrawAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or_else(|| CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+        let key = args.value_of("private_key_file").unwrap_or("splinter");
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        withdraw_circuit_proposal(&url, key, circuit_id)
+    }
+}
+
+fn withdraw_circuit_proposal(url: &str, key: &str, circuit_id: &str) -> Result<(), CliError> {
+    let private_key_hex = read_private_key(key)?;
+    let client = SplinterRestClient::new(url)?.with_signing_key(&private_key_hex)?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let proposal = client.fetch_proposal(circuit_id)?;
+
+    if let Some(proposal) = proposal {
+        let circuit_withdraw = CircuitWithdraw {
+            circuit_id: circuit_id.into(),
+            circuit_hash: proposal.circuit_hash,
+        };
+        let signed_payload =
+            make_signed_payload(&requester_node, &private_key_hex, circuit_withdraw)?;
+        client.submit_admin_payload(signed_payload)
+    } else {
+        Err(CliError::ActionError(format!(
+            "Proposal for circuit '{}' does not exist",
+            circuit_id
+        )))
+    }
+}
+
 pub struct CircuitListAction;
 
 impl Action for CircuitListAction {
@@ -609,7 +857,7 @@ impl Action for CircuitListAction {
 }
 
 fn list_circuits(url: &str, filter: Option<&str>, format: &str) -> Result<(), CliError> {
-    let client = SplinterRestClient::new(url);
+    let client = SplinterRestClient::new(url)?;
 
     let circuits = client.list_circuits(filter)?;
     let mut data = Vec::new();
@@ -663,7 +911,7 @@ impl Action for CircuitShowAction {
 }
 
 fn show_circuit(url: &str, circuit_id: &str, format: &str) -> Result<(), CliError> {
-    let client = SplinterRestClient::new(url);
+    let client = SplinterRestClient::new(url)?;
     let circuit = client.fetch_circuit(circuit_id)?;
     let mut print_circuit = false;
     let mut print_proposal = false;
@@ -725,6 +973,12 @@ pub struct CircuitProposalsAction;
 
 impl Action for CircuitProposalsAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        if let Some(withdraw_args) =
+            arg_matches.and_then(|args| args.subcommand_matches("withdraw"))
+        {
+            return CircuitProposalWithdrawAction.run(Some(withdraw_args));
+        }
+
         let url = arg_matches
             .and_then(|args| args.value_of("url"))
             .map(ToOwned::to_owned)
@@ -735,6 +989,9 @@ impl Action for CircuitProposalsAction {
 
         let member_filter = arg_matches.and_then(|args| args.value_of("member"));
 
+        #[cfg(feature = "circuit-history")]
+        let status_filter = arg_matches.and_then(|args| args.value_of("status"));
+
         let format = arg_matches
             .and_then(|args| {
                 if let Some(val) = args.value_of("hidden_format") {
@@ -745,7 +1002,14 @@ impl Action for CircuitProposalsAction {
             })
             .unwrap_or("human");
 
-        list_proposals(&url, management_type_filter, member_filter, format)
+        list_proposals(
+            &url,
+            management_type_filter,
+            member_filter,
+            #[cfg(feature = "circuit-history")]
+            status_filter,
+            format,
+        )
     }
 }
 
@@ -753,11 +1017,17 @@ fn list_proposals(
     url: &str,
     management_type_filter: Option<&str>,
     member_filter: Option<&str>,
+    #[cfg(feature = "circuit-history")] status_filter: Option<&str>,
     format: &str,
 ) -> Result<(), CliError> {
-    let client = SplinterRestClient::new(url);
-
-    let proposals = client.list_proposals(management_type_filter, member_filter)?;
+    let client = SplinterRestClient::new(url)?;
+
+    let proposals = client.list_proposals(
+        management_type_filter,
+        member_filter,
+        #[cfg(feature = "circuit-history")]
+        status_filter,
+    )?;
     let mut data = Vec::new();
     data.push(vec![
         "ID".to_string(),
@@ -792,35 +1062,61 @@ fn list_proposals(
     Ok(())
 }
 
-// Takes a vec of vecs of strings. The first vec should include the title of the columns.
-// The max length of each column is calculated and is used as the column with when printing the
-// table.
-fn print_table(table: Vec<Vec<String>>) {
-    let mut max_lengths = Vec::new();
+#[cfg(feature = "circuit-history")]
+pub struct CircuitHistoryAction;
 
-    // find the max lengths of the columns
-    for row in table.iter() {
-        for (i, col) in row.iter().enumerate() {
-            if let Some(length) = max_lengths.get_mut(i) {
-                if col.len() > *length {
-                    *length = col.len()
-                }
-            } else {
-                max_lengths.push(col.len())
-            }
-        }
+#[cfg(feature = "circuit-history")]
+impl Action for CircuitHistoryAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or_else(|| CliError::RequiresArgs)?;
+
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        let format = args.value_of("format").unwrap_or("human");
+
+        show_circuit_history(&url, circuit_id, format)
     }
+}
 
-    // print each row with correct column size
-    for row in table.iter() {
-        let mut col_string = String::from("");
-        for (i, len) in max_lengths.iter().enumerate() {
-            if let Some(value) = row.get(i) {
-                col_string += &format!("{}{} ", value, " ".repeat(*len - value.len()),);
-            } else {
-                col_string += &" ".repeat(*len);
-            }
+#[cfg(feature = "circuit-history")]
+fn show_circuit_history(url: &str, circuit_id: &str, format: &str) -> Result<(), CliError> {
+    let client = SplinterRestClient::new(url)?;
+
+    let history = client.fetch_circuit_history(circuit_id)?;
+    let mut data = Vec::new();
+    data.push(vec![
+        "TIMESTAMP".to_string(),
+        "EVENT".to_string(),
+        "NODE".to_string(),
+        "PUBLIC KEY".to_string(),
+        "VOTE".to_string(),
+        "CIRCUIT HASH".to_string(),
+    ]);
+    history.data.iter().for_each(|entry| {
+        data.push(vec![
+            entry.timestamp.to_string(),
+            entry.event_type.to_string(),
+            entry.node_id.clone().unwrap_or_else(|| "-".into()),
+            entry.public_key.clone().unwrap_or_else(|| "-".into()),
+            entry.vote.clone().unwrap_or_else(|| "-".into()),
+            entry.circuit_hash.to_string(),
+        ]);
+    });
+
+    if format == "csv" {
+        for row in data {
+            println!("{}", row.join(","))
         }
-        println!("{}", col_string);
+    } else {
+        print_table(data);
     }
+
+    Ok(())
 }
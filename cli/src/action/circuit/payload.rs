@@ -15,16 +15,16 @@
 use openssl::hash::{hash, MessageDigest};
 use protobuf::Message;
 use sawtooth_sdk::signing::secp256k1;
-use splinter::admin::messages::CreateCircuit;
 use splinter::protos::admin::{
     CircuitCreateRequest, CircuitManagementPayload, CircuitManagementPayload_Action as Action,
     CircuitManagementPayload_Header as Header, CircuitProposalVote, CircuitProposalVote_Vote,
+    CircuitProposalWithdraw,
 };
 use splinter::signing::{sawtooth, Signer};
 
 use crate::error::CliError;
 
-use super::{CircuitVote, Vote};
+use super::{CircuitCreate, CircuitVote, CircuitWithdraw, Vote};
 
 /// A circuit action that has a type and can be converted into a protobuf-serializable struct.
 pub trait CircuitAction<M: Message> {
@@ -92,18 +92,23 @@ where
 
 // Conversions for explicit actions and their associated types.
 
-impl CircuitAction<CircuitCreateRequest> for CreateCircuit {
+impl CircuitAction<CircuitCreateRequest> for CircuitCreate {
     fn action_type(&self) -> Action {
         Action::CIRCUIT_CREATE_REQUEST
     }
 
     fn into_proto(self) -> Result<CircuitCreateRequest, CliError> {
-        CreateCircuit::into_proto(self).map_err(|err| {
+        let mut create_request = self.create_circuit.into_proto().map_err(|err| {
             CliError::ActionError(format!(
                 "Failed to convert circuit create request to protobuf: {}",
                 err
             ))
-        })
+        })?;
+        create_request.set_expires_at(self.expires_at.unwrap_or(0));
+        create_request.set_voting_policy(self.voting_policy.into_proto());
+        create_request.set_signer_quorum(self.signer_quorum.into_proto());
+
+        Ok(create_request)
     }
 }
 
@@ -136,3 +141,23 @@ impl ApplyToEnvelope for CircuitProposalVote {
         circuit_management_payload.set_circuit_proposal_vote(self);
     }
 }
+
+impl CircuitAction<CircuitProposalWithdraw> for CircuitWithdraw {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_PROPOSAL_WITHDRAW
+    }
+
+    fn into_proto(self) -> Result<CircuitProposalWithdraw, CliError> {
+        let mut withdraw = CircuitProposalWithdraw::new();
+        withdraw.set_circuit_id(self.circuit_id);
+        withdraw.set_circuit_hash(self.circuit_hash);
+
+        Ok(withdraw)
+    }
+}
+
+impl ApplyToEnvelope for CircuitProposalWithdraw {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_proposal_withdraw(self);
+    }
+}
@@ -16,6 +16,7 @@ use clap::ArgMatches;
 use reqwest::StatusCode;
 use serde_json::Value;
 
+use super::api::new_http_client;
 use super::{Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
 
 use crate::error::CliError;
@@ -34,7 +35,9 @@ impl Action for StatusAction {
             .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
             .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
 
-        reqwest::blocking::get(&format!("{}/health/status", url))
+        new_http_client()?
+            .get(&format!("{}/health/status", url))
+            .send()
             .map_err(|err| match err.status() {
                 Some(StatusCode::NOT_FOUND) => {
                     CliError::ActionError(SPLINTERD_MISSING_HEALTH_STATUS.into())
@@ -0,0 +1,186 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::{blocking::Response, StatusCode};
+use serde::Deserialize;
+use splinter::protocol::REGISTRY_PROTOCOL_VERSION;
+use splinter::registry::Node;
+
+use crate::action::api::{ServerError, SplinterRestClient};
+use crate::error::CliError;
+
+const PAGING_LIMIT: usize = 1000;
+
+impl<'a> SplinterRestClient<'a> {
+    /// Lists all of the nodes in the registry of this client's Splinter node that match the given
+    /// metadata filter. All pages of the result are fetched.
+    pub fn list_registry_nodes(&self, filter: Option<&str>) -> Result<Vec<Node>, CliError> {
+        let mut nodes = vec![];
+        loop {
+            let mut query = vec![
+                ("limit", PAGING_LIMIT.to_string()),
+                ("offset", nodes.len().to_string()),
+            ];
+            if let Some(filter) = filter {
+                query.push(("filter", filter.to_string()));
+            }
+
+            let page = self
+                .send(
+                    self.client()
+                        .get(&format!("{}/registry/nodes", self.url))
+                        .header("SplinterProtocolVersion", REGISTRY_PROTOCOL_VERSION)
+                        .query(&query),
+                )
+                .map_err(|err| CliError::ActionError(format!("Failed to list nodes: {}", err)))
+                .and_then(|res| {
+                    let status = res.status();
+                    if status.is_success() {
+                        res.json::<NodeListSlice>().map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                    } else {
+                        Err(error_from_response(
+                            res,
+                            "Node list",
+                            "Failed to list nodes",
+                        ))
+                    }
+                })?;
+
+            let page_size = page.data.len();
+            nodes.extend(page.data);
+            if page_size == 0 || nodes.len() >= page.paging.total {
+                return Ok(nodes);
+            }
+        }
+    }
+
+    /// Fetches the node with the given identity from the registry of this client's Splinter
+    /// node; returns `None` if the node does not exist.
+    pub fn fetch_registry_node(&self, identity: &str) -> Result<Option<Node>, CliError> {
+        self.send(
+            self.client()
+                .get(&format!("{}/registry/nodes/{}", self.url, identity))
+                .header("SplinterProtocolVersion", REGISTRY_PROTOCOL_VERSION),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to fetch node: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<Node>().map(Some).map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else if status == StatusCode::NOT_FOUND {
+                Ok(None)
+            } else {
+                Err(error_from_response(
+                    res,
+                    "Node fetch",
+                    "Failed to fetch node",
+                ))
+            }
+        })
+    }
+
+    /// Adds a new node to the registry of this client's Splinter node.
+    pub fn add_registry_node(&self, node: &Node) -> Result<(), CliError> {
+        self.send(
+            self.client()
+                .post(&format!("{}/registry/nodes", self.url))
+                .header("SplinterProtocolVersion", REGISTRY_PROTOCOL_VERSION)
+                .json(node),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to add node: {}", err)))
+        .and_then(|res| {
+            if res.status().is_success() {
+                Ok(())
+            } else {
+                Err(error_from_response(res, "Node add", "Failed to add node"))
+            }
+        })
+    }
+
+    /// Replaces an existing node in the registry of this client's Splinter node.
+    pub fn update_registry_node(&self, node: &Node) -> Result<(), CliError> {
+        self.send(
+            self.client()
+                .put(&format!("{}/registry/nodes/{}", self.url, node.identity))
+                .header("SplinterProtocolVersion", REGISTRY_PROTOCOL_VERSION)
+                .json(node),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to update node: {}", err)))
+        .and_then(|res| {
+            if res.status().is_success() {
+                Ok(())
+            } else {
+                Err(error_from_response(
+                    res,
+                    "Node update",
+                    "Failed to update node",
+                ))
+            }
+        })
+    }
+
+    /// Removes the node with the given identity from the registry of this client's Splinter
+    /// node.
+    pub fn delete_registry_node(&self, identity: &str) -> Result<(), CliError> {
+        self.send(
+            self.client()
+                .delete(&format!("{}/registry/nodes/{}", self.url, identity))
+                .header("SplinterProtocolVersion", REGISTRY_PROTOCOL_VERSION),
+        )
+        .map_err(|err| CliError::ActionError(format!("Failed to remove node: {}", err)))
+        .and_then(|res| {
+            if res.status().is_success() {
+                Ok(())
+            } else {
+                Err(error_from_response(
+                    res,
+                    "Node remove",
+                    "Failed to remove node",
+                ))
+            }
+        })
+    }
+}
+
+/// Converts an unsuccessful response to an error, using the server's error message if the
+/// response contains one.
+fn error_from_response(res: Response, request: &str, failure: &str) -> CliError {
+    let status = res.status();
+    match res.json::<ServerError>() {
+        Ok(err) => CliError::ActionError(format!("{}: {}", failure, err.message)),
+        Err(_) => CliError::ActionError(format!(
+            "{} request failed with status code '{}', but error response was not valid",
+            request, status
+        )),
+    }
+}
+
+#[derive(Deserialize)]
+struct NodeListSlice {
+    data: Vec<Node>,
+    paging: Paging,
+}
+
+#[derive(Deserialize)]
+struct Paging {
+    total: usize,
+}
@@ -12,16 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "registry")]
+mod api;
+
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
-use clap::ArgMatches;
+use clap::{ArgMatches, Values};
 use splinter::registry::Node;
 
 use crate::error::CliError;
 
 use super::api::SplinterRestClient;
+#[cfg(feature = "registry")]
+use super::print_table;
 use super::{
     msg_from_io_error, read_private_key, Action, DEFAULT_SPLINTER_REST_API_URL,
     SPLINTER_REST_API_URL_ENV,
@@ -60,7 +66,7 @@ impl Action for RegistryGenerateAction {
             .map(ToOwned::to_owned)
             .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
             .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
-        let node_status = SplinterRestClient::new(&url).get_node_status()?;
+        let node_status = SplinterRestClient::new(&url)?.get_node_status()?;
 
         let keys = args
             .values_of("key_files")
@@ -68,42 +74,9 @@ impl Action for RegistryGenerateAction {
             .map(|key_file| read_private_key(key_file))
             .collect::<Result<_, _>>()?;
 
-        let metadata = if let Some(metadata) = args.values_of("metadata") {
-            metadata
-                .map(|kv| {
-                    let mut kv_iter = kv.splitn(2, '=');
-
-                    let key = kv_iter
-                        .next()
-                        .expect("str::split cannot return an empty iterator")
-                        .to_string();
-                    if key.is_empty() {
-                        return Err(CliError::ActionError(
-                            "Empty '--metadata' argument detected".into(),
-                        ));
-                    }
-
-                    let value = kv_iter
-                        .next()
-                        .ok_or_else(|| {
-                            CliError::ActionError(format!(
-                                "Missing value for metadata key '{}'",
-                                key
-                            ))
-                        })?
-                        .to_string();
-                    if value.is_empty() {
-                        return Err(CliError::ActionError(format!(
-                            "Empty value detected for metadata key '{}'",
-                            key
-                        )));
-                    }
-
-                    Ok((key, value))
-                })
-                .collect::<Result<_, _>>()?
-        } else {
-            Default::default()
+        let metadata = match args.values_of("metadata") {
+            Some(metadata) => parse_metadata(metadata)?,
+            None => Default::default(),
         };
 
         let node = Node {
@@ -162,3 +135,410 @@ impl Action for RegistryGenerateAction {
         Ok(())
     }
 }
+
+#[cfg(feature = "registry")]
+pub struct RegistryAddAction;
+
+#[cfg(feature = "registry")]
+impl Action for RegistryAddAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or_else(|| CliError::RequiresArgs)?;
+
+        let url = get_url(args);
+        let identity = args
+            .value_of("identity")
+            .ok_or_else(|| CliError::ActionError("'identity' argument is required".into()))?;
+
+        let endpoints = args
+            .values_of("endpoints")
+            .ok_or_else(|| CliError::ActionError("One or more endpoints must be specified".into()))?
+            .map(String::from)
+            .collect();
+        let display_name = args.value_of("display_name").unwrap_or(identity);
+        let keys = args
+            .values_of("key_files")
+            .ok_or_else(|| CliError::ActionError("One or more key files must be specified".into()))?
+            .map(|key_file| read_private_key(key_file))
+            .collect::<Result<_, _>>()?;
+        let metadata = match args.values_of("metadata") {
+            Some(metadata) => parse_metadata(metadata)?,
+            None => Default::default(),
+        };
+
+        let node = Node {
+            identity: identity.into(),
+            endpoints,
+            display_name: display_name.into(),
+            keys,
+            metadata,
+        };
+
+        println!("{}", format_node(&node));
+
+        if args.is_present("dry_run") {
+            info!("Dry run; node '{}' was not added", identity);
+            return Ok(());
+        }
+
+        SplinterRestClient::new(&url)?.add_registry_node(&node)?;
+
+        info!("Added node '{}'", identity);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "registry")]
+pub struct RegistryUpdateAction;
+
+#[cfg(feature = "registry")]
+impl Action for RegistryUpdateAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or_else(|| CliError::RequiresArgs)?;
+
+        let url = get_url(args);
+        let identity = args
+            .value_of("identity")
+            .ok_or_else(|| CliError::ActionError("'identity' argument is required".into()))?;
+
+        let client = SplinterRestClient::new(&url)?;
+        let old_node = client
+            .fetch_registry_node(identity)?
+            .ok_or_else(|| CliError::ActionError(format!("Node '{}' not found", identity)))?;
+
+        let mut new_node = old_node.clone();
+        if let Some(endpoints) = args.values_of("endpoints") {
+            new_node.endpoints = endpoints.map(String::from).collect();
+        }
+        if let Some(display_name) = args.value_of("display_name") {
+            new_node.display_name = display_name.into();
+        }
+        if let Some(key_files) = args.values_of("key_files") {
+            new_node.keys = key_files
+                .map(|key_file| read_private_key(key_file))
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(keys) = args.values_of("remove_metadata") {
+            for key in keys {
+                if new_node.metadata.remove(key).is_none() {
+                    return Err(CliError::ActionError(format!(
+                        "Node '{}' does not have metadata key '{}'",
+                        identity, key
+                    )));
+                }
+            }
+        }
+        if let Some(metadata) = args.values_of("metadata") {
+            new_node.metadata.extend(parse_metadata(metadata)?);
+        }
+
+        let diff = diff_nodes(&old_node, &new_node);
+        if diff.is_empty() {
+            info!("Node '{}' is unchanged", identity);
+            return Ok(());
+        }
+
+        for line in diff {
+            println!("{}", line);
+        }
+
+        if args.is_present("dry_run") {
+            info!("Dry run; node '{}' was not updated", identity);
+            return Ok(());
+        }
+
+        client.update_registry_node(&new_node)?;
+
+        info!("Updated node '{}'", identity);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "registry")]
+pub struct RegistryRemoveAction;
+
+#[cfg(feature = "registry")]
+impl Action for RegistryRemoveAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or_else(|| CliError::RequiresArgs)?;
+
+        let url = get_url(args);
+        let identity = args
+            .value_of("identity")
+            .ok_or_else(|| CliError::ActionError("'identity' argument is required".into()))?;
+
+        let client = SplinterRestClient::new(&url)?;
+        let node = client
+            .fetch_registry_node(identity)?
+            .ok_or_else(|| CliError::ActionError(format!("Node '{}' not found", identity)))?;
+
+        println!("{}", format_node(&node));
+
+        if args.is_present("dry_run") {
+            info!("Dry run; node '{}' was not removed", identity);
+            return Ok(());
+        }
+
+        client.delete_registry_node(identity)?;
+
+        info!("Removed node '{}'", identity);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "registry")]
+pub struct RegistryListAction;
+
+#[cfg(feature = "registry")]
+impl Action for RegistryListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or_else(|| CliError::RequiresArgs)?;
+
+        let url = get_url(args);
+        let filter = args.value_of("filter");
+        let format = args.value_of("format").unwrap_or("human");
+
+        let nodes = SplinterRestClient::new(&url)?.list_registry_nodes(filter)?;
+
+        let mut data = vec![vec![
+            "IDENTITY".to_string(),
+            "DISPLAY NAME".to_string(),
+            "ENDPOINTS".to_string(),
+        ]];
+        nodes.into_iter().for_each(|node| {
+            data.push(vec![
+                node.identity,
+                node.display_name,
+                node.endpoints.join(";"),
+            ])
+        });
+
+        if format == "csv" {
+            for row in data {
+                println!("{}", row.join(","))
+            }
+        } else {
+            print_table(data);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "registry")]
+pub struct RegistryShowAction;
+
+#[cfg(feature = "registry")]
+impl Action for RegistryShowAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or_else(|| CliError::RequiresArgs)?;
+
+        let url = get_url(args);
+        let identity = args
+            .value_of("identity")
+            .ok_or_else(|| CliError::ActionError("'identity' argument is required".into()))?;
+        let format = args.value_of("format").unwrap_or("human");
+
+        let node = SplinterRestClient::new(&url)?
+            .fetch_registry_node(identity)?
+            .ok_or_else(|| CliError::ActionError(format!("Node '{}' not found", identity)))?;
+
+        match format {
+            "json" => println!(
+                "{}",
+                serde_json::to_string(&node).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format node into json: {}",
+                    err
+                )))?
+            ),
+            "yaml" => println!(
+                "{}",
+                serde_yaml::to_string(&node).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format node into yaml: {}",
+                    err
+                )))?
+            ),
+            _ => println!("{}", format_node(&node)),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "registry")]
+fn get_url(args: &ArgMatches) -> String {
+    args.value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string())
+}
+
+/// Parses `<key>=<value>` metadata arguments.
+fn parse_metadata(metadata: Values) -> Result<HashMap<String, String>, CliError> {
+    metadata
+        .map(|kv| {
+            let mut kv_iter = kv.splitn(2, '=');
+
+            let key = kv_iter
+                .next()
+                .expect("str::split cannot return an empty iterator")
+                .to_string();
+            if key.is_empty() {
+                return Err(CliError::ActionError(
+                    "Empty '--metadata' argument detected".into(),
+                ));
+            }
+
+            let value = kv_iter
+                .next()
+                .ok_or_else(|| {
+                    CliError::ActionError(format!("Missing value for metadata key '{}'", key))
+                })?
+                .to_string();
+            if value.is_empty() {
+                return Err(CliError::ActionError(format!(
+                    "Empty value detected for metadata key '{}'",
+                    key
+                )));
+            }
+
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Formats a node for human-readable output.
+#[cfg(feature = "registry")]
+fn format_node(node: &Node) -> String {
+    let mut display_string = format!(
+        "Node: {}\n    Display Name: {}\n    Endpoints:\n",
+        node.identity, node.display_name
+    );
+    for endpoint in &node.endpoints {
+        display_string += &format!("        {}\n", endpoint);
+    }
+    display_string += "    Keys:\n";
+    for key in &node.keys {
+        display_string += &format!("        {}\n", key);
+    }
+    if !node.metadata.is_empty() {
+        display_string += "    Metadata:\n";
+        for (key, value) in sorted_metadata(node) {
+            display_string += &format!("        {}: {}\n", key, value);
+        }
+    }
+    display_string
+}
+
+/// Describes the changes between two versions of a node, one line per change. Lines for removed
+/// values start with `-` and lines for added values start with `+`; an empty list means the nodes
+/// are the same.
+#[cfg(feature = "registry")]
+fn diff_nodes(old: &Node, new: &Node) -> Vec<String> {
+    let mut diff = vec![];
+
+    if old.display_name != new.display_name {
+        diff.push("display_name:".into());
+        diff.push(format!("-   {}", old.display_name));
+        diff.push(format!("+   {}", new.display_name));
+    }
+
+    diff_lists("endpoints", &old.endpoints, &new.endpoints, &mut diff);
+    diff_lists("keys", &old.keys, &new.keys, &mut diff);
+
+    let old_metadata = sorted_metadata(old)
+        .into_iter()
+        .map(|(key, value)| format!("{}: {}", key, value))
+        .collect::<Vec<_>>();
+    let new_metadata = sorted_metadata(new)
+        .into_iter()
+        .map(|(key, value)| format!("{}: {}", key, value))
+        .collect::<Vec<_>>();
+    diff_lists("metadata", &old_metadata, &new_metadata, &mut diff);
+
+    diff
+}
+
+/// Adds the lines describing the changes between two lists to `diff`. If the lists only differ by
+/// order, the full lists are shown.
+#[cfg(feature = "registry")]
+fn diff_lists(name: &str, old: &[String], new: &[String], diff: &mut Vec<String>) {
+    if old == new {
+        return;
+    }
+
+    let removed = old
+        .iter()
+        .filter(|item| !new.contains(item))
+        .collect::<Vec<_>>();
+    let added = new
+        .iter()
+        .filter(|item| !old.contains(item))
+        .collect::<Vec<_>>();
+
+    diff.push(format!("{}:", name));
+    if removed.is_empty() && added.is_empty() {
+        diff.extend(old.iter().map(|item| format!("-   {}", item)));
+        diff.extend(new.iter().map(|item| format!("+   {}", item)));
+    } else {
+        diff.extend(removed.into_iter().map(|item| format!("-   {}", item)));
+        diff.extend(added.into_iter().map(|item| format!("+   {}", item)));
+    }
+}
+
+#[cfg(feature = "registry")]
+fn sorted_metadata(node: &Node) -> Vec<(&String, &String)> {
+    let mut metadata = node.metadata.iter().collect::<Vec<_>>();
+    metadata.sort();
+    metadata
+}
+
+#[cfg(all(test, feature = "registry"))]
+mod tests {
+    use super::*;
+
+    fn node(endpoints: &[&str], metadata: &[(&str, &str)]) -> Node {
+        Node {
+            identity: "node-1".into(),
+            endpoints: endpoints
+                .iter()
+                .map(|endpoint| endpoint.to_string())
+                .collect(),
+            display_name: "Node 1".into(),
+            keys: vec!["abcd".into()],
+            metadata: metadata
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Verify that `diff_nodes` reports only the values that changed, and reports nothing for
+    /// identical nodes.
+    #[test]
+    fn test_diff_nodes() {
+        let old = node(
+            &["tcps://12.0.0.123:8431", "tcps://12.0.0.123:8432"],
+            &[("company", "Bitwise IO"), ("region", "us-east")],
+        );
+        let new = node(
+            &["tcps://12.0.0.123:8431", "tcps://12.0.0.123:9432"],
+            &[("company", "Bitwise IO"), ("region", "us-west")],
+        );
+
+        assert_eq!(
+            diff_nodes(&old, &new),
+            vec![
+                "endpoints:",
+                "-   tcps://12.0.0.123:8432",
+                "+   tcps://12.0.0.123:9432",
+                "metadata:",
+                "-   region: us-east",
+                "+   region: us-west",
+            ]
+        );
+        assert!(diff_nodes(&new, &new).is_empty());
+    }
+}
@@ -303,11 +303,72 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 .help("Add human-readable comments to the proposal"),
         )
         .arg(
-            Arg::with_name("dry_run")
-                .long("dry-run")
-                .short("n")
-                .help("Print circuit definition without submitting the proposal"),
+            Arg::with_name("expires_in")
+                .long("expires-in")
+                .takes_value(true)
+                .value_name("duration")
+                .help(
+                    "Expire the proposal if it has not been accepted after the given duration \
+                     (for example, 3600, 90m, 12h or 7d)",
+                ),
+        )
+        .arg(
+            Arg::with_name("approval_threshold")
+                .long("approval-threshold")
+                .takes_value(true)
+                .value_name("threshold")
+                .help(
+                    "Accept the proposal once this many members, or this much total node \
+                     weight, have voted to accept (default: all members)",
+                ),
+        )
+        .arg(
+            Arg::with_name("node_weight")
+                .long("node-weight")
+                .takes_value(true)
+                .multiple(true)
+                .requires("approval_threshold")
+                .help("Weight of a member's vote (<node_id>::<weight>)"),
+        )
+        .arg(
+            Arg::with_name("required_approver")
+                .long("required-approver")
+                .takes_value(true)
+                .multiple(true)
+                .value_name("node_id")
+                .conflicts_with_all(&["approval_threshold", "node_weight"])
+                .help(
+                    "Accept the proposal once all of the required approvers have voted to accept",
+                ),
         )
+        .arg(
+            Arg::with_name("required_signers")
+                .long("required-signers")
+                .takes_value(true)
+                .value_name("count")
+                .help(
+                    "Number of keys of each member node that must vote to accept before the \
+                     node accepts (default: 1)",
+                ),
+        )
+        .arg(
+            Arg::with_name("node_required_signers")
+                .long("node-required-signers")
+                .takes_value(true)
+                .multiple(true)
+                .help(
+                    "Number of keys of a member node that must vote to accept before the node \
+                     accepts (<node_id>::<count>)",
+                ),
+        )
+        .arg(Arg::with_name("dry_run").long("dry-run").short("n").help(
+            if cfg!(feature = "proposal-validation") {
+                "Print circuit definition and validate the proposal on its members without \
+                     submitting it"
+            } else {
+                "Print circuit definition without submitting the proposal"
+            },
+        ))
         .after_help(CIRCUIT_PROPOSE_AFTER_HELP);
 
     #[cfg(feature = "circuit-auth-type")]
@@ -341,6 +402,87 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 ),
         );
 
+    let circuit_proposals = SubCommand::with_name("proposals")
+        .about("List the circuit proposals")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .help("URL of the Splinter daemon REST API")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("management_type")
+                .long("management-type")
+                .help(
+                    "Filter circuit proposals by circuit \
+                     management type",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("member")
+                .long("member")
+                .help(
+                    "Show proposals with the given node ID in \
+                    its member list",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("format")
+                .short("F")
+                .long("format")
+                .help("Output format")
+                .possible_values(&["human", "csv"])
+                .default_value("human")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("hidden_format")
+                .short("f")
+                .hidden(true)
+                .help("Output format")
+                .possible_values(&["human", "csv"])
+                .takes_value(true),
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw")
+                .about("Withdraw a pending circuit proposal made by this key")
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .takes_value(true)
+                        .help("URL of Splinter Daemon"),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Path to private key file"),
+                )
+                .arg(
+                    Arg::with_name("circuit_id")
+                        .value_name("circuit-id")
+                        .takes_value(true)
+                        .required(true)
+                        .help("ID of the proposed circuit"),
+                ),
+        );
+
+    #[cfg(feature = "circuit-history")]
+    let circuit_proposals = circuit_proposals.arg(
+        Arg::with_name("status")
+            .long("status")
+            .possible_values(&["pending", "accepted", "rejected", "withdrawn", "expired"])
+            .default_value("pending")
+            .takes_value(true)
+            .help("Show proposals with the given status"),
+    );
+
     let circuit_command = SubCommand::with_name("circuit")
         .about("Provides circuit management functionality")
         .setting(AppSettings::SubcommandRequiredElseHelp)
@@ -454,52 +596,36 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         .takes_value(true),
                 ),
         )
-        .subcommand(
-            SubCommand::with_name("proposals")
-                .about("List the circuit proposals")
-                .arg(
-                    Arg::with_name("url")
-                        .short("U")
-                        .long("url")
-                        .help("URL of the Splinter daemon REST API")
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("management_type")
-                        .long("management-type")
-                        .help(
-                            "Filter circuit proposals by circuit \
-                             management type",
-                        )
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("member")
-                        .long("member")
-                        .help(
-                            "Show proposals with the given node ID in \
-                            its member list",
-                        )
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("format")
-                        .short("F")
-                        .long("format")
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .default_value("human")
-                        .takes_value(true),
-                )
-                .arg(
-                    Arg::with_name("hidden_format")
-                        .short("f")
-                        .hidden(true)
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .takes_value(true),
-                ),
-        );
+        .subcommand(circuit_proposals);
+
+    #[cfg(feature = "circuit-history")]
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("history")
+            .about("Show the history of a circuit's proposals")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .help("URL of the Splinter daemon REST API")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit"),
+            )
+            .arg(
+                Arg::with_name("format")
+                    .short("F")
+                    .long("format")
+                    .help("Output format")
+                    .possible_values(&["human", "csv"])
+                    .default_value("human")
+                    .takes_value(true),
+            ),
+    );
 
     #[cfg(feature = "circuit-template")]
     let circuit_command = circuit_command.subcommand(
@@ -543,44 +669,237 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     app = app.subcommand(circuit_command);
 
-    app = app.subcommand(
-        SubCommand::with_name("registry")
-            .about("Splinter registry commands")
-            .setting(AppSettings::SubcommandRequiredElseHelp)
-            .subcommand(
-                SubCommand::with_name("build")
-                    .about("Add a node to a YAML file")
-                    .arg(Arg::with_name("file").long("file").takes_value(true).help(
-                        "Path of registry file to add node to; defaults to \
+    let registry_command = SubCommand::with_name("registry")
+        .about("Splinter registry commands")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("build")
+                .about("Add a node to a YAML file")
+                .arg(Arg::with_name("file").long("file").takes_value(true).help(
+                    "Path of registry file to add node to; defaults to \
                                 './nodes.yaml'",
-                    ))
-                    .arg(
-                        Arg::with_name("force")
-                            .long("force")
-                            .help("Overwrite node if it already exists"),
-                    )
-                    .arg(
-                        Arg::with_name("status_url")
-                            .takes_value(true)
-                            .help("URL of splinter REST API to query for node data"),
-                    )
-                    .arg(
-                        Arg::with_name("key_files")
-                            .long("key-file")
-                            .takes_value(true)
-                            .multiple(true)
-                            .required(true)
-                            .help("Path of public key file to include with node"),
-                    )
-                    .arg(
-                        Arg::with_name("metadata")
-                            .long("metadata")
-                            .takes_value(true)
-                            .multiple(true)
-                            .help("Metadata to include with node (<key>=<value>)"),
-                    ),
-            ),
-    );
+                ))
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .help("Overwrite node if it already exists"),
+                )
+                .arg(
+                    Arg::with_name("status_url")
+                        .takes_value(true)
+                        .help("URL of splinter REST API to query for node data"),
+                )
+                .arg(
+                    Arg::with_name("key_files")
+                        .long("key-file")
+                        .takes_value(true)
+                        .multiple(true)
+                        .required(true)
+                        .help("Path of public key file to include with node"),
+                )
+                .arg(
+                    Arg::with_name("metadata")
+                        .long("metadata")
+                        .takes_value(true)
+                        .multiple(true)
+                        .help("Metadata to include with node (<key>=<value>)"),
+                ),
+        );
+
+    #[cfg(feature = "registry")]
+    let registry_command = registry_command
+        .subcommand(
+            SubCommand::with_name("add")
+                .about("Add a node to the registry of a running Splinter daemon")
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("identity")
+                        .required(true)
+                        .takes_value(true)
+                        .help("Identity of the node to add"),
+                )
+                .arg(
+                    Arg::with_name("endpoints")
+                        .long("endpoint")
+                        .takes_value(true)
+                        .multiple(true)
+                        .required(true)
+                        .help("Endpoint the node can be reached at"),
+                )
+                .arg(
+                    Arg::with_name("display_name")
+                        .long("display-name")
+                        .takes_value(true)
+                        .help("Human-readable name of the node; defaults to the node's identity"),
+                )
+                .arg(
+                    Arg::with_name("key_files")
+                        .long("key-file")
+                        .takes_value(true)
+                        .multiple(true)
+                        .required(true)
+                        .help("Path of public key file to include with node"),
+                )
+                .arg(
+                    Arg::with_name("metadata")
+                        .long("metadata")
+                        .takes_value(true)
+                        .multiple(true)
+                        .help("Metadata to include with node (<key>=<value>)"),
+                )
+                .arg(
+                    Arg::with_name("dry_run")
+                        .long("dry-run")
+                        .help("Display the node without adding it"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("update")
+                .about("Update a node in the registry of a running Splinter daemon")
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("identity")
+                        .required(true)
+                        .takes_value(true)
+                        .help("Identity of the node to update"),
+                )
+                .arg(
+                    Arg::with_name("endpoints")
+                        .long("endpoint")
+                        .takes_value(true)
+                        .multiple(true)
+                        .help(
+                            "Endpoint the node can be reached at; replaces all existing \
+                             endpoints",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("display_name")
+                        .long("display-name")
+                        .takes_value(true)
+                        .help("Human-readable name of the node"),
+                )
+                .arg(
+                    Arg::with_name("key_files")
+                        .long("key-file")
+                        .takes_value(true)
+                        .multiple(true)
+                        .help(
+                            "Path of public key file to include with node; replaces all \
+                             existing keys",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("metadata")
+                        .long("metadata")
+                        .takes_value(true)
+                        .multiple(true)
+                        .help("Metadata to add to or change for the node (<key>=<value>)"),
+                )
+                .arg(
+                    Arg::with_name("remove_metadata")
+                        .long("remove-metadata")
+                        .takes_value(true)
+                        .multiple(true)
+                        .help("Key of metadata to remove from the node"),
+                )
+                .arg(
+                    Arg::with_name("dry_run")
+                        .long("dry-run")
+                        .help("Display the changes to the node without updating it"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove")
+                .about("Remove a node from the registry of a running Splinter daemon")
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("identity")
+                        .required(true)
+                        .takes_value(true)
+                        .help("Identity of the node to remove"),
+                )
+                .arg(
+                    Arg::with_name("dry_run")
+                        .long("dry-run")
+                        .help("Display the node without removing it"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("List the nodes in the registry of a running Splinter daemon")
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("filter")
+                        .long("filter")
+                        .takes_value(true)
+                        .help(
+                            "Metadata filter object to select nodes with, such as \
+                             '{\"company\":[\"=\",\"Bitwise IO\"]}'",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .short("F")
+                        .long("format")
+                        .help("Output format")
+                        .possible_values(&["human", "csv"])
+                        .default_value("human")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Show a node in the registry of a running Splinter daemon")
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("identity")
+                        .required(true)
+                        .takes_value(true)
+                        .help("Identity of the node to show"),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .short("F")
+                        .long("format")
+                        .help("Output format")
+                        .possible_values(&["human", "json", "yaml"])
+                        .default_value("human")
+                        .takes_value(true),
+                ),
+        );
+
+    app = app.subcommand(registry_command);
 
     #[cfg(feature = "health")]
     {
@@ -675,6 +994,9 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         .with_command("show", circuit::CircuitShowAction)
         .with_command("proposals", circuit::CircuitProposalsAction);
 
+    #[cfg(feature = "circuit-history")]
+    let circuit_command = circuit_command.with_command("history", circuit::CircuitHistoryAction);
+
     #[cfg(feature = "circuit-template")]
     let circuit_command = circuit_command.with_command(
         "template",
@@ -686,10 +1008,18 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     subcommands = subcommands.with_command("circuit", circuit_command);
 
-    subcommands = subcommands.with_command(
-        "registry",
-        SubcommandActions::new().with_command("build", registry::RegistryGenerateAction),
-    );
+    let registry_command =
+        SubcommandActions::new().with_command("build", registry::RegistryGenerateAction);
+
+    #[cfg(feature = "registry")]
+    let registry_command = registry_command
+        .with_command("add", registry::RegistryAddAction)
+        .with_command("update", registry::RegistryUpdateAction)
+        .with_command("remove", registry::RegistryRemoveAction)
+        .with_command("list", registry::RegistryListAction)
+        .with_command("show", registry::RegistryShowAction);
+
+    subcommands = subcommands.with_command("registry", registry_command);
 
     #[cfg(feature = "health")]
     {
@@ -14,8 +14,9 @@
 
 pub use super::service::messages::{
     is_valid_circuit_id, is_valid_service_id, AdminServiceEvent, AuthorizationType,
-    CircuitProposal, CircuitProposalVote, CreateCircuit, DurabilityType, PersistenceType,
-    ProposalType, RouteType, SplinterNode, SplinterService, Vote, VoteRecord,
+    CircuitProposal, CircuitProposalVote, CircuitProposalWithdraw, CreateCircuit, DurabilityType,
+    NodeSigners, NodeValidation, NodeValidationStatus, NodeWeight, PersistenceType, ProposalType,
+    RouteType, SignerQuorum, SplinterNode, SplinterService, Vote, VoteRecord, VotingPolicy,
 };
 
 pub use super::service::messages::builders::{
@@ -19,6 +19,10 @@ use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, Future};
 use std::collections::HashMap;
 
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::circuit::store::{CircuitFilter, CircuitStore};
 use crate::protocol;
 use crate::rest_api::{
@@ -30,7 +34,10 @@ use super::super::error::CircuitListError;
 use super::super::resources::circuits::{CircuitResponse, ListCircuitsResponse};
 
 pub fn make_list_circuits_resource<T: CircuitStore + 'static>(store: T) -> Resource {
-    Resource::build("/admin/circuits")
+    let resource = Resource::build("/admin/circuits");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::ADMIN_LIST_CIRCUITS_MIN,
             protocol::ADMIN_PROTOCOL_VERSION,
@@ -18,6 +18,10 @@
 use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::Future;
 
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::circuit::store::CircuitStore;
 use crate::protocol;
 use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
@@ -26,7 +30,10 @@ use super::super::error::CircuitFetchError;
 use super::super::resources::circuits_circuit_id::CircuitResponse;
 
 pub fn make_fetch_circuit_resource<T: CircuitStore + 'static>(store: T) -> Resource {
-    Resource::build("/admin/circuits/{circuit_id}")
+    let resource = Resource::build("/admin/circuits/{circuit_id}");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::ADMIN_FETCH_CIRCUIT_MIN,
             protocol::ADMIN_PROTOCOL_VERSION,
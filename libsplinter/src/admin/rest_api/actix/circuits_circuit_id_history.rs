@@ -0,0 +1,73 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Provides the `GET /admin/circuits/{circuit_id}/history` endpoint for listing the audit log of a
+//! circuit's proposals.
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::Future;
+
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
+use crate::admin::service::AdminCommands;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::protocol;
+use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
+
+use super::super::error::CircuitHistoryError;
+use super::super::resources::circuits_circuit_id_history::CircuitHistoryResponse;
+
+pub fn make_circuit_history_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource = Resource::build("/admin/circuits/{circuit_id}/history");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::ADMIN_CIRCUIT_HISTORY_PROTOCOL_MIN,
+            protocol::ADMIN_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, move |r, _| {
+            fetch_circuit_history(r, admin_commands.clone())
+        })
+}
+
+fn fetch_circuit_history<A: AdminCommands + Clone + 'static>(
+    request: HttpRequest,
+    admin_commands: A,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = request
+        .match_info()
+        .get("circuit_id")
+        .unwrap_or("")
+        .to_string();
+    Box::new(
+        web::block(move || {
+            admin_commands
+                .circuit_history(&circuit_id)
+                .map_err(|err| CircuitHistoryError::InternalError(err.to_string()))
+        })
+        .then(|res| match res {
+            Ok(entries) => {
+                Ok(HttpResponse::Ok().json(CircuitHistoryResponse::from(entries.as_slice())))
+            }
+            Err(BlockingError::Error(err)) => {
+                error!("{}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+            Err(_) => Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error())),
+        }),
+    )
+}
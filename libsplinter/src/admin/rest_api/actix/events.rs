@@ -0,0 +1,311 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `GET /admin/events` endpoint for fetching admin service events.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{
+    future::{self, Either, IntoFuture},
+    sync::oneshot,
+    Future,
+};
+use tokio::timer::Delay;
+
+use crate::admin::messages::AdminServiceEvent;
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
+use crate::admin::service::{
+    AdminCommands, AdminServiceEventSubscriber, AdminSubscriberError, ALL_EVENT_TYPES,
+};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::protocol;
+use crate::rest_api::paging::DEFAULT_LIMIT;
+use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
+
+use super::super::error::EventListError;
+use super::super::resources::events::{
+    cursor_from_timestamp, timestamp_from_cursor, EventResponse, ListEventsResponse,
+};
+
+/// The maximum number of events that may be returned by a single request
+const MAX_LIMIT: usize = 1000;
+/// The maximum number of seconds a request may wait for new events
+const MAX_WAIT_SECS: u64 = 60;
+
+pub fn make_list_events_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource = Resource::build("/admin/events");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::ADMIN_EVENTS_PROTOCOL_MIN,
+            protocol::ADMIN_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, move |r, _| {
+            list_events(r, admin_commands.clone())
+        })
+}
+
+fn list_events<A: AdminCommands + Clone + 'static>(
+    req: HttpRequest,
+    admin_commands: A,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(req.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    let since = match query
+        .get("since")
+        .map(|cursor| timestamp_from_cursor(cursor))
+    {
+        Some(Ok(since)) => Some(since),
+        Some(Err(err)) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&err))
+                    .into_future(),
+            )
+        }
+        None => None,
+    };
+
+    let event_type = query
+        .get("type")
+        .cloned()
+        .unwrap_or_else(|| ALL_EVENT_TYPES.to_string());
+
+    let limit = match query.get("limit") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(val) if val > 0 && val <= MAX_LIMIT => val,
+            _ => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid limit value passed: {}. Must be between 1 and {}",
+                            value, MAX_LIMIT
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    let wait = match query.get("wait") {
+        Some(value) => match value.parse::<u64>() {
+            Ok(val) if val <= MAX_WAIT_SECS => Duration::from_secs(val),
+            _ => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid wait value passed: {}. Must be between 0 and {}",
+                            value, MAX_WAIT_SECS
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => Duration::from_secs(0),
+    };
+
+    let wait_admin_commands = admin_commands.clone();
+    let wait_event_type = event_type.clone();
+    Box::new(
+        web::block(move || fetch_events(&admin_commands, since, &event_type, limit, wait))
+            .and_then(move |fetched| match fetched {
+                Fetched::Events(events, has_more) => Either::A(future::ok((events, has_more))),
+                Fetched::Waiting(receiver) => Either::B(wait_for_events(
+                    wait_admin_commands,
+                    receiver,
+                    since,
+                    wait_event_type,
+                    limit,
+                    wait,
+                )),
+            })
+            .then(move |res| match res {
+                Ok((events, has_more)) => {
+                    let cursor = events
+                        .last()
+                        .map(|(timestamp, _)| timestamp)
+                        .or_else(|| since.as_ref())
+                        .map(cursor_from_timestamp);
+                    Ok(HttpResponse::Ok().json(ListEventsResponse {
+                        data: events.iter().map(EventResponse::from).collect(),
+                        cursor,
+                        has_more,
+                    }))
+                }
+                Err(err) => match err {
+                    BlockingError::Error(err) => match err {
+                        EventListError::InternalError(_) => {
+                            error!("{}", err);
+                            Ok(HttpResponse::InternalServerError().into())
+                        }
+                    },
+                    _ => Ok(HttpResponse::InternalServerError().into()),
+                },
+            }),
+    )
+}
+
+/// A page of events, and whether more events are available.
+type FetchedEvents = (Vec<(SystemTime, AdminServiceEvent)>, bool);
+
+/// The result of the initial fetch of a request: either the events to return, or a receiver that
+/// is notified when a new event is added.
+enum Fetched {
+    Events(Vec<(SystemTime, AdminServiceEvent)>, bool),
+    Waiting(oneshot::Receiver<()>),
+}
+
+/// Returns up to `limit` events after `since`, and whether more events are available. If there
+/// are no such events and `wait` is not zero, subscribes to new events instead.
+fn fetch_events<A: AdminCommands>(
+    admin_commands: &A,
+    since: Option<SystemTime>,
+    event_type: &str,
+    limit: usize,
+    wait: Duration,
+) -> Result<Fetched, EventListError> {
+    let (events, has_more) = load_events(admin_commands, since, event_type, limit)?;
+    if !events.is_empty() || wait == Duration::from_secs(0) {
+        return Ok(Fetched::Events(events, has_more));
+    }
+
+    // Subscribe before checking for events again, so an event that is added between the two
+    // checks is not missed
+    let (sender, receiver) = oneshot::channel();
+    admin_commands
+        .add_event_subscriber(
+            event_type,
+            Box::new(LongPollEventSubscriber {
+                sender: Mutex::new(Some(sender)),
+            }),
+        )
+        .map_err(|err| EventListError::InternalError(err.to_string()))?;
+
+    let (events, has_more) = load_events(admin_commands, since, event_type, limit)?;
+    if !events.is_empty() {
+        return Ok(Fetched::Events(events, has_more));
+    }
+
+    Ok(Fetched::Waiting(receiver))
+}
+
+/// Waits up to `wait` for the receiver to be notified of a new event without blocking a thread,
+/// then loads the events after `since`. If no event was added, the request's subscriber is
+/// removed and no events are returned.
+fn wait_for_events<A: AdminCommands + 'static>(
+    admin_commands: A,
+    receiver: oneshot::Receiver<()>,
+    since: Option<SystemTime>,
+    event_type: String,
+    limit: usize,
+    wait: Duration,
+) -> Box<dyn Future<Item = FetchedEvents, Error = BlockingError<EventListError>>> {
+    Box::new(receiver.select2(Delay::new(Instant::now() + wait)).then(
+        move |res| -> Box<dyn Future<Item = _, Error = _>> {
+            match res {
+                Ok(Either::A(_)) => Box::new(web::block(move || {
+                    load_events(&admin_commands, since, &event_type, limit)
+                })),
+                Ok(Either::B((_, receiver))) | Err(Either::B((_, receiver))) => {
+                    // Close the subscription before removing it
+                    drop(receiver);
+                    Box::new(web::block(move || {
+                        admin_commands
+                            .remove_closed_event_subscribers()
+                            .map_err(|err| EventListError::InternalError(err.to_string()))?;
+                        Ok((vec![], false))
+                    }))
+                }
+                // The subscriber was removed by the admin service without being notified
+                Err(Either::A(_)) => Box::new(future::ok((vec![], false))),
+            }
+        },
+    ))
+}
+
+/// Returns up to `limit` events after `since`, and whether more events are available.
+pub(super) fn load_events<A: AdminCommands>(
+    admin_commands: &A,
+    since: Option<SystemTime>,
+    event_type: &str,
+    limit: usize,
+) -> Result<FetchedEvents, EventListError> {
+    // The mailbox includes events that occurred at the start time, but the event identified by
+    // the cursor has already been seen by the client.
+    let mut events = admin_commands
+        .get_events_since(&since.unwrap_or(UNIX_EPOCH), event_type)
+        .map_err(|err| EventListError::InternalError(err.to_string()))?
+        .filter(|(timestamp, _)| since.map(|since| *timestamp > since).unwrap_or(true))
+        .take(limit + 1)
+        .collect::<Vec<_>>();
+
+    let has_more = events.len() > limit;
+    events.truncate(limit);
+
+    Ok((events, has_more))
+}
+
+/// Notifies a waiting request that a new event is available. The subscriber is removed once it
+/// has sent its notification, or once the request has completed without one.
+struct LongPollEventSubscriber {
+    sender: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl AdminServiceEventSubscriber for LongPollEventSubscriber {
+    fn handle_event(
+        &self,
+        _admin_service_event: &AdminServiceEvent,
+        _timestamp: &SystemTime,
+    ) -> Result<(), AdminSubscriberError> {
+        let mut sender = self.sender.lock().map_err(|_| {
+            AdminSubscriberError::UnableToHandleEvent("Long poll sender lock poisoned".into())
+        })?;
+        if let Some(sender) = sender.take() {
+            // The request may have completed in the meantime, in which case there is no one to
+            // notify
+            let _ = sender.send(());
+        }
+        Err(AdminSubscriberError::Unsubscribe)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.sender
+            .lock()
+            .map(|sender| {
+                sender
+                    .as_ref()
+                    .map(|sender| sender.is_canceled())
+                    .unwrap_or(true)
+            })
+            .unwrap_or(true)
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `GET /admin/events/stream` endpoint for streaming admin service events as
+//! Server-Sent Events.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use actix_web::{error::ErrorInternalServerError, web, Error, HttpRequest, HttpResponse};
+use futures::{
+    future::IntoFuture,
+    stream::{self, Stream},
+    sync::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    Future,
+};
+
+use crate::admin::messages::AdminServiceEvent;
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
+use crate::admin::service::{
+    AdminCommands, AdminServiceEventSubscriber, AdminSubscriberError, ALL_EVENT_TYPES,
+};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::protocol;
+use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
+
+use super::super::resources::events::{
+    cursor_from_timestamp, timestamp_from_cursor, EventResponse,
+};
+use super::events::load_events;
+
+/// The header sent by Server-Sent Events clients when reconnecting, containing the ID of the last
+/// event they received
+const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+/// The number of past events that are loaded at a time when a stream starts
+const BACKLOG_PAGE_SIZE: usize = 100;
+
+pub fn make_event_stream_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource = Resource::build("/admin/events/stream");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::ADMIN_EVENTS_PROTOCOL_MIN,
+            protocol::ADMIN_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, move |r, _| {
+            stream_events(r, admin_commands.clone())
+        })
+}
+
+fn stream_events<A: AdminCommands + Clone + 'static>(
+    req: HttpRequest,
+    admin_commands: A,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(req.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    // A reconnecting client resumes from the last event it received, rather than from the
+    // cursor of its original request
+    let cursor = match req.headers().get(LAST_EVENT_ID_HEADER) {
+        Some(header) => match header.to_str() {
+            Ok(cursor) => Some(cursor.to_string()),
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid {} header",
+                            LAST_EVENT_ID_HEADER
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => query.get("since").cloned(),
+    };
+
+    let since = match cursor.map(|cursor| timestamp_from_cursor(&cursor)) {
+        Some(Ok(since)) => Some(since),
+        Some(Err(err)) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&err))
+                    .into_future(),
+            )
+        }
+        None => None,
+    };
+
+    let event_type = query
+        .get("type")
+        .cloned()
+        .unwrap_or_else(|| ALL_EVENT_TYPES.to_string());
+
+    // Subscribe before loading the past events, so an event that is added in between is not
+    // missed; events that are both loaded and received are filtered out of the stream.
+    let subscribe_admin_commands = admin_commands.clone();
+    let subscribe_event_type = event_type.clone();
+    Box::new(
+        web::block(move || {
+            let (sender, receiver) = unbounded();
+            subscribe_admin_commands
+                .add_event_subscriber(
+                    &subscribe_event_type,
+                    Box::new(SseAdminServiceEventSubscriber { sender }),
+                )
+                .map(|_| receiver)
+        })
+        .then(move |res| match res {
+            Ok(receiver) => Ok(HttpResponse::Ok()
+                .content_type("text/event-stream")
+                .header("Cache-Control", "no-cache")
+                .streaming(event_stream(admin_commands, since, event_type, receiver))),
+            Err(err) => {
+                error!("Unable to add admin event subscriber: {}", err);
+                Ok(HttpResponse::InternalServerError().finish())
+            }
+        }),
+    )
+}
+
+/// Streams the events after `since` as Server-Sent Events messages. The past events are loaded
+/// off the worker thread, a page at a time, before the events received by the subscriber.
+fn event_stream<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+    since: Option<SystemTime>,
+    event_type: String,
+    receiver: UnboundedReceiver<(SystemTime, AdminServiceEvent)>,
+) -> impl Stream<Item = web::Bytes, Error = Error> {
+    // The state is the start of the next page, or `None` once the last page has been loaded
+    let past_events = stream::unfold(Some(since), move |page_start| {
+        let admin_commands = admin_commands.clone();
+        let event_type = event_type.clone();
+        page_start.map(move |page_start| {
+            web::block(move || {
+                load_events(&admin_commands, page_start, &event_type, BACKLOG_PAGE_SIZE)
+            })
+            .map(|(events, has_more)| {
+                let next_page_start = if has_more {
+                    Some(events.last().map(|(timestamp, _)| *timestamp))
+                } else {
+                    None
+                };
+                (stream::iter_ok::<_, Error>(events), next_page_start)
+            })
+            .map_err(|err| {
+                error!("Unable to load past admin events: {}", err);
+                ErrorInternalServerError("Unable to load past admin events")
+            })
+        })
+    })
+    .flatten();
+
+    let new_events = receiver.map_err(|_| ErrorInternalServerError("Admin event stream failed"));
+
+    let mut last_event = since;
+    past_events
+        .chain(new_events)
+        .filter(move |(timestamp, _)| {
+            if last_event.map(|last| *timestamp > last).unwrap_or(true) {
+                last_event = Some(*timestamp);
+                true
+            } else {
+                false
+            }
+        })
+        .filter_map(|event| match to_sse_message(&event) {
+            Ok(message) => Some(message),
+            Err(err) => {
+                error!("Unable to serialize admin event: {}", err);
+                None
+            }
+        })
+}
+
+/// Formats an event as a Server-Sent Events message, using the event's cursor as its ID.
+fn to_sse_message(
+    event: &(SystemTime, AdminServiceEvent),
+) -> Result<web::Bytes, serde_json::Error> {
+    let data = serde_json::to_string(&EventResponse::from(event))?;
+    Ok(format!(
+        "id: {}\ndata: {}\n\n",
+        cursor_from_timestamp(&event.0),
+        data
+    )
+    .into())
+}
+
+struct SseAdminServiceEventSubscriber {
+    sender: UnboundedSender<(SystemTime, AdminServiceEvent)>,
+}
+
+impl AdminServiceEventSubscriber for SseAdminServiceEventSubscriber {
+    fn handle_event(
+        &self,
+        admin_service_event: &AdminServiceEvent,
+        timestamp: &SystemTime,
+    ) -> Result<(), AdminSubscriberError> {
+        self.sender
+            .unbounded_send((*timestamp, admin_service_event.clone()))
+            .map_err(|_| {
+                debug!("Dropping admin service event and unsubscribing due to stream being closed");
+                AdminSubscriberError::Unsubscribe
+            })
+    }
+
+    fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
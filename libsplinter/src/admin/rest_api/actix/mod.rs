@@ -14,7 +14,15 @@
 
 pub(super) mod circuits;
 pub(super) mod circuits_circuit_id;
+#[cfg(feature = "admin-audit-log")]
+pub(super) mod circuits_circuit_id_history;
+#[cfg(feature = "admin-event-endpoints")]
+pub(super) mod events;
+#[cfg(feature = "admin-event-endpoints")]
+pub(super) mod events_stream;
 pub(super) mod proposals;
 pub(super) mod proposals_circuit_id;
+#[cfg(feature = "admin-proposal-validation")]
+pub(super) mod proposals_validate;
 pub(super) mod submit;
 pub(super) mod ws_register_type;
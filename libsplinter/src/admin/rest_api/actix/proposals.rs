@@ -17,7 +17,13 @@ use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, Future};
 use std::collections::HashMap;
 
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
 use crate::admin::service::proposal_store::{ProposalFilter, ProposalStore};
+#[cfg(feature = "admin-audit-log")]
+use crate::admin::store::AuditEventType;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::protocol;
 use crate::rest_api::paging::{get_response_paging_info, DEFAULT_LIMIT, DEFAULT_OFFSET};
 use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
@@ -26,7 +32,10 @@ use super::super::error::ProposalListError;
 use super::super::resources::proposals::{ListProposalsResponse, ProposalResponse};
 
 pub fn make_list_proposals_resource<PS: ProposalStore + 'static>(proposal_store: PS) -> Resource {
-    Resource::build("admin/proposals")
+    let resource = Resource::build("admin/proposals");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::ADMIN_LIST_PROPOSALS_PROTOCOL_MIN,
             protocol::ADMIN_PROTOCOL_VERSION,
@@ -94,6 +103,35 @@ fn list_proposals<PS: ProposalStore + 'static>(
         new_queries.push(format!("member={}", member));
         member.to_string()
     });
+    #[cfg(feature = "admin-audit-log")]
+    let closed_by = match query.get("status").map(String::as_str) {
+        None => None,
+        Some("pending") => {
+            new_queries.push("status=pending".to_string());
+            None
+        }
+        Some(status) => {
+            let closed_by = match status {
+                "accepted" => AuditEventType::ProposalAccepted,
+                "rejected" => AuditEventType::ProposalRejected,
+                "withdrawn" => AuditEventType::ProposalWithdrawn,
+                "expired" => AuditEventType::ProposalExpired,
+                _ => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid status value passed: {}. Expected one of: pending, \
+                                 accepted, rejected, withdrawn, expired",
+                                status
+                            )))
+                            .into_future(),
+                    )
+                }
+            };
+            new_queries.push(format!("status={}", status));
+            Some(closed_by)
+        }
+    };
 
     let mut link = req.uri().path().to_string();
     if !new_queries.is_empty() {
@@ -105,6 +143,8 @@ fn list_proposals<PS: ProposalStore + 'static>(
         link,
         management_type_filter,
         member_filter,
+        #[cfg(feature = "admin-audit-log")]
+        closed_by,
         Some(offset),
         Some(limit),
     ))
@@ -115,6 +155,7 @@ fn query_list_proposals<PS: ProposalStore + 'static>(
     link: String,
     management_type_filter: Option<String>,
     member_filter: Option<String>,
+    #[cfg(feature = "admin-audit-log")] closed_by: Option<AuditEventType>,
     offset: Option<usize>,
     limit: Option<usize>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
@@ -127,17 +168,32 @@ fn query_list_proposals<PS: ProposalStore + 'static>(
             filters.push(ProposalFilter::WithMember(member));
         }
 
-        let proposals = proposal_store
-            .proposals(filters)
-            .map_err(|err| ProposalListError::InternalError(err.to_string()))?;
         let offset_value = offset.unwrap_or(0);
+
+        // Closed proposals are paged by the audit store; pending proposals are paged here
+        #[cfg(feature = "admin-audit-log")]
+        let (proposals, paged) = match closed_by {
+            Some(closed_by) => (
+                proposal_store.closed_proposals(closed_by, filters, offset_value, limit),
+                true,
+            ),
+            None => (proposal_store.proposals(filters), false),
+        };
+        #[cfg(not(feature = "admin-audit-log"))]
+        let (proposals, paged) = (proposal_store.proposals(filters), false);
+
+        let proposals =
+            proposals.map_err(|err| ProposalListError::InternalError(err.to_string()))?;
         let total = proposals.total() as usize;
-        let limit_value = limit.unwrap_or(total);
 
-        let proposals = proposals
-            .skip(offset_value)
-            .take(limit_value)
-            .collect::<Vec<_>>();
+        let proposals = if paged {
+            proposals.collect::<Vec<_>>()
+        } else {
+            proposals
+                .skip(offset_value)
+                .take(limit.unwrap_or(total))
+                .collect::<Vec<_>>()
+        };
 
         Ok((proposals, link, limit, offset, total))
     })
@@ -170,7 +226,7 @@ mod tests {
     use crate::admin::{
         messages::{
             AuthorizationType, CircuitProposal, CreateCircuit, DurabilityType, PersistenceType,
-            ProposalType, RouteType, SplinterNode,
+            ProposalType, RouteType, SignerQuorum, SplinterNode, VotingPolicy,
         },
         service::proposal_store::{ProposalFilter, ProposalIter, ProposalStoreError},
     };
@@ -466,6 +522,81 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[cfg(feature = "admin-audit-log")]
+    #[test]
+    /// Tests a GET /admin/proposals request with the `status` filter returns the proposals that
+    /// were closed with that status.
+    fn test_list_proposals_with_status_ok() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_proposals_resource(MockProposalStore)]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals?status=rejected",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", protocol::ADMIN_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let proposals: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            proposals.get("data").expect("no data field in response"),
+            &to_value(vec![ProposalResponse::from(&get_proposal_2())])
+                .expect("failed to convert expected data"),
+        );
+
+        assert_eq!(
+            proposals
+                .get("paging")
+                .expect("no paging field in response"),
+            &to_value(create_test_paging_response(
+                0,
+                100,
+                0,
+                0,
+                0,
+                1,
+                &format!("/admin/proposals?status=rejected&")
+            ))
+            .expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[cfg(feature = "admin-audit-log")]
+    #[test]
+    /// Tests a GET /admin/proposals request with an unknown `status` returns a BadRequest
+    /// response.
+    fn test_list_proposals_with_invalid_status() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_proposals_resource(MockProposalStore)]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals?status=unknown",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", protocol::ADMIN_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     fn create_test_paging_response(
         offset: usize,
         limit: usize,
@@ -523,6 +654,32 @@ mod tests {
         ) -> Result<Option<CircuitProposal>, ProposalStoreError> {
             unimplemented!()
         }
+
+        #[cfg(feature = "admin-audit-log")]
+        fn closed_proposals(
+            &self,
+            closed_by: AuditEventType,
+            filters: Vec<ProposalFilter>,
+            offset: usize,
+            limit: Option<usize>,
+        ) -> Result<ProposalIter, ProposalStoreError> {
+            let proposals = match closed_by {
+                AuditEventType::ProposalRejected => vec![get_proposal_2()],
+                _ => vec![],
+            }
+            .into_iter()
+            .filter(|proposal| filters.iter().all(|filter| filter.matches(&proposal)))
+            .collect::<Vec<_>>();
+
+            let total = proposals.len();
+            let proposals = proposals
+                .into_iter()
+                .skip(offset)
+                .take(limit.unwrap_or(total))
+                .collect::<Vec<_>>();
+
+            Ok(ProposalIter::new(Box::new(proposals.into_iter()), total))
+        }
     }
 
     fn get_proposal_1() -> CircuitProposal {
@@ -548,6 +705,9 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
+            voting_policy: VotingPolicy::Unanimous,
+            signer_quorum: SignerQuorum::default(),
         }
     }
 
@@ -571,6 +731,9 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
+            voting_policy: VotingPolicy::Unanimous,
+            signer_quorum: SignerQuorum::default(),
         }
     }
 
@@ -597,6 +760,9 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
+            voting_policy: VotingPolicy::Unanimous,
+            signer_quorum: SignerQuorum::default(),
         }
     }
 
@@ -19,14 +19,21 @@ use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::Future;
 
 use crate::admin::rest_api::error::ProposalFetchError;
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
 use crate::admin::service::proposal_store::ProposalStore;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::protocol;
 use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
 
 use super::super::resources::proposals_circuit_id::ProposalResponse;
 
 pub fn make_fetch_proposal_resource<PS: ProposalStore + 'static>(proposal_store: PS) -> Resource {
-    Resource::build("admin/proposals/{circuit_id}")
+    let resource = Resource::build("admin/proposals/{circuit_id}");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::ADMIN_FETCH_PROPOSALS_PROTOCOL_MIN,
             protocol::ADMIN_PROTOCOL_VERSION,
@@ -86,7 +93,7 @@ mod tests {
     use crate::admin::{
         messages::{
             AuthorizationType, CircuitProposal, CreateCircuit, DurabilityType, PersistenceType,
-            ProposalType, RouteType,
+            ProposalType, RouteType, SignerQuorum, VotingPolicy,
         },
         service::proposal_store::{ProposalFilter, ProposalIter, ProposalStoreError},
     };
@@ -172,6 +179,17 @@ mod tests {
                 None
             })
         }
+
+        #[cfg(feature = "admin-audit-log")]
+        fn closed_proposals(
+            &self,
+            _closed_by: crate::admin::store::AuditEventType,
+            _filters: Vec<ProposalFilter>,
+            _offset: usize,
+            _limit: Option<usize>,
+        ) -> Result<ProposalIter, ProposalStoreError> {
+            unimplemented!()
+        }
     }
 
     fn get_proposal() -> CircuitProposal {
@@ -194,6 +212,9 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
+            voting_policy: VotingPolicy::Unanimous,
+            signer_quorum: SignerQuorum::default(),
         }
     }
 
@@ -0,0 +1,89 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `POST /admin/proposals/validate` endpoint for validating circuit management
+//! payloads without submitting them.
+
+use actix_web::{error::BlockingError, web, Error, HttpResponse};
+use futures::Future;
+
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_WRITE_PERMISSION;
+use crate::admin::service::{AdminCommands, AdminServiceError};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::protocol;
+use crate::protos::admin::CircuitManagementPayload;
+use crate::rest_api::{into_protobuf, ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
+use crate::service::ServiceError;
+
+use super::super::error::ProposalValidationError;
+use super::super::resources::proposals_validate::ValidateProposalResponse;
+
+pub fn make_validate_proposal_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource = Resource::build("/admin/proposals/validate");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_WRITE_PERMISSION));
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::ADMIN_VALIDATE_PROPOSAL_PROTOCOL_MIN,
+            protocol::ADMIN_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Post, move |_, payload| {
+            let admin_commands = admin_commands.clone();
+            Box::new(
+                into_protobuf::<CircuitManagementPayload>(payload)
+                    .and_then(move |payload| validate_proposal(admin_commands, payload)),
+            )
+        })
+}
+
+fn validate_proposal<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+    payload: CircuitManagementPayload,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    // Validation waits for the other members' responses, so it must not block the server's
+    // worker threads
+    Box::new(
+        web::block(move || {
+            admin_commands
+                .validate_circuit_change(payload)
+                .map_err(|err| match err {
+                    AdminServiceError::ServiceError(ServiceError::InvalidMessageFormat(err)) => {
+                        ProposalValidationError::BadRequest(format!(
+                            "Failed to parse payload: {}",
+                            err
+                        ))
+                    }
+                    err => ProposalValidationError::InternalError(err.to_string()),
+                })
+        })
+        .then(|res| match res {
+            Ok(mut nodes) => {
+                nodes.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+                Ok(HttpResponse::Ok().json(ValidateProposalResponse::from(nodes.as_slice())))
+            }
+            Err(BlockingError::Error(ProposalValidationError::BadRequest(msg))) => {
+                Ok(HttpResponse::BadRequest().json(ErrorResponse::bad_request(&msg)))
+            }
+            Err(BlockingError::Error(err)) => {
+                error!("{}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+            Err(_) => Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error())),
+        }),
+    )
+}
@@ -15,14 +15,21 @@
 use actix_web::HttpResponse;
 use futures::{Future, IntoFuture};
 
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_WRITE_PERMISSION;
 use crate::admin::service::{AdminCommands, AdminServiceError};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::protocol;
 use crate::protos::admin::CircuitManagementPayload;
 use crate::rest_api::{into_protobuf, Method, ProtocolVersionRangeGuard, Resource};
 use crate::service::ServiceError;
 
 pub fn make_submit_route<A: AdminCommands + Clone + 'static>(admin_commands: A) -> Resource {
-    Resource::build("/admin/submit")
+    let resource = Resource::build("/admin/submit");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_WRITE_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::ADMIN_SUBMIT_PROTOCOL_MIN,
             protocol::ADMIN_PROTOCOL_VERSION,
@@ -18,9 +18,13 @@ use std::collections::HashMap;
 use std::time;
 
 use crate::admin::messages::AdminServiceEvent;
+#[cfg(feature = "authorization")]
+use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
 use crate::admin::service::{
     AdminCommands, AdminServiceEventSubscriber, AdminServiceStatus, AdminSubscriberError,
 };
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::protocol;
 use crate::rest_api::{
     new_websocket_event_sender, EventSender, Method, ProtocolVersionRangeGuard, Request, Resource,
@@ -29,7 +33,10 @@ use crate::rest_api::{
 pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 'static>(
     admin_commands: A,
 ) -> Resource {
-    Resource::build("/ws/admin/register/{type}")
+    let resource = Resource::build("/ws/admin/register/{type}");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(CIRCUIT_READ_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::ADMIN_APPLICATION_REGISTRATION_PROTOCOL_MIN,
             protocol::ADMIN_PROTOCOL_VERSION,
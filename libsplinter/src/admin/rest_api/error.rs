@@ -61,6 +61,85 @@ impl std::fmt::Display for ProposalListError {
     }
 }
 
+#[cfg(feature = "admin-event-endpoints")]
+#[derive(Debug)]
+pub enum EventListError {
+    InternalError(String),
+}
+
+#[cfg(feature = "admin-event-endpoints")]
+impl Error for EventListError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EventListError::InternalError(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "admin-event-endpoints")]
+impl std::fmt::Display for EventListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EventListError::InternalError(msg) => write!(f, "Ran into internal error: {}", msg),
+        }
+    }
+}
+
+#[cfg(feature = "admin-proposal-validation")]
+#[derive(Debug)]
+pub enum ProposalValidationError {
+    BadRequest(String),
+    InternalError(String),
+}
+
+#[cfg(feature = "admin-proposal-validation")]
+impl Error for ProposalValidationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProposalValidationError::BadRequest(_) => None,
+            ProposalValidationError::InternalError(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "admin-proposal-validation")]
+impl std::fmt::Display for ProposalValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ProposalValidationError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            ProposalValidationError::InternalError(msg) => {
+                write!(f, "Ran into internal error: {}", msg)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "admin-audit-log")]
+#[derive(Debug)]
+pub enum CircuitHistoryError {
+    InternalError(String),
+}
+
+#[cfg(feature = "admin-audit-log")]
+impl Error for CircuitHistoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CircuitHistoryError::InternalError(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "admin-audit-log")]
+impl std::fmt::Display for CircuitHistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CircuitHistoryError::InternalError(msg) => {
+                write!(f, "Ran into internal error: {}", msg)
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum CircuitFetchError {
     NotFound(String),
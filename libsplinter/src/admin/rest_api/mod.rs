@@ -24,6 +24,14 @@ use crate::admin::service::AdminService;
 use crate::circuit::store;
 use crate::rest_api::{Resource, RestResourceProvider};
 
+/// The permission that is required to list and fetch circuits and proposals, and to register
+/// application authorization handlers
+#[cfg(feature = "authorization")]
+pub const CIRCUIT_READ_PERMISSION: &str = "circuit.read";
+/// The permission that is required to submit circuit management payloads
+#[cfg(feature = "authorization")]
+pub const CIRCUIT_WRITE_PERMISSION: &str = "circuit.write";
+
 /// The admin service provides the following endpoints as REST API resources:
 ///
 /// * `GET /ws/admin/register/{type}` - Register as an application authorization handler for the
@@ -32,6 +40,16 @@ use crate::rest_api::{Resource, RestResourceProvider};
 /// * `GET /admin/proposals` - List circuit proposals in Splinter's state
 /// * `GET /admin/proposals/{circuit_id}` - Fetch a specific circuit proposal in Splinter's state
 ///   by circuit ID
+/// * `POST /admin/proposals/validate` - Validate a circuit management payload on this node and
+///   the circuit's other members without submitting it (requires the `admin-proposal-validation`
+///   feature)
+/// * `GET /admin/events` - Fetch a page of admin service events since a cursor, optionally
+///   waiting for new events (requires the `admin-event-endpoints` feature)
+/// * `GET /admin/events/stream` - Stream admin service events since a cursor as Server-Sent
+///   Events (requires the `admin-event-endpoints` feature)
+///
+/// If the `authorization` feature is enabled, submitting and validating payloads requires the
+/// `circuit.write` permission and the other endpoints require the `circuit.read` permission.
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
@@ -55,6 +73,31 @@ impl RestResourceProvider for AdminService {
             ]);
         }
 
+        // The validation resource must be added before the proposal fetch resource, so that
+        // "validate" is not mistaken for a circuit ID
+        #[cfg(feature = "admin-proposal-validation")]
+        {
+            resources.insert(
+                0,
+                actix::proposals_validate::make_validate_proposal_resource(self.commands()),
+            );
+        }
+
+        #[cfg(feature = "admin-audit-log")]
+        {
+            resources.push(
+                actix::circuits_circuit_id_history::make_circuit_history_resource(self.commands()),
+            );
+        }
+
+        #[cfg(feature = "admin-event-endpoints")]
+        {
+            resources.append(&mut vec![
+                actix::events::make_list_events_resource(self.commands()),
+                actix::events_stream::make_event_stream_resource(self.commands()),
+            ]);
+        }
+
         resources
     }
 }
@@ -89,6 +132,9 @@ impl<T: store::CircuitStore + 'static> CircuitResourceProvider<T> {
 /// * `GET /admin/circuits/{circuit_id}` - Fetch a specific circuit in Splinter's state by circuit
 ///   ID
 ///
+/// If the `authorization` feature is enabled, these endpoints require the `circuit.read`
+/// permission.
+///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
 /// * `rest-api-actix`
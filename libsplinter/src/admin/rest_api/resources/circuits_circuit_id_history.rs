@@ -0,0 +1,68 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::admin::store::{AuditEntry, AuditEventType, Vote};
+use crate::hex::to_hex;
+
+use super::proposals::ProposalResponse;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct CircuitHistoryResponse<'a> {
+    pub data: Vec<AuditEntryResponse<'a>>,
+}
+
+impl<'a> From<&'a [AuditEntry]> for CircuitHistoryResponse<'a> {
+    fn from(entries: &'a [AuditEntry]) -> Self {
+        Self {
+            data: entries.iter().map(AuditEntryResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct AuditEntryResponse<'a> {
+    pub event_type: &'a str,
+    pub timestamp: u64,
+    pub circuit_hash: &'a str,
+    pub public_key: Option<String>,
+    pub node_id: Option<&'a str>,
+    pub vote: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposal: Option<ProposalResponse<'a>>,
+}
+
+impl<'a> From<&'a AuditEntry> for AuditEntryResponse<'a> {
+    fn from(entry: &'a AuditEntry) -> Self {
+        let event_type = match entry.event_type() {
+            AuditEventType::ProposalSubmitted => "ProposalSubmitted",
+            AuditEventType::ProposalVote => "ProposalVote",
+            AuditEventType::ProposalAccepted => "ProposalAccepted",
+            AuditEventType::ProposalRejected => "ProposalRejected",
+            AuditEventType::ProposalWithdrawn => "ProposalWithdrawn",
+            AuditEventType::ProposalExpired => "ProposalExpired",
+        };
+
+        Self {
+            event_type,
+            timestamp: entry.timestamp(),
+            circuit_hash: entry.circuit_hash(),
+            public_key: entry.public_key().map(to_hex),
+            node_id: entry.node_id(),
+            vote: entry.vote().map(|vote| match vote {
+                Vote::Accept => "Accept",
+                Vote::Reject => "Reject",
+            }),
+            proposal: entry.proposal().map(ProposalResponse::from),
+        }
+    }
+}
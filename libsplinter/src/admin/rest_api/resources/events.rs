@@ -0,0 +1,95 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::admin::messages::AdminServiceEvent;
+use crate::hex::{parse_hex, to_hex};
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct ListEventsResponse<'a> {
+    pub data: Vec<EventResponse<'a>>,
+    /// The cursor to provide as `since` to fetch the events that follow this response, if known
+    pub cursor: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct EventResponse<'a> {
+    pub cursor: String,
+    /// Milliseconds since the Unix epoch, matching the events sent over the websocket
+    pub timestamp: u128,
+    #[serde(flatten)]
+    pub event: &'a AdminServiceEvent,
+}
+
+impl<'a> From<&'a (SystemTime, AdminServiceEvent)> for EventResponse<'a> {
+    fn from((timestamp, event): &'a (SystemTime, AdminServiceEvent)) -> Self {
+        Self {
+            cursor: cursor_from_timestamp(timestamp),
+            timestamp: timestamp
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis())
+                .unwrap_or(0),
+            event,
+        }
+    }
+}
+
+/// Returns the opaque cursor that identifies the event recorded at the given time.
+pub(crate) fn cursor_from_timestamp(timestamp: &SystemTime) -> String {
+    let nanos = timestamp
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0);
+    to_hex(&nanos.to_be_bytes())
+}
+
+/// Returns the time of the event identified by the given cursor.
+pub(crate) fn timestamp_from_cursor(cursor: &str) -> Result<SystemTime, String> {
+    let bytes = parse_hex(cursor).map_err(|err| format!("Invalid cursor: {}", err))?;
+    let nanos: [u8; 8] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| "Invalid cursor: incorrect length".to_string())?;
+    Ok(UNIX_EPOCH + Duration::from_nanos(u64::from_be_bytes(nanos)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a cursor created from a timestamp is converted back to the same timestamp,
+    /// without losing precision.
+    #[test]
+    fn cursor_round_trip() {
+        let timestamp = UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789);
+
+        let cursor = cursor_from_timestamp(&timestamp);
+
+        assert_eq!(
+            timestamp,
+            timestamp_from_cursor(&cursor).expect("Unable to parse cursor")
+        );
+    }
+
+    /// Verify that invalid cursors are rejected.
+    #[test]
+    fn invalid_cursors() {
+        assert!(timestamp_from_cursor("not hex").is_err());
+        assert!(timestamp_from_cursor("0102").is_err());
+        assert!(timestamp_from_cursor("").is_err());
+    }
+}
@@ -14,5 +14,11 @@
 
 pub(in super::super) mod circuits;
 pub(in super::super) mod circuits_circuit_id;
+#[cfg(feature = "admin-audit-log")]
+pub(in super::super) mod circuits_circuit_id_history;
+#[cfg(feature = "admin-event-endpoints")]
+pub(in super::super) mod events;
 pub(in super::super) mod proposals;
 pub(in super::super) mod proposals_circuit_id;
+#[cfg(feature = "admin-proposal-validation")]
+pub(in super::super) mod proposals_validate;
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use crate::admin::messages::{
-    CircuitProposal, CreateCircuit, ProposalType, SplinterNode, SplinterService, Vote, VoteRecord,
+    CircuitProposal, CreateCircuit, ProposalType, SignerQuorum, SplinterNode, SplinterService,
+    Vote, VoteRecord, VotingPolicy,
 };
 use crate::hex::as_hex;
 use crate::rest_api::paging::Paging;
@@ -34,6 +35,10 @@ pub(crate) struct ProposalResponse<'a> {
     #[serde(serialize_with = "as_hex")]
     pub requester: &'a [u8],
     pub requester_node_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    pub voting_policy: &'a VotingPolicy,
+    pub signer_quorum: &'a SignerQuorum,
 }
 
 impl<'a> From<&'a CircuitProposal> for ProposalResponse<'a> {
@@ -54,6 +59,9 @@ impl<'a> From<&'a CircuitProposal> for ProposalResponse<'a> {
             votes: proposal.votes.iter().map(VoteResponse::from).collect(),
             requester: &proposal.requester,
             requester_node_id: &proposal.requester_node_id,
+            expires_at: proposal.expires_at,
+            voting_policy: &proposal.voting_policy,
+            signer_quorum: &proposal.signer_quorum,
         }
     }
 }
@@ -0,0 +1,33 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::admin::messages::{NodeValidation, NodeValidationStatus};
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct ValidateProposalResponse<'a> {
+    /// Whether none of the circuit's members found the payload invalid
+    pub valid: bool,
+    pub nodes: &'a [NodeValidation],
+}
+
+impl<'a> From<&'a [NodeValidation]> for ValidateProposalResponse<'a> {
+    fn from(nodes: &'a [NodeValidation]) -> Self {
+        Self {
+            valid: !nodes
+                .iter()
+                .any(|node| node.status == NodeValidationStatus::Invalid),
+            nodes,
+        }
+    }
+}
@@ -0,0 +1,466 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Writes the entries of the circuit proposal audit log on a background thread, so that a slow or
+//! unavailable audit store does not hold up the admin service.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::admin::store::{AdminServiceStore, AuditEntry, AuditEventType};
+
+/// The delay before the first retry of an entry that could not be written
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// The maximum delay between retries of an entry that could not be written
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Appends entries to the audit log in the order they are recorded.
+///
+/// Entries that cannot be written are retried until they succeed; none are dropped. If a backlog
+/// file is configured, recorded entries are kept in it until they have been written, so entries
+/// that are still pending when the node stops are written after it restarts. An entry may be
+/// written twice if the node stops between writing it and removing it from the backlog.
+pub struct AuditLogWriter {
+    sender: Sender<AuditEntry>,
+    backlog: Arc<Mutex<Backlog>>,
+}
+
+impl AuditLogWriter {
+    /// Starts the writer thread. Entries left in the backlog file by a previous run are written
+    /// before any new entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `audit_store` - The store the entries are written to
+    /// * `backlog_path` - The file that holds the entries that have not been written yet; if
+    ///   `None`, pending entries are only kept in memory
+    pub fn start(
+        audit_store: Arc<dyn AdminServiceStore>,
+        backlog_path: Option<PathBuf>,
+    ) -> Result<Self, io::Error> {
+        let backlog = Backlog { path: backlog_path };
+        let pending = backlog.load()?;
+        if !pending.is_empty() {
+            info!(
+                "Writing {} audit log entries left from a previous run",
+                pending.len()
+            );
+        }
+        let backlog = Arc::new(Mutex::new(backlog));
+
+        let (sender, receiver) = channel();
+        let thread_backlog = backlog.clone();
+        thread::Builder::new()
+            .name("AdminAuditLogWriter".into())
+            .spawn(move || run(&*audit_store, &thread_backlog, pending, receiver))?;
+
+        Ok(Self { sender, backlog })
+    }
+
+    /// Records an entry to be written to the audit log. This does not wait for the entry to be
+    /// written.
+    pub fn record(&self, entry: AuditEntry) {
+        match self.backlog.lock() {
+            Ok(backlog) => {
+                if let Err(err) = backlog.append(&entry) {
+                    error!(
+                        "Unable to add {:?} for circuit proposal {} to the audit log backlog; it \
+                         will be lost if the node stops before it is written: {}",
+                        entry.event_type(),
+                        entry.circuit_id(),
+                        err
+                    );
+                }
+            }
+            Err(_) => error!("Audit log backlog lock was poisoned"),
+        }
+
+        if self.sender.send(entry).is_err() {
+            error!("Audit log writer has stopped; entry will be written after a restart");
+        }
+    }
+}
+
+/// Writes the pending entries, then each entry that is received, until the sender is dropped.
+fn run(
+    audit_store: &dyn AdminServiceStore,
+    backlog: &Mutex<Backlog>,
+    mut pending: VecDeque<AuditEntry>,
+    receiver: Receiver<AuditEntry>,
+) {
+    let mut retry_delay = INITIAL_RETRY_DELAY;
+    loop {
+        if pending.is_empty() {
+            match receiver.recv() {
+                Ok(entry) => pending.push_back(entry),
+                Err(_) => return,
+            }
+        }
+        pending.extend(receiver.try_iter());
+
+        let entry = match pending.front() {
+            Some(entry) => entry,
+            None => continue,
+        };
+
+        match audit_store.add_audit_entry(entry.clone()) {
+            Ok(()) => {
+                retry_delay = INITIAL_RETRY_DELAY;
+                match backlog.lock() {
+                    Ok(backlog) => {
+                        if let Err(err) = backlog.remove(entry) {
+                            error!("Unable to remove entry from the audit log backlog: {}", err);
+                        }
+                    }
+                    Err(_) => error!("Audit log backlog lock was poisoned"),
+                }
+                pending.pop_front();
+            }
+            Err(err) => {
+                warn!(
+                    "Unable to record {:?} for circuit proposal {} in the audit log, retrying \
+                     in {:?}: {}",
+                    entry.event_type(),
+                    entry.circuit_id(),
+                    retry_delay,
+                    err
+                );
+                record_audit_failure_metrics(entry.event_type());
+
+                // Wait before retrying, but keep receiving entries so they are not held up in the
+                // channel
+                match receiver.recv_timeout(retry_delay) {
+                    Ok(entry) => pending.push_back(entry),
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        warn!(
+                            "Stopping the audit log writer with {} entries not written",
+                            pending.len()
+                        );
+                        return;
+                    }
+                }
+                retry_delay = std::cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+            }
+        }
+    }
+}
+
+/// The file of entries that have been recorded but not yet written, one JSON entry per line, in
+/// the order they were recorded.
+struct Backlog {
+    path: Option<PathBuf>,
+}
+
+impl Backlog {
+    fn load(&self) -> Result<VecDeque<AuditEntry>, io::Error> {
+        let path = match self.path {
+            Some(ref path) if path.exists() => path,
+            _ => return Ok(VecDeque::new()),
+        };
+
+        BufReader::new(File::open(path)?)
+            .lines()
+            .filter(|line| line.as_ref().map(|line| !line.is_empty()).unwrap_or(true))
+            .map(|line| {
+                serde_json::from_str(&line?)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    fn append(&self, entry: &AuditEntry) -> Result<(), io::Error> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+
+        let mut line = serde_json::to_string(entry)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_data()
+    }
+
+    /// Removes an entry that has been written to the audit store
+    fn remove(&self, entry: &AuditEntry) -> Result<(), io::Error> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+
+        let written = serde_json::to_string(entry)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let contents = fs::read_to_string(path)?;
+        let mut removed = false;
+        let remaining = contents
+            .lines()
+            .filter(|line| {
+                if !removed && *line == written {
+                    removed = true;
+                    false
+                } else {
+                    true
+                }
+            })
+            .fold(String::new(), |mut remaining, line| {
+                remaining.push_str(line);
+                remaining.push('\n');
+                remaining
+            });
+
+        // Replace the file atomically, so a crash cannot leave a partial backlog
+        let temp_path = path.with_extension("tmp");
+        let mut file = File::create(&temp_path)?;
+        file.write_all(remaining.as_bytes())?;
+        file.sync_data()?;
+        fs::rename(temp_path, path)
+    }
+}
+
+#[cfg(feature = "metrics")]
+pub(super) fn record_audit_failure_metrics(event_type: &AuditEventType) {
+    crate::metrics::ADMIN_AUDIT_LOG_FAILURES
+        .with_label_values(&[&format!("{:?}", event_type)])
+        .inc();
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(super) fn record_audit_failure_metrics(_event_type: &AuditEventType) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    use tempdir::TempDir;
+
+    use crate::admin::store::error::AdminServiceStoreError;
+    use crate::admin::store::yaml::YamlAdminServiceStore;
+    use crate::admin::store::{
+        AuditEntryBuilder, AuditPredicate, Circuit, CircuitNode, CircuitPredicate, CircuitProposal,
+        Service, ServiceId,
+    };
+
+    /// A store that fails to add the first few audit entries, and otherwise forwards to a YAML
+    /// store
+    struct FlakyStore {
+        inner: YamlAdminServiceStore,
+        failures: AtomicUsize,
+    }
+
+    impl AdminServiceStore for FlakyStore {
+        fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+            self.inner.add_proposal(proposal)
+        }
+
+        fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+            self.inner.update_proposal(proposal)
+        }
+
+        fn remove_proposal(&self, proposal_id: &str) -> Result<(), AdminServiceStoreError> {
+            self.inner.remove_proposal(proposal_id)
+        }
+
+        fn get_proposal(
+            &self,
+            proposal_id: &str,
+        ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+            self.inner.get_proposal(proposal_id)
+        }
+
+        fn list_proposals(
+            &self,
+            predicates: &[CircuitPredicate],
+        ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>
+        {
+            self.inner.list_proposals(predicates)
+        }
+
+        fn add_circuit(
+            &self,
+            circuit: Circuit,
+            nodes: Vec<CircuitNode>,
+        ) -> Result<(), AdminServiceStoreError> {
+            self.inner.add_circuit(circuit, nodes)
+        }
+
+        fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
+            self.inner.update_circuit(circuit)
+        }
+
+        fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+            self.inner.remove_circuit(circuit_id)
+        }
+
+        fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
+            self.inner.get_circuit(circuit_id)
+        }
+
+        fn list_circuits(
+            &self,
+            predicates: &[CircuitPredicate],
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+            self.inner.list_circuits(predicates)
+        }
+
+        fn upgrade_proposal_to_circuit(
+            &self,
+            circuit_id: &str,
+        ) -> Result<(), AdminServiceStoreError> {
+            self.inner.upgrade_proposal_to_circuit(circuit_id)
+        }
+
+        fn get_node(&self, node_id: &str) -> Result<Option<CircuitNode>, AdminServiceStoreError> {
+            self.inner.get_node(node_id)
+        }
+
+        fn list_nodes(
+            &self,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitNode>>, AdminServiceStoreError>
+        {
+            self.inner.list_nodes()
+        }
+
+        fn get_service(
+            &self,
+            service_id: &ServiceId,
+        ) -> Result<Option<Service>, AdminServiceStoreError> {
+            self.inner.get_service(service_id)
+        }
+
+        fn list_services(
+            &self,
+            circuit_id: &str,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Service>>, AdminServiceStoreError> {
+            self.inner.list_services(circuit_id)
+        }
+
+        fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError> {
+            let failures = self.failures.load(Ordering::SeqCst);
+            if failures > 0 {
+                self.failures.store(failures - 1, Ordering::SeqCst);
+                return Err(AdminServiceStoreError::StorageError {
+                    context: "Audit log is unavailable".into(),
+                    source: None,
+                });
+            }
+            self.inner.add_audit_entry(entry)
+        }
+
+        fn list_audit_entries(
+            &self,
+            predicates: &[AuditPredicate],
+            offset: usize,
+            limit: Option<usize>,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError> {
+            self.inner.list_audit_entries(predicates, offset, limit)
+        }
+
+        fn count_audit_entries(
+            &self,
+            predicates: &[AuditPredicate],
+        ) -> Result<usize, AdminServiceStoreError> {
+            self.inner.count_audit_entries(predicates)
+        }
+    }
+
+    /// Verifies that entries that cannot be written at first are retried rather than dropped,
+    /// and are written in the order they were recorded.
+    #[test]
+    fn test_retries_failed_entries() {
+        let temp_dir = TempDir::new("test_retries_failed_entries").unwrap();
+        let store = Arc::new(FlakyStore {
+            inner: yaml_store(&temp_dir),
+            failures: AtomicUsize::new(3),
+        });
+
+        let writer = AuditLogWriter::start(store.clone(), None).unwrap();
+        writer.record(entry("01234-ABCDE", AuditEventType::ProposalSubmitted));
+        writer.record(entry("01234-ABCDE", AuditEventType::ProposalAccepted));
+
+        let entries = wait_for_entries(&store.inner, 2);
+        assert_eq!(entries[0].event_type(), &AuditEventType::ProposalSubmitted);
+        assert_eq!(entries[1].event_type(), &AuditEventType::ProposalAccepted);
+    }
+
+    /// Verifies that entries left in the backlog file are written when a writer starts, and are
+    /// removed from the backlog once written.
+    #[test]
+    fn test_writes_backlog_on_start() {
+        let temp_dir = TempDir::new("test_writes_backlog_on_start").unwrap();
+        let backlog_path = temp_dir.path().join("audit_backlog.jsonl");
+        Backlog {
+            path: Some(backlog_path.clone()),
+        }
+        .append(&entry("01234-ABCDE", AuditEventType::ProposalExpired))
+        .unwrap();
+
+        let store = Arc::new(yaml_store(&temp_dir));
+        let _writer = AuditLogWriter::start(store.clone(), Some(backlog_path.clone())).unwrap();
+
+        let entries = wait_for_entries(&*store, 1);
+        assert_eq!(entries[0].event_type(), &AuditEventType::ProposalExpired);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !fs::read_to_string(&backlog_path).unwrap().is_empty() {
+            assert!(Instant::now() < deadline, "Backlog was not cleared");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn yaml_store(temp_dir: &TempDir) -> YamlAdminServiceStore {
+        let path = |file: &str| temp_dir.path().join(file).to_str().unwrap().to_string();
+        YamlAdminServiceStore::new(path("circuits.yaml"), path("circuit_proposals.yaml")).unwrap()
+    }
+
+    fn entry(circuit_id: &str, event_type: AuditEventType) -> AuditEntry {
+        AuditEntryBuilder::new()
+            .with_circuit_id(circuit_id)
+            .with_event_type(&event_type)
+            .with_timestamp(1)
+            .with_circuit_hash("hash")
+            .build()
+            .unwrap()
+    }
+
+    fn wait_for_entries(store: &dyn AdminServiceStore, count: usize) -> Vec<AuditEntry> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let entries = store
+                .list_audit_entries(
+                    &[AuditPredicate::CircuitIdEq("01234-ABCDE".into())],
+                    0,
+                    None,
+                )
+                .unwrap()
+                .collect::<Vec<_>>();
+            if entries.len() >= count {
+                return entries;
+            }
+            assert!(Instant::now() < deadline, "Entries were not written");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
@@ -17,14 +17,20 @@ use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt;
 use std::ops::Bound;
-use std::time::SystemTime;
+#[cfg(feature = "admin-service-event-store")]
+use std::time::UNIX_EPOCH;
+use std::time::{Duration, SystemTime};
 
+#[cfg(feature = "admin-service-event-store")]
+use crate::storage::sets::diesel::DatabaseSetItem;
 use crate::storage::sets::DurableOrderedSet;
+#[cfg(feature = "admin-service-event-store")]
+use crate::storage::sets::DurableSetError;
 
 use super::messages::AdminServiceEvent;
 
 /// A simple entry for AdminServiceEvent values, marked with a timestamp
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct EventEntry {
     timestamp: SystemTime,
     event: AdminServiceEvent,
@@ -48,6 +54,41 @@ impl std::borrow::Borrow<SystemTime> for EventEntry {
     }
 }
 
+/// Event entries are stored in the database as JSON, keyed by the number of nanoseconds between
+/// the Unix epoch and their timestamp.
+#[cfg(feature = "admin-service-event-store")]
+impl DatabaseSetItem<SystemTime> for EventEntry {
+    fn index_key(index: &SystemTime) -> Result<i64, DurableSetError> {
+        let nanos = index
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| {
+                DurableSetError::with_source(
+                    "Event timestamp is before the Unix epoch",
+                    Box::new(err),
+                )
+            })?
+            .as_nanos();
+        if nanos > std::i64::MAX as u128 {
+            return Err(DurableSetError::new(
+                "Event timestamp is too large to be stored",
+            ));
+        }
+        Ok(nanos as i64)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, DurableSetError> {
+        serde_json::to_vec(self).map_err(|err| {
+            DurableSetError::with_source("Unable to serialize event entry", Box::new(err))
+        })
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DurableSetError> {
+        serde_json::from_slice(bytes).map_err(|err| {
+            DurableSetError::with_source("Unable to deserialize event entry", Box::new(err))
+        })
+    }
+}
+
 /// A Mailbox stores all admin services events that have occurred, ordered by a timestamp generated
 /// upon addition to the mailbox.
 ///
@@ -65,6 +106,9 @@ impl Mailbox {
 
     /// Add an event to the mailbox.  Returns the recorded event time and a copy of the event.
     ///
+    /// The recorded time is always later than that of the previously added event, so events added
+    /// within the same clock tick are not mistaken for one another.
+    ///
     /// # Errors
     ///
     /// Returns a MailboxError if there is an issue with the underlying storage set.
@@ -72,10 +116,16 @@ impl Mailbox {
         &mut self,
         event: AdminServiceEvent,
     ) -> Result<(SystemTime, AdminServiceEvent), MailboxError> {
-        let entry = EventEntry {
-            timestamp: SystemTime::now(),
-            event,
-        };
+        let last = self.durable_set.last().map_err(|err| {
+            MailboxError::with_source("Unable to read last event from storage", Box::new(err))
+        })?;
+        let mut timestamp = SystemTime::now();
+        if let Some(last) = last {
+            if timestamp <= last.timestamp {
+                timestamp = last.timestamp + Duration::from_nanos(1);
+            }
+        }
+        let entry = EventEntry { timestamp, event };
         self.durable_set.add(entry.clone()).map_err(|err| {
             MailboxError::with_source("Unable to add event to storage", Box::new(err))
         })?;
@@ -211,7 +261,9 @@ impl fmt::Display for MailboxError {
 
 #[cfg(test)]
 mod tests {
-    use crate::admin::messages::{self, AdminServiceEvent, CircuitProposal, ProposalType};
+    use crate::admin::messages::{
+        self, AdminServiceEvent, CircuitProposal, ProposalType, SignerQuorum, VotingPolicy,
+    };
     use crate::storage::sets::mem::DurableBTreeSet;
 
     use super::*;
@@ -316,6 +368,80 @@ mod tests {
         );
     }
 
+    /// Add events to a mailbox backed by a SQLite database, then create a new mailbox with the
+    /// same database, as would happen after a restart. Ensure that the events are still available
+    /// from the new mailbox.
+    #[cfg(all(feature = "admin-service-event-store", feature = "sqlite"))]
+    #[test]
+    fn test_database_mailbox_survives_restart() {
+        use diesel::r2d2::{ConnectionManager, Pool};
+        use diesel::sqlite::SqliteConnection;
+
+        use crate::storage::sets::diesel::{
+            migrations::run_sqlite_migrations, DieselDurableOrderedSet,
+        };
+
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .expect("Failed to build connection pool");
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        let mut mailbox = Mailbox::new(Box::new(DieselDurableOrderedSet::<
+            _,
+            EventEntry,
+            SystemTime,
+        >::new(pool.clone(), "admin_events")));
+        mailbox
+            .add(make_event("circuit_one", "default"))
+            .expect("Unable to add event");
+        let (entry_time, _) = mailbox
+            .add(make_event("gameroom_one", "gameroom"))
+            .expect("Unable to add event");
+        drop(mailbox);
+
+        let mailbox = Mailbox::new(Box::new(
+            DieselDurableOrderedSet::<_, EventEntry, SystemTime>::new(pool, "admin_events"),
+        ));
+        assert_eq!(
+            vec![(entry_time, make_event("gameroom_one", "gameroom")),],
+            mailbox
+                .iter_since(entry_time)
+                .expect("Unable to create an iterator")
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            2,
+            mailbox
+                .iter_since(SystemTime::UNIX_EPOCH)
+                .expect("Unable to create an iterator")
+                .count(),
+        );
+    }
+
+    /// Add an event to a mailbox whose last event has a timestamp in the future and ensure that
+    /// the new event is recorded after it rather than colliding with or preceding it.
+    #[test]
+    fn test_add_after_last_event() {
+        let mut durable_set: Box<dyn DurableOrderedSet<EventEntry, SystemTime>> =
+            DurableBTreeSet::new_boxed();
+        let last_timestamp = SystemTime::now() + Duration::from_secs(60);
+        durable_set
+            .add(EventEntry {
+                timestamp: last_timestamp,
+                event: make_event("circuit_one", "default"),
+            })
+            .expect("Unable to add event entry");
+        let mut mailbox = Mailbox::new(durable_set);
+
+        let (timestamp, _) = mailbox
+            .add(make_event("circuit_two", "default"))
+            .expect("Unable to add event");
+
+        assert!(timestamp > last_timestamp);
+    }
+
     fn make_event(circuit_id: &str, event_type: &str) -> AdminServiceEvent {
         AdminServiceEvent::ProposalSubmitted(CircuitProposal {
             proposal_type: ProposalType::Create,
@@ -336,6 +462,9 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "another-node".into(),
+            expires_at: None,
+            voting_policy: VotingPolicy::Unanimous,
+            signer_quorum: SignerQuorum::default(),
         })
     }
 }
@@ -284,6 +284,15 @@ pub struct CircuitProposal {
     #[serde(deserialize_with = "deserialize_hex")]
     pub requester: Vec<u8>,
     pub requester_node_id: String,
+    /// The time after which the proposal expires, in seconds since the Unix epoch
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// The policy that decides whether the proposal is accepted or rejected
+    #[serde(default)]
+    pub voting_policy: VotingPolicy,
+    /// The number of keys of each member node that must sign the node's vote
+    #[serde(default)]
+    pub signer_quorum: SignerQuorum,
 }
 
 impl CircuitProposal {
@@ -315,6 +324,12 @@ impl CircuitProposal {
             votes,
             requester: proto.take_requester(),
             requester_node_id: proto.take_requester_node_id(),
+            expires_at: match proto.get_expires_at() {
+                0 => None,
+                expires_at => Some(expires_at),
+            },
+            voting_policy: VotingPolicy::from_proto(proto.take_voting_policy()),
+            signer_quorum: SignerQuorum::from_proto(proto.take_signer_quorum()),
         })
     }
 
@@ -343,11 +358,177 @@ impl CircuitProposal {
         proposal.set_votes(RepeatedField::from_vec(votes));
         proposal.set_requester(self.requester.to_vec());
         proposal.set_requester_node_id(self.requester_node_id);
+        proposal.set_expires_at(self.expires_at.unwrap_or(0));
+        proposal.set_voting_policy(self.voting_policy.into_proto());
+        proposal.set_signer_quorum(self.signer_quorum.into_proto());
 
         Ok(proposal)
     }
 }
 
+/// The policy that decides whether a circuit proposal is accepted or rejected, based on the votes
+/// of its proposed members. The requester's node is counted as having voted to accept.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "policy_type")]
+pub enum VotingPolicy {
+    /// Every member must accept; a single rejection rejects the proposal.
+    Unanimous,
+    /// At least `threshold` members must accept.
+    Threshold { threshold: u32 },
+    /// The weights of the accepting members must add up to at least `threshold`.
+    Weighted {
+        weights: Vec<NodeWeight>,
+        threshold: u32,
+    },
+    /// Every member in `approvers` must accept; a rejection from one of them rejects the proposal.
+    RequiredApprovers { approvers: Vec<String> },
+}
+
+impl Default for VotingPolicy {
+    fn default() -> Self {
+        VotingPolicy::Unanimous
+    }
+}
+
+impl VotingPolicy {
+    pub fn from_proto(mut proto: admin::VotingPolicy) -> Self {
+        match proto.get_policy_type() {
+            admin::VotingPolicy_PolicyType::UNANIMOUS => VotingPolicy::Unanimous,
+            admin::VotingPolicy_PolicyType::THRESHOLD => VotingPolicy::Threshold {
+                threshold: proto.get_threshold(),
+            },
+            admin::VotingPolicy_PolicyType::WEIGHTED => VotingPolicy::Weighted {
+                weights: proto
+                    .take_weights()
+                    .into_iter()
+                    .map(NodeWeight::from_proto)
+                    .collect(),
+                threshold: proto.get_threshold(),
+            },
+            admin::VotingPolicy_PolicyType::REQUIRED_APPROVERS => VotingPolicy::RequiredApprovers {
+                approvers: proto.take_required_approvers().into(),
+            },
+        }
+    }
+
+    pub fn into_proto(self) -> admin::VotingPolicy {
+        let mut proto = admin::VotingPolicy::new();
+        match self {
+            VotingPolicy::Unanimous => {
+                proto.set_policy_type(admin::VotingPolicy_PolicyType::UNANIMOUS);
+            }
+            VotingPolicy::Threshold { threshold } => {
+                proto.set_policy_type(admin::VotingPolicy_PolicyType::THRESHOLD);
+                proto.set_threshold(threshold);
+            }
+            VotingPolicy::Weighted { weights, threshold } => {
+                proto.set_policy_type(admin::VotingPolicy_PolicyType::WEIGHTED);
+                proto.set_weights(RepeatedField::from_vec(
+                    weights.into_iter().map(NodeWeight::into_proto).collect(),
+                ));
+                proto.set_threshold(threshold);
+            }
+            VotingPolicy::RequiredApprovers { approvers } => {
+                proto.set_policy_type(admin::VotingPolicy_PolicyType::REQUIRED_APPROVERS);
+                proto.set_required_approvers(RepeatedField::from_vec(approvers));
+            }
+        }
+
+        proto
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct NodeWeight {
+    pub node_id: String,
+    pub weight: u32,
+}
+
+impl NodeWeight {
+    pub fn from_proto(mut proto: admin::VotingPolicy_NodeWeight) -> Self {
+        Self {
+            node_id: proto.take_node_id(),
+            weight: proto.get_weight(),
+        }
+    }
+
+    pub fn into_proto(self) -> admin::VotingPolicy_NodeWeight {
+        let mut proto = admin::VotingPolicy_NodeWeight::new();
+        proto.set_node_id(self.node_id);
+        proto.set_weight(self.weight);
+
+        proto
+    }
+}
+
+/// The number of distinct keys registered for a member node that must vote to accept before the
+/// node is counted as having accepted. A rejection signed by any one of the node's keys rejects on
+/// behalf of the node.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SignerQuorum {
+    /// The number of signers required for members without their own entry in `node_signers`
+    pub required_signers: u32,
+    pub node_signers: Vec<NodeSigners>,
+}
+
+impl Default for SignerQuorum {
+    fn default() -> Self {
+        SignerQuorum {
+            required_signers: 1,
+            node_signers: vec![],
+        }
+    }
+}
+
+impl SignerQuorum {
+    pub fn from_proto(mut proto: admin::SignerQuorum) -> Self {
+        Self {
+            required_signers: std::cmp::max(proto.get_required_signers(), 1),
+            node_signers: proto
+                .take_node_signers()
+                .into_iter()
+                .map(NodeSigners::from_proto)
+                .collect(),
+        }
+    }
+
+    pub fn into_proto(self) -> admin::SignerQuorum {
+        let mut proto = admin::SignerQuorum::new();
+        proto.set_required_signers(self.required_signers);
+        proto.set_node_signers(RepeatedField::from_vec(
+            self.node_signers
+                .into_iter()
+                .map(NodeSigners::into_proto)
+                .collect(),
+        ));
+
+        proto
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct NodeSigners {
+    pub node_id: String,
+    pub required_signers: u32,
+}
+
+impl NodeSigners {
+    pub fn from_proto(mut proto: admin::SignerQuorum_NodeSigners) -> Self {
+        Self {
+            node_id: proto.take_node_id(),
+            required_signers: proto.get_required_signers(),
+        }
+    }
+
+    pub fn into_proto(self) -> admin::SignerQuorum_NodeSigners {
+        let mut proto = admin::SignerQuorum_NodeSigners::new();
+        proto.set_node_id(self.node_id);
+        proto.set_required_signers(self.required_signers);
+
+        proto
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum ProposalType {
     Create,
@@ -393,6 +574,67 @@ impl CircuitProposalVote {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct CircuitProposalWithdraw {
+    pub circuit_id: String,
+    pub circuit_hash: String,
+}
+
+impl CircuitProposalWithdraw {
+    pub fn from_proto(mut proto: admin::CircuitProposalWithdraw) -> Self {
+        CircuitProposalWithdraw {
+            circuit_id: proto.take_circuit_id(),
+            circuit_hash: proto.take_circuit_hash(),
+        }
+    }
+
+    pub fn into_proto(self) -> admin::CircuitProposalWithdraw {
+        let mut withdraw = admin::CircuitProposalWithdraw::new();
+        withdraw.set_circuit_id(self.circuit_id);
+        withdraw.set_circuit_hash(self.circuit_hash);
+        withdraw
+    }
+}
+
+/// The result of validating a circuit management payload on one of the members of the circuit.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct NodeValidation {
+    pub node_id: String,
+    pub status: NodeValidationStatus,
+    /// Why the payload is invalid, or why the node could not validate it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl NodeValidation {
+    pub fn from_proto(mut proto: admin::ProposalValidationResponse) -> Self {
+        let error = proto.take_error();
+        if error.is_empty() {
+            NodeValidation {
+                node_id: proto.take_node_id(),
+                status: NodeValidationStatus::Valid,
+                error: None,
+            }
+        } else {
+            NodeValidation {
+                node_id: proto.take_node_id(),
+                status: NodeValidationStatus::Invalid,
+                error: Some(error),
+            }
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum NodeValidationStatus {
+    /// The node found the payload valid
+    Valid,
+    /// The node found the payload invalid
+    Invalid,
+    /// The node could not be asked to validate the payload, or did not respond in time
+    Unreachable,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct VoteRecord {
     #[serde(serialize_with = "as_hex")]
@@ -447,6 +689,7 @@ pub enum AdminServiceEvent {
     ProposalVote((CircuitProposal, Vec<u8>)),
     ProposalAccepted((CircuitProposal, Vec<u8>)),
     ProposalRejected((CircuitProposal, Vec<u8>)),
+    ProposalExpired(CircuitProposal),
     CircuitReady(CircuitProposal),
 }
 
@@ -457,6 +700,7 @@ impl AdminServiceEvent {
             AdminServiceEvent::ProposalVote((proposal, _)) => proposal,
             AdminServiceEvent::ProposalAccepted((proposal, _)) => proposal,
             AdminServiceEvent::ProposalRejected((proposal, _)) => proposal,
+            AdminServiceEvent::ProposalExpired(proposal) => proposal,
             AdminServiceEvent::CircuitReady(proposal) => proposal,
         }
     }
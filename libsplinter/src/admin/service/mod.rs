@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "admin-audit-log")]
+mod audit_writer;
 mod consensus;
 pub(crate) mod error;
 mod mailbox;
@@ -19,17 +21,29 @@ pub(crate) mod messages;
 pub(super) mod open_proposals;
 pub(super) mod proposal_store;
 mod shared;
+mod voting;
 
 use std::any::Any;
 #[cfg(feature = "service-arg-validation")]
 use std::collections::HashMap;
-use std::sync::{mpsc::channel, Arc, Mutex};
+#[cfg(feature = "admin-proposal-validation")]
+use std::collections::HashSet;
+use std::sync::{
+    mpsc::{channel, RecvTimeoutError, Sender},
+    Arc, Mutex,
+};
 use std::thread;
+#[cfg(feature = "admin-proposal-validation")]
+use std::time::Instant;
 use std::time::{Duration, SystemTime};
 
 use openssl::hash::{hash, MessageDigest};
 use protobuf::{self, Message};
+#[cfg(feature = "admin-proposal-validation")]
+use uuid::Uuid;
 
+#[cfg(feature = "admin-audit-log")]
+use crate::admin::store::{AdminServiceStore, AuditEntry, AuditPredicate};
 use crate::circuit::SplinterState;
 use crate::consensus::Proposal;
 use crate::hex::to_hex;
@@ -38,7 +52,8 @@ use crate::orchestrator::{ServiceDefinition, ServiceOrchestrator};
 use crate::peer::{PeerManagerConnector, PeerManagerNotification};
 use crate::protocol::{ADMIN_PROTOCOL_VERSION, ADMIN_SERVICE_PROTOCOL_MIN};
 use crate::protos::admin::{
-    AdminMessage, AdminMessage_Type, CircuitManagementPayload, ServiceProtocolVersionResponse,
+    AdminMessage, AdminMessage_Type, CircuitManagementPayload, ProposalValidationResponse,
+    ServiceProtocolVersionResponse,
 };
 #[cfg(feature = "registry")]
 use crate::registry::RegistryReader;
@@ -49,18 +64,27 @@ use crate::service::{
     Service, ServiceMessageContext, ServiceNetworkRegistry,
 };
 use crate::signing::SignatureVerifier;
+#[cfg(feature = "admin-service-event-store")]
+use crate::storage::sets::DurableOrderedSet;
 
 use self::consensus::AdminConsensusManager;
 use self::error::{AdminError, Sha256Error};
+#[cfg(feature = "admin-service-event-store")]
+use self::mailbox::Mailbox;
 use self::proposal_store::{AdminServiceProposals, ProposalStore};
 use self::shared::AdminServiceShared;
 
 pub use self::error::AdminKeyVerifierError;
 pub use self::error::AdminServiceError;
 pub use self::error::AdminSubscriberError;
+#[cfg(feature = "admin-service-event-store")]
+pub use self::mailbox::EventEntry;
 pub use self::shared::AdminServiceStatus;
 
 const DEFAULT_COORDINATOR_TIMEOUT: u64 = 30; // 30 seconds
+const PROPOSAL_EXPIRY_CHECK_INTERVAL: u64 = 5; // 5 seconds
+#[cfg(feature = "admin-proposal-validation")]
+const PROPOSAL_VALIDATION_TIMEOUT: u64 = 10; // 10 seconds
 
 pub trait AdminServiceEventSubscriber: Send {
     fn handle_event(
@@ -68,20 +92,37 @@ pub trait AdminServiceEventSubscriber: Send {
         admin_service_event: &messages::AdminServiceEvent,
         timestamp: &SystemTime,
     ) -> Result<(), AdminSubscriberError>;
+
+    /// Returns whether the subscriber will no longer handle events, so that it can be removed
+    /// before the next event is broadcast.
+    fn is_closed(&self) -> bool {
+        false
+    }
 }
 
+/// The event type used to subscribe to admin service events for every circuit management type.
+pub const ALL_EVENT_TYPES: &str = "*";
+
 pub trait AdminCommands: Send + Sync {
     fn submit_circuit_change(
         &self,
         circuit_change: CircuitManagementPayload,
     ) -> Result<(), AdminServiceError>;
 
+    /// Adds a subscriber for the events of proposals with the given circuit management type.
+    /// Subscribers added with the event type `ALL_EVENT_TYPES` receive the events of every
+    /// proposal.
     fn add_event_subscriber(
         &self,
         event_type: &str,
         subscriber: Box<dyn AdminServiceEventSubscriber>,
     ) -> Result<(), AdminServiceError>;
 
+    /// Removes the event subscribers that report that they are closed.
+    fn remove_closed_event_subscribers(&self) -> Result<(), AdminServiceError>;
+
+    /// Returns the events since the given time for proposals with the given circuit management
+    /// type, or for every proposal if the event type is `ALL_EVENT_TYPES`.
     fn get_events_since(
         &self,
         since_timestamp: &SystemTime,
@@ -90,6 +131,18 @@ pub trait AdminCommands: Send + Sync {
 
     fn admin_service_status(&self) -> Result<AdminServiceStatus, AdminServiceError>;
 
+    /// Validates a circuit management payload on this node and, where possible, on the other
+    /// members of the circuit, without submitting it. Returns the result of each member.
+    #[cfg(feature = "admin-proposal-validation")]
+    fn validate_circuit_change(
+        &self,
+        circuit_change: CircuitManagementPayload,
+    ) -> Result<Vec<messages::NodeValidation>, AdminServiceError>;
+
+    /// Returns the audit log entries of the given circuit's proposals, oldest first.
+    #[cfg(feature = "admin-audit-log")]
+    fn circuit_history(&self, circuit_id: &str) -> Result<Vec<AuditEntry>, AdminServiceError>;
+
     fn clone_boxed(&self) -> Box<dyn AdminCommands>;
 }
 
@@ -151,6 +204,7 @@ pub struct AdminService {
     /// The coordinator timeout for the two-phase commit consensus engine
     coordinator_timeout: Duration,
     consensus: Option<AdminConsensusManager>,
+    proposal_expiry_checker: Option<ProposalExpiryChecker>,
     peer_connector: PeerManagerConnector,
 }
 
@@ -201,6 +255,7 @@ impl AdminService {
             orchestrator,
             coordinator_timeout,
             consensus: None,
+            proposal_expiry_checker: None,
             peer_connector,
         };
 
@@ -243,6 +298,36 @@ impl AdminService {
         AdminServiceProposals::new(&self.admin_service_shared)
     }
 
+    /// Replaces the in-memory store of the admin service's event mailbox, which is used to replay
+    /// past events to subscribers. Events that have already been stored in memory are not copied
+    /// to the new store.
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn set_event_store(
+        &self,
+        event_store: Box<dyn DurableOrderedSet<EventEntry, SystemTime>>,
+    ) -> Result<(), ServiceError> {
+        self.admin_service_shared
+            .lock()
+            .map_err(|_| ServiceError::PoisonedLock("the admin shared lock was poisoned".into()))?
+            .set_event_mailbox(Mailbox::new(event_store));
+        Ok(())
+    }
+
+    /// Sets the store used to keep the audit log of circuit proposals. Every submission, vote,
+    /// decision, withdrawal and expiry committed after this call is appended to the log by a
+    /// background writer.
+    #[cfg(feature = "admin-audit-log")]
+    pub fn set_audit_store(
+        &self,
+        audit_store: Box<dyn AdminServiceStore>,
+    ) -> Result<(), ServiceError> {
+        self.admin_service_shared
+            .lock()
+            .map_err(|_| ServiceError::PoisonedLock("the admin shared lock was poisoned".into()))?
+            .set_audit_store(Arc::from(audit_store))
+            .map_err(|err| ServiceError::UnableToCreate(Box::new(err)))
+    }
+
     /// On restart of a splinter node, all services that this node should run on the existing
     /// circuits should be initialized using the service orchestrator. This may not include all
     /// services if they are not supported locally. It is expected that some services will be
@@ -429,6 +514,14 @@ impl Service for AdminService {
             })?
             .change_status();
 
+        self.proposal_expiry_checker = Some(
+            ProposalExpiryChecker::start(
+                self.admin_service_shared.clone(),
+                Duration::from_secs(PROPOSAL_EXPIRY_CHECK_INTERVAL),
+            )
+            .map_err(|err| ServiceStartError::Internal(Box::new(err)))?,
+        );
+
         Ok(())
     }
 
@@ -438,6 +531,10 @@ impl Service for AdminService {
     ) -> Result<(), ServiceStopError> {
         service_registry.disconnect(&self.service_id)?;
 
+        if let Some(proposal_expiry_checker) = self.proposal_expiry_checker.take() {
+            proposal_expiry_checker.shutdown();
+        }
+
         // Shutdown consensus
         self.consensus
             .take()
@@ -547,6 +644,66 @@ impl Service for AdminService {
                     .add_ready_member(circuit_id, member_node_id.into())
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
             }
+            AdminMessage_Type::PROPOSAL_VALIDATION_REQUEST => {
+                let request = admin_message.get_validation_request();
+
+                let admin_service_shared = self.admin_service_shared.lock().map_err(|_| {
+                    ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?;
+
+                let mut response = ProposalValidationResponse::new();
+                response.set_correlation_id(request.get_correlation_id().to_string());
+                response.set_node_id(admin_service_shared.node_id().to_string());
+                if let Err(err) =
+                    admin_service_shared.validate_payload(request.get_circuit_payload())
+                {
+                    response.set_error(shared::validation_error_message(err));
+                }
+
+                let mut msg = AdminMessage::new();
+                msg.set_message_type(AdminMessage_Type::PROPOSAL_VALIDATION_RESPONSE);
+                msg.set_validation_response(response);
+                let envelope_bytes = msg
+                    .write_to_bytes()
+                    .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
+                admin_service_shared
+                    .network_sender()
+                    .as_ref()
+                    .ok_or_else(|| ServiceError::NotStarted)?
+                    .send(&message_context.sender.to_string(), &envelope_bytes)
+                    .map_err(|err| ServiceError::UnableToSendMessage(Box::new(err)))
+            }
+            AdminMessage_Type::PROPOSAL_VALIDATION_RESPONSE => {
+                let response = admin_message.get_validation_response();
+
+                if admin_service_id(response.get_node_id()) != message_context.sender {
+                    warn!(
+                        "Ignoring validation response for {} sent by {}",
+                        response.get_node_id(),
+                        message_context.sender
+                    );
+                    return Ok(());
+                }
+
+                #[cfg(feature = "admin-proposal-validation")]
+                {
+                    self.admin_service_shared
+                        .lock()
+                        .map_err(|_| {
+                            ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                        })?
+                        .handle_validation_response(response.clone());
+                }
+
+                #[cfg(not(feature = "admin-proposal-validation"))]
+                debug!(
+                    "Ignoring validation response from {}",
+                    response.get_node_id()
+                );
+
+                Ok(())
+            }
             AdminMessage_Type::SERVICE_PROTOCOL_VERSION_REQUEST => {
                 let request = admin_message.get_protocol_request();
                 let protocol =
@@ -630,6 +787,54 @@ fn handle_peer_manager_notification(
     }
 }
 
+/// Periodically removes expired circuit proposals from the admin service until shut down.
+struct ProposalExpiryChecker {
+    shutdown_sender: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl ProposalExpiryChecker {
+    fn start(
+        admin_shared: Arc<Mutex<AdminServiceShared>>,
+        interval: Duration,
+    ) -> Result<Self, std::io::Error> {
+        let (shutdown_sender, shutdown_receiver) = channel();
+        let join_handle = thread::Builder::new()
+            .name("Admin Proposal Expiry".into())
+            .spawn(move || loop {
+                match shutdown_receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    _ => break,
+                }
+
+                match admin_shared.lock() {
+                    Ok(mut admin_shared) => {
+                        if let Err(err) = admin_shared.expire_proposals(SystemTime::now()) {
+                            error!("Unable to expire circuit proposals: {}", err);
+                        }
+                    }
+                    Err(_) => {
+                        error!("the admin shared lock was poisoned");
+                        break;
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            shutdown_sender,
+            join_handle,
+        })
+    }
+
+    fn shutdown(self) {
+        // the thread also exits if the receiver is disconnected, so a failed send can be ignored
+        let _ = self.shutdown_sender.send(());
+        if self.join_handle.join().is_err() {
+            error!("Admin proposal expiry thread panicked");
+        }
+    }
+}
+
 #[derive(Clone)]
 struct AdminServiceCommands {
     shared: Arc<Mutex<AdminServiceShared>>,
@@ -665,6 +870,15 @@ impl AdminCommands for AdminServiceCommands {
             })
     }
 
+    fn remove_closed_event_subscribers(&self) -> Result<(), AdminServiceError> {
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .remove_closed_event_subscribers();
+
+        Ok(())
+    }
+
     fn get_events_since(
         &self,
         since_timestamp: &SystemTime,
@@ -687,6 +901,77 @@ impl AdminCommands for AdminServiceCommands {
             .admin_service_status())
     }
 
+    #[cfg(feature = "admin-proposal-validation")]
+    fn validate_circuit_change(
+        &self,
+        circuit_change: CircuitManagementPayload,
+    ) -> Result<Vec<messages::NodeValidation>, AdminServiceError> {
+        let correlation_id = Uuid::new_v4().to_string();
+        let (sender, receiver) = channel();
+        let (mut results, requested_members) = self
+            .shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .request_payload_validation(&correlation_id, &circuit_change, sender)?;
+
+        // Wait for the other members' results without holding the lock, so that their responses
+        // can be handled
+        let mut pending_members = requested_members.into_iter().collect::<HashSet<_>>();
+        let deadline = Instant::now() + Duration::from_secs(PROPOSAL_VALIDATION_TIMEOUT);
+        while !pending_members.is_empty() {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match receiver.recv_timeout(timeout) {
+                Ok(result) => {
+                    if pending_members.remove(&result.node_id) {
+                        results.push(result);
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .remove_pending_validation(&correlation_id);
+
+        results.extend(
+            pending_members
+                .into_iter()
+                .map(|node_id| messages::NodeValidation {
+                    node_id,
+                    status: messages::NodeValidationStatus::Unreachable,
+                    error: Some(format!(
+                        "No response within {} seconds",
+                        PROPOSAL_VALIDATION_TIMEOUT
+                    )),
+                }),
+        );
+
+        Ok(results)
+    }
+
+    #[cfg(feature = "admin-audit-log")]
+    fn circuit_history(&self, circuit_id: &str) -> Result<Vec<AuditEntry>, AdminServiceError> {
+        // Release the lock before reading from the store
+        let audit_store = self
+            .shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .audit_store()
+            .ok_or_else(|| AdminServiceError::general_error("The audit log is not configured"))?;
+
+        audit_store
+            .list_audit_entries(&[AuditPredicate::CircuitIdEq(circuit_id.into())], 0, None)
+            .map(|entries| entries.collect())
+            .map_err(|err| {
+                AdminServiceError::general_error(&format!(
+                    "Unable to list audit log entries: {}",
+                    err
+                ))
+            })
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminCommands> {
         Box::new(self.clone())
     }
@@ -720,7 +1005,7 @@ fn supported_protocol_version(min: u32, max: u32) -> u32 {
             min
         );
         return 0;
-    } else if max < ADMIN_PROTOCOL_VERSION {
+    } else if max < ADMIN_SERVICE_PROTOCOL_MIN {
         info!(
             "Request requires older version than van be provided: {}",
             max
@@ -728,7 +1013,9 @@ fn supported_protocol_version(min: u32, max: u32) -> u32 {
         return 0;
     }
 
-    ADMIN_PROTOCOL_VERSION
+    // Agree on the newest version both sides support, so that older admin services can still
+    // take part in circuits that do not rely on newer features
+    std::cmp::min(max, ADMIN_PROTOCOL_VERSION)
 }
 
 #[cfg(test)]
@@ -1040,4 +1327,20 @@ mod tests {
             Ok(true)
         }
     }
+
+    /// Test that the highest protocol version supported by both sides is agreed on, and that no
+    /// version is agreed on when the ranges do not overlap.
+    #[test]
+    fn test_supported_protocol_version() {
+        assert_eq!(
+            ADMIN_PROTOCOL_VERSION,
+            supported_protocol_version(ADMIN_SERVICE_PROTOCOL_MIN, ADMIN_PROTOCOL_VERSION + 1)
+        );
+        assert_eq!(1, supported_protocol_version(1, 1));
+        assert_eq!(
+            0,
+            supported_protocol_version(ADMIN_PROTOCOL_VERSION + 1, u32::MAX)
+        );
+        assert_eq!(0, supported_protocol_version(0, 0));
+    }
 }
@@ -14,6 +14,9 @@
 
 use std::sync::{Arc, Mutex};
 
+#[cfg(feature = "admin-audit-log")]
+use crate::admin::store::{AuditEventType, AuditPredicate};
+
 use super::messages::CircuitProposal;
 use super::shared::AdminServiceShared;
 
@@ -50,6 +53,22 @@ pub trait ProposalStore: Send + Sync + Clone {
     fn proposals(&self, filters: Vec<ProposalFilter>) -> Result<ProposalIter, ProposalStoreError>;
 
     fn proposal(&self, circuit_id: &str) -> Result<Option<CircuitProposal>, ProposalStoreError>;
+
+    /// Return an iterator over a page of the proposals that have been closed by the given event,
+    /// such as `ProposalAccepted` or `ProposalRejected`, as recorded in the audit log. Each
+    /// proposal is returned as it was when it was closed. Proposal filters may optionally be
+    /// provided.
+    ///
+    /// The first `offset` matching proposals are skipped, and at most `limit` are returned; the
+    /// iterator's total is the number of all matching proposals.
+    #[cfg(feature = "admin-audit-log")]
+    fn closed_proposals(
+        &self,
+        closed_by: AuditEventType,
+        filters: Vec<ProposalFilter>,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<ProposalIter, ProposalStoreError>;
 }
 
 #[derive(Debug)]
@@ -149,6 +168,45 @@ impl ProposalStore for AdminServiceProposals {
             })
             .transpose()
     }
+
+    #[cfg(feature = "admin-audit-log")]
+    fn closed_proposals(
+        &self,
+        closed_by: AuditEventType,
+        filters: Vec<ProposalFilter>,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<ProposalIter, ProposalStoreError> {
+        let audit_store = self
+            .shared
+            .lock()
+            .map_err(|_| ProposalStoreError::new("Admin shared lock was lock poisoned"))?
+            .audit_store()
+            .ok_or_else(|| ProposalStoreError::new("The audit log is not configured"))?;
+
+        // The filters are applied by the audit store, so only the requested page is loaded
+        let mut predicates = vec![AuditPredicate::EventTypeEq(closed_by)];
+        predicates.extend(filters.into_iter().map(|filter| match filter {
+            ProposalFilter::WithManagementType(management_type) => {
+                AuditPredicate::ManagementTypeEq(management_type)
+            }
+            ProposalFilter::WithMember(member) => AuditPredicate::MembersInclude(vec![member]),
+        }));
+
+        let total = audit_store
+            .count_audit_entries(&predicates)
+            .map_err(|err| {
+                ProposalStoreError::new(&format!("Unable to count audit log entries: {}", err))
+            })?;
+        let proposals = audit_store
+            .list_audit_entries(&predicates, offset, limit)
+            .map_err(|err| {
+                ProposalStoreError::new(&format!("Unable to list audit log entries: {}", err))
+            })?
+            .filter_map(|entry| entry.into_proposal());
+
+        Ok(ProposalIter::new(Box::new(proposals), total))
+    }
 }
 
 /// An iterator over CircuitProposals, with a well-known count of values.
@@ -14,14 +14,22 @@
 
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+#[cfg(feature = "admin-audit-log")]
+use std::io;
 use std::iter::FromIterator;
 use std::path::Path;
+#[cfg(feature = "admin-audit-log")]
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use protobuf::{Message, RepeatedField};
 use std::sync::mpsc::Sender;
 
+#[cfg(feature = "admin-audit-log")]
+use crate::admin::store::{
+    AdminServiceStore, AuditEntryBuilder, AuditEventType, Vote as StoreVote,
+};
 use crate::circuit::SplinterState;
 use crate::circuit::{
     service::SplinterNode as StateNode,
@@ -34,17 +42,23 @@ use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
 use crate::orchestrator::{ServiceDefinition, ServiceOrchestrator};
 use crate::peer::{PeerManagerConnector, PeerRef};
-use crate::protocol::{ADMIN_PROTOCOL_VERSION, ADMIN_SERVICE_PROTOCOL_MIN};
+#[cfg(feature = "admin-proposal-validation")]
+use crate::protocol::ADMIN_VALIDATE_PROPOSAL_PROTOCOL_MIN;
+use crate::protocol::{
+    ADMIN_PROPOSAL_RULES_PROTOCOL_MIN, ADMIN_PROTOCOL_VERSION, ADMIN_SERVICE_PROTOCOL_MIN,
+};
 #[cfg(feature = "service-arg-validation")]
 use crate::protos::admin::SplinterService;
 use crate::protos::admin::{
-    AdminMessage, AdminMessage_Type, Circuit, CircuitManagementPayload,
+    AdminMessage, AdminMessage_Type, Circuit, CircuitCreateRequest, CircuitManagementPayload,
     CircuitManagementPayload_Action, CircuitManagementPayload_Header, CircuitProposal,
-    CircuitProposalVote, CircuitProposalVote_Vote, CircuitProposal_ProposalType,
-    CircuitProposal_VoteRecord, Circuit_AuthorizationType, Circuit_DurabilityType,
-    Circuit_PersistenceType, Circuit_RouteType, MemberReady, ServiceProtocolVersionRequest,
-    SplinterNode,
+    CircuitProposalVote, CircuitProposalVote_Vote, CircuitProposalWithdraw,
+    CircuitProposal_ProposalType, CircuitProposal_VoteRecord, Circuit_AuthorizationType,
+    Circuit_DurabilityType, Circuit_PersistenceType, Circuit_RouteType, MemberReady,
+    ServiceProtocolVersionRequest, SplinterNode, VotingPolicy_PolicyType,
 };
+#[cfg(feature = "admin-proposal-validation")]
+use crate::protos::admin::{ProposalValidationRequest, ProposalValidationResponse};
 use crate::service::error::ServiceError;
 #[cfg(feature = "service-arg-validation")]
 use crate::service::validation::ServiceArgValidator;
@@ -53,13 +67,16 @@ use crate::service::ServiceNetworkSender;
 use crate::signing::SignatureVerifier;
 use crate::storage::sets::mem::DurableBTreeSet;
 
+#[cfg(feature = "admin-audit-log")]
+use super::audit_writer::{record_audit_failure_metrics, AuditLogWriter};
 use super::error::{AdminSharedError, MarshallingError};
 use super::mailbox::Mailbox;
 use super::messages;
 use super::open_proposals::OpenProposals;
+use super::voting::{self, CircuitProposalStatus};
 use super::{
     admin_service_id, sha256, AdminKeyVerifier, AdminServiceEventSubscriber, AdminSubscriberError,
-    Events,
+    Events, ALL_EVENT_TYPES,
 };
 
 static VOTER_ROLE: &str = "voter";
@@ -67,6 +84,19 @@ static PROPOSER_ROLE: &str = "proposer";
 
 const DEFAULT_IN_MEMORY_EVENT_LIMIT: usize = 100;
 
+/// The time, in seconds, that a proposal is kept after it expires before this node removes it.
+///
+/// Each node expires proposals using its own clock, outside of consensus. Votes are rejected by
+/// every node once the proposal has expired by its clock, so a vote can only be committed while
+/// some node's clock is before the expiry; the grace period lets such a vote finish committing
+/// on the other nodes, as long as the nodes' clocks differ by less than this margin minus the
+/// time consensus takes.
+const PROPOSAL_EXPIRY_GRACE_PERIOD: u64 = 120;
+
+/// The file in the state directory that holds the audit log entries that have not been written
+#[cfg(feature = "admin-audit-log")]
+const AUDIT_BACKLOG_FILE: &str = "admin_audit_backlog.jsonl";
+
 pub enum PayloadType {
     Circuit(CircuitManagementPayload),
     Consensus(ProposalId, (Proposal, CircuitManagementPayload)),
@@ -88,12 +118,6 @@ pub struct PendingPayload {
     pub members: Vec<String>,
 }
 
-enum CircuitProposalStatus {
-    Accepted,
-    Rejected,
-    Pending,
-}
-
 struct CircuitProposalContext {
     pub circuit_proposal: CircuitProposal,
     pub action: CircuitManagementPayload_Action,
@@ -123,17 +147,19 @@ impl SubscriberMap {
         timestamp: &SystemTime,
     ) {
         let mut subscribers_by_type = self.subscribers_by_type.borrow_mut();
-        if let Some(subscribers) = subscribers_by_type.get_mut(event_type) {
-            subscribers.retain(|subscriber| {
-                match subscriber.handle_event(admin_service_event, timestamp) {
-                    Ok(()) => true,
-                    Err(AdminSubscriberError::Unsubscribe) => false,
-                    Err(AdminSubscriberError::UnableToHandleEvent(msg)) => {
-                        error!("Unable to send event: {}", msg);
-                        true
+        for subscribed_type in &[event_type, ALL_EVENT_TYPES] {
+            if let Some(subscribers) = subscribers_by_type.get_mut(*subscribed_type) {
+                subscribers.retain(|subscriber| {
+                    match subscriber.handle_event(admin_service_event, timestamp) {
+                        Ok(()) => true,
+                        Err(AdminSubscriberError::Unsubscribe) => false,
+                        Err(AdminSubscriberError::UnableToHandleEvent(msg)) => {
+                            error!("Unable to send event: {}", msg);
+                            true
+                        }
                     }
-                }
-            });
+                });
+            }
         }
     }
 
@@ -142,6 +168,7 @@ impl SubscriberMap {
         event_type: String,
         listener: Box<dyn AdminServiceEventSubscriber>,
     ) {
+        self.remove_closed();
         let mut subscribers_by_type = self.subscribers_by_type.borrow_mut();
         let subscribers = subscribers_by_type
             .entry(event_type)
@@ -149,6 +176,14 @@ impl SubscriberMap {
         subscribers.push(listener);
     }
 
+    fn remove_closed(&mut self) {
+        let mut subscribers_by_type = self.subscribers_by_type.borrow_mut();
+        for subscribers in subscribers_by_type.values_mut() {
+            subscribers.retain(|subscriber| !subscriber.is_closed());
+        }
+        subscribers_by_type.retain(|_, subscribers| !subscribers.is_empty());
+    }
+
     fn clear(&mut self) {
         self.subscribers_by_type.borrow_mut().clear()
     }
@@ -200,6 +235,19 @@ pub struct AdminServiceShared {
     key_verifier: Box<dyn AdminKeyVerifier>,
     key_permission_manager: Box<dyn KeyPermissionManager>,
     proposal_sender: Option<Sender<ProposalUpdate>>,
+    // the senders for the results of payload validations that are waiting for other members to
+    // respond, by correlation ID
+    #[cfg(feature = "admin-proposal-validation")]
+    pending_validations: HashMap<String, Sender<messages::NodeValidation>>,
+    // the append-only log of proposal lifecycle events, if one has been configured
+    #[cfg(feature = "admin-audit-log")]
+    audit_store: Option<Arc<dyn AdminServiceStore>>,
+    // writes the entries of the audit log in the background
+    #[cfg(feature = "admin-audit-log")]
+    audit_writer: Option<AuditLogWriter>,
+    // the file of audit log entries that have not been written yet; kept in memory if `None`
+    #[cfg(feature = "admin-audit-log")]
+    audit_backlog_path: Option<PathBuf>,
 
     admin_service_status: AdminServiceStatus,
 }
@@ -234,6 +282,12 @@ impl AdminServiceShared {
         let open_proposals = OpenProposals::new(storage_location)
             .map_err(|err| ServiceError::UnableToCreate(Box::new(err)))?;
 
+        #[cfg(feature = "admin-audit-log")]
+        let audit_backlog_path = match storage_type {
+            "yaml" => Some(Path::new(state_dir).join(AUDIT_BACKLOG_FILE)),
+            _ => None,
+        };
+
         let event_mailbox = Mailbox::new(DurableBTreeSet::new_boxed_with_bound(
             std::num::NonZeroUsize::new(DEFAULT_IN_MEMORY_EVENT_LIMIT).unwrap(),
         ));
@@ -262,6 +316,14 @@ impl AdminServiceShared {
             key_verifier,
             key_permission_manager,
             proposal_sender: None,
+            #[cfg(feature = "admin-proposal-validation")]
+            pending_validations: HashMap::new(),
+            #[cfg(feature = "admin-audit-log")]
+            audit_store: None,
+            #[cfg(feature = "admin-audit-log")]
+            audit_writer: None,
+            #[cfg(feature = "admin-audit-log")]
+            audit_backlog_path,
             admin_service_status: AdminServiceStatus::NotRunning,
         })
     }
@@ -360,14 +422,21 @@ impl AdminServiceShared {
             Some(circuit_proposal_context) => {
                 let circuit_proposal = circuit_proposal_context.circuit_proposal;
                 let action = circuit_proposal_context.action;
+                if action == CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_WITHDRAW {
+                    return self.commit_withdrawal(
+                        circuit_proposal,
+                        circuit_proposal_context.signer_public_key,
+                    );
+                }
+
                 let circuit_id = circuit_proposal.get_circuit_id();
                 let mgmt_type = circuit_proposal
                     .get_circuit_proposal()
                     .circuit_management_type
                     .clone();
 
-                match self.check_approved(&circuit_proposal) {
-                    Ok(CircuitProposalStatus::Accepted) => {
+                match voting::proposal_status(&circuit_proposal) {
+                    CircuitProposalStatus::Accepted => {
                         // commit new circuit
                         let circuit = circuit_proposal.get_circuit_proposal();
                         self.update_splinter_state(circuit)?;
@@ -378,6 +447,12 @@ impl AdminServiceShared {
                         let circuit_proposal_proto =
                             messages::CircuitProposal::from_proto(circuit_proposal.clone())
                                 .map_err(AdminSharedError::InvalidMessageFormat)?;
+                        #[cfg(feature = "admin-audit-log")]
+                        self.record_audit_entry(
+                            &circuit_proposal_proto,
+                            AuditEventType::ProposalAccepted,
+                            &circuit_proposal_context.signer_public_key,
+                        );
                         let event = messages::AdminServiceEvent::ProposalAccepted((
                             circuit_proposal_proto,
                             circuit_proposal_context.signer_public_key,
@@ -405,22 +480,34 @@ impl AdminServiceShared {
                             }
                         }
 
+                        self.record_proposal_metrics("accepted");
+
                         // add circuit as pending initialization
                         self.add_uninitialized_circuit(circuit_proposal.clone())
                     }
-                    Ok(CircuitProposalStatus::Pending) => {
+                    CircuitProposalStatus::Pending => {
                         self.add_proposal(circuit_proposal.clone())?;
 
                         match action {
                             CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
                                 // notify registered application authorization handlers of the
                                 // committed circuit proposal
-                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                let circuit_proposal_proto =
                                     messages::CircuitProposal::from_proto(circuit_proposal.clone())
-                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?;
+                                #[cfg(feature = "admin-audit-log")]
+                                self.record_audit_entry(
+                                    &circuit_proposal_proto,
+                                    AuditEventType::ProposalSubmitted,
+                                    &circuit_proposal_context.signer_public_key,
+                                );
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    circuit_proposal_proto,
                                 );
                                 self.send_event(&mgmt_type, event);
 
+                                self.record_proposal_metrics("submitted");
+
                                 info!("committed changes for new circuit proposal {}", circuit_id);
                                 Ok(())
                             }
@@ -431,12 +518,20 @@ impl AdminServiceShared {
                                 let circuit_proposal_proto =
                                     messages::CircuitProposal::from_proto(circuit_proposal.clone())
                                         .map_err(AdminSharedError::InvalidMessageFormat)?;
+                                #[cfg(feature = "admin-audit-log")]
+                                self.record_audit_entry(
+                                    &circuit_proposal_proto,
+                                    AuditEventType::ProposalVote,
+                                    &circuit_proposal_context.signer_public_key,
+                                );
                                 let event = messages::AdminServiceEvent::ProposalVote((
                                     circuit_proposal_proto,
                                     circuit_proposal_context.signer_public_key,
                                 ));
                                 self.send_event(&mgmt_type, event);
 
+                                self.record_proposal_metrics("voted");
+
                                 info!("committed vote for circuit proposal {}", circuit_id);
                                 Ok(())
                             }
@@ -446,7 +541,7 @@ impl AdminServiceShared {
                             ))),
                         }
                     }
-                    Ok(CircuitProposalStatus::Rejected) => {
+                    CircuitProposalStatus::Rejected => {
                         // remove circuit
                         let proposal = self.remove_proposal(&circuit_id)?;
                         if let Some(proposal) = proposal {
@@ -457,22 +552,195 @@ impl AdminServiceShared {
                         let circuit_proposal_proto =
                             messages::CircuitProposal::from_proto(circuit_proposal.clone())
                                 .map_err(AdminSharedError::InvalidMessageFormat)?;
+                        #[cfg(feature = "admin-audit-log")]
+                        self.record_audit_entry(
+                            &circuit_proposal_proto,
+                            AuditEventType::ProposalRejected,
+                            &circuit_proposal_context.signer_public_key,
+                        );
                         let event = messages::AdminServiceEvent::ProposalRejected((
                             circuit_proposal_proto,
                             circuit_proposal_context.signer_public_key,
                         ));
                         self.send_event(&mgmt_type, event);
 
+                        self.record_proposal_metrics("rejected");
+
                         info!("circuit proposal for {} has been rejected", circuit_id);
                         Ok(())
                     }
-                    Err(err) => Err(err),
                 }
             }
             None => Err(AdminSharedError::NoPendingChanges),
         }
     }
 
+    /// Removes a withdrawn proposal, regardless of the votes it has received, and notifies
+    /// subscribers with a `ProposalRejected` event signed by the requester.
+    fn commit_withdrawal(
+        &mut self,
+        circuit_proposal: CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id().to_string();
+        let mgmt_type = circuit_proposal
+            .get_circuit_proposal()
+            .circuit_management_type
+            .clone();
+
+        self.close_proposal(&circuit_id)?;
+
+        let circuit_proposal_proto = messages::CircuitProposal::from_proto(circuit_proposal)
+            .map_err(AdminSharedError::InvalidMessageFormat)?;
+        #[cfg(feature = "admin-audit-log")]
+        self.record_audit_entry(
+            &circuit_proposal_proto,
+            AuditEventType::ProposalWithdrawn,
+            &signer_public_key,
+        );
+        let event = messages::AdminServiceEvent::ProposalRejected((
+            circuit_proposal_proto,
+            signer_public_key,
+        ));
+        self.send_event(&mgmt_type, event);
+
+        self.record_proposal_metrics("withdrawn");
+
+        info!("circuit proposal for {} has been withdrawn", circuit_id);
+        Ok(())
+    }
+
+    /// Removes all open proposals that expired at least `PROPOSAL_EXPIRY_GRACE_PERIOD` seconds
+    /// before the given time, notifying subscribers with a `ProposalExpired` event for each.
+    ///
+    /// A proposal that is currently being committed is skipped; it will be expired by a later
+    /// check if it is still open.
+    pub fn expire_proposals(&mut self, now: SystemTime) -> Result<(), AdminSharedError> {
+        let now = unix_timestamp(&now).saturating_sub(PROPOSAL_EXPIRY_GRACE_PERIOD);
+        let committing_circuit_id = self
+            .pending_changes
+            .as_ref()
+            .map(|context| context.circuit_proposal.get_circuit_id().to_string());
+
+        let expired_proposals = self
+            .get_proposals()
+            .into_iter()
+            .filter(|(circuit_id, _)| Some(circuit_id) != committing_circuit_id.as_ref())
+            .filter(|(_, proposal)| match proposal.expires_at {
+                Some(expires_at) => expires_at <= now,
+                None => false,
+            })
+            .map(|(_, proposal)| proposal)
+            .collect::<Vec<_>>();
+
+        for proposal in expired_proposals {
+            self.close_proposal(&proposal.circuit_id)?;
+
+            info!("circuit proposal for {} has expired", proposal.circuit_id);
+
+            #[cfg(feature = "admin-audit-log")]
+            self.record_audit_entry(&proposal, AuditEventType::ProposalExpired, &[]);
+
+            let mgmt_type = proposal.circuit.circuit_management_type.clone();
+            self.send_event(
+                &mgmt_type,
+                messages::AdminServiceEvent::ProposalExpired(proposal),
+            );
+
+            self.record_proposal_metrics("expired");
+        }
+
+        Ok(())
+    }
+
+    /// Removes an open proposal and drops the peer refs held for its members.
+    fn close_proposal(&mut self, circuit_id: &str) -> Result<(), AdminSharedError> {
+        if let Some(proposal) = self.remove_proposal(circuit_id)? {
+            for member in proposal.get_circuit_proposal().members.iter() {
+                self.remove_peer_ref(member.get_node_id());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "metrics")]
+    fn record_proposal_metrics(&self, status: &str) {
+        crate::metrics::ADMIN_PROPOSALS
+            .with_label_values(&[status])
+            .inc();
+        crate::metrics::ADMIN_PENDING_PROPOSALS.set(self.get_proposals().len() as i64);
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    fn record_proposal_metrics(&self, _status: &str) {}
+
+    /// Appends an entry for the given proposal event to the audit log, if one is configured.
+    ///
+    /// The signer's node and vote are looked up from the proposal's vote records; the requester
+    /// is recorded for submissions and withdrawals. The entry is written by the audit log
+    /// writer in the background, outside of the admin service's lock; it is retried until it
+    /// succeeds, and each failure is counted in the `splinter_admin_audit_log_failures_total`
+    /// metric.
+    #[cfg(feature = "admin-audit-log")]
+    fn record_audit_entry(
+        &self,
+        proposal: &messages::CircuitProposal,
+        event_type: AuditEventType,
+        signer_public_key: &[u8],
+    ) {
+        let audit_writer = match self.audit_writer {
+            Some(ref audit_writer) => audit_writer,
+            None => return,
+        };
+
+        let mut builder = AuditEntryBuilder::new()
+            .with_circuit_id(&proposal.circuit_id)
+            .with_event_type(&event_type)
+            .with_timestamp(unix_timestamp(&SystemTime::now()))
+            .with_circuit_hash(&proposal.circuit_hash)
+            .with_proposal(proposal);
+
+        match event_type {
+            AuditEventType::ProposalSubmitted | AuditEventType::ProposalWithdrawn => {
+                builder = builder
+                    .with_public_key(&proposal.requester)
+                    .with_node_id(&proposal.requester_node_id);
+            }
+            AuditEventType::ProposalExpired => (),
+            _ => {
+                builder = builder.with_public_key(signer_public_key);
+                if let Some(vote_record) = proposal
+                    .votes
+                    .iter()
+                    .find(|vote_record| vote_record.public_key == signer_public_key)
+                {
+                    let vote = match vote_record.vote {
+                        messages::Vote::Accept => StoreVote::Accept,
+                        messages::Vote::Reject => StoreVote::Reject,
+                    };
+                    builder = builder
+                        .with_node_id(&vote_record.voter_node_id)
+                        .with_vote(&vote);
+                }
+            }
+        }
+
+        let entry = match builder.build() {
+            Ok(entry) => entry,
+            Err(err) => {
+                error!(
+                    "Unable to record {:?} for circuit proposal {} in the audit log: {}",
+                    event_type, proposal.circuit_id, err
+                );
+                record_audit_failure_metrics(&event_type);
+                return;
+            }
+        };
+
+        audit_writer.record(entry);
+    }
+
     pub fn rollback(&mut self) -> Result<(), AdminSharedError> {
         match self.pending_changes.take() {
             Some(circuit_proposal_context) => info!(
@@ -514,6 +782,23 @@ impl AdminServiceShared {
                     signer_public_key,
                     requester_node_id,
                 )
+                .and_then(|_| {
+                    voting::validate_voting_policy(
+                        create_request.get_voting_policy(),
+                        proposed_circuit.get_members(),
+                    )
+                    .and_then(|_| {
+                        voting::validate_signer_quorum(
+                            create_request.get_signer_quorum(),
+                            proposed_circuit.get_members(),
+                            requester_node_id,
+                        )
+                    })
+                    .map_err(AdminSharedError::ValidationFailed)
+                })
+                .and_then(|_| {
+                    self.validate_member_protocols(&create_request, proposed_circuit.get_members())
+                })
                 .map_err(|err| {
                     // remove peer_ref because we will not accept this proposal
                     for member in proposed_circuit.get_members() {
@@ -530,6 +815,9 @@ impl AdminServiceShared {
                 circuit_proposal.set_circuit_proposal(proposed_circuit);
                 circuit_proposal.set_requester(header.get_requester().to_vec());
                 circuit_proposal.set_requester_node_id(header.get_requester_node_id().to_string());
+                circuit_proposal.set_expires_at(create_request.get_expires_at());
+                circuit_proposal.set_voting_policy(create_request.take_voting_policy());
+                circuit_proposal.set_signer_quorum(create_request.take_signer_quorum());
 
                 let expected_hash = sha256(&circuit_proposal)?;
                 self.pending_changes = Some(CircuitProposalContext {
@@ -602,6 +890,47 @@ impl AdminServiceShared {
                 self.current_consensus_verifiers = verifiers;
                 Ok((expected_hash, circuit_proposal))
             }
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_WITHDRAW => {
+                let proposal_withdraw = circuit_payload.get_circuit_proposal_withdraw();
+
+                // check that the circuit proposal exists
+                let circuit_proposal = self
+                    .get_proposal(proposal_withdraw.get_circuit_id())
+                    .map_err(|err| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "error occurred when trying to get proposal {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Received withdrawal of a proposal that does not exist: circuit id {}",
+                            proposal_withdraw.circuit_id
+                        ))
+                    })?;
+
+                let mut verifiers = vec![];
+                for member in circuit_proposal.get_circuit_proposal().get_members() {
+                    verifiers.push(admin_service_id(member.get_node_id()));
+                }
+
+                self.validate_circuit_withdraw(
+                    proposal_withdraw,
+                    header.get_requester(),
+                    &circuit_proposal,
+                    header.get_requester_node_id(),
+                )?;
+
+                // the proposal is unchanged; committing the withdrawal removes it
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action: CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_WITHDRAW,
+                });
+                self.current_consensus_verifiers = verifiers;
+                Ok((expected_hash, circuit_proposal))
+            }
             CircuitManagementPayload_Action::ACTION_UNSET => Err(
                 AdminSharedError::ValidationFailed("Action must be set".to_string()),
             ),
@@ -675,6 +1004,39 @@ impl AdminServiceShared {
         )
     }
 
+    pub fn propose_withdraw(
+        &mut self,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit proposal withdrawal for {}",
+            payload.get_circuit_proposal_withdraw().get_circuit_id()
+        );
+        let circuit_id = payload.get_circuit_proposal_withdraw().get_circuit_id();
+        let proposal = self
+            .get_proposal(circuit_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("error occurred when trying to get proposal {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!(
+                        "Received withdrawal of a proposal that does not exist: circuit id {}",
+                        circuit_id
+                    ),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_vote(
+            proposal.get_circuit_proposal().get_members(),
+            payload,
+            message_sender,
+        )
+    }
+
     pub fn send_protocol_request(&mut self, node_id: &str) -> Result<(), ServiceError> {
         if self
             .service_protocols
@@ -813,11 +1175,37 @@ impl AdminServiceShared {
     pub fn submit(&mut self, payload: CircuitManagementPayload) -> Result<(), ServiceError> {
         debug!("Payload submitted: {:?}", payload);
 
+        let header = self.validate_payload(&payload)?;
+        match header.get_action() {
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
+                self.propose_circuit(payload, "local".to_string())
+            }
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
+                self.propose_vote(payload, "local".to_string())
+            }
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_WITHDRAW => {
+                self.propose_withdraw(payload, "local".to_string())
+            }
+            unknown_action => Err(ServiceError::UnableToHandleMessage(Box::new(
+                AdminSharedError::ValidationFailed(format!(
+                    "Unable to handle {:?}",
+                    unknown_action
+                )),
+            ))),
+        }
+    }
+
+    /// Validates a circuit management payload as if it were submitted to this node, without
+    /// submitting it, and returns the payload's header.
+    pub fn validate_payload(
+        &self,
+        payload: &CircuitManagementPayload,
+    ) -> Result<CircuitManagementPayload_Header, ServiceError> {
         let header =
             protobuf::parse_from_bytes::<CircuitManagementPayload_Header>(payload.get_header())?;
-        self.validate_circuit_management_payload(&payload, &header)
+        self.validate_circuit_management_payload(payload, &header)
             .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
-        self.verify_signature(&payload)?;
+        self.verify_signature(payload)?;
 
         match header.get_action() {
             CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
@@ -829,8 +1217,25 @@ impl AdminServiceShared {
                     requester_node_id,
                 )
                 .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
-
-                self.propose_circuit(payload, "local".to_string())
+                self.validate_expiry(payload.get_circuit_create_request().get_expires_at())
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                let create_request = payload.get_circuit_create_request();
+                let members = create_request.get_circuit().get_members();
+                voting::validate_voting_policy(create_request.get_voting_policy(), members)
+                    .and_then(|_| {
+                        voting::validate_signer_quorum(
+                            create_request.get_signer_quorum(),
+                            members,
+                            requester_node_id,
+                        )
+                    })
+                    .map_err(|err| {
+                        ServiceError::UnableToHandleMessage(Box::new(
+                            AdminSharedError::ValidationFailed(err),
+                        ))
+                    })?;
+                self.validate_member_protocols(create_request, members)
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
             }
             CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
                 let proposal_vote = payload.get_circuit_proposal_vote();
@@ -864,23 +1269,204 @@ impl AdminServiceShared {
                     header.get_requester_node_id(),
                 )
                 .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+            }
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_WITHDRAW => {
+                let proposal_withdraw = payload.get_circuit_proposal_withdraw();
 
-                self.propose_vote(payload, "local".to_string())
+                // check that the circuit proposal exists
+                let circuit_proposal = self
+                    .get_proposal(proposal_withdraw.get_circuit_id())
+                    .map_err(|err| {
+                        ServiceError::UnableToHandleMessage(Box::new(
+                            AdminSharedError::ValidationFailed(format!(
+                                "error occurred when trying to get proposal {}",
+                                err
+                            )),
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        ServiceError::UnableToHandleMessage(Box::new(
+                            AdminSharedError::ValidationFailed(format!(
+                                "Received withdrawal of a proposal that does not exist: \
+                                 circuit id {}",
+                                proposal_withdraw.circuit_id
+                            )),
+                        ))
+                    })?;
+
+                self.validate_circuit_withdraw(
+                    proposal_withdraw,
+                    header.get_requester(),
+                    &circuit_proposal,
+                    header.get_requester_node_id(),
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
             }
             CircuitManagementPayload_Action::ACTION_UNSET => {
-                Err(ServiceError::UnableToHandleMessage(Box::new(
+                return Err(ServiceError::UnableToHandleMessage(Box::new(
                     AdminSharedError::ValidationFailed(String::from("No action specified")),
-                )))
+                )));
             }
-            unknown_action => Err(ServiceError::UnableToHandleMessage(Box::new(
-                AdminSharedError::ValidationFailed(format!(
-                    "Unable to handle {:?}",
-                    unknown_action
-                )),
-            ))),
+            unknown_action => {
+                return Err(ServiceError::UnableToHandleMessage(Box::new(
+                    AdminSharedError::ValidationFailed(format!(
+                        "Unable to handle {:?}",
+                        unknown_action
+                    )),
+                )));
+            }
+        }
+
+        Ok(header)
+    }
+
+    /// Validates a circuit management payload on this node, and asks the admin services of the
+    /// payload's other members to validate it too. The results of the other members are sent to
+    /// `sender` as their responses arrive.
+    ///
+    /// Returns the results that are already known: this node's, and those of the members that
+    /// could not be asked because this node has not agreed on a protocol with their admin
+    /// services. Also returns the IDs of the members that were asked.
+    #[cfg(feature = "admin-proposal-validation")]
+    pub fn request_payload_validation(
+        &mut self,
+        correlation_id: &str,
+        payload: &CircuitManagementPayload,
+        sender: Sender<messages::NodeValidation>,
+    ) -> Result<(Vec<messages::NodeValidation>, Vec<String>), ServiceError> {
+        let mut results = vec![match self.validate_payload(payload) {
+            Ok(_) => messages::NodeValidation {
+                node_id: self.node_id.clone(),
+                status: messages::NodeValidationStatus::Valid,
+                error: None,
+            },
+            Err(err) => messages::NodeValidation {
+                node_id: self.node_id.clone(),
+                status: messages::NodeValidationStatus::Invalid,
+                error: Some(validation_error_message(err)),
+            },
+        }];
+
+        let mut request = ProposalValidationRequest::new();
+        request.set_correlation_id(correlation_id.to_string());
+        request.set_circuit_payload(payload.clone());
+        let mut msg = AdminMessage::new();
+        msg.set_message_type(AdminMessage_Type::PROPOSAL_VALIDATION_REQUEST);
+        msg.set_validation_request(request);
+        let envelope_bytes = msg
+            .write_to_bytes()
+            .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
+        let network_sender = self
+            .network_sender
+            .as_ref()
+            .ok_or_else(|| ServiceError::NotStarted)?;
+
+        let mut requested_members = vec![];
+        for member in self.payload_members(payload) {
+            if member == self.node_id {
+                continue;
+            }
+
+            let admin_service = admin_service_id(&member);
+            match self.service_protocols.get(&admin_service) {
+                Some(protocol) if *protocol >= ADMIN_VALIDATE_PROPOSAL_PROTOCOL_MIN => (),
+                Some(_) => {
+                    results.push(messages::NodeValidation {
+                        node_id: member,
+                        status: messages::NodeValidationStatus::Unreachable,
+                        error: Some(
+                            "The node's admin service does not support proposal validation".into(),
+                        ),
+                    });
+                    continue;
+                }
+                None => {
+                    results.push(messages::NodeValidation {
+                        node_id: member,
+                        status: messages::NodeValidationStatus::Unreachable,
+                        error: Some("Not connected to the node's admin service".into()),
+                    });
+                    continue;
+                }
+            }
+
+            match network_sender.send(&admin_service, &envelope_bytes) {
+                Ok(()) => requested_members.push(member),
+                Err(err) => results.push(messages::NodeValidation {
+                    node_id: member,
+                    status: messages::NodeValidationStatus::Unreachable,
+                    error: Some(format!("Unable to send validation request: {}", err)),
+                }),
+            }
+        }
+
+        if !requested_members.is_empty() {
+            self.pending_validations
+                .insert(correlation_id.to_string(), sender);
+        }
+
+        Ok((results, requested_members))
+    }
+
+    /// Passes another member's validation result to the request that it belongs to, if that
+    /// request is still waiting for results.
+    #[cfg(feature = "admin-proposal-validation")]
+    pub fn handle_validation_response(&mut self, response: ProposalValidationResponse) {
+        match self.pending_validations.get(response.get_correlation_id()) {
+            Some(sender) => {
+                if sender
+                    .send(messages::NodeValidation::from_proto(response))
+                    .is_err()
+                {
+                    debug!("Payload validation request is no longer waiting for results");
+                }
+            }
+            None => debug!(
+                "Ignoring validation response for unknown request {}",
+                response.get_correlation_id()
+            ),
         }
     }
 
+    /// Stops passing validation results to the request with the given correlation ID.
+    #[cfg(feature = "admin-proposal-validation")]
+    pub fn remove_pending_validation(&mut self, correlation_id: &str) {
+        self.pending_validations.remove(correlation_id);
+    }
+
+    /// Returns the IDs of the members of the circuit that the payload applies to.
+    #[cfg(feature = "admin-proposal-validation")]
+    fn payload_members(&self, payload: &CircuitManagementPayload) -> Vec<String> {
+        let members = if payload.has_circuit_create_request() {
+            payload
+                .get_circuit_create_request()
+                .get_circuit()
+                .get_members()
+                .to_vec()
+        } else {
+            let circuit_id = if payload.has_circuit_proposal_vote() {
+                payload.get_circuit_proposal_vote().get_circuit_id()
+            } else if payload.has_circuit_proposal_withdraw() {
+                payload.get_circuit_proposal_withdraw().get_circuit_id()
+            } else {
+                return vec![];
+            };
+
+            match self.get_proposal(circuit_id) {
+                Ok(Some(mut proposal)) => {
+                    proposal.take_circuit_proposal().take_members().into_vec()
+                }
+                _ => vec![],
+            }
+        };
+
+        members
+            .into_iter()
+            .map(|mut member| member.take_node_id())
+            .collect()
+    }
+
     /// Handle a new circuit proposal
     ///
     /// This operation will accept a new circuit proposal.  If there is no peer connection, a
@@ -960,11 +1546,36 @@ impl AdminServiceShared {
         }
     }
 
-    pub fn get_events_since(
-        &self,
-        since_timestamp: &SystemTime,
-        circuit_management_type: &str,
-    ) -> Result<Events, AdminSharedError> {
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn set_event_mailbox(&mut self, event_mailbox: Mailbox) {
+        self.event_mailbox = event_mailbox;
+    }
+
+    /// Sets the audit store, and starts the writer that appends entries to it. Entries left in
+    /// the backlog by a previous run are written first.
+    #[cfg(feature = "admin-audit-log")]
+    pub fn set_audit_store(
+        &mut self,
+        audit_store: Arc<dyn AdminServiceStore>,
+    ) -> Result<(), io::Error> {
+        self.audit_writer = Some(AuditLogWriter::start(
+            audit_store.clone(),
+            self.audit_backlog_path.clone(),
+        )?);
+        self.audit_store = Some(audit_store);
+        Ok(())
+    }
+
+    #[cfg(feature = "admin-audit-log")]
+    pub fn audit_store(&self) -> Option<Arc<dyn AdminServiceStore>> {
+        self.audit_store.clone()
+    }
+
+    pub fn get_events_since(
+        &self,
+        since_timestamp: &SystemTime,
+        circuit_management_type: &str,
+    ) -> Result<Events, AdminSharedError> {
         let events = self
             .event_mailbox
             .iter_since(*since_timestamp)
@@ -973,7 +1584,8 @@ impl AdminServiceShared {
         let circuit_management_type = circuit_management_type.to_string();
         Ok(Events {
             inner: Box::new(events.filter(move |(_, evt)| {
-                evt.proposal().circuit.circuit_management_type == circuit_management_type
+                circuit_management_type == ALL_EVENT_TYPES
+                    || evt.proposal().circuit.circuit_management_type == circuit_management_type
             })),
         })
     }
@@ -1010,6 +1622,10 @@ impl AdminServiceShared {
         self.event_subscribers.clear();
     }
 
+    pub fn remove_closed_event_subscribers(&mut self) {
+        self.event_subscribers.remove_closed();
+    }
+
     pub fn on_peer_disconnected(&mut self, peer_id: String) {
         self.service_protocols.remove(&admin_service_id(&peer_id));
         let mut pending_protocol_payloads =
@@ -1573,6 +2189,13 @@ impl AdminServiceShared {
     ) -> Result<(), AdminSharedError> {
         let circuit_hash = proposal_vote.get_circuit_hash();
 
+        if proposal_vote.get_vote() == CircuitProposalVote_Vote::UNSET_VOTE {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Received vote with no value for {}",
+                proposal_vote.get_circuit_id()
+            )));
+        }
+
         self.validate_key(signer_public_key)?;
 
         if !self.key_verifier.is_permitted(node_id, signer_public_key)? {
@@ -1590,19 +2213,19 @@ impl AdminServiceShared {
             )));
         }
 
-        let voted_nodes: Vec<String> = circuit_proposal
-            .get_votes()
-            .iter()
-            .map(|vote| vote.get_voter_node_id().to_string())
-            .collect();
+        voting::validate_voter(circuit_proposal, node_id)
+            .map_err(AdminSharedError::ValidationFailed)?;
 
-        if voted_nodes.iter().any(|node| *node == node_id) {
+        if is_expired(circuit_proposal, unix_timestamp(&SystemTime::now())) {
             return Err(AdminSharedError::ValidationFailed(format!(
-                "Received duplicate vote from {} for {}",
-                node_id, proposal_vote.circuit_id
+                "Received vote for expired proposal: {}",
+                proposal_vote.circuit_id
             )));
         }
 
+        voting::validate_signer(circuit_proposal, node_id, signer_public_key)
+            .map_err(AdminSharedError::ValidationFailed)?;
+
         self.key_permission_manager
             .is_permitted(signer_public_key, VOTER_ROLE)
             .map_err(|_| {
@@ -1624,6 +2247,99 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    fn validate_circuit_withdraw(
+        &self,
+        proposal_withdraw: &CircuitProposalWithdraw,
+        signer_public_key: &[u8],
+        circuit_proposal: &CircuitProposal,
+        node_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        self.validate_key(signer_public_key)?;
+
+        if !self.key_verifier.is_permitted(node_id, signer_public_key)? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for node {}",
+                to_hex(signer_public_key),
+                node_id,
+            )));
+        }
+
+        if circuit_proposal.get_requester() != signer_public_key
+            || circuit_proposal.get_requester_node_id() != node_id
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Only the requester may withdraw the proposal for {}",
+                proposal_withdraw.circuit_id
+            )));
+        }
+
+        // validate hash of circuit
+        if circuit_proposal.get_circuit_hash() != proposal_withdraw.get_circuit_hash() {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Hash of circuit does not match circuit proposal: {}",
+                proposal_withdraw.circuit_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Validates that a requested proposal expiry, if set, is in the future.
+    /// Validates that every member whose admin service agreed on an older protocol version would
+    /// apply the create request's expiry, voting policy and signer quorum. Older admin services
+    /// drop these fields, so a proposal that relies on them is rejected rather than tallied
+    /// differently by those members.
+    fn validate_member_protocols(
+        &self,
+        create_request: &CircuitCreateRequest,
+        members: &[SplinterNode],
+    ) -> Result<(), AdminSharedError> {
+        let uses_proposal_rules = create_request.get_expires_at() != 0
+            || create_request.get_voting_policy().get_policy_type()
+                != VotingPolicy_PolicyType::UNANIMOUS
+            || create_request.get_signer_quorum().get_required_signers() > 1
+            || create_request
+                .get_signer_quorum()
+                .get_node_signers()
+                .iter()
+                .any(|node_signers| node_signers.get_required_signers() > 1);
+        if !uses_proposal_rules {
+            return Ok(());
+        }
+
+        for member in members {
+            if member.get_node_id() == self.node_id {
+                continue;
+            }
+            if let Some(protocol) = self
+                .service_protocols
+                .get(&admin_service_id(member.get_node_id()))
+            {
+                if *protocol < ADMIN_PROPOSAL_RULES_PROTOCOL_MIN {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Member {} uses admin protocol version {}, which does not support \
+                         proposal expiry, voting policies or signer quorums",
+                        member.get_node_id(),
+                        protocol
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_expiry(&self, expires_at: u64) -> Result<(), AdminSharedError> {
+        if expires_at != 0 && expires_at <= unix_timestamp(&SystemTime::now()) {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Proposal expiry {} is not in the future",
+                expires_at
+            )));
+        }
+
+        Ok(())
+    }
+
     fn validate_circuit_management_payload(
         &self,
         payload: &CircuitManagementPayload,
@@ -1662,35 +2378,6 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    fn check_approved(
-        &self,
-        proposal: &CircuitProposal,
-    ) -> Result<CircuitProposalStatus, AdminSharedError> {
-        let mut received_votes = HashSet::new();
-        for vote in proposal.get_votes() {
-            if vote.get_vote() == CircuitProposalVote_Vote::REJECT {
-                return Ok(CircuitProposalStatus::Rejected);
-            }
-            received_votes.insert(vote.get_voter_node_id().to_string());
-        }
-
-        let mut required_votes = proposal
-            .get_circuit_proposal()
-            .get_members()
-            .to_vec()
-            .iter()
-            .map(|member| member.get_node_id().to_string())
-            .collect::<HashSet<String>>();
-
-        required_votes.remove(proposal.get_requester_node_id());
-
-        if required_votes == received_votes {
-            Ok(CircuitProposalStatus::Accepted)
-        } else {
-            Ok(CircuitProposalStatus::Pending)
-        }
-    }
-
     /// Initialize all services that this node should run on the created circuit using the service
     /// orchestrator. This may not include all services if they are not supported locally. It is
     /// expected that some services will be started externally.
@@ -1920,15 +2607,42 @@ impl AdminServiceShared {
     }
 }
 
+/// Returns the reason a payload failed validation, without the error's generic prefix.
+pub fn validation_error_message(err: ServiceError) -> String {
+    match err {
+        ServiceError::UnableToHandleMessage(err) => err.to_string(),
+        err => err.to_string(),
+    }
+}
+
+/// Returns the number of whole seconds between the Unix epoch and the given time.
+fn unix_timestamp(time: &SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Returns true if the proposal has an expiry that is at or before the given Unix timestamp.
+fn is_expired(proposal: &CircuitProposal, now: u64) -> bool {
+    proposal.get_expires_at() != 0 && proposal.get_expires_at() <= now
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use std::sync::{Arc, Mutex};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    };
 
     use protobuf::{Message, RepeatedField};
+    #[cfg(feature = "admin-audit-log")]
+    use tempdir::TempDir;
 
     use crate::admin::service::AdminKeyVerifierError;
+    #[cfg(feature = "admin-audit-log")]
+    use crate::admin::store::{yaml::YamlAdminServiceStore, AuditPredicate};
     use crate::circuit::directory::CircuitDirectory;
     use crate::keys::insecure::AllowAllKeyPermissionManager;
     use crate::mesh::{Envelope, Mesh};
@@ -2223,6 +2937,60 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that a create request relying on a voting policy, signer quorum or expiry is rejected
+    // when a member's admin service agreed on a protocol version that does not support them
+    fn test_validate_member_protocols() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let mut admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+        admin_shared
+            .service_protocols
+            .insert(admin_service_id("node_b"), 1);
+
+        let mut request = CircuitCreateRequest::new();
+        request.set_circuit(circuit.clone());
+        if let Err(err) = admin_shared.validate_member_protocols(&request, circuit.get_members()) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        let mut voting_policy = admin::VotingPolicy::new();
+        voting_policy.set_policy_type(VotingPolicy_PolicyType::THRESHOLD);
+        voting_policy.set_threshold(1);
+        request.set_voting_policy(voting_policy);
+        if admin_shared
+            .validate_member_protocols(&request, circuit.get_members())
+            .is_ok()
+        {
+            panic!("Should have been invalid because node_b agreed on protocol version 1");
+        }
+
+        admin_shared
+            .service_protocols
+            .insert(admin_service_id("node_b"), ADMIN_PROTOCOL_VERSION);
+        if let Err(err) = admin_shared.validate_member_protocols(&request, circuit.get_members()) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that a circuit proposed with a key that is not permitted for the requesting node is
     // invalid
@@ -3090,6 +3858,38 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that a vote without a value is invalid
+    fn test_validate_proposal_vote_unset() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+        let mut vote = setup_test_vote(&circuit);
+        vote.set_vote(CircuitProposalVote_Vote::UNSET_VOTE);
+        let proposal = setup_test_proposal(&circuit);
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(&vote, PUB_KEY, &proposal, "node_a") {
+            panic!("Should have been invalid because the vote is unset");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test if a voter has already voted on a proposal the new vote is invalid
     fn test_validate_proposal_vote_duplicate_vote() {
@@ -3128,6 +3928,53 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that if a node requires more than one signer, another key of a node that has already
+    // voted may vote
+    fn test_validate_proposal_vote_signer_quorum() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
+
+        let mut signer_quorum = admin::SignerQuorum::new();
+        signer_quorum.set_required_signers(2);
+        proposal.set_signer_quorum(signer_quorum);
+
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+        vote_record.set_public_key(vec![1; 33]);
+        vote_record.set_voter_node_id("node_a".to_string());
+
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
+
+        if let Err(err) = admin_shared.validate_circuit_vote(&vote, PUB_KEY, &proposal, "node_a") {
+            panic!("Should have been valid: {}", err);
+        }
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(&vote, &[1; 33], &proposal, "node_a") {
+            panic!("Should have been invalid because the key has already voted");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that if the circuit hash in the circuit proposal does not match the circuit hash on
     // the vote, the vote is invalid
@@ -3162,6 +4009,279 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that a vote on a proposal that has expired is invalid
+    fn test_validate_proposal_vote_expired() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
+
+        proposal.set_expires_at(unix_timestamp(&SystemTime::now()) - 1);
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(&vote, PUB_KEY, &proposal, "node_a") {
+            panic!("Should have been invalid because the proposal has expired");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a withdrawal from the proposal's requester is valid
+    fn test_validate_proposal_withdraw_valid() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+        let withdraw = setup_test_withdraw(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+
+        if let Err(err) =
+            admin_shared.validate_circuit_withdraw(&withdraw, PUB_KEY, &proposal, "node_b")
+        {
+            panic!("Should have been valid: {}", err);
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a withdrawal from a key other than the proposal's requester is invalid
+    fn test_validate_proposal_withdraw_not_requester() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+        let withdraw = setup_test_withdraw(&circuit);
+        let proposal = setup_test_proposal(&circuit);
+
+        if let Ok(_) =
+            admin_shared.validate_circuit_withdraw(&withdraw, PUB_KEY, &proposal, "node_b")
+        {
+            panic!("Should have been invalid because the signer is not the requester");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a withdrawal with a circuit hash that does not match the proposal is invalid
+    fn test_validate_proposal_withdraw_circuit_hash_mismatch() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+        let withdraw = setup_test_withdraw(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+        proposal.set_circuit_hash("bad_hash".to_string());
+
+        if let Ok(_) =
+            admin_shared.validate_circuit_withdraw(&withdraw, PUB_KEY, &proposal, "node_b")
+        {
+            panic!("Should have been invalid because the circuit hash does not match");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that expiring proposals removes only the expired proposals and emits a
+    // ProposalExpired event for each
+    fn test_expire_proposals() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let mut admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+        let now = SystemTime::now();
+
+        let mut expired_circuit = setup_test_circuit();
+        expired_circuit.set_circuit_id("01234-EXPIR".to_string());
+        let mut expired_proposal = setup_test_proposal(&expired_circuit);
+        expired_proposal.set_expires_at(unix_timestamp(&now) - PROPOSAL_EXPIRY_GRACE_PERIOD - 1);
+        admin_shared.add_proposal(expired_proposal).unwrap();
+
+        // Recently expired proposals are kept until the grace period has passed
+        let mut grace_circuit = setup_test_circuit();
+        grace_circuit.set_circuit_id("01234-GRACE".to_string());
+        let mut grace_proposal = setup_test_proposal(&grace_circuit);
+        grace_proposal.set_expires_at(unix_timestamp(&now) - 1);
+        admin_shared.add_proposal(grace_proposal).unwrap();
+
+        let mut future_circuit = setup_test_circuit();
+        future_circuit.set_circuit_id("01234-LATER".to_string());
+        let mut future_proposal = setup_test_proposal(&future_circuit);
+        future_proposal.set_expires_at(unix_timestamp(&now) + 3600);
+        admin_shared.add_proposal(future_proposal).unwrap();
+
+        let circuit = setup_test_circuit();
+        admin_shared
+            .add_proposal(setup_test_proposal(&circuit))
+            .unwrap();
+
+        admin_shared.expire_proposals(now).unwrap();
+
+        assert!(!admin_shared.has_proposal("01234-EXPIR"));
+        assert!(admin_shared.has_proposal("01234-GRACE"));
+        assert!(admin_shared.has_proposal("01234-LATER"));
+        assert!(admin_shared.has_proposal("01234-ABCDE"));
+
+        let events = admin_shared
+            .get_events_since(&UNIX_EPOCH, "test_circuit")
+            .unwrap()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            messages::AdminServiceEvent::ProposalExpired(proposal) => {
+                assert_eq!(proposal.circuit_id, "01234-EXPIR")
+            }
+            event => panic!("Expected a ProposalExpired event, got {:?}", event),
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[cfg(feature = "admin-audit-log")]
+    #[test]
+    // test that expiring a proposal appends a ProposalExpired entry, with the expired proposal,
+    // to the configured audit log
+    fn test_expire_proposals_audit_log() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let mut admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+
+        let temp_dir = TempDir::new("test_expire_proposals_audit_log").unwrap();
+        let path = |file: &str| temp_dir.path().join(file).to_str().unwrap().to_string();
+        let audit_store =
+            YamlAdminServiceStore::new(path("circuits.yaml"), path("circuit_proposals.yaml"))
+                .unwrap();
+        admin_shared.set_audit_store(Arc::new(audit_store)).unwrap();
+
+        let now = SystemTime::now();
+        let circuit = setup_test_circuit();
+        let mut proposal = setup_test_proposal(&circuit);
+        proposal.set_expires_at(unix_timestamp(&now) - PROPOSAL_EXPIRY_GRACE_PERIOD - 1);
+        admin_shared.add_proposal(proposal).unwrap();
+
+        admin_shared.expire_proposals(now).unwrap();
+
+        // The entry is written in the background
+        let audit_store = admin_shared.audit_store().unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let entries = loop {
+            let entries = audit_store
+                .list_audit_entries(
+                    &[AuditPredicate::CircuitIdEq("01234-ABCDE".into())],
+                    0,
+                    None,
+                )
+                .unwrap()
+                .collect::<Vec<_>>();
+            if !entries.is_empty() || std::time::Instant::now() > deadline {
+                break entries;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].event_type(), &AuditEventType::ProposalExpired);
+        assert_eq!(entries[0].public_key(), None);
+        assert_eq!(
+            entries[0]
+                .proposal()
+                .map(|proposal| proposal.circuit_id.as_str()),
+            Some("01234-ABCDE")
+        );
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that the validate_circuit_management_payload method returns an error in case the
     // signature is empty.
@@ -3263,6 +4383,48 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that validate_payload rejects a signed vote for a proposal that does not exist, and
+    // that the reason is returned without the generic error prefix
+    fn test_validate_payload_vote_unknown_proposal() {
+        let state = setup_splinter_state();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            state,
+            Box::new(HashVerifier),
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            "memory",
+            STATE_DIR,
+        )
+        .unwrap();
+
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE);
+        header.set_requester(PUB_KEY.into());
+        header.set_requester_node_id("node_a".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_signature(HashSigner.sign(&payload.header).unwrap());
+        payload.set_circuit_proposal_vote(vote);
+
+        match shared.validate_payload(&payload) {
+            Err(err) => assert!(validation_error_message(err).contains("does not exist")),
+            Ok(_) => panic!("Should have been invalid because the proposal does not exist"),
+        }
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that the validate_circuit_management_payload method returns an error in case the header
     // requester field is empty.
@@ -3403,6 +4565,52 @@ mod tests {
         circuit
     }
 
+    /// Test that closed subscribers are removed from the subscriber map, both explicitly and when
+    /// a new subscriber is added, while open subscribers are kept.
+    #[test]
+    fn test_remove_closed_subscribers() {
+        let mut subscribers = SubscriberMap::new();
+        let closed = Arc::new(AtomicBool::new(false));
+        subscribers.add_subscriber(
+            "test".into(),
+            Box::new(MockEventSubscriber(Arc::clone(&closed))),
+        );
+        subscribers.add_subscriber(
+            ALL_EVENT_TYPES.into(),
+            Box::new(MockEventSubscriber(Arc::new(AtomicBool::new(false)))),
+        );
+
+        subscribers.remove_closed();
+        assert_eq!(2, subscriber_count(&subscribers));
+
+        closed.store(true, Ordering::SeqCst);
+        subscribers.remove_closed();
+        assert_eq!(1, subscriber_count(&subscribers));
+        assert!(!subscribers
+            .subscribers_by_type
+            .borrow()
+            .contains_key("test"));
+
+        subscribers.add_subscriber(
+            "test".into(),
+            Box::new(MockEventSubscriber(Arc::clone(&closed))),
+        );
+        subscribers.add_subscriber(
+            "test".into(),
+            Box::new(MockEventSubscriber(Arc::new(AtomicBool::new(false)))),
+        );
+        assert_eq!(2, subscriber_count(&subscribers));
+    }
+
+    fn subscriber_count(subscribers: &SubscriberMap) -> usize {
+        subscribers
+            .subscribers_by_type
+            .borrow()
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+
     fn setup_test_vote(circuit: &Circuit) -> CircuitProposalVote {
         let mut circuit_vote = CircuitProposalVote::new();
         circuit_vote.set_vote(CircuitProposalVote_Vote::ACCEPT);
@@ -3413,6 +4621,14 @@ mod tests {
         circuit_vote
     }
 
+    fn setup_test_withdraw(circuit: &Circuit) -> CircuitProposalWithdraw {
+        let mut circuit_withdraw = CircuitProposalWithdraw::new();
+        circuit_withdraw.set_circuit_id(circuit.get_circuit_id().to_string());
+        circuit_withdraw.set_circuit_hash(sha256(circuit).unwrap());
+
+        circuit_withdraw
+    }
+
     fn setup_test_proposal(proposed_circuit: &Circuit) -> CircuitProposal {
         let mut circuit_proposal = CircuitProposal::new();
         circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::CREATE);
@@ -3686,6 +4902,22 @@ mod tests {
         }
     }
 
+    struct MockEventSubscriber(Arc<AtomicBool>);
+
+    impl AdminServiceEventSubscriber for MockEventSubscriber {
+        fn handle_event(
+            &self,
+            _admin_service_event: &messages::AdminServiceEvent,
+            _timestamp: &SystemTime,
+        ) -> Result<(), AdminSubscriberError> {
+            Ok(())
+        }
+
+        fn is_closed(&self) -> bool {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
     struct MockAdminKeyVerifier(bool);
 
     impl MockAdminKeyVerifier {
@@ -0,0 +1,639 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Evaluation and validation of the voting policies and signer quorums of circuit proposals.
+
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+
+use crate::hex::to_hex;
+use crate::protos::admin::{
+    CircuitProposal, CircuitProposalVote_Vote, SignerQuorum, SplinterNode, VotingPolicy,
+    VotingPolicy_PolicyType,
+};
+
+#[derive(Debug, PartialEq)]
+pub(super) enum CircuitProposalStatus {
+    Accepted,
+    Rejected,
+    Pending,
+}
+
+/// Determines the status of a circuit proposal by applying its voting policy to the votes it has
+/// received. The requester's node is counted as having voted to accept.
+pub(super) fn proposal_status(proposal: &CircuitProposal) -> CircuitProposalStatus {
+    let votes = node_votes(proposal);
+    let members = proposal
+        .get_circuit_proposal()
+        .get_members()
+        .iter()
+        .map(|member| member.get_node_id())
+        .collect::<Vec<_>>();
+    let policy = proposal.get_voting_policy();
+
+    match policy.get_policy_type() {
+        VotingPolicy_PolicyType::UNANIMOUS => {
+            if votes
+                .values()
+                .any(|vote| *vote == CircuitProposalVote_Vote::REJECT)
+            {
+                CircuitProposalStatus::Rejected
+            } else if members
+                .iter()
+                .all(|member| votes.get(member) == Some(&CircuitProposalVote_Vote::ACCEPT))
+            {
+                CircuitProposalStatus::Accepted
+            } else {
+                CircuitProposalStatus::Pending
+            }
+        }
+        VotingPolicy_PolicyType::THRESHOLD => {
+            tally(&members, &votes, policy.get_threshold(), |_| 1)
+        }
+        VotingPolicy_PolicyType::WEIGHTED => {
+            let weights = policy
+                .get_weights()
+                .iter()
+                .map(|node_weight| (node_weight.get_node_id(), node_weight.get_weight()))
+                .collect::<HashMap<_, _>>();
+            tally(&members, &votes, policy.get_threshold(), |member| {
+                weights.get(member).copied().unwrap_or(0)
+            })
+        }
+        VotingPolicy_PolicyType::REQUIRED_APPROVERS => {
+            let approver_votes = policy
+                .get_required_approvers()
+                .iter()
+                .map(|approver| votes.get(approver.as_str()))
+                .collect::<Vec<_>>();
+            if approver_votes
+                .iter()
+                .any(|vote| *vote == Some(&CircuitProposalVote_Vote::REJECT))
+            {
+                CircuitProposalStatus::Rejected
+            } else if approver_votes
+                .iter()
+                .all(|vote| *vote == Some(&CircuitProposalVote_Vote::ACCEPT))
+            {
+                CircuitProposalStatus::Accepted
+            } else {
+                CircuitProposalStatus::Pending
+            }
+        }
+    }
+}
+
+/// Validates that a voting policy is consistent with the members of the proposed circuit.
+pub(super) fn validate_voting_policy(
+    policy: &VotingPolicy,
+    members: &[SplinterNode],
+) -> Result<(), String> {
+    let member_ids = members
+        .iter()
+        .map(|member| member.get_node_id())
+        .collect::<HashSet<_>>();
+
+    match policy.get_policy_type() {
+        VotingPolicy_PolicyType::UNANIMOUS => {
+            if policy.get_threshold() != 0
+                || !policy.get_weights().is_empty()
+                || !policy.get_required_approvers().is_empty()
+            {
+                return Err(
+                    "Unanimous voting policy must not have a threshold, weights or \
+                     required approvers"
+                        .to_string(),
+                );
+            }
+        }
+        VotingPolicy_PolicyType::THRESHOLD => {
+            let threshold = policy.get_threshold() as usize;
+            if threshold == 0 || threshold > member_ids.len() {
+                return Err(format!(
+                    "Voting threshold must be between 1 and the number of members ({}), was {}",
+                    member_ids.len(),
+                    threshold
+                ));
+            }
+        }
+        VotingPolicy_PolicyType::WEIGHTED => {
+            if policy.get_weights().is_empty() {
+                return Err("Weighted voting policy must have at least one node weight".to_string());
+            }
+
+            let mut weighted_nodes = HashSet::new();
+            let mut total_weight: u64 = 0;
+            for node_weight in policy.get_weights() {
+                let node_id = node_weight.get_node_id();
+                if !member_ids.contains(node_id) {
+                    return Err(format!("Weighted node {} is not a member", node_id));
+                }
+                if !weighted_nodes.insert(node_id) {
+                    return Err(format!("Duplicate weight for node {}", node_id));
+                }
+                if node_weight.get_weight() == 0 {
+                    return Err(format!(
+                        "Weight for node {} must be greater than 0",
+                        node_id
+                    ));
+                }
+                total_weight += u64::from(node_weight.get_weight());
+            }
+
+            let threshold = u64::from(policy.get_threshold());
+            if threshold == 0 || threshold > total_weight {
+                return Err(format!(
+                    "Voting threshold must be between 1 and the total weight ({}), was {}",
+                    total_weight, threshold
+                ));
+            }
+        }
+        VotingPolicy_PolicyType::REQUIRED_APPROVERS => {
+            if policy.get_required_approvers().is_empty() {
+                return Err(
+                    "Required approvers voting policy must have at least one approver".to_string(),
+                );
+            }
+
+            let mut approvers = HashSet::new();
+            for approver in policy.get_required_approvers() {
+                if !member_ids.contains(approver.as_str()) {
+                    return Err(format!("Required approver {} is not a member", approver));
+                }
+                if !approvers.insert(approver) {
+                    return Err(format!("Duplicate required approver {}", approver));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates that a signer quorum only refers to members of the proposed circuit, each requiring
+/// at least one signer.
+///
+/// The proposal is signed by a single key of the requester's node, which counts as that node's
+/// vote, so the requester's node may not require more than one signer. The quorum's default number
+/// of required signers only applies to the other members.
+pub(super) fn validate_signer_quorum(
+    quorum: &SignerQuorum,
+    members: &[SplinterNode],
+    requester_node_id: &str,
+) -> Result<(), String> {
+    let member_ids = members
+        .iter()
+        .map(|member| member.get_node_id())
+        .collect::<HashSet<_>>();
+
+    let mut quorum_nodes = HashSet::new();
+    for node_signers in quorum.get_node_signers() {
+        let node_id = node_signers.get_node_id();
+        if !member_ids.contains(node_id) {
+            return Err(format!("Signer quorum node {} is not a member", node_id));
+        }
+        if !quorum_nodes.insert(node_id) {
+            return Err(format!("Duplicate signer quorum for node {}", node_id));
+        }
+        if node_signers.get_required_signers() == 0 {
+            return Err(format!(
+                "Required signers for node {} must be greater than 0",
+                node_id
+            ));
+        }
+        if node_id == requester_node_id && node_signers.get_required_signers() > 1 {
+            return Err(format!(
+                "Required signers for requester node {} must be 1",
+                node_id
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates that the given node may vote on the circuit proposal under its voting policy.
+pub(super) fn validate_voter(proposal: &CircuitProposal, node_id: &str) -> Result<(), String> {
+    if !proposal
+        .get_circuit_proposal()
+        .get_members()
+        .iter()
+        .any(|member| member.get_node_id() == node_id)
+    {
+        return Err(format!(
+            "Node {} is not a member of the proposed circuit {}",
+            node_id,
+            proposal.get_circuit_id()
+        ));
+    }
+
+    let policy = proposal.get_voting_policy();
+    if policy.get_policy_type() == VotingPolicy_PolicyType::WEIGHTED
+        && !policy
+            .get_weights()
+            .iter()
+            .any(|node_weight| node_weight.get_node_id() == node_id)
+    {
+        return Err(format!(
+            "Node {} has no vote weight for the proposed circuit {}",
+            node_id,
+            proposal.get_circuit_id()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates that the given key may sign a vote for the node: the key must not have voted on the
+/// proposal already, and the node's vote must not have been decided by its other keys.
+pub(super) fn validate_signer(
+    proposal: &CircuitProposal,
+    node_id: &str,
+    public_key: &[u8],
+) -> Result<(), String> {
+    if proposal
+        .get_votes()
+        .iter()
+        .any(|vote| vote.get_public_key() == public_key)
+    {
+        return Err(format!(
+            "Received duplicate vote from {} for {}",
+            to_hex(public_key),
+            proposal.get_circuit_id()
+        ));
+    }
+
+    if node_votes(proposal).contains_key(node_id) {
+        return Err(format!(
+            "Received vote from {} after its vote for {} was decided",
+            node_id,
+            proposal.get_circuit_id()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the number of distinct keys that must sign to accept on behalf of the given node.
+fn required_signers(proposal: &CircuitProposal, node_id: &str) -> usize {
+    let quorum = proposal.get_signer_quorum();
+    let required_signers = quorum
+        .get_node_signers()
+        .iter()
+        .find(|node_signers| node_signers.get_node_id() == node_id)
+        .map(|node_signers| node_signers.get_required_signers())
+        .unwrap_or_else(|| quorum.get_required_signers());
+
+    cmp::max(required_signers, 1) as usize
+}
+
+/// Returns the vote of each node whose vote has been decided, including the requester's node.
+///
+/// A node has accepted once its quorum of keys has signed an ACCEPT vote, and has rejected as soon
+/// as any one of its keys has signed a REJECT vote.
+fn node_votes(proposal: &CircuitProposal) -> HashMap<&str, CircuitProposalVote_Vote> {
+    let mut votes = HashMap::new();
+    votes.insert(
+        proposal.get_requester_node_id(),
+        CircuitProposalVote_Vote::ACCEPT,
+    );
+
+    let mut accept_signers: HashMap<&str, HashSet<&[u8]>> = HashMap::new();
+    for vote in proposal.get_votes() {
+        match vote.get_vote() {
+            CircuitProposalVote_Vote::REJECT => {
+                votes.insert(vote.get_voter_node_id(), CircuitProposalVote_Vote::REJECT);
+            }
+            CircuitProposalVote_Vote::ACCEPT => {
+                accept_signers
+                    .entry(vote.get_voter_node_id())
+                    .or_insert_with(HashSet::new)
+                    .insert(vote.get_public_key());
+            }
+            CircuitProposalVote_Vote::UNSET_VOTE => (),
+        }
+    }
+
+    for (node_id, signers) in accept_signers {
+        if signers.len() >= required_signers(proposal, node_id) {
+            votes
+                .entry(node_id)
+                .or_insert(CircuitProposalVote_Vote::ACCEPT);
+        }
+    }
+
+    votes
+}
+
+/// Accepts once the accepting members' weight reaches the threshold, and rejects once the
+/// members that have not rejected can no longer reach it.
+fn tally<F>(
+    members: &[&str],
+    votes: &HashMap<&str, CircuitProposalVote_Vote>,
+    threshold: u32,
+    weight: F,
+) -> CircuitProposalStatus
+where
+    F: Fn(&str) -> u32,
+{
+    let threshold = u64::from(threshold);
+    let mut accepted: u64 = 0;
+    let mut not_rejected: u64 = 0;
+    for member in members {
+        match votes.get(member) {
+            Some(CircuitProposalVote_Vote::ACCEPT) => {
+                accepted += u64::from(weight(member));
+                not_rejected += u64::from(weight(member));
+            }
+            Some(_) => (),
+            None => not_rejected += u64::from(weight(member)),
+        }
+    }
+
+    if accepted >= threshold {
+        CircuitProposalStatus::Accepted
+    } else if not_rejected < threshold {
+        CircuitProposalStatus::Rejected
+    } else {
+        CircuitProposalStatus::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use protobuf::RepeatedField;
+
+    use crate::protos::admin::{
+        Circuit, CircuitProposal_VoteRecord, SignerQuorum_NodeSigners, VotingPolicy_NodeWeight,
+    };
+
+    // Test that a unanimous policy accepts once every member has accepted, and rejects on a
+    // single rejection
+    #[test]
+    fn test_unanimous() {
+        let policy = VotingPolicy::new();
+
+        let proposal = setup_proposal(policy.clone(), &[("node_b", true)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Pending);
+
+        let proposal = setup_proposal(policy.clone(), &[("node_b", true), ("node_c", true)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Accepted);
+
+        let proposal = setup_proposal(policy, &[("node_b", false)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Rejected);
+    }
+
+    // Test that a threshold policy accepts once enough members have accepted, and rejects once
+    // the threshold can no longer be reached
+    #[test]
+    fn test_threshold() {
+        let mut policy = VotingPolicy::new();
+        policy.set_policy_type(VotingPolicy_PolicyType::THRESHOLD);
+        policy.set_threshold(2);
+
+        let proposal = setup_proposal(policy.clone(), &[]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Pending);
+
+        let proposal = setup_proposal(policy.clone(), &[("node_b", false), ("node_c", true)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Accepted);
+
+        policy.set_threshold(3);
+        let proposal = setup_proposal(policy, &[("node_b", false)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Rejected);
+    }
+
+    // Test that a weighted policy only counts the weights of accepting members
+    #[test]
+    fn test_weighted() {
+        let mut policy = VotingPolicy::new();
+        policy.set_policy_type(VotingPolicy_PolicyType::WEIGHTED);
+        policy.set_weights(RepeatedField::from_vec(vec![
+            node_weight("node_a", 1),
+            node_weight("node_b", 1),
+            node_weight("node_c", 3),
+        ]));
+        policy.set_threshold(3);
+
+        let proposal = setup_proposal(policy.clone(), &[("node_b", true)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Pending);
+
+        let proposal = setup_proposal(policy.clone(), &[("node_c", true)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Accepted);
+
+        let proposal = setup_proposal(policy, &[("node_c", false)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Rejected);
+    }
+
+    // Test that a required approvers policy ignores the votes of other members
+    #[test]
+    fn test_required_approvers() {
+        let mut policy = VotingPolicy::new();
+        policy.set_policy_type(VotingPolicy_PolicyType::REQUIRED_APPROVERS);
+        policy.set_required_approvers(RepeatedField::from_vec(vec!["node_c".to_string()]));
+
+        let proposal = setup_proposal(policy.clone(), &[("node_b", false)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Pending);
+
+        let proposal = setup_proposal(policy.clone(), &[("node_c", true)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Accepted);
+
+        let proposal = setup_proposal(policy, &[("node_b", true), ("node_c", false)]);
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Rejected);
+    }
+
+    // Test that voting policies that are inconsistent with the circuit's members are invalid
+    #[test]
+    fn test_validate_voting_policy() {
+        let members = setup_circuit().take_members().into_vec();
+
+        assert!(validate_voting_policy(&VotingPolicy::new(), &members).is_ok());
+
+        let mut threshold = VotingPolicy::new();
+        threshold.set_policy_type(VotingPolicy_PolicyType::THRESHOLD);
+        threshold.set_threshold(3);
+        assert!(validate_voting_policy(&threshold, &members).is_ok());
+        threshold.set_threshold(4);
+        assert!(validate_voting_policy(&threshold, &members).is_err());
+        threshold.set_threshold(0);
+        assert!(validate_voting_policy(&threshold, &members).is_err());
+
+        let mut weighted = VotingPolicy::new();
+        weighted.set_policy_type(VotingPolicy_PolicyType::WEIGHTED);
+        weighted.set_weights(RepeatedField::from_vec(vec![
+            node_weight("node_a", 1),
+            node_weight("node_b", 2),
+        ]));
+        weighted.set_threshold(3);
+        assert!(validate_voting_policy(&weighted, &members).is_ok());
+        weighted.set_threshold(4);
+        assert!(validate_voting_policy(&weighted, &members).is_err());
+        weighted.set_threshold(1);
+        weighted.mut_weights().push(node_weight("node_d", 1));
+        assert!(validate_voting_policy(&weighted, &members).is_err());
+
+        let mut approvers = VotingPolicy::new();
+        approvers.set_policy_type(VotingPolicy_PolicyType::REQUIRED_APPROVERS);
+        assert!(validate_voting_policy(&approvers, &members).is_err());
+        approvers.set_required_approvers(RepeatedField::from_vec(vec!["node_c".to_string()]));
+        assert!(validate_voting_policy(&approvers, &members).is_ok());
+        approvers
+            .mut_required_approvers()
+            .push("node_c".to_string());
+        assert!(validate_voting_policy(&approvers, &members).is_err());
+    }
+
+    // Test that a node only accepts once its quorum of keys has signed, and that a single key
+    // rejects on behalf of its node
+    #[test]
+    fn test_signer_quorum() {
+        let mut quorum = SignerQuorum::new();
+        quorum.set_required_signers(1);
+        let mut node_signers = SignerQuorum_NodeSigners::new();
+        node_signers.set_node_id("node_b".to_string());
+        node_signers.set_required_signers(2);
+        quorum.set_node_signers(RepeatedField::from_vec(vec![node_signers]));
+
+        let mut proposal = setup_proposal(VotingPolicy::new(), &[("node_c", true)]);
+        proposal.set_signer_quorum(quorum);
+
+        proposal
+            .mut_votes()
+            .push(vote_record("node_b", b"officer_1", true));
+        assert_eq!(proposal_status(&proposal), CircuitProposalStatus::Pending);
+        assert!(validate_signer(&proposal, "node_b", b"officer_1").is_err());
+        assert!(validate_signer(&proposal, "node_b", b"officer_2").is_ok());
+        assert!(validate_signer(&proposal, "node_c", b"node_c_2").is_err());
+
+        let mut accepted = proposal.clone();
+        accepted
+            .mut_votes()
+            .push(vote_record("node_b", b"officer_2", true));
+        assert_eq!(proposal_status(&accepted), CircuitProposalStatus::Accepted);
+
+        let mut rejected = proposal;
+        rejected
+            .mut_votes()
+            .push(vote_record("node_b", b"officer_2", false));
+        assert_eq!(proposal_status(&rejected), CircuitProposalStatus::Rejected);
+    }
+
+    // Test that signer quorums may only refer to members, and require at least one signer and at
+    // most one signer for the requester's node
+    #[test]
+    fn test_validate_signer_quorum() {
+        let members = setup_circuit().take_members().into_vec();
+
+        let mut quorum = SignerQuorum::new();
+        assert!(validate_signer_quorum(&quorum, &members, "node_a").is_ok());
+
+        let mut node_signers = SignerQuorum_NodeSigners::new();
+        node_signers.set_node_id("node_b".to_string());
+        node_signers.set_required_signers(2);
+        quorum.mut_node_signers().push(node_signers.clone());
+        assert!(validate_signer_quorum(&quorum, &members, "node_a").is_ok());
+
+        quorum.mut_node_signers().push(node_signers.clone());
+        assert!(validate_signer_quorum(&quorum, &members, "node_a").is_err());
+
+        node_signers.set_node_id("node_d".to_string());
+        quorum.set_node_signers(RepeatedField::from_vec(vec![node_signers.clone()]));
+        assert!(validate_signer_quorum(&quorum, &members, "node_a").is_err());
+
+        node_signers.set_node_id("node_b".to_string());
+        node_signers.set_required_signers(0);
+        quorum.set_node_signers(RepeatedField::from_vec(vec![node_signers.clone()]));
+        assert!(validate_signer_quorum(&quorum, &members, "node_a").is_err());
+
+        node_signers.set_node_id("node_a".to_string());
+        node_signers.set_required_signers(2);
+        quorum.set_node_signers(RepeatedField::from_vec(vec![node_signers.clone()]));
+        assert!(validate_signer_quorum(&quorum, &members, "node_a").is_err());
+
+        node_signers.set_required_signers(1);
+        quorum.set_node_signers(RepeatedField::from_vec(vec![node_signers]));
+        quorum.set_required_signers(2);
+        assert!(validate_signer_quorum(&quorum, &members, "node_a").is_ok());
+    }
+
+    // Test that only members, and only weighted members under a weighted policy, may vote
+    #[test]
+    fn test_validate_voter() {
+        let proposal = setup_proposal(VotingPolicy::new(), &[]);
+        assert!(validate_voter(&proposal, "node_b").is_ok());
+        assert!(validate_voter(&proposal, "node_d").is_err());
+
+        let mut policy = VotingPolicy::new();
+        policy.set_policy_type(VotingPolicy_PolicyType::WEIGHTED);
+        policy.set_weights(RepeatedField::from_vec(vec![node_weight("node_b", 1)]));
+        policy.set_threshold(1);
+        let proposal = setup_proposal(policy, &[]);
+        assert!(validate_voter(&proposal, "node_b").is_ok());
+        assert!(validate_voter(&proposal, "node_c").is_err());
+    }
+
+    fn setup_circuit() -> Circuit {
+        let mut circuit = Circuit::new();
+        circuit.set_circuit_id("01234-ABCDE".to_string());
+        circuit.set_members(RepeatedField::from_vec(
+            ["node_a", "node_b", "node_c"]
+                .iter()
+                .map(|node_id| {
+                    let mut node = SplinterNode::new();
+                    node.set_node_id(node_id.to_string());
+                    node
+                })
+                .collect(),
+        ));
+
+        circuit
+    }
+
+    // Creates a proposal requested by node_a, with the given votes (true for accept)
+    fn setup_proposal(policy: VotingPolicy, votes: &[(&str, bool)]) -> CircuitProposal {
+        let mut proposal = CircuitProposal::new();
+        proposal.set_circuit_id("01234-ABCDE".to_string());
+        proposal.set_circuit_proposal(setup_circuit());
+        proposal.set_requester_node_id("node_a".to_string());
+        proposal.set_voting_policy(policy);
+        proposal.set_votes(RepeatedField::from_vec(
+            votes
+                .iter()
+                .map(|(node_id, accept)| vote_record(node_id, node_id.as_bytes(), *accept))
+                .collect(),
+        ));
+
+        proposal
+    }
+
+    fn vote_record(node_id: &str, public_key: &[u8], accept: bool) -> CircuitProposal_VoteRecord {
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_voter_node_id(node_id.to_string());
+        vote_record.set_public_key(public_key.to_vec());
+        vote_record.set_vote(if accept {
+            CircuitProposalVote_Vote::ACCEPT
+        } else {
+            CircuitProposalVote_Vote::REJECT
+        });
+        vote_record
+    }
+
+    fn node_weight(node_id: &str, weight: u32) -> VotingPolicy_NodeWeight {
+        let mut node_weight = VotingPolicy_NodeWeight::new();
+        node_weight.set_node_id(node_id.to_string());
+        node_weight.set_weight(weight);
+        node_weight
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structs for building the entries of the circuit proposal audit log
+
+use crate::admin::messages::CircuitProposal;
+
+use super::error::BuilderError;
+use super::Vote;
+
+/// Native representation of an entry in the audit log. Each entry records an event in the
+/// lifecycle of a circuit proposal.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    circuit_id: String,
+    event_type: AuditEventType,
+    timestamp: u64,
+    circuit_hash: String,
+    public_key: Option<Vec<u8>>,
+    node_id: Option<String>,
+    vote: Option<Vote>,
+    proposal: Option<CircuitProposal>,
+}
+
+impl AuditEntry {
+    /// Returns the ID of the circuit the event applies to
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the type of the event
+    pub fn event_type(&self) -> &AuditEventType {
+        &self.event_type
+    }
+
+    /// Returns the time of the event, in seconds since the Unix epoch
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns the hash of the proposed circuit definition, which identifies the version of the
+    /// circuit that results if the proposal is accepted
+    pub fn circuit_hash(&self) -> &str {
+        &self.circuit_hash
+    }
+
+    /// Returns the public key that signed the payload that caused the event, if any
+    pub fn public_key(&self) -> Option<&[u8]> {
+        self.public_key.as_deref()
+    }
+
+    /// Returns the node that submitted the payload that caused the event, if any
+    pub fn node_id(&self) -> Option<&str> {
+        self.node_id.as_deref()
+    }
+
+    /// Returns the vote that caused the event, if any
+    pub fn vote(&self) -> Option<&Vote> {
+        self.vote.as_ref()
+    }
+
+    /// Returns the proposal as it was after the event, if recorded
+    pub fn proposal(&self) -> Option<&CircuitProposal> {
+        self.proposal.as_ref()
+    }
+
+    /// Returns the proposal as it was after the event, if recorded, consuming the entry
+    pub fn into_proposal(self) -> Option<CircuitProposal> {
+        self.proposal
+    }
+}
+
+#[derive(Default)]
+pub struct AuditEntryBuilder {
+    circuit_id: Option<String>,
+    event_type: Option<AuditEventType>,
+    timestamp: Option<u64>,
+    circuit_hash: Option<String>,
+    public_key: Option<Vec<u8>>,
+    node_id: Option<String>,
+    vote: Option<Vote>,
+    proposal: Option<CircuitProposal>,
+}
+
+impl AuditEntryBuilder {
+    pub fn new() -> Self {
+        AuditEntryBuilder::default()
+    }
+
+    pub fn with_circuit_id(mut self, circuit_id: &str) -> AuditEntryBuilder {
+        self.circuit_id = Some(circuit_id.to_string());
+        self
+    }
+
+    pub fn with_event_type(mut self, event_type: &AuditEventType) -> AuditEntryBuilder {
+        self.event_type = Some(event_type.clone());
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> AuditEntryBuilder {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn with_circuit_hash(mut self, circuit_hash: &str) -> AuditEntryBuilder {
+        self.circuit_hash = Some(circuit_hash.to_string());
+        self
+    }
+
+    pub fn with_public_key(mut self, public_key: &[u8]) -> AuditEntryBuilder {
+        self.public_key = Some(public_key.to_vec());
+        self
+    }
+
+    pub fn with_node_id(mut self, node_id: &str) -> AuditEntryBuilder {
+        self.node_id = Some(node_id.to_string());
+        self
+    }
+
+    pub fn with_vote(mut self, vote: &Vote) -> AuditEntryBuilder {
+        self.vote = Some(vote.clone());
+        self
+    }
+
+    pub fn with_proposal(mut self, proposal: &CircuitProposal) -> AuditEntryBuilder {
+        self.proposal = Some(proposal.clone());
+        self
+    }
+
+    pub fn build(self) -> Result<AuditEntry, BuilderError> {
+        let circuit_id = self
+            .circuit_id
+            .ok_or_else(|| BuilderError::MissingField("circuit_id".to_string()))?;
+
+        let event_type = self
+            .event_type
+            .ok_or_else(|| BuilderError::MissingField("event_type".to_string()))?;
+
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| BuilderError::MissingField("timestamp".to_string()))?;
+
+        let circuit_hash = self
+            .circuit_hash
+            .ok_or_else(|| BuilderError::MissingField("circuit_hash".to_string()))?;
+
+        Ok(AuditEntry {
+            circuit_id,
+            event_type,
+            timestamp,
+            circuit_hash,
+            public_key: self.public_key,
+            node_id: self.node_id,
+            vote: self.vote,
+            proposal: self.proposal,
+        })
+    }
+}
+
+/// Represents the events in the lifecycle of a circuit proposal that are recorded in the audit log
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum AuditEventType {
+    ProposalSubmitted,
+    ProposalVote,
+    ProposalAccepted,
+    ProposalRejected,
+    ProposalWithdrawn,
+    ProposalExpired,
+}
@@ -0,0 +1,39 @@
+---- Copyright 2018-2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id                        INTEGER PRIMARY KEY AUTOINCREMENT,
+    circuit_id                TEXT NOT NULL,
+    event_type                TEXT NOT NULL,
+    timestamp                 BIGINT NOT NULL,
+    circuit_hash              TEXT NOT NULL,
+    circuit_management_type   TEXT,
+    public_key                BINARY,
+    node_id                   TEXT,
+    vote                      TEXT,
+    proposal                  TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_circuit_id ON admin_audit_log (circuit_id);
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_event_type ON admin_audit_log (event_type);
+
+-- The members of the proposed circuit recorded with each entry, so entries may be filtered by
+-- member without deserializing the recorded proposals
+CREATE TABLE IF NOT EXISTS admin_audit_log_member (
+    audit_entry_id            BIGINT NOT NULL,
+    node_id                   TEXT NOT NULL,
+    PRIMARY KEY (audit_entry_id, node_id),
+    FOREIGN KEY (audit_entry_id) REFERENCES admin_audit_log(id) ON DELETE CASCADE
+);
@@ -29,15 +29,18 @@ mod schema;
 use diesel::r2d2::{ConnectionManager, Pool};
 
 use crate::admin::store::{
-    error::AdminServiceStoreError, AdminServiceStore, Circuit, CircuitNode, CircuitPredicate,
-    CircuitProposal, Service, ServiceId,
+    error::AdminServiceStoreError, AdminServiceStore, AuditEntry, AuditPredicate, Circuit,
+    CircuitNode, CircuitPredicate, CircuitProposal, Service, ServiceId,
 };
+use operations::add_audit_entry::AdminServiceStoreAddAuditEntryOperation as _;
 use operations::add_circuit::AdminServiceStoreAddCircuitOperation as _;
 use operations::add_proposal::AdminServiceStoreAddProposalOperation as _;
+use operations::count_audit_entries::AdminServiceStoreCountAuditEntriesOperation as _;
 use operations::get_circuit::AdminServiceStoreFetchCircuitOperation as _;
 use operations::get_node::AdminServiceStoreFetchNodeOperation as _;
 use operations::get_proposal::AdminServiceStoreFetchProposalOperation as _;
 use operations::get_service::AdminServiceStoreFetchServiceOperation as _;
+use operations::list_audit_entries::AdminServiceStoreListAuditEntriesOperation as _;
 use operations::list_circuits::AdminServiceStoreListCircuitsOperation as _;
 use operations::list_nodes::AdminServiceStoreListNodesOperation as _;
 use operations::list_proposals::AdminServiceStoreListProposalsOperation as _;
@@ -166,6 +169,28 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
     ) -> Result<Box<dyn ExactSizeIterator<Item = Service>>, AdminServiceStoreError> {
         AdminServiceStoreOperations::new(&*self.connection_pool.get()?).list_services(circuit_id)
     }
+
+    fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?).add_audit_entry(entry)
+    }
+
+    fn list_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?)
+            .list_audit_entries(predicates, offset, limit)
+    }
+
+    fn count_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+    ) -> Result<usize, AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?)
+            .count_audit_entries(predicates)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -251,11 +276,37 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
     ) -> Result<Box<dyn ExactSizeIterator<Item = Service>>, AdminServiceStoreError> {
         AdminServiceStoreOperations::new(&*self.connection_pool.get()?).list_services(circuit_id)
     }
+
+    fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?).add_audit_entry(entry)
+    }
+
+    fn list_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?)
+            .list_audit_entries(predicates, offset, limit)
+    }
+
+    fn count_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+    ) -> Result<usize, AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?)
+            .count_audit_entries(predicates)
+    }
 }
 
 #[cfg(all(test, feature = "sqlite"))]
 pub mod tests {
+    use super::*;
+
+    use crate::admin::messages;
     use crate::admin::store::diesel::migrations::run_sqlite_migrations;
+    use crate::admin::store::{AuditEntryBuilder, AuditEventType, Vote};
 
     use diesel::{
         r2d2::{ConnectionManager, Pool},
@@ -267,6 +318,189 @@ pub mod tests {
         create_connection_pool_and_migrate();
     }
 
+    /// Verify that audit entries are listed in the order they were added, and that they may be
+    /// filtered by circuit ID and event type.
+    #[test]
+    fn test_audit_entries() {
+        let store = DieselAdminServiceStore::new(create_connection_pool_and_migrate());
+
+        let submitted = AuditEntryBuilder::new()
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_event_type(&AuditEventType::ProposalSubmitted)
+            .with_timestamp(1_600_000_000)
+            .with_circuit_hash("7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
+            .with_public_key(b"requester_key")
+            .with_node_id("acme-node-000")
+            .build()
+            .expect("Unable to build audit entry");
+        let other_circuit = AuditEntryBuilder::new()
+            .with_circuit_id("WBKLF-CCCCC")
+            .with_event_type(&AuditEventType::ProposalExpired)
+            .with_timestamp(1_600_000_010)
+            .with_circuit_hash("8e066d41911817a42ab098eda35a2a2b11e93c753bc5ecc3ffb3e99ed99ada0d")
+            .build()
+            .expect("Unable to build audit entry");
+        let vote = AuditEntryBuilder::new()
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_event_type(&AuditEventType::ProposalVote)
+            .with_timestamp(1_600_000_020)
+            .with_circuit_hash("7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
+            .with_public_key(b"voter_key")
+            .with_node_id("bubba-node-000")
+            .with_vote(&Vote::Accept)
+            .build()
+            .expect("Unable to build audit entry");
+
+        for entry in vec![submitted.clone(), other_circuit.clone(), vote.clone()] {
+            store
+                .add_audit_entry(entry)
+                .expect("Unable to add audit entry");
+        }
+
+        let entries = store
+            .list_audit_entries(&[], 0, None)
+            .expect("Unable to list audit entries")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![submitted.clone(), other_circuit.clone(), vote.clone()]
+        );
+
+        let entries = store
+            .list_audit_entries(
+                &[AuditPredicate::CircuitIdEq("WBKLF-BBBBB".into())],
+                0,
+                None,
+            )
+            .expect("Unable to list audit entries")
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![submitted, vote.clone()]);
+
+        let entries = store
+            .list_audit_entries(
+                &[
+                    AuditPredicate::CircuitIdEq("WBKLF-BBBBB".into()),
+                    AuditPredicate::EventTypeEq(AuditEventType::ProposalVote),
+                ],
+                0,
+                None,
+            )
+            .expect("Unable to list audit entries")
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![vote]);
+    }
+
+    /// Verify that audit entries may be filtered by the management type and members of their
+    /// recorded proposals, and that a page of the matching entries may be listed and counted.
+    #[test]
+    fn test_audit_entries_filtered_and_paged() {
+        let store = DieselAdminServiceStore::new(create_connection_pool_and_migrate());
+
+        let entries = vec![
+            accepted_entry(
+                "WBKLF-AAAAA",
+                "gameroom",
+                &["acme-node-000", "bubba-node-000"],
+            ),
+            accepted_entry(
+                "WBKLF-BBBBB",
+                "gameroom",
+                &["acme-node-000", "cargill-node-000"],
+            ),
+            accepted_entry("WBKLF-CCCCC", "other", &["acme-node-000", "bubba-node-000"]),
+            accepted_entry(
+                "WBKLF-DDDDD",
+                "gameroom",
+                &["acme-node-000", "bubba-node-000"],
+            ),
+        ];
+        for entry in entries.iter().cloned() {
+            store
+                .add_audit_entry(entry)
+                .expect("Unable to add audit entry");
+        }
+
+        let predicates = [
+            AuditPredicate::EventTypeEq(AuditEventType::ProposalAccepted),
+            AuditPredicate::ManagementTypeEq("gameroom".into()),
+            AuditPredicate::MembersInclude(vec!["bubba-node-000".into()]),
+        ];
+        assert_eq!(
+            store
+                .count_audit_entries(&predicates)
+                .expect("Unable to count audit entries"),
+            2
+        );
+        assert_eq!(
+            store
+                .list_audit_entries(&predicates, 0, None)
+                .expect("Unable to list audit entries")
+                .collect::<Vec<_>>(),
+            vec![entries[0].clone(), entries[3].clone()]
+        );
+        assert_eq!(
+            store
+                .list_audit_entries(&predicates, 1, Some(1))
+                .expect("Unable to list audit entries")
+                .collect::<Vec<_>>(),
+            vec![entries[3].clone()]
+        );
+
+        let all_accepted = [AuditPredicate::EventTypeEq(
+            AuditEventType::ProposalAccepted,
+        )];
+        assert_eq!(
+            store
+                .list_audit_entries(&all_accepted, 1, Some(2))
+                .expect("Unable to list audit entries")
+                .collect::<Vec<_>>(),
+            vec![entries[1].clone(), entries[2].clone()]
+        );
+    }
+
+    /// Builds an entry for the acceptance of a proposal with the given management type and
+    /// members.
+    fn accepted_entry(circuit_id: &str, management_type: &str, members: &[&str]) -> AuditEntry {
+        let proposal = messages::CircuitProposal {
+            proposal_type: messages::ProposalType::Create,
+            circuit_id: circuit_id.into(),
+            circuit_hash: "7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d".into(),
+            circuit: messages::CreateCircuit {
+                circuit_id: circuit_id.into(),
+                roster: vec![],
+                members: members
+                    .iter()
+                    .map(|node_id| messages::SplinterNode {
+                        node_id: node_id.to_string(),
+                        endpoints: vec![],
+                    })
+                    .collect(),
+                authorization_type: messages::AuthorizationType::Trust,
+                persistence: messages::PersistenceType::Any,
+                durability: messages::DurabilityType::NoDurability,
+                routes: messages::RouteType::Any,
+                circuit_management_type: management_type.into(),
+                application_metadata: vec![],
+                comments: "test circuit".into(),
+            },
+            votes: vec![],
+            requester: b"requester_key".to_vec(),
+            requester_node_id: members[0].into(),
+            expires_at: None,
+            voting_policy: messages::VotingPolicy::default(),
+            signer_quorum: messages::SignerQuorum::default(),
+        };
+
+        AuditEntryBuilder::new()
+            .with_circuit_id(circuit_id)
+            .with_event_type(&AuditEventType::ProposalAccepted)
+            .with_timestamp(1_600_000_000)
+            .with_circuit_hash(&proposal.circuit_hash)
+            .with_proposal(&proposal)
+            .build()
+            .expect("Unable to build audit entry")
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection ensures that the same DB is used for all operations.
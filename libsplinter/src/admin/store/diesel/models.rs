@@ -19,14 +19,14 @@
 use std::convert::TryFrom;
 
 use crate::admin::store::diesel::schema::{
-    circuit, circuit_member, circuit_proposal, node_endpoint, proposed_circuit, proposed_node,
-    proposed_node_endpoint, proposed_service, proposed_service_argument, service, service_argument,
-    vote_record,
+    admin_audit_log, admin_audit_log_member, circuit, circuit_member, circuit_proposal,
+    node_endpoint, proposed_circuit, proposed_node, proposed_node_endpoint, proposed_service,
+    proposed_service_argument, service, service_argument, vote_record,
 };
 use crate::admin::store::error::AdminServiceStoreError;
 use crate::admin::store::{
-    AuthorizationType, DurabilityType, PersistenceType, ProposalType, RouteType, Vote, VoteRecord,
-    VoteRecordBuilder,
+    AuditEntry, AuditEntryBuilder, AuditEventType, AuthorizationType, DurabilityType,
+    PersistenceType, ProposalType, RouteType, Vote, VoteRecord, VoteRecordBuilder,
 };
 use crate::admin::store::{Circuit, CircuitProposal, ProposedCircuit};
 
@@ -358,9 +358,166 @@ pub struct NodeEndpointModel {
     pub endpoint: String,
 }
 
+/// Database model representation of an `AuditEntry`
+#[derive(Debug, PartialEq, Queryable)]
+pub struct AuditEntryModel {
+    pub id: i64,
+    pub circuit_id: String,
+    pub event_type: String,
+    pub timestamp: i64,
+    pub circuit_hash: String,
+    pub circuit_management_type: Option<String>,
+    pub public_key: Option<Vec<u8>>,
+    pub node_id: Option<String>,
+    pub vote: Option<String>,
+    /// The JSON representation of the proposal after the event
+    pub proposal: Option<String>,
+}
+
+impl TryFrom<AuditEntryModel> for AuditEntry {
+    type Error = AdminServiceStoreError;
+    fn try_from(entry: AuditEntryModel) -> Result<Self, Self::Error> {
+        let mut builder = AuditEntryBuilder::new()
+            .with_circuit_id(&entry.circuit_id)
+            .with_event_type(&AuditEventType::try_from(entry.event_type)?)
+            .with_timestamp(entry.timestamp as u64)
+            .with_circuit_hash(&entry.circuit_hash);
+
+        if let Some(public_key) = entry.public_key {
+            builder = builder.with_public_key(&public_key);
+        }
+        if let Some(node_id) = entry.node_id {
+            builder = builder.with_node_id(&node_id);
+        }
+        if let Some(vote) = entry.vote {
+            builder = builder.with_vote(&Vote::try_from(vote)?);
+        }
+        if let Some(proposal) = entry.proposal {
+            let proposal = serde_json::from_str(&proposal).map_err(|err| {
+                AdminServiceStoreError::StorageError {
+                    context: String::from("Failed to deserialize audit entry proposal"),
+                    source: Some(Box::new(err)),
+                }
+            })?;
+            builder = builder.with_proposal(&proposal);
+        }
+
+        builder
+            .build()
+            .map_err(|err| AdminServiceStoreError::StorageError {
+                context: String::from("Failed to build AuditEntry"),
+                source: Some(Box::new(err)),
+            })
+    }
+}
+
+/// Database model representation of an `AuditEntry` that has not been added yet; its ID is
+/// assigned by the database
+#[derive(Debug, PartialEq, Insertable)]
+#[table_name = "admin_audit_log"]
+pub struct NewAuditEntryModel {
+    pub circuit_id: String,
+    pub event_type: String,
+    pub timestamp: i64,
+    pub circuit_hash: String,
+    pub circuit_management_type: Option<String>,
+    pub public_key: Option<Vec<u8>>,
+    pub node_id: Option<String>,
+    pub vote: Option<String>,
+    pub proposal: Option<String>,
+}
+
+impl TryFrom<&AuditEntry> for NewAuditEntryModel {
+    type Error = AdminServiceStoreError;
+    fn try_from(entry: &AuditEntry) -> Result<Self, Self::Error> {
+        let proposal = entry
+            .proposal()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|err| AdminServiceStoreError::StorageError {
+                context: String::from("Failed to serialize audit entry proposal"),
+                source: Some(Box::new(err)),
+            })?;
+
+        Ok(NewAuditEntryModel {
+            circuit_id: entry.circuit_id().into(),
+            event_type: String::from(entry.event_type()),
+            timestamp: entry.timestamp() as i64,
+            circuit_hash: entry.circuit_hash().into(),
+            circuit_management_type: entry
+                .proposal()
+                .map(|proposal| proposal.circuit.circuit_management_type.clone()),
+            public_key: entry.public_key().map(|public_key| public_key.to_vec()),
+            node_id: entry.node_id().map(String::from),
+            vote: entry.vote().map(String::from),
+            proposal,
+        })
+    }
+}
+
+/// Database model representation of a member of the proposed circuit recorded with an
+/// `AuditEntry`
+#[derive(Debug, PartialEq, Insertable)]
+#[table_name = "admin_audit_log_member"]
+pub struct AuditEntryMemberModel {
+    pub audit_entry_id: i64,
+    pub node_id: String,
+}
+
+impl AuditEntryMemberModel {
+    /// Returns the member models for the given entry, once the entry has been assigned an ID
+    pub fn list_from_entry(audit_entry_id: i64, entry: &AuditEntry) -> Vec<AuditEntryMemberModel> {
+        entry
+            .proposal()
+            .map(|proposal| {
+                proposal
+                    .circuit
+                    .members
+                    .iter()
+                    .map(|member| AuditEntryMemberModel {
+                        audit_entry_id,
+                        node_id: member.node_id.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
 // All enums associated with the above structs have TryFrom and From implemented in order to
 // translate the enums to a `Text` representation to be stored in the database.
 
+impl TryFrom<String> for AuditEventType {
+    type Error = AdminServiceStoreError;
+    fn try_from(variant: String) -> Result<Self, Self::Error> {
+        match variant.as_ref() {
+            "ProposalSubmitted" => Ok(AuditEventType::ProposalSubmitted),
+            "ProposalVote" => Ok(AuditEventType::ProposalVote),
+            "ProposalAccepted" => Ok(AuditEventType::ProposalAccepted),
+            "ProposalRejected" => Ok(AuditEventType::ProposalRejected),
+            "ProposalWithdrawn" => Ok(AuditEventType::ProposalWithdrawn),
+            "ProposalExpired" => Ok(AuditEventType::ProposalExpired),
+            _ => Err(AdminServiceStoreError::StorageError {
+                context: "Unable to convert string to AuditEventType".into(),
+                source: None,
+            }),
+        }
+    }
+}
+
+impl From<&AuditEventType> for String {
+    fn from(variant: &AuditEventType) -> Self {
+        match variant {
+            AuditEventType::ProposalSubmitted => String::from("ProposalSubmitted"),
+            AuditEventType::ProposalVote => String::from("ProposalVote"),
+            AuditEventType::ProposalAccepted => String::from("ProposalAccepted"),
+            AuditEventType::ProposalRejected => String::from("ProposalRejected"),
+            AuditEventType::ProposalWithdrawn => String::from("ProposalWithdrawn"),
+            AuditEventType::ProposalExpired => String::from("ProposalExpired"),
+        }
+    }
+}
+
 impl TryFrom<String> for Vote {
     type Error = AdminServiceStoreError;
     fn try_from(variant: String) -> Result<Self, Self::Error> {
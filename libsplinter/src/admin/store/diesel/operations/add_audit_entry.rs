@@ -0,0 +1,104 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add audit entry" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{
+    diesel::{
+        models::{AuditEntryMemberModel, NewAuditEntryModel},
+        schema::{admin_audit_log, admin_audit_log_member},
+    },
+    error::AdminServiceStoreError,
+    AuditEntry,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreAddAuditEntryOperation {
+    fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreAddAuditEntryOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            let id = insert_into(admin_audit_log::table)
+                .values(NewAuditEntryModel::try_from(&entry)?)
+                .returning(admin_audit_log::id)
+                .get_result::<i64>(self.conn)
+                .map_err(|err| AdminServiceStoreError::QueryError {
+                    context: String::from("Unable to insert AuditEntry"),
+                    source: Box::new(err),
+                })?;
+
+            // Record the members of the proposed circuit, so entries may be filtered by member
+            insert_into(admin_audit_log_member::table)
+                .values(AuditEntryMemberModel::list_from_entry(id, &entry))
+                .execute(self.conn)
+                .map_err(|err| AdminServiceStoreError::QueryError {
+                    context: String::from("Unable to insert AuditEntry members"),
+                    source: Box::new(err),
+                })?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreAddAuditEntryOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            insert_into(admin_audit_log::table)
+                .values(NewAuditEntryModel::try_from(&entry)?)
+                .execute(self.conn)
+                .map_err(|err| AdminServiceStoreError::QueryError {
+                    context: String::from("Unable to insert AuditEntry"),
+                    source: Box::new(err),
+                })?;
+
+            // SQLite does not support `RETURNING`; IDs are assigned in increasing order, and
+            // writes are serialized, so the new entry has the largest ID in the transaction
+            let id = admin_audit_log::table
+                .select(diesel::dsl::max(admin_audit_log::id))
+                .first::<Option<i64>>(self.conn)
+                .map_err(|err| AdminServiceStoreError::QueryError {
+                    context: String::from("Unable to fetch the ID of the inserted AuditEntry"),
+                    source: Box::new(err),
+                })?
+                .ok_or_else(|| AdminServiceStoreError::StorageError {
+                    context: String::from("Inserted AuditEntry was not found"),
+                    source: None,
+                })?;
+
+            // Record the members of the proposed circuit, so entries may be filtered by member
+            insert_into(admin_audit_log_member::table)
+                .values(AuditEntryMemberModel::list_from_entry(id, &entry))
+                .execute(self.conn)
+                .map_err(|err| AdminServiceStoreError::QueryError {
+                    context: String::from("Unable to insert AuditEntry members"),
+                    source: Box::new(err),
+                })?;
+
+            Ok(())
+        })
+    }
+}
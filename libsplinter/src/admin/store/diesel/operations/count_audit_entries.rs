@@ -0,0 +1,71 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "count audit entries" operation for the `DieselAdminServiceStore`.
+
+use diesel::prelude::*;
+
+#[cfg(feature = "postgres")]
+use super::list_audit_entries::pg_audit_entries_query;
+#[cfg(feature = "sqlite")]
+use super::list_audit_entries::sqlite_audit_entries_query;
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{error::AdminServiceStoreError, AuditPredicate};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreCountAuditEntriesOperation {
+    fn count_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+    ) -> Result<usize, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreCountAuditEntriesOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn count_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+    ) -> Result<usize, AdminServiceStoreError> {
+        let count = pg_audit_entries_query(predicates)
+            .count()
+            .get_result::<i64>(self.conn)
+            .map_err(|err| AdminServiceStoreError::QueryError {
+                context: String::from("Unable to count AuditEntry information"),
+                source: Box::new(err),
+            })?;
+
+        Ok(count as usize)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreCountAuditEntriesOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn count_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+    ) -> Result<usize, AdminServiceStoreError> {
+        let count = sqlite_audit_entries_query(predicates)
+            .count()
+            .get_result::<i64>(self.conn)
+            .map_err(|err| AdminServiceStoreError::QueryError {
+                context: String::from("Unable to count AuditEntry information"),
+                source: Box::new(err),
+            })?;
+
+        Ok(count as usize)
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list audit entries" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::exists, prelude::*};
+
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{
+    diesel::{
+        models::AuditEntryModel,
+        schema::{admin_audit_log, admin_audit_log_member},
+    },
+    error::AdminServiceStoreError,
+    AuditEntry, AuditPredicate,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListAuditEntriesOperation {
+    fn list_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreListAuditEntriesOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError> {
+        // Entries are listed in the order they were added, which is the order of their IDs
+        let entries = pg_audit_entries_query(predicates)
+            .order(admin_audit_log::id.asc())
+            .offset(offset as i64)
+            .limit(limit.map(|limit| limit as i64).unwrap_or(i64::MAX))
+            .load::<AuditEntryModel>(self.conn)
+            .map_err(|err| AdminServiceStoreError::QueryError {
+                context: String::from("Unable to load AuditEntry information"),
+                source: Box::new(err),
+            })?
+            .into_iter()
+            .map(AuditEntry::try_from)
+            .collect::<Result<Vec<AuditEntry>, AdminServiceStoreError>>()?;
+
+        Ok(Box::new(entries.into_iter()))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreListAuditEntriesOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError> {
+        // Entries are listed in the order they were added, which is the order of their IDs
+        let entries = sqlite_audit_entries_query(predicates)
+            .order(admin_audit_log::id.asc())
+            .offset(offset as i64)
+            .limit(limit.map(|limit| limit as i64).unwrap_or(i64::MAX))
+            .load::<AuditEntryModel>(self.conn)
+            .map_err(|err| AdminServiceStoreError::QueryError {
+                context: String::from("Unable to load AuditEntry information"),
+                source: Box::new(err),
+            })?
+            .into_iter()
+            .map(AuditEntry::try_from)
+            .collect::<Result<Vec<AuditEntry>, AdminServiceStoreError>>()?;
+
+        Ok(Box::new(entries.into_iter()))
+    }
+}
+
+/// Builds a query for the audit log entries that match all of the given predicates.
+#[cfg(feature = "postgres")]
+pub(super) fn pg_audit_entries_query(
+    predicates: &[AuditPredicate],
+) -> admin_audit_log::BoxedQuery<'static, diesel::pg::Pg> {
+    let mut query = admin_audit_log::table.into_boxed();
+    for predicate in predicates {
+        query = match predicate {
+            AuditPredicate::CircuitIdEq(circuit_id) => {
+                query.filter(admin_audit_log::circuit_id.eq(circuit_id.to_string()))
+            }
+            AuditPredicate::EventTypeEq(event_type) => {
+                query.filter(admin_audit_log::event_type.eq(String::from(event_type)))
+            }
+            AuditPredicate::ManagementTypeEq(management_type) => query
+                .filter(admin_audit_log::circuit_management_type.eq(management_type.to_string())),
+            AuditPredicate::MembersInclude(nodes) => {
+                for node_id in nodes {
+                    query = query.filter(exists(
+                        admin_audit_log_member::table.filter(
+                            admin_audit_log_member::audit_entry_id
+                                .eq(admin_audit_log::id)
+                                .and(admin_audit_log_member::node_id.eq(node_id.to_string())),
+                        ),
+                    ));
+                }
+                query
+            }
+        };
+    }
+
+    query
+}
+
+/// Builds a query for the audit log entries that match all of the given predicates.
+#[cfg(feature = "sqlite")]
+pub(super) fn sqlite_audit_entries_query(
+    predicates: &[AuditPredicate],
+) -> admin_audit_log::BoxedQuery<'static, diesel::sqlite::Sqlite> {
+    let mut query = admin_audit_log::table.into_boxed();
+    for predicate in predicates {
+        query = match predicate {
+            AuditPredicate::CircuitIdEq(circuit_id) => {
+                query.filter(admin_audit_log::circuit_id.eq(circuit_id.to_string()))
+            }
+            AuditPredicate::EventTypeEq(event_type) => {
+                query.filter(admin_audit_log::event_type.eq(String::from(event_type)))
+            }
+            AuditPredicate::ManagementTypeEq(management_type) => query
+                .filter(admin_audit_log::circuit_management_type.eq(management_type.to_string())),
+            AuditPredicate::MembersInclude(nodes) => {
+                for node_id in nodes {
+                    query = query.filter(exists(
+                        admin_audit_log_member::table.filter(
+                            admin_audit_log_member::audit_entry_id
+                                .eq(admin_audit_log::id)
+                                .and(admin_audit_log_member::node_id.eq(node_id.to_string())),
+                        ),
+                    ));
+                }
+                query
+            }
+        };
+    }
+
+    query
+}
@@ -14,12 +14,15 @@
 
 //! Provides database operations for the `DieselAdminServiceStore`.
 
+pub(super) mod add_audit_entry;
 pub(super) mod add_circuit;
 pub(super) mod add_proposal;
+pub(super) mod count_audit_entries;
 pub(super) mod get_circuit;
 pub(super) mod get_node;
 pub(super) mod get_proposal;
 pub(super) mod get_service;
+pub(super) mod list_audit_entries;
 pub(super) mod list_circuits;
 pub(super) mod list_nodes;
 pub(super) mod list_proposals;
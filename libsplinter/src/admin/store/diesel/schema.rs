@@ -120,6 +120,28 @@ table! {
     }
 }
 
+table! {
+    admin_audit_log (id) {
+        id -> Int8,
+        circuit_id -> Text,
+        event_type -> Text,
+        timestamp -> Int8,
+        circuit_hash -> Text,
+        circuit_management_type -> Nullable<Text>,
+        public_key -> Nullable<Binary>,
+        node_id -> Nullable<Text>,
+        vote -> Nullable<Text>,
+        proposal -> Nullable<Text>,
+    }
+}
+
+table! {
+    admin_audit_log_member (audit_entry_id, node_id) {
+        audit_entry_id -> Int8,
+        node_id -> Text,
+    }
+}
+
 allow_tables_to_appear_in_same_query!(
     proposed_circuit,
     proposed_node,
@@ -137,3 +159,5 @@ allow_tables_to_appear_in_same_query!(
     circuit_member,
     node_endpoint
 );
+
+allow_tables_to_appear_in_same_query!(admin_audit_log, admin_audit_log_member);
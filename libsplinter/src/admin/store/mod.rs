@@ -12,10 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Data store for writing and reading circuit state and pending circuit proposals.
+//! Data store for writing and reading circuit state, pending circuit proposals and the audit log
+//! of circuit proposals.
 //!
-//! The [`AdminServiceStore`] trait provides the public interface for storing circuits and
-//! proposals. Splinter provides the following implementations of this trait:
+//! The [`AdminServiceStore`] trait provides the public interface for storing circuits, proposals
+//! and audit log entries. Splinter provides the following implementations of this trait:
 //!
 //! * [`YamlAdminServiceStore`] - A YAML-backed store that is available by default
 //! * [`DieselAdminServiceStore`] - A database-backed store, powered by [`Diesel`], that currently
@@ -27,6 +28,7 @@
 //! [`DieselAdminServiceStore`]: diesel/struct.DieselAdminServiceStore.html
 //! [`Diesel`]: https://crates.io/crates/diesel
 
+mod audit;
 mod circuit;
 mod circuit_node;
 mod circuit_proposal;
@@ -42,6 +44,7 @@ pub mod yaml;
 use std::cmp::Ordering;
 use std::fmt;
 
+pub use self::audit::{AuditEntry, AuditEntryBuilder, AuditEventType};
 pub use self::circuit::{
     AuthorizationType, Circuit, CircuitBuilder, DurabilityType, PersistenceType, RouteType,
 };
@@ -166,7 +169,45 @@ impl CircuitPredicate {
     }
 }
 
-/// Interface for performing CRUD operations on circuits, proposals, nodes, and services
+/// Predicate for filtering the list of audit log entries
+///
+/// `ManagementTypeEq` and `MembersInclude` apply to the proposal recorded with the entry; entries
+/// without a recorded proposal do not match them.
+pub enum AuditPredicate {
+    CircuitIdEq(String),
+    EventTypeEq(AuditEventType),
+    ManagementTypeEq(String),
+    MembersInclude(Vec<String>),
+}
+
+impl AuditPredicate {
+    /// Apply this predicate against a given audit log entry
+    pub fn apply_to_entry(&self, entry: &AuditEntry) -> bool {
+        match self {
+            AuditPredicate::CircuitIdEq(circuit_id) => entry.circuit_id() == circuit_id,
+            AuditPredicate::EventTypeEq(event_type) => entry.event_type() == event_type,
+            AuditPredicate::ManagementTypeEq(management_type) => entry
+                .proposal()
+                .map(|proposal| &proposal.circuit.circuit_management_type == management_type)
+                .unwrap_or(false),
+            AuditPredicate::MembersInclude(nodes) => entry
+                .proposal()
+                .map(|proposal| {
+                    nodes.iter().all(|node_id| {
+                        proposal
+                            .circuit
+                            .members
+                            .iter()
+                            .any(|member| &member.node_id == node_id)
+                    })
+                })
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Interface for performing CRUD operations on circuits, proposals, nodes, and services, and for
+/// appending to and reading the audit log
 pub trait AdminServiceStore: Send + Sync {
     /// Adds a circuit proposal to the store
     ///
@@ -301,4 +342,34 @@ pub trait AdminServiceStore: Send + Sync {
         &self,
         circuit_id: &str,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Service>>, AdminServiceStoreError>;
+
+    /// Appends an entry to the audit log. The audit log is append-only; entries cannot be updated
+    /// or removed.
+    ///
+    /// # Arguments
+    ///
+    ///  * `entry` - The audit log entry to be added
+    fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError>;
+
+    /// List audit log entries from the store, in the order they were added
+    ///
+    /// `AuditPredicate`s may be provided for filtering which entries are returned.
+    ///
+    /// # Arguments
+    ///
+    ///  * `predicates` - The predicates the returned entries must all match
+    ///  * `offset` - The number of matching entries to skip
+    ///  * `limit` - The maximum number of entries to return, or `None` to return all of them
+    fn list_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError>;
+
+    /// Returns the number of audit log entries that match all of the given predicates
+    fn count_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+    ) -> Result<usize, AdminServiceStoreError>;
 }
@@ -31,11 +31,11 @@ use std::sync::{Arc, Mutex};
 use self::error::YamlAdminStoreError;
 
 use super::{
-    error::BuilderError, AdminServiceStore, AdminServiceStoreError, AuthorizationType, Circuit,
-    CircuitBuilder, CircuitNode, CircuitPredicate, CircuitProposal, CircuitProposalBuilder,
-    DurabilityType, PersistenceType, ProposalType, ProposedCircuit, ProposedCircuitBuilder,
-    ProposedNode, ProposedService, ProposedServiceBuilder, RouteType, Service, ServiceBuilder,
-    ServiceId, Vote, VoteRecord, VoteRecordBuilder,
+    error::BuilderError, AdminServiceStore, AdminServiceStoreError, AuditEntry, AuditPredicate,
+    AuthorizationType, Circuit, CircuitBuilder, CircuitNode, CircuitPredicate, CircuitProposal,
+    CircuitProposalBuilder, DurabilityType, PersistenceType, ProposalType, ProposedCircuit,
+    ProposedCircuitBuilder, ProposedNode, ProposedService, ProposedServiceBuilder, RouteType,
+    Service, ServiceBuilder, ServiceId, Vote, VoteRecord, VoteRecordBuilder,
 };
 
 use crate::hex::{parse_hex, to_hex};
@@ -1008,6 +1008,79 @@ impl AdminServiceStore for YamlAdminServiceStore {
 
         Ok(Box::new(services.into_iter()))
     }
+
+    /// Appends an entry to the audit log. The v0.4 YAML state files do not include an audit log,
+    /// so the entries are only kept in memory and are lost on restart.
+    ///
+    /// # Arguments
+    ///
+    ///  * `entry` - The audit log entry to be added
+    fn add_audit_entry(&self, entry: AuditEntry) -> Result<(), AdminServiceStoreError> {
+        self.state
+            .lock()
+            .map_err(|_| AdminServiceStoreError::StorageError {
+                context: "YAML admin service store's internal lock was poisoned".to_string(),
+                source: None,
+            })?
+            .audit_log
+            .push(entry);
+
+        Ok(())
+    }
+
+    /// List the audit log entries that have been added since the store was created
+    ///
+    /// `AuditPredicate`s may be provided for filtering which entries are returned.
+    fn list_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AdminServiceStoreError> {
+        let entries: Vec<AuditEntry> = self
+            .state
+            .lock()
+            .map_err(|_| AdminServiceStoreError::StorageError {
+                context: "YAML admin service store's internal lock was poisoned".to_string(),
+                source: None,
+            })?
+            .audit_log
+            .iter()
+            .filter(|entry| {
+                predicates
+                    .iter()
+                    .all(|predicate| predicate.apply_to_entry(entry))
+            })
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    /// Returns the number of audit log entries added since the store was created that match all
+    /// of the given predicates
+    fn count_audit_entries(
+        &self,
+        predicates: &[AuditPredicate],
+    ) -> Result<usize, AdminServiceStoreError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| AdminServiceStoreError::StorageError {
+                context: "YAML admin service store's internal lock was poisoned".to_string(),
+                source: None,
+            })?
+            .audit_log
+            .iter()
+            .filter(|entry| {
+                predicates
+                    .iter()
+                    .all(|predicate| predicate.apply_to_entry(entry))
+            })
+            .count())
+    }
 }
 
 /// YAML file specific circuit definition. This circuit definition in the 0.4v YAML stores service
@@ -1414,6 +1487,7 @@ struct YamlState {
     circuit_state: CircuitState,
     proposal_state: ProposalState,
     service_directory: BTreeMap<ServiceId, Service>,
+    audit_log: Vec<AuditEntry>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,188 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A request guard that authorizes requests to a REST API resource
+
+use std::sync::Arc;
+
+use actix_web::{HttpRequest, HttpResponse};
+use futures::future::IntoFuture;
+
+use crate::rest_api::{Continuation, ErrorResponse, Method, RequestGuard};
+
+use super::{AuthorizationResult, Authorizer, Permission};
+
+/// A request guard that only allows requests whose caller has the permission that the resource
+/// requires for the request's method
+///
+/// Requests are rejected with `401 Unauthorized` if the caller cannot be identified, and with
+/// `403 Forbidden` if the caller does not have the required permission. The identity of an
+/// allowed caller is added to the request's extensions.
+#[derive(Clone)]
+pub(crate) struct AuthorizationGuard {
+    authorizer: Arc<Authorizer>,
+    permission: Option<Permission>,
+    method_permissions: Vec<(Method, Permission)>,
+}
+
+impl AuthorizationGuard {
+    /// Creates a new `AuthorizationGuard`
+    ///
+    /// # Arguments
+    ///
+    /// * `authorizer` - Checks the permissions of callers
+    /// * `permission` - The permission required for methods without their own permission
+    /// * `method_permissions` - The permissions required for specific methods
+    pub fn new(
+        authorizer: Arc<Authorizer>,
+        permission: Option<Permission>,
+        method_permissions: Vec<(Method, Permission)>,
+    ) -> Self {
+        Self {
+            authorizer,
+            permission,
+            method_permissions,
+        }
+    }
+
+    fn permission_for(&self, req: &HttpRequest) -> &Permission {
+        self.method_permissions
+            .iter()
+            .find(|(method, _)| method.to_string() == req.method().as_str())
+            .map(|(_, permission)| permission)
+            .or_else(|| self.permission.as_ref())
+            .unwrap_or(&Permission::AllowAuthenticated)
+    }
+}
+
+impl RequestGuard for AuthorizationGuard {
+    fn evaluate(&self, req: &HttpRequest) -> Continuation {
+        match self.authorizer.authorize(req, self.permission_for(req)) {
+            Ok(AuthorizationResult::Authorized(identity)) => {
+                if let Some(identity) = identity {
+                    req.extensions_mut().insert(identity);
+                }
+                Continuation::Continue
+            }
+            Ok(AuthorizationResult::Unauthenticated) => Continuation::terminate(
+                HttpResponse::Unauthorized()
+                    .json(ErrorResponse::unauthorized("User is not authorized"))
+                    .into_future(),
+            ),
+            Ok(AuthorizationResult::Forbidden(identity)) => {
+                debug!("Request to {} denied for {}", req.path(), identity);
+                Continuation::terminate(
+                    HttpResponse::Forbidden()
+                        .json(ErrorResponse::forbidden(
+                            "Client does not have permission to access this resource",
+                        ))
+                        .into_future(),
+                )
+            }
+            Err(err) => {
+                error!("Unable to authorize request to {}: {}", req.path(), err);
+                Continuation::terminate(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use actix_web::{http::StatusCode, test::TestRequest};
+    use futures::Future;
+    use rand::Rng;
+
+    use crate::auth::authorization::RoleBasedPermissionStore;
+    use crate::auth::identity::{
+        body_sha256, signed_request_message, SignedRequestIdentityProvider, SIGNED_REQUEST_SCHEME,
+    };
+    use crate::hex::to_hex;
+    use crate::signing::{
+        hash::{HashSigner, HashVerifier},
+        Signer,
+    };
+
+    /// Verifies that a resource that does not declare a permission rejects a request that is
+    /// validly signed by a key that is not known to the permission store, and allows the same
+    /// request by a known key.
+    #[test]
+    fn undeclared_permission_rejects_unknown_key() {
+        let known_key = to_hex(&rand::thread_rng().gen::<[u8; 32]>());
+        let authorizer = Arc::new(Authorizer::new(
+            vec![Box::new(SignedRequestIdentityProvider::new(Box::new(
+                HashVerifier,
+            )))],
+            Some(Box::new(
+                RoleBasedPermissionStore::new()
+                    .with_role("reader", &["circuit.read"])
+                    .with_key_roles(&known_key, &["reader"]),
+            )),
+        ));
+        let guard = AuthorizationGuard::new(authorizer, None, vec![]);
+
+        let random_key = to_hex(&rand::thread_rng().gen::<[u8; 32]>());
+        match guard.evaluate(&signed_request(&random_key, "nonce1")) {
+            Continuation::Terminate(response) => assert_eq!(
+                response.wait().expect("Failed to get response").status(),
+                StatusCode::UNAUTHORIZED
+            ),
+            Continuation::Continue => panic!("Request by an unknown key was allowed"),
+        }
+
+        match guard.evaluate(&signed_request(&known_key, "nonce2")) {
+            Continuation::Continue => (),
+            Continuation::Terminate(_) => panic!("Request by a known key was rejected"),
+        }
+    }
+
+    fn signed_request(public_key: &str, nonce: &str) -> HttpRequest {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get time")
+            .as_secs();
+        let empty_body = body_sha256(b"");
+        let signature = HashSigner
+            .sign(&signed_request_message(
+                "GET",
+                "/admin/circuits",
+                timestamp,
+                nonce,
+                &empty_body,
+            ))
+            .expect("Failed to sign request");
+        TestRequest::with_uri("/admin/circuits")
+            .header(
+                "Authorization",
+                format!(
+                    "{} {}:{}:{}:{}:{}",
+                    SIGNED_REQUEST_SCHEME,
+                    public_key,
+                    timestamp,
+                    nonce,
+                    empty_body,
+                    to_hex(&signature)
+                ),
+            )
+            .to_http_request()
+    }
+}
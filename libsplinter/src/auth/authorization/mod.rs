@@ -0,0 +1,324 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Authorization of REST API requests
+//!
+//! Each REST API [`Resource`] declares the [`Permission`] that is required to call it, either for
+//! all of its methods or per method. When authorization is enabled, the REST API checks every
+//! request with an [`Authorizer`], which identifies the caller using its identity providers and
+//! checks the caller's permissions in its [`PermissionStore`]:
+//!
+//! * Requests whose caller cannot be identified are rejected with `401 Unauthorized`
+//! * Requests whose caller does not have the required permission are rejected with
+//!   `403 Forbidden`
+//!
+//! Methods that do not declare a permission require an identified caller, but no specific
+//! permission. Since anyone can generate a key pair, a caller identified by a signing key is only
+//! considered identified if the permission store knows the key.
+//!
+//! [`Resource`]: ../../rest_api/struct.Resource.html
+//! [`Permission`]: enum.Permission.html
+//! [`Authorizer`]: struct.Authorizer.html
+//! [`PermissionStore`]: trait.PermissionStore.html
+
+mod guard;
+mod role_based;
+
+use std::error::Error;
+use std::fmt;
+
+use crate::actix_web::HttpRequest;
+
+use super::identity::{Identity, IdentityProvider, IdentityProviderError};
+
+pub(crate) use guard::AuthorizationGuard;
+pub use role_based::RoleBasedPermissionStore;
+
+/// The permission that is required to call a REST API resource
+#[derive(Clone, Debug, PartialEq)]
+pub enum Permission {
+    /// Any caller is allowed, including callers that cannot be identified
+    AllowUnauthenticated,
+    /// Any caller that can be identified is allowed
+    AllowAuthenticated,
+    /// Only callers that have been granted the permission with the given ID are allowed
+    Check(&'static str),
+}
+
+/// Stores the permissions that have been granted to identities
+pub trait PermissionStore: Send + Sync {
+    /// Returns whether or not the given identity has been granted the permission with the given ID
+    fn has_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+    ) -> Result<bool, PermissionStoreError>;
+
+    /// Returns whether or not the store has assigned any roles or permissions to the given
+    /// identity
+    fn is_known(&self, identity: &Identity) -> Result<bool, PermissionStoreError>;
+}
+
+/// An error that occurred while checking or loading permissions
+#[derive(Debug)]
+pub struct PermissionStoreError {
+    message: String,
+}
+
+impl PermissionStoreError {
+    pub fn new(message: &str) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PermissionStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "permission store error: {}", self.message)
+    }
+}
+
+impl Error for PermissionStoreError {}
+
+/// The outcome of authorizing a request
+#[derive(Debug, PartialEq)]
+pub enum AuthorizationResult {
+    /// The request is allowed; contains the identity of the caller if it could be determined
+    Authorized(Option<Identity>),
+    /// The caller of the request could not be identified
+    Unauthenticated,
+    /// The caller does not have the required permission
+    Forbidden(Identity),
+}
+
+/// An error that occurred while authorizing a request
+#[derive(Debug)]
+pub enum AuthorizationError {
+    IdentityError(IdentityProviderError),
+    PermissionError(PermissionStoreError),
+}
+
+impl fmt::Display for AuthorizationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuthorizationError::IdentityError(err) => write!(f, "{}", err),
+            AuthorizationError::PermissionError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for AuthorizationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AuthorizationError::IdentityError(err) => Some(err),
+            AuthorizationError::PermissionError(err) => Some(err),
+        }
+    }
+}
+
+impl From<IdentityProviderError> for AuthorizationError {
+    fn from(err: IdentityProviderError) -> Self {
+        AuthorizationError::IdentityError(err)
+    }
+}
+
+impl From<PermissionStoreError> for AuthorizationError {
+    fn from(err: PermissionStoreError) -> Self {
+        AuthorizationError::PermissionError(err)
+    }
+}
+
+/// Checks whether the callers of REST API requests have the permissions that are required
+///
+/// The caller of a request is the identity returned by the first identity provider that
+/// recognizes the request. If there is no permission store, permissions that must be checked are
+/// never granted.
+pub struct Authorizer {
+    identity_providers: Vec<Box<dyn IdentityProvider>>,
+    permission_store: Option<Box<dyn PermissionStore>>,
+}
+
+impl Authorizer {
+    /// Creates a new `Authorizer`
+    ///
+    /// # Arguments
+    ///
+    /// * `identity_providers` - The providers that identify callers, in order of precedence
+    /// * `permission_store` - The store of the permissions that are granted to callers
+    pub fn new(
+        identity_providers: Vec<Box<dyn IdentityProvider>>,
+        permission_store: Option<Box<dyn PermissionStore>>,
+    ) -> Self {
+        Self {
+            identity_providers,
+            permission_store,
+        }
+    }
+
+    /// Returns the identity of the caller of the given request, if it can be determined
+    pub fn get_identity(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<Option<Identity>, IdentityProviderError> {
+        for provider in &self.identity_providers {
+            if let Some(identity) = provider.get_identity(request)? {
+                return Ok(Some(identity));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the identity of the caller of the given request, if it can be determined and the
+    /// caller is authenticated. A valid signature only proves that the caller holds the key, and
+    /// anyone can generate a key, so a signing key must also be known to the permission store.
+    fn get_authenticated_identity(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<Option<Identity>, AuthorizationError> {
+        let identity = match self.get_identity(request)? {
+            Some(identity) => identity,
+            None => return Ok(None),
+        };
+
+        if let Identity::Key(_) = identity {
+            let known = match &self.permission_store {
+                Some(store) => store.is_known(&identity)?,
+                None => false,
+            };
+            if !known {
+                debug!("Request signed by unknown {}", identity);
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(identity))
+    }
+
+    /// Checks whether the caller of the given request has the given permission
+    pub fn authorize(
+        &self,
+        request: &HttpRequest,
+        permission: &Permission,
+    ) -> Result<AuthorizationResult, AuthorizationError> {
+        if let Permission::AllowUnauthenticated = permission {
+            return Ok(AuthorizationResult::Authorized(
+                self.get_authenticated_identity(request)?,
+            ));
+        }
+
+        let identity = match self.get_authenticated_identity(request)? {
+            Some(identity) => identity,
+            None => return Ok(AuthorizationResult::Unauthenticated),
+        };
+
+        let permission_id = match permission {
+            Permission::Check(permission_id) => permission_id,
+            _ => return Ok(AuthorizationResult::Authorized(Some(identity))),
+        };
+
+        let granted = match &self.permission_store {
+            Some(store) => store.has_permission(&identity, permission_id)?,
+            None => false,
+        };
+        if granted {
+            Ok(AuthorizationResult::Authorized(Some(identity)))
+        } else {
+            Ok(AuthorizationResult::Forbidden(identity))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::test::TestRequest;
+
+    /// Identifies callers by the `Caller` header
+    struct HeaderIdentityProvider;
+
+    impl IdentityProvider for HeaderIdentityProvider {
+        fn get_identity(
+            &self,
+            request: &HttpRequest,
+        ) -> Result<Option<Identity>, IdentityProviderError> {
+            Ok(request
+                .headers()
+                .get("Caller")
+                .and_then(|caller| caller.to_str().ok())
+                .map(|caller| Identity::User(caller.into())))
+        }
+    }
+
+    fn request(caller: Option<&str>) -> HttpRequest {
+        let request = match caller {
+            Some(caller) => TestRequest::default().header("Caller", caller),
+            None => TestRequest::default(),
+        };
+        request.to_http_request()
+    }
+
+    /// Verifies that the `Authorizer` allows unauthenticated requests only for resources that
+    /// allow them, and checks the caller's permissions in the permission store.
+    #[test]
+    fn authorize() {
+        let store = RoleBasedPermissionStore::new()
+            .with_role("reader", &["circuit.read"])
+            .with_user_roles("alice", &["reader"]);
+        let authorizer = Authorizer::new(
+            vec![Box::new(HeaderIdentityProvider)],
+            Some(Box::new(store)),
+        );
+        let alice = Identity::User("alice".into());
+        let bob = Identity::User("bob".into());
+
+        assert_eq!(
+            authorizer
+                .authorize(&request(None), &Permission::AllowUnauthenticated)
+                .expect("Failed to authorize"),
+            AuthorizationResult::Authorized(None)
+        );
+        assert_eq!(
+            authorizer
+                .authorize(&request(None), &Permission::AllowAuthenticated)
+                .expect("Failed to authorize"),
+            AuthorizationResult::Unauthenticated
+        );
+        assert_eq!(
+            authorizer
+                .authorize(&request(Some("bob")), &Permission::AllowAuthenticated)
+                .expect("Failed to authorize"),
+            AuthorizationResult::Authorized(Some(bob.clone()))
+        );
+        assert_eq!(
+            authorizer
+                .authorize(&request(Some("alice")), &Permission::Check("circuit.read"))
+                .expect("Failed to authorize"),
+            AuthorizationResult::Authorized(Some(alice.clone()))
+        );
+        assert_eq!(
+            authorizer
+                .authorize(&request(Some("alice")), &Permission::Check("circuit.write"))
+                .expect("Failed to authorize"),
+            AuthorizationResult::Forbidden(alice)
+        );
+        assert_eq!(
+            authorizer
+                .authorize(&request(Some("bob")), &Permission::Check("circuit.read"))
+                .expect("Failed to authorize"),
+            AuthorizationResult::Forbidden(bob)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A permission store that grants permissions to identities through roles
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+
+use crate::auth::identity::Identity;
+
+use super::{PermissionStore, PermissionStoreError};
+
+/// The permission ID that grants every permission to a role
+const ALL_PERMISSIONS: &str = "*";
+
+/// The contents of a permissions file
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct PermissionsFile {
+    roles: HashMap<String, Vec<String>>,
+    users: HashMap<String, Vec<String>>,
+    keys: HashMap<String, Vec<String>>,
+}
+
+/// A permission store that grants permissions to roles, and assigns roles to Biome users and to
+/// public keys
+///
+/// The store can be loaded from a YAML file of the following form, where a role that is granted
+/// `"*"` has every permission:
+///
+/// ```yaml
+/// roles:
+///   admin: ["*"]
+///   circuit_reader: [circuit.read]
+/// users:
+///   <biome user ID>: [admin]
+/// keys:
+///   <hex-encoded public key>: [circuit_reader]
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RoleBasedPermissionStore {
+    roles: HashMap<String, HashSet<String>>,
+    users: HashMap<String, HashSet<String>>,
+    keys: HashMap<String, HashSet<String>>,
+}
+
+impl RoleBasedPermissionStore {
+    /// Creates a new, empty `RoleBasedPermissionStore`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a `RoleBasedPermissionStore` from the YAML file at the given path
+    pub fn from_yaml_file(path: &str) -> Result<Self, PermissionStoreError> {
+        let file = File::open(path).map_err(|err| {
+            PermissionStoreError::new(&format!(
+                "unable to open permissions file {}: {}",
+                path, err
+            ))
+        })?;
+        let permissions_file: PermissionsFile = serde_yaml::from_reader(file).map_err(|err| {
+            PermissionStoreError::new(&format!(
+                "unable to parse permissions file {}: {}",
+                path, err
+            ))
+        })?;
+        Ok(Self::from(permissions_file))
+    }
+
+    /// Grants the given permissions to the given role
+    pub fn with_role(mut self, role: &str, permission_ids: &[&str]) -> Self {
+        extend(&mut self.roles, role, permission_ids);
+        self
+    }
+
+    /// Assigns the given roles to the Biome user with the given ID
+    pub fn with_user_roles(mut self, user_id: &str, roles: &[&str]) -> Self {
+        extend(&mut self.users, user_id, roles);
+        self
+    }
+
+    /// Assigns the given roles to the given hex-encoded public key
+    pub fn with_key_roles(mut self, public_key: &str, roles: &[&str]) -> Self {
+        extend(&mut self.keys, &public_key.to_lowercase(), roles);
+        self
+    }
+}
+
+impl From<PermissionsFile> for RoleBasedPermissionStore {
+    fn from(permissions_file: PermissionsFile) -> Self {
+        let into_sets = |map: HashMap<String, Vec<String>>| {
+            map.into_iter()
+                .map(|(key, values)| (key, values.into_iter().collect()))
+                .collect()
+        };
+        Self {
+            roles: into_sets(permissions_file.roles),
+            users: into_sets(permissions_file.users),
+            keys: into_sets(
+                permissions_file
+                    .keys
+                    .into_iter()
+                    .map(|(key, roles)| (key.to_lowercase(), roles))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl PermissionStore for RoleBasedPermissionStore {
+    fn has_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+    ) -> Result<bool, PermissionStoreError> {
+        let roles = match identity {
+            Identity::User(user_id) => self.users.get(user_id),
+            Identity::Key(public_key) => self.keys.get(&public_key.to_lowercase()),
+        };
+
+        Ok(roles
+            .into_iter()
+            .flatten()
+            .filter_map(|role| self.roles.get(role))
+            .any(|permissions| {
+                permissions.contains(ALL_PERMISSIONS) || permissions.contains(permission_id)
+            }))
+    }
+
+    fn is_known(&self, identity: &Identity) -> Result<bool, PermissionStoreError> {
+        Ok(match identity {
+            Identity::User(user_id) => self.users.contains_key(user_id),
+            Identity::Key(public_key) => self.keys.contains_key(&public_key.to_lowercase()),
+        })
+    }
+}
+
+fn extend(map: &mut HashMap<String, HashSet<String>>, key: &str, values: &[&str]) {
+    map.entry(key.into())
+        .or_insert_with(HashSet::new)
+        .extend(values.iter().map(|value| value.to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERMISSIONS_YAML: &str = r#"
+roles:
+  admin: ["*"]
+  circuit_reader: [circuit.read]
+users:
+  alice: [admin]
+keys:
+  02ABCDEF: [circuit_reader]
+"#;
+
+    /// Verifies that permissions loaded from YAML are granted through roles, that the `*`
+    /// permission grants everything, and that public keys are matched regardless of case.
+    #[test]
+    fn permissions_from_yaml() {
+        let permissions_file: PermissionsFile =
+            serde_yaml::from_str(PERMISSIONS_YAML).expect("Failed to parse permissions");
+        let store = RoleBasedPermissionStore::from(permissions_file);
+
+        let alice = Identity::User("alice".into());
+        let bob = Identity::User("bob".into());
+        let key = Identity::Key("02abcdef".into());
+
+        assert!(store
+            .has_permission(&alice, "circuit.write")
+            .expect("Failed to check permission"));
+        assert!(!store
+            .has_permission(&bob, "circuit.read")
+            .expect("Failed to check permission"));
+        assert!(store
+            .has_permission(&key, "circuit.read")
+            .expect("Failed to check permission"));
+        assert!(!store
+            .has_permission(&key, "circuit.write")
+            .expect("Failed to check permission"));
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An identity provider for Biome users
+
+use std::sync::Arc;
+
+use jsonwebtoken::decode;
+
+use crate::actix_web::HttpRequest;
+use crate::rest_api::secrets::SecretManager;
+use crate::rest_api::sessions::{default_validation, Claims};
+
+use super::{authorization_header, Identity, IdentityProvider, IdentityProviderError};
+
+/// Identifies Biome users by the access tokens that Biome issues, which are sent in the
+/// `Authorization` header as `Bearer <token>`
+pub struct BiomeUserIdentityProvider {
+    secret_manager: Arc<dyn SecretManager>,
+    issuer: String,
+}
+
+impl BiomeUserIdentityProvider {
+    /// Creates a new `BiomeUserIdentityProvider`
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_manager` - Provides the secret that Biome access tokens are signed with
+    /// * `issuer` - The issuer of the Biome access tokens
+    pub fn new(secret_manager: Arc<dyn SecretManager>, issuer: &str) -> Self {
+        Self {
+            secret_manager,
+            issuer: issuer.into(),
+        }
+    }
+}
+
+impl IdentityProvider for BiomeUserIdentityProvider {
+    fn get_identity(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<Option<Identity>, IdentityProviderError> {
+        let token = match authorization_header(request) {
+            Some(("Bearer", token)) => token,
+            _ => return Ok(None),
+        };
+
+        let secret = self
+            .secret_manager
+            .secret()
+            .map_err(|err| IdentityProviderError::new(&err.to_string()))?;
+
+        match decode::<Claims>(token, secret.as_ref(), &default_validation(&self.issuer)) {
+            Ok(token_data) => Ok(Some(Identity::User(token_data.claims.user_id()))),
+            Err(err) => {
+                debug!("Invalid Biome access token: {}", err);
+                Ok(None)
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Identification of the callers of REST API requests
+//!
+//! An [`IdentityProvider`] determines who made a request from the credentials that the request
+//! carries. The following providers are available:
+//!
+//! * [`BiomeUserIdentityProvider`] - identifies Biome users by their access tokens
+//! * [`SignedRequestIdentityProvider`] - identifies the holders of private keys by a signature
+//!   of the request
+//!
+//! Users that log in through OAuth are identified by the provider returned by
+//! `OAuthResourceProvider::identity_provider`.
+//!
+//! [`IdentityProvider`]: trait.IdentityProvider.html
+//! [`BiomeUserIdentityProvider`]: struct.BiomeUserIdentityProvider.html
+//! [`SignedRequestIdentityProvider`]: struct.SignedRequestIdentityProvider.html
+
+#[cfg(feature = "biome-credentials")]
+mod biome;
+mod signed;
+
+use std::error::Error;
+use std::fmt;
+
+use crate::actix_web::HttpRequest;
+
+#[cfg(feature = "biome-credentials")]
+pub use biome::BiomeUserIdentityProvider;
+pub(crate) use signed::verify_signed_body;
+pub use signed::{
+    body_sha256, signed_request_message, SignedRequestIdentityProvider, SIGNED_REQUEST_SCHEME,
+};
+
+/// The identity of the caller of a REST API request
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Identity {
+    /// A Biome user, identified by their user ID; this includes users that log in through OAuth
+    User(String),
+    /// The holder of a private key, identified by the hex-encoded public key
+    Key(String),
+}
+
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Identity::User(user_id) => write!(f, "user {}", user_id),
+            Identity::Key(public_key) => write!(f, "key {}", public_key),
+        }
+    }
+}
+
+/// Determines the identity of the caller of a REST API request
+pub trait IdentityProvider: Send + Sync {
+    /// Returns the identity of the caller of the given request. Returns `None` if the request does
+    /// not carry credentials that this provider recognizes, or if the credentials are not valid.
+    fn get_identity(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<Option<Identity>, IdentityProviderError>;
+}
+
+/// An unrecoverable error that occurred while determining the identity of a caller
+#[derive(Debug)]
+pub struct IdentityProviderError {
+    message: String,
+}
+
+impl IdentityProviderError {
+    pub fn new(message: &str) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for IdentityProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to get identity: {}", self.message)
+    }
+}
+
+impl Error for IdentityProviderError {}
+
+/// Splits the `Authorization` header of the given request into its scheme and credentials.
+/// Returns `None` if the header is missing or malformed.
+fn authorization_header(request: &HttpRequest) -> Option<(&str, &str)> {
+    let header = request.headers().get("Authorization")?.to_str().ok()?;
+    let mut parts = header.trim().splitn(2, ' ');
+    let scheme = parts.next()?;
+    let credentials = parts.next()?.trim();
+    Some((scheme, credentials))
+}
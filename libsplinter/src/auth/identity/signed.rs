@@ -0,0 +1,532 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An identity provider for requests that are signed with a private key
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use openssl::sha::{sha256, Sha256};
+
+use crate::actix_web::{
+    dev,
+    error::PayloadError,
+    web::{self, Bytes},
+    HttpRequest,
+};
+use crate::futures::{Async, Poll, Stream};
+use crate::hex::{parse_hex, to_hex};
+use crate::signing::SignatureVerifierFactory;
+
+use super::{authorization_header, Identity, IdentityProvider, IdentityProviderError};
+
+/// The scheme of the `Authorization` header of signed requests
+pub const SIGNED_REQUEST_SCHEME: &str = "Cygnus";
+/// The maximum difference, in seconds, between the timestamp of a signed request and the time it
+/// is received
+const MAX_CLOCK_SKEW_SECS: u64 = 300;
+/// The maximum length of the nonce of a signed request
+const MAX_NONCE_LEN: usize = 64;
+
+/// Returns the message that is signed for a request: the request's method, path (including the
+/// query string), timestamp, nonce and the SHA-256 digest of its body, separated by newlines
+///
+/// # Arguments
+///
+/// * `method` - The HTTP method of the request, such as `GET`
+/// * `path` - The path of the request, including the query string if there is one
+/// * `timestamp` - The time the request was signed, in seconds since the Unix epoch
+/// * `nonce` - A value that is unique to the request, such as a random hex string
+/// * `body_sha256` - The hex-encoded SHA-256 digest of the request body, as returned by
+///   [`body_sha256`]
+///
+/// [`body_sha256`]: fn.body_sha256.html
+pub fn signed_request_message(
+    method: &str,
+    path: &str,
+    timestamp: u64,
+    nonce: &str,
+    body_sha256: &str,
+) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}\n{}\n{}",
+        method, path, timestamp, nonce, body_sha256
+    )
+    .into_bytes()
+}
+
+/// Returns the hex-encoded SHA-256 digest of a request body; requests without a body use the
+/// digest of an empty body
+pub fn body_sha256(body: &[u8]) -> String {
+    to_hex(&sha256(body))
+}
+
+/// Identifies the holders of private keys by a signature of the request
+///
+/// Signed requests include the header
+/// `Authorization: Cygnus <public_key>:<timestamp>:<nonce>:<body_sha256>:<signature>`, where the
+/// public key and signature are hex-encoded and the signature is of the message returned by
+/// [`signed_request_message`]. Requests are only accepted within five minutes of their timestamp,
+/// and each nonce is only accepted once per key in that time, so captured requests cannot be
+/// replayed.
+///
+/// The body of an identified request is checked against the signed digest as the body is read;
+/// a body that does not match causes an error when it is read.
+///
+/// [`signed_request_message`]: fn.signed_request_message.html
+pub struct SignedRequestIdentityProvider {
+    verifier_factory: Mutex<Box<dyn SignatureVerifierFactory>>,
+    /// The nonces that have been used, by public key and nonce, with the timestamps of their
+    /// requests
+    used_nonces: Mutex<HashMap<(String, String), u64>>,
+}
+
+impl SignedRequestIdentityProvider {
+    /// Creates a new `SignedRequestIdentityProvider` that verifies signatures with verifiers from
+    /// the given factory
+    pub fn new(verifier_factory: Box<dyn SignatureVerifierFactory>) -> Self {
+        Self {
+            verifier_factory: Mutex::new(verifier_factory),
+            used_nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records the nonce of a request as used; returns false if it had already been used.
+    /// Nonces of requests that are too old to be accepted are forgotten.
+    fn use_nonce(
+        &self,
+        public_key: &str,
+        nonce: &str,
+        timestamp: u64,
+        now: u64,
+    ) -> Result<bool, IdentityProviderError> {
+        let mut used_nonces = self
+            .used_nonces
+            .lock()
+            .map_err(|_| IdentityProviderError::new("signed request nonce lock poisoned"))?;
+        used_nonces.retain(|_, timestamp| *timestamp + MAX_CLOCK_SKEW_SECS >= now);
+        Ok(used_nonces
+            .insert((public_key.to_string(), nonce.to_string()), timestamp)
+            .is_none())
+    }
+}
+
+/// The result of verifying a request's signature, which is stored in the request's extensions so
+/// that the request is only verified once and its body can be checked
+#[derive(Clone)]
+struct VerifiedSignedRequest {
+    credentials: String,
+    identity: Identity,
+    body_sha256: String,
+}
+
+impl IdentityProvider for SignedRequestIdentityProvider {
+    fn get_identity(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<Option<Identity>, IdentityProviderError> {
+        let credentials = match authorization_header(request) {
+            Some((SIGNED_REQUEST_SCHEME, credentials)) => credentials,
+            _ => return Ok(None),
+        };
+
+        // The nonce has already been used if this request was verified before
+        if let Some(verified) = request.extensions().get::<VerifiedSignedRequest>() {
+            if verified.credentials == credentials {
+                return Ok(Some(verified.identity.clone()));
+            }
+        }
+
+        let parsed = match parse_credentials(credentials) {
+            Some(parsed) => parsed,
+            None => {
+                debug!("Malformed signed request credentials: {}", credentials);
+                return Ok(None);
+            }
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| IdentityProviderError::new(&err.to_string()))?
+            .as_secs();
+        let skew = if now > parsed.timestamp {
+            now - parsed.timestamp
+        } else {
+            parsed.timestamp - now
+        };
+        if skew > MAX_CLOCK_SKEW_SECS {
+            debug!(
+                "Signed request timestamp {} is not current",
+                parsed.timestamp
+            );
+            return Ok(None);
+        }
+
+        let path = request
+            .uri()
+            .path_and_query()
+            .map(|path_and_query| path_and_query.as_str())
+            .unwrap_or_else(|| request.path());
+        let message = signed_request_message(
+            request.method().as_str(),
+            path,
+            parsed.timestamp,
+            &parsed.nonce,
+            &parsed.body_sha256,
+        );
+
+        let verifier = self
+            .verifier_factory
+            .lock()
+            .map_err(|_| IdentityProviderError::new("signature verifier factory lock poisoned"))?
+            .create_verifier();
+        let public_key = to_hex(&parsed.public_key);
+        match verifier.verify(&message, &parsed.signature, &parsed.public_key) {
+            Ok(true) => (),
+            Ok(false) => {
+                debug!("Invalid signature for request by key {}", public_key);
+                return Ok(None);
+            }
+            Err(err) => {
+                debug!("Unable to verify request signature: {}", err);
+                return Ok(None);
+            }
+        }
+
+        if !self.use_nonce(&public_key, &parsed.nonce, parsed.timestamp, now)? {
+            debug!(
+                "Nonce {} of signed request by key {} was already used",
+                parsed.nonce, public_key
+            );
+            return Ok(None);
+        }
+
+        let identity = Identity::Key(public_key);
+        request.extensions_mut().insert(VerifiedSignedRequest {
+            credentials: credentials.to_string(),
+            identity: identity.clone(),
+            body_sha256: parsed.body_sha256,
+        });
+        Ok(Some(identity))
+    }
+}
+
+/// Wraps the payload of a request that was identified by its signature, so that reading the body
+/// fails if it does not match the signed digest. The payloads of other requests are returned
+/// unchanged.
+pub(crate) fn verify_signed_body(request: &HttpRequest, payload: web::Payload) -> web::Payload {
+    match request.extensions().get::<VerifiedSignedRequest>() {
+        Some(verified) => web::Payload(dev::Payload::Stream(Box::new(SignedBodyStream {
+            payload,
+            hasher: Some(Sha256::new()),
+            body_sha256: verified.body_sha256.clone(),
+        }))),
+        None => payload,
+    }
+}
+
+/// A request body stream that checks the body's digest once the body has been read
+struct SignedBodyStream {
+    payload: web::Payload,
+    hasher: Option<Sha256>,
+    body_sha256: String,
+}
+
+impl Stream for SignedBodyStream {
+    type Item = Bytes;
+    type Error = PayloadError;
+
+    fn poll(&mut self) -> Poll<Option<Bytes>, PayloadError> {
+        match self.payload.poll()? {
+            Async::Ready(Some(chunk)) => {
+                if let Some(hasher) = self.hasher.as_mut() {
+                    hasher.update(&chunk);
+                }
+                Ok(Async::Ready(Some(chunk)))
+            }
+            Async::Ready(None) => match self.hasher.take() {
+                Some(hasher) if to_hex(&hasher.finish()) != self.body_sha256 => {
+                    Err(PayloadError::Io(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Request body does not match its signed digest",
+                    )))
+                }
+                _ => Ok(Async::Ready(None)),
+            },
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+/// The parts of the credentials of a signed request
+struct SignedRequestCredentials {
+    public_key: Vec<u8>,
+    timestamp: u64,
+    nonce: String,
+    body_sha256: String,
+    signature: Vec<u8>,
+}
+
+/// Parses `<public_key>:<timestamp>:<nonce>:<body_sha256>:<signature>`, decoding the public key
+/// and signature
+fn parse_credentials(credentials: &str) -> Option<SignedRequestCredentials> {
+    let mut parts = credentials.split(':');
+    let public_key = parse_hex(parts.next()?).ok()?;
+    let timestamp = parts.next()?.parse().ok()?;
+    let nonce = parts.next()?;
+    if nonce.is_empty() || nonce.len() > MAX_NONCE_LEN {
+        return None;
+    }
+    let body_sha256 = parts.next()?.to_lowercase();
+    parse_hex(&body_sha256)
+        .ok()
+        .filter(|digest| digest.len() == 32)?;
+    let signature = parse_hex(parts.next()?).ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(SignedRequestCredentials {
+        public_key,
+        timestamp,
+        nonce: nonce.to_string(),
+        body_sha256,
+        signature,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::test::TestRequest;
+    use futures::Future;
+
+    use crate::signing::{Error as SigningError, SignatureVerifier};
+
+    /// A verifier that accepts a signature if it is the message with the public key appended
+    struct AppendKeyVerifier;
+
+    impl SignatureVerifier for AppendKeyVerifier {
+        fn verify(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+            pk: &[u8],
+        ) -> Result<bool, SigningError> {
+            Ok(signature == [message, pk].concat().as_slice())
+        }
+    }
+
+    struct AppendKeyVerifierFactory;
+
+    impl SignatureVerifierFactory for AppendKeyVerifierFactory {
+        fn create_verifier(&self) -> Box<dyn SignatureVerifier> {
+            Box::new(AppendKeyVerifier)
+        }
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get time")
+            .as_secs()
+    }
+
+    fn signed_request(
+        path: &str,
+        public_key: &[u8],
+        timestamp: u64,
+        nonce: &str,
+        message: &[u8],
+    ) -> HttpRequest {
+        let signature = [message, public_key].concat();
+        TestRequest::with_uri(path)
+            .header(
+                "Authorization",
+                format!(
+                    "{} {}:{}:{}:{}:{}",
+                    SIGNED_REQUEST_SCHEME,
+                    to_hex(public_key),
+                    timestamp,
+                    nonce,
+                    body_sha256(b""),
+                    to_hex(&signature)
+                ),
+            )
+            .to_http_request()
+    }
+
+    /// Verifies that the `SignedRequestIdentityProvider` identifies the key that signed a request,
+    /// and ignores requests that are unsigned, signed for another path, or signed too long ago.
+    #[test]
+    fn signed_request_identity() {
+        let provider = SignedRequestIdentityProvider::new(Box::new(AppendKeyVerifierFactory));
+        let public_key = b"public_key".to_vec();
+        let timestamp = now();
+        let empty_body = body_sha256(b"");
+
+        let request = signed_request(
+            "/admin/circuits?status=active",
+            &public_key,
+            timestamp,
+            "nonce1",
+            &signed_request_message(
+                "GET",
+                "/admin/circuits?status=active",
+                timestamp,
+                "nonce1",
+                &empty_body,
+            ),
+        );
+        assert_eq!(
+            provider
+                .get_identity(&request)
+                .expect("Failed to get identity"),
+            Some(Identity::Key(to_hex(&public_key)))
+        );
+        // The same request is identified again, as its nonce was used by the request itself
+        assert_eq!(
+            provider
+                .get_identity(&request)
+                .expect("Failed to get identity"),
+            Some(Identity::Key(to_hex(&public_key)))
+        );
+
+        let request = signed_request(
+            "/admin/circuits",
+            &public_key,
+            timestamp,
+            "nonce2",
+            &signed_request_message("GET", "/admin/proposals", timestamp, "nonce2", &empty_body),
+        );
+        assert_eq!(
+            provider
+                .get_identity(&request)
+                .expect("Failed to get identity"),
+            None
+        );
+
+        let stale = timestamp - MAX_CLOCK_SKEW_SECS - 60;
+        let request = signed_request(
+            "/admin/circuits",
+            &public_key,
+            stale,
+            "nonce3",
+            &signed_request_message("GET", "/admin/circuits", stale, "nonce3", &empty_body),
+        );
+        assert_eq!(
+            provider
+                .get_identity(&request)
+                .expect("Failed to get identity"),
+            None
+        );
+
+        let request = TestRequest::with_uri("/admin/circuits")
+            .header("Authorization", "Bearer token")
+            .to_http_request();
+        assert_eq!(
+            provider
+                .get_identity(&request)
+                .expect("Failed to get identity"),
+            None
+        );
+    }
+
+    /// Verifies that a signed request cannot be replayed: a second request with the same key and
+    /// nonce is not identified, while a request with a new nonce is.
+    #[test]
+    fn signed_request_replay() {
+        let provider = SignedRequestIdentityProvider::new(Box::new(AppendKeyVerifierFactory));
+        let public_key = b"public_key".to_vec();
+        let timestamp = now();
+        let message = |nonce| {
+            signed_request_message(
+                "GET",
+                "/admin/circuits",
+                timestamp,
+                nonce,
+                &body_sha256(b""),
+            )
+        };
+
+        let request = signed_request(
+            "/admin/circuits",
+            &public_key,
+            timestamp,
+            "nonce",
+            &message("nonce"),
+        );
+        assert!(provider
+            .get_identity(&request)
+            .expect("Failed to get identity")
+            .is_some());
+
+        let replayed = signed_request(
+            "/admin/circuits",
+            &public_key,
+            timestamp,
+            "nonce",
+            &message("nonce"),
+        );
+        assert_eq!(
+            provider
+                .get_identity(&replayed)
+                .expect("Failed to get identity"),
+            None
+        );
+
+        let request = signed_request(
+            "/admin/circuits",
+            &public_key,
+            timestamp,
+            "other-nonce",
+            &message("other-nonce"),
+        );
+        assert!(provider
+            .get_identity(&request)
+            .expect("Failed to get identity")
+            .is_some());
+    }
+
+    /// Verifies that the body of a signed request is only read successfully if it matches the
+    /// signed digest.
+    #[test]
+    fn signed_request_body() {
+        assert_eq!(
+            read_signed_body(b"signed body", &body_sha256(b"signed body")),
+            Ok(b"signed body".to_vec())
+        );
+        assert_eq!(
+            read_signed_body(b"other body", &body_sha256(b"signed body")),
+            Err(())
+        );
+    }
+
+    fn read_signed_body(body: &'static [u8], signed_body_sha256: &str) -> Result<Vec<u8>, ()> {
+        let (request, payload) = TestRequest::default().set_payload(body).to_http_parts();
+        request.extensions_mut().insert(VerifiedSignedRequest {
+            credentials: "credentials".into(),
+            identity: Identity::Key("public_key".into()),
+            body_sha256: signed_body_sha256.into(),
+        });
+
+        verify_signed_body(&request, web::Payload(payload))
+            .concat2()
+            .wait()
+            .map(|body| body.to_vec())
+            .map_err(|_| ())
+    }
+}
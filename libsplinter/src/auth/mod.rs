@@ -14,5 +14,9 @@
 
 //! Authentication and authorization for Splinter
 
+#[cfg(feature = "authorization")]
+pub mod authorization;
+#[cfg(feature = "authorization")]
+pub mod identity;
 #[cfg(feature = "oauth")]
 pub mod oauth;
@@ -46,6 +46,8 @@ impl Error for OAuthClientError {}
 /// An error that can occur when configuring an OAuth client
 #[derive(Debug)]
 pub enum OAuthClientConfigurationError {
+    /// The provider's OpenID Connect discovery document could not be retrieved or was invalid
+    DiscoveryFailed(String),
     /// The specified authorization URL for the provider was invalid
     InvalidAuthUrl(String),
     /// The specified redirect URL for the client was invalid
@@ -57,6 +59,13 @@ pub enum OAuthClientConfigurationError {
 impl fmt::Display for OAuthClientConfigurationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Self::DiscoveryFailed(msg) => {
+                write!(
+                    f,
+                    "failed to discover OpenID provider configuration: {}",
+                    msg
+                )
+            }
             Self::InvalidAuthUrl(msg) => {
                 write!(f, "provided authorization URL is invalid: {}", msg)
             }
@@ -17,6 +17,7 @@
 mod error;
 #[cfg(feature = "rest-api")]
 pub mod rest_api;
+mod subject;
 
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -25,20 +26,30 @@ use oauth2::{
     basic::{BasicClient, BasicTokenResponse},
     reqwest::http_client,
     AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge,
-    PkceCodeVerifier, RedirectUrl, Scope, TokenResponse, TokenUrl,
+    PkceCodeVerifier, RedirectUrl, RefreshToken, RequestTokenError, Scope, TokenResponse, TokenUrl,
 };
 
 use crate::collections::TtlMap;
 
 pub use error::{OAuthClientConfigurationError, OAuthClientError};
+pub use subject::{GithubSubjectProvider, OpenIdSubjectProvider, SubjectProvider};
 
 /// The amount of time before a pending authorization expires and a new request must be made
 const PENDING_AUTHORIZATION_EXPIRATION_SECS: u64 = 3600; // 1 hour
 
+/// GitHub's OAuth endpoints
+const GITHUB_AUTH_URL: &str = "https://github.com/login/oauth/authorize";
+const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+
+/// The scopes requested from OpenID Connect providers; `offline_access` is requested so the
+/// provider issues a refresh token
+const OPENID_SCOPES: &[&str] = &["openid", "offline_access"];
+
 /// An OAuth2 client for Splinter
 ///
 /// This client currently supports OAuth2 authorization code grants
-/// (<https://tools.ietf.org/html/rfc6749#section-4.1>).
+/// (<https://tools.ietf.org/html/rfc6749#section-4.1>) and refreshing access tokens
+/// (<https://tools.ietf.org/html/rfc6749#section-6>).
 #[derive(Clone)]
 pub struct OAuthClient {
     /// The inner OAuth2 client
@@ -47,6 +58,8 @@ pub struct OAuthClient {
     pending_authorizations: Arc<Mutex<TtlMap<String, String>>>,
     /// The scopes that will be requested for each user that's authenticated
     scopes: Vec<String>,
+    /// Looks up the identity of authenticated users
+    subject_provider: Arc<dyn SubjectProvider>,
 }
 
 impl OAuthClient {
@@ -62,6 +75,7 @@ impl OAuthClient {
     /// * `token_url` - The provider's endpoint for exchanging an authorization code for an access
     ///   token
     /// * `scopes` - The scopes that will be requested for each user
+    /// * `subject_provider` - Looks up the identity of users with the provider
     pub fn new(
         client_id: String,
         client_secret: String,
@@ -69,6 +83,7 @@ impl OAuthClient {
         redirect_url: String,
         token_url: String,
         scopes: Vec<String>,
+        subject_provider: Box<dyn SubjectProvider>,
     ) -> Result<Self, OAuthClientConfigurationError> {
         let client =
             BasicClient::new(
@@ -90,9 +105,72 @@ impl OAuthClient {
                 PENDING_AUTHORIZATION_EXPIRATION_SECS,
             )))),
             scopes,
+            subject_provider: subject_provider.into(),
         })
     }
 
+    /// Creates a new `OAuthClient` for GitHub
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The GitHub OAuth app's client ID
+    /// * `client_secret` - The GitHub OAuth app's client secret
+    /// * `redirect_url` - The endpoint that GitHub will redirect to after it has completed
+    ///   authorization
+    pub fn new_github(
+        client_id: String,
+        client_secret: String,
+        redirect_url: String,
+    ) -> Result<Self, OAuthClientConfigurationError> {
+        Self::new(
+            client_id,
+            client_secret,
+            GITHUB_AUTH_URL.into(),
+            redirect_url,
+            GITHUB_TOKEN_URL.into(),
+            vec![],
+            Box::new(GithubSubjectProvider),
+        )
+    }
+
+    /// Creates a new `OAuthClient` for an OpenID Connect provider, using the provider's discovery
+    /// document (<https://openid.net/specs/openid-connect-discovery-1_0.html>) to determine its
+    /// endpoints
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The OAuth client ID
+    /// * `client_secret` - The OAuth client secret
+    /// * `redirect_url` - The endpoint that the provider will redirect to after it has completed
+    ///   authorization
+    /// * `discovery_url` - The URL of the provider's discovery document
+    pub fn new_openid(
+        client_id: String,
+        client_secret: String,
+        redirect_url: String,
+        discovery_url: &str,
+    ) -> Result<Self, OAuthClientConfigurationError> {
+        let discovery_document = reqwest::blocking::get(discovery_url)
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.json::<OpenIdDiscoveryDocument>())
+            .map_err(|err| OAuthClientConfigurationError::DiscoveryFailed(err.to_string()))?;
+
+        Self::new(
+            client_id,
+            client_secret,
+            discovery_document.authorization_endpoint,
+            redirect_url,
+            discovery_document.token_endpoint,
+            OPENID_SCOPES
+                .iter()
+                .map(|scope| scope.to_string())
+                .collect(),
+            Box::new(OpenIdSubjectProvider::new(
+                discovery_document.userinfo_endpoint,
+            )),
+        )
+    }
+
     /// Generates the URL that the end user should be redirected to for authorization
     pub fn get_authorization_url(&self) -> Result<String, OAuthClientError> {
         let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
@@ -151,6 +229,47 @@ impl OAuthClient {
 
         Ok(Some(UserTokens::from(token_response)))
     }
+
+    /// Exchanges the given refresh token for a new access token. Returns `Ok(None)` if the
+    /// provider rejects the refresh token, in which case the user must be authorized again.
+    ///
+    /// # Arguments
+    ///
+    /// * `refresh_token` - The refresh token that was supplied by the OAuth provider
+    pub fn exchange_refresh_token(
+        &self,
+        refresh_token: String,
+    ) -> Result<Option<UserTokens>, OAuthClientError> {
+        match self
+            .client
+            .exchange_refresh_token(&RefreshToken::new(refresh_token))
+            .request(http_client)
+        {
+            Ok(token_response) => Ok(Some(UserTokens::from(token_response))),
+            Err(RequestTokenError::ServerResponse(err)) => {
+                debug!("OAuth provider rejected refresh token: {}", err);
+                Ok(None)
+            }
+            Err(err) => Err(OAuthClientError::new(&format!(
+                "failed to make refresh token exchange request: {}",
+                err,
+            ))),
+        }
+    }
+
+    /// Gets the identity of the user that the given access token was issued to. Returns
+    /// `Ok(None)` if the provider does not accept the access token.
+    pub fn get_subject(&self, access_token: &str) -> Result<Option<String>, OAuthClientError> {
+        self.subject_provider.get_subject(access_token)
+    }
+}
+
+/// The parts of an OpenID Connect discovery document that are used by the client
+#[derive(Deserialize)]
+struct OpenIdDiscoveryDocument {
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
 }
 
 /// User information returned by the OAuth2 client
@@ -223,6 +342,7 @@ mod tests {
             "https://localhost/oauth/callback".into(),
             "https://provider.com/token".into(),
             vec![],
+            Box::new(GithubSubjectProvider),
         )
         .expect("Failed to create client from valid inputs");
 
@@ -234,6 +354,7 @@ mod tests {
                 "https://localhost/oauth/callback".into(),
                 "https://provider.com/token".into(),
                 vec![],
+                Box::new(GithubSubjectProvider),
             ),
             Err(OAuthClientConfigurationError::InvalidAuthUrl(_))
         ));
@@ -246,6 +367,7 @@ mod tests {
                 "invalid_redirect_url".into(),
                 "https://provider.com/token".into(),
                 vec![],
+                Box::new(GithubSubjectProvider),
             ),
             Err(OAuthClientConfigurationError::InvalidRedirectUrl(_))
         ));
@@ -258,6 +380,7 @@ mod tests {
                 "https://localhost/oauth/callback".into(),
                 "invalid_token_url".into(),
                 vec![],
+                Box::new(GithubSubjectProvider),
             ),
             Err(OAuthClientConfigurationError::InvalidTokenUrl(_))
         ));
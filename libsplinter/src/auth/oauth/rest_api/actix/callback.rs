@@ -12,21 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! The `GET /oauth/callback` endpoint for receiving the authorization code from the provider and
-//! exchanging it for an access token.
+//! The `GET /oauth/callback` endpoint for receiving the authorization code from the provider,
+//! exchanging it for an access token and starting a Splinter session for the user.
 
 use actix_web::{web::Query, HttpResponse};
 use futures::future::IntoFuture;
 
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::auth::oauth::{
-    rest_api::resources::callback::{CallbackQuery, CallbackResponse},
-    OAuthClient,
+    rest_api::{
+        resources::callback::{CallbackQuery, CallbackResponse},
+        session::OAuthSessionManager,
+    },
+    UserTokens,
 };
 use crate::protocol;
 use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
 
-pub fn make_callback_route(client: OAuthClient) -> Resource {
-    Resource::build("/oauth/callback")
+pub fn make_callback_route(session_manager: OAuthSessionManager) -> Resource {
+    let resource = Resource::build("/oauth/callback");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::OAUTH_CALLBACK_MIN,
             protocol::OAUTH_PROTOCOL_VERSION,
@@ -35,10 +43,11 @@ pub fn make_callback_route(client: OAuthClient) -> Resource {
             Box::new(
                 match Query::<CallbackQuery>::from_query(req.query_string()) {
                     Ok(query) => {
-                        match client.exchange_authorization_code(query.code.clone(), &query.state) {
-                            Ok(Some(user_tokens)) => {
-                                HttpResponse::Ok().json(CallbackResponse::from(&user_tokens))
-                            }
+                        match session_manager
+                            .client()
+                            .exchange_authorization_code(query.code.clone(), &query.state)
+                        {
+                            Ok(Some(user_tokens)) => start_session(&session_manager, &user_tokens),
                             Ok(None) => {
                                 error!(
                                 "Received OAuth callback request that does not correlate to an \
@@ -66,3 +75,22 @@ pub fn make_callback_route(client: OAuthClient) -> Resource {
             )
         })
 }
+
+fn start_session(session_manager: &OAuthSessionManager, user_tokens: &UserTokens) -> HttpResponse {
+    match session_manager.start_session(user_tokens) {
+        Ok(Some((user_id, token))) => HttpResponse::Ok().json(CallbackResponse {
+            user_id: &user_id,
+            token: &token,
+        }),
+        Ok(None) => {
+            error!("OAuth provider did not accept the access token it issued");
+            HttpResponse::Unauthorized().json(ErrorResponse::unauthorized(
+                "User could not be authenticated by the OAuth provider",
+            ))
+        }
+        Err(err) => {
+            error!("{}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    }
+}
@@ -17,12 +17,17 @@
 use actix_web::{http::header::LOCATION, HttpResponse};
 use futures::future::IntoFuture;
 
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::auth::oauth::OAuthClient;
 use crate::protocol;
 use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
 
 pub fn make_login_route(client: OAuthClient) -> Resource {
-    Resource::build("/oauth/login")
+    let resource = Resource::build("/oauth/login");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::OAUTH_LOGIN_MIN,
             protocol::OAUTH_PROTOCOL_VERSION,
@@ -14,3 +14,5 @@
 
 pub(super) mod callback;
 pub(super) mod login;
+pub(super) mod logout;
+pub(super) mod token;
@@ -0,0 +1,66 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `POST /oauth/token` endpoint for issuing a new Splinter access token for an OAuth session.
+//!
+//! The request must include a (possibly expired) access token for the session in the
+//! `Authorization` header.
+
+use actix_web::HttpResponse;
+use futures::future::IntoFuture;
+
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::auth::oauth::rest_api::session::OAuthSessionManager;
+use crate::protocol;
+use crate::rest_api::{
+    get_authorization_token, ErrorResponse, Method, ProtocolVersionRangeGuard, Resource,
+};
+
+pub fn make_token_route(session_manager: OAuthSessionManager) -> Resource {
+    let resource = Resource::build("/oauth/token");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::OAUTH_TOKEN_MIN,
+            protocol::OAUTH_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Post, move |req, _| {
+            let token = match get_authorization_token(&req) {
+                Ok(token) => token,
+                Err(err) => {
+                    debug!("Failed to get OAuth session token: {}", err);
+                    return Box::new(
+                        HttpResponse::Unauthorized()
+                            .json(ErrorResponse::unauthorized("User is not authorized"))
+                            .into_future(),
+                    );
+                }
+            };
+
+            Box::new(
+                match session_manager.refresh_session(&token) {
+                    Ok(Some(token)) => HttpResponse::Ok().json(json!({ "token": token })),
+                    Ok(None) => HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized("User is not authorized")),
+                    Err(err) => {
+                        error!("{}", err);
+                        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                    }
+                }
+                .into_future(),
+            )
+        })
+}
@@ -0,0 +1,72 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A request guard for protecting REST API resources with OAuth sessions
+
+use actix_web::{HttpRequest, HttpResponse};
+use futures::future::IntoFuture;
+
+use crate::rest_api::{get_authorization_token, Continuation, ErrorResponse, RequestGuard};
+
+use super::session::OAuthSessionManager;
+
+/// A request guard that only allows requests that include a Splinter access token for an active
+/// OAuth session in the `Authorization` header
+///
+/// If the user's provider access token has expired, it is refreshed before the request is allowed;
+/// requests are rejected with `401 Unauthorized` if the token is missing or invalid, or if the
+/// session has ended.
+#[derive(Clone)]
+pub struct OAuthSessionGuard {
+    session_manager: OAuthSessionManager,
+}
+
+impl OAuthSessionGuard {
+    pub(super) fn new(session_manager: OAuthSessionManager) -> Self {
+        Self { session_manager }
+    }
+}
+
+impl RequestGuard for OAuthSessionGuard {
+    fn evaluate(&self, req: &HttpRequest) -> Continuation {
+        let token = match get_authorization_token(req) {
+            Ok(token) => token,
+            Err(err) => {
+                debug!("Failed to get OAuth session token: {}", err);
+                return unauthorized();
+            }
+        };
+
+        match self.session_manager.authenticate(&token, false) {
+            Ok(Some(_)) => Continuation::Continue,
+            Ok(None) => unauthorized(),
+            Err(err) => {
+                error!("{}", err);
+                Continuation::terminate(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    }
+}
+
+fn unauthorized() -> Continuation {
+    Continuation::terminate(
+        HttpResponse::Unauthorized()
+            .json(ErrorResponse::unauthorized("User is not authorized"))
+            .into_future(),
+    )
+}
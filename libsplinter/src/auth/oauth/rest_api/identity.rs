@@ -0,0 +1,53 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An identity provider for users that log in through OAuth
+
+use crate::actix_web::HttpRequest;
+use crate::auth::identity::{Identity, IdentityProvider, IdentityProviderError};
+use crate::rest_api::get_authorization_token;
+
+use super::session::OAuthSessionManager;
+
+/// Identifies OAuth users by the Splinter access tokens of their active sessions; users are
+/// identified by the ID of the Biome user that their provider identity is mapped to
+#[derive(Clone)]
+pub struct OAuthUserIdentityProvider {
+    session_manager: OAuthSessionManager,
+}
+
+impl OAuthUserIdentityProvider {
+    pub(super) fn new(session_manager: OAuthSessionManager) -> Self {
+        Self { session_manager }
+    }
+}
+
+impl IdentityProvider for OAuthUserIdentityProvider {
+    fn get_identity(
+        &self,
+        request: &HttpRequest,
+    ) -> Result<Option<Identity>, IdentityProviderError> {
+        let token = match get_authorization_token(request) {
+            Ok(token) => token,
+            Err(_) => return Ok(None),
+        };
+
+        self.session_manager
+            .authenticate(&token, false)
+            .map(|oauth_user| {
+                oauth_user.map(|oauth_user| Identity::User(oauth_user.user_id().to_string()))
+            })
+            .map_err(|err| IdentityProviderError::new(&err.to_string()))
+    }
+}
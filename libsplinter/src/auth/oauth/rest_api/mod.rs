@@ -16,37 +16,95 @@
 
 #[cfg(feature = "rest-api-actix")]
 mod actix;
+mod guard;
+#[cfg(feature = "authorization")]
+mod identity;
 mod resources;
+mod session;
 
-use crate::rest_api::{Resource, RestResourceProvider};
+use std::sync::Arc;
+
+use crate::biome::{OAuthUserStore, UserStore};
+use crate::encryption;
+use crate::rest_api::{secrets::SecretManager, Resource, RestResourceProvider};
 
 use super::OAuthClient;
 
+pub use guard::OAuthSessionGuard;
+#[cfg(feature = "authorization")]
+pub use identity::OAuthUserIdentityProvider;
+use session::OAuthSessionManager;
+
 /// Provides the REST API [Resource](../../../rest_api/struct.Resource.html) definitions for OAuth
 /// endpoints. The following endpoints are provided:
 ///
 /// * `GET /oauth/login` - Get the URL for requesting authorization from the provider
-/// * `GET /oauth/callback` - Receive the authorization code from the provider
+/// * `GET /oauth/callback` - Receive the authorization code from the provider and start a session
+/// * `POST /oauth/token` - Get a new Splinter access token for the current session
+/// * `PATCH /oauth/logout` - End the current session
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
 /// * `rest-api-actix`
+///
+/// Users that log in are mapped to Biome users by their identity with the provider, and are
+/// issued Splinter access tokens for their session. Other resources can be protected with these
+/// sessions using the guard returned by `session_guard`.
 #[derive(Clone)]
 pub struct OAuthResourceProvider {
-    client: OAuthClient,
+    session_manager: OAuthSessionManager,
 }
 
 impl OAuthResourceProvider {
     /// Creates a new `OAuthResourceProvider`
-    pub fn new(client: OAuthClient) -> Self {
-        Self { client }
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The OAuth client for the provider
+    /// * `oauth_user_store` - Stores the mapping of provider identities to Biome users, along with
+    ///   their sessions
+    /// * `user_store` - Stores the Biome users that are created for new provider identities
+    /// * `secret_manager` - Provides the secret for signing and verifying Splinter access tokens
+    /// * `token_encryption_key` - The key used to encrypt the provider's tokens before they are
+    ///   stored, such as one generated by `generate_token_encryption_key`
+    pub fn new(
+        client: OAuthClient,
+        oauth_user_store: Arc<dyn OAuthUserStore>,
+        user_store: Arc<dyn UserStore>,
+        secret_manager: Arc<dyn SecretManager>,
+        token_encryption_key: &[u8],
+    ) -> Self {
+        Self {
+            session_manager: OAuthSessionManager::new(
+                client,
+                oauth_user_store,
+                user_store,
+                secret_manager,
+                token_encryption_key,
+            ),
+        }
+    }
+
+    /// Returns a request guard that only allows requests with a Splinter access token for an
+    /// active OAuth session
+    pub fn session_guard(&self) -> OAuthSessionGuard {
+        OAuthSessionGuard::new(self.session_manager.clone())
+    }
+
+    /// Returns an identity provider that identifies users by the Splinter access tokens of their
+    /// active OAuth sessions
+    #[cfg(feature = "authorization")]
+    pub fn identity_provider(&self) -> OAuthUserIdentityProvider {
+        OAuthUserIdentityProvider::new(self.session_manager.clone())
     }
 }
 
 /// `OAuthResourceProvider` provides the following endpoints as REST API resources:
 ///
 /// * `GET /oauth/login` - Get the URL for requesting authorization from the provider
-/// * `GET /oauth/callback` - Receive the authorization code from the provider
+/// * `GET /oauth/callback` - Receive the authorization code from the provider and start a session
+/// * `POST /oauth/token` - Get a new Splinter access token for the current session
+/// * `PATCH /oauth/logout` - End the current session
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
@@ -61,11 +119,18 @@ impl RestResourceProvider for OAuthResourceProvider {
         #[cfg(feature = "rest-api-actix")]
         {
             resources.append(&mut vec![
-                actix::login::make_login_route(self.client.clone()),
-                actix::callback::make_callback_route(self.client.clone()),
+                actix::login::make_login_route(self.session_manager.client().clone()),
+                actix::callback::make_callback_route(self.session_manager.clone()),
+                actix::token::make_token_route(self.session_manager.clone()),
+                actix::logout::make_logout_route(self.session_manager.clone()),
             ]);
         }
 
         resources
     }
 }
+
+/// Generates a new random key for encrypting the provider's tokens of OAuth users.
+pub fn generate_token_encryption_key() -> Vec<u8> {
+    encryption::generate_key()
+}
@@ -12,8 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::auth::oauth::UserTokens;
-
 #[derive(Deserialize)]
 pub struct CallbackQuery {
     pub code: String,
@@ -22,17 +20,6 @@ pub struct CallbackQuery {
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct CallbackResponse<'a> {
-    pub access_token: &'a str,
-    pub expires_in: Option<u64>,
-    pub refresh_token: Option<&'a str>,
-}
-
-impl<'a> From<&'a UserTokens> for CallbackResponse<'a> {
-    fn from(tokens: &'a UserTokens) -> Self {
-        Self {
-            access_token: tokens.access_token(),
-            expires_in: tokens.expires_in().map(|duration| duration.as_secs()),
-            refresh_token: tokens.refresh_token(),
-        }
-    }
+    pub user_id: &'a str,
+    pub token: &'a str,
 }
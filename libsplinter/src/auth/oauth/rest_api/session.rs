@@ -0,0 +1,601 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Management of the Splinter sessions of users that are authenticated by an OAuth provider
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use jsonwebtoken::{decode, Validation};
+use uuid::Uuid;
+
+use crate::auth::oauth::{OAuthClient, OAuthClientError, UserTokens};
+use crate::biome::{
+    OAuthUser, OAuthUserStore, OAuthUserStoreError, User, UserStore, UserStoreError,
+};
+use crate::encryption::{self, EncryptionError};
+use crate::rest_api::secrets::{SecretManager, SecretManagerError};
+use crate::rest_api::sessions::{
+    default_validation, ignore_exp_validation, AccessTokenIssuer, Claims, ClaimsBuildError,
+    ClaimsBuilder, TokenIssuer, TokenIssuerError,
+};
+
+/// The issuer of the Splinter access tokens for OAuth sessions
+const OAUTH_ISSUER: &str = "splinter-oauth";
+/// The amount of time that a Splinter access token for an OAuth session is valid for
+const SESSION_TOKEN_DURATION_SECS: u64 = 5400; // 90 minutes
+/// The custom claim that identifies the session that a Splinter access token was issued for
+const SESSION_ID_CLAIM: &str = "oauth_session";
+
+/// Manages the Splinter sessions of OAuth users
+///
+/// Each OAuth user has at most one session, which holds the provider's tokens. The Splinter access
+/// tokens issued for a session are only accepted while the session is active, so starting a new
+/// session or logging out invalidates all previously issued tokens. When the provider's access
+/// token expires, it is refreshed using the provider's refresh token; if it cannot be refreshed,
+/// the session is ended and the user must log in again. The provider's tokens are encrypted before
+/// they are stored.
+#[derive(Clone)]
+pub(super) struct OAuthSessionManager {
+    client: OAuthClient,
+    oauth_user_store: Arc<dyn OAuthUserStore>,
+    user_store: Arc<dyn UserStore>,
+    secret_manager: Arc<dyn SecretManager>,
+    token_encryption_key: Arc<Vec<u8>>,
+}
+
+impl OAuthSessionManager {
+    pub fn new(
+        client: OAuthClient,
+        oauth_user_store: Arc<dyn OAuthUserStore>,
+        user_store: Arc<dyn UserStore>,
+        secret_manager: Arc<dyn SecretManager>,
+        token_encryption_key: &[u8],
+    ) -> Self {
+        Self {
+            client,
+            oauth_user_store,
+            user_store,
+            secret_manager,
+            token_encryption_key: Arc::new(token_encryption_key.to_vec()),
+        }
+    }
+
+    pub fn client(&self) -> &OAuthClient {
+        &self.client
+    }
+
+    /// Starts a new session for the user that the given provider tokens were issued to, creating a
+    /// Biome user for the provider identity if one does not already exist. Returns the user's ID
+    /// and a Splinter access token for the session, or `None` if the provider does not accept its
+    /// own access token.
+    pub fn start_session(
+        &self,
+        user_tokens: &UserTokens,
+    ) -> Result<Option<(String, String)>, OAuthSessionError> {
+        let subject = match self.client.get_subject(user_tokens.access_token())? {
+            Some(subject) => subject,
+            None => return Ok(None),
+        };
+
+        let oauth_user = match self.oauth_user_store.fetch_by_subject(&subject)? {
+            Some(oauth_user) => oauth_user,
+            None => self.add_oauth_user(&subject)?,
+        };
+
+        let session_id = Uuid::new_v4().to_string();
+        let oauth_user = self.with_encrypted_session(
+            oauth_user,
+            &session_id,
+            user_tokens.access_token(),
+            user_tokens.refresh_token(),
+            expiration(user_tokens.expires_in()),
+        )?;
+        self.oauth_user_store
+            .update_oauth_user(oauth_user.clone())?;
+
+        let token = self.issue_token(&oauth_user)?;
+        Ok(Some((oauth_user.user_id().to_string(), token)))
+    }
+
+    /// Gets the OAuth user whose session the given Splinter access token was issued for,
+    /// refreshing the provider's tokens if they have expired. Returns `None` if the token is
+    /// invalid or its session is no longer active.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The Splinter access token
+    /// * `allow_expired` - Whether or not an expired Splinter access token is accepted
+    pub fn authenticate(
+        &self,
+        token: &str,
+        allow_expired: bool,
+    ) -> Result<Option<OAuthUser>, OAuthSessionError> {
+        let validation = if allow_expired {
+            ignore_exp_validation(OAUTH_ISSUER)
+        } else {
+            default_validation(OAUTH_ISSUER)
+        };
+        let claims = match self.decode_claims(token, &validation)? {
+            Some(claims) => claims,
+            None => return Ok(None),
+        };
+
+        let oauth_user = match self.oauth_user_store.fetch_by_user_id(&claims.user_id())? {
+            Some(oauth_user) => oauth_user,
+            None => return Ok(None),
+        };
+        let session_id = claims.custom_claims().remove(SESSION_ID_CLAIM);
+        if !oauth_user.has_session() || oauth_user.session_id() != session_id.as_deref() {
+            return Ok(None);
+        }
+
+        if oauth_user.is_expired() {
+            self.refresh_provider_tokens(oauth_user)
+        } else {
+            Ok(Some(oauth_user))
+        }
+    }
+
+    /// Issues a new Splinter access token for the session that the given (possibly expired)
+    /// Splinter access token was issued for. Returns `None` if the session is no longer active.
+    pub fn refresh_session(&self, token: &str) -> Result<Option<String>, OAuthSessionError> {
+        match self.authenticate(token, true)? {
+            Some(oauth_user) => Ok(Some(self.issue_token(&oauth_user)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Ends the session that the given (possibly expired) Splinter access token was issued for.
+    /// Returns `false` if the session is no longer active.
+    pub fn end_session(&self, token: &str) -> Result<bool, OAuthSessionError> {
+        match self.authenticate(token, true)? {
+            Some(oauth_user) => {
+                self.oauth_user_store
+                    .update_oauth_user(oauth_user.without_session())?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Creates a Biome user and an OAuth user for a new provider identity. If the identity is
+    /// added by a concurrent login first, the new Biome user is removed and the existing OAuth
+    /// user is returned instead.
+    fn add_oauth_user(&self, subject: &str) -> Result<OAuthUser, OAuthSessionError> {
+        let user_id = Uuid::new_v4().to_string();
+        debug!("Creating user {} for new OAuth user", user_id);
+        self.user_store.add_user(User::new(&user_id))?;
+
+        let oauth_user = OAuthUser::new(&user_id, subject);
+        let result = self.oauth_user_store.add_oauth_user(oauth_user.clone());
+        if result.is_err() {
+            // The two stores do not share a transaction, so the Biome user is removed to avoid
+            // leaving it without an OAuth user
+            if let Err(err) = self.user_store.remove_user(&user_id) {
+                error!("Failed to remove user {}: {}", user_id, err);
+            }
+        }
+
+        match result {
+            Ok(()) => Ok(oauth_user),
+            Err(OAuthUserStoreError::DuplicateError(msg)) => self
+                .oauth_user_store
+                .fetch_by_subject(subject)?
+                .ok_or_else(|| OAuthSessionError::new(&msg)),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Sets the user's session, encrypting the provider's tokens for storage
+    fn with_encrypted_session(
+        &self,
+        oauth_user: OAuthUser,
+        session_id: &str,
+        access_token: &str,
+        refresh_token: Option<&str>,
+        expires_at: Option<SystemTime>,
+    ) -> Result<OAuthUser, OAuthSessionError> {
+        let access_token =
+            encryption::encrypt(access_token.as_bytes(), &self.token_encryption_key)?;
+        let refresh_token = refresh_token
+            .map(|token| encryption::encrypt(token.as_bytes(), &self.token_encryption_key))
+            .transpose()?;
+        Ok(oauth_user.with_session(
+            session_id,
+            &access_token,
+            refresh_token.as_deref(),
+            expires_at,
+        ))
+    }
+
+    /// Decrypts the user's stored provider refresh token. A token that cannot be decrypted, such
+    /// as one that was encrypted with a previous key, is treated as missing.
+    fn decrypt_refresh_token(&self, oauth_user: &OAuthUser) -> Option<String> {
+        let encrypted = oauth_user.refresh_token()?;
+        match encryption::decrypt(encrypted, &self.token_encryption_key)
+            .map_err(|err| err.to_string())
+            .and_then(|token| String::from_utf8(token).map_err(|err| err.to_string()))
+        {
+            Ok(token) => Some(token),
+            Err(err) => {
+                debug!(
+                    "Unable to decrypt refresh token of user {}: {}",
+                    oauth_user.user_id(),
+                    err
+                );
+                None
+            }
+        }
+    }
+
+    /// Exchanges the user's provider refresh token for new provider tokens, ending the session if
+    /// the provider does not issue new tokens
+    fn refresh_provider_tokens(
+        &self,
+        oauth_user: OAuthUser,
+    ) -> Result<Option<OAuthUser>, OAuthSessionError> {
+        let refresh_token = self.decrypt_refresh_token(&oauth_user);
+        let user_tokens = match &refresh_token {
+            Some(refresh_token) => self
+                .client
+                .exchange_refresh_token(refresh_token.to_string())?,
+            None => None,
+        };
+
+        match (user_tokens, oauth_user.session_id()) {
+            (Some(user_tokens), Some(session_id)) => {
+                let session_id = session_id.to_string();
+                // Providers are not required to issue a new refresh token
+                let refresh_token = user_tokens
+                    .refresh_token()
+                    .map(String::from)
+                    .or(refresh_token);
+                let oauth_user = self.with_encrypted_session(
+                    oauth_user,
+                    &session_id,
+                    user_tokens.access_token(),
+                    refresh_token.as_deref(),
+                    expiration(user_tokens.expires_in()),
+                )?;
+                self.oauth_user_store
+                    .update_oauth_user(oauth_user.clone())?;
+                Ok(Some(oauth_user))
+            }
+            _ => {
+                debug!(
+                    "Ending OAuth session of user {}; provider tokens could not be refreshed",
+                    oauth_user.user_id()
+                );
+                self.oauth_user_store
+                    .update_oauth_user(oauth_user.without_session())?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn issue_token(&self, oauth_user: &OAuthUser) -> Result<String, OAuthSessionError> {
+        let session_id = oauth_user
+            .session_id()
+            .ok_or_else(|| OAuthSessionError::new("cannot issue a token without a session"))?;
+        let claims = ClaimsBuilder::default()
+            .with_user_id(oauth_user.user_id())
+            .with_issuer(OAUTH_ISSUER)
+            .with_duration(Duration::from_secs(SESSION_TOKEN_DURATION_SECS))
+            .with_custom_claim(SESSION_ID_CLAIM, session_id)
+            .build()?;
+
+        Ok(AccessTokenIssuer::new(
+            self.secret_manager.clone(),
+            #[cfg(feature = "biome-credentials")]
+            self.secret_manager.clone(),
+        )
+        .issue_token_with_claims(claims)?)
+    }
+
+    fn decode_claims(
+        &self,
+        token: &str,
+        validation: &Validation,
+    ) -> Result<Option<Claims>, OAuthSessionError> {
+        let secret = self.secret_manager.secret()?;
+        match decode::<Claims>(token, secret.as_ref(), validation) {
+            Ok(token_data) => Ok(Some(token_data.claims)),
+            Err(err) => {
+                debug!("Invalid OAuth session token: {}", err);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Converts the lifetime of a provider access token to its expiration time
+fn expiration(expires_in: Option<Duration>) -> Option<SystemTime> {
+    expires_in.map(|expires_in| SystemTime::now() + expires_in)
+}
+
+/// An unrecoverable error that can occur when managing an OAuth user's session
+#[derive(Debug)]
+pub(super) struct OAuthSessionError {
+    message: String,
+}
+
+impl OAuthSessionError {
+    fn new(message: &str) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for OAuthSessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to manage OAuth session: {}", self.message)
+    }
+}
+
+impl Error for OAuthSessionError {}
+
+impl From<OAuthClientError> for OAuthSessionError {
+    fn from(err: OAuthClientError) -> Self {
+        Self::new(&err.to_string())
+    }
+}
+
+impl From<OAuthUserStoreError> for OAuthSessionError {
+    fn from(err: OAuthUserStoreError) -> Self {
+        Self::new(&err.to_string())
+    }
+}
+
+impl From<UserStoreError> for OAuthSessionError {
+    fn from(err: UserStoreError) -> Self {
+        Self::new(&err.to_string())
+    }
+}
+
+impl From<ClaimsBuildError> for OAuthSessionError {
+    fn from(err: ClaimsBuildError) -> Self {
+        Self::new(&err.to_string())
+    }
+}
+
+impl From<TokenIssuerError> for OAuthSessionError {
+    fn from(err: TokenIssuerError) -> Self {
+        Self::new(&err.to_string())
+    }
+}
+
+impl From<EncryptionError> for OAuthSessionError {
+    fn from(err: EncryptionError) -> Self {
+        Self::new(&err.to_string())
+    }
+}
+
+impl From<SecretManagerError> for OAuthSessionError {
+    fn from(err: SecretManagerError) -> Self {
+        Self::new(&err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::auth::oauth::SubjectProvider;
+    #[cfg(feature = "biome-credentials")]
+    use crate::biome::MemoryCredentialsStore;
+    use crate::biome::{MemoryOAuthUserStore, MemoryUserStore};
+    use crate::rest_api::secrets::AutoSecretManager;
+
+    /// A subject provider that uses the access token as the subject, and rejects empty tokens
+    struct TestSubjectProvider;
+
+    impl SubjectProvider for TestSubjectProvider {
+        fn get_subject(&self, access_token: &str) -> Result<Option<String>, OAuthClientError> {
+            if access_token.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(access_token.to_string()))
+            }
+        }
+    }
+
+    /// Verifies the lifecycle of OAuth sessions:
+    ///
+    /// 1. Start a session for a new provider identity and verify that a Biome user is created,
+    ///    that the issued token is authenticated, and that the provider's token is stored
+    ///    encrypted.
+    /// 2. Refresh the session and verify that the new token is authenticated.
+    /// 3. Start a new session for the same identity and verify that the same user is used and
+    ///    that the tokens for the previous session are no longer authenticated.
+    /// 4. End the session and verify that its token is no longer authenticated, refreshed or
+    ///    ended again.
+    /// 5. Verify that invalid tokens are not authenticated and that no session is started for
+    ///    provider tokens the provider does not accept.
+    #[test]
+    fn session_lifecycle() {
+        #[cfg(feature = "biome-credentials")]
+        let user_store = MemoryUserStore::new(MemoryCredentialsStore::new());
+        #[cfg(not(feature = "biome-credentials"))]
+        let user_store = MemoryUserStore::new();
+
+        let session_manager = OAuthSessionManager::new(
+            test_client(),
+            Arc::new(MemoryOAuthUserStore::new()),
+            Arc::new(user_store.clone()),
+            Arc::new(AutoSecretManager::default()),
+            &encryption::generate_key(),
+        );
+
+        let (user_id, token) = session_manager
+            .start_session(&user_tokens("subject"))
+            .expect("Failed to start session")
+            .expect("Session not started");
+        user_store
+            .fetch_user(&user_id)
+            .expect("Biome user not created");
+        let oauth_user = session_manager
+            .authenticate(&token, false)
+            .expect("Failed to authenticate")
+            .expect("Token not authenticated");
+        assert_eq!(oauth_user.user_id(), user_id);
+        assert_eq!(oauth_user.subject(), "subject");
+        assert!(oauth_user.access_token().is_some());
+        assert_ne!(oauth_user.access_token(), Some("subject"));
+
+        let refreshed_token = session_manager
+            .refresh_session(&token)
+            .expect("Failed to refresh session")
+            .expect("Session not refreshed");
+        assert!(session_manager
+            .authenticate(&refreshed_token, false)
+            .expect("Failed to authenticate")
+            .is_some());
+
+        let (second_user_id, second_token) = session_manager
+            .start_session(&user_tokens("subject"))
+            .expect("Failed to start session")
+            .expect("Session not started");
+        assert_eq!(second_user_id, user_id);
+        assert!(session_manager
+            .authenticate(&token, false)
+            .expect("Failed to authenticate")
+            .is_none());
+        assert!(session_manager
+            .authenticate(&refreshed_token, false)
+            .expect("Failed to authenticate")
+            .is_none());
+
+        assert!(session_manager
+            .end_session(&second_token)
+            .expect("Failed to end session"));
+        assert!(session_manager
+            .authenticate(&second_token, false)
+            .expect("Failed to authenticate")
+            .is_none());
+        assert!(session_manager
+            .refresh_session(&second_token)
+            .expect("Failed to refresh session")
+            .is_none());
+        assert!(!session_manager
+            .end_session(&second_token)
+            .expect("Failed to end session"));
+
+        assert!(session_manager
+            .authenticate("invalid", false)
+            .expect("Failed to authenticate")
+            .is_none());
+        assert!(session_manager
+            .start_session(&user_tokens(""))
+            .expect("Failed to start session")
+            .is_none());
+    }
+
+    /// An OAuth user store that adds an OAuth user for the same subject just before each new OAuth
+    /// user is added, as a concurrent login would
+    struct RacingOAuthUserStore {
+        inner: MemoryOAuthUserStore,
+        user_store: Arc<dyn UserStore>,
+    }
+
+    impl OAuthUserStore for RacingOAuthUserStore {
+        fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+            self.user_store
+                .add_user(User::new("concurrent_user"))
+                .expect("Failed to add user");
+            self.inner
+                .add_oauth_user(OAuthUser::new("concurrent_user", oauth_user.subject()))?;
+            self.inner.add_oauth_user(oauth_user)
+        }
+
+        fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+            self.inner.update_oauth_user(oauth_user)
+        }
+
+        fn fetch_by_subject(
+            &self,
+            subject: &str,
+        ) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+            self.inner.fetch_by_subject(subject)
+        }
+
+        fn fetch_by_user_id(
+            &self,
+            user_id: &str,
+        ) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+            self.inner.fetch_by_user_id(user_id)
+        }
+    }
+
+    /// Verifies that if a concurrent login adds the OAuth user for a provider identity first, the
+    /// session is started for the existing user and the Biome user created for the losing login
+    /// is removed.
+    #[test]
+    fn concurrent_user_creation() {
+        #[cfg(feature = "biome-credentials")]
+        let user_store = MemoryUserStore::new(MemoryCredentialsStore::new());
+        #[cfg(not(feature = "biome-credentials"))]
+        let user_store = MemoryUserStore::new();
+
+        let session_manager = OAuthSessionManager::new(
+            test_client(),
+            Arc::new(RacingOAuthUserStore {
+                inner: MemoryOAuthUserStore::new(),
+                user_store: Arc::new(user_store.clone()),
+            }),
+            Arc::new(user_store.clone()),
+            Arc::new(AutoSecretManager::default()),
+            &encryption::generate_key(),
+        );
+
+        let (user_id, _) = session_manager
+            .start_session(&user_tokens("subject"))
+            .expect("Failed to start session")
+            .expect("Session not started");
+        assert_eq!(user_id, "concurrent_user");
+        assert_eq!(
+            user_store
+                .list_users()
+                .expect("Failed to list users")
+                .iter()
+                .map(User::id)
+                .collect::<Vec<_>>(),
+            vec!["concurrent_user".to_string()]
+        );
+    }
+
+    fn test_client() -> OAuthClient {
+        OAuthClient::new(
+            "client_id".into(),
+            "client_secret".into(),
+            "https://provider.com/auth".into(),
+            "https://localhost/oauth/callback".into(),
+            "https://provider.com/token".into(),
+            vec![],
+            Box::new(TestSubjectProvider),
+        )
+        .expect("Failed to create client")
+    }
+
+    fn user_tokens(access_token: &str) -> UserTokens {
+        UserTokens {
+            access_token: access_token.into(),
+            expires_in: None,
+            refresh_token: None,
+        }
+    }
+}
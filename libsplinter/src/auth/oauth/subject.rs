@@ -0,0 +1,115 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Providers for looking up the identity of an OAuth user
+
+use reqwest::{blocking::Client, StatusCode};
+
+use super::OAuthClientError;
+
+/// The GitHub endpoint for getting the authenticated user
+const GITHUB_USER_URL: &str = "https://api.github.com/user";
+
+/// Looks up the identity of the user that an access token was issued to
+///
+/// The identity (subject) is used to correlate the OAuth user with a Biome user, so it must be
+/// unique and must not change for a given user of the provider.
+pub trait SubjectProvider: Send + Sync {
+    /// Gets the subject of the user that the given access token was issued to. Returns `Ok(None)`
+    /// if the provider does not accept the access token.
+    fn get_subject(&self, access_token: &str) -> Result<Option<String>, OAuthClientError>;
+}
+
+/// Gets the subject of a GitHub user from the GitHub API, using the user's numeric ID since the
+/// username can be changed.
+#[derive(Clone, Default)]
+pub struct GithubSubjectProvider;
+
+#[derive(Deserialize)]
+struct GithubUser {
+    id: u64,
+}
+
+impl SubjectProvider for GithubSubjectProvider {
+    fn get_subject(&self, access_token: &str) -> Result<Option<String>, OAuthClientError> {
+        let response = Client::new()
+            .get(GITHUB_USER_URL)
+            .header("Authorization", format!("token {}", access_token))
+            // GitHub rejects requests without a user agent
+            .header("User-Agent", "splinter")
+            .send()
+            .map_err(|err| {
+                OAuthClientError::new(&format!("failed to request GitHub user: {}", err))
+            })?;
+
+        match response.status() {
+            StatusCode::UNAUTHORIZED => Ok(None),
+            status if status.is_success() => {
+                let user = response.json::<GithubUser>().map_err(|err| {
+                    OAuthClientError::new(&format!("failed to parse GitHub user: {}", err))
+                })?;
+                Ok(Some(user.id.to_string()))
+            }
+            status => Err(OAuthClientError::new(&format!(
+                "failed to request GitHub user: received status {}",
+                status
+            ))),
+        }
+    }
+}
+
+/// Gets the subject of a user from an OpenID Connect provider's userinfo endpoint
+/// (<https://openid.net/specs/openid-connect-core-1_0.html#UserInfo>).
+#[derive(Clone)]
+pub struct OpenIdSubjectProvider {
+    userinfo_endpoint: String,
+}
+
+impl OpenIdSubjectProvider {
+    /// Creates a new `OpenIdSubjectProvider` that uses the given userinfo endpoint
+    pub fn new(userinfo_endpoint: String) -> Self {
+        Self { userinfo_endpoint }
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenIdUserInfo {
+    sub: String,
+}
+
+impl SubjectProvider for OpenIdSubjectProvider {
+    fn get_subject(&self, access_token: &str) -> Result<Option<String>, OAuthClientError> {
+        let response = Client::new()
+            .get(&self.userinfo_endpoint)
+            .bearer_auth(access_token)
+            .send()
+            .map_err(|err| {
+                OAuthClientError::new(&format!("failed to request OpenID userinfo: {}", err))
+            })?;
+
+        match response.status() {
+            StatusCode::UNAUTHORIZED => Ok(None),
+            status if status.is_success() => {
+                let user_info = response.json::<OpenIdUserInfo>().map_err(|err| {
+                    OAuthClientError::new(&format!("failed to parse OpenID userinfo: {}", err))
+                })?;
+                Ok(Some(user_info.sub))
+            }
+            status => Err(OAuthClientError::new(&format!(
+                "failed to request OpenID userinfo: received status {}",
+                status
+            ))),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracks failed login attempts so that accounts can be locked after repeated failures.
+
+use std::time::{Duration, SystemTime};
+
+/// The failed login attempts recorded for a user since their last successful login.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoginAttempts {
+    /// The user the attempts were made for
+    pub user_id: String,
+    /// The number of consecutive failed attempts
+    pub failed_attempts: u32,
+    /// The time of the most recent failed attempt, if any
+    pub last_failed: Option<SystemTime>,
+}
+
+impl LoginAttempts {
+    /// Creates a record with no failed attempts for the given user.
+    pub fn none(user_id: &str) -> Self {
+        LoginAttempts {
+            user_id: user_id.to_string(),
+            failed_attempts: 0,
+            last_failed: None,
+        }
+    }
+
+    /// Returns true if the user has reached the maximum number of failed attempts and the
+    /// lockout started by the most recent failure has not yet elapsed.
+    pub fn is_locked(&self, policy: &LockoutPolicy, now: SystemTime) -> bool {
+        self.failed_attempts >= policy.max_failed_attempts
+            && self
+                .last_failed
+                .map(|last_failed| now < last_failed + policy.lockout_duration)
+                .unwrap_or(false)
+    }
+
+    /// Returns true if the user has reached the maximum number of failed attempts, but the
+    /// lockout has elapsed; the attempts should be cleared before further attempts are counted.
+    pub fn is_lockout_expired(&self, policy: &LockoutPolicy, now: SystemTime) -> bool {
+        self.failed_attempts >= policy.max_failed_attempts && !self.is_locked(policy, now)
+    }
+}
+
+/// Determines when an account is locked after failed login attempts.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct LockoutPolicy {
+    /// The number of consecutive failed attempts that locks an account
+    pub max_failed_attempts: u32,
+    /// How long an account stays locked after the last failed attempt
+    pub lockout_duration: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that an account is locked only after the maximum number of failed attempts, and
+    /// only until the lockout duration has elapsed.
+    #[test]
+    fn lockout() {
+        let policy = LockoutPolicy {
+            max_failed_attempts: 3,
+            lockout_duration: Duration::from_secs(60),
+        };
+        let now = SystemTime::now();
+
+        let mut attempts = LoginAttempts::none("id");
+        assert!(!attempts.is_locked(&policy, now));
+        assert!(!attempts.is_lockout_expired(&policy, now));
+
+        attempts.failed_attempts = 2;
+        attempts.last_failed = Some(now);
+        assert!(!attempts.is_locked(&policy, now));
+
+        attempts.failed_attempts = 3;
+        assert!(attempts.is_locked(&policy, now));
+        assert!(attempts.is_locked(&policy, now + Duration::from_secs(59)));
+        assert!(!attempts.is_lockout_expired(&policy, now));
+
+        let later = now + Duration::from_secs(60);
+        assert!(!attempts.is_locked(&policy, later));
+        assert!(attempts.is_lockout_expired(&policy, later));
+    }
+}
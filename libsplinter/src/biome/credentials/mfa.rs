@@ -0,0 +1,401 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides time-based one-time password (TOTP) multi-factor authentication, as defined by
+//! RFC 6238, and single-use recovery codes for users that have lost their authenticator.
+
+use std::error::Error;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bcrypt::{hash, verify};
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use rand::{distributions::Alphanumeric, Rng};
+
+use crate::encryption::{self, EncryptionError};
+
+use super::store::PasswordEncryptionCost;
+
+/// The length, in bytes, of the key used to encrypt TOTP secrets
+pub const ENCRYPTION_KEY_LENGTH: usize = encryption::KEY_LENGTH;
+/// The number of recovery codes that are generated when a user enrols
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+const SECRET_LENGTH: usize = 20;
+const TOTP_STEP_SECS: u64 = 30;
+const TOTP_DIGITS: u32 = 6;
+// The number of steps before and after the current step that are also accepted, to allow for
+// clock drift between the server and the user's authenticator
+const TOTP_SKEW_STEPS: u64 = 1;
+const RECOVERY_CODE_HALF_LENGTH: usize = 5;
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A user's TOTP enrolment, as stored by a `CredentialsStore`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TotpCredentials {
+    /// The unique identifier of the user
+    pub user_id: String,
+    /// The user's TOTP secret, encrypted with `TotpSecret::encrypt`
+    pub encrypted_secret: String,
+    /// Whether or not the user has confirmed the enrolment by providing a valid code; only
+    /// confirmed enrolments are enforced at login
+    pub confirmed: bool,
+    /// The time step of the last TOTP code that was accepted; codes for this step or earlier
+    /// steps are rejected so that a code cannot be replayed
+    pub last_used_step: Option<u64>,
+    /// The bcrypt hashes of the user's unused recovery codes
+    pub recovery_codes: Vec<String>,
+}
+
+/// The shared secret used to generate and verify a user's TOTP codes.
+pub struct TotpSecret {
+    bytes: Vec<u8>,
+}
+
+impl TotpSecret {
+    /// Generates a new random secret.
+    pub fn generate() -> Self {
+        let mut bytes = vec![0u8; SECRET_LENGTH];
+        rand::thread_rng().fill(&mut bytes[..]);
+        TotpSecret { bytes }
+    }
+
+    /// Returns the secret as an unpadded base32 string, which is the format expected by
+    /// authenticator apps.
+    pub fn to_base32(&self) -> String {
+        to_base32(&self.bytes)
+    }
+
+    /// Returns the `otpauth://` URI that an authenticator app uses to add this secret, typically
+    /// by scanning it as a QR code.
+    ///
+    /// # Arguments
+    ///
+    /// * `issuer` - The name of the service that the codes are used for
+    /// * `account` - The name of the user's account, such as their username
+    pub fn provisioning_uri(&self, issuer: &str, account: &str) -> String {
+        let issuer = url::form_urlencoded::byte_serialize(issuer.as_bytes()).collect::<String>();
+        let account = url::form_urlencoded::byte_serialize(account.as_bytes()).collect::<String>();
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            issuer,
+            account,
+            self.to_base32(),
+            issuer,
+            TOTP_DIGITS,
+            TOTP_STEP_SECS
+        )
+    }
+
+    /// Checks whether the given code is valid at the given time. Codes for the steps immediately
+    /// before and after the current one are also accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code provided by the user
+    /// * `time` - The time to verify the code at, usually the current time
+    pub fn verify(&self, code: &str, time: SystemTime) -> Result<bool, MfaError> {
+        self.verify_step(code, time).map(|step| step.is_some())
+    }
+
+    /// Checks whether the given code is valid at the given time, as with `TotpSecret::verify`,
+    /// and returns the time step that the code was generated for. The step should be recorded
+    /// with `CredentialsStore::record_totp_step` so that the code cannot be used again.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code provided by the user
+    /// * `time` - The time to verify the code at, usually the current time
+    pub fn verify_step(&self, code: &str, time: SystemTime) -> Result<Option<u64>, MfaError> {
+        let code = code.trim();
+        let current_step = time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() / TOTP_STEP_SECS)
+            .map_err(|_| MfaError::CryptoError("time is before the Unix epoch".into()))?;
+
+        let first_step = current_step.saturating_sub(TOTP_SKEW_STEPS);
+        for step in first_step..=current_step + TOTP_SKEW_STEPS {
+            let expected = self.code_at_step(step)?;
+            if expected.len() == code.len() && memcmp::eq(expected.as_bytes(), code.as_bytes()) {
+                return Ok(Some(step));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Encrypts the secret with AES-256-GCM for storage; the result is a hex string of the
+    /// nonce, ciphertext, and authentication tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The `ENCRYPTION_KEY_LENGTH`-byte key used to encrypt the secret
+    pub fn encrypt(&self, key: &[u8]) -> Result<String, MfaError> {
+        Ok(encryption::encrypt(&self.bytes, key)?)
+    }
+
+    /// Decrypts a secret that was encrypted with `TotpSecret::encrypt`.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted` - The encrypted secret
+    /// * `key` - The key that was used to encrypt the secret
+    pub fn decrypt(encrypted: &str, key: &[u8]) -> Result<Self, MfaError> {
+        let bytes = encryption::decrypt(encrypted, key)?;
+        Ok(TotpSecret { bytes })
+    }
+
+    /// Computes the HOTP value (RFC 4226) for the given time step.
+    fn code_at_step(&self, step: u64) -> Result<String, MfaError> {
+        let key = PKey::hmac(&self.bytes)?;
+        let mut signer = Signer::new(MessageDigest::sha1(), &key)?;
+        signer.update(&step.to_be_bytes())?;
+        let hmac = signer.sign_to_vec()?;
+
+        let offset = (hmac[hmac.len() - 1] & 0x0f) as usize;
+        let binary = (u32::from(hmac[offset]) & 0x7f) << 24
+            | u32::from(hmac[offset + 1]) << 16
+            | u32::from(hmac[offset + 2]) << 8
+            | u32::from(hmac[offset + 3]);
+
+        Ok(format!(
+            "{:0width$}",
+            binary % 10u32.pow(TOTP_DIGITS),
+            width = TOTP_DIGITS as usize
+        ))
+    }
+}
+
+/// Generates a new random key for encrypting TOTP secrets.
+pub fn generate_encryption_key() -> Vec<u8> {
+    encryption::generate_key()
+}
+
+/// Generates a new set of `RECOVERY_CODE_COUNT` recovery codes, formatted as `xxxxx-xxxxx`.
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let code = rand::thread_rng()
+                .sample_iter(Alphanumeric)
+                .take(RECOVERY_CODE_HALF_LENGTH * 2)
+                .collect::<String>()
+                .to_lowercase();
+            format!(
+                "{}-{}",
+                &code[..RECOVERY_CODE_HALF_LENGTH],
+                &code[RECOVERY_CODE_HALF_LENGTH..]
+            )
+        })
+        .collect()
+}
+
+/// Hashes a recovery code for storage.
+///
+/// # Arguments
+///
+/// * `code` - The recovery code to be hashed
+/// * `cost` - The cost of the hash
+pub fn hash_recovery_code(code: &str, cost: PasswordEncryptionCost) -> Result<String, MfaError> {
+    hash(normalize_recovery_code(code), cost.to_value())
+        .map_err(|err| MfaError::CryptoError(err.to_string()))
+}
+
+/// Checks whether a recovery code provided by a user matches a stored hash. Case, whitespace,
+/// and dashes are ignored.
+///
+/// # Arguments
+///
+/// * `code` - The recovery code provided by the user
+/// * `code_hash` - The stored hash of a recovery code
+pub fn verify_recovery_code(code: &str, code_hash: &str) -> Result<bool, MfaError> {
+    verify(normalize_recovery_code(code), code_hash)
+        .map_err(|err| MfaError::CryptoError(err.to_string()))
+}
+
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Encodes bytes as unpadded base32 (RFC 4648).
+fn to_base32(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 8 + 4) / 5);
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u16::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            let index = (buffer >> (bits - 5)) & 0x1f;
+            encoded.push(BASE32_ALPHABET[index as usize] as char);
+            bits -= 5;
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        let index = (buffer << (5 - bits)) & 0x1f;
+        encoded.push(BASE32_ALPHABET[index as usize] as char);
+    }
+    encoded
+}
+
+/// Errors that may occur when generating, verifying, or encrypting multi-factor authentication
+/// secrets.
+#[derive(Debug)]
+pub enum MfaError {
+    /// A cryptographic operation failed
+    CryptoError(String),
+    /// An encrypted secret could not be parsed or decrypted
+    InvalidEncryptedSecret(String),
+    /// The encryption key is invalid
+    InvalidKey(String),
+}
+
+impl Error for MfaError {}
+
+impl fmt::Display for MfaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MfaError::CryptoError(msg) => write!(f, "cryptographic operation failed: {}", msg),
+            MfaError::InvalidEncryptedSecret(msg) => {
+                write!(f, "invalid encrypted secret: {}", msg)
+            }
+            MfaError::InvalidKey(msg) => write!(f, "invalid encryption key: {}", msg),
+        }
+    }
+}
+
+impl From<ErrorStack> for MfaError {
+    fn from(err: ErrorStack) -> Self {
+        MfaError::CryptoError(err.to_string())
+    }
+}
+
+impl From<EncryptionError> for MfaError {
+    fn from(err: EncryptionError) -> Self {
+        match err {
+            EncryptionError::CryptoError(msg) => MfaError::CryptoError(msg),
+            EncryptionError::InvalidCiphertext(msg) => MfaError::InvalidEncryptedSecret(msg),
+            EncryptionError::InvalidKey(msg) => MfaError::InvalidKey(msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Verify that the base32 encoding matches the test vectors in RFC 4648.
+    #[test]
+    fn base32_encoding() {
+        assert_eq!(to_base32(b""), "");
+        assert_eq!(to_base32(b"f"), "MY");
+        assert_eq!(to_base32(b"fo"), "MZXQ");
+        assert_eq!(to_base32(b"foo"), "MZXW6");
+        assert_eq!(to_base32(b"foob"), "MZXW6YQ");
+        assert_eq!(to_base32(b"fooba"), "MZXW6YTB");
+        assert_eq!(to_base32(b"foobar"), "MZXW6YTBOI");
+    }
+
+    /// Verify that TOTP codes match the SHA1 test vectors in RFC 6238 (truncated to six digits),
+    /// that codes from adjacent steps are accepted, and that other codes are rejected.
+    #[test]
+    fn totp_verification() {
+        let secret = TotpSecret {
+            bytes: b"12345678901234567890".to_vec(),
+        };
+
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+
+        assert!(secret.verify("287082", at(59)).expect("Failed to verify"));
+        assert!(secret
+            .verify("081804", at(1_111_111_109))
+            .expect("Failed to verify"));
+        assert!(secret
+            .verify("050471", at(1_111_111_111))
+            .expect("Failed to verify"));
+        assert!(secret
+            .verify("005924", at(1_234_567_890))
+            .expect("Failed to verify"));
+        assert!(secret
+            .verify("279037", at(2_000_000_000))
+            .expect("Failed to verify"));
+
+        // The code for 59 seconds is accepted one step later, but not two steps later
+        assert!(secret.verify("287082", at(89)).expect("Failed to verify"));
+        assert!(!secret.verify("287082", at(120)).expect("Failed to verify"));
+
+        assert!(!secret.verify("000000", at(59)).expect("Failed to verify"));
+        assert!(!secret.verify("28708", at(59)).expect("Failed to verify"));
+
+        // The matched step is returned so that it can be recorded, including for adjacent steps
+        assert_eq!(
+            secret
+                .verify_step("287082", at(59))
+                .expect("Failed to verify"),
+            Some(1)
+        );
+        assert_eq!(
+            secret
+                .verify_step("287082", at(89))
+                .expect("Failed to verify"),
+            Some(1)
+        );
+    }
+
+    /// Verify that an encrypted secret can be decrypted with the same key, but not with a
+    /// different key.
+    #[test]
+    fn secret_encryption() {
+        let secret = TotpSecret::generate();
+        let key = generate_encryption_key();
+
+        let encrypted = secret.encrypt(&key).expect("Failed to encrypt secret");
+        let decrypted = TotpSecret::decrypt(&encrypted, &key).expect("Failed to decrypt secret");
+        assert_eq!(secret.bytes, decrypted.bytes);
+
+        match TotpSecret::decrypt(&encrypted, &generate_encryption_key()) {
+            Err(MfaError::InvalidEncryptedSecret(_)) => (),
+            res => panic!(
+                "Expected InvalidEncryptedSecret, got {:?}",
+                res.map(|secret| secret.bytes)
+            ),
+        }
+    }
+
+    /// Verify that recovery codes are verified against their hashes regardless of case and
+    /// dashes.
+    #[test]
+    fn recovery_codes() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+
+        let code_hash =
+            hash_recovery_code(&codes[0], PasswordEncryptionCost::Low).expect("Failed to hash");
+        assert!(verify_recovery_code(&codes[0], &code_hash).expect("Failed to verify"));
+        assert!(
+            verify_recovery_code(&codes[0].to_uppercase().replace("-", ""), &code_hash)
+                .expect("Failed to verify")
+        );
+        assert!(!verify_recovery_code(&codes[1], &code_hash).expect("Failed to verify"));
+    }
+}
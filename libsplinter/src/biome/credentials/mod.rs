@@ -15,4 +15,10 @@
 //! Defines a basic API to register and authenticate a User using a username and a password.
 //! Not recommended for use in production.
 
+#[cfg(feature = "biome-login-lockout")]
+pub mod lockout;
+#[cfg(feature = "biome-mfa")]
+pub mod mfa;
+#[cfg(feature = "biome-password-reset")]
+pub mod reset;
 pub mod store;
@@ -0,0 +1,232 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides single-use, time-limited tokens for resetting a forgotten password.
+//!
+//! Tokens are delivered to users by a `PasswordResetNotifier`, which allows applications to plug
+//! in their own delivery mechanism (email, chat, etc.). Only a hash of each token is stored.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use openssl::sha::sha256;
+use rand::{thread_rng, RngCore};
+
+use crate::hex::to_hex;
+
+/// Number of random bytes in a password reset token
+const TOKEN_LENGTH: usize = 32;
+
+/// A password reset token that has been issued to a user.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PasswordResetToken {
+    /// The user the token was issued to
+    pub user_id: String,
+    /// SHA-256 hash of the token, hex-encoded
+    pub token_hash: String,
+    /// The time after which the token may no longer be used
+    pub expires: SystemTime,
+}
+
+impl PasswordResetToken {
+    /// Returns true if the token has expired as of the given time.
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        now >= self.expires
+    }
+}
+
+/// Generates a new random password reset token, hex-encoded.
+pub fn generate_reset_token() -> String {
+    let mut bytes = [0u8; TOKEN_LENGTH];
+    thread_rng().fill_bytes(&mut bytes);
+    to_hex(&bytes)
+}
+
+/// Returns the hash of a password reset token as it is stored in a `CredentialsStore`.
+pub fn hash_reset_token(token: &str) -> String {
+    to_hex(&sha256(token.as_bytes()))
+}
+
+/// Delivers password reset tokens to users.
+pub trait PasswordResetNotifier: Send + Sync {
+    /// Sends a password reset token to the user with the given username. The token may be used
+    /// until the given expiry time.
+    fn send_reset_token(
+        &self,
+        username: &str,
+        token: &str,
+        expires: SystemTime,
+    ) -> Result<(), PasswordResetNotifierError>;
+}
+
+impl<N> PasswordResetNotifier for Box<N>
+where
+    N: PasswordResetNotifier + ?Sized,
+{
+    fn send_reset_token(
+        &self,
+        username: &str,
+        token: &str,
+        expires: SystemTime,
+    ) -> Result<(), PasswordResetNotifierError> {
+        (**self).send_reset_token(username, token, expires)
+    }
+}
+
+/// Errors returned by a `PasswordResetNotifier`.
+#[derive(Debug)]
+pub enum PasswordResetNotifierError {
+    /// The token could not be delivered to the user
+    DeliveryError(String),
+}
+
+impl Error for PasswordResetNotifierError {}
+
+impl fmt::Display for PasswordResetNotifierError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PasswordResetNotifierError::DeliveryError(msg) => {
+                write!(f, "unable to deliver password reset token: {}", msg)
+            }
+        }
+    }
+}
+
+/// A `PasswordResetNotifier` that appends each token to a file, one JSON object per line.
+///
+/// This is a stand-in for a real delivery mechanism and is intended for tests and local
+/// development; anyone with access to the file can reset any user's password.
+pub struct FilePasswordResetNotifier {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FilePasswordResetNotifier {
+    /// Creates a new notifier that appends tokens to the file at the given path, creating it if
+    /// necessary.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl PasswordResetNotifier for FilePasswordResetNotifier {
+    fn send_reset_token(
+        &self,
+        username: &str,
+        token: &str,
+        expires: SystemTime,
+    ) -> Result<(), PasswordResetNotifierError> {
+        let expires = expires
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let line = json!({
+            "username": username,
+            "token": token,
+            "expires": expires,
+        })
+        .to_string();
+
+        let _guard = self.lock.lock().map_err(|_| {
+            PasswordResetNotifierError::DeliveryError("notifier lock poisoned".into())
+        })?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(|err| {
+                PasswordResetNotifierError::DeliveryError(format!(
+                    "unable to write to {}: {}",
+                    self.path.display(),
+                    err
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::time::Duration;
+
+    use tempdir::TempDir;
+
+    /// Verify that generated tokens are unique and that their hashes are stable and do not
+    /// contain the token.
+    #[test]
+    fn token_generation() {
+        let token1 = generate_reset_token();
+        let token2 = generate_reset_token();
+        assert_eq!(token1.len(), TOKEN_LENGTH * 2);
+        assert_ne!(token1, token2);
+
+        assert_eq!(hash_reset_token(&token1), hash_reset_token(&token1));
+        assert_ne!(hash_reset_token(&token1), hash_reset_token(&token2));
+        assert_ne!(hash_reset_token(&token1), token1);
+    }
+
+    /// Verify that a token is expired at and after its expiry time.
+    #[test]
+    fn token_expiry() {
+        let now = SystemTime::now();
+        let token = PasswordResetToken {
+            user_id: "id".into(),
+            token_hash: hash_reset_token("token"),
+            expires: now,
+        };
+        assert!(!token.is_expired(now - Duration::from_secs(1)));
+        assert!(token.is_expired(now));
+        assert!(token.is_expired(now + Duration::from_secs(1)));
+    }
+
+    /// Verify that the `FilePasswordResetNotifier` appends one JSON line per token.
+    #[test]
+    fn file_notifier() {
+        let temp_dir = TempDir::new("file_notifier").expect("Failed to create temp dir");
+        let path = temp_dir.path().join("reset_tokens");
+        let notifier = FilePasswordResetNotifier::new(&path);
+
+        let expires = UNIX_EPOCH + Duration::from_secs(100);
+        notifier
+            .send_reset_token("user1", "token1", expires)
+            .expect("Failed to send token1");
+        notifier
+            .send_reset_token("user2", "token2", expires)
+            .expect("Failed to send token2");
+
+        let contents = fs::read_to_string(&path).expect("Failed to read tokens file");
+        let lines = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("Invalid JSON"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                json!({"username": "user1", "token": "token1", "expires": 100}),
+                json!({"username": "user2", "token": "token2", "expires": 100}),
+            ]
+        );
+    }
+}
@@ -18,6 +18,15 @@ pub(in crate::biome) mod schema;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
+#[cfg(feature = "biome-login-lockout")]
+use std::time::SystemTime;
+
+#[cfg(feature = "biome-login-lockout")]
+use super::super::lockout::LoginAttempts;
+#[cfg(feature = "biome-mfa")]
+use super::super::mfa::TotpCredentials;
+#[cfg(feature = "biome-password-reset")]
+use super::super::reset::PasswordResetToken;
 use super::{
     Credentials, CredentialsStore, CredentialsStoreError, PasswordEncryptionCost, UsernameId,
 };
@@ -31,6 +40,26 @@ use operations::list_usernames::CredentialsStoreListUsernamesOperation as _;
 use operations::remove_credentials::CredentialsStoreRemoveCredentialsOperation as _;
 use operations::update_credentials::CredentialsStoreUpdateCredentialsOperation as _;
 use operations::CredentialsStoreOperations;
+#[cfg(feature = "biome-password-reset")]
+use operations::{
+    add_password_reset_token::CredentialsStoreAddPasswordResetTokenOperation as _,
+    take_password_reset_token::CredentialsStoreTakePasswordResetTokenOperation as _,
+};
+#[cfg(feature = "biome-login-lockout")]
+use operations::{
+    clear_failed_logins::CredentialsStoreClearFailedLoginsOperation as _,
+    fetch_login_attempts::CredentialsStoreFetchLoginAttemptsOperation as _,
+    record_failed_login::CredentialsStoreRecordFailedLoginOperation as _,
+};
+#[cfg(feature = "biome-mfa")]
+use operations::{
+    confirm_totp::CredentialsStoreConfirmTotpOperation as _,
+    fetch_totp::CredentialsStoreFetchTotpOperation as _,
+    record_totp_step::CredentialsStoreRecordTotpStepOperation as _,
+    remove_recovery_code::CredentialsStoreRemoveRecoveryCodeOperation as _,
+    remove_totp::CredentialsStoreRemoveTotpOperation as _,
+    set_totp::CredentialsStoreSetTotpOperation as _,
+};
 
 /// Manages creating, updating and fetching SplinterCredentials from the database
 pub struct DieselCredentialsStore<C: diesel::Connection + 'static> {
@@ -96,6 +125,80 @@ impl CredentialsStore for DieselCredentialsStore<diesel::pg::PgConnection> {
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError> {
         CredentialsStoreOperations::new(&*self.connection_pool.get()?).list_usernames()
     }
+
+    #[cfg(feature = "biome-mfa")]
+    fn set_totp(&self, totp: TotpCredentials) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).set_totp(totp)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn fetch_totp(&self, user_id: &str) -> Result<TotpCredentials, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).fetch_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn confirm_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).confirm_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_recovery_code(
+        &self,
+        user_id: &str,
+        code_hash: &str,
+    ) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .remove_recovery_code(user_id, code_hash)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn record_totp_step(&self, user_id: &str, step: u64) -> Result<bool, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .record_totp_step(user_id, step)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).remove_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .add_password_reset_token(token)
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .take_password_reset_token(token_hash)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn fetch_login_attempts(&self, user_id: &str) -> Result<LoginAttempts, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).fetch_login_attempts(user_id)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .record_failed_login(user_id, attempted)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn clear_failed_logins(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).clear_failed_logins(user_id)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -146,6 +249,80 @@ impl CredentialsStore for DieselCredentialsStore<diesel::sqlite::SqliteConnectio
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError> {
         CredentialsStoreOperations::new(&*self.connection_pool.get()?).list_usernames()
     }
+
+    #[cfg(feature = "biome-mfa")]
+    fn set_totp(&self, totp: TotpCredentials) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).set_totp(totp)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn fetch_totp(&self, user_id: &str) -> Result<TotpCredentials, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).fetch_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn confirm_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).confirm_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_recovery_code(
+        &self,
+        user_id: &str,
+        code_hash: &str,
+    ) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .remove_recovery_code(user_id, code_hash)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn record_totp_step(&self, user_id: &str, step: u64) -> Result<bool, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .record_totp_step(user_id, step)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).remove_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .add_password_reset_token(token)
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .take_password_reset_token(token_hash)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn fetch_login_attempts(&self, user_id: &str) -> Result<LoginAttempts, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).fetch_login_attempts(user_id)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?)
+            .record_failed_login(user_id, attempted)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn clear_failed_logins(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        CredentialsStoreOperations::new(&*self.connection_pool.get()?).clear_failed_logins(user_id)
+    }
 }
 
 impl From<CredentialsModel> for UsernameId {
@@ -573,6 +750,201 @@ pub mod tests {
         }));
     }
 
+    /// Verify that a SQLite-backed `DieselCredentialsStore` correctly supports TOTP enrolments.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselCredentialsStore`.
+    /// 3. Set an unconfirmed TOTP enrolment and verify that it can be fetched.
+    /// 4. Confirm the enrolment and verify that it is marked as confirmed.
+    /// 5. Record a TOTP step and verify that the same or an earlier step is not recorded again,
+    ///    but a later step is.
+    /// 6. Remove a recovery code and verify that it is no longer in the enrolment.
+    /// 7. Remove the enrolment and verify that fetching it returns a
+    ///    `CredentialsStoreError::NotFoundError`.
+    #[cfg(feature = "biome-mfa")]
+    #[test]
+    fn sqlite_totp() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselCredentialsStore::new(pool);
+
+        let totp = TotpCredentials {
+            user_id: "id1".into(),
+            encrypted_secret: "secret".into(),
+            confirmed: false,
+            last_used_step: None,
+            recovery_codes: vec!["code1".into(), "code2".into()],
+        };
+        store.set_totp(totp.clone()).expect("Failed to set totp");
+        assert_eq!(store.fetch_totp("id1").expect("Failed to fetch totp"), totp);
+
+        store.confirm_totp("id1").expect("Failed to confirm totp");
+        assert!(
+            store
+                .fetch_totp("id1")
+                .expect("Failed to fetch totp")
+                .confirmed
+        );
+
+        assert!(store
+            .record_totp_step("id1", 10)
+            .expect("Failed to record step"));
+        assert!(!store
+            .record_totp_step("id1", 10)
+            .expect("Failed to record step"));
+        assert!(!store
+            .record_totp_step("id1", 9)
+            .expect("Failed to record step"));
+        assert!(store
+            .record_totp_step("id1", 11)
+            .expect("Failed to record step"));
+        assert_eq!(
+            store
+                .fetch_totp("id1")
+                .expect("Failed to fetch totp")
+                .last_used_step,
+            Some(11)
+        );
+        match store.record_totp_step("id2", 10) {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+
+        store
+            .remove_recovery_code("id1", "code1")
+            .expect("Failed to remove recovery code");
+        assert_eq!(
+            store
+                .fetch_totp("id1")
+                .expect("Failed to fetch totp")
+                .recovery_codes,
+            vec!["code2".to_string()]
+        );
+        match store.remove_recovery_code("id1", "code1") {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+
+        store.remove_totp("id1").expect("Failed to remove totp");
+        match store.fetch_totp("id1") {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
+    /// Verify that a SQLite-backed `DieselCredentialsStore` correctly supports password reset
+    /// tokens.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselCredentialsStore`.
+    /// 3. Add two tokens for the same user and verify that only the second can be taken.
+    /// 4. Verify that the second token cannot be taken twice.
+    #[cfg(feature = "biome-password-reset")]
+    #[test]
+    fn sqlite_password_reset_tokens() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselCredentialsStore::new(pool);
+
+        let expires = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1000);
+        let token1 = PasswordResetToken {
+            user_id: "id1".into(),
+            token_hash: "hash1".into(),
+            expires,
+        };
+        let token2 = PasswordResetToken {
+            user_id: "id1".into(),
+            token_hash: "hash2".into(),
+            expires,
+        };
+        store
+            .add_password_reset_token(token1)
+            .expect("Failed to add token1");
+        store
+            .add_password_reset_token(token2.clone())
+            .expect("Failed to add token2");
+
+        match store.take_password_reset_token("hash1") {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+        assert_eq!(
+            store
+                .take_password_reset_token("hash2")
+                .expect("Failed to take token2"),
+            token2
+        );
+        match store.take_password_reset_token("hash2") {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
+    /// Verify that a SQLite-backed `DieselCredentialsStore` correctly supports recording and
+    /// clearing failed login attempts.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselCredentialsStore`.
+    /// 3. Verify that a user without recorded attempts has no failed attempts.
+    /// 4. Record two failed attempts and verify the count and time of the last attempt.
+    /// 5. Clear the attempts and verify that the user has no failed attempts.
+    #[cfg(feature = "biome-login-lockout")]
+    #[test]
+    fn sqlite_login_attempts() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselCredentialsStore::new(pool);
+
+        assert_eq!(
+            store
+                .fetch_login_attempts("id1")
+                .expect("Failed to fetch attempts"),
+            LoginAttempts::none("id1")
+        );
+
+        let first = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1000);
+        let second = first + std::time::Duration::from_secs(10);
+        store
+            .record_failed_login("id1", first)
+            .expect("Failed to record first attempt");
+        let attempts = store
+            .record_failed_login("id1", second)
+            .expect("Failed to record second attempt");
+        assert_eq!(attempts.failed_attempts, 2);
+        assert_eq!(attempts.last_failed, Some(second));
+        assert_eq!(
+            store
+                .fetch_login_attempts("id1")
+                .expect("Failed to fetch attempts"),
+            attempts
+        );
+
+        store
+            .clear_failed_logins("id1")
+            .expect("Failed to clear attempts");
+        assert_eq!(
+            store
+                .fetch_login_attempts("id1")
+                .expect("Failed to fetch attempts"),
+            LoginAttempts::none("id1")
+        );
+    }
+
     /// Creates a conneciton pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
@@ -12,7 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(any(feature = "biome-login-lockout", feature = "biome-password-reset"))]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "biome-password-reset")]
+use super::schema::password_reset_tokens;
 use super::schema::user_credentials;
+#[cfg(feature = "biome-login-lockout")]
+use super::schema::user_login_attempts;
+#[cfg(feature = "biome-mfa")]
+use super::schema::{user_totp, user_totp_recovery_codes};
+#[cfg(feature = "biome-login-lockout")]
+use crate::biome::credentials::lockout::LoginAttempts;
+#[cfg(feature = "biome-password-reset")]
+use crate::biome::credentials::reset::PasswordResetToken;
 use crate::biome::user::store::diesel::models::UserModel;
 
 #[derive(Queryable, Identifiable, Associations, PartialEq, Debug)]
@@ -32,3 +45,94 @@ pub struct NewCredentialsModel {
     pub username: String,
     pub password: String,
 }
+
+#[cfg(feature = "biome-mfa")]
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "user_totp"]
+pub struct TotpModel {
+    pub user_id: String,
+    pub encrypted_secret: String,
+    pub confirmed: bool,
+    pub last_used_step: Option<i64>,
+}
+
+#[cfg(feature = "biome-mfa")]
+#[derive(Queryable, PartialEq, Debug)]
+pub struct TotpRecoveryCodeModel {
+    pub id: i64,
+    pub user_id: String,
+    pub code_hash: String,
+}
+
+#[cfg(feature = "biome-mfa")]
+#[derive(Insertable, PartialEq, Debug)]
+#[table_name = "user_totp_recovery_codes"]
+pub struct NewTotpRecoveryCodeModel<'a> {
+    pub user_id: &'a str,
+    pub code_hash: &'a str,
+}
+
+#[cfg(feature = "biome-password-reset")]
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "password_reset_tokens"]
+pub struct PasswordResetTokenModel {
+    pub token_hash: String,
+    pub user_id: String,
+    /// The time the token expires, in seconds since the Unix epoch
+    pub expires: i64,
+}
+
+#[cfg(feature = "biome-password-reset")]
+impl From<PasswordResetToken> for PasswordResetTokenModel {
+    fn from(token: PasswordResetToken) -> Self {
+        PasswordResetTokenModel {
+            token_hash: token.token_hash,
+            user_id: token.user_id,
+            expires: to_epoch_secs(token.expires),
+        }
+    }
+}
+
+#[cfg(feature = "biome-password-reset")]
+impl From<PasswordResetTokenModel> for PasswordResetToken {
+    fn from(model: PasswordResetTokenModel) -> Self {
+        PasswordResetToken {
+            token_hash: model.token_hash,
+            user_id: model.user_id,
+            expires: from_epoch_secs(model.expires),
+        }
+    }
+}
+
+#[cfg(feature = "biome-login-lockout")]
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "user_login_attempts"]
+pub struct LoginAttemptsModel {
+    pub user_id: String,
+    pub failed_attempts: i64,
+    /// The time of the last failed attempt, in seconds since the Unix epoch
+    pub last_failed: Option<i64>,
+}
+
+#[cfg(feature = "biome-login-lockout")]
+impl From<LoginAttemptsModel> for LoginAttempts {
+    fn from(model: LoginAttemptsModel) -> Self {
+        LoginAttempts {
+            user_id: model.user_id,
+            failed_attempts: model.failed_attempts as u32,
+            last_failed: model.last_failed.map(from_epoch_secs),
+        }
+    }
+}
+
+#[cfg(any(feature = "biome-login-lockout", feature = "biome-password-reset"))]
+pub fn to_epoch_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(any(feature = "biome-login-lockout", feature = "biome-password-reset"))]
+fn from_epoch_secs(secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs as u64)
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::reset::PasswordResetToken;
+use crate::biome::credentials::store::diesel::{
+    models::PasswordResetTokenModel, schema::password_reset_tokens, CredentialsStoreError,
+};
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+    result::Error as QueryError,
+};
+
+pub(in crate::biome::credentials) trait CredentialsStoreAddPasswordResetTokenOperation {
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CredentialsStoreAddPasswordResetTokenOperation
+    for CredentialsStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        let model = PasswordResetTokenModel::from(token);
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                delete(
+                    password_reset_tokens::table
+                        .filter(password_reset_tokens::user_id.eq(&model.user_id)),
+                )
+                .execute(self.conn)?;
+                insert_into(password_reset_tokens::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+                Ok(())
+            })
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to add password reset token".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CredentialsStoreAddPasswordResetTokenOperation
+    for CredentialsStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        let model = PasswordResetTokenModel::from(token);
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                delete(
+                    password_reset_tokens::table
+                        .filter(password_reset_tokens::user_id.eq(&model.user_id)),
+                )
+                .execute(self.conn)?;
+                insert_into(password_reset_tokens::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+                Ok(())
+            })
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to add password reset token".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
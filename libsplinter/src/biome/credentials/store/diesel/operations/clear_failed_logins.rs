@@ -0,0 +1,39 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{
+    schema::user_login_attempts, CredentialsStoreError,
+};
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::biome::credentials) trait CredentialsStoreClearFailedLoginsOperation {
+    fn clear_failed_logins(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreClearFailedLoginsOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn clear_failed_logins(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        delete(user_login_attempts::table.find(user_id))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to clear failed logins".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{schema::user_totp, CredentialsStoreError};
+use diesel::prelude::*;
+
+pub(in crate::biome::credentials) trait CredentialsStoreConfirmTotpOperation {
+    fn confirm_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreConfirmTotpOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    C::Backend: diesel::sql_types::HasSqlType<diesel::sql_types::Bool>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>
+        + diesel::serialize::ToSql<diesel::sql_types::Bool, C::Backend>,
+{
+    fn confirm_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        match diesel::update(user_totp::table.find(user_id))
+            .set(user_totp::confirmed.eq(true))
+            .execute(self.conn)
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to confirm TOTP enrolment".to_string(),
+                source: Box::new(err),
+            })? {
+            0 => Err(CredentialsStoreError::NotFoundError(format!(
+                "TOTP enrolment not found for user id: {}",
+                user_id
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::lockout::LoginAttempts;
+use crate::biome::credentials::store::diesel::{
+    models::LoginAttemptsModel, schema::user_login_attempts, CredentialsStoreError,
+};
+use diesel::prelude::*;
+
+pub(in crate::biome::credentials) trait CredentialsStoreFetchLoginAttemptsOperation {
+    fn fetch_login_attempts(&self, user_id: &str) -> Result<LoginAttempts, CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreFetchLoginAttemptsOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn fetch_login_attempts(&self, user_id: &str) -> Result<LoginAttempts, CredentialsStoreError> {
+        Ok(user_login_attempts::table
+            .find(user_id)
+            .first::<LoginAttemptsModel>(self.conn)
+            .optional()
+            .map_err(|err| CredentialsStoreError::QueryError {
+                context: "Failed to fetch login attempts".to_string(),
+                source: Box::new(err),
+            })?
+            .map(LoginAttempts::from)
+            .unwrap_or_else(|| LoginAttempts::none(user_id)))
+    }
+}
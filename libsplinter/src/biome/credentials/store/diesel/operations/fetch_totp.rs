@@ -0,0 +1,74 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::mfa::TotpCredentials;
+use crate::biome::credentials::store::diesel::{
+    models::{TotpModel, TotpRecoveryCodeModel},
+    schema::{user_totp, user_totp_recovery_codes},
+    CredentialsStoreError,
+};
+use diesel::{prelude::*, result::Error::NotFound};
+
+pub(in crate::biome::credentials) trait CredentialsStoreFetchTotpOperation {
+    fn fetch_totp(&self, user_id: &str) -> Result<TotpCredentials, CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreFetchTotpOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    C::Backend: diesel::sql_types::HasSqlType<diesel::sql_types::Bool>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>
+        + diesel::serialize::ToSql<diesel::sql_types::Bool, C::Backend>,
+{
+    fn fetch_totp(&self, user_id: &str) -> Result<TotpCredentials, CredentialsStoreError> {
+        let totp = user_totp::table
+            .find(user_id)
+            .first::<TotpModel>(self.conn)
+            .map(Some)
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| CredentialsStoreError::QueryError {
+                context: "Failed to fetch TOTP enrolment".to_string(),
+                source: Box::new(err),
+            })?
+            .ok_or_else(|| {
+                CredentialsStoreError::NotFoundError(format!(
+                    "TOTP enrolment not found for user id: {}",
+                    user_id
+                ))
+            })?;
+
+        let recovery_codes = user_totp_recovery_codes::table
+            .filter(user_totp_recovery_codes::user_id.eq(user_id))
+            .order(user_totp_recovery_codes::id)
+            .load::<TotpRecoveryCodeModel>(self.conn)
+            .map_err(|err| CredentialsStoreError::QueryError {
+                context: "Failed to fetch TOTP recovery codes".to_string(),
+                source: Box::new(err),
+            })?
+            .into_iter()
+            .map(|recovery_code| recovery_code.code_hash)
+            .collect();
+
+        Ok(TotpCredentials {
+            user_id: totp.user_id,
+            encrypted_secret: totp.encrypted_secret,
+            confirmed: totp.confirmed,
+            last_used_step: totp.last_used_step.map(|step| step as u64),
+            recovery_codes,
+        })
+    }
+}
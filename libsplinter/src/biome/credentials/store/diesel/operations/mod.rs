@@ -15,11 +15,33 @@
 //! Provides CredentialsStoreOperations implemented for a diesel backend
 
 pub(super) mod add_credentials;
+#[cfg(feature = "biome-password-reset")]
+pub(super) mod add_password_reset_token;
+#[cfg(feature = "biome-login-lockout")]
+pub(super) mod clear_failed_logins;
+#[cfg(feature = "biome-mfa")]
+pub(super) mod confirm_totp;
 pub(super) mod fetch_credential_by_id;
 pub(super) mod fetch_credential_by_username;
+#[cfg(feature = "biome-login-lockout")]
+pub(super) mod fetch_login_attempts;
+#[cfg(feature = "biome-mfa")]
+pub(super) mod fetch_totp;
 pub(super) mod fetch_username;
 pub(super) mod list_usernames;
+#[cfg(feature = "biome-login-lockout")]
+pub(super) mod record_failed_login;
+#[cfg(feature = "biome-mfa")]
+pub(super) mod record_totp_step;
 pub(super) mod remove_credentials;
+#[cfg(feature = "biome-mfa")]
+pub(super) mod remove_recovery_code;
+#[cfg(feature = "biome-mfa")]
+pub(super) mod remove_totp;
+#[cfg(feature = "biome-mfa")]
+pub(super) mod set_totp;
+#[cfg(feature = "biome-password-reset")]
+pub(super) mod take_password_reset_token;
 pub(super) mod update_credentials;
 
 pub(super) struct CredentialsStoreOperations<'a, C> {
@@ -0,0 +1,140 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::SystemTime;
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::lockout::LoginAttempts;
+use crate::biome::credentials::store::diesel::{
+    models::{to_epoch_secs, LoginAttemptsModel},
+    schema::user_login_attempts,
+    CredentialsStoreError,
+};
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+    result::Error as QueryError,
+};
+
+pub(in crate::biome::credentials) trait CredentialsStoreRecordFailedLoginOperation {
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CredentialsStoreRecordFailedLoginOperation
+    for CredentialsStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError> {
+        let attempted = to_epoch_secs(attempted);
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                let attempts = match user_login_attempts::table
+                    .find(user_id)
+                    .first::<LoginAttemptsModel>(self.conn)
+                    .optional()?
+                {
+                    Some(attempts) => {
+                        let attempts = LoginAttemptsModel {
+                            failed_attempts: attempts.failed_attempts + 1,
+                            last_failed: Some(attempted),
+                            ..attempts
+                        };
+                        update(user_login_attempts::table.find(user_id))
+                            .set((
+                                user_login_attempts::failed_attempts.eq(attempts.failed_attempts),
+                                user_login_attempts::last_failed.eq(attempts.last_failed),
+                            ))
+                            .execute(self.conn)?;
+                        attempts
+                    }
+                    None => {
+                        let attempts = LoginAttemptsModel {
+                            user_id: user_id.to_string(),
+                            failed_attempts: 1,
+                            last_failed: Some(attempted),
+                        };
+                        insert_into(user_login_attempts::table)
+                            .values(&attempts)
+                            .execute(self.conn)?;
+                        attempts
+                    }
+                };
+                Ok(LoginAttempts::from(attempts))
+            })
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to record failed login".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CredentialsStoreRecordFailedLoginOperation
+    for CredentialsStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError> {
+        let attempted = to_epoch_secs(attempted);
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                let attempts = match user_login_attempts::table
+                    .find(user_id)
+                    .first::<LoginAttemptsModel>(self.conn)
+                    .optional()?
+                {
+                    Some(attempts) => {
+                        let attempts = LoginAttemptsModel {
+                            failed_attempts: attempts.failed_attempts + 1,
+                            last_failed: Some(attempted),
+                            ..attempts
+                        };
+                        update(user_login_attempts::table.find(user_id))
+                            .set((
+                                user_login_attempts::failed_attempts.eq(attempts.failed_attempts),
+                                user_login_attempts::last_failed.eq(attempts.last_failed),
+                            ))
+                            .execute(self.conn)?;
+                        attempts
+                    }
+                    None => {
+                        let attempts = LoginAttemptsModel {
+                            user_id: user_id.to_string(),
+                            failed_attempts: 1,
+                            last_failed: Some(attempted),
+                        };
+                        insert_into(user_login_attempts::table)
+                            .values(&attempts)
+                            .execute(self.conn)?;
+                        attempts
+                    }
+                };
+                Ok(LoginAttempts::from(attempts))
+            })
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to record failed login".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{schema::user_totp, CredentialsStoreError};
+use diesel::prelude::*;
+
+pub(in crate::biome::credentials) trait CredentialsStoreRecordTotpStepOperation {
+    fn record_totp_step(&self, user_id: &str, step: u64) -> Result<bool, CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreRecordTotpStepOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn record_totp_step(&self, user_id: &str, step: u64) -> Result<bool, CredentialsStoreError> {
+        let step = step as i64;
+        // The step is only recorded if it is later than the last used step, so that concurrent
+        // logins with the same code cannot both succeed
+        let updated = diesel::update(
+            user_totp::table.filter(
+                user_totp::user_id.eq(user_id).and(
+                    user_totp::last_used_step
+                        .is_null()
+                        .or(user_totp::last_used_step.lt(step)),
+                ),
+            ),
+        )
+        .set(user_totp::last_used_step.eq(step))
+        .execute(self.conn)
+        .map_err(|err| CredentialsStoreError::OperationError {
+            context: "Failed to record TOTP step".to_string(),
+            source: Box::new(err),
+        })?;
+
+        if updated > 0 {
+            return Ok(true);
+        }
+
+        // Distinguish a reused step from a missing enrolment
+        let enrolled = user_totp::table
+            .find(user_id)
+            .select(user_totp::user_id)
+            .first::<String>(self.conn)
+            .optional()
+            .map_err(|err| CredentialsStoreError::QueryError {
+                context: "Failed to fetch TOTP enrolment".to_string(),
+                source: Box::new(err),
+            })?
+            .is_some();
+        if enrolled {
+            Ok(false)
+        } else {
+            Err(CredentialsStoreError::NotFoundError(format!(
+                "TOTP enrolment not found for user id: {}",
+                user_id
+            )))
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{
+    schema::user_totp_recovery_codes, CredentialsStoreError,
+};
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::biome::credentials) trait CredentialsStoreRemoveRecoveryCodeOperation {
+    fn remove_recovery_code(
+        &self,
+        user_id: &str,
+        code_hash: &str,
+    ) -> Result<(), CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreRemoveRecoveryCodeOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn remove_recovery_code(
+        &self,
+        user_id: &str,
+        code_hash: &str,
+    ) -> Result<(), CredentialsStoreError> {
+        match delete(
+            user_totp_recovery_codes::table.filter(
+                user_totp_recovery_codes::user_id
+                    .eq(user_id)
+                    .and(user_totp_recovery_codes::code_hash.eq(code_hash)),
+            ),
+        )
+        .execute(self.conn)
+        .map_err(|err| CredentialsStoreError::OperationError {
+            context: "Failed to remove TOTP recovery code".to_string(),
+            source: Box::new(err),
+        })? {
+            0 => Err(CredentialsStoreError::NotFoundError(format!(
+                "Recovery code not found for user id: {}",
+                user_id
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::mfa::TotpCredentials;
+use crate::biome::credentials::store::diesel::{
+    models::{NewTotpRecoveryCodeModel, TotpModel},
+    schema::{user_totp, user_totp_recovery_codes},
+    CredentialsStoreError,
+};
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+    result::Error as QueryError,
+};
+
+pub(in crate::biome::credentials) trait CredentialsStoreSetTotpOperation {
+    fn set_totp(&self, totp: TotpCredentials) -> Result<(), CredentialsStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CredentialsStoreSetTotpOperation
+    for CredentialsStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_totp(&self, totp: TotpCredentials) -> Result<(), CredentialsStoreError> {
+        let recovery_code_models = totp
+            .recovery_codes
+            .iter()
+            .map(|code_hash| NewTotpRecoveryCodeModel {
+                user_id: &totp.user_id,
+                code_hash,
+            })
+            .collect::<Vec<_>>();
+        let totp_model = TotpModel {
+            user_id: totp.user_id.clone(),
+            encrypted_secret: totp.encrypted_secret.clone(),
+            confirmed: totp.confirmed,
+            last_used_step: totp.last_used_step.map(|step| step as i64),
+        };
+
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                delete(
+                    user_totp_recovery_codes::table
+                        .filter(user_totp_recovery_codes::user_id.eq(&totp.user_id)),
+                )
+                .execute(self.conn)?;
+                delete(user_totp::table.find(&totp.user_id)).execute(self.conn)?;
+                insert_into(user_totp::table)
+                    .values(totp_model)
+                    .execute(self.conn)?;
+                insert_into(user_totp_recovery_codes::table)
+                    .values(recovery_code_models)
+                    .execute(self.conn)?;
+                Ok(())
+            })
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to set TOTP enrolment".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CredentialsStoreSetTotpOperation
+    for CredentialsStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_totp(&self, totp: TotpCredentials) -> Result<(), CredentialsStoreError> {
+        let recovery_code_models = totp
+            .recovery_codes
+            .iter()
+            .map(|code_hash| NewTotpRecoveryCodeModel {
+                user_id: &totp.user_id,
+                code_hash,
+            })
+            .collect::<Vec<_>>();
+        let totp_model = TotpModel {
+            user_id: totp.user_id.clone(),
+            encrypted_secret: totp.encrypted_secret.clone(),
+            confirmed: totp.confirmed,
+            last_used_step: totp.last_used_step.map(|step| step as i64),
+        };
+
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                delete(
+                    user_totp_recovery_codes::table
+                        .filter(user_totp_recovery_codes::user_id.eq(&totp.user_id)),
+                )
+                .execute(self.conn)?;
+                delete(user_totp::table.find(&totp.user_id)).execute(self.conn)?;
+                insert_into(user_totp::table)
+                    .values(totp_model)
+                    .execute(self.conn)?;
+                insert_into(user_totp_recovery_codes::table)
+                    .values(recovery_code_models)
+                    .execute(self.conn)?;
+                Ok(())
+            })
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to set TOTP enrolment".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
@@ -20,3 +20,40 @@ table! {
         password -> Text,
     }
 }
+
+#[cfg(feature = "biome-mfa")]
+table! {
+    user_totp (user_id) {
+        user_id -> Text,
+        encrypted_secret -> Text,
+        confirmed -> Bool,
+        last_used_step -> Nullable<Int8>,
+    }
+}
+
+#[cfg(feature = "biome-mfa")]
+table! {
+    user_totp_recovery_codes (id) {
+        id -> Int8,
+        user_id -> Text,
+        code_hash -> Text,
+    }
+}
+
+#[cfg(feature = "biome-password-reset")]
+table! {
+    password_reset_tokens (token_hash) {
+        token_hash -> Text,
+        user_id -> Text,
+        expires -> Int8,
+    }
+}
+
+#[cfg(feature = "biome-login-lockout")]
+table! {
+    user_login_attempts (user_id) {
+        user_id -> Text,
+        failed_attempts -> Int8,
+        last_failed -> Nullable<Int8>,
+    }
+}
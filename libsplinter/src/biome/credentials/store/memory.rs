@@ -14,7 +14,15 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+#[cfg(feature = "biome-login-lockout")]
+use std::time::SystemTime;
 
+#[cfg(feature = "biome-login-lockout")]
+use crate::biome::credentials::lockout::LoginAttempts;
+#[cfg(feature = "biome-mfa")]
+use crate::biome::credentials::mfa::TotpCredentials;
+#[cfg(feature = "biome-password-reset")]
+use crate::biome::credentials::reset::PasswordResetToken;
 use crate::biome::credentials::store::{
     error::CredentialsStoreError, Credentials, CredentialsBuilder, CredentialsStore,
     PasswordEncryptionCost, UsernameId,
@@ -23,12 +31,24 @@ use crate::biome::credentials::store::{
 #[derive(Default, Clone)]
 pub struct MemoryCredentialsStore {
     inner: Arc<Mutex<HashMap<String, Credentials>>>,
+    #[cfg(feature = "biome-mfa")]
+    totp: Arc<Mutex<HashMap<String, TotpCredentials>>>,
+    #[cfg(feature = "biome-password-reset")]
+    reset_tokens: Arc<Mutex<HashMap<String, PasswordResetToken>>>,
+    #[cfg(feature = "biome-login-lockout")]
+    login_attempts: Arc<Mutex<HashMap<String, LoginAttempts>>>,
 }
 
 impl MemoryCredentialsStore {
     pub fn new() -> Self {
         MemoryCredentialsStore {
             inner: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(feature = "biome-mfa")]
+            totp: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(feature = "biome-password-reset")]
+            reset_tokens: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(feature = "biome-login-lockout")]
+            login_attempts: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -90,6 +110,14 @@ impl CredentialsStore for MemoryCredentialsStore {
                 source: None,
             })?;
         if inner.remove(user_id).is_some() {
+            #[cfg(feature = "biome-mfa")]
+            self.totp
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access TOTP enrolments: mutex lock poisoned".to_string(),
+                    source: None,
+                })?
+                .remove(user_id);
             Ok(())
         } else {
             Err(CredentialsStoreError::NotFoundError(format!(
@@ -180,4 +208,213 @@ impl CredentialsStore for MemoryCredentialsStore {
             })
             .collect())
     }
+
+    #[cfg(feature = "biome-mfa")]
+    fn set_totp(&self, totp_credentials: TotpCredentials) -> Result<(), CredentialsStoreError> {
+        let mut totp = self
+            .totp
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access TOTP enrolments: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+        totp.insert(totp_credentials.user_id.clone(), totp_credentials);
+        Ok(())
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn fetch_totp(&self, user_id: &str) -> Result<TotpCredentials, CredentialsStoreError> {
+        let totp = self
+            .totp
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access TOTP enrolments: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+        totp.get(user_id).cloned().ok_or_else(|| {
+            CredentialsStoreError::NotFoundError(format!(
+                "TOTP enrolment for user id {} not found",
+                user_id
+            ))
+        })
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn confirm_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        let mut totp = self
+            .totp
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access TOTP enrolments: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+        match totp.get_mut(user_id) {
+            Some(totp_credentials) => {
+                totp_credentials.confirmed = true;
+                Ok(())
+            }
+            None => Err(CredentialsStoreError::NotFoundError(format!(
+                "TOTP enrolment for user id {} not found",
+                user_id
+            ))),
+        }
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_recovery_code(
+        &self,
+        user_id: &str,
+        code_hash: &str,
+    ) -> Result<(), CredentialsStoreError> {
+        let mut totp = self
+            .totp
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access TOTP enrolments: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+        let recovery_codes = &mut totp
+            .get_mut(user_id)
+            .ok_or_else(|| {
+                CredentialsStoreError::NotFoundError(format!(
+                    "TOTP enrolment for user id {} not found",
+                    user_id
+                ))
+            })?
+            .recovery_codes;
+        match recovery_codes.iter().position(|hash| hash == code_hash) {
+            Some(index) => {
+                recovery_codes.remove(index);
+                Ok(())
+            }
+            None => Err(CredentialsStoreError::NotFoundError(format!(
+                "Recovery code for user id {} not found",
+                user_id
+            ))),
+        }
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn record_totp_step(&self, user_id: &str, step: u64) -> Result<bool, CredentialsStoreError> {
+        let mut totp = self
+            .totp
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access TOTP enrolments: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+        let totp_credentials = totp.get_mut(user_id).ok_or_else(|| {
+            CredentialsStoreError::NotFoundError(format!(
+                "TOTP enrolment for user id {} not found",
+                user_id
+            ))
+        })?;
+        match totp_credentials.last_used_step {
+            Some(last_used_step) if last_used_step >= step => Ok(false),
+            _ => {
+                totp_credentials.last_used_step = Some(step);
+                Ok(true)
+            }
+        }
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        let mut totp = self
+            .totp
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access TOTP enrolments: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+        match totp.remove(user_id) {
+            Some(_) => Ok(()),
+            None => Err(CredentialsStoreError::NotFoundError(format!(
+                "TOTP enrolment for user id {} not found",
+                user_id
+            ))),
+        }
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        let mut reset_tokens =
+            self.reset_tokens
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access password reset tokens: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        reset_tokens.retain(|_, existing| existing.user_id != token.user_id);
+        reset_tokens.insert(token.token_hash.clone(), token);
+        Ok(())
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        let mut reset_tokens =
+            self.reset_tokens
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access password reset tokens: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        reset_tokens.remove(token_hash).ok_or_else(|| {
+            CredentialsStoreError::NotFoundError("Password reset token not found".to_string())
+        })
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn fetch_login_attempts(&self, user_id: &str) -> Result<LoginAttempts, CredentialsStoreError> {
+        let login_attempts =
+            self.login_attempts
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access login attempts: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        Ok(login_attempts
+            .get(user_id)
+            .cloned()
+            .unwrap_or_else(|| LoginAttempts::none(user_id)))
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError> {
+        let mut login_attempts =
+            self.login_attempts
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access login attempts: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        let attempts = login_attempts
+            .entry(user_id.to_string())
+            .or_insert_with(|| LoginAttempts::none(user_id));
+        attempts.failed_attempts += 1;
+        attempts.last_failed = Some(attempted);
+        Ok(attempts.clone())
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn clear_failed_logins(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        self.login_attempts
+            .lock()
+            .map_err(|_| CredentialsStoreError::StorageError {
+                context: "Cannot access login attempts: mutex lock poisoned".to_string(),
+                source: None,
+            })?
+            .remove(user_id);
+        Ok(())
+    }
 }
@@ -26,6 +26,15 @@ use bcrypt::{hash, verify, DEFAULT_COST};
 
 #[cfg(feature = "diesel")]
 use self::diesel::models::{CredentialsModel, NewCredentialsModel};
+#[cfg(feature = "biome-login-lockout")]
+use std::time::SystemTime;
+
+#[cfg(feature = "biome-login-lockout")]
+use super::lockout::LoginAttempts;
+#[cfg(feature = "biome-mfa")]
+use super::mfa::TotpCredentials;
+#[cfg(feature = "biome-password-reset")]
+use super::reset::PasswordResetToken;
 use error::{CredentialsBuilderError, CredentialsError};
 
 const MEDIUM_COST: u32 = 8;
@@ -236,6 +245,166 @@ pub trait CredentialsStore: Send + Sync {
     ///
     /// Returns a CredentialsStoreError if implementation cannot fetch the user IDs
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError>;
+
+    /// Adds a user's TOTP enrolment, replacing any existing enrolment for the user
+    ///
+    /// # Arguments
+    ///
+    ///  * `totp` - The user's TOTP enrolment
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot add the enrolment
+    #[cfg(feature = "biome-mfa")]
+    fn set_totp(&self, totp: TotpCredentials) -> Result<(), CredentialsStoreError>;
+
+    /// Fetches a user's TOTP enrolment
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot fetch the enrolment or if
+    /// the user is not enrolled
+    #[cfg(feature = "biome-mfa")]
+    fn fetch_totp(&self, user_id: &str) -> Result<TotpCredentials, CredentialsStoreError>;
+
+    /// Marks a user's TOTP enrolment as confirmed, after which it is enforced at login
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot update the enrolment or if
+    /// the user is not enrolled
+    #[cfg(feature = "biome-mfa")]
+    fn confirm_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
+
+    /// Removes a used recovery code from a user's TOTP enrolment
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///  * `code_hash` - The stored hash of the recovery code
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot remove the recovery code or
+    /// if the recovery code does not exist
+    #[cfg(feature = "biome-mfa")]
+    fn remove_recovery_code(
+        &self,
+        user_id: &str,
+        code_hash: &str,
+    ) -> Result<(), CredentialsStoreError>;
+
+    /// Records the time step of a TOTP code that a user has just used, so that the code cannot
+    /// be used again. Returns `false`, without updating the enrolment, if the step is not later
+    /// than the last recorded step.
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///  * `step` - The time step of the code that was used
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot update the enrolment or if
+    /// the user is not enrolled
+    #[cfg(feature = "biome-mfa")]
+    fn record_totp_step(&self, user_id: &str, step: u64) -> Result<bool, CredentialsStoreError>;
+
+    /// Removes a user's TOTP enrolment, including its recovery codes
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot remove the enrolment or if
+    /// the user is not enrolled
+    #[cfg(feature = "biome-mfa")]
+    fn remove_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
+
+    /// Adds a password reset token, replacing any token previously issued to the same user
+    ///
+    /// # Arguments
+    ///
+    ///  * `token` - The password reset token, which contains the hash of the issued token
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot add the token
+    #[cfg(feature = "biome-password-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError>;
+
+    /// Removes and returns the password reset token with the given hash, so that each token can
+    /// only be used once. Expired tokens are returned as well; the caller must check the expiry.
+    ///
+    /// # Arguments
+    ///
+    ///  * `token_hash` - The hash of the issued token
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot remove the token or if the
+    /// token does not exist
+    #[cfg(feature = "biome-password-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError>;
+
+    /// Fetches the failed login attempts recorded for a user since their last successful login.
+    /// Returns a record with no failed attempts if none have been recorded.
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot fetch the attempts
+    #[cfg(feature = "biome-login-lockout")]
+    fn fetch_login_attempts(&self, user_id: &str) -> Result<LoginAttempts, CredentialsStoreError>;
+
+    /// Records a failed login attempt for a user and returns the updated attempts
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///  * `attempted` - The time of the failed attempt
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot record the attempt
+    #[cfg(feature = "biome-login-lockout")]
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError>;
+
+    /// Clears the failed login attempts recorded for a user, which unlocks their account. This
+    /// succeeds if no attempts have been recorded.
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot clear the attempts
+    #[cfg(feature = "biome-login-lockout")]
+    fn clear_failed_logins(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
 }
 
 impl<CS> CredentialsStore for Box<CS>
@@ -286,6 +455,75 @@ where
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError> {
         (**self).list_usernames()
     }
+
+    #[cfg(feature = "biome-mfa")]
+    fn set_totp(&self, totp: TotpCredentials) -> Result<(), CredentialsStoreError> {
+        (**self).set_totp(totp)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn fetch_totp(&self, user_id: &str) -> Result<TotpCredentials, CredentialsStoreError> {
+        (**self).fetch_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn confirm_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        (**self).confirm_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_recovery_code(
+        &self,
+        user_id: &str,
+        code_hash: &str,
+    ) -> Result<(), CredentialsStoreError> {
+        (**self).remove_recovery_code(user_id, code_hash)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn record_totp_step(&self, user_id: &str, step: u64) -> Result<bool, CredentialsStoreError> {
+        (**self).record_totp_step(user_id, step)
+    }
+
+    #[cfg(feature = "biome-mfa")]
+    fn remove_totp(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        (**self).remove_totp(user_id)
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        (**self).add_password_reset_token(token)
+    }
+
+    #[cfg(feature = "biome-password-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        (**self).take_password_reset_token(token_hash)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn fetch_login_attempts(&self, user_id: &str) -> Result<LoginAttempts, CredentialsStoreError> {
+        (**self).fetch_login_attempts(user_id)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn record_failed_login(
+        &self,
+        user_id: &str,
+        attempted: SystemTime,
+    ) -> Result<LoginAttempts, CredentialsStoreError> {
+        (**self).record_failed_login(user_id, attempted)
+    }
+
+    #[cfg(feature = "biome-login-lockout")]
+    fn clear_failed_logins(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        (**self).clear_failed_logins(user_id)
+    }
 }
 
 #[cfg(feature = "diesel")]
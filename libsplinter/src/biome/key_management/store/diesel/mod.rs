@@ -14,7 +14,7 @@
 
 pub(in crate::biome) mod models;
 mod operations;
-mod schema;
+pub(in crate::biome) mod schema;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
@@ -27,7 +27,9 @@ use crate::biome::key_management::Key;
 use operations::update_keys_and_password::KeyStoreUpdateKeysAndPasswordOperation as _;
 use operations::{
     fetch_key::KeyStoreFetchKeyOperation as _, insert_key::KeyStoreInsertKeyOperation as _,
-    list_keys::KeyStoreListKeysOperation as _, list_keys::KeyStoreListKeysWithUserIDOperation as _,
+    list_keys::KeyStoreListKeysOperation as _,
+    list_keys::KeyStoreListKeysWithPublicKeysOperation as _,
+    list_keys::KeyStoreListKeysWithUserIDOperation as _,
     remove_key::KeyStoreRemoveKeyOperation as _, update_key::KeyStoreUpdateKeyOperation as _,
     KeyStoreOperations,
 };
@@ -84,6 +86,14 @@ impl KeyStore for DieselKeyStore<diesel::pg::PgConnection> {
         }
     }
 
+    fn list_keys_with_public_keys(
+        &self,
+        public_keys: &[String],
+    ) -> Result<Vec<Key>, KeyStoreError> {
+        KeyStoreOperations::new(&*self.connection_pool.get()?)
+            .list_keys_with_public_keys(public_keys)
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn update_keys_and_password(
         &self,
@@ -136,6 +146,14 @@ impl KeyStore for DieselKeyStore<diesel::sqlite::SqliteConnection> {
         }
     }
 
+    fn list_keys_with_public_keys(
+        &self,
+        public_keys: &[String],
+    ) -> Result<Vec<Key>, KeyStoreError> {
+        KeyStoreOperations::new(&*self.connection_pool.get()?)
+            .list_keys_with_public_keys(public_keys)
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn update_keys_and_password(
         &self,
@@ -274,6 +292,40 @@ pub mod tests {
         assert!(keys.is_empty());
     }
 
+    /// Verify that a SQLite-backed `DieselKeyStore` correctly supports listing keys by their
+    /// public keys.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselKeyStore`.
+    /// 3. Add some keys, with one public key owned by two users.
+    /// 4. Verify that `list_keys_with_public_keys` returns the keys of every owner of the given
+    ///    public keys, and nothing for unknown public keys.
+    #[test]
+    fn sqlite_list_with_public_keys() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselKeyStore::new(pool);
+
+        let key1 = Key::new("pubkey1", "privkey1", "user1", "name1");
+        store.add_key(key1.clone()).expect("Failed to add key1");
+        let key2 = Key::new("pubkey2", "privkey2", "user1", "name2");
+        store.add_key(key2.clone()).expect("Failed to add key2");
+        let key3 = Key::new("pubkey1", "privkey3", "user2", "name3");
+        store.add_key(key3.clone()).expect("Failed to add key3");
+
+        let keys = store
+            .list_keys_with_public_keys(&["pubkey1".to_string(), "pubkey4".to_string()])
+            .expect("Failed to list keys");
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains(&key1));
+        assert!(keys.contains(&key3));
+
+        assert!(store
+            .list_keys_with_public_keys(&["pubkey4".to_string()])
+            .expect("Failed to list keys")
+            .is_empty());
+    }
+
     /// Verify that a SQLite-backed `DieselKeyStore` correctly supports updating keys.
     ///
     /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
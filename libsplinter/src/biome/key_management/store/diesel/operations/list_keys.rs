@@ -77,3 +77,31 @@ where
         Ok(keys)
     }
 }
+
+pub(in crate::biome::key_management) trait KeyStoreListKeysWithPublicKeysOperation {
+    fn list_keys_with_public_keys(&self, public_keys: &[String])
+        -> Result<Vec<Key>, KeyStoreError>;
+}
+
+impl<'a, C> KeyStoreListKeysWithPublicKeysOperation for KeyStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn list_keys_with_public_keys(
+        &self,
+        public_keys: &[String],
+    ) -> Result<Vec<Key>, KeyStoreError> {
+        let keys = keys::table
+            .filter(keys::public_key.eq_any(public_keys))
+            .load::<KeyModel>(self.conn)
+            .map_err(|err| KeyStoreError::OperationError {
+                context: "Failed to get keys with public keys".to_string(),
+                source: Box::new(err),
+            })?
+            .into_iter()
+            .map(Key::from)
+            .collect();
+        Ok(keys)
+    }
+}
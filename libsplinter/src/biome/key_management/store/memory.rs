@@ -126,6 +126,21 @@ impl KeyStore for MemoryKeyStore {
         }
     }
 
+    fn list_keys_with_public_keys(
+        &self,
+        public_keys: &[String],
+    ) -> Result<Vec<Key>, KeyStoreError> {
+        let inner = self.inner.lock().map_err(|_| KeyStoreError::StorageError {
+            context: "Cannot access key store: mutex lock poisoned".to_string(),
+            source: None,
+        })?;
+        Ok(inner
+            .iter()
+            .filter(|((_, public_key), _)| public_keys.contains(public_key))
+            .map(|(_, v)| v.clone())
+            .collect())
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn update_keys_and_password(
         &self,
@@ -71,6 +71,14 @@ pub trait KeyStore: Sync + Send {
     /// * `user_id`: The ID owner of the key records to list.
     fn list_keys(&self, user_id: Option<&str>) -> Result<Vec<Key>, KeyStoreError>;
 
+    /// List the keys with any of the given public keys, for every user that owns them
+    ///
+    /// # Arguments
+    ///
+    /// * `public_keys`: The public keys of the key records to list.
+    fn list_keys_with_public_keys(&self, public_keys: &[String])
+        -> Result<Vec<Key>, KeyStoreError>;
+
     #[cfg(feature = "biome-credentials")]
     /// Updates keys and the associated user's password in the underlying storage
     ///
@@ -118,6 +126,13 @@ where
         (**self).list_keys(user_id)
     }
 
+    fn list_keys_with_public_keys(
+        &self,
+        public_keys: &[String],
+    ) -> Result<Vec<Key>, KeyStoreError> {
+        (**self).list_keys_with_public_keys(public_keys)
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn update_keys_and_password(
         &self,
@@ -0,0 +1,27 @@
+---- Copyright 2018-2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Recipients are tracked by the user_notifications table, which may have an entry for each
+-- recipient of a notification.
+ALTER TABLE notifications DROP COLUMN recipients;
+
+ALTER TABLE notifications
+ ALTER COLUMN created TYPE BIGINT USING EXTRACT(EPOCH FROM created)::BIGINT;
+
+ALTER TABLE user_notifications DROP CONSTRAINT user_notifications_pkey;
+
+ALTER TABLE user_notifications ADD PRIMARY KEY (notification_id, user_id);
+
+CREATE INDEX IF NOT EXISTS idx_user_notifications_user_id ON user_notifications(user_id);
@@ -0,0 +1,56 @@
+---- Copyright 2018-2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+ALTER TABLE user_notifications RENAME TO user_notifications_new;
+ALTER TABLE notification_properties RENAME TO notification_properties_new;
+ALTER TABLE notifications RENAME TO notifications_new;
+
+CREATE TABLE IF NOT EXISTS notifications (
+  id                        TEXT        PRIMARY KEY,
+  payload_title             TEXT        NOT NULL,
+  payload_body              TEXT        NOT NULL,
+  created                   TIMESTAMP   NOT NULL,
+  recipients                TEXT[]      NOT NULL
+);
+
+INSERT INTO notifications (id, payload_title, payload_body, created, recipients)
+  SELECT id, payload_title, payload_body, datetime(created, 'unixepoch'), '{}'
+  FROM notifications_new;
+
+CREATE TABLE IF NOT EXISTS notification_properties (
+  id                        INTEGER     PRIMARY KEY AUTOINCREMENT,
+  notification_id           TEXT        NOT NULL,
+  property                  TEXT        NOT NULL,
+  property_value            TEXT        NOT NULL,
+  FOREIGN KEY (notification_id) REFERENCES notifications(id) ON DELETE CASCADE
+);
+
+INSERT INTO notification_properties (id, notification_id, property, property_value)
+  SELECT id, notification_id, property, property_value FROM notification_properties_new;
+
+-- The original schema only allows a single user per notification
+CREATE TABLE IF NOT EXISTS user_notifications (
+  notification_id           TEXT        PRIMARY KEY,
+  user_id                   TEXT        NOT NULL,
+  unread                    BOOL        NOT NULL,
+  FOREIGN KEY (notification_id) REFERENCES notifications(id) ON DELETE CASCADE
+);
+
+INSERT OR IGNORE INTO user_notifications (notification_id, user_id, unread)
+  SELECT notification_id, user_id, unread FROM user_notifications_new;
+
+DROP TABLE user_notifications_new;
+DROP TABLE notification_properties_new;
+DROP TABLE notifications_new;
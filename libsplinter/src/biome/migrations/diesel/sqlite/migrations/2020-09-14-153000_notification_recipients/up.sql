@@ -0,0 +1,60 @@
+---- Copyright 2018-2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- SQLite cannot alter columns or primary keys, so the notification tables are rebuilt. Recipients
+-- are tracked by the user_notifications table, which may have an entry for each recipient of a
+-- notification.
+ALTER TABLE user_notifications RENAME TO user_notifications_old;
+ALTER TABLE notification_properties RENAME TO notification_properties_old;
+ALTER TABLE notifications RENAME TO notifications_old;
+
+CREATE TABLE IF NOT EXISTS notifications (
+  id                        TEXT        PRIMARY KEY,
+  payload_title             TEXT        NOT NULL,
+  payload_body              TEXT        NOT NULL,
+  created                   BIGINT      NOT NULL
+);
+
+INSERT INTO notifications (id, payload_title, payload_body, created)
+  SELECT id, payload_title, payload_body, CAST(strftime('%s', created) AS INTEGER)
+  FROM notifications_old;
+
+CREATE TABLE IF NOT EXISTS notification_properties (
+  id                        INTEGER     PRIMARY KEY AUTOINCREMENT,
+  notification_id           TEXT        NOT NULL,
+  property                  TEXT        NOT NULL,
+  property_value            TEXT        NOT NULL,
+  FOREIGN KEY (notification_id) REFERENCES notifications(id) ON DELETE CASCADE
+);
+
+INSERT INTO notification_properties (id, notification_id, property, property_value)
+  SELECT id, notification_id, property, property_value FROM notification_properties_old;
+
+CREATE TABLE IF NOT EXISTS user_notifications (
+  notification_id           TEXT        NOT NULL,
+  user_id                   TEXT        NOT NULL,
+  unread                    BOOL        NOT NULL,
+  PRIMARY KEY (notification_id, user_id),
+  FOREIGN KEY (notification_id) REFERENCES notifications(id) ON DELETE CASCADE
+);
+
+INSERT INTO user_notifications (notification_id, user_id, unread)
+  SELECT notification_id, user_id, unread FROM user_notifications_old;
+
+CREATE INDEX IF NOT EXISTS idx_user_notifications_user_id ON user_notifications(user_id);
+
+DROP TABLE user_notifications_old;
+DROP TABLE notification_properties_old;
+DROP TABLE notifications_old;
@@ -0,0 +1,29 @@
+-- Copyright 2018-2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS user_totp (
+  user_id                   TEXT            PRIMARY KEY,
+  encrypted_secret          TEXT            NOT NULL,
+  confirmed                 BOOLEAN         NOT NULL,
+  last_used_step            BIGINT,
+  FOREIGN KEY (user_id) REFERENCES splinter_user(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS user_totp_recovery_codes (
+  id                        INTEGER         PRIMARY KEY AUTOINCREMENT,
+  user_id                   TEXT            NOT NULL,
+  code_hash                 TEXT            NOT NULL,
+  FOREIGN KEY (user_id) REFERENCES user_totp(user_id) ON DELETE CASCADE
+);
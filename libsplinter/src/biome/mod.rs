@@ -24,6 +24,8 @@
 //! Private Key Management: API to store and retrieve encrypted private keys.
 //!
 //! User Notifications: API to create and manage user notifications.
+//!
+//! OAuth Users: API to map OAuth provider identities to users and manage their sessions.
 
 #[cfg(feature = "biome-credentials")]
 pub mod credentials;
@@ -36,6 +38,9 @@ pub mod migrations;
 #[cfg(feature = "biome-notifications")]
 pub mod notifications;
 
+#[cfg(feature = "oauth")]
+pub mod oauth;
+
 #[cfg(feature = "biome-credentials")]
 pub mod refresh_tokens;
 
@@ -57,6 +62,20 @@ pub use key_management::store::memory::MemoryKeyStore;
 #[cfg(feature = "biome-key-management")]
 pub use key_management::store::KeyStore;
 
+#[cfg(all(feature = "biome-notifications", feature = "diesel"))]
+pub use notifications::store::diesel::DieselNotificationStore;
+#[cfg(feature = "biome-notifications")]
+pub use notifications::store::memory::MemoryNotificationStore;
+#[cfg(feature = "biome-notifications")]
+pub use notifications::store::NotificationStore;
+
+#[cfg(all(feature = "oauth", feature = "diesel"))]
+pub use oauth::store::diesel::DieselOAuthUserStore;
+#[cfg(feature = "oauth")]
+pub use oauth::store::memory::MemoryOAuthUserStore;
+#[cfg(feature = "oauth")]
+pub use oauth::store::{OAuthUser, OAuthUserStore, OAuthUserStoreError};
+
 #[cfg(all(feature = "biome-credentials", feature = "diesel"))]
 pub use refresh_tokens::store::diesel::DieselRefreshTokenStore;
 #[cfg(feature = "biome-credentials")]
@@ -67,4 +86,6 @@ pub use refresh_tokens::store::RefreshTokenStore;
 #[cfg(feature = "diesel")]
 pub use user::store::diesel::DieselUserStore;
 pub use user::store::memory::MemoryUserStore;
-pub use user::store::UserStore;
+#[cfg(feature = "biome-user-admin")]
+pub use user::store::ADMIN_ROLE;
+pub use user::store::{User, UserStore, UserStoreError};
@@ -17,4 +17,141 @@
 
 //! Provides an API for notifications.
 
+#[cfg(feature = "biome-key-management")]
+mod proposals;
 pub mod store;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "biome-key-management")]
+pub use proposals::ProposalNotifier;
+
+/// A notification that is sent to one or more users.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    /// The unique ID of the notification
+    pub id: String,
+    /// The title of the notification
+    pub payload_title: String,
+    /// The body of the notification
+    pub payload_body: String,
+    /// The time the notification was created; notifications are stored with a precision of one
+    /// second
+    pub created: SystemTime,
+    /// The IDs of the users that receive the notification
+    pub recipients: Vec<String>,
+    /// Additional application-specific information about the notification
+    pub properties: HashMap<String, String>,
+}
+
+impl Notification {
+    /// Creates a new notification with a random ID, which is created at the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload_title` - The title of the notification
+    /// * `payload_body` - The body of the notification
+    /// * `recipients` - The IDs of the users that receive the notification
+    /// * `properties` - Additional application-specific information about the notification
+    pub fn new(
+        payload_title: &str,
+        payload_body: &str,
+        recipients: Vec<String>,
+        properties: HashMap<String, String>,
+    ) -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        Notification {
+            id: uuid::Uuid::new_v4().to_string(),
+            payload_title: payload_title.to_string(),
+            payload_body: payload_body.to_string(),
+            created: UNIX_EPOCH + Duration::from_secs(seconds),
+            recipients,
+            properties,
+        }
+    }
+}
+
+/// A notification as seen by one of its recipients.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserNotification {
+    /// The unique ID of the notification
+    pub id: String,
+    /// The ID of the user that received the notification
+    pub user_id: String,
+    /// The title of the notification
+    pub payload_title: String,
+    /// The body of the notification
+    pub payload_body: String,
+    /// The time the notification was created
+    pub created: SystemTime,
+    /// Whether or not the user has not yet read the notification
+    pub unread: bool,
+    /// Additional application-specific information about the notification
+    pub properties: HashMap<String, String>,
+}
+
+impl UserNotification {
+    /// Creates the unread copy of the notification that is received by the given user.
+    fn new(notification: &Notification, user_id: &str) -> Self {
+        UserNotification {
+            id: notification.id.clone(),
+            user_id: user_id.to_string(),
+            payload_title: notification.payload_title.clone(),
+            payload_body: notification.payload_body.clone(),
+            created: notification.created,
+            unread: true,
+            properties: notification.properties.clone(),
+        }
+    }
+}
+
+/// Receives the notifications that are added to a `NotificationStore`.
+pub trait NotificationSubscriber: Send {
+    /// Handles a single notification. Returning `NotificationSubscriberError::Unsubscribe` removes
+    /// the subscriber from the store.
+    fn handle_notification(
+        &self,
+        notification: &Notification,
+    ) -> Result<(), NotificationSubscriberError>;
+}
+
+impl<F> NotificationSubscriber for F
+where
+    F: Fn(&Notification) -> Result<(), NotificationSubscriberError> + Send,
+{
+    fn handle_notification(
+        &self,
+        notification: &Notification,
+    ) -> Result<(), NotificationSubscriberError> {
+        (*self)(notification)
+    }
+}
+
+/// Errors returned by a `NotificationSubscriber`.
+#[derive(Debug)]
+pub enum NotificationSubscriberError {
+    /// The subscriber no longer wishes to receive notifications
+    Unsubscribe,
+    /// The subscriber was unable to handle the notification
+    UnableToHandleNotification(String),
+}
+
+impl Error for NotificationSubscriberError {}
+
+impl fmt::Display for NotificationSubscriberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NotificationSubscriberError::Unsubscribe => f.write_str("unsubscribe requested"),
+            NotificationSubscriberError::UnableToHandleNotification(msg) => {
+                write!(f, "unable to handle notification: {}", msg)
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+//! Creates notifications for the users whose keys are involved in circuit proposals.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::thread;
+use std::time::SystemTime;
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::admin::messages::AdminServiceEvent;
+use crate::admin::service::{AdminServiceEventSubscriber, AdminSubscriberError};
+use crate::biome::key_management::store::KeyStore;
+use crate::hex::to_hex;
+
+use super::store::NotificationStore;
+use super::Notification;
+
+/// A notification waiting for its recipients to be looked up.
+struct PendingNotification {
+    title: &'static str,
+    body: String,
+    involved_keys: Vec<String>,
+    properties: HashMap<String, String>,
+}
+
+/// An admin service event subscriber that adds a notification to a `NotificationStore` for each
+/// proposal event. The notification is sent to every user that owns the proposal's requester key,
+/// one of the keys that has voted on the proposal, or the key that signed the event.
+///
+/// Events are handled while the admin service is locked, so the recipients are looked up and the
+/// notifications are added by a background thread, which stops when the notifier is dropped.
+///
+/// Each notification has the properties `circuit_id`, `management_type`, and `event_type`.
+pub struct ProposalNotifier {
+    sender: Sender<PendingNotification>,
+}
+
+impl ProposalNotifier {
+    /// Creates a new `ProposalNotifier`, starting the thread that adds its notifications
+    ///
+    /// # Arguments
+    ///
+    /// * `notification_store` - The store the proposal notifications are added to
+    /// * `key_store` - The store used to find the users that own the keys involved in a proposal
+    ///
+    /// Returns an error if the thread could not be started.
+    pub fn new(
+        notification_store: Arc<dyn NotificationStore>,
+        key_store: Arc<dyn KeyStore>,
+    ) -> Result<Self, io::Error> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        thread::Builder::new()
+            .name("ProposalNotifier".into())
+            .spawn(move || add_notifications(&receiver, &*notification_store, &*key_store))?;
+
+        Ok(ProposalNotifier { sender })
+    }
+}
+
+impl AdminServiceEventSubscriber for ProposalNotifier {
+    fn handle_event(
+        &self,
+        admin_service_event: &AdminServiceEvent,
+        _timestamp: &SystemTime,
+    ) -> Result<(), AdminSubscriberError> {
+        let proposal = admin_service_event.proposal();
+
+        let (event_type, title, signer) = match admin_service_event {
+            AdminServiceEvent::ProposalSubmitted(_) => {
+                ("ProposalSubmitted", "Circuit proposal submitted", None)
+            }
+            AdminServiceEvent::ProposalVote((_, signer)) => {
+                ("ProposalVote", "Vote on circuit proposal", Some(signer))
+            }
+            AdminServiceEvent::ProposalAccepted((_, signer)) => (
+                "ProposalAccepted",
+                "Circuit proposal accepted",
+                Some(signer),
+            ),
+            AdminServiceEvent::ProposalRejected((_, signer)) => (
+                "ProposalRejected",
+                "Circuit proposal rejected",
+                Some(signer),
+            ),
+            AdminServiceEvent::ProposalExpired(_) => {
+                ("ProposalExpired", "Circuit proposal expired", None)
+            }
+            AdminServiceEvent::CircuitReady(_) => ("CircuitReady", "Circuit ready", None),
+        };
+
+        let mut involved_keys = std::iter::once(&proposal.requester)
+            .chain(proposal.votes.iter().map(|vote| &vote.public_key))
+            .chain(signer)
+            .map(|key| to_hex(key))
+            .collect::<Vec<_>>();
+        involved_keys.sort();
+        involved_keys.dedup();
+
+        let mut properties = HashMap::new();
+        properties.insert("circuit_id".to_string(), proposal.circuit_id.clone());
+        properties.insert(
+            "management_type".to_string(),
+            proposal.circuit.circuit_management_type.clone(),
+        );
+        properties.insert("event_type".to_string(), event_type.to_string());
+
+        let body = format!(
+            "{:?} proposal for circuit {} ({})",
+            proposal.proposal_type, proposal.circuit_id, proposal.circuit.circuit_management_type
+        );
+
+        self.sender
+            .send(PendingNotification {
+                title,
+                body,
+                involved_keys,
+                properties,
+            })
+            .map_err(|_| {
+                AdminSubscriberError::UnableToHandleEvent(
+                    "Proposal notifier has stopped".to_string(),
+                )
+            })
+    }
+}
+
+/// Adds the received notifications for the users that own their involved keys, until the
+/// notifier is dropped.
+fn add_notifications(
+    receiver: &Receiver<PendingNotification>,
+    notification_store: &dyn NotificationStore,
+    key_store: &dyn KeyStore,
+) {
+    for pending in receiver {
+        let mut recipients = match key_store.list_keys_with_public_keys(&pending.involved_keys) {
+            Ok(keys) => keys.into_iter().map(|key| key.user_id).collect::<Vec<_>>(),
+            Err(err) => {
+                error!("Unable to find the users of proposal keys: {}", err);
+                continue;
+            }
+        };
+        recipients.sort();
+        recipients.dedup();
+
+        if recipients.is_empty() {
+            continue;
+        }
+
+        if let Err(err) = notification_store.add_notification(Notification::new(
+            pending.title,
+            &pending.body,
+            recipients,
+            pending.properties,
+        )) {
+            error!("Unable to add proposal notification: {}", err);
+        }
+    }
+}
@@ -16,4 +16,265 @@
  */
 
 pub(in crate::biome) mod models;
-mod schema;
+mod operations;
+pub(in crate::biome) mod schema;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::biome::notifications::store::{
+    NotificationStore, NotificationStoreError, NotificationSubscribers,
+};
+use crate::biome::notifications::{Notification, NotificationSubscriber, UserNotification};
+
+use operations::{
+    add_notification::NotificationStoreAddNotificationOperation as _,
+    fetch_notification::NotificationStoreFetchNotificationOperation as _,
+    list_notifications::NotificationStoreListNotificationsOperation as _,
+    mark_read::NotificationStoreMarkReadOperation as _,
+    remove_notification::NotificationStoreRemoveNotificationOperation as _,
+    NotificationStoreOperations,
+};
+
+/// Manages adding, listing, and removing users' notifications in a database.
+pub struct DieselNotificationStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+    subscribers: NotificationSubscribers,
+}
+
+impl<C: diesel::Connection> DieselNotificationStore<C> {
+    /// Creates a new DieselNotificationStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    ///
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselNotificationStore {
+            connection_pool,
+            subscribers: NotificationSubscribers::default(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl NotificationStore for DieselNotificationStore<diesel::pg::PgConnection> {
+    fn add_notification(&self, notification: Notification) -> Result<(), NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .add_notification(&notification)?;
+        self.subscribers.notify(&notification);
+        Ok(())
+    }
+
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?).list_notifications(user_id)
+    }
+
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_notification(user_id, notification_id)
+    }
+
+    fn mark_read(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .mark_read(user_id, notification_id)
+    }
+
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .remove_notification(user_id, notification_id)
+    }
+
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn NotificationSubscriber>,
+    ) -> Result<(), NotificationStoreError> {
+        self.subscribers.add(subscriber)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl NotificationStore for DieselNotificationStore<diesel::sqlite::SqliteConnection> {
+    fn add_notification(&self, notification: Notification) -> Result<(), NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .add_notification(&notification)?;
+        self.subscribers.notify(&notification);
+        Ok(())
+    }
+
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?).list_notifications(user_id)
+    }
+
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .fetch_notification(user_id, notification_id)
+    }
+
+    fn mark_read(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .mark_read(user_id, notification_id)
+    }
+
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        NotificationStoreOperations::new(&*self.connection_pool.get()?)
+            .remove_notification(user_id, notification_id)
+    }
+
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn NotificationSubscriber>,
+    ) -> Result<(), NotificationStoreError> {
+        self.subscribers.add(subscriber)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::biome::migrations::run_sqlite_migrations;
+
+    /// Verify that a SQLite-backed `DieselNotificationStore` correctly supports adding, listing,
+    /// marking as read, and removing a user's notifications.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselNotificationStore`.
+    /// 3. Add a notification for two users and a notification for one user.
+    /// 4. Verify that `list_notifications` returns the unread notifications, newest first, with
+    ///    their properties.
+    /// 5. Mark a notification as read for one user and verify that it is still unread for the
+    ///    other.
+    /// 6. Remove the notification for both users and verify that it is no longer listed and that
+    ///    it is not found for either user.
+    #[test]
+    fn sqlite_user_notifications() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselNotificationStore::new(pool);
+
+        let mut properties = HashMap::new();
+        properties.insert("circuit_id".to_string(), "abcde-01234".to_string());
+        let mut notification1 = Notification::new(
+            "Proposal submitted",
+            "A circuit was proposed",
+            vec!["alice".into(), "bob".into()],
+            properties,
+        );
+        notification1.created -= std::time::Duration::from_secs(60);
+        let notification2 = Notification::new(
+            "Proposal accepted",
+            "A circuit was accepted",
+            vec!["alice".into()],
+            HashMap::new(),
+        );
+
+        store
+            .add_notification(notification1.clone())
+            .expect("Failed to add notification1");
+        store
+            .add_notification(notification2.clone())
+            .expect("Failed to add notification2");
+
+        let alice = store
+            .list_notifications("alice")
+            .expect("Failed to list alice's notifications");
+        assert_eq!(
+            alice,
+            vec![
+                UserNotification::new(&notification2, "alice"),
+                UserNotification::new(&notification1, "alice"),
+            ]
+        );
+
+        store
+            .mark_read("bob", &notification1.id)
+            .expect("Failed to mark notification1 read");
+        assert!(
+            !store
+                .fetch_notification("bob", &notification1.id)
+                .expect("Failed to fetch notification1")
+                .unread
+        );
+        assert!(
+            store
+                .fetch_notification("alice", &notification1.id)
+                .expect("Failed to fetch notification1")
+                .unread
+        );
+
+        store
+            .remove_notification("alice", &notification1.id)
+            .expect("Failed to remove notification1 for alice");
+        store
+            .remove_notification("bob", &notification1.id)
+            .expect("Failed to remove notification1 for bob");
+        assert_eq!(
+            store
+                .list_notifications("alice")
+                .expect("Failed to list alice's notifications"),
+            vec![UserNotification::new(&notification2, "alice")]
+        );
+        assert!(store
+            .list_notifications("bob")
+            .expect("Failed to list bob's notifications")
+            .is_empty());
+
+        match store.mark_read("bob", &notification1.id) {
+            Err(NotificationStoreError::NotFoundError(_)) => (),
+            res => panic!("Expected NotFoundError, got {:?}", res),
+        }
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection insures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
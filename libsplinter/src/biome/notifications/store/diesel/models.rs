@@ -15,33 +15,38 @@
  * -----------------------------------------------------------------------------
  */
 
-use std::time::SystemTime;
-
 use super::schema::{notification_properties, notifications, user_notifications};
 
 #[derive(Insertable, Queryable)]
 #[table_name = "notifications"]
-pub struct Notification {
+pub struct NotificationModel {
     pub id: String,
     pub payload_title: String,
     pub payload_body: String,
-    pub created: SystemTime,
-    pub recipients: Vec<String>,
+    /// The time the notification was created, in seconds since the Unix epoch
+    pub created: i64,
 }
 
 #[derive(Insertable, Queryable)]
 #[table_name = "user_notifications"]
-pub struct UserNotification {
+pub struct UserNotificationModel {
     pub notification_id: String,
     pub user_id: String,
     pub unread: bool,
 }
 
-#[derive(Insertable, Queryable)]
-#[table_name = "notification_properties"]
-pub struct NotificationProperty {
+#[derive(Queryable)]
+pub struct NotificationPropertyModel {
     pub id: i64,
     pub notification_id: String,
     pub property: String,
     pub property_value: String,
 }
+
+#[derive(Insertable)]
+#[table_name = "notification_properties"]
+pub struct NewNotificationPropertyModel<'a> {
+    pub notification_id: &'a str,
+    pub property: &'a str,
+    pub property_value: &'a str,
+}
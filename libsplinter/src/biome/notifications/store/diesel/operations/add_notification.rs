@@ -0,0 +1,151 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+use super::{to_epoch_seconds, NotificationStoreOperations};
+use crate::biome::notifications::store::diesel::models::{
+    NewNotificationPropertyModel, NotificationModel, UserNotificationModel,
+};
+use crate::biome::notifications::store::diesel::schema::{
+    notification_properties, notifications, user_notifications,
+};
+use crate::biome::notifications::{store::NotificationStoreError, Notification};
+
+use diesel::{
+    dsl::insert_into,
+    prelude::*,
+    result::{DatabaseErrorKind, Error as QueryError},
+};
+
+pub(in crate::biome::notifications) trait NotificationStoreAddNotificationOperation {
+    fn add_notification(&self, notification: &Notification) -> Result<(), NotificationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> NotificationStoreAddNotificationOperation
+    for NotificationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_notification(&self, notification: &Notification) -> Result<(), NotificationStoreError> {
+        let notification_model = NotificationModel {
+            id: notification.id.clone(),
+            payload_title: notification.payload_title.clone(),
+            payload_body: notification.payload_body.clone(),
+            created: to_epoch_seconds(notification.created),
+        };
+        let user_notification_models = notification
+            .recipients
+            .iter()
+            .map(|user_id| UserNotificationModel {
+                notification_id: notification.id.clone(),
+                user_id: user_id.clone(),
+                unread: true,
+            })
+            .collect::<Vec<_>>();
+        let property_models = notification
+            .properties
+            .iter()
+            .map(|(property, property_value)| NewNotificationPropertyModel {
+                notification_id: &notification.id,
+                property,
+                property_value,
+            })
+            .collect::<Vec<_>>();
+
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                insert_into(notifications::table)
+                    .values(notification_model)
+                    .execute(self.conn)?;
+                insert_into(user_notifications::table)
+                    .values(user_notification_models)
+                    .execute(self.conn)?;
+                insert_into(notification_properties::table)
+                    .values(property_models)
+                    .execute(self.conn)?;
+                Ok(())
+            })
+            .map_err(|err| match err {
+                QueryError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    NotificationStoreError::DuplicateNotificationError(format!(
+                        "Notification with ID {} is already in database",
+                        notification.id
+                    ))
+                }
+                _ => NotificationStoreError::OperationError {
+                    context: "Failed to add notification".to_string(),
+                    source: Box::new(err),
+                },
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> NotificationStoreAddNotificationOperation
+    for NotificationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_notification(&self, notification: &Notification) -> Result<(), NotificationStoreError> {
+        let notification_model = NotificationModel {
+            id: notification.id.clone(),
+            payload_title: notification.payload_title.clone(),
+            payload_body: notification.payload_body.clone(),
+            created: to_epoch_seconds(notification.created),
+        };
+        let user_notification_models = notification
+            .recipients
+            .iter()
+            .map(|user_id| UserNotificationModel {
+                notification_id: notification.id.clone(),
+                user_id: user_id.clone(),
+                unread: true,
+            })
+            .collect::<Vec<_>>();
+        let property_models = notification
+            .properties
+            .iter()
+            .map(|(property, property_value)| NewNotificationPropertyModel {
+                notification_id: &notification.id,
+                property,
+                property_value,
+            })
+            .collect::<Vec<_>>();
+
+        self.conn
+            .transaction::<_, QueryError, _>(|| {
+                insert_into(notifications::table)
+                    .values(notification_model)
+                    .execute(self.conn)?;
+                insert_into(user_notifications::table)
+                    .values(user_notification_models)
+                    .execute(self.conn)?;
+                insert_into(notification_properties::table)
+                    .values(property_models)
+                    .execute(self.conn)?;
+                Ok(())
+            })
+            .map_err(|err| match err {
+                QueryError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    NotificationStoreError::DuplicateNotificationError(format!(
+                        "Notification with ID {} is already in database",
+                        notification.id
+                    ))
+                }
+                _ => NotificationStoreError::OperationError {
+                    context: "Failed to add notification".to_string(),
+                    source: Box::new(err),
+                },
+            })
+    }
+}
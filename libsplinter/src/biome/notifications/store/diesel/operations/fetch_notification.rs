@@ -0,0 +1,89 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+use super::{to_user_notification, NotificationStoreOperations};
+use crate::biome::notifications::store::diesel::models::{
+    NotificationModel, NotificationPropertyModel, UserNotificationModel,
+};
+use crate::biome::notifications::store::diesel::schema::{
+    notification_properties, notifications, user_notifications,
+};
+use crate::biome::notifications::{store::NotificationStoreError, UserNotification};
+
+use diesel::{prelude::*, result::Error::NotFound};
+
+pub(in crate::biome::notifications) trait NotificationStoreFetchNotificationOperation {
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError>;
+}
+
+impl<'a, C> NotificationStoreFetchNotificationOperation for NotificationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    C::Backend: diesel::sql_types::HasSqlType<diesel::sql_types::Bool>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>
+        + diesel::serialize::ToSql<diesel::sql_types::Bool, C::Backend>,
+{
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError> {
+        let user_notification = user_notifications::table
+            .find((notification_id, user_id))
+            .first::<UserNotificationModel>(self.conn)
+            .map(Some)
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| NotificationStoreError::QueryError {
+                context: "Failed to fetch notification for user".to_string(),
+                source: Box::new(err),
+            })?
+            .ok_or_else(|| {
+                NotificationStoreError::NotFoundError(format!(
+                    "Failed to find notification {} for user {}",
+                    notification_id, user_id
+                ))
+            })?;
+
+        let notification = notifications::table
+            .find(notification_id)
+            .first::<NotificationModel>(self.conn)
+            .map_err(|err| NotificationStoreError::QueryError {
+                context: "Failed to fetch notification".to_string(),
+                source: Box::new(err),
+            })?;
+
+        let properties = notification_properties::table
+            .filter(notification_properties::notification_id.eq(notification_id))
+            .load::<NotificationPropertyModel>(self.conn)
+            .map_err(|err| NotificationStoreError::QueryError {
+                context: "Failed to fetch notification properties".to_string(),
+                source: Box::new(err),
+            })?;
+
+        Ok(to_user_notification(
+            notification,
+            &user_notification,
+            properties,
+        ))
+    }
+}
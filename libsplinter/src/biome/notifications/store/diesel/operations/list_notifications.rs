@@ -0,0 +1,102 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+use super::{to_user_notification, NotificationStoreOperations};
+use crate::biome::notifications::store::diesel::models::{
+    NotificationModel, NotificationPropertyModel, UserNotificationModel,
+};
+use crate::biome::notifications::store::diesel::schema::{
+    notification_properties, notifications, user_notifications,
+};
+use crate::biome::notifications::{store::NotificationStoreError, UserNotification};
+
+use diesel::prelude::*;
+
+pub(in crate::biome::notifications) trait NotificationStoreListNotificationsOperation {
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError>;
+}
+
+impl<'a, C> NotificationStoreListNotificationsOperation for NotificationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    C::Backend: diesel::sql_types::HasSqlType<diesel::sql_types::Bool>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>
+        + diesel::serialize::ToSql<diesel::sql_types::Bool, C::Backend>,
+{
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError> {
+        let user_notification_models = user_notifications::table
+            .filter(user_notifications::user_id.eq(user_id))
+            .load::<UserNotificationModel>(self.conn)
+            .map_err(|err| NotificationStoreError::QueryError {
+                context: "Failed to fetch notifications for user".to_string(),
+                source: Box::new(err),
+            })?;
+
+        if user_notification_models.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let notification_ids = user_notification_models
+            .iter()
+            .map(|user_notification| user_notification.notification_id.clone())
+            .collect::<Vec<_>>();
+
+        let notifications = notifications::table
+            .filter(notifications::id.eq_any(&notification_ids))
+            .order((notifications::created.desc(), notifications::id.asc()))
+            .load::<NotificationModel>(self.conn)
+            .map_err(|err| NotificationStoreError::QueryError {
+                context: "Failed to fetch notifications".to_string(),
+                source: Box::new(err),
+            })?;
+
+        let mut properties = notification_properties::table
+            .filter(notification_properties::notification_id.eq_any(&notification_ids))
+            .load::<NotificationPropertyModel>(self.conn)
+            .map_err(|err| NotificationStoreError::QueryError {
+                context: "Failed to fetch notification properties".to_string(),
+                source: Box::new(err),
+            })?;
+
+        Ok(notifications
+            .into_iter()
+            .filter_map(|notification| {
+                let user_notification =
+                    user_notification_models.iter().find(|user_notification| {
+                        user_notification.notification_id == notification.id
+                    })?;
+                let (own_properties, remaining): (Vec<_>, Vec<_>) = properties
+                    .drain(..)
+                    .partition(|property| property.notification_id == notification.id);
+                properties = remaining;
+                Some(to_user_notification(
+                    notification,
+                    user_notification,
+                    own_properties,
+                ))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+use super::NotificationStoreOperations;
+use crate::biome::notifications::store::diesel::schema::user_notifications;
+use crate::biome::notifications::store::NotificationStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::biome::notifications) trait NotificationStoreMarkReadOperation {
+    fn mark_read(&self, user_id: &str, notification_id: &str)
+        -> Result<(), NotificationStoreError>;
+}
+
+impl<'a, C> NotificationStoreMarkReadOperation for NotificationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    C::Backend: diesel::sql_types::HasSqlType<diesel::sql_types::Bool>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>
+        + diesel::serialize::ToSql<diesel::sql_types::Bool, C::Backend>,
+{
+    fn mark_read(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        match diesel::update(user_notifications::table.find((notification_id, user_id)))
+            .set(user_notifications::unread.eq(false))
+            .execute(self.conn)
+            .map_err(|err| NotificationStoreError::OperationError {
+                context: "Failed to mark notification as read".to_string(),
+                source: Box::new(err),
+            })? {
+            0 => Err(NotificationStoreError::NotFoundError(format!(
+                "Notification {} for user {} not found",
+                notification_id, user_id
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+pub(super) mod add_notification;
+pub(super) mod fetch_notification;
+pub(super) mod list_notifications;
+pub(super) mod mark_read;
+pub(super) mod remove_notification;
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::biome::notifications::UserNotification;
+
+use super::models::{NotificationModel, NotificationPropertyModel, UserNotificationModel};
+
+pub(super) struct NotificationStoreOperations<'a, C> {
+    conn: &'a C,
+}
+
+impl<'a, C> NotificationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    pub fn new(conn: &'a C) -> Self {
+        NotificationStoreOperations { conn }
+    }
+}
+
+/// Converts a time to the number of seconds since the Unix epoch, which is how it is stored.
+fn to_epoch_seconds(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Converts a stored number of seconds since the Unix epoch to a time.
+fn from_epoch_seconds(seconds: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64)
+}
+
+/// Builds the user's view of a notification from the stored notification, the user's row, and
+/// the notification's properties.
+fn to_user_notification(
+    notification: NotificationModel,
+    user_notification: &UserNotificationModel,
+    properties: Vec<NotificationPropertyModel>,
+) -> UserNotification {
+    UserNotification {
+        id: notification.id,
+        user_id: user_notification.user_id.clone(),
+        payload_title: notification.payload_title,
+        payload_body: notification.payload_body,
+        created: from_epoch_seconds(notification.created),
+        unread: user_notification.unread,
+        properties: properties
+            .into_iter()
+            .map(|property| (property.property, property.property_value))
+            .collect::<HashMap<_, _>>(),
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+use super::NotificationStoreOperations;
+use crate::biome::notifications::store::diesel::schema::{
+    notification_properties, notifications, user_notifications,
+};
+use crate::biome::notifications::store::NotificationStoreError;
+
+use diesel::{dsl::delete, prelude::*, result::Error as QueryError};
+
+pub(in crate::biome::notifications) trait NotificationStoreRemoveNotificationOperation {
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError>;
+}
+
+impl<'a, C> NotificationStoreRemoveNotificationOperation for NotificationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        let removed = self
+            .conn
+            .transaction::<_, QueryError, _>(|| {
+                let removed = delete(user_notifications::table.find((notification_id, user_id)))
+                    .execute(self.conn)?;
+                if removed == 0 {
+                    return Ok(false);
+                }
+
+                let remaining_recipients: i64 = user_notifications::table
+                    .filter(user_notifications::notification_id.eq(notification_id))
+                    .count()
+                    .get_result(self.conn)?;
+                if remaining_recipients == 0 {
+                    delete(
+                        notification_properties::table
+                            .filter(notification_properties::notification_id.eq(notification_id)),
+                    )
+                    .execute(self.conn)?;
+                    delete(notifications::table.find(notification_id)).execute(self.conn)?;
+                }
+
+                Ok(true)
+            })
+            .map_err(|err| NotificationStoreError::OperationError {
+                context: "Failed to remove notification".to_string(),
+                source: Box::new(err),
+            })?;
+
+        if removed {
+            Ok(())
+        } else {
+            Err(NotificationStoreError::NotFoundError(format!(
+                "Notification {} for user {} not found",
+                notification_id, user_id
+            )))
+        }
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use crate::biome::notifications::{Notification, NotificationSubscriber, UserNotification};
+
+use super::{NotificationStore, NotificationStoreError, NotificationSubscribers};
+
+/// A `NotificationStore` backed by memory.
+#[derive(Default, Clone)]
+pub struct MemoryNotificationStore {
+    /// The notifications received by each user, keyed by user ID and then notification ID
+    inner: Arc<Mutex<HashMap<String, BTreeMap<String, UserNotification>>>>,
+    subscribers: NotificationSubscribers,
+}
+
+impl MemoryNotificationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NotificationStore for MemoryNotificationStore {
+    fn add_notification(&self, notification: Notification) -> Result<(), NotificationStoreError> {
+        {
+            let mut inner =
+                self.inner
+                    .lock()
+                    .map_err(|_| NotificationStoreError::StorageError {
+                        context: "Cannot access notification store: mutex lock poisoned"
+                            .to_string(),
+                        source: None,
+                    })?;
+
+            if inner
+                .values()
+                .any(|notifications| notifications.contains_key(&notification.id))
+            {
+                return Err(NotificationStoreError::DuplicateNotificationError(format!(
+                    "Notification with ID {} already exists",
+                    notification.id
+                )));
+            }
+
+            for user_id in &notification.recipients {
+                inner.entry(user_id.to_string()).or_default().insert(
+                    notification.id.clone(),
+                    UserNotification::new(&notification, user_id),
+                );
+            }
+        }
+
+        self.subscribers.notify(&notification);
+
+        Ok(())
+    }
+
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|_| NotificationStoreError::StorageError {
+                context: "Cannot access notification store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        let mut notifications = inner
+            .get(user_id)
+            .map(|notifications| notifications.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        notifications.sort_by(|a, b| b.created.cmp(&a.created).then_with(|| a.id.cmp(&b.id)));
+
+        Ok(notifications)
+    }
+
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|_| NotificationStoreError::StorageError {
+                context: "Cannot access notification store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        inner
+            .get(user_id)
+            .and_then(|notifications| notifications.get(notification_id))
+            .cloned()
+            .ok_or_else(|| {
+                NotificationStoreError::NotFoundError(format!(
+                    "Notification {} for user {} not found",
+                    notification_id, user_id
+                ))
+            })
+    }
+
+    fn mark_read(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| NotificationStoreError::StorageError {
+                context: "Cannot access notification store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        match inner
+            .get_mut(user_id)
+            .and_then(|notifications| notifications.get_mut(notification_id))
+        {
+            Some(notification) => {
+                notification.unread = false;
+                Ok(())
+            }
+            None => Err(NotificationStoreError::NotFoundError(format!(
+                "Notification {} for user {} not found",
+                notification_id, user_id
+            ))),
+        }
+    }
+
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| NotificationStoreError::StorageError {
+                context: "Cannot access notification store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        match inner
+            .get_mut(user_id)
+            .and_then(|notifications| notifications.remove(notification_id))
+        {
+            Some(_) => Ok(()),
+            None => Err(NotificationStoreError::NotFoundError(format!(
+                "Notification {} for user {} not found",
+                notification_id, user_id
+            ))),
+        }
+    }
+
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn NotificationSubscriber>,
+    ) -> Result<(), NotificationStoreError> {
+        self.subscribers.add(subscriber)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::biome::notifications::NotificationSubscriberError;
+
+    fn notification(id: &str, created: u64, recipients: &[&str]) -> Notification {
+        Notification {
+            id: id.into(),
+            payload_title: format!("Title {}", id),
+            payload_body: format!("Body {}", id),
+            created: UNIX_EPOCH + Duration::from_secs(created),
+            recipients: recipients.iter().map(|user| user.to_string()).collect(),
+            properties: vec![("circuit_id".to_string(), "abcde-01234".to_string())]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Verify that a `MemoryNotificationStore` gives each recipient its own copy of a
+    /// notification, which can be marked as read and removed without affecting other recipients,
+    /// and that subscribers are sent added notifications.
+    ///
+    /// 1. Add a subscriber and two notifications, one of which is for two users.
+    /// 2. Verify that the subscriber received both notifications.
+    /// 3. Verify that the notifications are listed for each user, newest first, and are unread.
+    /// 4. Mark a notification as read for one user and verify it is still unread for the other.
+    /// 5. Remove the notification for one user and verify it is still listed for the other.
+    /// 6. Verify that operations on a notification that the user did not receive return a
+    ///    `NotificationStoreError::NotFoundError`.
+    #[test]
+    fn memory_user_notifications() {
+        let store = MemoryNotificationStore::new();
+
+        let received = Arc::new(Mutex::new(vec![]));
+        let received_clone = received.clone();
+        store
+            .add_subscriber(Box::new(
+                move |notification: &Notification| -> Result<(), NotificationSubscriberError> {
+                    received_clone
+                        .lock()
+                        .expect("Failed to get lock")
+                        .push(notification.id.clone());
+                    Ok(())
+                },
+            ))
+            .expect("Failed to add subscriber");
+
+        store
+            .add_notification(notification("n1", 10, &["alice", "bob"]))
+            .expect("Failed to add n1");
+        store
+            .add_notification(notification("n2", 20, &["alice"]))
+            .expect("Failed to add n2");
+
+        assert_eq!(
+            *received.lock().expect("Failed to get lock"),
+            vec!["n1".to_string(), "n2".to_string()]
+        );
+
+        let alice = store
+            .list_notifications("alice")
+            .expect("Failed to list alice's notifications");
+        assert_eq!(
+            alice.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(),
+            vec!["n2", "n1"]
+        );
+        assert!(alice.iter().all(|n| n.unread && n.user_id == "alice"));
+
+        store
+            .mark_read("alice", "n1")
+            .expect("Failed to mark n1 read");
+        assert!(
+            !store
+                .fetch_notification("alice", "n1")
+                .expect("Failed to fetch n1")
+                .unread
+        );
+        assert!(
+            store
+                .fetch_notification("bob", "n1")
+                .expect("Failed to fetch n1")
+                .unread
+        );
+
+        store
+            .remove_notification("alice", "n1")
+            .expect("Failed to remove n1");
+        assert_eq!(
+            store
+                .list_notifications("alice")
+                .expect("Failed to list alice's notifications")
+                .len(),
+            1
+        );
+        assert_eq!(
+            store
+                .list_notifications("bob")
+                .expect("Failed to list bob's notifications")
+                .len(),
+            1
+        );
+
+        match store.mark_read("bob", "n2") {
+            Err(NotificationStoreError::NotFoundError(_)) => (),
+            res => panic!("Expected NotFoundError, got {:?}", res),
+        }
+        match store.remove_notification("carol", "n1") {
+            Err(NotificationStoreError::NotFoundError(_)) => (),
+            res => panic!("Expected NotFoundError, got {:?}", res),
+        }
+    }
+}
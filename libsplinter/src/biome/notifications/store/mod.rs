@@ -12,7 +12,220 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Defines a basic representation of a notification.
+//! Defines a basic representation of a notification and a store for notifications.
 
 #[cfg(feature = "diesel")]
 pub(in crate::biome) mod diesel;
+pub mod error;
+pub(in crate::biome) mod memory;
+
+use std::sync::{Arc, Mutex};
+
+use super::{Notification, NotificationSubscriber, NotificationSubscriberError, UserNotification};
+
+pub use error::NotificationStoreError;
+
+/// Defines methods for adding, listing, and managing users' notifications without defining a
+/// storage strategy
+pub trait NotificationStore: Sync + Send {
+    /// Adds a notification to the underlying storage; the notification is unread for each of its
+    /// recipients. The store's subscribers are sent the notification once it has been added.
+    ///
+    /// # Arguments
+    ///
+    ///  * `notification` - The notification to be added
+    fn add_notification(&self, notification: Notification) -> Result<(), NotificationStoreError>;
+
+    /// Lists the notifications received by a user, newest first
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The ID of the user whose notifications will be listed
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError>;
+
+    /// Fetches a notification received by a user
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The ID of the user that received the notification
+    ///  * `notification_id` - The ID of the notification to be fetched
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError>;
+
+    /// Marks a notification received by a user as read
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The ID of the user that received the notification
+    ///  * `notification_id` - The ID of the notification to be marked as read
+    fn mark_read(&self, user_id: &str, notification_id: &str)
+        -> Result<(), NotificationStoreError>;
+
+    /// Removes a notification received by a user; the notification itself is removed once it has
+    /// been removed by all of its recipients
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The ID of the user that received the notification
+    ///  * `notification_id` - The ID of the notification to be removed
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError>;
+
+    /// Adds a subscriber that is sent each notification that is added to this store
+    ///
+    /// # Arguments
+    ///
+    ///  * `subscriber` - The subscriber to be added
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn NotificationSubscriber>,
+    ) -> Result<(), NotificationStoreError>;
+}
+
+impl<NS> NotificationStore for Box<NS>
+where
+    NS: NotificationStore + ?Sized,
+{
+    fn add_notification(&self, notification: Notification) -> Result<(), NotificationStoreError> {
+        (**self).add_notification(notification)
+    }
+
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError> {
+        (**self).list_notifications(user_id)
+    }
+
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError> {
+        (**self).fetch_notification(user_id, notification_id)
+    }
+
+    fn mark_read(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        (**self).mark_read(user_id, notification_id)
+    }
+
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        (**self).remove_notification(user_id, notification_id)
+    }
+
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn NotificationSubscriber>,
+    ) -> Result<(), NotificationStoreError> {
+        (**self).add_subscriber(subscriber)
+    }
+}
+
+impl<NS> NotificationStore for Arc<NS>
+where
+    NS: NotificationStore + ?Sized,
+{
+    fn add_notification(&self, notification: Notification) -> Result<(), NotificationStoreError> {
+        (**self).add_notification(notification)
+    }
+
+    fn list_notifications(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<UserNotification>, NotificationStoreError> {
+        (**self).list_notifications(user_id)
+    }
+
+    fn fetch_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<UserNotification, NotificationStoreError> {
+        (**self).fetch_notification(user_id, notification_id)
+    }
+
+    fn mark_read(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        (**self).mark_read(user_id, notification_id)
+    }
+
+    fn remove_notification(
+        &self,
+        user_id: &str,
+        notification_id: &str,
+    ) -> Result<(), NotificationStoreError> {
+        (**self).remove_notification(user_id, notification_id)
+    }
+
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn NotificationSubscriber>,
+    ) -> Result<(), NotificationStoreError> {
+        (**self).add_subscriber(subscriber)
+    }
+}
+
+/// The set of subscribers registered with a notification store implementation.
+#[derive(Clone, Default)]
+pub(in crate::biome) struct NotificationSubscribers {
+    subscribers: Arc<Mutex<Vec<Box<dyn NotificationSubscriber>>>>,
+}
+
+impl NotificationSubscribers {
+    pub fn add(
+        &self,
+        subscriber: Box<dyn NotificationSubscriber>,
+    ) -> Result<(), NotificationStoreError> {
+        self.subscribers
+            .lock()
+            .map_err(|_| NotificationStoreError::StorageError {
+                context: "Cannot access notification subscribers: mutex lock poisoned".to_string(),
+                source: None,
+            })?
+            .push(subscriber);
+        Ok(())
+    }
+
+    /// Sends the notification to every subscriber; subscribers that return
+    /// `NotificationSubscriberError::Unsubscribe` are dropped.
+    pub fn notify(&self, notification: &Notification) {
+        let mut subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(_) => {
+                error!("Notification subscriber lock was poisoned; unable to send notification");
+                return;
+            }
+        };
+
+        subscribers.retain(
+            |subscriber| match subscriber.handle_notification(notification) {
+                Ok(()) => true,
+                Err(NotificationSubscriberError::Unsubscribe) => false,
+                Err(err) => {
+                    error!("Unable to send notification: {}", err);
+                    true
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod models;
+mod operations;
+pub(in crate::biome) mod schema;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::biome::oauth::store::{OAuthUser, OAuthUserStore, OAuthUserStoreError};
+
+use operations::{
+    add_oauth_user::OAuthUserStoreAddOAuthUserOperation as _,
+    fetch_by_subject::OAuthUserStoreFetchBySubjectOperation as _,
+    fetch_by_user_id::OAuthUserStoreFetchByUserIdOperation as _,
+    update_oauth_user::OAuthUserStoreUpdateOAuthUserOperation as _, OAuthUserStoreOperations,
+};
+
+/// A database-backed OAuthUserStore, powered by [`Diesel`](https://crates.io/crates/diesel).
+pub struct DieselOAuthUserStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection> DieselOAuthUserStore<C> {
+    /// Creates a new DieselOAuthUserStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselOAuthUserStore { connection_pool }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl OAuthUserStore for DieselOAuthUserStore<diesel::pg::PgConnection> {
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).add_oauth_user(oauth_user)
+    }
+
+    fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).update_oauth_user(oauth_user)
+    }
+
+    fn fetch_by_subject(&self, subject: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).fetch_by_subject(subject)
+    }
+
+    fn fetch_by_user_id(&self, user_id: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).fetch_by_user_id(user_id)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl OAuthUserStore for DieselOAuthUserStore<diesel::sqlite::SqliteConnection> {
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).add_oauth_user(oauth_user)
+    }
+
+    fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).update_oauth_user(oauth_user)
+    }
+
+    fn fetch_by_subject(&self, subject: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).fetch_by_subject(subject)
+    }
+
+    fn fetch_by_user_id(&self, user_id: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        OAuthUserStoreOperations::new(&*self.connection_pool.get()?).fetch_by_user_id(user_id)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::biome::migrations::run_sqlite_migrations;
+    use crate::biome::user::store::{diesel::DieselUserStore, User, UserStore};
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    /// Verify that a SQLite-backed `DieselOAuthUserStore` correctly supports adding, fetching and
+    /// updating OAuth users.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create a `DieselUserStore` and add the necessary users.
+    /// 3. Create the `DieselOAuthUserStore` and add an OAuth user.
+    /// 4. Verify that the OAuth user can be fetched by subject and by user ID, and that fetching an
+    ///    unknown subject returns `None`.
+    /// 5. Verify that adding another OAuth user for the same subject or user fails.
+    /// 6. Verify that adding an OAuth user for an unknown user fails.
+    /// 7. Start a session for the OAuth user and verify that it is persisted.
+    /// 8. Remove the session and verify that it is no longer persisted.
+    #[test]
+    fn sqlite_add_fetch_and_update() {
+        let pool = create_connection_pool_and_migrate();
+
+        let user_store = DieselUserStore::new(pool.clone());
+        user_store
+            .add_user(User::new("user1"))
+            .expect("Failed to add user1");
+        user_store
+            .add_user(User::new("user2"))
+            .expect("Failed to add user2");
+
+        let store = DieselOAuthUserStore::new(pool);
+
+        let oauth_user = OAuthUser::new("user1", "subject1");
+        store
+            .add_oauth_user(oauth_user.clone())
+            .expect("Failed to add OAuth user");
+
+        assert_eq!(
+            store
+                .fetch_by_subject("subject1")
+                .expect("Failed to fetch by subject"),
+            Some(oauth_user.clone())
+        );
+        assert_eq!(
+            store
+                .fetch_by_user_id("user1")
+                .expect("Failed to fetch by user ID"),
+            Some(oauth_user.clone())
+        );
+        assert_eq!(
+            store
+                .fetch_by_subject("subject2")
+                .expect("Failed to fetch by subject"),
+            None
+        );
+
+        match store.add_oauth_user(OAuthUser::new("user2", "subject1")) {
+            Err(OAuthUserStoreError::DuplicateError(_)) => {}
+            res => panic!(
+                "Expected Err(OAuthUserStoreError::DuplicateError), got {:?} instead",
+                res
+            ),
+        }
+        match store.add_oauth_user(OAuthUser::new("user1", "subject2")) {
+            Err(OAuthUserStoreError::DuplicateError(_)) => {}
+            res => panic!(
+                "Expected Err(OAuthUserStoreError::DuplicateError), got {:?} instead",
+                res
+            ),
+        }
+        match store.add_oauth_user(OAuthUser::new("user3", "subject3")) {
+            Err(OAuthUserStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(OAuthUserStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+
+        let with_session = oauth_user.with_session(
+            "session",
+            "access",
+            Some("refresh"),
+            Some(UNIX_EPOCH + Duration::from_secs(1_000_000)),
+        );
+        store
+            .update_oauth_user(with_session.clone())
+            .expect("Failed to start session");
+        let fetched = store
+            .fetch_by_user_id("user1")
+            .expect("Failed to fetch by user ID")
+            .expect("OAuth user not found");
+        assert_eq!(fetched, with_session);
+        assert!(fetched.has_session());
+
+        store
+            .update_oauth_user(with_session.without_session())
+            .expect("Failed to remove session");
+        let fetched = store
+            .fetch_by_subject("subject1")
+            .expect("Failed to fetch by subject")
+            .expect("OAuth user not found");
+        assert!(!fetched.has_session());
+        assert_eq!(fetched.expires_at(), None);
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection insures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::schema::oauth_users;
+use crate::biome::oauth::store::OAuthUser;
+
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "oauth_users"]
+pub struct OAuthUserModel {
+    pub user_id: String,
+    pub subject: String,
+    pub session_id: Option<String>,
+    pub access_token: Option<String>,
+    pub refresh_token: Option<String>,
+    /// Seconds since the Unix epoch
+    pub expires_at: Option<i64>,
+}
+
+impl From<OAuthUser> for OAuthUserModel {
+    fn from(oauth_user: OAuthUser) -> Self {
+        Self {
+            user_id: oauth_user.user_id,
+            subject: oauth_user.subject,
+            session_id: oauth_user.session_id,
+            access_token: oauth_user.access_token,
+            refresh_token: oauth_user.refresh_token,
+            expires_at: oauth_user.expires_at.map(|expires_at| {
+                expires_at
+                    .duration_since(UNIX_EPOCH)
+                    .ok()
+                    .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+                    .unwrap_or(0)
+            }),
+        }
+    }
+}
+
+impl From<OAuthUserModel> for OAuthUser {
+    fn from(model: OAuthUserModel) -> Self {
+        Self {
+            user_id: model.user_id,
+            subject: model.subject,
+            session_id: model.session_id,
+            access_token: model.access_token,
+            refresh_token: model.refresh_token,
+            expires_at: model
+                .expires_at
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(u64::try_from(secs).unwrap_or(0))),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OAuthUserStoreOperations;
+use crate::biome::oauth::store::{
+    diesel::{models::OAuthUserModel, schema::oauth_users},
+    OAuthUser, OAuthUserStoreError,
+};
+use crate::biome::user::store::diesel::{models::UserModel, schema::splinter_user};
+use diesel::{
+    dsl::insert_into,
+    prelude::*,
+    result::{DatabaseErrorKind, Error as QueryError},
+};
+
+pub(in crate::biome) trait OAuthUserStoreAddOAuthUserOperation {
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> OAuthUserStoreAddOAuthUserOperation
+    for OAuthUserStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        let model = OAuthUserModel::from(oauth_user);
+
+        splinter_user::table
+            .find(&model.user_id)
+            .first::<UserModel>(self.conn)
+            .optional()
+            .map_err(|err| OAuthUserStoreError::QueryError {
+                context: "Failed to check if user exists".into(),
+                source: Box::new(err),
+            })?
+            .ok_or_else(|| {
+                OAuthUserStoreError::NotFoundError(format!("User {} not found", model.user_id))
+            })?;
+
+        let duplicate = oauth_users::table
+            .filter(
+                oauth_users::user_id
+                    .eq(&model.user_id)
+                    .or(oauth_users::subject.eq(&model.subject)),
+            )
+            .first::<OAuthUserModel>(self.conn)
+            .optional()
+            .map_err(|err| OAuthUserStoreError::QueryError {
+                context: "Failed to check for an existing OAuth user".into(),
+                source: Box::new(err),
+            })?;
+        let duplicate_error = format!(
+            "An OAuth user already exists for user {} or subject {}",
+            model.user_id, model.subject
+        );
+        if duplicate.is_some() {
+            return Err(OAuthUserStoreError::DuplicateError(duplicate_error));
+        }
+
+        // An OAuth user added concurrently since the check above violates the unique constraints
+        insert_into(oauth_users::table)
+            .values(model)
+            .execute(self.conn)
+            .map_err(|err| match err {
+                QueryError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    OAuthUserStoreError::DuplicateError(duplicate_error)
+                }
+                err => OAuthUserStoreError::OperationError {
+                    context: "Failed to add OAuth user".to_string(),
+                    source: Box::new(err),
+                },
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> OAuthUserStoreAddOAuthUserOperation
+    for OAuthUserStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        let model = OAuthUserModel::from(oauth_user);
+
+        splinter_user::table
+            .find(&model.user_id)
+            .first::<UserModel>(self.conn)
+            .optional()
+            .map_err(|err| OAuthUserStoreError::QueryError {
+                context: "Failed to check if user exists".into(),
+                source: Box::new(err),
+            })?
+            .ok_or_else(|| {
+                OAuthUserStoreError::NotFoundError(format!("User {} not found", model.user_id))
+            })?;
+
+        let duplicate = oauth_users::table
+            .filter(
+                oauth_users::user_id
+                    .eq(&model.user_id)
+                    .or(oauth_users::subject.eq(&model.subject)),
+            )
+            .first::<OAuthUserModel>(self.conn)
+            .optional()
+            .map_err(|err| OAuthUserStoreError::QueryError {
+                context: "Failed to check for an existing OAuth user".into(),
+                source: Box::new(err),
+            })?;
+        let duplicate_error = format!(
+            "An OAuth user already exists for user {} or subject {}",
+            model.user_id, model.subject
+        );
+        if duplicate.is_some() {
+            return Err(OAuthUserStoreError::DuplicateError(duplicate_error));
+        }
+
+        // An OAuth user added concurrently since the check above violates the unique constraints
+        insert_into(oauth_users::table)
+            .values(model)
+            .execute(self.conn)
+            .map_err(|err| match err {
+                QueryError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    OAuthUserStoreError::DuplicateError(duplicate_error)
+                }
+                err => OAuthUserStoreError::OperationError {
+                    context: "Failed to add OAuth user".to_string(),
+                    source: Box::new(err),
+                },
+            })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OAuthUserStoreOperations;
+use crate::biome::oauth::store::{
+    diesel::{models::OAuthUserModel, schema::oauth_users},
+    OAuthUser, OAuthUserStoreError,
+};
+use diesel::prelude::*;
+
+pub(in crate::biome) trait OAuthUserStoreFetchByUserIdOperation {
+    fn fetch_by_user_id(&self, user_id: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError>;
+}
+
+impl<'a, C> OAuthUserStoreFetchByUserIdOperation for OAuthUserStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn fetch_by_user_id(&self, user_id: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        oauth_users::table
+            .filter(oauth_users::user_id.eq(user_id))
+            .first::<OAuthUserModel>(self.conn)
+            .optional()
+            .map(|model| model.map(OAuthUser::from))
+            .map_err(|err| OAuthUserStoreError::QueryError {
+                context: format!("Failed to fetch OAuth user for user {}", user_id),
+                source: Box::new(err),
+            })
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OAuthUserStoreOperations;
+use crate::biome::oauth::store::{
+    diesel::{models::OAuthUserModel, schema::oauth_users},
+    OAuthUser, OAuthUserStoreError,
+};
+use diesel::{dsl::update, prelude::*};
+
+pub(in crate::biome) trait OAuthUserStoreUpdateOAuthUserOperation {
+    fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError>;
+}
+
+impl<'a, C> OAuthUserStoreUpdateOAuthUserOperation for OAuthUserStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::serialize::ToSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        let model = OAuthUserModel::from(oauth_user);
+
+        let updated = update(
+            oauth_users::table.filter(
+                oauth_users::user_id
+                    .eq(&model.user_id)
+                    .and(oauth_users::subject.eq(&model.subject)),
+            ),
+        )
+        .set((
+            oauth_users::session_id.eq(&model.session_id),
+            oauth_users::access_token.eq(&model.access_token),
+            oauth_users::refresh_token.eq(&model.refresh_token),
+            oauth_users::expires_at.eq(&model.expires_at),
+        ))
+        .execute(self.conn)
+        .map_err(|err| OAuthUserStoreError::OperationError {
+            context: format!("Failed to update OAuth user for user {}", model.user_id),
+            source: Box::new(err),
+        })?;
+
+        if updated == 0 {
+            Err(OAuthUserStoreError::NotFoundError(format!(
+                "No OAuth user for user {} with subject {} found",
+                model.user_id, model.subject
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::{OAuthUser, OAuthUserStore, OAuthUserStoreError};
+
+#[derive(Default, Clone)]
+pub struct MemoryOAuthUserStore {
+    inner: Arc<Mutex<HashMap<String, OAuthUser>>>,
+}
+
+impl MemoryOAuthUserStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OAuthUserStore for MemoryOAuthUserStore {
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| OAuthUserStoreError::StorageError {
+                context: "Cannot access OAuth user store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        if inner.contains_key(oauth_user.user_id())
+            || inner
+                .values()
+                .any(|existing| existing.subject() == oauth_user.subject())
+        {
+            return Err(OAuthUserStoreError::DuplicateError(format!(
+                "An OAuth user already exists for user {} or subject {}",
+                oauth_user.user_id(),
+                oauth_user.subject()
+            )));
+        }
+
+        inner.insert(oauth_user.user_id().to_string(), oauth_user);
+        Ok(())
+    }
+
+    fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| OAuthUserStoreError::StorageError {
+                context: "Cannot access OAuth user store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        match inner.get_mut(oauth_user.user_id()) {
+            Some(existing) if existing.subject() == oauth_user.subject() => {
+                *existing = oauth_user;
+                Ok(())
+            }
+            Some(_) => Err(OAuthUserStoreError::StorageError {
+                context: format!(
+                    "The subject of the OAuth user for user {} cannot be changed",
+                    oauth_user.user_id()
+                ),
+                source: None,
+            }),
+            None => Err(OAuthUserStoreError::NotFoundError(format!(
+                "No OAuth user for user {} found",
+                oauth_user.user_id()
+            ))),
+        }
+    }
+
+    fn fetch_by_subject(&self, subject: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|_| OAuthUserStoreError::StorageError {
+                context: "Cannot access OAuth user store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        Ok(inner
+            .values()
+            .find(|oauth_user| oauth_user.subject() == subject)
+            .cloned())
+    }
+
+    fn fetch_by_user_id(&self, user_id: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|_| OAuthUserStoreError::StorageError {
+                context: "Cannot access OAuth user store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        Ok(inner.get(user_id).cloned())
+    }
+}
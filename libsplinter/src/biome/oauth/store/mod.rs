@@ -0,0 +1,202 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "diesel")]
+pub(crate) mod diesel;
+mod error;
+pub(in crate::biome) mod memory;
+
+use std::fmt;
+use std::time::SystemTime;
+
+pub use error::OAuthUserStoreError;
+
+/// Represents a Biome user that is authenticated by an OAuth provider
+///
+/// The user's session holds the provider's tokens; a user without a session has either logged out
+/// or has not yet logged in.
+#[derive(Clone, PartialEq)]
+pub struct OAuthUser {
+    user_id: String,
+    subject: String,
+    session_id: Option<String>,
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    expires_at: Option<SystemTime>,
+}
+
+impl OAuthUser {
+    /// Creates a new `OAuthUser` without a session
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the Biome user
+    /// * `subject` - The identifier of the user with the OAuth provider
+    pub fn new(user_id: &str, subject: &str) -> Self {
+        Self {
+            user_id: user_id.into(),
+            subject: subject.into(),
+            session_id: None,
+            access_token: None,
+            refresh_token: None,
+            expires_at: None,
+        }
+    }
+
+    /// Returns the ID of the Biome user
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// Returns the identifier of the user with the OAuth provider
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Returns the ID of the user's current session, if the user has one
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    /// Returns the provider's access token for the user's current session
+    pub fn access_token(&self) -> Option<&str> {
+        self.access_token.as_deref()
+    }
+
+    /// Returns the provider's refresh token for the user's current session. Not all providers use
+    /// refresh tokens, so this may be `None` even if the user has a session.
+    pub fn refresh_token(&self) -> Option<&str> {
+        self.refresh_token.as_deref()
+    }
+
+    /// Returns the time at which the provider's access token expires. Not all providers expire
+    /// access tokens, so this may be `None` even if the user has a session.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Returns whether or not the user currently has a session
+    pub fn has_session(&self) -> bool {
+        self.session_id.is_some() && self.access_token.is_some()
+    }
+
+    /// Returns whether or not the provider's access token has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= SystemTime::now())
+            .unwrap_or(false)
+    }
+
+    /// Sets the user's session
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The ID of the session
+    /// * `access_token` - The provider's access token
+    /// * `refresh_token` - The provider's refresh token, if it has one
+    /// * `expires_at` - The time at which the provider's access token expires, if it does
+    pub fn with_session(
+        mut self,
+        session_id: &str,
+        access_token: &str,
+        refresh_token: Option<&str>,
+        expires_at: Option<SystemTime>,
+    ) -> Self {
+        self.session_id = Some(session_id.into());
+        self.access_token = Some(access_token.into());
+        self.refresh_token = refresh_token.map(String::from);
+        self.expires_at = expires_at;
+        self
+    }
+
+    /// Removes the user's session
+    pub fn without_session(mut self) -> Self {
+        self.session_id = None;
+        self.access_token = None;
+        self.refresh_token = None;
+        self.expires_at = None;
+        self
+    }
+}
+
+impl fmt::Debug for OAuthUser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OAuthUser")
+            .field("user_id", &self.user_id)
+            .field("subject", &self.subject)
+            .field("session_id", &self.session_id)
+            .field(
+                "access_token",
+                &self.access_token.as_deref().map(|_| "<Redacted>"),
+            )
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_deref().map(|_| "<Redacted>"),
+            )
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Defines methods for CRUD operations for handling OAuth users
+pub trait OAuthUserStore: Send + Sync {
+    /// Adds an OAuth user to the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///   * `oauth_user` - The OAuth user to be added; its Biome user must already exist
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError>;
+
+    /// Replaces the session of an existing OAuth user in the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///   * `oauth_user` - The updated OAuth user
+    fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError>;
+
+    /// Fetches the OAuth user with the given provider identity from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///   * `subject` - The identifier of the user with the OAuth provider
+    fn fetch_by_subject(&self, subject: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError>;
+
+    /// Fetches the OAuth user for the given Biome user from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///   * `user_id` - The ID of the Biome user
+    fn fetch_by_user_id(&self, user_id: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError>;
+}
+
+impl<OS> OAuthUserStore for Box<OS>
+where
+    OS: OAuthUserStore + ?Sized,
+{
+    fn add_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        (**self).add_oauth_user(oauth_user)
+    }
+
+    fn update_oauth_user(&self, oauth_user: OAuthUser) -> Result<(), OAuthUserStoreError> {
+        (**self).update_oauth_user(oauth_user)
+    }
+
+    fn fetch_by_subject(&self, subject: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        (**self).fetch_by_subject(subject)
+    }
+
+    fn fetch_by_user_id(&self, user_id: &str) -> Result<Option<OAuthUser>, OAuthUserStoreError> {
+        (**self).fetch_by_user_id(user_id)
+    }
+}
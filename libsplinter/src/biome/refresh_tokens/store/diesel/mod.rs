@@ -14,7 +14,7 @@
 
 mod models;
 mod operations;
-mod schema;
+pub(in crate::biome) mod schema;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
@@ -0,0 +1,258 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /biome/users/{id}/lockout` for fetching a user's failed login attempts
+//! * `DELETE /biome/users/{id}/lockout` for unlocking a user's account
+//!
+//! Both endpoints may only be used by administrators; if the `biome-user-admin` feature is
+//! enabled, users with the `admin` role are also administrators. It also provides the lockout
+//! checks performed by the login endpoint.
+
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::authorize::authorize_user;
+#[cfg(feature = "biome-user-admin")]
+use super::user_admin::is_admin;
+use crate::actix_web::{HttpRequest, HttpResponse};
+use crate::biome::credentials::store::CredentialsStore;
+use crate::biome::rest_api::resources::authorize::AuthorizationResult;
+use crate::biome::rest_api::BiomeRestConfig;
+#[cfg(feature = "biome-user-admin")]
+use crate::biome::user::store::UserStore;
+use crate::futures::IntoFuture;
+use crate::protocol;
+use crate::rest_api::{
+    secrets::SecretManager, sessions::default_validation, ErrorResponse, HandlerFunction, Method,
+    ProtocolVersionRangeGuard, Resource,
+};
+
+/// Defines a REST endpoint for administering a user's account lockout
+pub fn make_lockout_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+    #[cfg(feature = "biome-user-admin")] user_store: Arc<dyn UserStore>,
+) -> Resource {
+    Resource::build("/biome/users/{id}/lockout")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_LOCKOUT_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(
+            Method::Get,
+            handle_fetch(
+                credentials_store.clone(),
+                rest_config.clone(),
+                secret_manager.clone(),
+                #[cfg(feature = "biome-user-admin")]
+                user_store.clone(),
+            ),
+        )
+        .add_method(
+            Method::Delete,
+            handle_unlock(
+                credentials_store,
+                rest_config,
+                secret_manager,
+                #[cfg(feature = "biome-user-admin")]
+                user_store,
+            ),
+        )
+}
+
+/// Returns the failed login attempts of the user in the path and whether they are locked out
+fn handle_fetch(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+    #[cfg(feature = "biome-user-admin")] user_store: Arc<dyn UserStore>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match authorize_admin(
+            &request,
+            &rest_config,
+            &secret_manager,
+            #[cfg(feature = "biome-user-admin")]
+            &*user_store,
+        ) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+
+        match credentials_store.fetch_login_attempts(&user_id) {
+            Ok(attempts) => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({
+                        "user_id": attempts.user_id,
+                        "failed_attempts": attempts.failed_attempts,
+                        "locked": attempts
+                            .is_locked(rest_config.lockout_policy(), SystemTime::now()),
+                    }))
+                    .into_future(),
+            ),
+            Err(err) => {
+                error!("Failed to fetch login attempts: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Clears the failed login attempts of the user in the path, unlocking their account
+fn handle_unlock(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+    #[cfg(feature = "biome-user-admin")] user_store: Arc<dyn UserStore>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match authorize_admin(
+            &request,
+            &rest_config,
+            &secret_manager,
+            #[cfg(feature = "biome-user-admin")]
+            &*user_store,
+        ) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+
+        match credentials_store.clear_failed_logins(&user_id) {
+            Ok(()) => {
+                info!("Unlocked account of user {}", user_id);
+                Box::new(
+                    HttpResponse::Ok()
+                        .json(json!({ "message": "Account unlocked" }))
+                        .into_future(),
+                )
+            }
+            Err(err) => {
+                error!("Failed to clear failed logins: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Rejects a login for a user whose account is locked. If a lockout has elapsed, the user's
+/// failed attempts are cleared so that they get the full number of attempts again.
+pub(super) fn check_lockout(
+    credentials_store: &dyn CredentialsStore,
+    rest_config: &BiomeRestConfig,
+    user_id: &str,
+) -> Result<(), HttpResponse> {
+    let attempts = credentials_store
+        .fetch_login_attempts(user_id)
+        .map_err(|err| {
+            error!("Failed to fetch login attempts: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })?;
+
+    let now = SystemTime::now();
+    if attempts.is_locked(rest_config.lockout_policy(), now) {
+        return Err(HttpResponse::Forbidden().json(ErrorResponse::forbidden(
+            "Account is locked due to too many failed login attempts",
+        )));
+    }
+    if attempts.is_lockout_expired(rest_config.lockout_policy(), now) {
+        clear_failed_logins(credentials_store, user_id)?;
+    }
+    Ok(())
+}
+
+/// Records a failed login attempt for a user.
+pub(super) fn record_failed_login(
+    credentials_store: &dyn CredentialsStore,
+    rest_config: &BiomeRestConfig,
+    user_id: &str,
+) -> Result<(), HttpResponse> {
+    let now = SystemTime::now();
+    let attempts = credentials_store
+        .record_failed_login(user_id, now)
+        .map_err(|err| {
+            error!("Failed to record failed login: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })?;
+    if attempts.is_locked(rest_config.lockout_policy(), now) {
+        info!(
+            "Locked account of user {} after {} failed login attempts",
+            user_id, attempts.failed_attempts
+        );
+    }
+    Ok(())
+}
+
+/// Clears a user's failed login attempts after a successful login.
+pub(super) fn clear_failed_logins(
+    credentials_store: &dyn CredentialsStore,
+    user_id: &str,
+) -> Result<(), HttpResponse> {
+    credentials_store
+        .clear_failed_logins(user_id)
+        .map_err(|err| {
+            error!("Failed to clear failed logins: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })
+}
+
+/// Checks that the request is authorized by an administrator, and returns the user ID in the
+/// path.
+fn authorize_admin(
+    request: &HttpRequest,
+    rest_config: &BiomeRestConfig,
+    secret_manager: &Arc<dyn SecretManager>,
+    #[cfg(feature = "biome-user-admin")] user_store: &dyn UserStore,
+) -> Result<String, HttpResponse> {
+    let validation = default_validation(&rest_config.issuer());
+    match authorize_user(request, secret_manager, &validation) {
+        AuthorizationResult::Authorized(claims) => {
+            #[cfg(not(feature = "biome-user-admin"))]
+            let caller_is_admin = rest_config.is_admin(&claims.user_id());
+            #[cfg(feature = "biome-user-admin")]
+            let caller_is_admin = is_admin(user_store, rest_config, &claims.user_id())?;
+            if !caller_is_admin {
+                return Err(HttpResponse::Forbidden().json(ErrorResponse::forbidden(
+                    "Only administrators may manage account lockouts",
+                )));
+            }
+        }
+        AuthorizationResult::Unauthorized(msg) => {
+            return Err(HttpResponse::Unauthorized().json(ErrorResponse::unauthorized(&msg)))
+        }
+        AuthorizationResult::Failed => {
+            return Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+    }
+
+    match request.match_info().get("id") {
+        Some(user_id) => Ok(user_id.to_string()),
+        None => {
+            error!("User ID is not in path request");
+            Err(HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                &"Failed to process request: no user ID".to_string(),
+            )))
+        }
+    }
+}
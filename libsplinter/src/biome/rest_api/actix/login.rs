@@ -15,14 +15,24 @@
 use std::sync::Arc;
 
 use crate::actix_web::HttpResponse;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::biome::refresh_tokens::store::RefreshTokenStore;
 use crate::futures::{Future, IntoFuture};
 use crate::protocol;
 use crate::rest_api::{into_bytes, ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
 
+#[cfg(feature = "biome-login-lockout")]
+use super::lockout::{check_lockout, clear_failed_logins, record_failed_login};
+#[cfg(feature = "biome-mfa")]
+use super::mfa::check_second_factor;
+#[cfg(feature = "biome-user-admin")]
+use super::user_admin::{check_account_enabled, with_role_claims};
 use crate::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
 use crate::biome::rest_api::resources::credentials::UsernamePassword;
 use crate::biome::rest_api::BiomeRestConfig;
+#[cfg(feature = "biome-user-admin")]
+use crate::biome::user::store::UserStore;
 use crate::rest_api::sessions::{AccessTokenIssuer, ClaimsBuilder, TokenIssuer};
 
 /// Defines a REST endpoint for login
@@ -31,14 +41,23 @@ use crate::rest_api::sessions::{AccessTokenIssuer, ClaimsBuilder, TokenIssuer};
 ///   {
 ///       "username": <existing username of the user>
 ///       "hashed_password": <hash of the user's existing password>
+///       "totp_code": <optional TOTP code, required if the user is enrolled in MFA>
+///       "recovery_code": <optional recovery code, used in place of a TOTP code>
 ///   }
+///
+/// Users whose accounts have been disabled may not log in. The access token includes the user's
+/// roles, if any have been assigned.
 pub fn make_login_route(
     credentials_store: Arc<dyn CredentialsStore>,
     refresh_token_store: Arc<dyn RefreshTokenStore>,
     rest_config: Arc<BiomeRestConfig>,
     token_issuer: Arc<AccessTokenIssuer>,
+    #[cfg(feature = "biome-user-admin")] user_store: Arc<dyn UserStore>,
 ) -> Resource {
-    Resource::build("/biome/login")
+    let resource = Resource::build("/biome/login");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::BIOME_LOGIN_PROTOCOL_MIN,
             protocol::BIOME_PROTOCOL_VERSION,
@@ -48,6 +67,8 @@ pub fn make_login_route(
             let rest_config = rest_config.clone();
             let token_issuer = token_issuer.clone();
             let refresh_token_store = refresh_token_store.clone();
+            #[cfg(feature = "biome-user-admin")]
+            let user_store = user_store.clone();
             Box::new(into_bytes(payload).and_then(move |bytes| {
                 let username_password = match serde_json::from_slice::<UsernamePassword>(&bytes) {
                     Ok(val) => val,
@@ -86,10 +107,59 @@ pub fn make_login_route(
                     }
                 };
 
+                #[cfg(feature = "biome-login-lockout")]
+                {
+                    if let Err(response) =
+                        check_lockout(&*credentials_store, &rest_config, &credentials.user_id)
+                    {
+                        return response.into_future();
+                    }
+                }
+
                 match credentials.verify_password(&username_password.hashed_password) {
                     Ok(is_valid) => {
                         if is_valid {
+                            #[cfg(feature = "biome-user-admin")]
+                            {
+                                if let Err(response) =
+                                    check_account_enabled(&*user_store, &credentials.user_id)
+                                {
+                                    return response.into_future();
+                                }
+                            }
+
+                            #[cfg(feature = "biome-mfa")]
+                            {
+                                if let Err(response) = check_second_factor(
+                                    &*credentials_store,
+                                    &rest_config,
+                                    &credentials.user_id,
+                                    username_password.totp_code.as_deref(),
+                                    username_password.recovery_code.as_deref(),
+                                ) {
+                                    return response.into_future();
+                                }
+                            }
+
+                            #[cfg(feature = "biome-login-lockout")]
+                            {
+                                if let Err(response) =
+                                    clear_failed_logins(&*credentials_store, &credentials.user_id)
+                                {
+                                    return response.into_future();
+                                }
+                            }
+
                             let claim_builder = ClaimsBuilder::default();
+                            #[cfg(feature = "biome-user-admin")]
+                            let claim_builder = match with_role_claims(
+                                claim_builder,
+                                &*user_store,
+                                &credentials.user_id,
+                            ) {
+                                Ok(claim_builder) => claim_builder,
+                                Err(response) => return response.into_future(),
+                            };
                             let claim = match claim_builder
                                 .with_user_id(&credentials.user_id)
                                 .with_issuer(&rest_config.issuer())
@@ -160,6 +230,17 @@ pub fn make_login_route(
                                 }))
                                 .into_future()
                         } else {
+                            #[cfg(feature = "biome-login-lockout")]
+                            {
+                                if let Err(response) = record_failed_login(
+                                    &*credentials_store,
+                                    &rest_config,
+                                    &credentials.user_id,
+                                ) {
+                                    return response.into_future();
+                                }
+                            }
+
                             HttpResponse::BadRequest()
                                 .json(ErrorResponse::bad_request("Invalid password"))
                                 .into_future()
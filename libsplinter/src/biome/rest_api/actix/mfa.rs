@@ -0,0 +1,568 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `POST /biome/mfa/totp` for enrolling a user in TOTP multi-factor authentication
+//! * `POST /biome/mfa/totp/verify` for confirming a TOTP enrolment
+//! * `DELETE /biome/mfa/totp` for removing a user's TOTP enrolment
+//!
+//! It also provides the second-factor check performed by the login endpoint. If the
+//! `biome-login-lockout` feature is enabled, wrong passwords and codes given to these endpoints
+//! count as failed login attempts, and locked accounts are rejected.
+
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::authorize::authorize_user;
+#[cfg(feature = "biome-login-lockout")]
+use super::lockout::{check_lockout, record_failed_login};
+use crate::actix_web::HttpResponse;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::biome::credentials::mfa::{
+    generate_recovery_codes, hash_recovery_code, verify_recovery_code, TotpCredentials, TotpSecret,
+};
+use crate::biome::credentials::store::{Credentials, CredentialsStore, CredentialsStoreError};
+use crate::biome::rest_api::resources::authorize::AuthorizationResult;
+use crate::biome::rest_api::resources::mfa::{
+    TotpConfirmationRequest, TotpEnrolmentRequest, TotpEnrolmentResponse, TotpRemovalRequest,
+};
+use crate::biome::rest_api::BiomeRestConfig;
+use crate::futures::{Future, IntoFuture};
+use crate::protocol;
+use crate::rest_api::{
+    into_bytes, secrets::SecretManager, sessions::default_validation, ErrorResponse,
+    HandlerFunction, Method, ProtocolVersionRangeGuard, Resource,
+};
+
+/// Defines a REST endpoint for enrolling in and removing TOTP multi-factor authentication
+///
+/// The `POST` payload should be in the JSON format:
+///   {
+///       "username": <existing username of the user>
+///       "hashed_password": <hash of the user's existing password>
+///   }
+///
+/// The `DELETE` method requires the user's access token and a payload in the JSON format:
+///   {
+///       "code": <current TOTP code or an unused recovery code>
+///   }
+pub fn make_totp_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> Resource {
+    let resource = Resource::build("/biome/mfa/totp");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_method_permission(Method::Post, Permission::AllowUnauthenticated);
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_MFA_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(
+            Method::Post,
+            handle_enrol(credentials_store.clone(), rest_config.clone()),
+        )
+        .add_method(
+            Method::Delete,
+            handle_remove(credentials_store, rest_config, secret_manager),
+        )
+}
+
+/// Defines a REST endpoint for confirming a TOTP enrolment
+///
+/// The payload should be in the JSON format:
+///   {
+///       "username": <existing username of the user>
+///       "hashed_password": <hash of the user's existing password>
+///       "code": <current TOTP code from the user's authenticator>
+///   }
+pub fn make_totp_verify_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+) -> Resource {
+    let resource = Resource::build("/biome/mfa/totp/verify");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_MFA_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Post, move |_, payload| {
+            let credentials_store = credentials_store.clone();
+            let rest_config = rest_config.clone();
+            Box::new(into_bytes(payload).and_then(move |bytes| {
+                let request = match serde_json::from_slice::<TotpConfirmationRequest>(&bytes) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        debug!("Error parsing payload {}", err);
+                        return HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Failed to parse payload: {}",
+                                err
+                            )))
+                            .into_future();
+                    }
+                };
+
+                let credentials = match authenticate(
+                    &*credentials_store,
+                    #[cfg(feature = "biome-login-lockout")]
+                    &rest_config,
+                    &request.username,
+                    &request.hashed_password,
+                ) {
+                    Ok(credentials) => credentials,
+                    Err(response) => return response.into_future(),
+                };
+
+                let totp = match credentials_store.fetch_totp(&credentials.user_id) {
+                    Ok(totp) => totp,
+                    Err(CredentialsStoreError::NotFoundError(_)) => {
+                        return HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(
+                                "User is not enrolled in TOTP authentication",
+                            ))
+                            .into_future()
+                    }
+                    Err(err) => {
+                        error!("Failed to fetch TOTP enrolment: {}", err);
+                        return HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future();
+                    }
+                };
+
+                match verify_totp_code(&*credentials_store, &totp, &rest_config, &request.code) {
+                    Ok(true) => (),
+                    Ok(false) => {
+                        #[cfg(feature = "biome-login-lockout")]
+                        {
+                            if let Err(response) = record_failed_login(
+                                &*credentials_store,
+                                &rest_config,
+                                &credentials.user_id,
+                            ) {
+                                return response.into_future();
+                            }
+                        }
+
+                        return HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("Invalid TOTP code"))
+                            .into_future();
+                    }
+                    Err(response) => return response.into_future(),
+                }
+
+                match credentials_store.confirm_totp(&credentials.user_id) {
+                    Ok(()) => HttpResponse::Ok()
+                        .json(json!({ "message": "TOTP enrolment confirmed" }))
+                        .into_future(),
+                    Err(err) => {
+                        error!("Failed to confirm TOTP enrolment: {}", err);
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future()
+                    }
+                }
+            }))
+        })
+}
+
+/// Enrols a user in TOTP authentication, replacing any unconfirmed enrolment. The enrolment
+/// does not take effect until it has been confirmed.
+fn handle_enrol(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+) -> HandlerFunction {
+    Box::new(move |_, payload| {
+        let credentials_store = credentials_store.clone();
+        let rest_config = rest_config.clone();
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let request = match serde_json::from_slice::<TotpEnrolmentRequest>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            let credentials = match authenticate(
+                &*credentials_store,
+                #[cfg(feature = "biome-login-lockout")]
+                &rest_config,
+                &request.username,
+                &request.hashed_password,
+            ) {
+                Ok(credentials) => credentials,
+                Err(response) => return response.into_future(),
+            };
+
+            match credentials_store.fetch_totp(&credentials.user_id) {
+                Ok(totp) if totp.confirmed => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "User is already enrolled in TOTP authentication",
+                        ))
+                        .into_future()
+                }
+                Ok(_) | Err(CredentialsStoreError::NotFoundError(_)) => (),
+                Err(err) => {
+                    error!("Failed to fetch TOTP enrolment: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            }
+
+            let key = match rest_config.mfa_encryption_key() {
+                Some(key) => key,
+                None => {
+                    error!("No MFA encryption key is configured");
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            };
+
+            let secret = TotpSecret::generate();
+            let recovery_codes = generate_recovery_codes();
+            let totp = match secret.encrypt(key).and_then(|encrypted_secret| {
+                Ok(TotpCredentials {
+                    user_id: credentials.user_id.clone(),
+                    encrypted_secret,
+                    confirmed: false,
+                    last_used_step: None,
+                    recovery_codes: recovery_codes
+                        .iter()
+                        .map(|code| {
+                            hash_recovery_code(code, rest_config.password_encryption_cost())
+                        })
+                        .collect::<Result<_, _>>()?,
+                })
+            }) {
+                Ok(totp) => totp,
+                Err(err) => {
+                    error!("Failed to create TOTP enrolment: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            };
+
+            if let Err(err) = credentials_store.set_totp(totp) {
+                error!("Failed to store TOTP enrolment: {}", err);
+                return HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future();
+            }
+
+            HttpResponse::Ok()
+                .json(TotpEnrolmentResponse {
+                    secret: secret.to_base32(),
+                    provisioning_uri: secret
+                        .provisioning_uri(&rest_config.issuer(), &credentials.username),
+                    recovery_codes: &recovery_codes,
+                })
+                .into_future()
+        }))
+    })
+}
+
+/// Removes the authorized user's TOTP enrolment after checking the given TOTP or recovery code.
+fn handle_remove(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, payload| {
+        let credentials_store = credentials_store.clone();
+        let rest_config = rest_config.clone();
+        let validation = default_validation(&rest_config.issuer());
+        let user_id = match authorize_user(&request, &secret_manager, &validation) {
+            AuthorizationResult::Authorized(claims) => claims.user_id(),
+            AuthorizationResult::Unauthorized(msg) => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized(&msg))
+                        .into_future(),
+                )
+            }
+            AuthorizationResult::Failed => {
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let request = match serde_json::from_slice::<TotpRemovalRequest>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            #[cfg(feature = "biome-login-lockout")]
+            {
+                if let Err(response) = check_lockout(&*credentials_store, &rest_config, &user_id) {
+                    return response.into_future();
+                }
+            }
+
+            let totp = match credentials_store.fetch_totp(&user_id) {
+                Ok(totp) => totp,
+                Err(CredentialsStoreError::NotFoundError(_)) => {
+                    return HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found(
+                            "User is not enrolled in TOTP authentication",
+                        ))
+                        .into_future()
+                }
+                Err(err) => {
+                    error!("Failed to fetch TOTP enrolment: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            };
+
+            let is_valid =
+                match verify_totp_code(&*credentials_store, &totp, &rest_config, &request.code) {
+                    Ok(true) => Ok(true),
+                    Ok(false) => consume_recovery_code(&*credentials_store, &totp, &request.code),
+                    Err(response) => Err(response),
+                };
+            match is_valid {
+                Ok(true) => (),
+                Ok(false) => {
+                    #[cfg(feature = "biome-login-lockout")]
+                    {
+                        if let Err(response) =
+                            record_failed_login(&*credentials_store, &rest_config, &user_id)
+                        {
+                            return response.into_future();
+                        }
+                    }
+
+                    return HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized(
+                            "Invalid multi-factor authentication code",
+                        ))
+                        .into_future();
+                }
+                Err(response) => return response.into_future(),
+            }
+
+            match credentials_store.remove_totp(&user_id) {
+                Ok(()) => HttpResponse::Ok()
+                    .json(json!({ "message": "TOTP enrolment removed" }))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to remove TOTP enrolment: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            }
+        }))
+    })
+}
+
+/// Checks the second authentication factor of a user whose password has been verified.
+///
+/// Users with a confirmed TOTP enrolment must provide either a valid TOTP code or an unused
+/// recovery code; a recovery code is consumed when it is used. An invalid code is recorded as a
+/// failed login attempt. Users without a confirmed enrolment are only rejected if the
+/// configuration requires multi-factor authentication.
+pub(super) fn check_second_factor(
+    credentials_store: &dyn CredentialsStore,
+    rest_config: &BiomeRestConfig,
+    user_id: &str,
+    totp_code: Option<&str>,
+    recovery_code: Option<&str>,
+) -> Result<(), HttpResponse> {
+    let totp = match credentials_store.fetch_totp(user_id) {
+        Ok(totp) if totp.confirmed => totp,
+        Ok(_) | Err(CredentialsStoreError::NotFoundError(_)) => {
+            if rest_config.mfa_required() {
+                return Err(
+                    HttpResponse::Unauthorized().json(ErrorResponse::unauthorized(
+                        "Multi-factor authentication enrolment required",
+                    )),
+                );
+            }
+            return Ok(());
+        }
+        Err(err) => {
+            error!("Failed to fetch TOTP enrolment: {}", err);
+            return Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()));
+        }
+    };
+
+    let is_valid = match (totp_code, recovery_code) {
+        (Some(code), _) => verify_totp_code(credentials_store, &totp, rest_config, code)?,
+        (None, Some(code)) => consume_recovery_code(credentials_store, &totp, code)?,
+        (None, None) => {
+            return Err(
+                HttpResponse::Unauthorized().json(ErrorResponse::unauthorized(
+                    "Multi-factor authentication code required",
+                )),
+            )
+        }
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        #[cfg(feature = "biome-login-lockout")]
+        record_failed_login(credentials_store, rest_config, user_id)?;
+
+        Err(
+            HttpResponse::Unauthorized().json(ErrorResponse::unauthorized(
+                "Invalid multi-factor authentication code",
+            )),
+        )
+    }
+}
+
+/// Fetches a user's credentials by username and verifies their password. Locked accounts are
+/// rejected, and a wrong password is recorded as a failed login attempt.
+fn authenticate(
+    credentials_store: &dyn CredentialsStore,
+    #[cfg(feature = "biome-login-lockout")] rest_config: &BiomeRestConfig,
+    username: &str,
+    hashed_password: &str,
+) -> Result<Credentials, HttpResponse> {
+    let credentials = match credentials_store.fetch_credential_by_username(username) {
+        Ok(credentials) => credentials,
+        Err(CredentialsStoreError::NotFoundError(_)) => {
+            return Err(
+                HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+                    "Username not found: {}",
+                    username
+                ))),
+            )
+        }
+        Err(err) => {
+            debug!("Failed to fetch credentials {}", err);
+            return Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()));
+        }
+    };
+
+    #[cfg(feature = "biome-login-lockout")]
+    check_lockout(credentials_store, rest_config, &credentials.user_id)?;
+
+    match credentials.verify_password(hashed_password) {
+        Ok(true) => Ok(credentials),
+        Ok(false) => {
+            #[cfg(feature = "biome-login-lockout")]
+            record_failed_login(credentials_store, rest_config, &credentials.user_id)?;
+
+            Err(HttpResponse::BadRequest().json(ErrorResponse::bad_request("Invalid password")))
+        }
+        Err(err) => {
+            debug!("Failed to verify password {}", err);
+            Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+    }
+}
+
+/// Checks a TOTP code against the user's decrypted secret and records its time step, so that
+/// neither the code nor any earlier code can be used again.
+fn verify_totp_code(
+    credentials_store: &dyn CredentialsStore,
+    totp: &TotpCredentials,
+    rest_config: &BiomeRestConfig,
+    code: &str,
+) -> Result<bool, HttpResponse> {
+    let key = rest_config.mfa_encryption_key().ok_or_else(|| {
+        error!(
+            "User {} is enrolled in TOTP authentication, but no MFA encryption key is configured",
+            totp.user_id
+        );
+        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+    })?;
+
+    let step = TotpSecret::decrypt(&totp.encrypted_secret, key)
+        .and_then(|secret| secret.verify_step(code, SystemTime::now()))
+        .map_err(|err| {
+            error!("Failed to verify TOTP code: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })?;
+
+    match step {
+        Some(step) if totp.last_used_step.map_or(true, |last| step > last) => {
+            // The store only records the step if it is still later than the last used step, so
+            // a code used by a concurrent request is rejected here
+            match credentials_store.record_totp_step(&totp.user_id, step) {
+                Ok(recorded) => Ok(recorded),
+                Err(CredentialsStoreError::NotFoundError(_)) => Ok(false),
+                Err(err) => {
+                    error!("Failed to record TOTP step: {}", err);
+                    Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            }
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Checks a recovery code against the user's unused recovery codes, removing it if it matches.
+fn consume_recovery_code(
+    credentials_store: &dyn CredentialsStore,
+    totp: &TotpCredentials,
+    code: &str,
+) -> Result<bool, HttpResponse> {
+    for code_hash in &totp.recovery_codes {
+        match verify_recovery_code(code, code_hash) {
+            Ok(true) => {
+                // If a concurrent request has already used the code, it is no longer valid
+                return match credentials_store.remove_recovery_code(&totp.user_id, code_hash) {
+                    Ok(()) => Ok(true),
+                    Err(CredentialsStoreError::NotFoundError(_)) => Ok(false),
+                    Err(err) => {
+                        error!("Failed to remove recovery code: {}", err);
+                        Err(HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error()))
+                    }
+                };
+            }
+            Ok(false) => (),
+            Err(err) => {
+                error!("Failed to verify recovery code: {}", err);
+                return Err(
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                );
+            }
+        }
+    }
+    Ok(false)
+}
@@ -12,19 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-#[cfg(any(feature = "biome-key-management", feature = "biome-credentials"))]
+#[cfg(any(
+    feature = "biome-credentials",
+    feature = "biome-key-management",
+    feature = "biome-notifications"
+))]
 pub(crate) mod authorize;
 #[cfg(feature = "biome-key-management")]
 pub(super) mod key_management;
+#[cfg(feature = "biome-login-lockout")]
+pub(super) mod lockout;
 #[cfg(feature = "biome-credentials")]
 pub(super) mod login;
 #[cfg(feature = "biome-credentials")]
 pub(super) mod logout;
+#[cfg(feature = "biome-mfa")]
+pub(super) mod mfa;
+#[cfg(feature = "biome-notifications")]
+pub(super) mod notifications;
+#[cfg(feature = "biome-password-reset")]
+pub(super) mod password_reset;
 #[cfg(feature = "biome-credentials")]
 pub(super) mod register;
 #[cfg(feature = "biome-credentials")]
 pub(super) mod token;
 #[cfg(feature = "biome-credentials")]
 pub(super) mod user;
+#[cfg(feature = "biome-user-admin")]
+pub(super) mod user_admin;
 #[cfg(feature = "biome-credentials")]
 pub(super) mod verify;
@@ -0,0 +1,396 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /biome/users/{id}/notifications` for listing a user's notifications
+//! * `GET /biome/users/{id}/notifications/subscribe` for opening a websocket that receives the
+//!   user's new notifications
+//! * `GET /biome/users/{id}/notifications/{notification_id}` for fetching a notification
+//! * `PATCH /biome/users/{id}/notifications/{notification_id}` for marking a notification as read
+//! * `DELETE /biome/users/{id}/notifications/{notification_id}` for removing a notification
+
+use std::sync::Arc;
+
+use super::authorize::authorize_user;
+use crate::actix_web::{HttpRequest, HttpResponse};
+use crate::biome::notifications::{
+    store::{NotificationStore, NotificationStoreError},
+    Notification, NotificationSubscriber, NotificationSubscriberError,
+};
+use crate::biome::rest_api::resources::authorize::AuthorizationResult;
+use crate::biome::rest_api::resources::notifications::NotificationResponse;
+use crate::biome::rest_api::BiomeRestConfig;
+use crate::futures::IntoFuture;
+use crate::protocol;
+use crate::rest_api::{
+    new_websocket_event_sender, EventSender, HandlerFunction, Method, ProtocolVersionRangeGuard,
+    Request, Resource,
+};
+use crate::rest_api::{secrets::SecretManager, sessions::default_validation, ErrorResponse};
+
+/// Defines a REST endpoint for listing a user's notifications
+pub fn make_notifications_route(
+    rest_config: Arc<BiomeRestConfig>,
+    notification_store: Arc<dyn NotificationStore>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> Resource {
+    Resource::build("/biome/users/{id}/notifications")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_NOTIFICATIONS_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(
+            Method::Get,
+            handle_list(rest_config, notification_store, secret_manager),
+        )
+}
+
+/// Defines a REST endpoint for fetching, marking as read, and removing one of a user's
+/// notifications
+pub fn make_notification_route(
+    rest_config: Arc<BiomeRestConfig>,
+    notification_store: Arc<dyn NotificationStore>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> Resource {
+    Resource::build("/biome/users/{id}/notifications/{notification_id}")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_NOTIFICATIONS_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(
+            Method::Get,
+            handle_fetch(
+                rest_config.clone(),
+                notification_store.clone(),
+                secret_manager.clone(),
+            ),
+        )
+        .add_method(
+            Method::Patch,
+            handle_mark_read(
+                rest_config.clone(),
+                notification_store.clone(),
+                secret_manager.clone(),
+            ),
+        )
+        .add_method(
+            Method::Delete,
+            handle_delete(rest_config, notification_store, secret_manager),
+        )
+}
+
+/// Defines a websocket endpoint that sends a user each notification they receive
+pub fn make_notifications_subscribe_route(
+    rest_config: Arc<BiomeRestConfig>,
+    notification_store: Arc<dyn NotificationStore>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> Resource {
+    Resource::build("/biome/users/{id}/notifications/subscribe")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_NOTIFICATIONS_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, move |request, payload| {
+            let user_id = match authorize_path_user(&request, &rest_config, &secret_manager) {
+                Ok(user_id) => user_id,
+                Err(response) => return Box::new(response.into_future()),
+            };
+
+            let request = Request::from((request, payload));
+            match new_websocket_event_sender(request, Box::new(std::iter::empty())) {
+                Ok((sender, res)) => {
+                    if let Err(err) = notification_store
+                        .add_subscriber(Box::new(WsNotificationSubscriber { user_id, sender }))
+                    {
+                        error!("Unable to add notification subscriber: {}", err);
+                        return Box::new(
+                            HttpResponse::InternalServerError()
+                                .json(ErrorResponse::internal_error())
+                                .into_future(),
+                        );
+                    }
+                    debug!("Websocket response: {:?}", res);
+                    Box::new(res.into_future())
+                }
+                Err(err) => {
+                    debug!("Failed to create websocket: {:?}", err);
+                    Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    )
+                }
+            }
+        })
+}
+
+/// Sends the notifications received by a user over a websocket.
+struct WsNotificationSubscriber {
+    user_id: String,
+    sender: EventSender<NotificationResponse>,
+}
+
+impl NotificationSubscriber for WsNotificationSubscriber {
+    fn handle_notification(
+        &self,
+        notification: &Notification,
+    ) -> Result<(), NotificationSubscriberError> {
+        if !notification.recipients.contains(&self.user_id) {
+            return Ok(());
+        }
+
+        self.sender
+            .send(NotificationResponse::new_for_user(
+                notification,
+                &self.user_id,
+            ))
+            .map_err(|_| {
+                debug!("Dropping notification and unsubscribing due to websocket being closed");
+                NotificationSubscriberError::Unsubscribe
+            })
+    }
+}
+
+/// Defines a REST endpoint for listing a user's notifications, newest first
+fn handle_list(
+    rest_config: Arc<BiomeRestConfig>,
+    notification_store: Arc<dyn NotificationStore>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match authorize_path_user(&request, &rest_config, &secret_manager) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+
+        match notification_store.list_notifications(&user_id) {
+            Ok(notifications) => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({
+                        "data": notifications
+                            .into_iter()
+                            .map(NotificationResponse::from)
+                            .collect::<Vec<_>>()
+                    }))
+                    .into_future(),
+            ),
+            Err(err) => {
+                error!("Failed to list notifications: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Defines a REST endpoint for fetching one of a user's notifications
+fn handle_fetch(
+    rest_config: Arc<BiomeRestConfig>,
+    notification_store: Arc<dyn NotificationStore>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match authorize_path_user(&request, &rest_config, &secret_manager) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+        let notification_id = match request.match_info().get("notification_id") {
+            Some(id) => id.to_owned(),
+            None => {
+                error!("Notification ID is not in path request");
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            &"Failed to process request: no notification ID".to_string(),
+                        ))
+                        .into_future(),
+                );
+            }
+        };
+
+        match notification_store.fetch_notification(&user_id, &notification_id) {
+            Ok(notification) => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({ "data": NotificationResponse::from(notification) }))
+                    .into_future(),
+            ),
+            Err(NotificationStoreError::NotFoundError(msg)) => {
+                debug!("Notification not found: {}", msg);
+                Box::new(
+                    HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found(&format!(
+                            "Notification {} not found",
+                            notification_id
+                        )))
+                        .into_future(),
+                )
+            }
+            Err(err) => {
+                error!("Failed to fetch notification: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Defines a REST endpoint for marking one of a user's notifications as read
+fn handle_mark_read(
+    rest_config: Arc<BiomeRestConfig>,
+    notification_store: Arc<dyn NotificationStore>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match authorize_path_user(&request, &rest_config, &secret_manager) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+        let notification_id = match request.match_info().get("notification_id") {
+            Some(id) => id.to_owned(),
+            None => {
+                error!("Notification ID is not in path request");
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            &"Failed to process request: no notification ID".to_string(),
+                        ))
+                        .into_future(),
+                );
+            }
+        };
+
+        match notification_store.mark_read(&user_id, &notification_id) {
+            Ok(()) => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({ "message": "Notification marked as read" }))
+                    .into_future(),
+            ),
+            Err(NotificationStoreError::NotFoundError(msg)) => {
+                debug!("Notification not found: {}", msg);
+                Box::new(
+                    HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found(&format!(
+                            "Notification {} not found",
+                            notification_id
+                        )))
+                        .into_future(),
+                )
+            }
+            Err(err) => {
+                error!("Failed to mark as read notification: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Defines a REST endpoint for removing one of a user's notifications
+fn handle_delete(
+    rest_config: Arc<BiomeRestConfig>,
+    notification_store: Arc<dyn NotificationStore>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match authorize_path_user(&request, &rest_config, &secret_manager) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+        let notification_id = match request.match_info().get("notification_id") {
+            Some(id) => id.to_owned(),
+            None => {
+                error!("Notification ID is not in path request");
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            &"Failed to process request: no notification ID".to_string(),
+                        ))
+                        .into_future(),
+                );
+            }
+        };
+
+        match notification_store.remove_notification(&user_id, &notification_id) {
+            Ok(()) => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({ "message": "Notification removed" }))
+                    .into_future(),
+            ),
+            Err(NotificationStoreError::NotFoundError(msg)) => {
+                debug!("Notification not found: {}", msg);
+                Box::new(
+                    HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found(&format!(
+                            "Notification {} not found",
+                            notification_id
+                        )))
+                        .into_future(),
+                )
+            }
+            Err(err) => {
+                error!("Failed to remove notification: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Authorizes the request and checks that the authorized user is the user in the request's path.
+/// On success, returns the user's ID; otherwise returns the response to send.
+fn authorize_path_user(
+    request: &HttpRequest,
+    rest_config: &BiomeRestConfig,
+    secret_manager: &Arc<dyn SecretManager>,
+) -> Result<String, HttpResponse> {
+    let validation = default_validation(&rest_config.issuer());
+    let authorized_user_id = match authorize_user(request, secret_manager, &validation) {
+        AuthorizationResult::Authorized(claims) => claims.user_id(),
+        AuthorizationResult::Unauthorized(msg) => {
+            return Err(HttpResponse::Unauthorized().json(ErrorResponse::unauthorized(&msg)))
+        }
+        AuthorizationResult::Failed => {
+            return Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+    };
+
+    match request.match_info().get("id") {
+        Some(user_id) if user_id == authorized_user_id => Ok(authorized_user_id),
+        Some(_) => Err(HttpResponse::Forbidden().json(ErrorResponse::forbidden(
+            "User is not permitted to access another user's notifications",
+        ))),
+        None => {
+            error!("User ID is not in path request");
+            Err(HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                &"Failed to process request: no user ID".to_string(),
+            )))
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `POST /biome/password/reset_request` for requesting a password reset token
+//! * `POST /biome/password/reset` for resetting a password with a password reset token
+
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::actix_web::HttpResponse;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::biome::credentials::reset::{
+    generate_reset_token, hash_reset_token, PasswordResetNotifier, PasswordResetToken,
+};
+use crate::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
+use crate::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
+use crate::biome::rest_api::resources::password_reset::{PasswordReset, PasswordResetRequest};
+use crate::biome::rest_api::BiomeRestConfig;
+use crate::futures::{Future, IntoFuture};
+use crate::protocol;
+use crate::rest_api::{into_bytes, ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
+
+/// Defines a REST endpoint for requesting a password reset token. The token is delivered to the
+/// user by the configured `PasswordResetNotifier`. The same response is returned whether or not
+/// the user exists.
+///
+/// The payload should be in the JSON format:
+///   {
+///       "username": <existing username of the user>
+///   }
+pub fn make_password_reset_request_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    notifier: Arc<dyn PasswordResetNotifier>,
+) -> Resource {
+    let resource = Resource::build("/biome/password/reset_request");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_PASSWORD_RESET_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Post, move |_, payload| {
+            let credentials_store = credentials_store.clone();
+            let rest_config = rest_config.clone();
+            let notifier = notifier.clone();
+            Box::new(into_bytes(payload).and_then(move |bytes| {
+                let request = match serde_json::from_slice::<PasswordResetRequest>(&bytes) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        debug!("Error parsing payload {}", err);
+                        return HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Failed to parse payload: {}",
+                                err
+                            )))
+                            .into_future();
+                    }
+                };
+
+                let response = HttpResponse::Ok()
+                    .json(json!({
+                        "message": "If the user exists, a password reset token has been sent"
+                    }))
+                    .into_future();
+
+                let credentials =
+                    match credentials_store.fetch_credential_by_username(&request.username) {
+                        Ok(credentials) => credentials,
+                        Err(CredentialsStoreError::NotFoundError(_)) => {
+                            debug!("Password reset requested for unknown user");
+                            return response;
+                        }
+                        Err(err) => {
+                            error!("Failed to fetch credentials: {}", err);
+                            return HttpResponse::InternalServerError()
+                                .json(ErrorResponse::internal_error())
+                                .into_future();
+                        }
+                    };
+
+                let token = generate_reset_token();
+                let expires = SystemTime::now() + rest_config.password_reset_token_duration();
+                if let Err(err) = credentials_store.add_password_reset_token(PasswordResetToken {
+                    user_id: credentials.user_id,
+                    token_hash: hash_reset_token(&token),
+                    expires,
+                }) {
+                    error!("Failed to store password reset token: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+
+                if let Err(err) = notifier.send_reset_token(&credentials.username, &token, expires)
+                {
+                    error!("Failed to send password reset token: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+
+                response
+            }))
+        })
+}
+
+/// Defines a REST endpoint for resetting a password with a password reset token. Each token can
+/// only be used once. A successful reset also revokes the user's refresh token.
+///
+/// The payload should be in the JSON format:
+///   {
+///       "token": <password reset token>
+///       "new_hashed_password": <hash of the user's new password>
+///   }
+pub fn make_password_reset_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeRestConfig>,
+) -> Resource {
+    let resource = Resource::build("/biome/password/reset");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_PASSWORD_RESET_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Post, move |_, payload| {
+            let credentials_store = credentials_store.clone();
+            let refresh_token_store = refresh_token_store.clone();
+            let rest_config = rest_config.clone();
+            Box::new(into_bytes(payload).and_then(move |bytes| {
+                let request = match serde_json::from_slice::<PasswordReset>(&bytes) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        debug!("Error parsing payload {}", err);
+                        return HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Failed to parse payload: {}",
+                                err
+                            )))
+                            .into_future();
+                    }
+                };
+
+                let token = match credentials_store
+                    .take_password_reset_token(&hash_reset_token(&request.token))
+                {
+                    Ok(token) => token,
+                    Err(CredentialsStoreError::NotFoundError(_)) => {
+                        return HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("Invalid password reset token"))
+                            .into_future()
+                    }
+                    Err(err) => {
+                        error!("Failed to take password reset token: {}", err);
+                        return HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future();
+                    }
+                };
+                if token.is_expired(SystemTime::now()) {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Password reset token has expired",
+                        ))
+                        .into_future();
+                }
+
+                let username = match credentials_store.fetch_username_by_id(&token.user_id) {
+                    Ok(username_id) => username_id.username,
+                    Err(err) => {
+                        error!("Failed to fetch username: {}", err);
+                        return HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future();
+                    }
+                };
+
+                if let Err(err) = credentials_store.update_credentials(
+                    &token.user_id,
+                    &username,
+                    &request.new_hashed_password,
+                    rest_config.password_encryption_cost(),
+                ) {
+                    error!("Failed to update credentials: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+
+                match refresh_token_store.remove_token(&token.user_id) {
+                    Ok(()) | Err(RefreshTokenError::NotFoundError(_)) => (),
+                    Err(err) => {
+                        error!("Failed to remove refresh token: {}", err);
+                        return HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future();
+                    }
+                }
+
+                #[cfg(feature = "biome-login-lockout")]
+                {
+                    if let Err(err) = credentials_store.clear_failed_logins(&token.user_id) {
+                        error!("Failed to clear failed logins: {}", err);
+                        return HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future();
+                    }
+                }
+
+                HttpResponse::Ok()
+                    .json(json!({ "message": "Password reset" }))
+                    .into_future()
+            }))
+        })
+}
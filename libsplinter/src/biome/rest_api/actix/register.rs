@@ -16,6 +16,8 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::actix_web::HttpResponse;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::biome::credentials::store::{
     CredentialsBuilder, CredentialsStore, CredentialsStoreError,
 };
@@ -38,7 +40,10 @@ pub fn make_register_route(
     user_store: Arc<dyn UserStore>,
     rest_config: Arc<BiomeRestConfig>,
 ) -> Resource {
-    Resource::build("/biome/register")
+    let resource = Resource::build("/biome/register");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::BIOME_REGISTER_PROTOCOL_MIN,
             protocol::BIOME_PROTOCOL_VERSION,
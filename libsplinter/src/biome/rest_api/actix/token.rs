@@ -14,7 +14,13 @@
 
 use std::sync::Arc;
 
+#[cfg(feature = "biome-user-admin")]
+use super::user_admin::{check_account_enabled, with_role_claims};
 use crate::actix_web::HttpResponse;
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+#[cfg(feature = "biome-user-admin")]
+use crate::biome::user::store::UserStore;
 use crate::biome::{
     refresh_tokens::store::{RefreshTokenError, RefreshTokenStore},
     rest_api::{
@@ -45,14 +51,20 @@ use crate::rest_api::{
 ///   {
 ///     "token": <new auth token>
 ///   }
+///
+/// No token is issued to a user whose account has been disabled.
 pub fn make_token_route(
     refresh_token_store: Arc<dyn RefreshTokenStore>,
     secret_manager: Arc<dyn SecretManager>,
     refresh_token_secret_manager: Arc<dyn SecretManager>,
     token_issuer: Arc<AccessTokenIssuer>,
     rest_config: Arc<BiomeRestConfig>,
+    #[cfg(feature = "biome-user-admin")] user_store: Arc<dyn UserStore>,
 ) -> Resource {
-    Resource::build("/biome/token")
+    let resource = Resource::build("/biome/token");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::BIOME_LOGIN_PROTOCOL_MIN,
             protocol::BIOME_PROTOCOL_VERSION,
@@ -65,6 +77,8 @@ pub fn make_token_route(
             let refresh_token_store = refresh_token_store.clone();
             let token_issuer = token_issuer.clone();
             let rest_config = rest_config.clone();
+            #[cfg(feature = "biome-user-admin")]
+            let user_store = user_store.clone();
             Box::new(into_bytes(payload).and_then(move |bytes| {
                 let claims = match authorize_user(&req, &secret_manager, &validation) {
                     AuthorizationResult::Authorized(claims) => claims,
@@ -139,7 +153,21 @@ pub fn make_token_route(
                             .into_future();
                     }
                 }
+
+                #[cfg(feature = "biome-user-admin")]
+                {
+                    if let Err(response) = check_account_enabled(&*user_store, &claims.user_id()) {
+                        return response.into_future();
+                    }
+                }
+
                 let claim_builder = ClaimsBuilder::default();
+                #[cfg(feature = "biome-user-admin")]
+                let claim_builder =
+                    match with_role_claims(claim_builder, &*user_store, &claims.user_id()) {
+                        Ok(claim_builder) => claim_builder,
+                        Err(response) => return response.into_future(),
+                    };
                 let claim = match claim_builder
                     .with_user_id(&claims.user_id())
                     .with_issuer(&rest_config.issuer())
@@ -36,10 +36,13 @@ use crate::biome::key_management::{
 use crate::rest_api::secrets::SecretManager;
 
 use crate::biome::rest_api::actix::authorize::authorize_user;
+#[cfg(feature = "biome-user-admin")]
+use crate::biome::rest_api::actix::user_admin::is_admin;
 #[cfg(feature = "biome-key-management")]
 use crate::biome::rest_api::resources::{key_management::ResponseKey, user::ModifyUser};
 
 /// Defines a REST endpoint to list users from the db
+#[cfg(not(feature = "biome-user-admin"))]
 pub fn make_list_route(credentials_store: Arc<dyn CredentialsStore>) -> Resource {
     Resource::build("/biome/users")
         .add_request_guard(ProtocolVersionRangeGuard::new(
@@ -290,7 +293,11 @@ fn add_modify_user_method(
     })
 }
 
-/// Defines a REST endpoint to delete a user from the database
+/// Defines a REST endpoint to delete a user from the database, along with the user's credentials,
+/// keys and refresh tokens
+///
+/// If the `biome-user-admin` feature is enabled, the user in the path is deleted; users may only
+/// delete themselves, unless they are an administrator. Otherwise the authorized user is deleted.
 fn add_delete_user_method(
     rest_config: Arc<BiomeRestConfig>,
     secret_manager: Arc<dyn SecretManager>,
@@ -317,6 +324,41 @@ fn add_delete_user_method(
             }
         };
 
+        #[cfg(feature = "biome-user-admin")]
+        let user_id = {
+            let caller_id = user_id;
+            let user_id = match request.match_info().get("id") {
+                Some(user_id) => user_id.to_string(),
+                None => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(
+                                &"Failed to process request: no user id".to_string(),
+                            ))
+                            .into_future(),
+                    )
+                }
+            };
+
+            if user_id != caller_id {
+                match is_admin(&*user_store, &rest_config, &caller_id) {
+                    Ok(true) => (),
+                    Ok(false) => {
+                        return Box::new(
+                            HttpResponse::Forbidden()
+                                .json(ErrorResponse::forbidden(
+                                    "Only administrators may delete other users",
+                                ))
+                                .into_future(),
+                        )
+                    }
+                    Err(response) => return Box::new(response.into_future()),
+                }
+            }
+
+            user_id
+        };
+
         Box::new(match user_store.remove_user(&user_id) {
             Ok(()) => HttpResponse::Ok()
                 .json(json!({ "message": "User deleted sucessfully" }))
@@ -0,0 +1,506 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /biome/users` for listing the users that the authorized user may see
+//! * `GET /biome/users/{id}/roles` for fetching the roles of a user
+//! * `PUT /biome/users/{id}/roles` for replacing the roles of a user; admin only
+//! * `GET /biome/users/{id}/status` for fetching whether a user's account is disabled
+//! * `PUT /biome/users/{id}/status` for disabling or re-enabling a user's account; admin only
+//!
+//! Users may view their own roles and status, while administrators may view those of any user.
+//! A user is an administrator if their ID is configured in the `BiomeRestConfig` or if they have
+//! been assigned the `admin` role. It also provides the checks and role claims used by the login
+//! and token endpoints.
+
+use std::sync::Arc;
+
+use super::authorize::authorize_user;
+use crate::actix_web::{HttpRequest, HttpResponse};
+use crate::biome::credentials::store::CredentialsStore;
+use crate::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
+use crate::biome::rest_api::resources::authorize::AuthorizationResult;
+use crate::biome::rest_api::resources::user_admin::{UserRoles, UserStatus};
+use crate::biome::rest_api::{BiomeRestConfig, ROLES_CLAIM};
+use crate::biome::user::store::{UserStore, UserStoreError, ADMIN_ROLE};
+use crate::futures::{Future, IntoFuture};
+use crate::protocol;
+use crate::rest_api::{
+    into_bytes,
+    secrets::SecretManager,
+    sessions::{default_validation, ClaimsBuilder},
+    ErrorResponse, HandlerFunction, Method, ProtocolVersionRangeGuard, Resource,
+};
+
+/// Defines a REST endpoint to list users. Administrators receive every user, while any other
+/// user only receives their own entry.
+pub fn make_list_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    user_store: Arc<dyn UserStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> Resource {
+    Resource::build("/biome/users")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_LIST_USERS_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, move |request, _| {
+            let (caller_id, caller_is_admin) =
+                match authorize_caller(&request, &rest_config, &secret_manager, &*user_store) {
+                    Ok(caller) => caller,
+                    Err(response) => return Box::new(response.into_future()),
+                };
+
+            Box::new(match credentials_store.list_usernames() {
+                Ok(users) => {
+                    let users = users
+                        .into_iter()
+                        .filter(|user| caller_is_admin || user.user_id == caller_id)
+                        .collect::<Vec<_>>();
+                    HttpResponse::Ok().json(users).into_future()
+                }
+                Err(err) => {
+                    debug!("Failed to get users from the database {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        })
+}
+
+/// Defines the `/biome/users/{id}/roles` REST resource for managing the roles of a user
+pub fn make_user_roles_route(
+    user_store: Arc<dyn UserStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> Resource {
+    Resource::build("/biome/users/{id}/roles")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_USER_ADMIN_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(
+            Method::Get,
+            handle_fetch_roles(
+                user_store.clone(),
+                rest_config.clone(),
+                secret_manager.clone(),
+            ),
+        )
+        .add_method(
+            Method::Put,
+            handle_set_roles(user_store, rest_config, secret_manager),
+        )
+}
+
+/// Defines the `/biome/users/{id}/status` REST resource for disabling and re-enabling the account
+/// of a user
+pub fn make_user_status_route(
+    user_store: Arc<dyn UserStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> Resource {
+    Resource::build("/biome/users/{id}/status")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::BIOME_USER_ADMIN_PROTOCOL_MIN,
+            protocol::BIOME_PROTOCOL_VERSION,
+        ))
+        .add_method(
+            Method::Get,
+            handle_fetch_status(
+                user_store.clone(),
+                rest_config.clone(),
+                secret_manager.clone(),
+            ),
+        )
+        .add_method(
+            Method::Put,
+            handle_set_status(user_store, refresh_token_store, rest_config, secret_manager),
+        )
+}
+
+/// Returns the roles of the user in the path
+fn handle_fetch_roles(
+    user_store: Arc<dyn UserStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id =
+            match authorize_self_or_admin(&request, &rest_config, &secret_manager, &*user_store) {
+                Ok(user_id) => user_id,
+                Err(response) => return Box::new(response.into_future()),
+            };
+
+        if let Err(response) = check_user_exists(&*user_store, &user_id) {
+            return Box::new(response.into_future());
+        }
+
+        Box::new(match user_store.fetch_roles(&user_id) {
+            Ok(roles) => HttpResponse::Ok()
+                .json(json!({
+                    "user_id": user_id,
+                    "roles": roles,
+                }))
+                .into_future(),
+            Err(err) => {
+                error!("Failed to fetch user roles: {}", err);
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future()
+            }
+        })
+    })
+}
+
+/// Replaces the roles of the user in the path
+///
+/// The payload should be in the JSON format:
+///   {
+///       "roles": [<role>, ...]
+///   }
+fn handle_set_roles(
+    user_store: Arc<dyn UserStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, payload| {
+        let user_store = user_store.clone();
+        let user_id = match authorize_admin(&request, &rest_config, &secret_manager, &*user_store) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let user_roles = match serde_json::from_slice::<UserRoles>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            // Roles are joined with commas in the role claim of access tokens
+            if user_roles
+                .roles
+                .iter()
+                .any(|role| role.is_empty() || role.contains(','))
+            {
+                return HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(
+                        "Roles must be non-empty and must not contain commas",
+                    ))
+                    .into_future();
+            }
+
+            match user_store.set_roles(&user_id, &user_roles.roles) {
+                Ok(()) => HttpResponse::Ok()
+                    .json(json!({ "message": "User roles updated successfully" }))
+                    .into_future(),
+                Err(UserStoreError::NotFoundError(_)) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found(&format!(
+                        "User ID not found: {}",
+                        user_id
+                    )))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to set user roles: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            }
+        }))
+    })
+}
+
+/// Returns whether or not the account of the user in the path is disabled
+fn handle_fetch_status(
+    user_store: Arc<dyn UserStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id =
+            match authorize_self_or_admin(&request, &rest_config, &secret_manager, &*user_store) {
+                Ok(user_id) => user_id,
+                Err(response) => return Box::new(response.into_future()),
+            };
+
+        if let Err(response) = check_user_exists(&*user_store, &user_id) {
+            return Box::new(response.into_future());
+        }
+
+        Box::new(match user_store.is_disabled(&user_id) {
+            Ok(disabled) => HttpResponse::Ok()
+                .json(json!({
+                    "user_id": user_id,
+                    "disabled": disabled,
+                }))
+                .into_future(),
+            Err(err) => {
+                error!("Failed to fetch user status: {}", err);
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future()
+            }
+        })
+    })
+}
+
+/// Disables or re-enables the account of the user in the path. Disabling an account also removes
+/// the user's refresh token, so that no new access tokens may be issued to the user.
+///
+/// The payload should be in the JSON format:
+///   {
+///       "disabled": <true or false>
+///   }
+fn handle_set_status(
+    user_store: Arc<dyn UserStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeRestConfig>,
+    secret_manager: Arc<dyn SecretManager>,
+) -> HandlerFunction {
+    Box::new(move |request, payload| {
+        let user_store = user_store.clone();
+        let refresh_token_store = refresh_token_store.clone();
+        let (caller_id, caller_is_admin) =
+            match authorize_caller(&request, &rest_config, &secret_manager, &*user_store) {
+                Ok(caller) => caller,
+                Err(response) => return Box::new(response.into_future()),
+            };
+        if !caller_is_admin {
+            return Box::new(
+                HttpResponse::Forbidden()
+                    .json(ErrorResponse::forbidden(
+                        "Only administrators may disable user accounts",
+                    ))
+                    .into_future(),
+            );
+        }
+        let user_id = match path_user_id(&request) {
+            Ok(user_id) => user_id,
+            Err(response) => return Box::new(response.into_future()),
+        };
+
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let user_status = match serde_json::from_slice::<UserStatus>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            if user_status.disabled && user_id == caller_id {
+                return HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(
+                        "Administrators may not disable their own account",
+                    ))
+                    .into_future();
+            }
+
+            match user_store.set_disabled(&user_id, user_status.disabled) {
+                Ok(()) => (),
+                Err(UserStoreError::NotFoundError(_)) => {
+                    return HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found(&format!(
+                            "User ID not found: {}",
+                            user_id
+                        )))
+                        .into_future()
+                }
+                Err(err) => {
+                    error!("Failed to set user status: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            }
+
+            if user_status.disabled {
+                match refresh_token_store.remove_token(&user_id) {
+                    Ok(()) | Err(RefreshTokenError::NotFoundError(_)) => (),
+                    Err(err) => {
+                        error!("Failed to remove refresh token: {}", err);
+                        return HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future();
+                    }
+                }
+            }
+
+            HttpResponse::Ok()
+                .json(json!({ "message": "User status updated successfully" }))
+                .into_future()
+        }))
+    })
+}
+
+/// Returns true if the given user is an administrator, either by configuration or by role.
+pub(super) fn is_admin(
+    user_store: &dyn UserStore,
+    rest_config: &BiomeRestConfig,
+    user_id: &str,
+) -> Result<bool, HttpResponse> {
+    if rest_config.is_admin(user_id) {
+        return Ok(true);
+    }
+
+    user_store
+        .fetch_roles(user_id)
+        .map(|roles| roles.iter().any(|role| role == ADMIN_ROLE))
+        .map_err(|err| {
+            error!("Failed to fetch user roles: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })
+}
+
+/// Checks that the account of the given user has not been disabled.
+pub(super) fn check_account_enabled(
+    user_store: &dyn UserStore,
+    user_id: &str,
+) -> Result<(), HttpResponse> {
+    match user_store.is_disabled(user_id) {
+        Ok(false) => Ok(()),
+        Ok(true) => Err(HttpResponse::Forbidden().json(ErrorResponse::forbidden(
+            "Account is disabled; contact an administrator",
+        ))),
+        Err(err) => {
+            error!("Failed to fetch user status: {}", err);
+            Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+    }
+}
+
+/// Adds the roles of the given user to the claims being built, so that other resources may
+/// authorize requests based on them. No claim is added if the user has no roles.
+pub(super) fn with_role_claims(
+    claims_builder: ClaimsBuilder,
+    user_store: &dyn UserStore,
+    user_id: &str,
+) -> Result<ClaimsBuilder, HttpResponse> {
+    let roles = user_store.fetch_roles(user_id).map_err(|err| {
+        error!("Failed to fetch user roles: {}", err);
+        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+    })?;
+
+    if roles.is_empty() {
+        Ok(claims_builder)
+    } else {
+        Ok(claims_builder.with_custom_claim(ROLES_CLAIM, &roles.join(",")))
+    }
+}
+
+/// Authorizes the request, returning the ID of the authorized user and whether or not they are
+/// an administrator.
+fn authorize_caller(
+    request: &HttpRequest,
+    rest_config: &BiomeRestConfig,
+    secret_manager: &Arc<dyn SecretManager>,
+    user_store: &dyn UserStore,
+) -> Result<(String, bool), HttpResponse> {
+    let validation = default_validation(&rest_config.issuer());
+    let caller_id = match authorize_user(request, secret_manager, &validation) {
+        AuthorizationResult::Authorized(claims) => claims.user_id(),
+        AuthorizationResult::Unauthorized(msg) => {
+            return Err(HttpResponse::Unauthorized().json(ErrorResponse::unauthorized(&msg)))
+        }
+        AuthorizationResult::Failed => {
+            return Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+    };
+
+    let caller_is_admin = is_admin(user_store, rest_config, &caller_id)?;
+    Ok((caller_id, caller_is_admin))
+}
+
+/// Checks that the request is authorized by an administrator, and returns the user ID in the
+/// path.
+fn authorize_admin(
+    request: &HttpRequest,
+    rest_config: &BiomeRestConfig,
+    secret_manager: &Arc<dyn SecretManager>,
+    user_store: &dyn UserStore,
+) -> Result<String, HttpResponse> {
+    let (_, caller_is_admin) = authorize_caller(request, rest_config, secret_manager, user_store)?;
+    if !caller_is_admin {
+        return Err(HttpResponse::Forbidden().json(ErrorResponse::forbidden(
+            "Only administrators may manage the roles of users",
+        )));
+    }
+
+    path_user_id(request)
+}
+
+/// Checks that the request is authorized by the user in the path or by an administrator, and
+/// returns the user ID in the path.
+fn authorize_self_or_admin(
+    request: &HttpRequest,
+    rest_config: &BiomeRestConfig,
+    secret_manager: &Arc<dyn SecretManager>,
+    user_store: &dyn UserStore,
+) -> Result<String, HttpResponse> {
+    let (caller_id, caller_is_admin) =
+        authorize_caller(request, rest_config, secret_manager, user_store)?;
+    let user_id = path_user_id(request)?;
+
+    if user_id != caller_id && !caller_is_admin {
+        return Err(HttpResponse::Forbidden().json(ErrorResponse::forbidden(
+            "Only administrators may view other users",
+        )));
+    }
+
+    Ok(user_id)
+}
+
+/// Returns a not found response if the given user does not exist.
+fn check_user_exists(user_store: &dyn UserStore, user_id: &str) -> Result<(), HttpResponse> {
+    match user_store.fetch_user(user_id) {
+        Ok(_) => Ok(()),
+        Err(UserStoreError::NotFoundError(_)) => Err(HttpResponse::NotFound().json(
+            ErrorResponse::not_found(&format!("User ID not found: {}", user_id)),
+        )),
+        Err(err) => {
+            error!("Failed to fetch user: {}", err);
+            Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+    }
+}
+
+fn path_user_id(request: &HttpRequest) -> Result<String, HttpResponse> {
+    match request.match_info().get("id") {
+        Some(user_id) => Ok(user_id.to_string()),
+        None => {
+            error!("User ID is not in path request");
+            Err(HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                &"Failed to process request: no user ID".to_string(),
+            )))
+        }
+    }
+}
@@ -15,6 +15,10 @@
 use std::time::Duration;
 
 use super::error::BiomeRestConfigBuilderError;
+#[cfg(feature = "biome-login-lockout")]
+use crate::biome::credentials::lockout::LockoutPolicy;
+#[cfg(feature = "biome-mfa")]
+use crate::biome::credentials::mfa::ENCRYPTION_KEY_LENGTH;
 #[cfg(feature = "biome-credentials")]
 use crate::biome::credentials::store::PasswordEncryptionCost;
 
@@ -22,6 +26,12 @@ const DEFAULT_ISSUER: &str = "self-issued";
 const DEFAULT_DURATION: u64 = 5400; // in seconds = 90 minutes
 #[cfg(feature = "biome-credentials")]
 const DEFAULT_REFRESH_DURATION: u64 = 5_184_000; // in seconds = 60 days
+#[cfg(feature = "biome-password-reset")]
+const DEFAULT_PASSWORD_RESET_DURATION: u64 = 3600; // in seconds = 1 hour
+#[cfg(feature = "biome-login-lockout")]
+const DEFAULT_MAX_FAILED_LOGINS: u32 = 5;
+#[cfg(feature = "biome-login-lockout")]
+const DEFAULT_LOCKOUT_DURATION: u64 = 900; // in seconds = 15 minutes
 
 /// Configuration for Biome REST resources
 #[derive(Deserialize, Debug)]
@@ -36,6 +46,21 @@ pub struct BiomeRestConfig {
     #[cfg(feature = "biome-credentials")]
     /// Cost for encrypting user's password
     password_encryption_cost: PasswordEncryptionCost,
+    /// Whether users must complete multi-factor authentication to log in
+    #[cfg(feature = "biome-mfa")]
+    mfa_required: bool,
+    /// Key used to encrypt users' TOTP secrets at rest
+    #[cfg(feature = "biome-mfa")]
+    mfa_encryption_key: Option<Vec<u8>>,
+    /// Duration of password reset tokens issued by this service
+    #[cfg(feature = "biome-password-reset")]
+    password_reset_token_duration: Duration,
+    /// Policy for locking accounts after failed login attempts
+    #[cfg(feature = "biome-login-lockout")]
+    lockout_policy: LockoutPolicy,
+    /// IDs of the users that may administer other users' accounts
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    admin_user_ids: Vec<String>,
 }
 
 impl BiomeRestConfig {
@@ -64,6 +89,41 @@ impl BiomeRestConfig {
     pub fn password_encryption_cost(&self) -> PasswordEncryptionCost {
         self.password_encryption_cost
     }
+
+    /// Returns whether users must have a confirmed multi-factor authentication enrolment in
+    /// order to log in. Defaults to false.
+    #[cfg(feature = "biome-mfa")]
+    pub fn mfa_required(&self) -> bool {
+        self.mfa_required
+    }
+
+    /// Returns the key used to encrypt TOTP secrets, if one was configured. Multi-factor
+    /// authentication endpoints are only available when a key is configured.
+    #[cfg(feature = "biome-mfa")]
+    pub fn mfa_encryption_key(&self) -> Option<&[u8]> {
+        self.mfa_encryption_key.as_deref()
+    }
+
+    /// Returns the duration that password reset tokens are valid.
+    /// Defaults to 1 hour.
+    #[cfg(feature = "biome-password-reset")]
+    pub fn password_reset_token_duration(&self) -> Duration {
+        self.password_reset_token_duration
+    }
+
+    /// Returns the policy for locking accounts after failed login attempts. Defaults to locking
+    /// an account for 15 minutes after 5 consecutive failures.
+    #[cfg(feature = "biome-login-lockout")]
+    pub fn lockout_policy(&self) -> &LockoutPolicy {
+        &self.lockout_policy
+    }
+
+    /// Returns true if the given user may administer other users' accounts, such as unlocking
+    /// them. Defaults to no administrators.
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    pub fn is_admin(&self, user_id: &str) -> bool {
+        self.admin_user_ids.iter().any(|admin| admin == user_id)
+    }
 }
 
 /// Builder for BiomeRestConfig
@@ -74,6 +134,18 @@ pub struct BiomeRestConfigBuilder {
     refresh_token_duration: Option<Duration>,
     #[cfg(feature = "biome-credentials")]
     password_encryption_cost: Option<String>,
+    #[cfg(feature = "biome-mfa")]
+    mfa_required: bool,
+    #[cfg(feature = "biome-mfa")]
+    mfa_encryption_key: Option<Vec<u8>>,
+    #[cfg(feature = "biome-password-reset")]
+    password_reset_token_duration: Option<Duration>,
+    #[cfg(feature = "biome-login-lockout")]
+    max_failed_logins: Option<u32>,
+    #[cfg(feature = "biome-login-lockout")]
+    lockout_duration: Option<Duration>,
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    admin_user_ids: Vec<String>,
 }
 
 impl Default for BiomeRestConfigBuilder {
@@ -85,6 +157,20 @@ impl Default for BiomeRestConfigBuilder {
             refresh_token_duration: Some(Duration::from_secs(DEFAULT_REFRESH_DURATION)),
             #[cfg(feature = "biome-credentials")]
             password_encryption_cost: Some("high".to_string()),
+            #[cfg(feature = "biome-mfa")]
+            mfa_required: false,
+            #[cfg(feature = "biome-mfa")]
+            mfa_encryption_key: None,
+            #[cfg(feature = "biome-password-reset")]
+            password_reset_token_duration: Some(Duration::from_secs(
+                DEFAULT_PASSWORD_RESET_DURATION,
+            )),
+            #[cfg(feature = "biome-login-lockout")]
+            max_failed_logins: Some(DEFAULT_MAX_FAILED_LOGINS),
+            #[cfg(feature = "biome-login-lockout")]
+            lockout_duration: Some(Duration::from_secs(DEFAULT_LOCKOUT_DURATION)),
+            #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+            admin_user_ids: vec![],
         }
     }
 }
@@ -99,6 +185,18 @@ impl BiomeRestConfigBuilder {
             refresh_token_duration: None,
             #[cfg(feature = "biome-credentials")]
             password_encryption_cost: None,
+            #[cfg(feature = "biome-mfa")]
+            mfa_required: false,
+            #[cfg(feature = "biome-mfa")]
+            mfa_encryption_key: None,
+            #[cfg(feature = "biome-password-reset")]
+            password_reset_token_duration: None,
+            #[cfg(feature = "biome-login-lockout")]
+            max_failed_logins: None,
+            #[cfg(feature = "biome-login-lockout")]
+            lockout_duration: None,
+            #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+            admin_user_ids: vec![],
         }
     }
 
@@ -129,6 +227,50 @@ impl BiomeRestConfigBuilder {
         self
     }
 
+    /// Sets whether users must complete multi-factor authentication to log in. If set, an
+    /// encryption key must also be provided.
+    #[cfg(feature = "biome-mfa")]
+    pub fn with_mfa_required(mut self, required: bool) -> Self {
+        self.mfa_required = required;
+        self
+    }
+
+    /// Adds the key used to encrypt TOTP secrets. The key must be 32 bytes long.
+    #[cfg(feature = "biome-mfa")]
+    pub fn with_mfa_encryption_key(mut self, key: &[u8]) -> Self {
+        self.mfa_encryption_key = Some(key.to_vec());
+        self
+    }
+
+    /// Adds a password reset token duration in seconds.
+    #[cfg(feature = "biome-password-reset")]
+    pub fn with_password_reset_token_duration_in_secs(mut self, duration: u64) -> Self {
+        self.password_reset_token_duration = Some(Duration::from_secs(duration));
+        self
+    }
+
+    /// Sets the number of consecutive failed login attempts that locks an account. Must be
+    /// greater than 0.
+    #[cfg(feature = "biome-login-lockout")]
+    pub fn with_max_failed_logins(mut self, max_failed_logins: u32) -> Self {
+        self.max_failed_logins = Some(max_failed_logins);
+        self
+    }
+
+    /// Sets how long, in seconds, an account stays locked after the last failed login attempt.
+    #[cfg(feature = "biome-login-lockout")]
+    pub fn with_lockout_duration_in_secs(mut self, duration: u64) -> Self {
+        self.lockout_duration = Some(Duration::from_secs(duration));
+        self
+    }
+
+    /// Sets the IDs of the users that may administer other users' accounts.
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    pub fn with_admin_user_ids(mut self, admin_user_ids: Vec<String>) -> Self {
+        self.admin_user_ids = admin_user_ids;
+        self
+    }
+
     /// Creates a new BiomeRestConfig.
     pub fn build(self) -> Result<BiomeRestConfig, BiomeRestConfigBuilderError> {
         let issuer = self.issuer.unwrap_or_else(|| {
@@ -152,6 +294,45 @@ impl BiomeRestConfigBuilder {
             .parse()
             .map_err(BiomeRestConfigBuilderError::InvalidValue)?;
 
+        #[cfg(feature = "biome-mfa")]
+        {
+            match self.mfa_encryption_key {
+                Some(ref key) if key.len() != ENCRYPTION_KEY_LENGTH => {
+                    return Err(BiomeRestConfigBuilderError::InvalidValue(format!(
+                        "MFA encryption key must be {} bytes long",
+                        ENCRYPTION_KEY_LENGTH
+                    )));
+                }
+                None if self.mfa_required => {
+                    return Err(BiomeRestConfigBuilderError::InvalidValue(
+                        "MFA encryption key is required when MFA is required".to_string(),
+                    ));
+                }
+                _ => (),
+            }
+        }
+
+        #[cfg(feature = "biome-password-reset")]
+        let password_reset_token_duration = self
+            .password_reset_token_duration
+            .unwrap_or_else(|| Duration::from_secs(DEFAULT_PASSWORD_RESET_DURATION));
+
+        #[cfg(feature = "biome-login-lockout")]
+        let lockout_policy = LockoutPolicy {
+            max_failed_attempts: match self.max_failed_logins {
+                Some(0) => {
+                    return Err(BiomeRestConfigBuilderError::InvalidValue(
+                        "max failed logins must be greater than 0".to_string(),
+                    ))
+                }
+                Some(max) => max,
+                None => DEFAULT_MAX_FAILED_LOGINS,
+            },
+            lockout_duration: self
+                .lockout_duration
+                .unwrap_or_else(|| Duration::from_secs(DEFAULT_LOCKOUT_DURATION)),
+        };
+
         Ok(BiomeRestConfig {
             issuer,
             access_token_duration,
@@ -159,6 +340,16 @@ impl BiomeRestConfigBuilder {
             refresh_token_duration,
             #[cfg(feature = "biome-credentials")]
             password_encryption_cost,
+            #[cfg(feature = "biome-mfa")]
+            mfa_required: self.mfa_required,
+            #[cfg(feature = "biome-mfa")]
+            mfa_encryption_key: self.mfa_encryption_key,
+            #[cfg(feature = "biome-password-reset")]
+            password_reset_token_duration,
+            #[cfg(feature = "biome-login-lockout")]
+            lockout_policy,
+            #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+            admin_user_ids: self.admin_user_ids,
         })
     }
 }
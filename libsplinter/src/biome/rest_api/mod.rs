@@ -57,34 +57,86 @@ use self::actix::key_management::{
 
 #[cfg(feature = "biome-key-management")]
 use super::key_management::store::KeyStore;
+#[cfg(feature = "biome-notifications")]
+use super::notifications::store::NotificationStore;
 use super::user::store::UserStore;
 
-#[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+#[cfg(any(
+    feature = "biome-credentials",
+    feature = "biome-key-management",
+    feature = "biome-notifications",
+))]
 use crate::rest_api::secrets::AutoSecretManager;
 use crate::rest_api::secrets::SecretManager;
 
+#[cfg(all(feature = "authorization", feature = "biome-credentials"))]
+use crate::auth::identity::BiomeUserIdentityProvider;
+
 pub use config::{BiomeRestConfig, BiomeRestConfigBuilder};
 pub use error::BiomeRestResourceManagerBuilderError;
 
+#[cfg(all(feature = "biome-login-lockout", feature = "rest-api-actix"))]
+use self::actix::lockout::make_lockout_route;
 #[cfg(all(feature = "rest-api-actix", feature = "biome-credentials"))]
 use self::actix::logout::make_logout_route;
+#[cfg(all(feature = "biome-mfa", feature = "rest-api-actix"))]
+use self::actix::mfa::{make_totp_route, make_totp_verify_route};
+#[cfg(all(feature = "biome-notifications", feature = "rest-api-actix"))]
+use self::actix::notifications::{
+    make_notification_route, make_notifications_route, make_notifications_subscribe_route,
+};
+#[cfg(all(feature = "biome-password-reset", feature = "rest-api-actix"))]
+use self::actix::password_reset::{make_password_reset_request_route, make_password_reset_route};
 #[cfg(all(feature = "biome-credentials", feature = "rest-api-actix"))]
 use self::actix::register::make_register_route;
 #[cfg(all(feature = "biome-credentials", feature = "rest-api-actix"))]
 use self::actix::token::make_token_route;
+#[cfg(all(
+    feature = "biome-credentials",
+    not(feature = "biome-user-admin"),
+    feature = "rest-api-actix",
+))]
+use self::actix::user::make_list_route;
 #[cfg(all(
     feature = "biome-credentials",
     feature = "biome-key-management",
     feature = "rest-api-actix",
 ))]
 use self::actix::user::make_user_routes;
+#[cfg(all(feature = "biome-user-admin", feature = "rest-api-actix"))]
+use self::actix::user_admin::{make_list_route, make_user_roles_route, make_user_status_route};
 #[cfg(all(feature = "biome-credentials", feature = "rest-api-actix",))]
-use self::actix::{login::make_login_route, user::make_list_route, verify::make_verify_route};
+use self::actix::{login::make_login_route, verify::make_verify_route};
+#[cfg(feature = "biome-password-reset")]
+use super::credentials::reset::PasswordResetNotifier;
 #[cfg(feature = "biome-credentials")]
 use super::credentials::store::CredentialsStore;
 
 #[allow(unused_imports)]
 use crate::rest_api::sessions::AccessTokenIssuer;
+#[cfg(feature = "biome-user-admin")]
+use crate::rest_api::sessions::Claims;
+
+/// The custom claim of biome access tokens that contains the comma-separated roles of the user
+#[cfg(feature = "biome-user-admin")]
+pub const ROLES_CLAIM: &str = "roles";
+
+/// Returns the roles in the role claim of a biome access token, so that resources may authorize
+/// requests based on the roles of the user.
+#[cfg(feature = "biome-user-admin")]
+pub fn roles_from_claims(claims: &Claims) -> Vec<String> {
+    claims
+        .custom_claims()
+        .get(ROLES_CLAIM)
+        .map(|roles| {
+            roles
+                .split(',')
+                .filter(|role| !role.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
 /// Provides the REST API endpoints for biome
 ///
@@ -100,22 +152,55 @@ use crate::rest_api::sessions::AccessTokenIssuer;
 ///    `public key`
 /// * `POST /biome/login` - Login enpoint for getting access tokens and refresh tokens
 /// * `PATCH /biome/logout` - Login endpoint for removing refresh tokens
+/// * `POST /biome/mfa/totp` - Enrol a user in TOTP multi-factor authentication
+/// * `DELETE /biome/mfa/totp` - Remove the authorized user's TOTP enrolment
+/// * `POST /biome/mfa/totp/verify` - Confirm a user's TOTP enrolment
+/// * `POST /biome/password/reset_request` - Send a password reset token to a user, if a
+///    `PasswordResetNotifier` has been provided
+/// * `POST /biome/password/reset` - Reset a user's password with a password reset token, if a
+///    `PasswordResetNotifier` has been provided
 /// * `POST /biome/register - Creates credentials for a user
 /// * `POST /biome/token` - Creates a new access token for the authorized user
 /// * `POST /biome/verify` - Verify a users password
 /// * `POST /biome/users` - Create new user
-/// * `GET /biome/user` - Get a list of all users in biome
+/// * `GET /biome/user` - Get a list of all users in biome; if the `biome-user-admin` feature is
+///    enabled, only administrators receive every user
 /// * `PUT /biome/user/{id}` - Update user with specified ID
 /// * `GET /biome/user/{id}` - Retrieve user with specified ID
 /// * `DELETE /biome/user/{id}` - Remove user with specified ID
+/// * `GET /biome/users/{id}/roles` - Retrieve the roles of a user
+/// * `PUT /biome/users/{id}/roles` - Replace the roles of a user; admin only
+/// * `GET /biome/users/{id}/status` - Retrieve whether a user's account is disabled
+/// * `PUT /biome/users/{id}/status` - Disable or re-enable a user's account; admin only
+/// * `GET /biome/users/{id}/lockout` - Retrieve the failed login attempts of a user; admin only
+/// * `DELETE /biome/users/{id}/lockout` - Unlock the account of a user; admin only
+/// * `GET /biome/users/{id}/notifications` - List the notifications of the authorized user
+/// * `GET /biome/users/{id}/notifications/subscribe` - Open a websocket that receives the
+///    authorized user's new notifications
+/// * `GET /biome/users/{id}/notifications/{notification_id}` - Retrieve a notification of the
+///    authorized user
+/// * `PATCH /biome/users/{id}/notifications/{notification_id}` - Mark a notification of the
+///    authorized user as read
+/// * `DELETE /biome/users/{id}/notifications/{notification_id}` - Remove a notification of the
+///    authorized user
 pub struct BiomeRestResourceManager {
     #[cfg(feature = "biome-credentials")]
     user_store: Arc<dyn UserStore>,
     #[cfg(feature = "biome-key-management")]
     key_store: Arc<dyn KeyStore>,
-    #[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+    #[cfg(feature = "biome-notifications")]
+    notification_store: Arc<dyn NotificationStore>,
+    #[cfg(any(
+        feature = "biome-credentials",
+        feature = "biome-key-management",
+        feature = "biome-notifications",
+    ))]
     rest_config: Arc<BiomeRestConfig>,
-    #[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+    #[cfg(any(
+        feature = "biome-credentials",
+        feature = "biome-key-management",
+        feature = "biome-notifications",
+    ))]
     token_secret_manager: Arc<dyn SecretManager>,
     #[cfg(feature = "biome-credentials")]
     refresh_token_secret_manager: Arc<dyn SecretManager>,
@@ -123,6 +208,20 @@ pub struct BiomeRestResourceManager {
     refresh_token_store: Arc<dyn RefreshTokenStore>,
     #[cfg(feature = "biome-credentials")]
     credentials_store: Arc<dyn CredentialsStore>,
+    #[cfg(feature = "biome-password-reset")]
+    password_reset_notifier: Option<Arc<dyn PasswordResetNotifier>>,
+}
+
+#[cfg(all(feature = "authorization", feature = "biome-credentials"))]
+impl BiomeRestResourceManager {
+    /// Returns an identity provider that identifies Biome users by the access tokens that these
+    /// resources issue
+    pub fn identity_provider(&self) -> BiomeUserIdentityProvider {
+        BiomeUserIdentityProvider::new(
+            self.token_secret_manager.clone(),
+            &self.rest_config.issuer(),
+        )
+    }
 }
 
 impl RestResourceProvider for BiomeRestResourceManager {
@@ -148,7 +247,15 @@ impl RestResourceProvider for BiomeRestResourceManager {
 
         #[cfg(all(feature = "biome-credentials", feature = "rest-api-actix",))]
         {
+            #[cfg(not(feature = "biome-user-admin"))]
             resources.push(make_list_route(self.credentials_store.clone()));
+            #[cfg(feature = "biome-user-admin")]
+            resources.push(make_list_route(
+                self.credentials_store.clone(),
+                self.user_store.clone(),
+                self.rest_config.clone(),
+                self.token_secret_manager.clone(),
+            ));
             resources.push(make_verify_route(
                 self.credentials_store.clone(),
                 self.rest_config.clone(),
@@ -162,6 +269,8 @@ impl RestResourceProvider for BiomeRestResourceManager {
                     self.token_secret_manager.clone(),
                     self.refresh_token_secret_manager.clone(),
                 )),
+                #[cfg(feature = "biome-user-admin")]
+                self.user_store.clone(),
             ));
             resources.push(make_token_route(
                 self.refresh_token_store.clone(),
@@ -172,6 +281,8 @@ impl RestResourceProvider for BiomeRestResourceManager {
                     self.refresh_token_secret_manager.clone(),
                 )),
                 self.rest_config.clone(),
+                #[cfg(feature = "biome-user-admin")]
+                self.user_store.clone(),
             ));
             resources.push(make_logout_route(
                 self.refresh_token_store.clone(),
@@ -186,6 +297,65 @@ impl RestResourceProvider for BiomeRestResourceManager {
             ));
         }
 
+        // The MFA endpoints are only available if TOTP secrets can be encrypted
+        #[cfg(all(feature = "biome-mfa", feature = "rest-api-actix"))]
+        {
+            if self.rest_config.mfa_encryption_key().is_some() {
+                resources.push(make_totp_route(
+                    self.credentials_store.clone(),
+                    self.rest_config.clone(),
+                    self.token_secret_manager.clone(),
+                ));
+                resources.push(make_totp_verify_route(
+                    self.credentials_store.clone(),
+                    self.rest_config.clone(),
+                ));
+            }
+        }
+
+        // The password reset endpoints are only available if tokens can be delivered to users
+        #[cfg(all(feature = "biome-password-reset", feature = "rest-api-actix"))]
+        {
+            if let Some(notifier) = &self.password_reset_notifier {
+                resources.push(make_password_reset_request_route(
+                    self.credentials_store.clone(),
+                    self.rest_config.clone(),
+                    notifier.clone(),
+                ));
+                resources.push(make_password_reset_route(
+                    self.credentials_store.clone(),
+                    self.refresh_token_store.clone(),
+                    self.rest_config.clone(),
+                ));
+            }
+        }
+
+        #[cfg(all(feature = "biome-login-lockout", feature = "rest-api-actix"))]
+        {
+            resources.push(make_lockout_route(
+                self.credentials_store.clone(),
+                self.rest_config.clone(),
+                self.token_secret_manager.clone(),
+                #[cfg(feature = "biome-user-admin")]
+                self.user_store.clone(),
+            ));
+        }
+
+        #[cfg(all(feature = "biome-user-admin", feature = "rest-api-actix"))]
+        {
+            resources.push(make_user_roles_route(
+                self.user_store.clone(),
+                self.rest_config.clone(),
+                self.token_secret_manager.clone(),
+            ));
+            resources.push(make_user_status_route(
+                self.user_store.clone(),
+                self.refresh_token_store.clone(),
+                self.rest_config.clone(),
+                self.token_secret_manager.clone(),
+            ));
+        }
+
         #[cfg(all(feature = "biome-key-management", feature = "rest-api-actix",))]
         {
             resources.push(make_key_management_route(
@@ -199,6 +369,27 @@ impl RestResourceProvider for BiomeRestResourceManager {
                 self.token_secret_manager.clone(),
             ));
         }
+
+        #[cfg(all(feature = "biome-notifications", feature = "rest-api-actix",))]
+        {
+            resources.push(make_notifications_route(
+                self.rest_config.clone(),
+                self.notification_store.clone(),
+                self.token_secret_manager.clone(),
+            ));
+            // The subscribe route must be added before the notification route so that it is not
+            // matched as a notification ID
+            resources.push(make_notifications_subscribe_route(
+                self.rest_config.clone(),
+                self.notification_store.clone(),
+                self.token_secret_manager.clone(),
+            ));
+            resources.push(make_notification_route(
+                self.rest_config.clone(),
+                self.notification_store.clone(),
+                self.token_secret_manager.clone(),
+            ));
+        }
         resources
     }
 }
@@ -209,6 +400,8 @@ pub struct BiomeRestResourceManagerBuilder {
     user_store: Option<Arc<dyn UserStore>>,
     #[cfg(feature = "biome-key-management")]
     key_store: Option<Arc<dyn KeyStore>>,
+    #[cfg(feature = "biome-notifications")]
+    notification_store: Option<Arc<dyn NotificationStore>>,
     rest_config: Option<BiomeRestConfig>,
     token_secret_manager: Option<Arc<dyn SecretManager>>,
     #[cfg(feature = "biome-credentials")]
@@ -217,6 +410,8 @@ pub struct BiomeRestResourceManagerBuilder {
     refresh_token_store: Option<Arc<dyn RefreshTokenStore>>,
     #[cfg(feature = "biome-credentials")]
     credentials_store: Option<Arc<dyn CredentialsStore>>,
+    #[cfg(feature = "biome-password-reset")]
+    password_reset_notifier: Option<Arc<dyn PasswordResetNotifier>>,
 }
 
 impl BiomeRestResourceManagerBuilder {
@@ -247,6 +442,20 @@ impl BiomeRestResourceManagerBuilder {
         self
     }
 
+    /// Sets a NotificationStore for the BiomeRestResourceManager
+    ///
+    /// # Arguments
+    ///
+    /// * `store`: the NotificationStore that will serve the users' notifications
+    #[cfg(feature = "biome-notifications")]
+    pub fn with_notification_store(
+        mut self,
+        store: impl NotificationStore + 'static,
+    ) -> BiomeRestResourceManagerBuilder {
+        self.notification_store = Some(Arc::new(store));
+        self
+    }
+
     /// Sets a BiomeRestConfig for the BiomeRestResourceManager
     ///
     /// # Arguments
@@ -271,6 +480,21 @@ impl BiomeRestResourceManagerBuilder {
         self
     }
 
+    #[cfg(feature = "biome-password-reset")]
+    /// Sets a PasswordResetNotifier for the BiomeRestResourceManager. The password reset
+    /// endpoints are only provided if a notifier is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `notifier`: the PasswordResetNotifier that will deliver password reset tokens to users
+    pub fn with_password_reset_notifier(
+        mut self,
+        notifier: impl PasswordResetNotifier + 'static,
+    ) -> BiomeRestResourceManagerBuilder {
+        self.password_reset_notifier = Some(Arc::new(notifier));
+        self
+    }
+
     /// Sets a SecretManager for JWT tokens for the BiomeRestResourceManager
     ///
     /// # Arguments
@@ -330,7 +554,17 @@ impl BiomeRestResourceManagerBuilder {
                 "Missing key store".to_string(),
             )
         })?;
-        #[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+        #[cfg(feature = "biome-notifications")]
+        let notification_store = self.notification_store.ok_or_else(|| {
+            BiomeRestResourceManagerBuilderError::MissingRequiredField(
+                "Missing notification store".to_string(),
+            )
+        })?;
+        #[cfg(any(
+            feature = "biome-credentials",
+            feature = "biome-key-management",
+            feature = "biome-notifications",
+        ))]
         let rest_config = match self.rest_config {
             Some(config) => config,
             None => {
@@ -339,7 +573,11 @@ impl BiomeRestResourceManagerBuilder {
             }
         };
 
-        #[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+        #[cfg(any(
+            feature = "biome-credentials",
+            feature = "biome-key-management",
+            feature = "biome-notifications",
+        ))]
         let token_secret_manager = self.token_secret_manager.unwrap_or_else(|| {
             debug!("Building BiomeRestResourceManager with default SecretManager.");
             Arc::new(AutoSecretManager::default())
@@ -359,7 +597,11 @@ impl BiomeRestResourceManagerBuilder {
         })?;
 
         #[cfg(feature = "biome-credentials")]
-        #[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+        #[cfg(any(
+            feature = "biome-credentials",
+            feature = "biome-key-management",
+            feature = "biome-notifications",
+        ))]
         let credentials_store = self.credentials_store.ok_or_else(|| {
             BiomeRestResourceManagerBuilderError::MissingRequiredField(
                 "Missing credentials store".to_string(),
@@ -371,9 +613,19 @@ impl BiomeRestResourceManagerBuilder {
             user_store,
             #[cfg(feature = "biome-key-management")]
             key_store,
-            #[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+            #[cfg(feature = "biome-notifications")]
+            notification_store,
+            #[cfg(any(
+                feature = "biome-credentials",
+                feature = "biome-key-management",
+                feature = "biome-notifications",
+            ))]
             rest_config: Arc::new(rest_config),
-            #[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+            #[cfg(any(
+                feature = "biome-credentials",
+                feature = "biome-key-management",
+                feature = "biome-notifications",
+            ))]
             token_secret_manager,
             #[cfg(feature = "biome-credentials")]
             refresh_token_secret_manager,
@@ -381,6 +633,8 @@ impl BiomeRestResourceManagerBuilder {
             refresh_token_store,
             #[cfg(feature = "biome-credentials")]
             credentials_store,
+            #[cfg(feature = "biome-password-reset")]
+            password_reset_notifier: self.password_reset_notifier,
         })
     }
 }
@@ -498,14 +752,20 @@ mod tests {
     }
 
     fn start_biome_rest_api() -> (RestApiShutdownHandle, thread::JoinHandle<()>) {
-        let refresh_token_store = MemoryRefreshTokenStore::new();
-        let cred_store = MemoryCredentialsStore::new();
-        let user_store = MemoryUserStore::new(cred_store.clone());
-        let key_store = MemoryKeyStore::new(cred_store.clone());
         let config = BiomeRestConfigBuilder::default()
             .with_password_encryption_cost("low")
             .build()
             .unwrap();
+        start_biome_rest_api_with_config(MemoryCredentialsStore::new(), config)
+    }
+
+    fn start_biome_rest_api_with_config(
+        cred_store: MemoryCredentialsStore,
+        config: BiomeRestConfig,
+    ) -> (RestApiShutdownHandle, thread::JoinHandle<()>) {
+        let refresh_token_store = MemoryRefreshTokenStore::new();
+        let user_store = MemoryUserStore::new(cred_store.clone());
+        let key_store = MemoryKeyStore::new(cred_store.clone());
 
         let resource_manager = BiomeRestResourceManagerBuilder::default()
             .with_user_store(user_store)
@@ -558,7 +818,14 @@ mod tests {
     where
         F: FnOnce(&str, Client) -> () + panic::UnwindSafe,
     {
-        let (handle, join_handle) = start_biome_rest_api();
+        run_test_with_api(start_biome_rest_api(), f)
+    }
+
+    fn run_test_with_api<F>(api: (RestApiShutdownHandle, thread::JoinHandle<()>), f: F)
+    where
+        F: FnOnce(&str, Client) -> () + panic::UnwindSafe,
+    {
+        let (handle, join_handle) = api;
 
         let port_no = handle.port_numbers()[0];
 
@@ -1225,4 +1492,155 @@ mod tests {
             token_response.json::<PostToken>().unwrap();
         });
     }
+
+    #[cfg(all(feature = "biome-login-lockout", feature = "biome-mfa"))]
+    #[derive(Serialize)]
+    struct LoginWithTotpCode {
+        pub username: String,
+        pub hashed_password: String,
+        pub totp_code: String,
+    }
+
+    #[cfg(all(feature = "biome-login-lockout", feature = "biome-mfa"))]
+    #[derive(Serialize)]
+    struct TotpConfirmation {
+        pub username: String,
+        pub hashed_password: String,
+        pub code: String,
+    }
+
+    #[cfg(all(feature = "biome-login-lockout", feature = "biome-mfa"))]
+    fn mfa_lockout_config(mfa_key: &[u8]) -> BiomeRestConfig {
+        BiomeRestConfigBuilder::default()
+            .with_password_encryption_cost("low")
+            .with_mfa_encryption_key(mfa_key)
+            .with_max_failed_logins(2)
+            .build()
+            .unwrap()
+    }
+
+    /// Test that wrong TOTP codes given to POST /biome/login lock the user's account.
+    ///
+    /// Procedure
+    ///
+    /// 1) Create a user with a confirmed TOTP enrolment
+    /// 2) Log in twice with the right password but a wrong TOTP code
+    /// 3) Verify that both attempts are rejected as unauthorized
+    /// 4) Verify that the next login is rejected because the account is locked
+    #[cfg(all(feature = "biome-login-lockout", feature = "biome-mfa"))]
+    #[test]
+    fn test_login_lockout_totp_code() {
+        use crate::biome::credentials::mfa::{
+            generate_encryption_key, TotpCredentials, TotpSecret,
+        };
+        use crate::biome::CredentialsStore;
+
+        let mfa_key = generate_encryption_key();
+        let cred_store = MemoryCredentialsStore::new();
+        let api =
+            start_biome_rest_api_with_config(cred_store.clone(), mfa_lockout_config(&mfa_key));
+
+        run_test_with_api(api, move |url, client| {
+            let login = create_and_authorize_user(
+                url,
+                &client,
+                "test_login_lockout_totp@gmail.com",
+                "Admin2193!",
+            );
+
+            cred_store
+                .set_totp(TotpCredentials {
+                    user_id: login.user_id.clone(),
+                    encrypted_secret: TotpSecret::generate().encrypt(&mfa_key).unwrap(),
+                    confirmed: false,
+                    last_used_step: None,
+                    recovery_codes: vec![],
+                })
+                .unwrap();
+            cred_store.confirm_totp(&login.user_id).unwrap();
+
+            let attempt_login = || {
+                client
+                    .post(&format!("{}/biome/login", url))
+                    .json(&LoginWithTotpCode {
+                        username: "test_login_lockout_totp@gmail.com".to_string(),
+                        hashed_password: "Admin2193!".to_string(),
+                        // TOTP codes are numeric, so this code never matches
+                        totp_code: "abcdef".to_string(),
+                    })
+                    .send()
+                    .unwrap()
+                    .status()
+                    .as_u16()
+            };
+
+            assert_eq!(attempt_login(), 401);
+            assert_eq!(attempt_login(), 401);
+            assert_eq!(attempt_login(), 403);
+        });
+    }
+
+    /// Test that wrong passwords and TOTP codes given to the MFA endpoints count towards the
+    /// account lockout, and that locked accounts are rejected by them.
+    ///
+    /// Procedure
+    ///
+    /// 1) Create a user
+    /// 2) Enrol in TOTP via POST /biome/mfa/totp with a wrong password, then the right one
+    /// 3) Confirm the enrolment via POST /biome/mfa/totp/verify with a wrong code
+    /// 4) Verify that enrolling and logging in are rejected because the account is locked
+    #[cfg(all(feature = "biome-login-lockout", feature = "biome-mfa"))]
+    #[test]
+    fn test_mfa_lockout() {
+        use crate::biome::credentials::mfa::generate_encryption_key;
+
+        let mfa_key = generate_encryption_key();
+        let api = start_biome_rest_api_with_config(
+            MemoryCredentialsStore::new(),
+            mfa_lockout_config(&mfa_key),
+        );
+
+        run_test_with_api(api, |url, client| {
+            create_and_authorize_user(url, &client, "test_mfa_lockout@gmail.com", "Admin2193!");
+
+            let enrol = |password: &str| {
+                client
+                    .post(&format!("{}/biome/mfa/totp", url))
+                    .json(&UsernamePassword {
+                        username: "test_mfa_lockout@gmail.com".to_string(),
+                        hashed_password: password.to_string(),
+                    })
+                    .send()
+                    .unwrap()
+                    .status()
+                    .as_u16()
+            };
+
+            assert_eq!(enrol("wrong_password"), 400);
+            assert_eq!(enrol("Admin2193!"), 200);
+
+            let verify_response = client
+                .post(&format!("{}/biome/mfa/totp/verify", url))
+                .json(&TotpConfirmation {
+                    username: "test_mfa_lockout@gmail.com".to_string(),
+                    hashed_password: "Admin2193!".to_string(),
+                    code: "abcdef".to_string(),
+                })
+                .send()
+                .unwrap();
+            assert_eq!(verify_response.status().as_u16(), 400);
+
+            assert_eq!(enrol("Admin2193!"), 403);
+
+            let login_response = client
+                .post(&format!("{}/biome/login", url))
+                .json(&UsernamePassword {
+                    username: "test_mfa_lockout@gmail.com".to_string(),
+                    hashed_password: "Admin2193!".to_string(),
+                })
+                .send()
+                .unwrap();
+            assert_eq!(login_response.status().as_u16(), 403);
+        });
+    }
 }
@@ -18,6 +18,14 @@
 pub(crate) struct UsernamePassword {
     pub username: String,
     pub hashed_password: String,
+    /// TOTP code for users enrolled in multi-factor authentication
+    #[cfg(feature = "biome-mfa")]
+    #[serde(default)]
+    pub totp_code: Option<String>,
+    /// Recovery code used in place of a TOTP code
+    #[cfg(feature = "biome-mfa")]
+    #[serde(default)]
+    pub recovery_code: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -0,0 +1,45 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the payloads used to enrol in and remove multi-factor authentication.
+
+/// Payload for enrolling in TOTP multi-factor authentication.
+#[derive(Deserialize)]
+pub(crate) struct TotpEnrolmentRequest {
+    pub username: String,
+    pub hashed_password: String,
+}
+
+/// Payload for confirming a TOTP enrolment with a code from the user's authenticator.
+#[derive(Deserialize)]
+pub(crate) struct TotpConfirmationRequest {
+    pub username: String,
+    pub hashed_password: String,
+    pub code: String,
+}
+
+/// Payload for removing a TOTP enrolment; the code may be either a TOTP code or a recovery code.
+#[derive(Deserialize)]
+pub(crate) struct TotpRemovalRequest {
+    pub code: String,
+}
+
+/// Response to a successful TOTP enrolment. This is the only time the secret and the recovery
+/// codes are returned in plain text.
+#[derive(Serialize)]
+pub(crate) struct TotpEnrolmentResponse<'a> {
+    pub secret: String,
+    pub provisioning_uri: String,
+    pub recovery_codes: &'a [String],
+}
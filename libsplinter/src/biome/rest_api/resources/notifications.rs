@@ -0,0 +1,70 @@
+/*
+ * Copyright 2018-2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+//! Defines structures used in notifications.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::biome::notifications::{Notification, UserNotification};
+
+#[derive(Debug, Serialize)]
+pub(crate) struct NotificationResponse {
+    id: String,
+    user_id: String,
+    payload_title: String,
+    payload_body: String,
+    /// The time the notification was created, in seconds since the Unix epoch
+    created: u64,
+    unread: bool,
+    properties: HashMap<String, String>,
+}
+
+impl NotificationResponse {
+    /// Creates the response for a notification that was just received by the given user.
+    pub fn new_for_user(notification: &Notification, user_id: &str) -> Self {
+        NotificationResponse {
+            id: notification.id.clone(),
+            user_id: user_id.to_string(),
+            payload_title: notification.payload_title.clone(),
+            payload_body: notification.payload_body.clone(),
+            created: epoch_seconds(notification.created),
+            unread: true,
+            properties: notification.properties.clone(),
+        }
+    }
+}
+
+impl From<UserNotification> for NotificationResponse {
+    fn from(notification: UserNotification) -> Self {
+        NotificationResponse {
+            id: notification.id,
+            user_id: notification.user_id,
+            payload_title: notification.payload_title,
+            payload_body: notification.payload_body,
+            created: epoch_seconds(notification.created),
+            unread: notification.unread,
+            properties: notification.properties,
+        }
+    }
+}
+
+fn epoch_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -22,8 +22,16 @@ use super::{User, UserStore, UserStoreError};
 
 use operations::add_user::UserStoreAddUserOperation as _;
 use operations::delete_user::UserStoreDeleteUserOperation as _;
+#[cfg(feature = "biome-user-admin")]
+use operations::fetch_roles::UserStoreFetchRolesOperation as _;
 use operations::fetch_user::UserStoreFetchUserOperation as _;
+#[cfg(feature = "biome-user-admin")]
+use operations::is_disabled::UserStoreIsDisabledOperation as _;
 use operations::list_users::UserStoreListUsersOperation as _;
+#[cfg(feature = "biome-user-admin")]
+use operations::set_disabled::UserStoreSetDisabledOperation as _;
+#[cfg(feature = "biome-user-admin")]
+use operations::set_roles::UserStoreSetRolesOperation as _;
 use operations::update_user::UserStoreUpdateUserOperation as _;
 use operations::UserStoreOperations;
 
@@ -67,6 +75,26 @@ impl UserStore for DieselUserStore<diesel::pg::PgConnection> {
     fn list_users(&self) -> Result<Vec<User>, UserStoreError> {
         UserStoreOperations::new(&*self.connection_pool.get()?).list_users()
     }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn fetch_roles(&self, id: &str) -> Result<Vec<String>, UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).fetch_roles(id)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_roles(&self, id: &str, roles: &[String]) -> Result<(), UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).set_roles(id, roles)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn is_disabled(&self, id: &str) -> Result<bool, UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).is_disabled(id)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_disabled(&self, id: &str, disabled: bool) -> Result<(), UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).set_disabled(id, disabled)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -90,6 +118,26 @@ impl UserStore for DieselUserStore<diesel::sqlite::SqliteConnection> {
     fn list_users(&self) -> Result<Vec<User>, UserStoreError> {
         UserStoreOperations::new(&*self.connection_pool.get()?).list_users()
     }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn fetch_roles(&self, id: &str) -> Result<Vec<String>, UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).fetch_roles(id)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_roles(&self, id: &str, roles: &[String]) -> Result<(), UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).set_roles(id, roles)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn is_disabled(&self, id: &str) -> Result<bool, UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).is_disabled(id)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_disabled(&self, id: &str, disabled: bool) -> Result<(), UserStoreError> {
+        UserStoreOperations::new(&*self.connection_pool.get()?).set_disabled(id, disabled)
+    }
 }
 
 #[cfg(all(test, feature = "sqlite"))]
@@ -98,6 +146,9 @@ pub mod tests {
 
     use crate::biome::migrations::run_sqlite_migrations;
 
+    use super::schema::splinter_user;
+    #[cfg(feature = "biome-user-admin")]
+    use super::schema::{disabled_users, user_roles};
     use diesel::{
         r2d2::{ConnectionManager, Pool},
         sqlite::SqliteConnection,
@@ -205,6 +256,433 @@ pub mod tests {
         assert!(!users.contains(&user3));
     }
 
+    /// Verify that removing a user from a SQLite-backed `DieselUserStore` also removes the user's
+    /// credentials and refresh tokens.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselUserStore`, `DieselCredentialsStore` and `DieselRefreshTokenStore`.
+    /// 3. Add a user with credentials and a refresh token.
+    /// 4. Remove the user and verify that the credentials and refresh token have been removed.
+    /// 5. Verify that removing the user again returns a `UserStoreError::NotFoundError`.
+    #[cfg(feature = "biome-credentials")]
+    #[test]
+    fn sqlite_remove_with_credentials() {
+        use crate::biome::credentials::store::{
+            CredentialsBuilder, CredentialsStore, PasswordEncryptionCost,
+        };
+        use crate::biome::refresh_tokens::store::RefreshTokenStore;
+        use crate::biome::{DieselCredentialsStore, DieselRefreshTokenStore};
+
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselUserStore::new(pool.clone());
+        let credentials_store = DieselCredentialsStore::new(pool.clone());
+        let refresh_token_store = DieselRefreshTokenStore::new(pool);
+
+        store
+            .add_user(User::new("user1"))
+            .expect("Failed to add user1");
+        credentials_store
+            .add_credentials(
+                CredentialsBuilder::default()
+                    .with_user_id("user1")
+                    .with_username("username1")
+                    .with_password("pwd1")
+                    .with_password_encryption_cost(PasswordEncryptionCost::Low)
+                    .build()
+                    .expect("Failed to build credentials"),
+            )
+            .expect("Failed to add credentials");
+        refresh_token_store
+            .add_token("user1", "token1")
+            .expect("Failed to add refresh token");
+
+        store.remove_user("user1").expect("Failed to remove user1");
+
+        assert!(credentials_store
+            .fetch_credential_by_username("username1")
+            .is_err());
+        assert!(refresh_token_store.fetch_token("user1").is_err());
+
+        match store.remove_user("user1") {
+            Err(UserStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(UserStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
+    /// Verify that removing a user from a SQLite-backed `DieselUserStore` removes every row that
+    /// belongs to the user, in each of the enabled Biome tables.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Add a user and insert a row for the user in each table that references users.
+    /// 3. Remove the user and verify that all of these tables are empty.
+    #[test]
+    fn sqlite_remove_deletes_user_data() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselUserStore::new(pool.clone());
+
+        store
+            .add_user(User::new("user1"))
+            .expect("Failed to add user1");
+
+        {
+            let conn = pool.get().expect("Failed to get connection");
+            insert_user_data(&*conn, "user1");
+        }
+
+        store.remove_user("user1").expect("Failed to remove user1");
+
+        let conn = pool.get().expect("Failed to get connection");
+        assert_eq!(count_user_data(&*conn), vec![0; USER_DATA_TABLES]);
+    }
+
+    /// Verify that a SQLite-backed `DieselUserStore` correctly supports user roles and disabling
+    /// users.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselUserStore` and add a user.
+    /// 3. Set the user's roles and verify that `fetch_roles` returns them, sorted and without
+    ///    duplicates.
+    /// 4. Disable the user and verify `is_disabled`; then re-enable the user.
+    /// 5. Verify that setting roles or disabling a non-existent user returns a
+    ///    `UserStoreError::NotFoundError`.
+    /// 6. Remove the user and verify that the user's roles have been removed.
+    #[cfg(feature = "biome-user-admin")]
+    #[test]
+    fn sqlite_roles_and_disabled() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselUserStore::new(pool);
+
+        store
+            .add_user(User::new("user1"))
+            .expect("Failed to add user1");
+
+        assert!(store
+            .fetch_roles("user1")
+            .expect("Failed to fetch roles")
+            .is_empty());
+
+        store
+            .set_roles(
+                "user1",
+                &[
+                    "viewer".to_string(),
+                    "admin".to_string(),
+                    "viewer".to_string(),
+                ],
+            )
+            .expect("Failed to set roles");
+        assert_eq!(
+            store.fetch_roles("user1").expect("Failed to fetch roles"),
+            vec!["admin".to_string(), "viewer".to_string()],
+        );
+
+        assert!(!store.is_disabled("user1").expect("Failed to check user"));
+        store
+            .set_disabled("user1", true)
+            .expect("Failed to disable user");
+        assert!(store.is_disabled("user1").expect("Failed to check user"));
+        store
+            .set_disabled("user1", false)
+            .expect("Failed to enable user");
+        assert!(!store.is_disabled("user1").expect("Failed to check user"));
+
+        match store.set_roles("user2", &["admin".to_string()]) {
+            Err(UserStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(UserStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+        match store.set_disabled("user2", true) {
+            Err(UserStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(UserStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+
+        store.remove_user("user1").expect("Failed to remove user1");
+        assert!(store
+            .fetch_roles("user1")
+            .expect("Failed to fetch roles")
+            .is_empty());
+    }
+
+    // The number of tables, including the splinter_user table, that hold rows for a user
+    const USER_DATA_TABLES: usize = 1
+        + cfg!(feature = "biome-key-management") as usize
+        + 2 * cfg!(feature = "biome-credentials") as usize
+        + 2 * cfg!(feature = "biome-user-admin") as usize
+        + 2 * cfg!(feature = "biome-mfa") as usize
+        + cfg!(feature = "biome-password-reset") as usize
+        + cfg!(feature = "biome-login-lockout") as usize
+        + cfg!(feature = "biome-notifications") as usize
+        + cfg!(feature = "oauth") as usize;
+
+    /// Inserts a row for the given user in each enabled table that references users.
+    fn insert_user_data(conn: &SqliteConnection, user_id: &str) {
+        use diesel::{insert_into, prelude::*};
+
+        #[cfg(feature = "biome-key-management")]
+        {
+            use crate::biome::key_management::store::diesel::schema::keys;
+            insert_into(keys::table)
+                .values((
+                    keys::public_key.eq("public_key"),
+                    keys::encrypted_private_key.eq("private_key"),
+                    keys::user_id.eq(user_id),
+                    keys::display_name.eq("key"),
+                ))
+                .execute(conn)
+                .expect("Failed to insert key");
+        }
+
+        #[cfg(feature = "biome-credentials")]
+        {
+            use crate::biome::credentials::store::diesel::schema::user_credentials;
+            use crate::biome::refresh_tokens::store::diesel::schema::refresh_tokens;
+            insert_into(user_credentials::table)
+                .values((
+                    user_credentials::user_id.eq(user_id),
+                    user_credentials::username.eq("username"),
+                    user_credentials::password.eq("password"),
+                ))
+                .execute(conn)
+                .expect("Failed to insert credentials");
+            insert_into(refresh_tokens::table)
+                .values((
+                    refresh_tokens::user_id.eq(user_id),
+                    refresh_tokens::token.eq("token"),
+                ))
+                .execute(conn)
+                .expect("Failed to insert refresh token");
+        }
+
+        #[cfg(feature = "biome-user-admin")]
+        {
+            insert_into(user_roles::table)
+                .values((
+                    user_roles::user_id.eq(user_id),
+                    user_roles::role.eq("admin"),
+                ))
+                .execute(conn)
+                .expect("Failed to insert role");
+            insert_into(disabled_users::table)
+                .values(disabled_users::user_id.eq(user_id))
+                .execute(conn)
+                .expect("Failed to insert disabled user");
+        }
+
+        #[cfg(feature = "biome-mfa")]
+        {
+            use crate::biome::credentials::store::diesel::schema::{
+                user_totp, user_totp_recovery_codes,
+            };
+            insert_into(user_totp::table)
+                .values((
+                    user_totp::user_id.eq(user_id),
+                    user_totp::encrypted_secret.eq("secret"),
+                    user_totp::confirmed.eq(true),
+                ))
+                .execute(conn)
+                .expect("Failed to insert TOTP secret");
+            insert_into(user_totp_recovery_codes::table)
+                .values((
+                    user_totp_recovery_codes::user_id.eq(user_id),
+                    user_totp_recovery_codes::code_hash.eq("code"),
+                ))
+                .execute(conn)
+                .expect("Failed to insert recovery code");
+        }
+
+        #[cfg(feature = "biome-password-reset")]
+        {
+            use crate::biome::credentials::store::diesel::schema::password_reset_tokens;
+            insert_into(password_reset_tokens::table)
+                .values((
+                    password_reset_tokens::token_hash.eq("token"),
+                    password_reset_tokens::user_id.eq(user_id),
+                    password_reset_tokens::expires.eq(0),
+                ))
+                .execute(conn)
+                .expect("Failed to insert password reset token");
+        }
+
+        #[cfg(feature = "biome-login-lockout")]
+        {
+            use crate::biome::credentials::store::diesel::schema::user_login_attempts;
+            insert_into(user_login_attempts::table)
+                .values((
+                    user_login_attempts::user_id.eq(user_id),
+                    user_login_attempts::failed_attempts.eq(1),
+                ))
+                .execute(conn)
+                .expect("Failed to insert login attempts");
+        }
+
+        #[cfg(feature = "biome-notifications")]
+        {
+            use crate::biome::notifications::store::diesel::schema::{
+                notifications, user_notifications,
+            };
+            insert_into(notifications::table)
+                .values((
+                    notifications::id.eq("notification"),
+                    notifications::payload_title.eq("title"),
+                    notifications::payload_body.eq("body"),
+                    notifications::created.eq(0),
+                ))
+                .execute(conn)
+                .expect("Failed to insert notification");
+            insert_into(user_notifications::table)
+                .values((
+                    user_notifications::notification_id.eq("notification"),
+                    user_notifications::user_id.eq(user_id),
+                    user_notifications::unread.eq(true),
+                ))
+                .execute(conn)
+                .expect("Failed to insert user notification");
+        }
+
+        #[cfg(feature = "oauth")]
+        {
+            use crate::biome::oauth::store::diesel::schema::oauth_users;
+            insert_into(oauth_users::table)
+                .values((
+                    oauth_users::user_id.eq(user_id),
+                    oauth_users::subject.eq("subject"),
+                ))
+                .execute(conn)
+                .expect("Failed to insert OAuth user");
+        }
+    }
+
+    /// Counts the rows in each enabled table that references users.
+    fn count_user_data(conn: &SqliteConnection) -> Vec<i64> {
+        use diesel::prelude::*;
+
+        let mut counts = vec![splinter_user::table
+            .count()
+            .get_result(conn)
+            .expect("Failed to count users")];
+
+        #[cfg(feature = "biome-key-management")]
+        {
+            use crate::biome::key_management::store::diesel::schema::keys;
+            counts.push(
+                keys::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        #[cfg(feature = "biome-credentials")]
+        {
+            use crate::biome::credentials::store::diesel::schema::user_credentials;
+            use crate::biome::refresh_tokens::store::diesel::schema::refresh_tokens;
+            counts.push(
+                user_credentials::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+            counts.push(
+                refresh_tokens::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        #[cfg(feature = "biome-user-admin")]
+        {
+            counts.push(
+                user_roles::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+            counts.push(
+                disabled_users::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        #[cfg(feature = "biome-mfa")]
+        {
+            use crate::biome::credentials::store::diesel::schema::{
+                user_totp, user_totp_recovery_codes,
+            };
+            counts.push(
+                user_totp::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+            counts.push(
+                user_totp_recovery_codes::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        #[cfg(feature = "biome-password-reset")]
+        {
+            use crate::biome::credentials::store::diesel::schema::password_reset_tokens;
+            counts.push(
+                password_reset_tokens::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        #[cfg(feature = "biome-login-lockout")]
+        {
+            use crate::biome::credentials::store::diesel::schema::user_login_attempts;
+            counts.push(
+                user_login_attempts::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        #[cfg(feature = "biome-notifications")]
+        {
+            use crate::biome::notifications::store::diesel::schema::user_notifications;
+            counts.push(
+                user_notifications::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        #[cfg(feature = "oauth")]
+        {
+            use crate::biome::oauth::store::diesel::schema::oauth_users;
+            counts.push(
+                oauth_users::table
+                    .count()
+                    .get_result(conn)
+                    .expect("Failed to count"),
+            );
+        }
+
+        counts
+    }
+
     /// Creates a conneciton pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
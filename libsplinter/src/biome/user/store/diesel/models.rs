@@ -33,3 +33,18 @@ impl Into<UserModel> for User {
         UserModel { id: self.id }
     }
 }
+
+#[cfg(feature = "biome-user-admin")]
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "user_roles"]
+pub struct UserRoleModel {
+    pub user_id: String,
+    pub role: String,
+}
+
+#[cfg(feature = "biome-user-admin")]
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "disabled_users"]
+pub struct DisabledUserModel {
+    pub user_id: String,
+}
@@ -13,11 +13,29 @@
 // limitations under the License.
 
 use super::UserStoreOperations;
+#[cfg(feature = "biome-password-reset")]
+use crate::biome::credentials::store::diesel::schema::password_reset_tokens;
+#[cfg(feature = "biome-credentials")]
+use crate::biome::credentials::store::diesel::schema::user_credentials;
+#[cfg(feature = "biome-login-lockout")]
+use crate::biome::credentials::store::diesel::schema::user_login_attempts;
+#[cfg(feature = "biome-mfa")]
+use crate::biome::credentials::store::diesel::schema::{user_totp, user_totp_recovery_codes};
+#[cfg(feature = "biome-key-management")]
+use crate::biome::key_management::store::diesel::schema::keys;
+#[cfg(feature = "biome-notifications")]
+use crate::biome::notifications::store::diesel::schema::user_notifications;
+#[cfg(feature = "oauth")]
+use crate::biome::oauth::store::diesel::schema::oauth_users;
+#[cfg(feature = "biome-credentials")]
+use crate::biome::refresh_tokens::store::diesel::schema::refresh_tokens;
 use crate::biome::user::store::diesel::models::UserModel;
 use crate::biome::user::store::diesel::schema::splinter_user;
+#[cfg(feature = "biome-user-admin")]
+use crate::biome::user::store::diesel::schema::{disabled_users, user_roles};
 use crate::biome::user::store::error::UserStoreError;
 
-use diesel::{dsl::delete, prelude::*};
+use diesel::{dsl::delete, prelude::*, result::Error as QueryError};
 
 pub(in crate::biome::user) trait UserStoreDeleteUserOperation {
     fn delete_user(&self, user_id: &str) -> Result<(), UserStoreError>;
@@ -29,29 +47,93 @@ where
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
     fn delete_user(&self, user_id: &str) -> Result<(), UserStoreError> {
-        let user = splinter_user::table
-            .find(&user_id)
-            .first::<UserModel>(self.conn)
-            .map(Some)
+        // Everything that belongs to the user is removed in the same transaction as the user, as
+        // not every backend enforces the foreign key constraints on these tables
+        let deleted = self
+            .conn
+            .transaction::<_, QueryError, _>(|| {
+                let user = splinter_user::table
+                    .find(&user_id)
+                    .first::<UserModel>(self.conn)
+                    .optional()?;
+
+                if user.is_none() {
+                    return Ok(false);
+                }
+
+                #[cfg(feature = "biome-key-management")]
+                {
+                    delete(keys::table.filter(keys::user_id.eq(&user_id))).execute(self.conn)?;
+                }
+
+                #[cfg(feature = "biome-credentials")]
+                {
+                    delete(refresh_tokens::table.filter(refresh_tokens::user_id.eq(&user_id)))
+                        .execute(self.conn)?;
+                    delete(user_credentials::table.filter(user_credentials::user_id.eq(&user_id)))
+                        .execute(self.conn)?;
+                }
+
+                #[cfg(feature = "biome-mfa")]
+                {
+                    delete(user_totp::table.find(&user_id)).execute(self.conn)?;
+                    delete(
+                        user_totp_recovery_codes::table
+                            .filter(user_totp_recovery_codes::user_id.eq(&user_id)),
+                    )
+                    .execute(self.conn)?;
+                }
+
+                #[cfg(feature = "biome-password-reset")]
+                {
+                    delete(
+                        password_reset_tokens::table
+                            .filter(password_reset_tokens::user_id.eq(&user_id)),
+                    )
+                    .execute(self.conn)?;
+                }
+
+                #[cfg(feature = "biome-login-lockout")]
+                {
+                    delete(user_login_attempts::table.find(&user_id)).execute(self.conn)?;
+                }
+
+                #[cfg(feature = "biome-notifications")]
+                {
+                    delete(
+                        user_notifications::table.filter(user_notifications::user_id.eq(&user_id)),
+                    )
+                    .execute(self.conn)?;
+                }
+
+                #[cfg(feature = "biome-user-admin")]
+                {
+                    delete(user_roles::table.filter(user_roles::user_id.eq(&user_id)))
+                        .execute(self.conn)?;
+                    delete(disabled_users::table.find(&user_id)).execute(self.conn)?;
+                }
+
+                #[cfg(feature = "oauth")]
+                {
+                    delete(oauth_users::table.find(&user_id)).execute(self.conn)?;
+                }
+
+                delete(splinter_user::table.filter(splinter_user::id.eq(&user_id)))
+                    .execute(self.conn)?;
+                Ok(true)
+            })
             .map_err(|err| UserStoreError::OperationError {
-                context: "Failed to fetch user".to_string(),
+                context: "Failed to delete user".to_string(),
                 source: Box::new(err),
             })?;
 
-        if user.is_none() {
-            return Err(UserStoreError::NotFoundError(format!(
+        if deleted {
+            Ok(())
+        } else {
+            Err(UserStoreError::NotFoundError(format!(
                 "Failed to find user: {}",
                 &user_id
-            )));
+            )))
         }
-
-        delete(splinter_user::table.filter(splinter_user::id.eq(&user_id)))
-            .execute(self.conn)
-            .map(|_| ())
-            .map_err(|err| UserStoreError::OperationError {
-                context: "Failed to delete user".to_string(),
-                source: Box::new(err),
-            })?;
-        Ok(())
     }
 }
@@ -0,0 +1,41 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::UserStoreOperations;
+use crate::biome::user::store::diesel::schema::user_roles;
+use crate::biome::user::store::error::UserStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::biome::user) trait UserStoreFetchRolesOperation {
+    fn fetch_roles(&self, user_id: &str) -> Result<Vec<String>, UserStoreError>;
+}
+
+impl<'a, C> UserStoreFetchRolesOperation for UserStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn fetch_roles(&self, user_id: &str) -> Result<Vec<String>, UserStoreError> {
+        user_roles::table
+            .filter(user_roles::user_id.eq(user_id))
+            .select(user_roles::role)
+            .order(user_roles::role)
+            .load::<String>(self.conn)
+            .map_err(|err| UserStoreError::QueryError {
+                context: "Failed to fetch user roles".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
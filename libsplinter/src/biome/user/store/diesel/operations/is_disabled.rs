@@ -0,0 +1,42 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::UserStoreOperations;
+use crate::biome::user::store::diesel::schema::disabled_users;
+use crate::biome::user::store::error::UserStoreError;
+
+use diesel::prelude::*;
+
+pub(in crate::biome::user) trait UserStoreIsDisabledOperation {
+    fn is_disabled(&self, user_id: &str) -> Result<bool, UserStoreError>;
+}
+
+impl<'a, C> UserStoreIsDisabledOperation for UserStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn is_disabled(&self, user_id: &str) -> Result<bool, UserStoreError> {
+        disabled_users::table
+            .find(user_id)
+            .select(disabled_users::user_id)
+            .first::<String>(self.conn)
+            .optional()
+            .map(|user| user.is_some())
+            .map_err(|err| UserStoreError::QueryError {
+                context: "Failed to fetch disabled user".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
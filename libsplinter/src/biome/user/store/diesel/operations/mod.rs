@@ -14,8 +14,16 @@
 
 pub(super) mod add_user;
 pub(super) mod delete_user;
+#[cfg(feature = "biome-user-admin")]
+pub(super) mod fetch_roles;
 pub(super) mod fetch_user;
+#[cfg(feature = "biome-user-admin")]
+pub(super) mod is_disabled;
 pub(super) mod list_users;
+#[cfg(feature = "biome-user-admin")]
+pub(super) mod set_disabled;
+#[cfg(feature = "biome-user-admin")]
+pub(super) mod set_roles;
 pub(super) mod update_user;
 
 pub(super) struct UserStoreOperations<'a, C> {
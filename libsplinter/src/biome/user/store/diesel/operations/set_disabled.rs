@@ -0,0 +1,112 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::UserStoreOperations;
+use crate::biome::user::store::diesel::models::{DisabledUserModel, UserModel};
+use crate::biome::user::store::diesel::schema::{disabled_users, splinter_user};
+use crate::biome::user::store::error::UserStoreError;
+
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+    result::Error as QueryError,
+};
+
+pub(in crate::biome::user) trait UserStoreSetDisabledOperation {
+    fn set_disabled(&self, user_id: &str, disabled: bool) -> Result<(), UserStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> UserStoreSetDisabledOperation for UserStoreOperations<'a, diesel::pg::PgConnection> {
+    fn set_disabled(&self, user_id: &str, disabled: bool) -> Result<(), UserStoreError> {
+        let found = self
+            .conn
+            .transaction::<_, QueryError, _>(|| {
+                let user = splinter_user::table
+                    .find(user_id)
+                    .first::<UserModel>(self.conn)
+                    .optional()?;
+
+                if user.is_none() {
+                    return Ok(false);
+                }
+
+                delete(disabled_users::table.find(user_id)).execute(self.conn)?;
+                if disabled {
+                    insert_into(disabled_users::table)
+                        .values(DisabledUserModel {
+                            user_id: user_id.to_string(),
+                        })
+                        .execute(self.conn)?;
+                }
+                Ok(true)
+            })
+            .map_err(|err| UserStoreError::OperationError {
+                context: "Failed to set disabled status of user".to_string(),
+                source: Box::new(err),
+            })?;
+
+        if found {
+            Ok(())
+        } else {
+            Err(UserStoreError::NotFoundError(format!(
+                "Failed to find user: {}",
+                user_id
+            )))
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> UserStoreSetDisabledOperation
+    for UserStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_disabled(&self, user_id: &str, disabled: bool) -> Result<(), UserStoreError> {
+        let found = self
+            .conn
+            .transaction::<_, QueryError, _>(|| {
+                let user = splinter_user::table
+                    .find(user_id)
+                    .first::<UserModel>(self.conn)
+                    .optional()?;
+
+                if user.is_none() {
+                    return Ok(false);
+                }
+
+                delete(disabled_users::table.find(user_id)).execute(self.conn)?;
+                if disabled {
+                    insert_into(disabled_users::table)
+                        .values(DisabledUserModel {
+                            user_id: user_id.to_string(),
+                        })
+                        .execute(self.conn)?;
+                }
+                Ok(true)
+            })
+            .map_err(|err| UserStoreError::OperationError {
+                context: "Failed to set disabled status of user".to_string(),
+                source: Box::new(err),
+            })?;
+
+        if found {
+            Ok(())
+        } else {
+            Err(UserStoreError::NotFoundError(format!(
+                "Failed to find user: {}",
+                user_id
+            )))
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::UserStoreOperations;
+use crate::biome::user::store::diesel::models::{UserModel, UserRoleModel};
+use crate::biome::user::store::diesel::schema::{splinter_user, user_roles};
+use crate::biome::user::store::error::UserStoreError;
+
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+    result::Error as QueryError,
+};
+
+pub(in crate::biome::user) trait UserStoreSetRolesOperation {
+    fn set_roles(&self, user_id: &str, roles: &[String]) -> Result<(), UserStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> UserStoreSetRolesOperation for UserStoreOperations<'a, diesel::pg::PgConnection> {
+    fn set_roles(&self, user_id: &str, roles: &[String]) -> Result<(), UserStoreError> {
+        let mut roles = roles.to_vec();
+        roles.sort();
+        roles.dedup();
+        let role_models = roles
+            .into_iter()
+            .map(|role| UserRoleModel {
+                user_id: user_id.to_string(),
+                role,
+            })
+            .collect::<Vec<_>>();
+
+        let found = self
+            .conn
+            .transaction::<_, QueryError, _>(|| {
+                let user = splinter_user::table
+                    .find(user_id)
+                    .first::<UserModel>(self.conn)
+                    .optional()?;
+
+                if user.is_none() {
+                    return Ok(false);
+                }
+
+                delete(user_roles::table.filter(user_roles::user_id.eq(user_id)))
+                    .execute(self.conn)?;
+                if !role_models.is_empty() {
+                    insert_into(user_roles::table)
+                        .values(role_models)
+                        .execute(self.conn)?;
+                }
+                Ok(true)
+            })
+            .map_err(|err| UserStoreError::OperationError {
+                context: "Failed to set user roles".to_string(),
+                source: Box::new(err),
+            })?;
+
+        if found {
+            Ok(())
+        } else {
+            Err(UserStoreError::NotFoundError(format!(
+                "Failed to find user: {}",
+                user_id
+            )))
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> UserStoreSetRolesOperation for UserStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn set_roles(&self, user_id: &str, roles: &[String]) -> Result<(), UserStoreError> {
+        let mut roles = roles.to_vec();
+        roles.sort();
+        roles.dedup();
+        let role_models = roles
+            .into_iter()
+            .map(|role| UserRoleModel {
+                user_id: user_id.to_string(),
+                role,
+            })
+            .collect::<Vec<_>>();
+
+        let found = self
+            .conn
+            .transaction::<_, QueryError, _>(|| {
+                let user = splinter_user::table
+                    .find(user_id)
+                    .first::<UserModel>(self.conn)
+                    .optional()?;
+
+                if user.is_none() {
+                    return Ok(false);
+                }
+
+                delete(user_roles::table.filter(user_roles::user_id.eq(user_id)))
+                    .execute(self.conn)?;
+                if !role_models.is_empty() {
+                    insert_into(user_roles::table)
+                        .values(role_models)
+                        .execute(self.conn)?;
+                }
+                Ok(true)
+            })
+            .map_err(|err| UserStoreError::OperationError {
+                context: "Failed to set user roles".to_string(),
+                source: Box::new(err),
+            })?;
+
+        if found {
+            Ok(())
+        } else {
+            Err(UserStoreError::NotFoundError(format!(
+                "Failed to find user: {}",
+                user_id
+            )))
+        }
+    }
+}
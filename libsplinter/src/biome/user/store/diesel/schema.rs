@@ -17,3 +17,18 @@ table! {
         id -> Text,
     }
 }
+
+#[cfg(feature = "biome-user-admin")]
+table! {
+    user_roles (user_id, role) {
+        user_id -> Text,
+        role -> Text,
+    }
+}
+
+#[cfg(feature = "biome-user-admin")]
+table! {
+    disabled_users (user_id) {
+        user_id -> Text,
+    }
+}
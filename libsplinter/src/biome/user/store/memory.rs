@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+#[cfg(feature = "biome-user-admin")]
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 #[cfg(feature = "biome-credentials")]
-use crate::biome::credentials::store::{memory::MemoryCredentialsStore, CredentialsStore};
+use crate::biome::credentials::store::{
+    memory::MemoryCredentialsStore, CredentialsStore, CredentialsStoreError,
+};
 use crate::biome::user::store::{error::UserStoreError, User, UserStore};
 
 ///Implementation of UserStore that stores Users in memory. Useful for when
@@ -26,6 +30,10 @@ pub struct MemoryUserStore {
     inner: Arc<Mutex<HashMap<String, User>>>,
     #[cfg(feature = "biome-credentials")]
     credentials_store: MemoryCredentialsStore,
+    #[cfg(feature = "biome-user-admin")]
+    roles: Arc<Mutex<HashMap<String, Vec<String>>>>,
+    #[cfg(feature = "biome-user-admin")]
+    disabled: Arc<Mutex<HashSet<String>>>,
 }
 
 impl MemoryUserStore {
@@ -34,6 +42,10 @@ impl MemoryUserStore {
         Self {
             inner: Arc::new(Mutex::new(HashMap::new())),
             credentials_store,
+            #[cfg(feature = "biome-user-admin")]
+            roles: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(feature = "biome-user-admin")]
+            disabled: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -89,13 +101,35 @@ impl UserStore for MemoryUserStore {
             })?;
 
         if inner.remove(id).is_some() {
+            // Users that are authenticated by other means, such as OAuth, have no credentials
             #[cfg(feature = "biome-credentials")]
-            self.credentials_store
-                .remove_credentials(id)
-                .map_err(|err| UserStoreError::QueryError {
-                    context: format!("Cannot delete user {} from credentials store", id),
-                    source: Box::new(err),
-                })?;
+            match self.credentials_store.remove_credentials(id) {
+                Ok(()) | Err(CredentialsStoreError::NotFoundError(_)) => (),
+                Err(err) => {
+                    return Err(UserStoreError::QueryError {
+                        context: format!("Cannot delete user {} from credentials store", id),
+                        source: Box::new(err),
+                    })
+                }
+            }
+
+            #[cfg(feature = "biome-user-admin")]
+            {
+                self.roles
+                    .lock()
+                    .map_err(|_| UserStoreError::StorageError {
+                        context: "Cannot access user roles: mutex lock poisoned".to_string(),
+                        source: None,
+                    })?
+                    .remove(id);
+                self.disabled
+                    .lock()
+                    .map_err(|_| UserStoreError::StorageError {
+                        context: "Cannot access disabled users: mutex lock poisoned".to_string(),
+                        source: None,
+                    })?
+                    .remove(id);
+            }
 
             Ok(())
         } else {
@@ -136,4 +170,74 @@ impl UserStore for MemoryUserStore {
 
         Ok(inner.iter().map(|(_, v)| v.clone()).collect())
     }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn fetch_roles(&self, id: &str) -> Result<Vec<String>, UserStoreError> {
+        let roles = self
+            .roles
+            .lock()
+            .map_err(|_| UserStoreError::StorageError {
+                context: "Cannot access user roles: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        Ok(roles.get(id).cloned().unwrap_or_default())
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_roles(&self, id: &str, roles: &[String]) -> Result<(), UserStoreError> {
+        self.fetch_user(id)?;
+
+        let mut user_roles = self
+            .roles
+            .lock()
+            .map_err(|_| UserStoreError::StorageError {
+                context: "Cannot access user roles: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        let mut roles = roles.to_vec();
+        roles.sort();
+        roles.dedup();
+
+        if roles.is_empty() {
+            user_roles.remove(id);
+        } else {
+            user_roles.insert(id.to_string(), roles);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn is_disabled(&self, id: &str) -> Result<bool, UserStoreError> {
+        let disabled = self
+            .disabled
+            .lock()
+            .map_err(|_| UserStoreError::StorageError {
+                context: "Cannot access disabled users: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        Ok(disabled.contains(id))
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_disabled(&self, id: &str, disabled: bool) -> Result<(), UserStoreError> {
+        self.fetch_user(id)?;
+
+        let mut disabled_users =
+            self.disabled
+                .lock()
+                .map_err(|_| UserStoreError::StorageError {
+                    context: "Cannot access disabled users: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        if disabled {
+            disabled_users.insert(id.to_string());
+        } else {
+            disabled_users.remove(id);
+        }
+        Ok(())
+    }
 }
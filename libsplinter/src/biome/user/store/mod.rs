@@ -21,6 +21,10 @@ pub(in crate::biome) mod memory;
 
 pub use error::UserStoreError;
 
+/// The role that grants a user administrative access to the users of biome
+#[cfg(feature = "biome-user-admin")]
+pub const ADMIN_ROLE: &str = "admin";
+
 /// Represents a user of a splinter application
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct User {
@@ -62,7 +66,8 @@ pub trait UserStore: Send + Sync {
     ///  * `user` - The user with the updated information
     fn update_user(&self, updated_user: User) -> Result<(), UserStoreError>;
 
-    /// Removes a user from the underlying storage
+    /// Removes a user from the underlying storage, along with the user's credentials, keys and
+    /// refresh tokens
     ///
     /// # Arguments
     ///
@@ -78,6 +83,40 @@ pub trait UserStore: Send + Sync {
 
     /// List all users from the underlying storage
     fn list_users(&self) -> Result<Vec<User>, UserStoreError>;
+
+    /// Fetches the roles assigned to a user
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The unique id of the user whose roles are returned
+    #[cfg(feature = "biome-user-admin")]
+    fn fetch_roles(&self, id: &str) -> Result<Vec<String>, UserStoreError>;
+
+    /// Replaces the roles assigned to a user
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The unique id of the user
+    ///  * `roles` - The complete set of roles to assign to the user
+    #[cfg(feature = "biome-user-admin")]
+    fn set_roles(&self, id: &str, roles: &[String]) -> Result<(), UserStoreError>;
+
+    /// Returns whether or not a user's account has been disabled
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The unique id of the user
+    #[cfg(feature = "biome-user-admin")]
+    fn is_disabled(&self, id: &str) -> Result<bool, UserStoreError>;
+
+    /// Disables or re-enables a user's account
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The unique id of the user
+    ///  * `disabled` - Whether or not the account should be disabled
+    #[cfg(feature = "biome-user-admin")]
+    fn set_disabled(&self, id: &str, disabled: bool) -> Result<(), UserStoreError>;
 }
 
 pub trait CloneBoxUserStore: UserStore {
@@ -113,4 +152,24 @@ where
     fn list_users(&self) -> Result<Vec<User>, UserStoreError> {
         (**self).list_users()
     }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn fetch_roles(&self, id: &str) -> Result<Vec<String>, UserStoreError> {
+        (**self).fetch_roles(id)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_roles(&self, id: &str, roles: &[String]) -> Result<(), UserStoreError> {
+        (**self).set_roles(id, roles)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn is_disabled(&self, id: &str) -> Result<bool, UserStoreError> {
+        (**self).is_disabled(id)
+    }
+
+    #[cfg(feature = "biome-user-admin")]
+    fn set_disabled(&self, id: &str, disabled: bool) -> Result<(), UserStoreError> {
+        (**self).set_disabled(id, disabled)
+    }
 }
@@ -74,18 +74,24 @@ impl ServiceInstances for SplinterStateServiceInstances {
             })?
             .ok_or(ServiceAddInstanceError::CircuitDoesNotExist)?;
 
-        let service_def = if !service_id.starts_with("admin::") {
-            circuit
-                .roster()
-                .iter()
-                .find(|service| service.service_id == service_id)
-                .cloned()
-        } else {
+        let service_def = if service_id.starts_with("admin::") {
             Some(
                 ServiceDefinition::builder(service_id.clone(), "admin".into())
                     .with_allowed_nodes(vec![self.splinter_node.id().to_string()])
                     .build(),
             )
+        } else if cfg!(feature = "health") && service_id.starts_with("health::") {
+            Some(
+                ServiceDefinition::builder(service_id.clone(), "health".into())
+                    .with_allowed_nodes(vec![self.splinter_node.id().to_string()])
+                    .build(),
+            )
+        } else {
+            circuit
+                .roster()
+                .iter()
+                .find(|service| service.service_id == service_id)
+                .cloned()
         };
 
         if let Some(service_def) = service_def {
@@ -21,6 +21,8 @@ use crate::protos::circuit::{
 use protobuf::Message;
 
 const ADMIN_SERVICE_ID_PREFIX: &str = "admin::";
+#[cfg(feature = "health")]
+const HEALTH_SERVICE_ID_PREFIX: &str = "health::";
 
 // Implements a handler that handles AdminDirectMessage
 pub struct AdminDirectMessageHandler {
@@ -128,13 +130,15 @@ impl AdminDirectMessageHandler {
             .map_err(|err| DispatchError::HandleError(err.context()))?;
 
         let response = if circuit.is_some() {
-            let node_id = &recipient[ADMIN_SERVICE_ID_PREFIX.len()..];
+            // The recipient was checked above, so it always has a node ID
+            let node_id = admin_circuit_service_node_id(recipient).unwrap_or_default();
             // If the service is on this node send message to the service, otherwise
             // send the message to the node the service is connected to
             let target_node = if node_id != self.node_id {
                 node_id
             } else {
-                // The internal admin service is at the node id with an identical name
+                // The internal admin and health services are at the node id with an identical
+                // name
                 recipient
             };
 
@@ -174,7 +178,24 @@ fn create_circuit_error_msg(
 }
 
 fn is_admin_service_id(service_id: &str) -> bool {
-    service_id.starts_with(ADMIN_SERVICE_ID_PREFIX)
+    admin_circuit_service_node_id(service_id).is_some()
+}
+
+/// Returns the ID of the node that runs the given service, if the service is one that is allowed
+/// to send and receive messages on the admin circuit.
+fn admin_circuit_service_node_id(service_id: &str) -> Option<&str> {
+    if service_id.starts_with(ADMIN_SERVICE_ID_PREFIX) {
+        return Some(&service_id[ADMIN_SERVICE_ID_PREFIX.len()..]);
+    }
+
+    #[cfg(feature = "health")]
+    {
+        if service_id.starts_with(HEALTH_SERVICE_ID_PREFIX) {
+            return Some(&service_id[HEALTH_SERVICE_ID_PREFIX.len()..]);
+        }
+    }
+
+    None
 }
 
 #[cfg(test)]
@@ -466,6 +487,53 @@ mod tests {
         )
     }
 
+    /// Send a message from a health service to another node's health service via the admin
+    /// circuit. Expect that the message is sent to the node that hosts the target health service.
+    #[cfg(feature = "health")]
+    #[test]
+    fn test_send_health_direct_message_via_admin_circuit() {
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let circuit_directory = CircuitDirectory::new();
+
+        let state = SplinterState::new("memory".to_string(), circuit_directory);
+
+        let handler = AdminDirectMessageHandler::new("1234".into(), state);
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = AdminDirectMessage::new();
+        direct_message.set_circuit("admin".into());
+        direct_message.set_sender("health::1234".into());
+        direct_message.set_recipient("health::5678".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("random_corr_id".into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        assert_eq!(
+            Ok(()),
+            dispatcher.dispatch(
+                "health::1234".into(),
+                &CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                direct_bytes
+            )
+        );
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "5678",
+            CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+            |msg: AdminDirectMessage| {
+                assert_eq!(msg.get_circuit(), "admin");
+                assert_eq!(msg.get_sender(), "health::1234");
+                assert_eq!(msg.get_recipient(), "health::5678");
+                assert_eq!(msg.get_payload(), b"test");
+            },
+        )
+    }
+
     fn assert_network_message<M: protobuf::Message, F: Fn(M)>(
         message: Vec<u8>,
         recipient: String,
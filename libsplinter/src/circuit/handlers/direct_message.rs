@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use crate::circuit::handlers::create_message;
+#[cfg(feature = "circuit-rate-limit")]
+use crate::circuit::rate_limit::RateLimiter;
 use crate::circuit::{ServiceId, SplinterState};
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::protos::circuit::{
@@ -25,6 +27,8 @@ use protobuf::Message;
 pub struct CircuitDirectMessageHandler {
     node_id: String,
     state: SplinterState,
+    #[cfg(feature = "circuit-rate-limit")]
+    rate_limiter: Option<RateLimiter>,
 }
 
 impl Handler for CircuitDirectMessageHandler {
@@ -110,6 +114,24 @@ impl Handler for CircuitDirectMessageHandler {
                         recipient
                     ));
 
+                    let msg_bytes = error_message.write_to_bytes()?;
+                    let network_msg_bytes =
+                        create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
+                    (network_msg_bytes, context.source_peer_id().to_string())
+                } else if let Some(err_msg) = self.check_rate_limit(
+                    context.source_peer_id(),
+                    circuit_name,
+                    msg.get_payload().len(),
+                ) {
+                    // if the sending peer or the circuit has exceeded its rate limit, reject the
+                    // message with a circuit error
+                    let mut error_message = CircuitError::new();
+                    error_message.set_correlation_id(msg.get_correlation_id().to_string());
+                    error_message.set_service_id(msg_sender.into());
+                    error_message.set_circuit_name(circuit_name.into());
+                    error_message.set_error(CircuitError_Error::ERROR_RATE_LIMIT_EXCEEDED);
+                    error_message.set_error_message(err_msg);
+
                     let msg_bytes = error_message.write_to_bytes()?;
                     let network_msg_bytes =
                         create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
@@ -214,7 +236,38 @@ impl Handler for CircuitDirectMessageHandler {
 
 impl CircuitDirectMessageHandler {
     pub fn new(node_id: String, state: SplinterState) -> Self {
-        CircuitDirectMessageHandler { node_id, state }
+        CircuitDirectMessageHandler {
+            node_id,
+            state,
+            #[cfg(feature = "circuit-rate-limit")]
+            rate_limiter: None,
+        }
+    }
+
+    /// Limit the rate of direct messages sent by each peer and on each circuit. Messages over the
+    /// limit are rejected with an `ERROR_RATE_LIMIT_EXCEEDED` circuit error.
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Returns a description of the exceeded limit, if the message should be rejected.
+    #[cfg(feature = "circuit-rate-limit")]
+    fn check_rate_limit(&self, peer_id: &str, circuit_name: &str, len: usize) -> Option<String> {
+        let rate_limiter = self.rate_limiter.as_ref()?;
+        match rate_limiter.check(peer_id, circuit_name, len) {
+            Ok(()) => None,
+            Err(err) => {
+                debug!("Rejecting direct message on {}: {}", circuit_name, err);
+                Some(err.to_string())
+            }
+        }
+    }
+
+    #[cfg(not(feature = "circuit-rate-limit"))]
+    fn check_rate_limit(&self, _: &str, _: &str, _: usize) -> Option<String> {
+        None
     }
 }
 
@@ -723,6 +776,109 @@ mod tests {
         )
     }
 
+    // Test that a direct message is rejected with a rate limit error once the sending peer has
+    // used its burst of messages
+    #[cfg(feature = "circuit-rate-limit")]
+    #[test]
+    fn test_circuit_direct_message_handler_rate_limit_exceeded() {
+        use crate::circuit::rate_limit::{RateLimit, RateLimiter};
+
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        // Add circuit and service to splinter state
+        let circuit = Circuit::builder()
+            .with_id("alpha".into())
+            .with_auth(AuthorizationType::Trust)
+            .with_members(vec!["123".into()])
+            .with_roster(vec!["abc".into(), "def".into()])
+            .with_persistence(PersistenceType::Any)
+            .with_durability(DurabilityType::NoDurability)
+            .with_routes(RouteType::Any)
+            .with_circuit_management_type("circuit_direct_test_app".into())
+            .build()
+            .expect("Should have built a correct circuit");
+
+        let mut circuit_directory = CircuitDirectory::new();
+        circuit_directory.add_circuit("alpha".to_string(), circuit);
+
+        let state = SplinterState::new("memory".to_string(), circuit_directory);
+
+        let node = SplinterNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()]);
+        let service_abc = Service::new(
+            "abc".to_string(),
+            Some("abc_network".to_string()),
+            node.clone(),
+        );
+        let service_def = Service::new("def".to_string(), Some("def_network".to_string()), node);
+        let abc_id = ServiceId::new("alpha".into(), "abc".into());
+        let def_id = ServiceId::new("alpha".into(), "def".into());
+        state.add_service(abc_id, service_abc).unwrap();
+        state.add_service(def_id, service_def).unwrap();
+
+        // Add direct message handler, which allows a single message per peer, to the dispatcher
+        let rate_limiter = RateLimiter::new(
+            RateLimit::new().with_messages_per_second(1, 1),
+            RateLimit::new(),
+        );
+        let handler = CircuitDirectMessageHandler::new("123".to_string(), state)
+            .with_rate_limiter(rate_limiter.clone());
+        dispatcher.set_handler(Box::new(handler));
+
+        // Create the direct message
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("alpha".into());
+        direct_message.set_sender("def".into());
+        direct_message.set_recipient("abc".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("1234".into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        // dispatch the direct message twice
+        for _ in 0..2 {
+            dispatcher
+                .dispatch(
+                    "def".into(),
+                    &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                    direct_bytes.clone(),
+                )
+                .unwrap();
+        }
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "abc_network",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_sender(), "def");
+                assert_eq!(msg.get_recipient(), "abc");
+            },
+        );
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "def",
+            CircuitMessageType::CIRCUIT_ERROR_MESSAGE,
+            |msg: CircuitError| {
+                assert_eq!(msg.get_service_id(), "def");
+                assert_eq!(
+                    msg.get_error(),
+                    CircuitError_Error::ERROR_RATE_LIMIT_EXCEEDED
+                );
+                assert_eq!(msg.get_correlation_id(), "1234");
+            },
+        );
+
+        let metrics = rate_limiter.metrics();
+        assert_eq!(1, metrics.peers["def"].accepted_messages);
+        assert_eq!(1, metrics.peers["def"].rejected_messages);
+    }
+
     fn assert_network_message<M: protobuf::Message, F: Fn(M)>(
         message: Vec<u8>,
         recipient: String,
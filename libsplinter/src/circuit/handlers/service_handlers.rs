@@ -73,19 +73,23 @@ impl Handler for ServiceConnectRequestHandler {
                     .map_err(|err| DispatchError::HandleError(err.context()))?
             {
                 // This should never return None since we just checked if it exists.
-                // If admin service create a service defination for the admin service
+                // If admin or health service create a service defination for the service
                 let service = {
-                    if !service_id.starts_with("admin::") {
+                    if service_id.starts_with("admin::") {
+                        ServiceDefinition::builder(service_id.into(), "admin".into())
+                            .with_allowed_nodes(vec![self.node_id.to_string()])
+                            .build()
+                    } else if cfg!(feature = "health") && service_id.starts_with("health::") {
+                        ServiceDefinition::builder(service_id.into(), "health".into())
+                            .with_allowed_nodes(vec![self.node_id.to_string()])
+                            .build()
+                    } else {
                         circuit
                             .roster()
                             .iter()
                             .find(|service| service.service_id == service_id)
                             .expect("Cannot find service in circuit")
                             .clone()
-                    } else {
-                        ServiceDefinition::builder(service_id.into(), "admin".into())
-                            .with_allowed_nodes(vec![self.node_id.to_string()])
-                            .build()
                     }
                 };
 
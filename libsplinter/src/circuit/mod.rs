@@ -16,6 +16,8 @@
 pub mod component;
 pub mod directory;
 pub mod handlers;
+#[cfg(feature = "circuit-rate-limit")]
+pub mod rate_limit;
 #[cfg(feature = "routing-table")]
 pub mod routing;
 pub mod service;
@@ -32,6 +34,8 @@ use std::fmt::{self, Display, Formatter};
 use std::sync::{Arc, RwLock};
 
 use crate::circuit::directory::CircuitDirectory;
+#[cfg(feature = "circuit-rate-limit")]
+use crate::circuit::rate_limit::RateLimiter;
 use crate::circuit::service::{Service, ServiceId, SplinterNode};
 use crate::circuit::store::{CircuitFilter, CircuitIter, CircuitStore, CircuitStoreError};
 use crate::storage::get_storage;
@@ -323,7 +327,10 @@ impl Roster {
             Roster::Standard(roster) => roster
                 .iter()
                 .any(|service_def| service_def.service_id == service_name),
-            Roster::Admin => service_name.starts_with("admin::"),
+            Roster::Admin => {
+                service_name.starts_with("admin::")
+                    || (cfg!(feature = "health") && service_name.starts_with("health::"))
+            }
         }
     }
 
@@ -395,6 +402,9 @@ pub struct SplinterState {
     circuit_directory: Arc<RwLock<CircuitDirectory>>,
     // Service id to Service that contains the node the service is connected to. Not persisted.
     service_directory: Arc<RwLock<HashMap<ServiceId, Service>>>,
+    // The rate limiter whose circuit buckets are removed along with the circuits
+    #[cfg(feature = "circuit-rate-limit")]
+    rate_limiter: Option<RateLimiter>,
 }
 
 impl SplinterState {
@@ -403,9 +413,19 @@ impl SplinterState {
             storage_location,
             circuit_directory: Arc::new(RwLock::new(circuit_directory)),
             service_directory: Arc::new(RwLock::new(HashMap::new())),
+            #[cfg(feature = "circuit-rate-limit")]
+            rate_limiter: None,
         }
     }
 
+    /// Removes a circuit's rate limit buckets and statistics from the given limiter when the
+    /// circuit is removed.
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
     pub fn storage_location(&self) -> &str {
         &self.storage_location
     }
@@ -530,6 +550,10 @@ impl SplinterState {
             circuit_directory.remove_circuit(name);
         }
         self.commit_circuit_directory()?;
+        #[cfg(feature = "circuit-rate-limit")]
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.remove_circuit(name);
+        }
         Ok(())
     }
 
@@ -0,0 +1,501 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token-bucket rate limiting for circuit messages.
+//!
+//! A `RateLimiter` tracks the messages routed by this node, both per peer and per circuit. Each
+//! peer and each circuit has its own bucket of message and byte tokens, which refill at a
+//! configured rate up to a configured burst size. A message is only accepted if every bucket it
+//! is counted against has enough tokens.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// The limits applied to a single peer or circuit.
+///
+/// Either dimension may be left unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimit {
+    messages: Option<(u64, u64)>,
+    bytes: Option<(u64, u64)>,
+}
+
+impl RateLimit {
+    /// Construct a new limit, which does not limit anything until rates are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the number of messages per second, allowing bursts of up to `burst` messages.
+    pub fn with_messages_per_second(mut self, rate: u64, burst: u64) -> Self {
+        self.messages = Some((rate, burst));
+        self
+    }
+
+    /// Limit the number of payload bytes per second, allowing bursts of up to `burst` bytes.
+    pub fn with_bytes_per_second(mut self, rate: u64, burst: u64) -> Self {
+        self.bytes = Some((rate, burst));
+        self
+    }
+
+    /// Returns the message rate and burst size, if messages are limited.
+    pub fn messages_per_second(&self) -> Option<(u64, u64)> {
+        self.messages
+    }
+
+    /// Returns the byte rate and burst size, if bytes are limited.
+    pub fn bytes_per_second(&self) -> Option<(u64, u64)> {
+        self.bytes
+    }
+
+    fn is_unlimited(&self) -> bool {
+        self.messages.is_none() && self.bytes.is_none()
+    }
+}
+
+/// The reason a message was rejected by a `RateLimiter`.
+#[derive(Debug, PartialEq)]
+pub enum RateLimitError {
+    /// The peer, identified by its peer ID, has exceeded its limit.
+    PeerLimitExceeded(String),
+    /// The circuit, identified by its circuit ID, has exceeded its limit.
+    CircuitLimitExceeded(String),
+}
+
+impl Error for RateLimitError {}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RateLimitError::PeerLimitExceeded(peer_id) => {
+                write!(f, "Rate limit exceeded for peer {}", peer_id)
+            }
+            RateLimitError::CircuitLimitExceeded(circuit_id) => {
+                write!(f, "Rate limit exceeded for circuit {}", circuit_id)
+            }
+        }
+    }
+}
+
+/// Counts of the messages and bytes accepted and rejected for a single peer or circuit.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimitStats {
+    pub accepted_messages: u64,
+    pub accepted_bytes: u64,
+    pub rejected_messages: u64,
+    pub rejected_bytes: u64,
+}
+
+impl RateLimitStats {
+    fn record(&mut self, accepted: bool, bytes: usize) {
+        if accepted {
+            self.accepted_messages += 1;
+            self.accepted_bytes += bytes as u64;
+        } else {
+            self.rejected_messages += 1;
+            self.rejected_bytes += bytes as u64;
+        }
+    }
+}
+
+/// A snapshot of the statistics of a `RateLimiter`, keyed by peer ID and circuit ID.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitMetrics {
+    pub peers: HashMap<String, RateLimitStats>,
+    pub circuits: HashMap<String, RateLimitStats>,
+}
+
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, burst: u64, now: Instant) -> Self {
+        // A bucket that cannot hold a single token would never accept anything.
+        let capacity = burst.max(1) as f64;
+        TokenBucket {
+            rate: rate as f64,
+            capacity,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill);
+        let elapsed_secs = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9;
+        self.tokens = (self.tokens + elapsed_secs * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    fn has(&self, amount: f64) -> bool {
+        // Amounts larger than the bucket are allowed through when the bucket is full, otherwise
+        // they could never be sent.
+        self.tokens >= amount.min(self.capacity)
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.tokens = (self.tokens - amount).max(0.0);
+    }
+}
+
+/// The message and byte buckets for a single peer or circuit.
+struct Buckets {
+    messages: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+    stats: RateLimitStats,
+}
+
+impl Buckets {
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        Buckets {
+            messages: limit
+                .messages
+                .map(|(rate, burst)| TokenBucket::new(rate, burst, now)),
+            bytes: limit
+                .bytes
+                .map(|(rate, burst)| TokenBucket::new(rate, burst, now)),
+            stats: RateLimitStats::default(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if let Some(bucket) = self.messages.as_mut() {
+            bucket.refill(now);
+        }
+        if let Some(bucket) = self.bytes.as_mut() {
+            bucket.refill(now);
+        }
+    }
+
+    fn has(&self, bytes: usize) -> bool {
+        self.messages.as_ref().map(|b| b.has(1.0)).unwrap_or(true)
+            && self
+                .bytes
+                .as_ref()
+                .map(|b| b.has(bytes as f64))
+                .unwrap_or(true)
+    }
+
+    fn take(&mut self, bytes: usize) {
+        if let Some(bucket) = self.messages.as_mut() {
+            bucket.take(1.0);
+        }
+        if let Some(bucket) = self.bytes.as_mut() {
+            bucket.take(bytes as f64);
+        }
+    }
+}
+
+#[derive(Default)]
+struct RateLimiterState {
+    peers: HashMap<String, Buckets>,
+    circuits: HashMap<String, Buckets>,
+}
+
+/// Applies token-bucket limits to the messages sent by peers and on circuits.
+///
+/// The limiter may be cloned; all clones share the same buckets.
+#[derive(Clone, Default)]
+pub struct RateLimiter {
+    peer_limit: RateLimit,
+    circuit_limit: RateLimit,
+    state: Arc<Mutex<RateLimiterState>>,
+}
+
+impl RateLimiter {
+    /// Construct a new `RateLimiter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_limit` - The limit applied to each peer
+    /// * `circuit_limit` - The limit applied to each circuit
+    pub fn new(peer_limit: RateLimit, circuit_limit: RateLimit) -> Self {
+        RateLimiter {
+            peer_limit,
+            circuit_limit,
+            state: Arc::new(Mutex::new(RateLimiterState::default())),
+        }
+    }
+
+    /// Checks whether a message of the given size, sent by the given peer on the given circuit,
+    /// is within the limits. Tokens are only consumed if the message is accepted.
+    pub fn check(
+        &self,
+        peer_id: &str,
+        circuit_id: &str,
+        bytes: usize,
+    ) -> Result<(), RateLimitError> {
+        let now = Instant::now();
+        let mut state = mutex_lock_unwrap!(self.state);
+        let RateLimiterState { peers, circuits } = &mut *state;
+
+        let peer = peers
+            .entry(peer_id.to_string())
+            .or_insert_with(|| Buckets::new(&self.peer_limit, now));
+        let circuit = circuits
+            .entry(circuit_id.to_string())
+            .or_insert_with(|| Buckets::new(&self.circuit_limit, now));
+
+        peer.refill(now);
+        circuit.refill(now);
+
+        let result = if !peer.has(bytes) {
+            Err(RateLimitError::PeerLimitExceeded(peer_id.to_string()))
+        } else if !circuit.has(bytes) {
+            Err(RateLimitError::CircuitLimitExceeded(circuit_id.to_string()))
+        } else {
+            peer.take(bytes);
+            circuit.take(bytes);
+            Ok(())
+        };
+
+        peer.stats.record(result.is_ok(), bytes);
+        circuit.stats.record(result.is_ok(), bytes);
+
+        #[cfg(feature = "metrics")]
+        {
+            let outcome = if result.is_ok() {
+                "accepted"
+            } else {
+                "rejected"
+            };
+            for (limit, id) in &[("peer", peer_id), ("circuit", circuit_id)] {
+                crate::metrics::RATE_LIMIT_MESSAGES
+                    .with_label_values(&[limit, id, outcome])
+                    .inc();
+                crate::metrics::RATE_LIMIT_BYTES
+                    .with_label_values(&[limit, id, outcome])
+                    .inc_by(bytes as i64);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        match &result {
+            Err(RateLimitError::PeerLimitExceeded(id)) => crate::metrics::RATE_LIMITED_MESSAGES
+                .with_label_values(&["peer", id])
+                .inc(),
+            Err(RateLimitError::CircuitLimitExceeded(id)) => crate::metrics::RATE_LIMITED_MESSAGES
+                .with_label_values(&["circuit", id])
+                .inc(),
+            Ok(()) => (),
+        }
+
+        result
+    }
+
+    /// Returns whether this limiter has any limits configured.
+    pub fn is_enabled(&self) -> bool {
+        !(self.peer_limit.is_unlimited() && self.circuit_limit.is_unlimited())
+    }
+
+    /// Removes the buckets and statistics of a peer, such as when it has disconnected.
+    pub fn remove_peer(&self, peer_id: &str) {
+        mutex_lock_unwrap!(self.state).peers.remove(peer_id);
+        #[cfg(feature = "metrics")]
+        remove_metrics("peer", peer_id);
+    }
+
+    /// Removes the buckets and statistics of a circuit, such as when it has been disbanded.
+    pub fn remove_circuit(&self, circuit_id: &str) {
+        mutex_lock_unwrap!(self.state).circuits.remove(circuit_id);
+        #[cfg(feature = "metrics")]
+        remove_metrics("circuit", circuit_id);
+    }
+
+    /// Returns a snapshot of the accepted and rejected message counts.
+    pub fn metrics(&self) -> RateLimitMetrics {
+        let state = mutex_lock_unwrap!(self.state);
+        RateLimitMetrics {
+            peers: state
+                .peers
+                .iter()
+                .map(|(id, buckets)| (id.clone(), buckets.stats))
+                .collect(),
+            circuits: state
+                .circuits
+                .iter()
+                .map(|(id, buckets)| (id.clone(), buckets.stats))
+                .collect(),
+        }
+    }
+}
+
+/// Removes the Prometheus series of a peer or circuit that is no longer tracked.
+#[cfg(feature = "metrics")]
+fn remove_metrics(limit: &str, id: &str) {
+    // The series may not exist if no message was checked, or none was rejected
+    let _ = crate::metrics::RATE_LIMITED_MESSAGES.remove_label_values(&[limit, id]);
+    for outcome in &["accepted", "rejected"] {
+        let _ = crate::metrics::RATE_LIMIT_MESSAGES.remove_label_values(&[limit, id, outcome]);
+        let _ = crate::metrics::RATE_LIMIT_BYTES.remove_label_values(&[limit, id, outcome]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Test that a limiter with no limits accepts all messages, and still records statistics.
+    #[test]
+    fn test_unlimited() {
+        let limiter = RateLimiter::new(RateLimit::new(), RateLimit::new());
+        assert!(!limiter.is_enabled());
+
+        for _ in 0..1000 {
+            assert_eq!(Ok(()), limiter.check("peer", "circuit", 1024));
+        }
+
+        let metrics = limiter.metrics();
+        assert_eq!(1000, metrics.peers["peer"].accepted_messages);
+        assert_eq!(1000 * 1024, metrics.circuits["circuit"].accepted_bytes);
+    }
+
+    /// Test that a peer is rejected once its message burst is used, without affecting other
+    /// peers, and that the rejection is recorded for both the peer and the circuit.
+    #[test]
+    fn test_peer_message_limit() {
+        let limiter = RateLimiter::new(
+            RateLimit::new().with_messages_per_second(1, 3),
+            RateLimit::new(),
+        );
+
+        for _ in 0..3 {
+            assert_eq!(Ok(()), limiter.check("peer_a", "circuit", 10));
+        }
+        assert_eq!(
+            Err(RateLimitError::PeerLimitExceeded("peer_a".into())),
+            limiter.check("peer_a", "circuit", 10)
+        );
+        assert_eq!(Ok(()), limiter.check("peer_b", "circuit", 10));
+
+        let metrics = limiter.metrics();
+        assert_eq!(3, metrics.peers["peer_a"].accepted_messages);
+        assert_eq!(1, metrics.peers["peer_a"].rejected_messages);
+        assert_eq!(4, metrics.circuits["circuit"].accepted_messages);
+        assert_eq!(1, metrics.circuits["circuit"].rejected_messages);
+    }
+
+    /// Test that a circuit is rejected once its byte burst is used, across all peers, and that a
+    /// rejected message does not consume the peer's tokens.
+    #[test]
+    fn test_circuit_byte_limit() {
+        let limiter = RateLimiter::new(
+            RateLimit::new().with_messages_per_second(1, 2),
+            RateLimit::new().with_bytes_per_second(1, 100),
+        );
+
+        assert_eq!(Ok(()), limiter.check("peer_a", "circuit", 60));
+        assert_eq!(
+            Err(RateLimitError::CircuitLimitExceeded("circuit".into())),
+            limiter.check("peer_b", "circuit", 60)
+        );
+        // peer_b still has both of its message tokens
+        assert_eq!(Ok(()), limiter.check("peer_b", "other", 60));
+        assert_eq!(Ok(()), limiter.check("peer_b", "other", 10));
+    }
+
+    /// Test that a message larger than the burst size is accepted when the bucket is full.
+    #[test]
+    fn test_oversized_message() {
+        let limiter = RateLimiter::new(
+            RateLimit::new().with_bytes_per_second(10, 100),
+            RateLimit::new(),
+        );
+
+        assert_eq!(Ok(()), limiter.check("peer", "circuit", 1000));
+        assert_eq!(
+            Err(RateLimitError::PeerLimitExceeded("peer".into())),
+            limiter.check("peer", "circuit", 1)
+        );
+    }
+
+    /// Test that removing a peer or circuit drops its statistics, and that it starts over with a
+    /// full bucket when it is seen again.
+    #[test]
+    fn test_remove_peer_and_circuit() {
+        let limiter = RateLimiter::new(
+            RateLimit::new().with_messages_per_second(1, 1),
+            RateLimit::new(),
+        );
+
+        assert_eq!(Ok(()), limiter.check("peer", "circuit", 10));
+        assert!(limiter.check("peer", "circuit", 10).is_err());
+
+        limiter.remove_peer("peer");
+        limiter.remove_circuit("circuit");
+        let metrics = limiter.metrics();
+        assert!(metrics.peers.is_empty());
+        assert!(metrics.circuits.is_empty());
+
+        assert_eq!(Ok(()), limiter.check("peer", "circuit", 10));
+    }
+
+    /// Test that the accepted and rejected counts are exported to Prometheus, and that a removed
+    /// peer's series are dropped.
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_exported_metrics() {
+        let limiter = RateLimiter::new(
+            RateLimit::new().with_messages_per_second(1, 1),
+            RateLimit::new(),
+        );
+
+        assert_eq!(Ok(()), limiter.check("metrics_peer", "metrics_circuit", 10));
+        assert!(limiter
+            .check("metrics_peer", "metrics_circuit", 10)
+            .is_err());
+
+        let text = crate::metrics::gather().expect("Unable to gather metrics");
+        assert!(text.contains(
+            "splinter_rate_limit_messages_total{id=\"metrics_peer\",limit=\"peer\",\
+             result=\"rejected\"} 1"
+        ));
+        assert!(text.contains(
+            "splinter_rate_limit_bytes_total{id=\"metrics_circuit\",limit=\"circuit\",\
+             result=\"accepted\"} 10"
+        ));
+
+        limiter.remove_peer("metrics_peer");
+        let text = crate::metrics::gather().expect("Unable to gather metrics");
+        assert!(!text.contains("id=\"metrics_peer\""));
+    }
+
+    /// Test that the tokens of a bucket are refilled over time, up to its capacity.
+    #[test]
+    fn test_token_bucket_refill() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(10, 5, start);
+
+        bucket.take(5.0);
+        assert!(!bucket.has(1.0));
+
+        bucket.refill(start + Duration::from_millis(200));
+        assert!(bucket.has(1.5));
+        assert!(!bucket.has(2.5));
+
+        bucket.refill(start + Duration::from_secs(10));
+        assert!(bucket.has(5.0));
+        bucket.take(5.0);
+        assert!(!bucket.has(1.0));
+    }
+}
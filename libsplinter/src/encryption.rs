@@ -0,0 +1,170 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! AES-256-GCM encryption of secrets that are kept in storage, such as users' TOTP secrets and
+//! OAuth provider tokens.
+
+use std::error::Error;
+use std::fmt;
+
+use openssl::error::ErrorStack;
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use rand::Rng;
+
+use crate::hex::{parse_hex, to_hex};
+
+/// The length, in bytes, of an encryption key
+pub const KEY_LENGTH: usize = 32;
+
+const NONCE_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 16;
+
+/// Generates a new random encryption key.
+pub fn generate_key() -> Vec<u8> {
+    let mut key = vec![0u8; KEY_LENGTH];
+    rand::thread_rng().fill(&mut key[..]);
+    key
+}
+
+/// Encrypts the given bytes; the result is a hex string of the nonce, ciphertext, and
+/// authentication tag.
+///
+/// # Arguments
+///
+/// * `plaintext` - The bytes to be encrypted
+/// * `key` - The `KEY_LENGTH`-byte key used to encrypt the bytes
+pub fn encrypt(plaintext: &[u8], key: &[u8]) -> Result<String, EncryptionError> {
+    check_key(key)?;
+
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::thread_rng().fill(&mut nonce[..]);
+    let mut tag = [0u8; TAG_LENGTH];
+    let ciphertext = encrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(&nonce),
+        &[],
+        plaintext,
+        &mut tag,
+    )?;
+
+    let mut encrypted = nonce.to_vec();
+    encrypted.extend(ciphertext);
+    encrypted.extend(&tag);
+    Ok(to_hex(&encrypted))
+}
+
+/// Decrypts bytes that were encrypted with `encrypt`.
+///
+/// # Arguments
+///
+/// * `encrypted` - The encrypted bytes
+/// * `key` - The key that was used to encrypt the bytes
+pub fn decrypt(encrypted: &str, key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    check_key(key)?;
+
+    let encrypted =
+        parse_hex(encrypted).map_err(|err| EncryptionError::InvalidCiphertext(err.to_string()))?;
+    if encrypted.len() < NONCE_LENGTH + TAG_LENGTH {
+        return Err(EncryptionError::InvalidCiphertext(
+            "ciphertext is too short".into(),
+        ));
+    }
+
+    let (nonce, rest) = encrypted.split_at(NONCE_LENGTH);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LENGTH);
+    decrypt_aead(
+        Cipher::aes_256_gcm(),
+        key,
+        Some(nonce),
+        &[],
+        ciphertext,
+        tag,
+    )
+    .map_err(|_| {
+        EncryptionError::InvalidCiphertext(
+            "unable to decrypt ciphertext; the key may be incorrect".into(),
+        )
+    })
+}
+
+fn check_key(key: &[u8]) -> Result<(), EncryptionError> {
+    if key.len() == KEY_LENGTH {
+        Ok(())
+    } else {
+        Err(EncryptionError::InvalidKey(format!(
+            "expected a {}-byte key, but the key is {} bytes",
+            KEY_LENGTH,
+            key.len()
+        )))
+    }
+}
+
+/// Errors that may occur when encrypting or decrypting
+#[derive(Debug)]
+pub enum EncryptionError {
+    /// A cryptographic operation failed
+    CryptoError(String),
+    /// The ciphertext could not be parsed or decrypted
+    InvalidCiphertext(String),
+    /// The encryption key is invalid
+    InvalidKey(String),
+}
+
+impl Error for EncryptionError {}
+
+impl fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncryptionError::CryptoError(msg) => {
+                write!(f, "cryptographic operation failed: {}", msg)
+            }
+            EncryptionError::InvalidCiphertext(msg) => write!(f, "invalid ciphertext: {}", msg),
+            EncryptionError::InvalidKey(msg) => write!(f, "invalid encryption key: {}", msg),
+        }
+    }
+}
+
+impl From<ErrorStack> for EncryptionError {
+    fn from(err: ErrorStack) -> Self {
+        EncryptionError::CryptoError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that encrypted bytes can be decrypted with the same key, but not with a different
+    /// key, and that keys of the wrong length are rejected.
+    #[test]
+    fn encrypt_and_decrypt() {
+        let key = generate_key();
+
+        let encrypted = encrypt(b"secret", &key).expect("Failed to encrypt");
+        assert_eq!(
+            decrypt(&encrypted, &key).expect("Failed to decrypt"),
+            b"secret".to_vec()
+        );
+
+        match decrypt(&encrypted, &generate_key()) {
+            Err(EncryptionError::InvalidCiphertext(_)) => (),
+            res => panic!("Expected InvalidCiphertext, got {:?}", res),
+        }
+        match encrypt(b"secret", &key[1..]) {
+            Err(EncryptionError::InvalidKey(_)) => (),
+            res => panic!("Expected InvalidKey, got {:?}", res),
+        }
+    }
+}
@@ -70,11 +70,15 @@ mod collections;
 pub mod consensus;
 #[cfg(feature = "diesel")]
 pub mod database;
+#[cfg(any(feature = "biome-mfa", feature = "oauth"))]
+mod encryption;
 #[cfg(feature = "events")]
 pub mod events;
 mod hex;
 pub mod keys;
 pub mod mesh;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod network;
 pub mod orchestrator;
 pub mod peer;
@@ -90,9 +94,15 @@ pub mod storage;
 #[cfg(feature = "store-factory")]
 pub mod store;
 mod threading;
+#[cfg(feature = "client-tls")]
+pub mod tls;
 pub mod transport;
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
 
 #[cfg(feature = "rest-api")]
 pub use actix_web;
 #[cfg(feature = "rest-api")]
 pub use futures;
+#[cfg(feature = "metrics")]
+pub use prometheus;
@@ -28,12 +28,26 @@ impl Incoming {
     }
 
     pub fn recv(&self) -> Result<InternalEnvelope, RecvError> {
-        self.rx.recv().map_err(|_| RecvError {})
+        let envelope = self.rx.recv().map_err(|_| RecvError {})?;
+        self.update_queue_depth();
+        Ok(envelope)
     }
 
     pub fn recv_timeout(&self, timeout: Duration) -> Result<InternalEnvelope, RecvTimeoutError> {
-        Ok(self.rx.recv_timeout(timeout)?)
+        let envelope = self.rx.recv_timeout(timeout)?;
+        self.update_queue_depth();
+        Ok(envelope)
     }
+
+    #[cfg(feature = "metrics")]
+    fn update_queue_depth(&self) {
+        crate::metrics::MESH_QUEUE_DEPTH
+            .with_label_values(&["incoming"])
+            .set(self.rx.len() as i64);
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    fn update_queue_depth(&self) {}
 }
 
 /// The background sender disconnected and the queue is empty
@@ -15,6 +15,11 @@
 use mio_extras::channel::{SyncSender, TrySendError};
 
 use std::io;
+#[cfg(feature = "metrics")]
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 use super::InternalEnvelope;
 
@@ -23,19 +28,40 @@ use super::InternalEnvelope;
 pub struct Outgoing {
     id: usize,
     tx: SyncSender<InternalEnvelope>,
+    #[cfg(feature = "metrics")]
+    depth: OutgoingDepth,
 }
 
 impl Outgoing {
-    pub(super) fn new(id: usize, tx: SyncSender<InternalEnvelope>) -> Self {
-        Outgoing { id, tx }
+    pub(super) fn new(
+        id: usize,
+        tx: SyncSender<InternalEnvelope>,
+        #[cfg(feature = "metrics")] depth: OutgoingDepth,
+    ) -> Self {
+        Outgoing {
+            id,
+            tx,
+            #[cfg(feature = "metrics")]
+            depth,
+        }
     }
 
     pub fn send(&self, payload: Vec<u8>) -> Result<(), SendError> {
-        self.tx.try_send(InternalEnvelope::Message {
+        // The message is counted before it is queued, so that the reactor cannot take it off the
+        // queue before it is counted
+        #[cfg(feature = "metrics")]
+        self.depth.add(1);
+        let result = self.tx.try_send(InternalEnvelope::Message {
             id: self.id,
             payload,
-        })?;
-        Ok(())
+        });
+        #[cfg(feature = "metrics")]
+        {
+            if result.is_err() {
+                self.depth.sub(1);
+            }
+        }
+        result.map_err(SendError::from)
     }
 
     pub fn id(&self) -> usize {
@@ -43,6 +69,31 @@ impl Outgoing {
     }
 }
 
+/// The number of messages in all of the mesh's outgoing queues. `mio_extras` channels do not
+/// report their length, so the queues count their messages as they are sent and received.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Default)]
+pub(super) struct OutgoingDepth(Arc<AtomicUsize>);
+
+#[cfg(feature = "metrics")]
+impl OutgoingDepth {
+    /// Counts messages that were added to an outgoing queue
+    pub fn add(&self, count: usize) {
+        self.report(self.0.fetch_add(count, Ordering::SeqCst) + count);
+    }
+
+    /// Counts messages that were taken off an outgoing queue, or dropped with it
+    pub fn sub(&self, count: usize) {
+        self.report(self.0.fetch_sub(count, Ordering::SeqCst) - count);
+    }
+
+    fn report(&self, depth: usize) {
+        crate::metrics::MESH_QUEUE_DEPTH
+            .with_label_values(&["outgoing"])
+            .set(depth as i64);
+    }
+}
+
 #[derive(Debug)]
 pub enum SendError {
     IoError(io::Error),
@@ -24,6 +24,8 @@ use std::sync::mpsc::TryRecvError;
 
 use crate::transport::{Connection, RecvError, SendError};
 
+#[cfg(feature = "metrics")]
+use super::outgoing::OutgoingDepth;
 use super::InternalEnvelope;
 
 /// A structure for holding onto many connections and receivers and assigning new connections
@@ -34,6 +36,8 @@ pub(super) struct Pool {
     next_id: usize,
     poll: Poll,
     disconnected: HashMap<usize, Option<Box<dyn Connection>>>,
+    #[cfg(feature = "metrics")]
+    outgoing_depth: OutgoingDepth,
 }
 
 impl fmt::Debug for Pool {
@@ -55,9 +59,17 @@ impl Pool {
             next_id: 0,
             poll,
             disconnected: HashMap::new(),
+            #[cfg(feature = "metrics")]
+            outgoing_depth: OutgoingDepth::default(),
         }
     }
 
+    /// Returns the count of the messages in the outgoing queues of the pool's connections
+    #[cfg(feature = "metrics")]
+    pub fn outgoing_depth(&self) -> OutgoingDepth {
+        self.outgoing_depth.clone()
+    }
+
     /// Add a new connection to the reactor, returning unique ids for the actual connection and the
     /// outgoing queue
     pub fn add(
@@ -87,7 +99,15 @@ impl Pool {
         self.tokens.insert(outgoing_token, id);
         self.entries.insert(
             id,
-            Entry::new(id, connection, connection_token, outgoing, outgoing_token),
+            Entry::new(
+                id,
+                connection,
+                connection_token,
+                outgoing,
+                outgoing_token,
+                #[cfg(feature = "metrics")]
+                self.outgoing_depth.clone(),
+            ),
         );
 
         Ok(id)
@@ -107,6 +127,17 @@ impl Pool {
             self.poll.deregister(connection.evented())?;
             self.poll.deregister(&outgoing)?;
 
+            // The messages that are still queued are dropped with the queue
+            #[cfg(feature = "metrics")]
+            {
+                let dropped = std::iter::from_fn(|| outgoing.try_recv().ok())
+                    .filter(|envelope| matches!(envelope, InternalEnvelope::Message { .. }))
+                    .count();
+                if dropped > 0 {
+                    self.outgoing_depth.sub(dropped);
+                }
+            }
+
             Ok(Some(connection))
         } else if let Some(connection) = self.disconnected.remove(&id) {
             Ok(connection)
@@ -190,6 +221,8 @@ struct Entry {
     outgoing_token: Token,
     cached: RefCell<Option<Vec<u8>>>,
     write_evented_guard: RefCell<bool>,
+    #[cfg(feature = "metrics")]
+    outgoing_depth: OutgoingDepth,
 }
 
 impl fmt::Debug for Entry {
@@ -209,6 +242,7 @@ impl Entry {
         connection_token: Token,
         outgoing: mio_channel::Receiver<InternalEnvelope>,
         outgoing_token: Token,
+        #[cfg(feature = "metrics")] outgoing_depth: OutgoingDepth,
     ) -> Self {
         Entry {
             id,
@@ -218,6 +252,8 @@ impl Entry {
             outgoing_token,
             cached: RefCell::new(None),
             write_evented_guard: RefCell::new(false),
+            #[cfg(feature = "metrics")]
+            outgoing_depth,
         }
     }
 
@@ -271,6 +307,8 @@ impl Entry {
 
         match envelope {
             InternalEnvelope::Message { payload, .. } => {
+                #[cfg(feature = "metrics")]
+                self.outgoing_depth.sub(1);
                 self.try_send_connection_or_cache(payload, poll)
             }
             // won't be sent outgoing
@@ -372,7 +410,13 @@ impl Entry {
                         Err(TrySendError::Disconnected(_)) => {
                             Err(TryEventError::IncomingDisconnected)
                         }
-                        Ok(()) => Ok(()),
+                        Ok(()) => {
+                            #[cfg(feature = "metrics")]
+                            crate::metrics::MESH_QUEUE_DEPTH
+                                .with_label_values(&["incoming"])
+                                .set(incoming_tx.len() as i64);
+                            Ok(())
+                        }
                     }
                 }
                 Err(RecvError::WouldBlock) => Ok(()),
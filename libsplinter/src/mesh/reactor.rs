@@ -151,7 +151,12 @@ impl Reactor {
         let (tx, rx) = mio_channel::sync_channel(self.outgoing_capacity);
 
         match self.pool.add(connection, rx) {
-            Ok(id) => Ok(Outgoing::new(id, tx)),
+            Ok(id) => Ok(Outgoing::new(
+                id,
+                tx,
+                #[cfg(feature = "metrics")]
+                self.pool.outgoing_depth(),
+            )),
             Err(err) => Err(AddError::Io(err)),
         }
     }
@@ -0,0 +1,168 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus metrics for Splinter components.
+//!
+//! Components record their metrics in the process-wide Prometheus registry as they run. Services
+//! built on Splinter may register their own metrics in the same registry through the re-exported
+//! `splinter::prometheus` crate. All of the registered metrics may be rendered in the Prometheus
+//! text format using `gather`.
+
+use std::error::Error;
+use std::fmt;
+
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    Encoder, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder,
+};
+
+#[cfg(feature = "rest-api")]
+use actix_web::{Error as ActixError, HttpResponse};
+#[cfg(feature = "rest-api")]
+use futures::Future;
+#[cfg(feature = "rest-api")]
+use std::time::Instant;
+
+/// The content type of the text returned by `gather`.
+pub const TEXT_CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+lazy_static! {
+    pub(crate) static ref CONNECTIONS: IntGaugeVec = register_int_gauge_vec!(
+        "splinter_connections",
+        "Connections managed by the connection manager, by peer and connection state",
+        &["peer", "state"]
+    )
+    .expect("Unable to register splinter_connections");
+    pub(crate) static ref MESH_QUEUE_DEPTH: IntGaugeVec = register_int_gauge_vec!(
+        "splinter_mesh_queue_depth",
+        "Messages waiting in the mesh's incoming queue and in all of its outgoing queues",
+        &["queue"]
+    )
+    .expect("Unable to register splinter_mesh_queue_depth");
+    pub(crate) static ref DISPATCHED_MESSAGES: IntCounterVec = register_int_counter_vec!(
+        "splinter_dispatched_messages_total",
+        "Messages dispatched to handlers, by message type",
+        &["message_type"]
+    )
+    .expect("Unable to register splinter_dispatched_messages_total");
+    pub(crate) static ref ADMIN_PROPOSALS: IntCounterVec = register_int_counter_vec!(
+        "splinter_admin_proposals_total",
+        "Circuit proposal changes committed by the admin service, by resulting status",
+        &["status"]
+    )
+    .expect("Unable to register splinter_admin_proposals_total");
+    pub(crate) static ref ADMIN_PENDING_PROPOSALS: IntGauge = register_int_gauge!(
+        "splinter_admin_pending_proposals",
+        "Circuit proposals which are waiting for votes"
+    )
+    .expect("Unable to register splinter_admin_pending_proposals");
+    pub(crate) static ref ADMIN_AUDIT_LOG_FAILURES: IntCounterVec = register_int_counter_vec!(
+        "splinter_admin_audit_log_failures_total",
+        "Failed attempts to record circuit proposal events in the audit log, by event type",
+        &["event_type"]
+    )
+    .expect("Unable to register splinter_admin_audit_log_failures_total");
+    pub(crate) static ref RATE_LIMITED_MESSAGES: IntCounterVec = register_int_counter_vec!(
+        "splinter_rate_limited_messages_total",
+        "Messages rejected for exceeding a rate limit, by limit type and peer or circuit ID",
+        &["limit", "id"]
+    )
+    .expect("Unable to register splinter_rate_limited_messages_total");
+    pub(crate) static ref RATE_LIMIT_MESSAGES: IntCounterVec = register_int_counter_vec!(
+        "splinter_rate_limit_messages_total",
+        "Messages checked against the rate limits, by limit type, peer or circuit ID and result",
+        &["limit", "id", "result"]
+    )
+    .expect("Unable to register splinter_rate_limit_messages_total");
+    pub(crate) static ref RATE_LIMIT_BYTES: IntCounterVec = register_int_counter_vec!(
+        "splinter_rate_limit_bytes_total",
+        "Payload bytes checked against the rate limits, by limit type, peer or circuit ID and \
+         result",
+        &["limit", "id", "result"]
+    )
+    .expect("Unable to register splinter_rate_limit_bytes_total");
+    pub(crate) static ref REST_API_REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "splinter_rest_api_request_duration_seconds",
+        "Time taken to respond to REST API requests, by route, method and status code",
+        &["route", "method", "status"]
+    )
+    .expect("Unable to register splinter_rest_api_request_duration_seconds");
+}
+
+/// Renders all registered metrics in the Prometheus text format.
+pub fn gather() -> Result<String, MetricsError> {
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .map_err(|err| MetricsError(err.to_string()))?;
+
+    String::from_utf8(buffer).map_err(|err| MetricsError(err.to_string()))
+}
+
+/// Records the duration and status of a REST API request once its response is complete.
+#[cfg(feature = "rest-api")]
+pub(crate) fn observe_rest_request(
+    route: String,
+    method: String,
+    response: Box<dyn Future<Item = HttpResponse, Error = ActixError>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = ActixError>> {
+    let start = Instant::now();
+    Box::new(response.then(move |result| {
+        let status = match &result {
+            Ok(response) => response.status(),
+            Err(err) => err.as_response_error().error_response().status(),
+        };
+        let status = status.as_u16().to_string();
+        let elapsed = start.elapsed();
+        REST_API_REQUEST_DURATION
+            .with_label_values(&[&route, &method, &status])
+            .observe(elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9);
+        result
+    }))
+}
+
+/// An error which occurred while rendering metrics.
+#[derive(Debug)]
+pub struct MetricsError(String);
+
+impl Error for MetricsError {}
+
+impl fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unable to gather metrics: {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that recorded metrics are rendered in the Prometheus text format.
+    #[test]
+    fn test_gather() {
+        DISPATCHED_MESSAGES
+            .with_label_values(&["TEST_GATHER_MESSAGE"])
+            .inc();
+        ADMIN_PENDING_PROPOSALS.set(0);
+
+        let text = gather().expect("Unable to gather metrics");
+
+        assert!(text.contains("# TYPE splinter_dispatched_messages_total counter"));
+        assert!(text.contains(
+            "splinter_dispatched_messages_total{message_type=\"TEST_GATHER_MESSAGE\"} 1"
+        ));
+        assert!(text.contains("# TYPE splinter_admin_pending_proposals gauge"));
+    }
+}
@@ -187,6 +187,9 @@ where
                             break;
                         }
                     }
+
+                    #[cfg(feature = "metrics")]
+                    state.update_connection_metrics();
                 }
             })?;
 
@@ -496,6 +496,29 @@ where
         }
     }
 
+    /// Records the number of connections to each peer, by connection state.
+    #[cfg(feature = "metrics")]
+    fn update_connection_metrics(&self) {
+        let mut counts: HashMap<(&str, &str), i64> = HashMap::new();
+        for metadata in self.connections.values() {
+            let state = match metadata.extended_metadata {
+                ConnectionMetadataExt::Outbound {
+                    reconnecting: true, ..
+                } => "reconnecting",
+                ConnectionMetadataExt::Inbound { disconnected: true } => "disconnected",
+                _ => "connected",
+            };
+            *counts.entry((metadata.identity(), state)).or_insert(0) += 1;
+        }
+
+        crate::metrics::CONNECTIONS.reset();
+        for ((peer, state), count) in counts {
+            crate::metrics::CONNECTIONS
+                .with_label_values(&[peer, state])
+                .set(count);
+        }
+    }
+
     /// Adds a new connection as an inbound connection.
     fn add_inbound_connection(
         &mut self,
@@ -344,6 +344,11 @@ where
     }
 
     fn execute(&self, ctx: MessageContext<Source, MT>) -> Result<(), DispatchError> {
+        #[cfg(feature = "metrics")]
+        crate::metrics::DISPATCHED_MESSAGES
+            .with_label_values(&[&format!("{:?}", ctx.message_type())])
+            .inc();
+
         self.handlers
             .get(ctx.message_type())
             .ok_or_else(|| {
@@ -286,22 +286,14 @@ impl ServiceOrchestrator {
         circuits: Vec<String>,
         service_types: Vec<String>,
     ) -> Result<Vec<ServiceDefinition>, ListServicesError> {
-        Ok(self
-            .services
-            .lock()
-            .map_err(|_| ListServicesError::LockPoisoned)?
-            .iter()
-            .filter_map(|(service, _)| {
-                if (circuits.is_empty() || circuits.contains(&service.circuit))
-                    && (service_types.is_empty() || service_types.contains(&service.service_type))
-                {
-                    Some(service)
-                } else {
-                    None
-                }
-            })
-            .cloned()
-            .collect())
+        list_services(&self.services, circuits, service_types)
+    }
+
+    /// Get a `ServiceLister` for the services managed by this `ServiceOrchestrator`.
+    pub fn service_lister(&self) -> ServiceLister {
+        ServiceLister {
+            services: self.services.clone(),
+        }
     }
 
     pub fn supported_service_types(&self) -> &[String] {
@@ -309,6 +301,47 @@ impl ServiceOrchestrator {
     }
 }
 
+/// Lists the services managed by a `ServiceOrchestrator`. Unlike the orchestrator itself, a
+/// `ServiceLister` may be cloned and shared with other components.
+#[derive(Clone)]
+pub struct ServiceLister {
+    services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+}
+
+impl ServiceLister {
+    /// List the services managed by the `ServiceOrchestrator`; filters may be provided to only
+    /// show services on specified circuit(s) and of given service type(s).
+    pub fn list_services(
+        &self,
+        circuits: Vec<String>,
+        service_types: Vec<String>,
+    ) -> Result<Vec<ServiceDefinition>, ListServicesError> {
+        list_services(&self.services, circuits, service_types)
+    }
+}
+
+fn list_services(
+    services: &Mutex<HashMap<ServiceDefinition, ManagedService>>,
+    circuits: Vec<String>,
+    service_types: Vec<String>,
+) -> Result<Vec<ServiceDefinition>, ListServicesError> {
+    Ok(services
+        .lock()
+        .map_err(|_| ListServicesError::LockPoisoned)?
+        .iter()
+        .filter_map(|(service, _)| {
+            if (circuits.is_empty() || circuits.contains(&service.circuit))
+                && (service_types.is_empty() || service_types.contains(&service.service_type))
+            {
+                Some(service)
+            } else {
+                None
+            }
+        })
+        .cloned()
+        .collect())
+}
+
 pub struct JoinHandles<T> {
     join_handles: Vec<JoinHandle<T>>,
 }
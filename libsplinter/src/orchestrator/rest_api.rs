@@ -43,6 +43,11 @@ impl RestResourceProvider for ServiceOrchestrator {
                         let services = self.services.clone();
 
                         let mut resource_builder = Resource::build(&route);
+                        #[cfg(feature = "authorization")]
+                        {
+                            resource_builder =
+                                resource_builder.with_permission(endpoint.permission);
+                        }
 
                         for request_guard in endpoint.request_guards.into_iter() {
                             resource_builder = resource_builder.add_request_guard(request_guard);
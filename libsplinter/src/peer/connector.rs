@@ -19,8 +19,8 @@ use std::sync::mpsc::{channel, Sender};
 use crate::collections::BiHashMap;
 
 use super::error::{
-    PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
-    PeerRefRemoveError, PeerUnknownAddError,
+    PeerConnectionIdError, PeerEndpointsUpdateError, PeerListError, PeerLookupError,
+    PeerManagerError, PeerRefAddError, PeerRefRemoveError, PeerUnknownAddError,
 };
 use super::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
 use super::{EndpointPeerRef, PeerRef};
@@ -133,6 +133,67 @@ impl PeerManagerConnector {
             .map_err(|err| PeerUnknownAddError::ReceiveError(format!("{:?}", err)))?
     }
 
+    /// Requests that the endpoints of an existing peer are replaced with the given endpoints.
+    ///
+    /// If the peer is currently connected over an endpoint that is no longer in its list of
+    /// endpoints, the connection is replaced with a connection to one of the new endpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The unique ID for the peer
+    /// * `endpoints` - The new list of endpoints the peer is reachable at
+    pub fn update_peer_endpoints(
+        &self,
+        peer_id: &str,
+        endpoints: Vec<String>,
+    ) -> Result<(), PeerEndpointsUpdateError> {
+        self.send_update_peer_endpoints(peer_id, endpoints, false)
+    }
+
+    /// Requests that the endpoints of an existing peer are replaced with the given endpoints, and
+    /// that the peer's connection is replaced regardless of the endpoint it is connected over.
+    ///
+    /// This is used when the peer's keys have changed, so the peer is authorized again.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The unique ID for the peer
+    /// * `endpoints` - The new list of endpoints the peer is reachable at
+    pub fn reconnect_peer(
+        &self,
+        peer_id: &str,
+        endpoints: Vec<String>,
+    ) -> Result<(), PeerEndpointsUpdateError> {
+        self.send_update_peer_endpoints(peer_id, endpoints, true)
+    }
+
+    fn send_update_peer_endpoints(
+        &self,
+        peer_id: &str,
+        endpoints: Vec<String>,
+        reconnect: bool,
+    ) -> Result<(), PeerEndpointsUpdateError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::UpdatePeerEndpoints {
+            peer_id: peer_id.to_string(),
+            endpoints,
+            reconnect,
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerEndpointsUpdateError::InternalError(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerEndpointsUpdateError::ReceiveError(format!("{:?}", err)))?
+    }
+
     /// Requests the list of currently connected peers.
     ///
     /// Returns the list of peer IDs.
@@ -169,6 +169,35 @@ impl fmt::Display for PeerConnectionIdError {
     }
 }
 
+/// Errors that could be raised when requesting that a peer's endpoints are updated
+#[derive(Debug, PartialEq)]
+pub enum PeerEndpointsUpdateError {
+    /// Internal `PeerManager` error
+    InternalError(String),
+    /// Unable to receive response
+    ReceiveError(String),
+    /// Unable to update the peer's endpoints
+    UpdateError(String),
+}
+
+impl error::Error for PeerEndpointsUpdateError {}
+
+impl fmt::Display for PeerEndpointsUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeerEndpointsUpdateError::InternalError(msg) => {
+                write!(f, "Received internal error: {}", msg)
+            }
+            PeerEndpointsUpdateError::ReceiveError(msg) => {
+                write!(f, "Unable to receive response from PeerManager: {}", msg)
+            }
+            PeerEndpointsUpdateError::UpdateError(msg) => {
+                write!(f, "Unable to update peer endpoints: {}", msg)
+            }
+        }
+    }
+}
+
 /// Errors raised by trying to update a peer
 #[derive(Debug)]
 pub struct PeerUpdateError(pub String);
@@ -48,8 +48,8 @@ pub use self::builder::PeerManagerBuilder;
 pub use self::connector::PeerManagerConnector;
 use self::connector::PeerRemover;
 use self::error::{
-    PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
-    PeerRefRemoveError, PeerUnknownAddError,
+    PeerConnectionIdError, PeerEndpointsUpdateError, PeerListError, PeerLookupError,
+    PeerManagerError, PeerRefAddError, PeerRefRemoveError, PeerUnknownAddError,
 };
 pub use self::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
 use self::notification::{Subscriber, SubscriberMap};
@@ -96,6 +96,12 @@ pub(crate) enum PeerManagerRequest {
         endpoint: String,
         sender: Sender<Result<(), PeerRefRemoveError>>,
     },
+    UpdatePeerEndpoints {
+        peer_id: String,
+        endpoints: Vec<String>,
+        reconnect: bool,
+        sender: Sender<Result<(), PeerEndpointsUpdateError>>,
+    },
     ListPeers {
         sender: Sender<Result<Vec<String>, PeerListError>>,
     },
@@ -490,6 +496,21 @@ fn handle_request(
                 warn!("Connector dropped before receiving result of removing peer");
             }
         }
+        PeerManagerRequest::UpdatePeerEndpoints {
+            peer_id,
+            endpoints,
+            reconnect,
+            sender,
+        } => {
+            if sender
+                .send(update_peer_endpoints(
+                    peer_id, endpoints, reconnect, connector, peers,
+                ))
+                .is_err()
+            {
+                warn!("Connector dropped before receiving result of updating peer endpoints");
+            }
+        }
         PeerManagerRequest::ListPeers { sender } => {
             if sender.send(Ok(peers.peer_ids())).is_err() {
                 warn!("Connector dropped before receiving result of list peers");
@@ -592,6 +613,65 @@ impl UnreferencedPeerState {
     }
 }
 
+fn update_peer_endpoints(
+    peer_id: String,
+    endpoints: Vec<String>,
+    reconnect: bool,
+    connector: Connector,
+    peers: &mut PeerMap,
+) -> Result<(), PeerEndpointsUpdateError> {
+    if endpoints.is_empty() {
+        return Err(PeerEndpointsUpdateError::UpdateError(format!(
+            "Peer {} must have at least one endpoint",
+            peer_id
+        )));
+    }
+
+    let mut peer_metadata = peers.get_by_peer_id(&peer_id).cloned().ok_or_else(|| {
+        PeerEndpointsUpdateError::UpdateError(format!("Peer {} does not exist", peer_id))
+    })?;
+
+    if peer_metadata.endpoints == endpoints && !reconnect {
+        return Ok(());
+    }
+
+    info!(
+        "Updating peer {} endpoints from {:?} to {:?}",
+        peer_id, peer_metadata.endpoints, endpoints
+    );
+
+    // If the peer was connected to over one of its old endpoints that is no longer valid, the
+    // connection is replaced; inbound connections are left alone unless a reconnect was requested
+    let replace_connection = reconnect
+        || (peer_metadata
+            .endpoints
+            .contains(&peer_metadata.active_endpoint)
+            && !endpoints.contains(&peer_metadata.active_endpoint));
+    peer_metadata.endpoints = endpoints;
+
+    if replace_connection {
+        if let Err(err) = connector.remove_connection(&peer_metadata.active_endpoint) {
+            error!("Unable to clean up old connection: {}", err);
+        }
+
+        info!("Attempting to find available endpoint for {}", peer_id);
+        for endpoint in peer_metadata.endpoints.iter() {
+            match connector.request_connection(&endpoint, &peer_metadata.connection_id) {
+                Ok(()) => break,
+                Err(err) => {
+                    log_connect_request_err(err, &peer_metadata.id, &endpoint);
+                }
+            }
+        }
+        peer_metadata.status = PeerStatus::Pending;
+        peer_metadata.last_connection_attempt = Instant::now();
+    }
+
+    peers
+        .update_peer(peer_metadata)
+        .map_err(|err| PeerEndpointsUpdateError::UpdateError(err.to_string()))
+}
+
 // Allow clippy errors for too_many_arguments. The arguments are required
 // to avoid needing a lock in the PeerManager.
 #[allow(clippy::too_many_arguments)]
@@ -153,7 +153,14 @@ impl PeerMap {
     /// * `peer_metadata` - The updated peer metadata for the peer
     pub fn update_peer(&mut self, peer_metadata: PeerMetadata) -> Result<(), PeerUpdateError> {
         // Only valid if the peer already exists
-        if self.peers.contains_key(&peer_metadata.id) {
+        if let Some(old_metadata) = self.peers.get(&peer_metadata.id) {
+            // Remove any endpoints the peer is no longer reachable at
+            for endpoint in old_metadata.endpoints.iter() {
+                if !peer_metadata.endpoints.contains(endpoint) {
+                    self.endpoints.remove(endpoint);
+                }
+            }
+
             for endpoint in peer_metadata.endpoints.iter() {
                 self.endpoints
                     .insert(endpoint.to_string(), peer_metadata.id.clone());
@@ -443,4 +450,36 @@ pub mod tests {
             PeerStatus::Disconnected { retry_attempts: 5 }
         );
     }
+
+    // Test that endpoints removed from a peer are no longer mapped to it
+    //  1. Insert test_peer with endpoints test_endpoint1 and test_endpoint2
+    //  2. Update the peer's endpoints to test_endpoint2 and new_endpoint
+    //  3. Check that test_endpoint1 no longer maps to the peer, but the other endpoints do
+    #[test]
+    fn test_update_peer_removes_endpoints() {
+        let mut peer_map = PeerMap::new(10);
+
+        peer_map.insert(
+            "test_peer".to_string(),
+            "connection_id".to_string(),
+            vec!["test_endpoint1".to_string(), "test_endpoint2".to_string()],
+            "test_endpoint2".to_string(),
+            PeerStatus::Connected,
+        );
+
+        let mut peer_metadata = peer_map
+            .get_by_peer_id("test_peer")
+            .cloned()
+            .expect("Unable to retrieve peer metadata");
+        peer_metadata.endpoints = vec!["test_endpoint2".to_string(), "new_endpoint".to_string()];
+
+        peer_map
+            .update_peer(peer_metadata)
+            .expect("Unable to update endpoints");
+
+        assert!(!peer_map.contains_endpoint("test_endpoint1"));
+        assert!(peer_map.contains_endpoint("test_endpoint2"));
+        assert!(peer_map.contains_endpoint("new_endpoint"));
+        assert!(peer_map.get_peer_from_endpoint("test_endpoint1").is_none());
+    }
 }
@@ -18,11 +18,14 @@ pub mod authorization;
 pub mod component;
 pub mod service;
 
-pub const ADMIN_PROTOCOL_VERSION: u32 = 1;
+pub const ADMIN_PROTOCOL_VERSION: u32 = 2;
 
 #[cfg(feature = "rest-api-actix")]
 pub(crate) const ADMIN_APPLICATION_REGISTRATION_PROTOCOL_MIN: u32 = 1;
 pub(crate) const ADMIN_SERVICE_PROTOCOL_MIN: u32 = 1;
+// Admin services that agreed on an older version drop a proposal's expiry, voting policy and
+// signer quorum, and would tally the proposal as unanimous
+pub(crate) const ADMIN_PROPOSAL_RULES_PROTOCOL_MIN: u32 = 2;
 #[cfg(feature = "rest-api-actix")]
 pub(crate) const ADMIN_SUBMIT_PROTOCOL_MIN: u32 = 1;
 
@@ -35,6 +38,12 @@ pub(crate) const ADMIN_LIST_PROPOSALS_PROTOCOL_MIN: u32 = 1;
 pub(crate) const ADMIN_LIST_CIRCUITS_MIN: u32 = 1;
 #[cfg(feature = "rest-api-actix")]
 pub(crate) const ADMIN_FETCH_CIRCUIT_MIN: u32 = 1;
+#[cfg(feature = "admin-event-endpoints")]
+pub(crate) const ADMIN_EVENTS_PROTOCOL_MIN: u32 = 1;
+#[cfg(feature = "admin-proposal-validation")]
+pub(crate) const ADMIN_VALIDATE_PROPOSAL_PROTOCOL_MIN: u32 = 2;
+#[cfg(feature = "admin-audit-log")]
+pub(crate) const ADMIN_CIRCUIT_HISTORY_PROTOCOL_MIN: u32 = 1;
 
 #[cfg(feature = "oauth")]
 pub const OAUTH_PROTOCOL_VERSION: u32 = 1;
@@ -43,6 +52,10 @@ pub const OAUTH_PROTOCOL_VERSION: u32 = 1;
 pub(crate) const OAUTH_CALLBACK_MIN: u32 = 1;
 #[cfg(all(feature = "oauth", feature = "rest-api-actix"))]
 pub(crate) const OAUTH_LOGIN_MIN: u32 = 1;
+#[cfg(all(feature = "oauth", feature = "rest-api-actix"))]
+pub(crate) const OAUTH_LOGOUT_MIN: u32 = 1;
+#[cfg(all(feature = "oauth", feature = "rest-api-actix"))]
+pub(crate) const OAUTH_TOKEN_MIN: u32 = 1;
 
 #[cfg(feature = "registry")]
 pub const REGISTRY_PROTOCOL_VERSION: u32 = 1;
@@ -51,6 +64,8 @@ pub const REGISTRY_PROTOCOL_VERSION: u32 = 1;
 pub(crate) const REGISTRY_LIST_NODES_MIN: u32 = 1;
 #[cfg(all(feature = "registry", feature = "rest-api-actix"))]
 pub(crate) const REGISTRY_FETCH_NODE_MIN: u32 = 1;
+#[cfg(all(feature = "registry-events", feature = "rest-api-actix"))]
+pub(crate) const REGISTRY_SUBSCRIBE_MIN: u32 = 1;
 
 #[cfg(feature = "biome")]
 pub const BIOME_PROTOCOL_VERSION: u32 = 1;
@@ -68,3 +83,24 @@ pub(crate) const BIOME_VERIFY_PROTOCOL_MIN: u32 = 1;
 
 #[cfg(all(feature = "biome-key-management", feature = "rest-api",))]
 pub(crate) const BIOME_KEYS_PROTOCOL_MIN: u32 = 1;
+
+#[cfg(all(feature = "biome-mfa", feature = "rest-api"))]
+pub(crate) const BIOME_MFA_PROTOCOL_MIN: u32 = 1;
+
+#[cfg(all(feature = "biome-password-reset", feature = "rest-api"))]
+pub(crate) const BIOME_PASSWORD_RESET_PROTOCOL_MIN: u32 = 1;
+
+#[cfg(all(feature = "biome-login-lockout", feature = "rest-api"))]
+pub(crate) const BIOME_LOCKOUT_PROTOCOL_MIN: u32 = 1;
+
+#[cfg(all(feature = "biome-user-admin", feature = "rest-api"))]
+pub(crate) const BIOME_USER_ADMIN_PROTOCOL_MIN: u32 = 1;
+
+#[cfg(all(feature = "biome-notifications", feature = "rest-api",))]
+pub(crate) const BIOME_NOTIFICATIONS_PROTOCOL_MIN: u32 = 1;
+
+#[cfg(feature = "webhooks")]
+pub const WEBHOOKS_PROTOCOL_VERSION: u32 = 1;
+
+#[cfg(all(feature = "webhooks", feature = "rest-api-actix"))]
+pub(crate) const WEBHOOKS_PROTOCOL_MIN: u32 = 1;
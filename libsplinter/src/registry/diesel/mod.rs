@@ -36,8 +36,9 @@ use operations::delete_node::RegistryDeleteNodeOperation as _;
 use operations::fetch_node::RegistryFetchNodeOperation as _;
 use operations::has_node::RegistryHasNodeOperation as _;
 use operations::insert_node::RegistryInsertNodeOperation as _;
+use operations::insert_nodes::RegistryInsertNodesOperation as _;
 use operations::list_nodes::RegistryListNodesOperation as _;
-use operations::RegistryOperations;
+use operations::{MetadataSql, RegistryOperations};
 
 /// A database-backed registry, powered by [`Diesel`](https://crates.io/crates/diesel).
 pub struct DieselRegistry<C: diesel::Connection + 'static> {
@@ -76,6 +77,7 @@ impl Clone for DieselRegistry<diesel::sqlite::SqliteConnection> {
 impl<C> RegistryReader for DieselRegistry<C>
 where
     C: diesel::Connection,
+    C::Backend: MetadataSql,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
@@ -107,6 +109,10 @@ impl RegistryWriter for DieselRegistry<diesel::pg::PgConnection> {
         RegistryOperations::new(&*self.connection_pool.get()?).insert_node(node)
     }
 
+    fn insert_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        RegistryOperations::new(&*self.connection_pool.get()?).insert_nodes(nodes)
+    }
+
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
         RegistryOperations::new(&*self.connection_pool.get()?).delete_node(identity)
     }
@@ -118,6 +124,10 @@ impl RegistryWriter for DieselRegistry<diesel::sqlite::SqliteConnection> {
         RegistryOperations::new(&*self.connection_pool.get()?).insert_node(node)
     }
 
+    fn insert_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        RegistryOperations::new(&*self.connection_pool.get()?).insert_nodes(nodes)
+    }
+
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
         RegistryOperations::new(&*self.connection_pool.get()?).delete_node(identity)
     }
@@ -158,3 +168,107 @@ where
         Box::new(self.clone())
     }
 }
+
+#[cfg(all(test, feature = "registry-query", feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::registry::{ComparisonOperator, ValueType};
+
+    use migrations::run_sqlite_migrations;
+
+    /// Verify that numeric comparisons of node metadata, which are evaluated by SQLite, match the
+    /// same comparisons evaluated in memory: values are compared as numbers rather than as text,
+    /// and values that are not numbers, including ones SQLite would cast to a number, never
+    /// satisfy a comparison other than "not equal".
+    #[test]
+    fn list_nodes_numeric_comparison() {
+        let registry = DieselRegistry::new(create_connection_pool_and_migrate());
+
+        let capacities = [
+            "10", "9", " 12 ", "1e2", "-3.5", ".5", "5.", "abc", "12abc", "1e", "1-2", "1.2.3",
+            "e5", "",
+        ];
+        let mut nodes = capacities
+            .iter()
+            .enumerate()
+            .map(|(i, capacity)| {
+                Node::builder(format!("node-{}", i))
+                    .with_endpoint(format!("tcps://12.0.0.{}:8080", i))
+                    .with_key(format!("key-{}", i))
+                    .with_metadata("capacity", *capacity)
+                    .build()
+                    .expect("Failed to build node")
+            })
+            .collect::<Vec<_>>();
+        nodes.push(
+            Node::builder("node-none")
+                .with_endpoint("tcps://12.0.0.100:8080")
+                .with_key("key-none")
+                .build()
+                .expect("Failed to build node"),
+        );
+        registry
+            .insert_nodes(nodes.clone())
+            .expect("Failed to insert nodes");
+
+        for operator in &[
+            ComparisonOperator::Eq,
+            ComparisonOperator::Ne,
+            ComparisonOperator::Gt,
+            ComparisonOperator::Ge,
+            ComparisonOperator::Lt,
+            ComparisonOperator::Le,
+        ] {
+            let predicate =
+                MetadataPredicate::compare("capacity", *operator, ValueType::Number, "10")
+                    .expect("Failed to create predicate");
+
+            let mut expected = nodes
+                .iter()
+                .filter(|node| predicate.apply(node))
+                .map(|node| node.identity.clone())
+                .collect::<Vec<_>>();
+            expected.sort();
+            let mut listed = registry
+                .list_nodes(&[predicate])
+                .expect("Failed to list nodes")
+                .map(|node| node.identity)
+                .collect::<Vec<_>>();
+            listed.sort();
+
+            assert_eq!(listed, expected, "Mismatch for {:?}", operator);
+        }
+
+        let greater = registry
+            .list_nodes(&[MetadataPredicate::compare(
+                "capacity",
+                ComparisonOperator::Gt,
+                ValueType::Number,
+                "9",
+            )
+            .expect("Failed to create predicate")])
+            .expect("Failed to list nodes")
+            .map(|node| node.identity)
+            .collect::<Vec<_>>();
+        assert_eq!(greater.len(), 3);
+        for identity in &["node-0", "node-2", "node-3"] {
+            assert!(greater.iter().any(|listed| listed == identity));
+        }
+    }
+
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
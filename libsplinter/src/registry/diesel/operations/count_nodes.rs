@@ -21,7 +21,8 @@ use crate::registry::{
     MetadataPredicate, RegistryError,
 };
 
-use super::{exists_statements_from_metadata_predicates, RegistryOperations};
+use super::list_nodes::RegistryListNodesOperation as _;
+use super::{MetadataFilter, MetadataSql, RegistryOperations};
 
 pub(in crate::registry::diesel) trait RegistryCountNodesOperation {
     fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError>;
@@ -30,26 +31,19 @@ pub(in crate::registry::diesel) trait RegistryCountNodesOperation {
 impl<'a, C> RegistryCountNodesOperation for RegistryOperations<'a, C>
 where
     C: diesel::Connection,
+    C::Backend: MetadataSql,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
     fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
-        if predicates.is_empty() {
-            // No predicates were specified, just count all nodes
-            splinter_nodes::table
-                .count()
-                // Parse as an i64 here because Diesel knows how to convert a `BigInt` into an i64
-                .get_result::<i64>(self.conn)
-                .map(|count| count as u32)
-                .map_err(|err| {
-                    RegistryError::general_error_with_source(
-                        "Failed to count all nodes",
-                        Box::new(err),
-                    )
-                })
-        } else {
+        let filter = MetadataFilter::new::<C::Backend>(predicates);
+
+        if !filter.remaining.is_empty() {
+            // Some predicates can only be applied in memory, so the matching nodes must be listed
+            self.list_nodes(predicates).map(|nodes| nodes.len() as u32)
+        } else if let Some(filters) = filter.sql {
             // With predicates, this query is too complicated for pure Diesel, so a raw SQL
             // query is needed.
-            let filters = exists_statements_from_metadata_predicates(predicates);
             sql_query(format!(
                 "SELECT COUNT(*) FROM splinter_nodes WHERE {}",
                 filters
@@ -64,6 +58,19 @@ where
                     Box::new(err),
                 )
             })
+        } else {
+            // No predicates were specified, just count all nodes
+            splinter_nodes::table
+                .count()
+                // Parse as an i64 here because Diesel knows how to convert a `BigInt` into an i64
+                .get_result::<i64>(self.conn)
+                .map(|count| count as u32)
+                .map_err(|err| {
+                    RegistryError::general_error_with_source(
+                        "Failed to count all nodes",
+                        Box::new(err),
+                    )
+                })
         }
     }
 }
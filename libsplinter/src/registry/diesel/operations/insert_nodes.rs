@@ -0,0 +1,42 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "insert nodes" operation for the `DieselRegistry`.
+
+use crate::registry::{Node, RegistryError};
+
+use super::insert_node::RegistryInsertNodeOperation;
+use super::RegistryOperations;
+
+pub(in crate::registry::diesel) trait RegistryInsertNodesOperation {
+    fn insert_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError>;
+}
+
+impl<'a, C> RegistryInsertNodesOperation for RegistryOperations<'a, C>
+where
+    C: diesel::Connection,
+    RegistryOperations<'a, C>: RegistryInsertNodeOperation,
+{
+    fn insert_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        // Each node is inserted in its own transaction; diesel nests these in the outer
+        // transaction as savepoints, so a failure rolls back all of the nodes
+        self.conn.transaction::<(), _, _>(|| {
+            for node in nodes {
+                self.insert_node(node)?;
+            }
+
+            Ok(())
+        })
+    }
+}
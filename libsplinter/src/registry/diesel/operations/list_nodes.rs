@@ -26,7 +26,7 @@ use crate::registry::{
     MetadataPredicate, Node, NodeBuilder, RegistryError,
 };
 
-use super::{exists_statements_from_metadata_predicates, RegistryOperations};
+use super::{MetadataFilter, MetadataSql, RegistryOperations};
 
 pub(in crate::registry::diesel) trait RegistryListNodesOperation {
     fn list_nodes(&self, predicates: &[MetadataPredicate]) -> Result<Vec<Node>, RegistryError>;
@@ -35,22 +35,16 @@ pub(in crate::registry::diesel) trait RegistryListNodesOperation {
 impl<'a, C> RegistryListNodesOperation for RegistryOperations<'a, C>
 where
     C: diesel::Connection,
+    C::Backend: MetadataSql,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
     fn list_nodes(&self, predicates: &[MetadataPredicate]) -> Result<Vec<Node>, RegistryError> {
+        let filter = MetadataFilter::new::<C::Backend>(predicates);
+
         self.conn.transaction::<_, _, _>(|| {
-            let nodes: Vec<NodesModel> = if predicates.is_empty() {
-                // No predicates were specified, just get all nodes
-                splinter_nodes::table.load(self.conn).map_err(|err| {
-                    RegistryError::general_error_with_source(
-                        "Failed to get all nodes",
-                        Box::new(err),
-                    )
-                })?
-            } else {
+            let nodes: Vec<NodesModel> = if let Some(filters) = &filter.sql {
                 // With predicates, this query is too complicated for pure Diesel, so a raw SQL
                 // query is needed.
-                let filters = exists_statements_from_metadata_predicates(predicates);
                 sql_query(format!("SELECT * FROM splinter_nodes WHERE {}", filters))
                     .load(self.conn)
                     .map_err(|err| {
@@ -59,6 +53,14 @@ where
                             Box::new(err),
                         )
                     })?
+            } else {
+                // No predicates could be pushed down, just get all nodes
+                splinter_nodes::table.load(self.conn).map_err(|err| {
+                    RegistryError::general_error_with_source(
+                        "Failed to get all nodes",
+                        Box::new(err),
+                    )
+                })?
             };
 
             // Checking if there are any nodes here serves two purposes: 1) It saves time by
@@ -126,6 +128,12 @@ where
 
                         builder.build().map_err(RegistryError::from)
                     })
+                    // Apply the predicates that could not be pushed down into the query
+                    .filter(|res| {
+                        res.as_ref()
+                            .map(|node| filter.apply_remaining(node))
+                            .unwrap_or(true)
+                    })
                     .collect::<Result<Vec<_>, _>>()
             }
         })
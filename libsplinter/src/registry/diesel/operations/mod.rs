@@ -19,9 +19,12 @@ pub(super) mod delete_node;
 pub(super) mod fetch_node;
 pub(super) mod has_node;
 pub(super) mod insert_node;
+pub(super) mod insert_nodes;
 pub(super) mod list_nodes;
 
-use crate::registry::MetadataPredicate;
+#[cfg(feature = "registry-query")]
+use crate::registry::{query::parse_number, ComparisonOperator, ValueType};
+use crate::registry::{MetadataPredicate, Node};
 
 pub struct RegistryOperations<'a, C> {
     conn: &'a C,
@@ -33,69 +36,293 @@ impl<'a, C: diesel::Connection> RegistryOperations<'a, C> {
     }
 }
 
-/// Generates a string with a series of `EXISTS` SQL statements from a list of node metadata
-/// predicates. Filtering on node metadata is too complicated for pure Diesel, so raw SQL queries
-/// are needed.
-///
-/// This function assumes that the resulting statements will be used with a
-/// `SELECT _ FROM splinter_nodes` query. Each `EXISTS` statement checks for the existence of a
-/// matching metadata value in the `splinter_nodes_metadata` table.
-fn exists_statements_from_metadata_predicates(predicates: &[MetadataPredicate]) -> String {
-    predicates
-        .iter()
-        .map(|predicate| {
-            match predicate {
-                MetadataPredicate::Eq(key, val) => format!(
-                    "EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
-                     splinter_nodes_metadata.identity = splinter_nodes.identity \
-                     AND splinter_nodes_metadata.key = {} \
-                     AND splinter_nodes_metadata.value = {})",
-                    key, val
-                ),
-                MetadataPredicate::Ne(key, val) => {
-                    // If the metadata key is not set for a node, the predicate is
-                    // satisfied
-                    format!(
-                        "NOT EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
-                         splinter_nodes_metadata.identity = splinter_nodes.identity \
-                         AND splinter_nodes_metadata.key = {0}) \
-                         OR EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
-                         splinter_nodes_metadata.identity = splinter_nodes.identity \
-                         AND splinter_nodes_metadata.key = {} \
-                         AND splinter_nodes_metadata.value <> {})",
-                        key, val
-                    )
-                }
-                MetadataPredicate::Gt(key, val) => format!(
-                    "EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
-                     splinter_nodes_metadata.identity = splinter_nodes.identity \
-                     AND splinter_nodes_metadata.key = {} \
-                     AND splinter_nodes_metadata.value > {})",
-                    key, val
-                ),
-                MetadataPredicate::Ge(key, val) => format!(
-                    "EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
-                     splinter_nodes_metadata.identity = splinter_nodes.identity \
-                     AND splinter_nodes_metadata.key = {} \
-                     AND splinter_nodes_metadata.value >= {})",
-                    key, val
-                ),
-                MetadataPredicate::Lt(key, val) => format!(
-                    "EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
-                     splinter_nodes_metadata.identity = splinter_nodes.identity \
-                     AND splinter_nodes_metadata.key = {} \
-                     AND splinter_nodes_metadata.value < {})",
-                    key, val
+/// Backend-specific SQL used to filter on node metadata.
+pub trait MetadataSql {
+    /// Returns a SQL expression that evaluates to the numeric value of the `text` expression, or
+    /// to `NULL` if it is not a number; returns `None` if the backend cannot reliably convert
+    /// text to numbers, in which case numeric comparisons are performed in memory.
+    #[cfg(feature = "registry-query")]
+    fn numeric_value(text: &str) -> Option<String>;
+
+    /// Returns a SQL condition that is true if the `text` expression contains the `substring`
+    /// expression.
+    #[cfg(feature = "registry-query")]
+    fn contains(text: &str, substring: &str) -> String;
+}
+
+#[cfg(feature = "postgres")]
+impl MetadataSql for diesel::pg::Pg {
+    #[cfg(feature = "registry-query")]
+    fn numeric_value(text: &str) -> Option<String> {
+        // Only cast values that are numbers, since a failed cast is an error rather than NULL
+        Some(format!(
+            "CASE WHEN {0} ~ '^\\s*[-+]?([0-9]+(\\.[0-9]*)?|\\.[0-9]+)([eE][-+]?[0-9]+)?\\s*$' \
+             THEN CAST({0} AS NUMERIC) END",
+            text
+        ))
+    }
+
+    #[cfg(feature = "registry-query")]
+    fn contains(text: &str, substring: &str) -> String {
+        format!("strpos({}, {}) > 0", text, substring)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl MetadataSql for diesel::sqlite::Sqlite {
+    #[cfg(feature = "registry-query")]
+    fn numeric_value(text: &str) -> Option<String> {
+        // SQLite casts any text to a number (e.g. 'abc' to 0), so only values that are numbers
+        // are cast. GLOB has no repetition, so the syntax is checked piece by piece: only digits,
+        // one decimal point before an optional exponent, signs only at the start or directly
+        // after the exponent's 'e', and digits in both the mantissa and the exponent.
+        Some(format!(
+            "CASE WHEN trim({0}) NOT GLOB '*[^0-9.eE+-]*' \
+             AND trim({0}) GLOB '*[0-9]*' \
+             AND trim({0}) NOT GLOB '*[^eE][+-]*' \
+             AND trim({0}) NOT GLOB '*.*.*' \
+             AND trim({0}) NOT GLOB '*[eE]*[eE]*' \
+             AND trim({0}) NOT GLOB '*[eE]*.*' \
+             AND trim({0}) NOT GLOB '*[eE]' \
+             AND trim({0}) NOT GLOB '*[eE][+-]' \
+             AND (trim({0}) NOT GLOB '*[eE]*' OR trim({0}) GLOB '*[0-9]*[eE]*') \
+             THEN CAST(trim({0}) AS REAL) END",
+            text
+        ))
+    }
+
+    #[cfg(feature = "registry-query")]
+    fn contains(text: &str, substring: &str) -> String {
+        format!("instr({}, {}) > 0", text, substring)
+    }
+}
+
+/// The node metadata predicates of a query, split into the SQL condition that can be pushed down
+/// into the query and the predicates that must be applied to the query's results.
+struct MetadataFilter<'a> {
+    /// The SQL condition for `SELECT _ FROM splinter_nodes` queries, if any predicates could be
+    /// expressed in SQL
+    sql: Option<String>,
+    /// The predicates that could not be expressed in SQL
+    remaining: Vec<&'a MetadataPredicate>,
+}
+
+impl<'a> MetadataFilter<'a> {
+    /// Generates the filter for a list of node metadata predicates. Filtering on node metadata is
+    /// too complicated for pure Diesel, so raw SQL queries are needed.
+    ///
+    /// Each predicate is converted to a series of `EXISTS` SQL statements, which check for the
+    /// existence of a matching metadata value in the `splinter_nodes_metadata` table.
+    fn new<B: MetadataSql>(predicates: &'a [MetadataPredicate]) -> Self {
+        let mut conditions = vec![];
+        let mut remaining = vec![];
+        for predicate in predicates {
+            match predicate_to_sql::<B>(predicate) {
+                Some(condition) => conditions.push(condition),
+                None => remaining.push(predicate),
+            }
+        }
+
+        let sql = if conditions.is_empty() {
+            None
+        } else {
+            Some(conditions.join(" AND "))
+        };
+
+        MetadataFilter { sql, remaining }
+    }
+
+    /// Returns whether or not the node matches the predicates that were not pushed down.
+    fn apply_remaining(&self, node: &Node) -> bool {
+        self.remaining.iter().all(|predicate| predicate.apply(node))
+    }
+}
+
+/// Converts a predicate to a SQL condition; returns `None` if the predicate cannot be expressed
+/// in SQL for the backend.
+fn predicate_to_sql<B: MetadataSql>(predicate: &MetadataPredicate) -> Option<String> {
+    let value = "splinter_nodes_metadata.value";
+    let sql = match predicate {
+        MetadataPredicate::Eq(key, val) => {
+            metadata_exists(key, Some(&format!("{} = {}", value, quote(val))))
+        }
+        // If the metadata key is not set for a node, the predicate is satisfied
+        MetadataPredicate::Ne(key, val) => format!(
+            "NOT {}",
+            metadata_exists(key, Some(&format!("{} = {}", value, quote(val))))
+        ),
+        MetadataPredicate::Gt(key, val) => {
+            metadata_exists(key, Some(&format!("{} > {}", value, quote(val))))
+        }
+        MetadataPredicate::Ge(key, val) => {
+            metadata_exists(key, Some(&format!("{} >= {}", value, quote(val))))
+        }
+        MetadataPredicate::Lt(key, val) => {
+            metadata_exists(key, Some(&format!("{} < {}", value, quote(val))))
+        }
+        MetadataPredicate::Le(key, val) => {
+            metadata_exists(key, Some(&format!("{} <= {}", value, quote(val))))
+        }
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::Compare {
+            key,
+            operator,
+            value_type: ValueType::Number,
+            value: val,
+        } => {
+            let numeric_value = B::numeric_value(value)?;
+            // Formatting a parsed number never uses exponent notation, so it is a valid literal
+            let number = parse_number(val)?;
+            match operator {
+                // A missing or non-numeric value is "not equal"
+                ComparisonOperator::Ne => format!(
+                    "NOT {}",
+                    metadata_exists(key, Some(&format!("{} = {}", numeric_value, number)))
                 ),
-                MetadataPredicate::Le(key, val) => format!(
-                    "EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
-                     splinter_nodes_metadata.identity = splinter_nodes.identity \
-                     AND splinter_nodes_metadata.key = {} \
-                     AND splinter_nodes_metadata.value <= {})",
-                    key, val
+                _ => metadata_exists(
+                    key,
+                    Some(&format!(
+                        "{} {} {}",
+                        numeric_value,
+                        sql_operator(*operator),
+                        number
+                    )),
                 ),
             }
-        })
-        .collect::<Vec<_>>()
-        .join(" AND ")
+        }
+        // Versions and timestamps are compared in memory
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::Compare { .. } => return None,
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::In(key, vals) => {
+            if vals.is_empty() {
+                "1 = 0".to_string()
+            } else {
+                let vals = vals.iter().map(|val| quote(val)).collect::<Vec<_>>();
+                metadata_exists(key, Some(&format!("{} IN ({})", value, vals.join(", "))))
+            }
+        }
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::Contains(key, val) => {
+            metadata_exists(key, Some(&B::contains(value, &quote(val))))
+        }
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::Exists(key) => metadata_exists(key, None),
+        // Regular expression syntax differs between backends, so these are matched in memory
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::Matches(..) => return None,
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::And(predicates) => {
+            if predicates.is_empty() {
+                "1 = 1".to_string()
+            } else {
+                predicates
+                    .iter()
+                    .map(predicate_to_sql::<B>)
+                    .collect::<Option<Vec<_>>>()?
+                    .join(" AND ")
+            }
+        }
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::Or(predicates) => {
+            if predicates.is_empty() {
+                "1 = 0".to_string()
+            } else {
+                predicates
+                    .iter()
+                    .map(predicate_to_sql::<B>)
+                    .collect::<Option<Vec<_>>>()?
+                    .join(" OR ")
+            }
+        }
+        #[cfg(feature = "registry-query")]
+        MetadataPredicate::Not(predicate) => format!("NOT {}", predicate_to_sql::<B>(predicate)?),
+    };
+
+    Some(format!("({})", sql))
+}
+
+/// Returns an `EXISTS` statement that checks for a metadata entry for the node with the given key
+/// and, optionally, a value that satisfies the given condition.
+fn metadata_exists(key: &str, condition: Option<&str>) -> String {
+    let mut statement = format!(
+        "EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
+         splinter_nodes_metadata.identity = splinter_nodes.identity \
+         AND splinter_nodes_metadata.key = {}",
+        quote(key)
+    );
+    if let Some(condition) = condition {
+        statement.push_str(&format!(" AND {}", condition));
+    }
+    statement.push(')');
+    statement
+}
+
+#[cfg(feature = "registry-query")]
+fn sql_operator(operator: ComparisonOperator) -> &'static str {
+    match operator {
+        ComparisonOperator::Eq => "=",
+        ComparisonOperator::Ne => "<>",
+        ComparisonOperator::Gt => ">",
+        ComparisonOperator::Ge => ">=",
+        ComparisonOperator::Lt => "<",
+        ComparisonOperator::Le => "<=",
+    }
+}
+
+/// Quotes the string as a SQL string literal.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(all(test, feature = "registry-query", feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::sqlite::Sqlite;
+
+    /// Verify that predicates that can be expressed in SQL are pushed down, and that the rest are
+    /// left to be applied in memory.
+    #[test]
+    fn metadata_filter_pushdown() {
+        let predicates = vec![
+            MetadataPredicate::eq("company", "Bitwise IO"),
+            MetadataPredicate::Or(vec![
+                MetadataPredicate::Exists("region".into()),
+                MetadataPredicate::Contains("name".into(), "it's".into()),
+            ]),
+            MetadataPredicate::compare("capacity", ComparisonOperator::Gt, ValueType::Number, "9")
+                .expect("Failed to create predicate"),
+            MetadataPredicate::Not(Box::new(
+                MetadataPredicate::matches("region", "^us-").expect("Failed to create predicate"),
+            )),
+        ];
+
+        let filter = MetadataFilter::new::<Sqlite>(&predicates);
+
+        assert_eq!(
+            filter.sql,
+            Some(format!(
+                "(EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
+                 splinter_nodes_metadata.identity = splinter_nodes.identity \
+                 AND splinter_nodes_metadata.key = 'company' \
+                 AND splinter_nodes_metadata.value = 'Bitwise IO')) \
+                 AND ((EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
+                 splinter_nodes_metadata.identity = splinter_nodes.identity \
+                 AND splinter_nodes_metadata.key = 'region')) \
+                 OR (EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
+                 splinter_nodes_metadata.identity = splinter_nodes.identity \
+                 AND splinter_nodes_metadata.key = 'name' \
+                 AND instr(splinter_nodes_metadata.value, 'it''s') > 0))) \
+                 AND (EXISTS (SELECT * FROM splinter_nodes_metadata WHERE \
+                 splinter_nodes_metadata.identity = splinter_nodes.identity \
+                 AND splinter_nodes_metadata.key = 'capacity' \
+                 AND {} > 9))",
+                Sqlite::numeric_value("splinter_nodes_metadata.value")
+                    .expect("SQLite numbers not supported")
+            ))
+        );
+        // No backend matches regular expressions
+        assert_eq!(filter.remaining.len(), 1);
+    }
 }
@@ -157,3 +157,28 @@ impl fmt::Display for InvalidNodeError {
         }
     }
 }
+
+/// Returned by a `RegistryEventSubscriber` that failed to handle an event
+#[cfg(feature = "registry-events")]
+#[derive(Debug)]
+pub enum RegistrySubscriberError {
+    /// The subscriber was unable to handle the event
+    UnableToHandleEvent(String),
+    /// The subscriber no longer wishes to receive events and should be removed
+    Unsubscribe,
+}
+
+#[cfg(feature = "registry-events")]
+impl Error for RegistrySubscriberError {}
+
+#[cfg(feature = "registry-events")]
+impl fmt::Display for RegistrySubscriberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistrySubscriberError::UnableToHandleEvent(msg) => {
+                write!(f, "Unable to handle event: {}", msg)
+            }
+            RegistrySubscriberError::Unsubscribe => f.write_str("Unsubscribe"),
+        }
+    }
+}
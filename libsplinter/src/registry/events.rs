@@ -0,0 +1,327 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Events emitted when the contents of a registry change.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, TryLockError};
+
+use super::{Node, RegistryError, RegistrySubscriberError};
+
+/// A change to the contents of a registry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RegistryEvent {
+    /// A node was added to the registry.
+    NodeAdded(Node),
+    /// A node that was already in the registry has changed.
+    NodeUpdated { old: Node, new: Node },
+    /// A node was removed from the registry.
+    NodeRemoved(Node),
+}
+
+impl RegistryEvent {
+    /// Returns the identity of the node that this event is about.
+    pub fn identity(&self) -> &str {
+        match self {
+            RegistryEvent::NodeAdded(node) => &node.identity,
+            RegistryEvent::NodeUpdated { new, .. } => &new.identity,
+            RegistryEvent::NodeRemoved(node) => &node.identity,
+        }
+    }
+}
+
+/// Receives the changes made to a registry.
+pub trait RegistryEventSubscriber: Send {
+    /// Handles a single registry event. Returning `RegistrySubscriberError::Unsubscribe` removes
+    /// the subscriber from the registry.
+    fn handle_event(&self, event: &RegistryEvent) -> Result<(), RegistrySubscriberError>;
+
+    /// Returns whether the subscriber will no longer handle events, so that it can be removed
+    /// without waiting for the next event.
+    fn is_closed(&self) -> bool {
+        false
+    }
+}
+
+impl<F> RegistryEventSubscriber for F
+where
+    F: Fn(&RegistryEvent) -> Result<(), RegistrySubscriberError> + Send,
+{
+    fn handle_event(&self, event: &RegistryEvent) -> Result<(), RegistrySubscriberError> {
+        (*self)(event)
+    }
+}
+
+/// A subscriber shared between the subscriber list and the notifications in progress.
+type SharedSubscriber = Arc<Mutex<Box<dyn RegistryEventSubscriber>>>;
+
+/// The set of subscribers registered with a registry implementation.
+#[derive(Clone, Default)]
+pub(crate) struct RegistryEventSubscribers {
+    subscribers: Arc<Mutex<Vec<SharedSubscriber>>>,
+}
+
+impl RegistryEventSubscribers {
+    /// Adds the subscriber, removing any subscribers that have been closed.
+    pub fn add(&self, subscriber: Box<dyn RegistryEventSubscriber>) -> Result<(), RegistryError> {
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .map_err(|_| RegistryError::general_error("Registry subscriber lock was poisoned"))?;
+        // Subscribers that are busy handling events are kept until a later check
+        subscribers.retain(|subscriber| match subscriber.try_lock() {
+            Ok(subscriber) => !subscriber.is_closed(),
+            Err(TryLockError::WouldBlock) => true,
+            Err(TryLockError::Poisoned(_)) => false,
+        });
+        subscribers.push(Arc::new(Mutex::new(subscriber)));
+        Ok(())
+    }
+
+    /// Sends each of the events, in order, to every subscriber; subscribers that are closed or
+    /// that return `RegistrySubscriberError::Unsubscribe` are dropped.
+    ///
+    /// The subscriber list is not locked while the events are handled, so subscribers may be
+    /// added, and the registry accessed, from within a subscriber.
+    pub fn notify(&self, events: &[RegistryEvent]) {
+        if events.is_empty() {
+            return;
+        }
+
+        let subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers.clone(),
+            Err(_) => {
+                error!("Registry subscriber lock was poisoned; unable to send registry events");
+                return;
+            }
+        };
+
+        let removed = subscribers
+            .into_iter()
+            .filter(|subscriber| !Self::dispatch(subscriber, events))
+            .collect::<Vec<_>>();
+
+        if removed.is_empty() {
+            return;
+        }
+
+        match self.subscribers.lock() {
+            Ok(mut subscribers) => subscribers.retain(|subscriber| {
+                !removed
+                    .iter()
+                    .any(|removed| Arc::ptr_eq(subscriber, removed))
+            }),
+            Err(_) => error!("Registry subscriber lock was poisoned; unable to remove subscribers"),
+        }
+    }
+
+    /// Sends the events to the subscriber; returns whether the subscriber should be kept.
+    fn dispatch(subscriber: &SharedSubscriber, events: &[RegistryEvent]) -> bool {
+        let subscriber = match subscriber.lock() {
+            Ok(subscriber) => subscriber,
+            Err(_) => {
+                error!("Registry subscriber lock was poisoned; removing subscriber");
+                return false;
+            }
+        };
+
+        if subscriber.is_closed() {
+            return false;
+        }
+
+        for event in events {
+            match subscriber.handle_event(event) {
+                Ok(()) => (),
+                Err(RegistrySubscriberError::Unsubscribe) => return false,
+                Err(err) => error!("Unable to handle registry event: {}", err),
+            }
+        }
+        true
+    }
+}
+
+/// Computes the events that transform the `old` set of nodes into the `new` set of nodes. Both
+/// maps are keyed by node identity.
+pub(crate) fn diff_nodes(
+    old: &HashMap<String, Node>,
+    new: &HashMap<String, Node>,
+) -> Vec<RegistryEvent> {
+    let mut events = new
+        .values()
+        .filter_map(|node| match old.get(&node.identity) {
+            None => Some(RegistryEvent::NodeAdded(node.clone())),
+            Some(existing) if existing != node => Some(RegistryEvent::NodeUpdated {
+                old: existing.clone(),
+                new: node.clone(),
+            }),
+            Some(_) => None,
+        })
+        .chain(
+            old.values()
+                .filter(|node| !new.contains_key(&node.identity))
+                .map(|node| RegistryEvent::NodeRemoved(node.clone())),
+        )
+        .collect::<Vec<_>>();
+
+    events.sort_by(|a, b| a.identity().cmp(b.identity()));
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(identity: &str, endpoint: &str) -> Node {
+        Node::builder(identity)
+            .with_endpoint(endpoint)
+            .with_display_name(identity)
+            .with_key("abcd")
+            .build()
+            .expect("Failed to build node")
+    }
+
+    fn node_map(nodes: Vec<Node>) -> HashMap<String, Node> {
+        nodes
+            .into_iter()
+            .map(|node| (node.identity.clone(), node))
+            .collect()
+    }
+
+    /// Verify that `diff_nodes` reports added, updated, and removed nodes, and ignores nodes that
+    /// have not changed.
+    #[test]
+    fn diff_nodes_events() {
+        let old = node_map(vec![
+            node("node-1", "tcps://12.0.0.123:8431"),
+            node("node-2", "tcps://12.0.0.123:8432"),
+            node("node-3", "tcps://12.0.0.123:8433"),
+        ]);
+        let new = node_map(vec![
+            node("node-1", "tcps://12.0.0.123:8431"),
+            node("node-2", "tcps://12.0.0.123:9432"),
+            node("node-4", "tcps://12.0.0.123:8434"),
+        ]);
+
+        assert_eq!(
+            diff_nodes(&old, &new),
+            vec![
+                RegistryEvent::NodeUpdated {
+                    old: node("node-2", "tcps://12.0.0.123:8432"),
+                    new: node("node-2", "tcps://12.0.0.123:9432"),
+                },
+                RegistryEvent::NodeRemoved(node("node-3", "tcps://12.0.0.123:8433")),
+                RegistryEvent::NodeAdded(node("node-4", "tcps://12.0.0.123:8434")),
+            ]
+        );
+        assert!(diff_nodes(&new, &new).is_empty());
+    }
+
+    /// Verify that a subscriber returning `Unsubscribe` no longer receives events.
+    #[test]
+    fn subscribers_unsubscribe() {
+        let subscribers = RegistryEventSubscribers::default();
+        let received = Arc::new(Mutex::new(vec![]));
+
+        let received_clone = received.clone();
+        subscribers
+            .add(Box::new(
+                move |event: &RegistryEvent| -> Result<(), RegistrySubscriberError> {
+                    received_clone
+                        .lock()
+                        .expect("Failed to get lock")
+                        .push(event.clone());
+                    Err(RegistrySubscriberError::Unsubscribe)
+                },
+            ))
+            .expect("Failed to add subscriber");
+
+        let event = RegistryEvent::NodeAdded(node("node-1", "tcps://12.0.0.123:8431"));
+        subscribers.notify(&[event.clone()]);
+        subscribers.notify(&[event.clone()]);
+
+        assert_eq!(*received.lock().expect("Failed to get lock"), vec![event]);
+    }
+
+    /// Verify that a subscriber may add another subscriber while it is handling an event, which
+    /// requires that the subscriber list is not locked while events are dispatched.
+    #[test]
+    fn subscribers_add_while_notifying() {
+        let subscribers = RegistryEventSubscribers::default();
+
+        let subscribers_clone = subscribers.clone();
+        subscribers
+            .add(Box::new(
+                move |_: &RegistryEvent| -> Result<(), RegistrySubscriberError> {
+                    subscribers_clone
+                        .add(Box::new(
+                            |_: &RegistryEvent| -> Result<(), RegistrySubscriberError> { Ok(()) },
+                        ))
+                        .expect("Failed to add subscriber");
+                    Err(RegistrySubscriberError::Unsubscribe)
+                },
+            ))
+            .expect("Failed to add subscriber");
+
+        subscribers.notify(&[RegistryEvent::NodeAdded(node(
+            "node-1",
+            "tcps://12.0.0.123:8431",
+        ))]);
+
+        assert_eq!(
+            subscribers
+                .subscribers
+                .lock()
+                .expect("Failed to get lock")
+                .len(),
+            1
+        );
+    }
+
+    /// Verify that closed subscribers are removed when a subscriber is added, without an event
+    /// being sent.
+    #[test]
+    fn subscribers_remove_closed() {
+        struct ClosableSubscriber(Arc<Mutex<bool>>);
+
+        impl RegistryEventSubscriber for ClosableSubscriber {
+            fn handle_event(&self, _: &RegistryEvent) -> Result<(), RegistrySubscriberError> {
+                Ok(())
+            }
+
+            fn is_closed(&self) -> bool {
+                *self.0.lock().expect("Failed to get lock")
+            }
+        }
+
+        let subscribers = RegistryEventSubscribers::default();
+        let closed = Arc::new(Mutex::new(false));
+        subscribers
+            .add(Box::new(ClosableSubscriber(closed.clone())))
+            .expect("Failed to add subscriber");
+        *closed.lock().expect("Failed to get lock") = true;
+
+        subscribers
+            .add(Box::new(ClosableSubscriber(Arc::new(Mutex::new(false)))))
+            .expect("Failed to add subscriber");
+
+        assert_eq!(
+            subscribers
+                .subscribers
+                .lock()
+                .expect("Failed to get lock")
+                .len(),
+            1
+        );
+    }
+}
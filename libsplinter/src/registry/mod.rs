@@ -27,6 +27,10 @@
 #[cfg(feature = "registry-database")]
 mod diesel;
 mod error;
+#[cfg(feature = "registry-events")]
+mod events;
+#[cfg(feature = "registry-query")]
+mod query;
 #[cfg(feature = "rest-api")]
 mod rest_api;
 mod unified;
@@ -35,17 +39,28 @@ mod yaml;
 use std::collections::HashMap;
 use std::iter::ExactSizeIterator;
 
+#[cfg(feature = "registry-query")]
+use regex::Regex;
+
 #[cfg(all(feature = "registry-database", feature = "postgres"))]
 pub use self::diesel::migrations::run_postgres_migrations;
 #[cfg(all(feature = "registry-database", feature = "sqlite"))]
 pub use self::diesel::migrations::run_sqlite_migrations;
 #[cfg(feature = "registry-database")]
 pub use self::diesel::DieselRegistry;
+#[cfg(feature = "registry-events")]
+pub use error::RegistrySubscriberError;
 pub use error::{InvalidNodeError, RegistryError};
+#[cfg(feature = "registry-events")]
+pub use events::{RegistryEvent, RegistryEventSubscriber};
+#[cfg(feature = "registry-query")]
+pub use query::{ComparisonOperator, ValueType};
 pub use unified::UnifiedRegistry;
 pub use yaml::LocalYamlRegistry;
+#[cfg(feature = "registry-remote-signing")]
+pub use yaml::RemoteYamlPublisherVerifier;
 #[cfg(feature = "registry-remote")]
-pub use yaml::{RemoteYamlRegistry, RemoteYamlShutdownHandle};
+pub use yaml::{RemoteYamlRegistry, RemoteYamlShutdownHandle, RemoteYamlStatusHandle};
 
 /// Native representation of a node in a registry.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -162,6 +177,9 @@ impl NodeBuilder {
 ///
 /// If the item is missing in a node's metadata table, the predicate returns false (with the
 /// exception of the `Ne` variant).
+///
+/// The `Eq`, `Ne`, `Gt`, `Ge`, `Lt`, and `Le` variants compare values as strings; the `Compare`
+/// variant compares them as numbers, semantic versions, or timestamps.
 #[derive(Clone)]
 pub enum MetadataPredicate {
     /// Applies the `==` operator.
@@ -176,6 +194,37 @@ pub enum MetadataPredicate {
     Lt(String, String),
     /// Applies the `<=` operator.
     Le(String, String),
+    /// Applies the operator to the values at the key, interpreted as the given type. If the item
+    /// is missing or cannot be interpreted as the type, the predicate returns false (with the
+    /// exception of the `Ne` operator).
+    #[cfg(feature = "registry-query")]
+    Compare {
+        key: String,
+        operator: ComparisonOperator,
+        value_type: ValueType,
+        value: String,
+    },
+    /// Returns true if the value at the key is one of the given values.
+    #[cfg(feature = "registry-query")]
+    In(String, Vec<String>),
+    /// Returns true if the value at the key contains the given substring.
+    #[cfg(feature = "registry-query")]
+    Contains(String, String),
+    /// Returns true if the key is set, regardless of its value.
+    #[cfg(feature = "registry-query")]
+    Exists(String),
+    /// Returns true if the value at the key matches the regular expression.
+    #[cfg(feature = "registry-query")]
+    Matches(String, Regex),
+    /// Returns true if all of the predicates are true.
+    #[cfg(feature = "registry-query")]
+    And(Vec<MetadataPredicate>),
+    /// Returns true if any of the predicates are true.
+    #[cfg(feature = "registry-query")]
+    Or(Vec<MetadataPredicate>),
+    /// Returns true if the predicate is false.
+    #[cfg(feature = "registry-query")]
+    Not(Box<MetadataPredicate>),
 }
 
 impl MetadataPredicate {
@@ -201,6 +250,49 @@ impl MetadataPredicate {
             MetadataPredicate::Le(key, val) => {
                 node.metadata.get(key).map(|v| v <= val).unwrap_or(false)
             }
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::Compare {
+                key,
+                operator,
+                value_type,
+                value,
+            } => node
+                .metadata
+                .get(key)
+                .and_then(|v| value_type.compare(v, value))
+                .map(|ordering| operator.accepts(ordering))
+                // As with `Ne`, a missing or incomparable value is "not equal"
+                .unwrap_or(*operator == ComparisonOperator::Ne),
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::In(key, vals) => node
+                .metadata
+                .get(key)
+                .map(|v| vals.contains(v))
+                .unwrap_or(false),
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::Contains(key, val) => node
+                .metadata
+                .get(key)
+                .map(|v| v.contains(val.as_str()))
+                .unwrap_or(false),
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::Exists(key) => node.metadata.contains_key(key),
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::Matches(key, regex) => node
+                .metadata
+                .get(key)
+                .map(|v| regex.is_match(v))
+                .unwrap_or(false),
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::And(predicates) => {
+                predicates.iter().all(|predicate| predicate.apply(node))
+            }
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::Or(predicates) => {
+                predicates.iter().any(|predicate| predicate.apply(node))
+            }
+            #[cfg(feature = "registry-query")]
+            MetadataPredicate::Not(predicate) => !predicate.apply(node),
         }
     }
 
@@ -213,6 +305,46 @@ impl MetadataPredicate {
     pub fn ne<S: Into<String>>(key: S, value: S) -> MetadataPredicate {
         MetadataPredicate::Ne(key.into(), value.into())
     }
+
+    /// Returns the `Compare` predicate for the given key, operator, type and value; returns an
+    /// error if the value cannot be interpreted as the type.
+    #[cfg(feature = "registry-query")]
+    pub fn compare<S: Into<String>>(
+        key: S,
+        operator: ComparisonOperator,
+        value_type: ValueType,
+        value: S,
+    ) -> Result<MetadataPredicate, RegistryError> {
+        let value = value.into();
+        if !value_type.is_valid(&value) {
+            return Err(RegistryError::general_error(&format!(
+                "{} is not a valid {:?} value",
+                value, value_type
+            )));
+        }
+        Ok(MetadataPredicate::Compare {
+            key: key.into(),
+            operator,
+            value_type,
+            value,
+        })
+    }
+
+    /// Returns the `Matches` predicate for the given key and regular expression; returns an error
+    /// if the regular expression is invalid.
+    #[cfg(feature = "registry-query")]
+    pub fn matches<S: Into<String>>(
+        key: S,
+        pattern: &str,
+    ) -> Result<MetadataPredicate, RegistryError> {
+        let regex = Regex::new(pattern).map_err(|err| {
+            RegistryError::general_error_with_source(
+                &format!("Invalid regular expression: {}", pattern),
+                Box::new(err),
+            )
+        })?;
+        Ok(MetadataPredicate::Matches(key.into(), regex))
+    }
 }
 
 /// Type returned by the `RegistryReader::list_nodes` method
@@ -256,6 +388,23 @@ pub trait RegistryReader: Send + Sync {
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         self.fetch_node(identity).map(|opt| opt.is_some())
     }
+
+    /// Adds a subscriber that will be notified of every node that is added to, updated in, or
+    /// removed from the registry. Returns an error if the registry does not support
+    /// subscriptions.
+    ///
+    /// # Arguments
+    ///
+    ///  * `subscriber` - The subscriber that will receive the registry's events.
+    #[cfg(feature = "registry-events")]
+    fn add_event_subscriber(
+        &self,
+        _subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        Err(RegistryError::general_error(
+            "Registry does not support event subscriptions",
+        ))
+    }
 }
 
 /// Defines registry write capabilities.
@@ -268,6 +417,22 @@ pub trait RegistryWriter: Send + Sync {
     ///
     fn insert_node(&self, node: Node) -> Result<(), RegistryError>;
 
+    /// Adds or replaces all of the given nodes in the registry.
+    ///
+    /// By default, the nodes are inserted one at a time, so if inserting a node fails, the nodes
+    /// before it remain in the registry. Registries backed by a database insert all of the nodes
+    /// in a single transaction, so either all of the nodes are inserted or none are.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The nodes to be added to or updated in the registry.
+    ///
+    fn insert_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        nodes
+            .into_iter()
+            .try_for_each(|node| self.insert_node(node))
+    }
+
     /// Deletes a node with the given identity and returns the node if it was in the registry.
     ///
     /// # Arguments
@@ -324,6 +489,14 @@ where
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         (**self).has_node(identity)
     }
+
+    #[cfg(feature = "registry-events")]
+    fn add_event_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        (**self).add_event_subscriber(subscriber)
+    }
 }
 
 impl<NW> RegistryWriter for Box<NW>
@@ -334,6 +507,10 @@ where
         (**self).insert_node(node)
     }
 
+    fn insert_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        (**self).insert_nodes(nodes)
+    }
+
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
         (**self).delete_node(identity)
     }
@@ -570,6 +747,69 @@ mod tests {
         assert!(!MetadataPredicate::Le("key".into(), "4".into()).apply(&node));
     }
 
+    /// Verify that the rich metadata predicates are applied correctly, including typed
+    /// comparisons and grouping.
+    #[cfg(feature = "registry-query")]
+    #[test]
+    fn metadata_predicates_rich() {
+        let node = Node::builder("identity")
+            .with_endpoint("endpoint")
+            .with_key("key")
+            .with_metadata("capacity", "10")
+            .with_metadata("region", "us-east-1")
+            .build()
+            .expect("Failed to build node");
+
+        // "10" is lexicographically less than "9", but numerically greater
+        assert!(!MetadataPredicate::Gt("capacity".into(), "9".into()).apply(&node));
+        let numeric_gt =
+            MetadataPredicate::compare("capacity", ComparisonOperator::Gt, ValueType::Number, "9")
+                .expect("Failed to create predicate");
+        assert!(numeric_gt.apply(&node));
+        assert!(MetadataPredicate::compare(
+            "capacity",
+            ComparisonOperator::Gt,
+            ValueType::Number,
+            "nine"
+        )
+        .is_err());
+        // A value that cannot be interpreted as the type is "not equal"
+        assert!(MetadataPredicate::compare(
+            "region",
+            ComparisonOperator::Ne,
+            ValueType::Number,
+            "9"
+        )
+        .expect("Failed to create predicate")
+        .apply(&node));
+
+        assert!(MetadataPredicate::In(
+            "region".into(),
+            vec!["us-west-1".into(), "us-east-1".into()]
+        )
+        .apply(&node));
+        assert!(!MetadataPredicate::In("region".into(), vec![]).apply(&node));
+        assert!(MetadataPredicate::Contains("region".into(), "east".into()).apply(&node));
+        assert!(MetadataPredicate::Exists("region".into()).apply(&node));
+        assert!(!MetadataPredicate::Exists("company".into()).apply(&node));
+        assert!(MetadataPredicate::matches("region", "^us-[a-z]+-[0-9]$")
+            .expect("Failed to create predicate")
+            .apply(&node));
+        assert!(MetadataPredicate::matches("region", "(").is_err());
+
+        assert!(MetadataPredicate::Or(vec![
+            MetadataPredicate::Exists("company".into()),
+            numeric_gt.clone(),
+        ])
+        .apply(&node));
+        assert!(!MetadataPredicate::And(vec![
+            MetadataPredicate::Exists("company".into()),
+            numeric_gt.clone(),
+        ])
+        .apply(&node));
+        assert!(!MetadataPredicate::Not(Box::new(numeric_gt)).apply(&node));
+    }
+
     /// Verify that the `validate_nodes` method properly validates nodes based on the following
     /// criteria:
     ///
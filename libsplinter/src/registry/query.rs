@@ -0,0 +1,144 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed comparisons of node metadata values, used by `MetadataPredicate::Compare`.
+
+use std::cmp::Ordering;
+
+use chrono::DateTime;
+use semver::Version;
+
+/// The operator of a typed metadata comparison.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComparisonOperator {
+    /// Applies the `==` operator.
+    Eq,
+    /// Applies the `!=` operator.
+    Ne,
+    /// Applies the `>` operator.
+    Gt,
+    /// Applies the `>=` operator.
+    Ge,
+    /// Applies the `<` operator.
+    Lt,
+    /// Applies the `<=` operator.
+    Le,
+}
+
+impl ComparisonOperator {
+    /// Returns whether or not the ordering of the node's value relative to the predicate's value
+    /// satisfies this operator.
+    pub(crate) fn accepts(self, ordering: Ordering) -> bool {
+        match self {
+            ComparisonOperator::Eq => ordering == Ordering::Equal,
+            ComparisonOperator::Ne => ordering != Ordering::Equal,
+            ComparisonOperator::Gt => ordering == Ordering::Greater,
+            ComparisonOperator::Ge => ordering != Ordering::Less,
+            ComparisonOperator::Lt => ordering == Ordering::Less,
+            ComparisonOperator::Le => ordering != Ordering::Greater,
+        }
+    }
+}
+
+/// The type that metadata values are interpreted as for a typed comparison.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueType {
+    /// A finite decimal number, such as `10` or `-2.5e3`.
+    Number,
+    /// A semantic version, such as `1.2.3` or `2.0.0-rc.1`.
+    Version,
+    /// An RFC 3339 timestamp, such as `2020-06-01T12:00:00Z`.
+    Timestamp,
+}
+
+impl ValueType {
+    /// Returns whether or not the value can be interpreted as this type.
+    pub fn is_valid(self, value: &str) -> bool {
+        match self {
+            ValueType::Number => parse_number(value).is_some(),
+            ValueType::Version => Version::parse(value.trim()).is_ok(),
+            ValueType::Timestamp => DateTime::parse_from_rfc3339(value.trim()).is_ok(),
+        }
+    }
+
+    /// Compares two values as this type; returns `None` if either value cannot be interpreted as
+    /// this type.
+    pub(crate) fn compare(self, lhs: &str, rhs: &str) -> Option<Ordering> {
+        match self {
+            ValueType::Number => parse_number(lhs)?.partial_cmp(&parse_number(rhs)?),
+            ValueType::Version => Some(
+                Version::parse(lhs.trim())
+                    .ok()?
+                    .cmp(&Version::parse(rhs.trim()).ok()?),
+            ),
+            ValueType::Timestamp => Some(
+                DateTime::parse_from_rfc3339(lhs.trim())
+                    .ok()?
+                    .cmp(&DateTime::parse_from_rfc3339(rhs.trim()).ok()?),
+            ),
+        }
+    }
+}
+
+/// Parses a value as a finite number.
+pub(crate) fn parse_number(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that values are compared according to their type, rather than lexicographically,
+    /// and that values that are not of the type cannot be compared.
+    #[test]
+    fn typed_comparisons() {
+        assert_eq!(
+            ValueType::Number.compare("10", "9"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            ValueType::Number.compare(" 2.5e1", "25"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(ValueType::Number.compare("ten", "9"), None);
+        assert_eq!(ValueType::Number.compare("inf", "9"), None);
+
+        assert_eq!(
+            ValueType::Version.compare("1.10.0", "1.9.0"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            ValueType::Version.compare("2.0.0-rc.1", "2.0.0"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(ValueType::Version.compare("1.9", "1.9.0"), None);
+
+        assert_eq!(
+            ValueType::Timestamp.compare("2020-06-01T12:00:00Z", "2020-06-01T13:00:00+02:00"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            ValueType::Timestamp.compare("2020-06-01", "2020-06-01"),
+            None
+        );
+
+        assert!(ComparisonOperator::Ge.accepts(Ordering::Equal));
+        assert!(!ComparisonOperator::Lt.accepts(Ordering::Equal));
+    }
+}
@@ -19,24 +19,42 @@
 
 use std::collections::HashMap;
 
+#[cfg(feature = "registry-query")]
+use serde_json::Value;
+
 use crate::actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::futures::{future::IntoFuture, stream::Stream, Future};
 use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::registry::rest_api::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
 use crate::registry::{
     rest_api::resources::nodes::{ListNodesResponse, NodeResponse},
     InvalidNodeError, MetadataPredicate, Node, RegistryError, RegistryReader, RegistryWriter,
     RwRegistry,
 };
+#[cfg(feature = "registry-query")]
+use crate::registry::{ComparisonOperator, ValueType};
 use crate::rest_api::{
     paging::{get_response_paging_info, DEFAULT_LIMIT, DEFAULT_OFFSET},
     percent_encode_filter_query, ErrorResponse, Method, ProtocolVersionRangeGuard, Resource,
 };
 
+#[cfg(not(feature = "registry-query"))]
 type Filter = HashMap<String, (String, String)>;
+/// A filter object; see `to_predicates` for its format.
+#[cfg(feature = "registry-query")]
+type Filter = Value;
 
 pub fn make_nodes_resource(registry: Box<dyn RwRegistry>) -> Resource {
     let registry1 = registry.clone();
-    Resource::build("/registry/nodes")
+    let resource = Resource::build("/registry/nodes");
+    #[cfg(feature = "authorization")]
+    let resource = resource
+        .with_permission(Permission::Check(REGISTRY_WRITE_PERMISSION))
+        .with_method_permission(Method::Get, Permission::Check(REGISTRY_READ_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::REGISTRY_LIST_NODES_MIN,
             protocol::REGISTRY_PROTOCOL_VERSION,
@@ -177,6 +195,7 @@ fn query_list_nodes(
     })
 }
 
+#[cfg(not(feature = "registry-query"))]
 fn to_predicates(filters: Option<Filter>) -> Result<Vec<MetadataPredicate>, String> {
     match filters {
         Some(filters) => filters
@@ -195,6 +214,140 @@ fn to_predicates(filters: Option<Filter>) -> Result<Vec<MetadataPredicate>, Stri
     }
 }
 
+/// Converts a filter object to a list of predicates, which are combined as an AND.
+///
+/// Each entry of the object maps a metadata key to a condition array of the form
+/// `[OPERATOR, VALUE, TYPE]`:
+///
+/// * `=`, `!=`, `>`, `>=`, `<` and `<=` compare the value. The optional type is one of `number`,
+///   `version` or `timestamp`; if it is omitted, the values are compared as strings, unless the
+///   value is a JSON number.
+/// * `in` matches any of the values in an array.
+/// * `contains` matches values that contain a substring.
+/// * `matches` matches values against a regular expression.
+/// * `exists` matches nodes that have the key, and takes no value.
+///
+/// The special entries `$and` and `$or` group an array of filter objects, and `$not` negates a
+/// filter object.
+#[cfg(feature = "registry-query")]
+fn to_predicates(filters: Option<Filter>) -> Result<Vec<MetadataPredicate>, String> {
+    match filters {
+        Some(filters) => parse_filter_object(&filters),
+        None => Ok(vec![]),
+    }
+}
+
+#[cfg(feature = "registry-query")]
+fn parse_filter_object(filter: &Value) -> Result<Vec<MetadataPredicate>, String> {
+    filter
+        .as_object()
+        .ok_or_else(|| format!("{} is not a filter object", filter))?
+        .iter()
+        .map(|(key, condition)| match key.as_str() {
+            "$and" => Ok(MetadataPredicate::And(parse_filter_list(condition)?)),
+            "$or" => Ok(MetadataPredicate::Or(parse_filter_list(condition)?)),
+            "$not" => Ok(MetadataPredicate::Not(Box::new(MetadataPredicate::And(
+                parse_filter_object(condition)?,
+            )))),
+            _ => parse_condition(key, condition),
+        })
+        .collect()
+}
+
+#[cfg(feature = "registry-query")]
+fn parse_filter_list(filters: &Value) -> Result<Vec<MetadataPredicate>, String> {
+    filters
+        .as_array()
+        .ok_or_else(|| format!("{} is not a list of filter objects", filters))?
+        .iter()
+        .map(|filter| parse_filter_object(filter).map(MetadataPredicate::And))
+        .collect()
+}
+
+#[cfg(feature = "registry-query")]
+fn parse_condition(key: &str, condition: &Value) -> Result<MetadataPredicate, String> {
+    let condition = condition
+        .as_array()
+        .ok_or_else(|| format!("{} is not a condition array", condition))?;
+    let operator = condition
+        .get(0)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("condition for {} is missing an operator", key))?;
+    let operand = || {
+        condition
+            .get(1)
+            .ok_or_else(|| format!("condition for {} is missing a value", key))
+    };
+
+    let comparison_operator = match operator {
+        "=" => ComparisonOperator::Eq,
+        "!=" => ComparisonOperator::Ne,
+        ">" => ComparisonOperator::Gt,
+        ">=" => ComparisonOperator::Ge,
+        "<" => ComparisonOperator::Lt,
+        "<=" => ComparisonOperator::Le,
+        "in" => {
+            let values = operand()?
+                .as_array()
+                .ok_or_else(|| format!("value for {} must be a list", key))?
+                .iter()
+                .map(|value| parse_scalar(key, value).map(|(value, _)| value))
+                .collect::<Result<_, _>>()?;
+            return Ok(MetadataPredicate::In(key.into(), values));
+        }
+        "contains" => {
+            let (value, _) = parse_scalar(key, operand()?)?;
+            return Ok(MetadataPredicate::Contains(key.into(), value));
+        }
+        "matches" => {
+            let (pattern, _) = parse_scalar(key, operand()?)?;
+            return MetadataPredicate::matches(key, &pattern).map_err(|err| err.to_string());
+        }
+        "exists" => return Ok(MetadataPredicate::Exists(key.into())),
+        _ => return Err(format!("{} is not a valid operator", operator)),
+    };
+
+    let (value, is_number) = parse_scalar(key, operand()?)?;
+    let value_type = match condition.get(2) {
+        Some(value_type) => match value_type.as_str() {
+            Some("number") => Some(ValueType::Number),
+            Some("version") => Some(ValueType::Version),
+            Some("timestamp") => Some(ValueType::Timestamp),
+            _ => return Err(format!("{} is not a valid value type", value_type)),
+        },
+        None if is_number => Some(ValueType::Number),
+        None => None,
+    };
+
+    match value_type {
+        Some(value_type) => {
+            MetadataPredicate::compare(key, comparison_operator, value_type, value.as_str())
+                .map_err(|err| err.to_string())
+        }
+        None => Ok(match comparison_operator {
+            ComparisonOperator::Eq => MetadataPredicate::Eq(key.into(), value),
+            ComparisonOperator::Ne => MetadataPredicate::Ne(key.into(), value),
+            ComparisonOperator::Gt => MetadataPredicate::Gt(key.into(), value),
+            ComparisonOperator::Ge => MetadataPredicate::Ge(key.into(), value),
+            ComparisonOperator::Lt => MetadataPredicate::Lt(key.into(), value),
+            ComparisonOperator::Le => MetadataPredicate::Le(key.into(), value),
+        }),
+    }
+}
+
+/// Gets a string or number value as a string, along with whether or not it was a number.
+#[cfg(feature = "registry-query")]
+fn parse_scalar(key: &str, value: &Value) -> Result<(String, bool), String> {
+    match value {
+        Value::String(value) => Ok((value.clone(), false)),
+        Value::Number(value) => Ok((value.to_string(), true)),
+        _ => Err(format!(
+            "value for {} must be a string or a number: {}",
+            key, value
+        )),
+    }
+}
+
 fn add_node(
     payload: web::Payload,
     registry: web::Data<Box<dyn RwRegistry>>,
@@ -400,6 +553,60 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[cfg(feature = "registry-query")]
+    #[test]
+    /// Tests that rich filters are converted to the expected predicates, and that invalid filters
+    /// are rejected.
+    fn test_rich_filter_predicates() {
+        let node1 = get_node_1();
+        let node2 = get_node_2();
+        let matching = |filter: &str| {
+            let predicates = to_predicates(Some(
+                serde_json::from_str(filter).expect("Failed to parse filter"),
+            ))
+            .expect("Failed to convert filter");
+            vec![&node1, &node2]
+                .into_iter()
+                .filter(|node| predicates.iter().all(|predicate| predicate.apply(node)))
+                .map(|node| node.identity.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            matching("{\"company\":[\"=\",\"Bitwise IO\"]}"),
+            vec!["Node-123"]
+        );
+        assert_eq!(
+            matching("{\"company\":[\"in\",[\"Cargill\",\"Intel\"]]}"),
+            vec!["Node-456"]
+        );
+        assert_eq!(
+            matching(
+                "{\"$or\":[{\"company\":[\"contains\",\"IO\"]},\
+                 {\"company\":[\"matches\",\"^Car\"]}]}"
+            ),
+            vec!["Node-123", "Node-456"]
+        );
+        assert_eq!(
+            matching("{\"$not\":{\"company\":[\"exists\"]}}"),
+            Vec::<String>::new()
+        );
+
+        for filter in &[
+            "{\"company\":[\"*\",\"Bitwise IO\"]}",
+            "{\"company\":[\">\",\"nine\",\"number\"]}",
+            "{\"company\":[\">\",\"9\",\"decimal\"]}",
+            "{\"company\":[\"matches\",\"(\"]}",
+            "{\"company\":[\"in\",\"Cargill\"]}",
+            "{\"$or\":{\"company\":[\"exists\"]}}",
+        ] {
+            assert!(to_predicates(Some(
+                serde_json::from_str(filter).expect("Failed to parse filter")
+            ))
+            .is_err());
+        }
+    }
+
     #[test]
     /// Test the POST /registry/nodes route for adding a node to the registry.
     fn test_add_node() {
@@ -19,8 +19,12 @@
 //! * `DELETE /registry/nodes/{identity}` for deleting a node from the registry
 
 use crate::actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::futures::{future::IntoFuture, stream::Stream, Future};
 use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::registry::rest_api::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
 use crate::registry::{
     rest_api::resources::nodes_identity::NodeResponse, InvalidNodeError, Node, RegistryError,
     RegistryReader, RegistryWriter, RwRegistry,
@@ -30,7 +34,12 @@ use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource
 pub fn make_nodes_identity_resource(registry: Box<dyn RwRegistry>) -> Resource {
     let registry1 = registry.clone();
     let registry2 = registry.clone();
-    Resource::build("/registry/nodes/{identity}")
+    let resource = Resource::build("/registry/nodes/{identity}");
+    #[cfg(feature = "authorization")]
+    let resource = resource
+        .with_permission(Permission::Check(REGISTRY_WRITE_PERMISSION))
+        .with_method_permission(Method::Get, Permission::Check(REGISTRY_READ_PERMISSION));
+    resource
         .add_request_guard(ProtocolVersionRangeGuard::new(
             protocol::REGISTRY_FETCH_NODE_MIN,
             protocol::REGISTRY_PROTOCOL_VERSION,
@@ -22,6 +22,13 @@ use crate::rest_api::{Resource, RestResourceProvider};
 
 use super::RwRegistry;
 
+/// The permission that is required to list, fetch and subscribe to nodes
+#[cfg(feature = "authorization")]
+pub const REGISTRY_READ_PERMISSION: &str = "registry.read";
+/// The permission that is required to add, replace and delete nodes
+#[cfg(feature = "authorization")]
+pub const REGISTRY_WRITE_PERMISSION: &str = "registry.write";
+
 /// The `RwRegistry` trait service provides the following endpoints as REST API resources:
 ///
 /// * `GET /registry/nodes` - List the nodes in the registry
@@ -29,6 +36,11 @@ use super::RwRegistry;
 /// * `GET /registry/nodes/{identity}` - Fetch a specific node in the registry
 /// * `PUT /registry/nodes/{identity}` - Replace a node in the registry
 /// * `DELETE /registry/nodes/{identity}` - Delete a node from the registry
+/// * `GET /registry/nodes/subscribe` - Open a websocket that receives the registry's events
+///   (requires the `registry-events` feature)
+///
+/// If the `authorization` feature is enabled, the `GET` endpoints require the `registry.read`
+/// permission and the others require the `registry.write` permission.
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
@@ -40,6 +52,13 @@ impl RestResourceProvider for dyn RwRegistry {
         #[allow(unused_mut)]
         let mut resources = Vec::new();
 
+        // The subscribe resource must be added before the identity resource, which would
+        // otherwise match its path
+        #[cfg(all(feature = "registry-events", feature = "rest-api-actix"))]
+        resources.push(actix::nodes_subscribe::make_nodes_subscribe_resource(
+            self.clone_box(),
+        ));
+
         #[cfg(feature = "rest-api-actix")]
         {
             resources.append(&mut vec![
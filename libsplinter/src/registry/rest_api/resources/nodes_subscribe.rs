@@ -0,0 +1,41 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::registry::{Node, RegistryEvent};
+
+/// A registry event, as sent to websocket subscribers.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum RegistryEventResponse {
+    NodeAdded { node: Node },
+    NodeUpdated { old: Node, new: Node },
+    NodeRemoved { node: Node },
+}
+
+impl From<&RegistryEvent> for RegistryEventResponse {
+    fn from(event: &RegistryEvent) -> Self {
+        match event {
+            RegistryEvent::NodeAdded(node) => {
+                RegistryEventResponse::NodeAdded { node: node.clone() }
+            }
+            RegistryEvent::NodeUpdated { old, new } => RegistryEventResponse::NodeUpdated {
+                old: old.clone(),
+                new: new.clone(),
+            },
+            RegistryEvent::NodeRemoved(node) => {
+                RegistryEventResponse::NodeRemoved { node: node.clone() }
+            }
+        }
+    }
+}
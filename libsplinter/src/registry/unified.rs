@@ -22,7 +22,14 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+#[cfg(feature = "registry-events")]
+use std::sync::{Mutex, Weak};
 
+#[cfg(feature = "registry-events")]
+use super::{
+    events::{diff_nodes, RegistryEventSubscribers},
+    RegistryEvent, RegistryEventSubscriber, RegistrySubscriberError,
+};
 use super::{
     MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
@@ -61,6 +68,12 @@ use super::{
 /// If the same metadata key is set for the node in different registires, the value for that key
 /// from the highest-precedence registry will be used.
 ///
+/// # Events
+///
+/// Subscribers receive events for changes to the unified view of the nodes, whether the change was
+/// written through this registry or reported by one of the source registries that supports event
+/// subscriptions (such as the `RemoteYamlRegistry`).
+///
 /// [`RegistryReader`]: ../trait.RegistryReader.html
 /// [`RegistryWriter`]: ../trait.RegistryWriter.html
 /// [`RwRegistry`]: ../trait.RwRegistry.html
@@ -72,6 +85,8 @@ use super::{
 pub struct UnifiedRegistry {
     internal_source: Arc<dyn RwRegistry>,
     external_sources: Vec<Arc<dyn RegistryReader>>,
+    #[cfg(feature = "registry-events")]
+    event_state: Arc<EventState>,
 }
 
 impl UnifiedRegistry {
@@ -81,10 +96,91 @@ impl UnifiedRegistry {
         internal_source: Box<dyn RwRegistry>,
         external_sources: Vec<Box<dyn RegistryReader>>,
     ) -> Self {
-        Self {
+        let registry = Self {
             internal_source: internal_source.into(),
             external_sources: external_sources.into_iter().map(Arc::from).collect(),
+            #[cfg(feature = "registry-events")]
+            event_state: Arc::new(EventState::default()),
+        };
+
+        #[cfg(feature = "registry-events")]
+        registry.subscribe_to_sources();
+
+        registry
+    }
+
+    /// Gets the unified view of the nodes, keyed by identity, with duplicates resolved by
+    /// precedence and metadata merged.
+    fn merged_nodes(&self) -> HashMap<String, Node> {
+        self
+            // Get all nodes from all sources
+            .all_nodes()
+            // Deduplicate and merge metadata
+            .fold(HashMap::<String, Node>::new(), |mut acc, mut node| {
+                // If the node is already present, merge metadata
+                if let Some(existing) = acc.remove(&node.identity) {
+                    // Overwrite the existing node's metadata with the new node's if they share
+                    // the same metadata keys
+                    let mut merged_metadata = existing.metadata;
+                    merged_metadata.extend(node.metadata);
+                    node.metadata = merged_metadata;
+                }
+                acc.insert(node.identity.clone(), node);
+                acc
+            })
+    }
+
+    /// Subscribes to every source registry that supports event subscriptions, so changes made
+    /// outside of this registry are reflected in its events.
+    #[cfg(feature = "registry-events")]
+    fn subscribe_to_sources(&self) {
+        let subscriber = SourceSubscriber {
+            internal_source: Arc::downgrade(&self.internal_source),
+            external_sources: self.external_sources.iter().map(Arc::downgrade).collect(),
+            event_state: Arc::downgrade(&self.event_state),
+        };
+
+        if let Err(err) = self
+            .internal_source
+            .add_event_subscriber(Box::new(subscriber.clone()))
+        {
+            debug!("Not subscribing to internal source registry: {}", err);
+        }
+        for source in &self.external_sources {
+            if let Err(err) = source.add_event_subscriber(Box::new(subscriber.clone())) {
+                debug!("Not subscribing to external source registry: {}", err);
+            }
+        }
+    }
+
+    /// Recomputes the unified view of the nodes and notifies subscribers of any changes since the
+    /// last time it was computed. Nothing is computed until the first subscriber is added.
+    #[cfg(feature = "registry-events")]
+    fn resync(&self) {
+        if !self.event_state.is_tracking() {
+            return;
         }
+
+        // Compute the view before taking the snapshot lock, since reading from the sources may
+        // cause them to notify this registry
+        let nodes = self.merged_nodes();
+
+        let events = match self.event_state.snapshot.lock() {
+            Ok(mut snapshot) => {
+                let events = snapshot
+                    .as_ref()
+                    .map(|old| diff_nodes(old, &nodes))
+                    .unwrap_or_default();
+                *snapshot = Some(nodes);
+                events
+            }
+            Err(_) => {
+                error!("Unified registry snapshot lock was poisoned");
+                return;
+            }
+        };
+
+        self.event_state.subscribers.notify(&events);
     }
 
     /// Gets all nodes from all sources (in ascending order of precedence) without deduplication.
@@ -115,22 +211,7 @@ impl RegistryReader for UnifiedRegistry {
         &'b self,
         predicates: &'a [MetadataPredicate],
     ) -> Result<NodeIter<'a>, RegistryError> {
-        let mut id_map = self
-            // Get all nodes from all sources
-            .all_nodes()
-            // Deduplicate and merge metadata
-            .fold(HashMap::<String, Node>::new(), |mut acc, mut node| {
-                // If the node is already present, merge metadata
-                if let Some(existing) = acc.remove(&node.identity) {
-                    // Overwrite the existing node's metadata with the new node's if they share
-                    // the same metadata keys
-                    let mut merged_metadata = existing.metadata;
-                    merged_metadata.extend(node.metadata);
-                    node.metadata = merged_metadata;
-                }
-                acc.insert(node.identity.clone(), node);
-                acc
-            });
+        let mut id_map = self.merged_nodes();
         // Apply predicate filters
         id_map.retain(|_, node| predicates.iter().all(|predicate| predicate.apply(node)));
 
@@ -197,15 +278,39 @@ impl RegistryReader for UnifiedRegistry {
                 })
             }))
     }
+
+    #[cfg(feature = "registry-events")]
+    fn add_event_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        // Take the initial snapshot so that subsequent changes can be reported
+        {
+            let mut snapshot = self.event_state.snapshot.lock().map_err(|_| {
+                RegistryError::general_error("Unified registry snapshot lock was poisoned")
+            })?;
+            if snapshot.is_none() {
+                *snapshot = Some(self.merged_nodes());
+            }
+        }
+
+        self.event_state.subscribers.add(subscriber)
+    }
 }
 
 impl RegistryWriter for UnifiedRegistry {
     fn insert_node(&self, node: Node) -> Result<(), RegistryError> {
-        self.internal_source.insert_node(node)
+        self.internal_source.insert_node(node)?;
+        #[cfg(feature = "registry-events")]
+        self.resync();
+        Ok(())
     }
 
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
-        self.internal_source.delete_node(identity)
+        let node = self.internal_source.delete_node(identity)?;
+        #[cfg(feature = "registry-events")]
+        self.resync();
+        Ok(node)
     }
 }
 
@@ -223,6 +328,62 @@ impl RwRegistry for UnifiedRegistry {
     }
 }
 
+/// The event state shared by all clones of a `UnifiedRegistry`.
+#[cfg(feature = "registry-events")]
+#[derive(Default)]
+struct EventState {
+    /// The unified view of the nodes as of the last notification; `None` until the first
+    /// subscriber is added.
+    snapshot: Mutex<Option<HashMap<String, Node>>>,
+    subscribers: RegistryEventSubscribers,
+}
+
+#[cfg(feature = "registry-events")]
+impl EventState {
+    fn is_tracking(&self) -> bool {
+        self.snapshot
+            .lock()
+            .map(|snapshot| snapshot.is_some())
+            .unwrap_or(false)
+    }
+}
+
+/// Subscribed to the source registries of a `UnifiedRegistry`; resyncs the unified view whenever a
+/// source changes. Only weak references are held, so the sources do not keep the unified registry
+/// alive.
+#[cfg(feature = "registry-events")]
+#[derive(Clone)]
+struct SourceSubscriber {
+    internal_source: Weak<dyn RwRegistry>,
+    external_sources: Vec<Weak<dyn RegistryReader>>,
+    event_state: Weak<EventState>,
+}
+
+#[cfg(feature = "registry-events")]
+impl RegistryEventSubscriber for SourceSubscriber {
+    fn handle_event(&self, _event: &RegistryEvent) -> Result<(), RegistrySubscriberError> {
+        let registry = UnifiedRegistry {
+            internal_source: self
+                .internal_source
+                .upgrade()
+                .ok_or(RegistrySubscriberError::Unsubscribe)?,
+            external_sources: self
+                .external_sources
+                .iter()
+                .map(|source| source.upgrade())
+                .collect::<Option<_>>()
+                .ok_or(RegistrySubscriberError::Unsubscribe)?,
+            event_state: self
+                .event_state
+                .upgrade()
+                .ok_or(RegistrySubscriberError::Unsubscribe)?,
+        };
+
+        registry.resync();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -666,6 +827,73 @@ mod test {
             .expect("Unable to check writeable for node1"));
     }
 
+    /// Verify that subscribers of the unified registry receive events for nodes written through
+    /// it, and that the events reflect the unified view of the nodes.
+    #[cfg(feature = "registry-events")]
+    #[test]
+    fn write_nodes_events() {
+        let node1 = new_node("node1", "endpoint1", &[("meta_a", "val_a")]);
+        let node1_read_only = new_node("node1", "endpoint3", &[("meta_b", "val_b")]);
+        let node2 = new_node("node2", "endpoint2", &[("meta_b", "val_b")]);
+
+        let readable = MemRegistry::default();
+        readable
+            .insert_node(node1_read_only.clone())
+            .expect("Unable to insert node1 into read-only registry");
+
+        let unified =
+            UnifiedRegistry::new(Box::new(MemRegistry::default()), vec![Box::new(readable)]);
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let events_clone = events.clone();
+        unified
+            .add_event_subscriber(Box::new(
+                move |event: &RegistryEvent| -> Result<(), RegistrySubscriberError> {
+                    events_clone
+                        .lock()
+                        .expect("events lock was poisoned")
+                        .push(event.clone());
+                    Ok(())
+                },
+            ))
+            .expect("Unable to add subscriber");
+
+        unified
+            .insert_node(node2.clone())
+            .expect("Unable to add node2");
+        unified
+            .insert_node(node1.clone())
+            .expect("Unable to add node1");
+        // Deleting node1 from the internal registry reverts to the read-only definition
+        unified
+            .delete_node(&node1.identity)
+            .expect("Unable to remove node1");
+        unified
+            .delete_node(&node2.identity)
+            .expect("Unable to remove node2");
+
+        let merged_node1 = new_node(
+            "node1",
+            "endpoint1",
+            &[("meta_a", "val_a"), ("meta_b", "val_b")],
+        );
+        assert_eq!(
+            *events.lock().expect("events lock was poisoned"),
+            vec![
+                RegistryEvent::NodeAdded(node2.clone()),
+                RegistryEvent::NodeUpdated {
+                    old: node1_read_only.clone(),
+                    new: merged_node1.clone(),
+                },
+                RegistryEvent::NodeUpdated {
+                    old: merged_node1,
+                    new: node1_read_only,
+                },
+                RegistryEvent::NodeRemoved(node2),
+            ]
+        );
+    }
+
     #[derive(Clone, Default)]
     struct MemRegistry {
         nodes: Arc<Mutex<HashMap<String, Node>>>,
@@ -20,4 +20,9 @@ mod remote;
 
 pub use local::LocalYamlRegistry;
 #[cfg(feature = "registry-remote")]
-pub use remote::{RemoteYamlRegistry, ShutdownHandle as RemoteYamlShutdownHandle};
+pub use remote::{
+    RemoteYamlRegistry, ShutdownHandle as RemoteYamlShutdownHandle,
+    StatusHandle as RemoteYamlStatusHandle,
+};
+#[cfg(feature = "registry-remote-signing")]
+pub use remote::PublisherVerifier as RemoteYamlPublisherVerifier;
@@ -20,20 +20,31 @@
 //! [`RemoteYamlRegistry`]: struct.RemoteYamlRegistry.html
 //! [`RegistryReader`]: ../../trait.RegistryReader.html
 
+#[cfg(feature = "registry-events")]
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
+    Arc, Mutex, MutexGuard,
 };
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use openssl::hash::{hash, MessageDigest};
 
+#[cfg(feature = "registry-remote-signing")]
+use crate::hex::parse_hex;
 use crate::hex::to_hex;
+#[cfg(feature = "registry-events")]
+use crate::registry::{
+    events::{diff_nodes, RegistryEventSubscribers},
+    RegistryEvent, RegistryEventSubscriber,
+};
 use crate::registry::{
     validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader,
 };
+#[cfg(feature = "registry-remote-signing")]
+use crate::signing::SignatureVerifier;
 
 use super::LocalYamlRegistry;
 
@@ -67,12 +78,19 @@ use super::LocalYamlRegistry;
 /// and the previously cached registry values will continue to be used. The next time the registry
 /// is read, it will try again to refresh the cache.
 ///
+/// If the registry is created with a [`PublisherVerifier`], the signature of the remote file must
+/// be verified before the cache is replaced; a file that fails verification is treated like any
+/// other failed refresh.
+///
 /// [`Node`]: struct.Node.html
 /// [`RegistryReader`]: trait.RegistryReader.html
 /// [`constructor`]: struct.RemoteYamlRegistry.html#method.new
+/// [`PublisherVerifier`]: struct.PublisherVerifier.html
 pub struct RemoteYamlRegistry {
     internal: Arc<Mutex<Internal>>,
     shutdown_handle: ShutdownHandle,
+    #[cfg(feature = "registry-events")]
+    subscribers: RegistryEventSubscribers,
 }
 
 impl RemoteYamlRegistry {
@@ -93,12 +111,64 @@ impl RemoteYamlRegistry {
         cache_dir: &str,
         automatic_refresh_period: Option<Duration>,
         forced_refresh_period: Option<Duration>,
+    ) -> Result<Self, RegistryError> {
+        Self::create(
+            url,
+            cache_dir,
+            automatic_refresh_period,
+            forced_refresh_period,
+            #[cfg(feature = "registry-remote-signing")]
+            None,
+        )
+    }
+
+    /// Construct a new `RemoteYamlRegistry` that only accepts remote files with a valid signature
+    /// from one of the publishers known to the given `PublisherVerifier`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the registry's backing YAML file.
+    /// * `cache_dir` - Directory that the local registry cache will be stored in.
+    /// * `automatic_refresh_period` - Amount of time between attempts to automatically fetch and
+    ///   cache the remote YAML file in the background. If `None`, background refreshes will be
+    ///   disabled. The automatic refresh occurs with a tolerance of +/- 1 second.
+    /// * `forced_refresh_period` - Amount of time since the last successful cache refresh before
+    ///   attempting to refresh on every read operation. If `None`, forced refreshes will be
+    ///   disabled.
+    /// * `publisher_verifier` - Verifies the signature of the remote file on every refresh.
+    #[cfg(feature = "registry-remote-signing")]
+    pub fn new_with_publisher_verifier(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        publisher_verifier: PublisherVerifier,
+    ) -> Result<Self, RegistryError> {
+        Self::create(
+            url,
+            cache_dir,
+            automatic_refresh_period,
+            forced_refresh_period,
+            Some(publisher_verifier),
+        )
+    }
+
+    fn create(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        #[cfg(feature = "registry-remote-signing")] publisher_verifier: Option<PublisherVerifier>,
     ) -> Result<Self, RegistryError> {
         let internal = Arc::new(Mutex::new(Internal::new(
             url,
             cache_dir,
             forced_refresh_period,
+            #[cfg(feature = "registry-remote-signing")]
+            publisher_verifier,
         )?));
+        #[cfg(feature = "registry-events")]
+        let subscribers = RegistryEventSubscribers::default();
 
         let running = automatic_refresh_period
             .map::<Result<_, RegistryError>, _>(|refresh_period| {
@@ -107,6 +177,8 @@ impl RemoteYamlRegistry {
                 let thread_internal = internal.clone();
                 let thread_url = url.to_string();
                 let thread_running = running.clone();
+                #[cfg(feature = "registry-events")]
+                let thread_subscribers = subscribers.clone();
                 thread::Builder::new()
                     .name(format!("Remote Registry Automatic Refresh: {}", url))
                     .spawn(move || {
@@ -115,6 +187,8 @@ impl RemoteYamlRegistry {
                             thread_internal,
                             &thread_url,
                             thread_running,
+                            #[cfg(feature = "registry-events")]
+                            thread_subscribers,
                         )
                     })
                     .map_err(|err| {
@@ -134,6 +208,8 @@ impl RemoteYamlRegistry {
         Ok(Self {
             internal,
             shutdown_handle,
+            #[cfg(feature = "registry-events")]
+            subscribers,
         })
     }
 
@@ -142,12 +218,31 @@ impl RemoteYamlRegistry {
         self.shutdown_handle.clone()
     }
 
+    /// Get a `StatusHandle` for checking the state of the registry's cache.
+    pub fn status_handle(&self) -> StatusHandle {
+        StatusHandle {
+            internal: self.internal.clone(),
+        }
+    }
+
     /// Acquire the lock for the internal cache and get the nodes from it.
     fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
-        self.internal
+        let mut internal = self
+            .internal
             .lock()
-            .map_err(|_| RegistryError::general_error("Internal lock poisoned"))?
-            .get_nodes()
+            .map_err(|_| RegistryError::general_error("Internal lock poisoned"))?;
+        let nodes = internal.get_nodes();
+
+        // Subscribers are notified after the lock is released, since they may read from this
+        // registry
+        #[cfg(feature = "registry-events")]
+        {
+            let events = internal.take_pending_events();
+            drop(internal);
+            self.subscribers.notify(&events);
+        }
+
+        nodes
     }
 }
 
@@ -175,6 +270,14 @@ impl RegistryReader for RemoteYamlRegistry {
             .filter(move |node| predicates.iter().all(|predicate| predicate.apply(node)))
             .count() as u32)
     }
+
+    #[cfg(feature = "registry-events")]
+    fn add_event_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        self.subscribers.add(subscriber)
+    }
 }
 
 /// Holds the internal state of the remote registry.
@@ -182,8 +285,15 @@ struct Internal {
     url: String,
     cache: LocalYamlRegistry,
     last_refresh_successful: bool,
+    last_successful_refresh: Option<SystemTime>,
+    last_refresh_error: Option<String>,
     forced_refresh_period: Option<Duration>,
     next_forced_refresh: Option<Instant>,
+    #[cfg(feature = "registry-remote-signing")]
+    publisher_verifier: Option<PublisherVerifier>,
+    /// Changes made to the cache that subscribers have not yet been notified of
+    #[cfg(feature = "registry-events")]
+    pending_events: Vec<RegistryEvent>,
 }
 
 impl Internal {
@@ -192,6 +302,7 @@ impl Internal {
         url: &str,
         cache_dir: &str,
         forced_refresh_period: Option<Duration>,
+        #[cfg(feature = "registry-remote-signing")] publisher_verifier: Option<PublisherVerifier>,
     ) -> Result<Self, RegistryError> {
         let url = url.to_string();
 
@@ -201,8 +312,14 @@ impl Internal {
             url,
             cache,
             last_refresh_successful: false,
+            last_successful_refresh: None,
+            last_refresh_error: None,
             forced_refresh_period,
             next_forced_refresh: None,
+            #[cfg(feature = "registry-remote-signing")]
+            publisher_verifier,
+            #[cfg(feature = "registry-events")]
+            pending_events: vec![],
         };
 
         // If initial fetch/cache fails, it will be re-attempted on the next registry read, so just
@@ -214,34 +331,76 @@ impl Internal {
             );
         }
 
+        // Nobody can have subscribed yet, so the initial contents of the cache are not reported
+        #[cfg(feature = "registry-events")]
+        internal.pending_events.clear();
+
         Ok(internal)
     }
 
     /// Attempt to refresh the internal cache and update state accordingly.
     fn refresh_cache(&mut self) -> Result<(), RegistryError> {
-        fetch_nodes_from_remote(&self.url)
-            .and_then(|nodes| self.cache.write_nodes(nodes))
-            .map_err(|err| {
-                self.last_refresh_successful = false;
-                err
-            })
-            .and_then(|_| {
-                self.last_refresh_successful = true;
-                // If a forced refresh period was configured, set the next time a forced refresh
-                // will be required
-                self.next_forced_refresh = self
-                    .forced_refresh_period
-                    .map(|duration| {
-                        Instant::now().checked_add(duration).ok_or_else(|| {
-                            RegistryError::general_error(
-                                "Forced refresh time could not be determined; \
+        fetch_nodes_from_remote(
+            &self.url,
+            #[cfg(feature = "registry-remote-signing")]
+            self.publisher_verifier.as_ref(),
+        )
+        .and_then(|nodes| self.write_cache(nodes))
+        .map_err(|err| {
+            self.last_refresh_successful = false;
+            self.last_refresh_error = Some(err.to_string());
+            err
+        })
+        .and_then(|_| {
+            self.last_refresh_successful = true;
+            self.last_successful_refresh = Some(SystemTime::now());
+            self.last_refresh_error = None;
+            // If a forced refresh period was configured, set the next time a forced refresh
+            // will be required
+            self.next_forced_refresh = self
+                .forced_refresh_period
+                .map(|duration| {
+                    Instant::now().checked_add(duration).ok_or_else(|| {
+                        RegistryError::general_error(
+                            "Forced refresh time could not be determined; \
                                  forced_refresh_period may be too large",
-                            )
-                        })
+                        )
                     })
-                    .transpose()?;
-                Ok(())
-            })
+                })
+                .transpose()?;
+            Ok(())
+        })
+    }
+
+    /// Write the nodes to the cache.
+    #[cfg(not(feature = "registry-events"))]
+    fn write_cache(&mut self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        self.cache.write_nodes(nodes)
+    }
+
+    /// Write the nodes to the cache, recording the changes to its contents as pending events.
+    #[cfg(feature = "registry-events")]
+    fn write_cache(&mut self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        let old = self
+            .cache
+            .get_nodes()?
+            .into_iter()
+            .map(|node| (node.identity.clone(), node))
+            .collect::<HashMap<_, _>>();
+        let new = nodes
+            .iter()
+            .map(|node| (node.identity.clone(), node.clone()))
+            .collect::<HashMap<_, _>>();
+
+        self.cache.write_nodes(nodes)?;
+        self.pending_events.extend(diff_nodes(&old, &new));
+        Ok(())
+    }
+
+    /// Take the events that subscribers have not yet been notified of.
+    #[cfg(feature = "registry-events")]
+    fn take_pending_events(&mut self) -> Vec<RegistryEvent> {
+        self.pending_events.drain(..).collect()
     }
 
     /// Attempt to refresh the internal cache if necessary and return the cache's contents.
@@ -297,8 +456,33 @@ fn compute_cache_filename(url: &str, cache_dir: &str) -> Result<String, Registry
         .to_string())
 }
 
-/// Fetch, parse, and validate the YAML registry file at the given URL.
-fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
+/// Fetch, parse, and validate the YAML registry file at the given URL. If a `publisher_verifier`
+/// is provided, the file's signature is verified before it is parsed.
+fn fetch_nodes_from_remote(
+    url: &str,
+    #[cfg(feature = "registry-remote-signing")] publisher_verifier: Option<&PublisherVerifier>,
+) -> Result<Vec<Node>, RegistryError> {
+    let bytes = fetch_bytes(url)?;
+
+    #[cfg(feature = "registry-remote-signing")]
+    let bytes = match publisher_verifier {
+        Some(publisher_verifier) => publisher_verifier.verify_file(url, &bytes)?.to_vec(),
+        None => bytes,
+    };
+
+    let nodes: Vec<Node> = serde_yaml::from_slice(&bytes).map_err(|_| {
+        RegistryError::general_error(
+            "Failed to deserialize remote registry file: Not a valid YAML sequence of nodes",
+        )
+    })?;
+
+    validate_nodes(&nodes)?;
+
+    Ok(nodes)
+}
+
+/// Fetch the file at the given URL.
+fn fetch_bytes(url: &str) -> Result<Vec<u8>, RegistryError> {
     let bytes = reqwest::blocking::get(url)
         .and_then(|response| response.error_for_status())
         .map_err(|err| {
@@ -314,15 +498,125 @@ fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
                 Box::new(err),
             )
         })?;
-    let nodes: Vec<Node> = serde_yaml::from_slice(&bytes).map_err(|_| {
-        RegistryError::general_error(
-            "Failed to deserialize remote registry file: Not a valid YAML sequence of nodes",
-        )
-    })?;
+    Ok(bytes.to_vec())
+}
 
-    validate_nodes(&nodes)?;
+/// The prefix of the line that holds an embedded signature in a remote registry file.
+#[cfg(feature = "registry-remote-signing")]
+const EMBEDDED_SIGNATURE_PREFIX: &str = "# signature:";
 
-    Ok(nodes)
+/// Verifies that remote registry files were signed by a trusted publisher.
+///
+/// A remote registry file may be signed in one of two ways:
+///
+/// * With an embedded signature: the last line of the file is a YAML comment of the form
+///   `# signature: <hex-encoded signature>`, and the signature covers all of the file's contents
+///   before that line.
+/// * With a detached signature: the hex-encoded signature of the entire file is served at the
+///   file's URL with a `.sig` suffix.
+///
+/// If the file has an embedded signature, the detached signature is not fetched. A file is
+/// accepted if its signature is valid for any of the publisher keys.
+#[cfg(feature = "registry-remote-signing")]
+pub struct PublisherVerifier {
+    publisher_keys: Vec<Vec<u8>>,
+    signature_verifier: Box<dyn SignatureVerifier>,
+}
+
+#[cfg(feature = "registry-remote-signing")]
+impl PublisherVerifier {
+    /// Construct a new `PublisherVerifier`.
+    ///
+    /// # Arguments
+    ///
+    /// * `publisher_keys` - Hex-encoded public keys of the publishers that are trusted to sign
+    ///   registry files.
+    /// * `signature_verifier` - Verifies signatures made with the publisher keys.
+    pub fn new(
+        publisher_keys: &[String],
+        signature_verifier: Box<dyn SignatureVerifier>,
+    ) -> Result<Self, RegistryError> {
+        let publisher_keys = publisher_keys
+            .iter()
+            .map(|key| {
+                parse_hex(key).map_err(|err| {
+                    RegistryError::general_error_with_source(
+                        &format!("Invalid registry publisher key: {}", key),
+                        Box::new(err),
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            publisher_keys,
+            signature_verifier,
+        })
+    }
+
+    /// Verify the signature of the registry file fetched from the given URL, returning the signed
+    /// contents of the file.
+    fn verify_file<'a>(&self, url: &str, file: &'a [u8]) -> Result<&'a [u8], RegistryError> {
+        let (contents, signature) = match split_embedded_signature(file) {
+            Some((contents, signature)) => (contents, signature.to_string()),
+            None => {
+                let signature_url = format!("{}.sig", url);
+                let signature = String::from_utf8(fetch_bytes(&signature_url)?).map_err(|_| {
+                    RegistryError::general_error(&format!(
+                        "Detached signature at {} is not valid UTF-8",
+                        signature_url
+                    ))
+                })?;
+                (file, signature.trim().to_string())
+            }
+        };
+
+        let signature = parse_hex(&signature).map_err(|err| {
+            RegistryError::general_error_with_source(
+                "Remote registry file signature is not valid hex",
+                Box::new(err),
+            )
+        })?;
+
+        let verified = self.publisher_keys.iter().any(|key| {
+            self.signature_verifier
+                .verify(contents, &signature, key)
+                .unwrap_or_else(|err| {
+                    debug!(
+                        "Failed to verify signature of remote registry file: {}",
+                        err
+                    );
+                    false
+                })
+        });
+
+        if verified {
+            Ok(contents)
+        } else {
+            warn!(
+                "Signature of remote registry file '{}' was not made by a trusted publisher; \
+                 keeping the previous cache",
+                url
+            );
+            Err(RegistryError::general_error(
+                "Remote registry file signature could not be verified with any publisher key",
+            ))
+        }
+    }
+}
+
+/// Split a registry file into its contents and its embedded signature, if it has one.
+#[cfg(feature = "registry-remote-signing")]
+fn split_embedded_signature(file: &[u8]) -> Option<(&[u8], &str)> {
+    let text = std::str::from_utf8(file).ok()?;
+    let trimmed = text.trim_end();
+    let line_start = trimmed.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let last_line = &trimmed[line_start..];
+    if !last_line.starts_with(EMBEDDED_SIGNATURE_PREFIX) {
+        return None;
+    }
+    let signature = last_line[EMBEDDED_SIGNATURE_PREFIX.len()..].trim();
+    Some((&file[..line_start], signature))
 }
 
 /// Infinitely loop, attempting to refresh the `internal` cache every `refresh_period`, until no
@@ -332,6 +626,7 @@ fn automatic_refresh_loop(
     internal: Arc<Mutex<Internal>>,
     url: &str,
     running: Arc<AtomicBool>,
+    #[cfg(feature = "registry-events")] subscribers: RegistryEventSubscribers,
 ) {
     loop {
         // Wait the `refresh_period`, checking for shutdown every second
@@ -372,6 +667,13 @@ fn automatic_refresh_loop(
                 }
             }
         }
+
+        #[cfg(feature = "registry-events")]
+        {
+            let events = internal.take_pending_events();
+            drop(internal);
+            subscribers.notify(&events);
+        }
     }
 }
 
@@ -390,10 +692,47 @@ impl ShutdownHandle {
     }
 }
 
+/// Handle for checking the state of a `RemoteYamlRegistry`'s cache.
+#[derive(Clone)]
+pub struct StatusHandle {
+    internal: Arc<Mutex<Internal>>,
+}
+
+impl StatusHandle {
+    /// Get the URL of the registry's backing YAML file.
+    pub fn url(&self) -> Result<String, RegistryError> {
+        Ok(self.lock_internal()?.url.clone())
+    }
+
+    /// Get whether or not the last attempt to refresh the cache was successful.
+    pub fn last_refresh_successful(&self) -> Result<bool, RegistryError> {
+        Ok(self.lock_internal()?.last_refresh_successful)
+    }
+
+    /// Get the time of the last successful cache refresh, or `None` if the cache has not been
+    /// refreshed since the registry was created.
+    pub fn last_successful_refresh(&self) -> Result<Option<SystemTime>, RegistryError> {
+        Ok(self.lock_internal()?.last_successful_refresh)
+    }
+
+    /// Get the error of the last attempt to refresh the cache, or `None` if it was successful.
+    pub fn last_refresh_error(&self) -> Result<Option<String>, RegistryError> {
+        Ok(self.lock_internal()?.last_refresh_error.clone())
+    }
+
+    fn lock_internal(&self) -> Result<MutexGuard<Internal>, RegistryError> {
+        self.internal
+            .lock()
+            .map_err(|_| RegistryError::general_error("Internal lock poisoned"))
+    }
+}
+
 #[cfg(all(test, feature = "rest-api", feature = "rest-api-actix"))]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "registry-remote-signing")]
+    use std::collections::HashMap;
     use std::fs::File;
 
     use actix_web::HttpResponse;
@@ -405,6 +744,11 @@ mod tests {
     use crate::rest_api::{
         Method, Resource, RestApiBuilder, RestApiServerError, RestApiShutdownHandle,
     };
+    #[cfg(feature = "registry-remote-signing")]
+    use crate::signing::{
+        hash::{HashSigner, HashVerifier},
+        Signer,
+    };
 
     /// Verifies that a remote file that contains two nodes with the same identity is rejected (not
     /// loaded).
@@ -799,6 +1143,48 @@ mod tests {
         test_config.shutdown();
     }
 
+    /// Verifies that the registry's `StatusHandle` reports whether or not the cache has been
+    /// refreshed successfully, and when.
+    #[test]
+    fn status_handle() {
+        // Start without a remote file
+        let test_config = TestConfig::setup("status_handle", None);
+
+        let remote_registry =
+            RemoteYamlRegistry::new(test_config.url(), test_config.path(), None, None)
+                .expect("Failed to create registry");
+        let status_handle = remote_registry.status_handle();
+
+        assert_eq!(
+            status_handle.url().expect("Failed to get url"),
+            test_config.url()
+        );
+        assert!(!status_handle
+            .last_refresh_successful()
+            .expect("Failed to get refresh status"));
+        assert!(status_handle
+            .last_successful_refresh()
+            .expect("Failed to get refresh time")
+            .is_none());
+
+        // Make the remote file available now and read from the registry to refresh it
+        test_config.update_registry(Some(mock_registry()));
+        remote_registry
+            .fetch_node("NodeNotInRegistry")
+            .expect("Failed to fetch node");
+
+        assert!(status_handle
+            .last_refresh_successful()
+            .expect("Failed to get refresh status"));
+        assert!(status_handle
+            .last_successful_refresh()
+            .expect("Failed to get refresh time")
+            .is_some());
+
+        remote_registry.shutdown_handle().shutdown();
+        test_config.shutdown();
+    }
+
     /// Verifies that when auto refresh is turned off, the auto refresh thread is not running.
     #[test]
     fn auto_refresh_disabled() {
@@ -950,6 +1336,214 @@ mod tests {
         test_config.shutdown();
     }
 
+    /// Verifies that a remote file with a valid embedded signature is loaded.
+    #[cfg(feature = "registry-remote-signing")]
+    #[test]
+    fn signed_embedded_signature() {
+        let temp_dir =
+            TempDir::new("signed_embedded_signature").expect("Failed to create temp dir");
+        let contents = serde_yaml::to_vec(&mock_registry()).expect("Failed to serialize registry");
+        let files = Arc::new(Mutex::new(HashMap::new()));
+        files
+            .lock()
+            .expect("Files lock poisoned")
+            .insert("/registry.yaml", embed_signature(&contents));
+        let (shutdown_handle, join_handle, url) = serve_files(files);
+
+        let remote_registry = RemoteYamlRegistry::new_with_publisher_verifier(
+            &url,
+            temp_dir.path().to_str().expect("Failed to get path"),
+            None,
+            None,
+            mock_publisher_verifier(),
+        )
+        .expect("Failed to create registry");
+
+        assert_eq!(
+            remote_registry.get_nodes().expect("Failed to get nodes"),
+            mock_registry()
+        );
+
+        remote_registry.shutdown_handle().shutdown();
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Verifies that a remote file with a valid detached signature is loaded.
+    #[cfg(feature = "registry-remote-signing")]
+    #[test]
+    fn signed_detached_signature() {
+        let temp_dir =
+            TempDir::new("signed_detached_signature").expect("Failed to create temp dir");
+        let contents = serde_yaml::to_vec(&mock_registry()).expect("Failed to serialize registry");
+        let files = Arc::new(Mutex::new(HashMap::new()));
+        {
+            let mut files = files.lock().expect("Files lock poisoned");
+            files.insert("/registry.yaml.sig", sign(&contents).into_bytes());
+            files.insert("/registry.yaml", contents);
+        }
+        let (shutdown_handle, join_handle, url) = serve_files(files);
+
+        let remote_registry = RemoteYamlRegistry::new_with_publisher_verifier(
+            &url,
+            temp_dir.path().to_str().expect("Failed to get path"),
+            None,
+            None,
+            mock_publisher_verifier(),
+        )
+        .expect("Failed to create registry");
+
+        assert_eq!(
+            remote_registry.get_nodes().expect("Failed to get nodes"),
+            mock_registry()
+        );
+
+        remote_registry.shutdown_handle().shutdown();
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Verifies that when the remote file is replaced by one that is unsigned or whose signature
+    /// does not match its contents, the previous cache is kept and the failure is reported by the
+    /// registry's `StatusHandle`.
+    #[cfg(feature = "registry-remote-signing")]
+    #[test]
+    fn signed_verification_failure() {
+        let temp_dir =
+            TempDir::new("signed_verification_failure").expect("Failed to create temp dir");
+        let contents = serde_yaml::to_vec(&mock_registry()).expect("Failed to serialize registry");
+        let files = Arc::new(Mutex::new(HashMap::new()));
+        files
+            .lock()
+            .expect("Files lock poisoned")
+            .insert("/registry.yaml", embed_signature(&contents));
+        let (shutdown_handle, join_handle, url) = serve_files(files.clone());
+
+        let remote_registry = RemoteYamlRegistry::new_with_publisher_verifier(
+            &url,
+            temp_dir.path().to_str().expect("Failed to get path"),
+            None,
+            Some(Duration::from_secs(0)),
+            mock_publisher_verifier(),
+        )
+        .expect("Failed to create registry");
+        let status_handle = remote_registry.status_handle();
+        assert_eq!(
+            remote_registry.get_nodes().expect("Failed to get nodes"),
+            mock_registry()
+        );
+
+        // Tamper with the file by keeping the old signature but changing the contents
+        let mut tampered_registry = mock_registry();
+        tampered_registry[0].keys = vec!["injected_key".into()];
+        let tampered_contents =
+            serde_yaml::to_vec(&tampered_registry).expect("Failed to serialize registry");
+        let mut tampered_file = tampered_contents.clone();
+        tampered_file.extend_from_slice(
+            format!("{} {}\n", EMBEDDED_SIGNATURE_PREFIX, sign(&contents)).as_bytes(),
+        );
+        files
+            .lock()
+            .expect("Files lock poisoned")
+            .insert("/registry.yaml", tampered_file);
+
+        assert_eq!(
+            remote_registry.get_nodes().expect("Failed to get nodes"),
+            mock_registry()
+        );
+        assert!(!status_handle
+            .last_refresh_successful()
+            .expect("Failed to get refresh status"));
+        assert!(status_handle
+            .last_refresh_error()
+            .expect("Failed to get refresh error")
+            .is_some());
+
+        // Serve the file without any signature
+        files
+            .lock()
+            .expect("Files lock poisoned")
+            .insert("/registry.yaml", tampered_contents);
+
+        assert_eq!(
+            remote_registry.get_nodes().expect("Failed to get nodes"),
+            mock_registry()
+        );
+        assert!(!status_handle
+            .last_refresh_successful()
+            .expect("Failed to get refresh status"));
+
+        remote_registry.shutdown_handle().shutdown();
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Verifies that when the remote file is replaced by one with a valid signature made by a key
+    /// that is not one of the publisher keys, the file is rejected and the previous cache is kept.
+    #[cfg(feature = "registry-remote-signing")]
+    #[test]
+    fn signed_by_untrusted_key() {
+        let temp_dir = TempDir::new("signed_by_untrusted_key").expect("Failed to create temp dir");
+        let contents = serde_yaml::to_vec(&mock_registry()).expect("Failed to serialize registry");
+        let files = Arc::new(Mutex::new(HashMap::new()));
+        files.lock().expect("Files lock poisoned").insert(
+            "/registry.yaml",
+            embed_key_signature(&contents, b"trusted_key"),
+        );
+        let (shutdown_handle, join_handle, url) = serve_files(files.clone());
+
+        let publisher_verifier =
+            PublisherVerifier::new(&[to_hex(b"trusted_key")], Box::new(AppendKeyVerifier))
+                .expect("Failed to create publisher verifier");
+        let remote_registry = RemoteYamlRegistry::new_with_publisher_verifier(
+            &url,
+            temp_dir.path().to_str().expect("Failed to get path"),
+            None,
+            Some(Duration::from_secs(0)),
+            publisher_verifier,
+        )
+        .expect("Failed to create registry");
+        let status_handle = remote_registry.status_handle();
+        assert_eq!(
+            remote_registry.get_nodes().expect("Failed to get nodes"),
+            mock_registry()
+        );
+
+        // Serve a different file whose signature is valid, but made by an untrusted key
+        let mut untrusted_registry = mock_registry();
+        untrusted_registry[0].keys = vec!["injected_key".into()];
+        let untrusted_contents =
+            serde_yaml::to_vec(&untrusted_registry).expect("Failed to serialize registry");
+        files.lock().expect("Files lock poisoned").insert(
+            "/registry.yaml",
+            embed_key_signature(&untrusted_contents, b"untrusted_key"),
+        );
+
+        assert_eq!(
+            remote_registry.get_nodes().expect("Failed to get nodes"),
+            mock_registry()
+        );
+        assert!(!status_handle
+            .last_refresh_successful()
+            .expect("Failed to get refresh status"));
+        assert!(status_handle
+            .last_refresh_error()
+            .expect("Failed to get refresh error")
+            .is_some());
+
+        remote_registry.shutdown_handle().shutdown();
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     // Restart, remote file not available
 
     /// Creates a mock registry.
@@ -1090,6 +1684,85 @@ mod tests {
         (shutdown, join, format!("http://{}/registry.yaml", url))
     }
 
+    /// Serves the given files, keyed by path, at the returned URL of `/registry.yaml`. Files that
+    /// are not in the map will not be available.
+    #[cfg(feature = "registry-remote-signing")]
+    fn serve_files(
+        files: Arc<Mutex<HashMap<&'static str, Vec<u8>>>>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        let resources = vec!["/registry.yaml", "/registry.yaml.sig"]
+            .into_iter()
+            .map(|path: &'static str| {
+                let files = files.clone();
+                Resource::build(path).add_method(Method::Get, move |_, _| {
+                    Box::new(match files.lock().expect("Files lock poisoned").get(path) {
+                        Some(file) => HttpResponse::Ok().body(file.clone()).into_future(),
+                        None => HttpResponse::NotFound().finish().into_future(),
+                    })
+                })
+            })
+            .collect();
+        let (shutdown, join, url) = run_rest_api_on_open_port(resources);
+
+        (shutdown, join, format!("http://{}/registry.yaml", url))
+    }
+
+    /// Creates a `PublisherVerifier` that accepts signatures made by `sign`.
+    #[cfg(feature = "registry-remote-signing")]
+    fn mock_publisher_verifier() -> PublisherVerifier {
+        PublisherVerifier::new(&[to_hex(HashSigner.public_key())], Box::new(HashVerifier))
+            .expect("Failed to create publisher verifier")
+    }
+
+    /// Signs the given contents, returning the hex-encoded signature.
+    #[cfg(feature = "registry-remote-signing")]
+    fn sign(contents: &[u8]) -> String {
+        to_hex(&HashSigner.sign(contents).expect("Failed to sign contents"))
+    }
+
+    /// Appends an embedded signature of the given contents.
+    #[cfg(feature = "registry-remote-signing")]
+    fn embed_signature(contents: &[u8]) -> Vec<u8> {
+        let mut file = contents.to_vec();
+        file.extend_from_slice(
+            format!("{} {}\n", EMBEDDED_SIGNATURE_PREFIX, sign(contents)).as_bytes(),
+        );
+        file
+    }
+
+    /// A signature verifier that checks the public key: a signature is only valid if it is the
+    /// message with the public key appended.
+    #[cfg(feature = "registry-remote-signing")]
+    struct AppendKeyVerifier;
+
+    #[cfg(feature = "registry-remote-signing")]
+    impl SignatureVerifier for AppendKeyVerifier {
+        fn verify(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+            pk: &[u8],
+        ) -> Result<bool, crate::signing::Error> {
+            Ok(signature == [message, pk].concat().as_slice())
+        }
+    }
+
+    /// Appends an embedded signature of the given contents that `AppendKeyVerifier` accepts for
+    /// the given public key.
+    #[cfg(feature = "registry-remote-signing")]
+    fn embed_key_signature(contents: &[u8], public_key: &[u8]) -> Vec<u8> {
+        let mut file = contents.to_vec();
+        file.extend_from_slice(
+            format!(
+                "{} {}\n",
+                EMBEDDED_SIGNATURE_PREFIX,
+                to_hex(&[contents, public_key].concat())
+            )
+            .as_bytes(),
+        );
+        file
+    }
+
     /// Runs a REST API with the given `resources` on an open port. Returned string is the URL the
     /// REST API is bound to.
     fn run_rest_api_on_open_port(
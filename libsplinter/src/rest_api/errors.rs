@@ -24,6 +24,8 @@ pub enum RestApiServerError {
     StartUpError(String),
     MissingField(String),
     StdError(std::io::Error),
+    #[cfg(feature = "https-bind")]
+    InvalidTlsConfig(String),
 }
 
 impl From<std::io::Error> for RestApiServerError {
@@ -39,6 +41,8 @@ impl Error for RestApiServerError {
             RestApiServerError::StartUpError(_) => None,
             RestApiServerError::StdError(err) => Some(err),
             RestApiServerError::MissingField(_) => None,
+            #[cfg(feature = "https-bind")]
+            RestApiServerError::InvalidTlsConfig(_) => None,
         }
     }
 }
@@ -52,6 +56,8 @@ impl fmt::Display for RestApiServerError {
             RestApiServerError::MissingField(field) => {
                 write!(f, "Missing required field: {}", field)
             }
+            #[cfg(feature = "https-bind")]
+            RestApiServerError::InvalidTlsConfig(e) => write!(f, "Invalid TLS config: {}", e),
         }
     }
 }
@@ -66,6 +66,11 @@ impl<T: Serialize + Debug + 'static> EventSender<T> {
             })
     }
 
+    /// Returns whether the websocket has been closed, in which case no more events can be sent.
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
     pub fn shutdown(self) {
         if self
             .sender
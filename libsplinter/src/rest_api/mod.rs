@@ -59,6 +59,8 @@ pub mod paging;
 mod response_models;
 pub mod secrets;
 pub mod sessions;
+#[cfg(feature = "https-bind")]
+mod tls;
 
 use actix_web::{
     error::ErrorBadRequest, http::header, middleware, web, App, Error as ActixError, HttpRequest,
@@ -71,8 +73,12 @@ use std::boxed::Box;
 use std::sync::{mpsc, Arc};
 use std::thread;
 
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::{AuthorizationGuard, Authorizer, Permission, PermissionStore};
+#[cfg(feature = "authorization")]
+use crate::auth::identity::IdentityProvider;
 #[cfg(feature = "oauth")]
-use crate::auth::oauth::{rest_api::OAuthResourceProvider, OAuthClient};
+use crate::auth::oauth::rest_api::OAuthResourceProvider;
 
 pub use errors::{RequestError, ResponseError, RestApiServerError};
 
@@ -80,6 +86,9 @@ pub use events::{new_websocket_event_sender, EventSender};
 
 pub use response_models::ErrorResponse;
 
+#[cfg(feature = "https-bind")]
+pub use tls::{RestApiTlsConfig, TlsVersion};
+
 const QUERY_ENCODE_SET: &AsciiSet = &CONTROLS
     .add(b' ')
     .add(b'"')
@@ -163,7 +172,7 @@ impl std::fmt::Debug for Response {
 }
 
 /// Rest methods compatible with `RestApi`.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Method {
     Get,
     Post,
@@ -207,6 +216,10 @@ pub struct Resource {
     route: String,
     request_guards: Vec<Arc<dyn RequestGuard>>,
     methods: Vec<(Method, Arc<HandlerFunction>)>,
+    #[cfg(feature = "authorization")]
+    permission: Option<Permission>,
+    #[cfg(feature = "authorization")]
+    method_permissions: Vec<(Method, Permission)>,
 }
 
 impl Resource {
@@ -229,6 +242,10 @@ impl Resource {
             route: route.to_string(),
             methods: vec![],
             request_guards: vec![],
+            #[cfg(feature = "authorization")]
+            permission: None,
+            #[cfg(feature = "authorization")]
+            method_permissions: vec![],
         }
     }
 
@@ -283,6 +300,34 @@ impl Resource {
         self
     }
 
+    /// Sets the permission that is required to call the methods of this resource that do not
+    /// declare their own permission. If no permission is declared for a method, callers must be
+    /// authenticated.
+    #[cfg(feature = "authorization")]
+    pub fn with_permission(mut self, permission: Permission) -> Self {
+        self.permission = Some(permission);
+        self
+    }
+
+    /// Sets the permission that is required to call the given method of this resource
+    #[cfg(feature = "authorization")]
+    pub fn with_method_permission(mut self, method: Method, permission: Permission) -> Self {
+        self.method_permissions.push((method, permission));
+        self
+    }
+
+    /// Guards this resource with the given authorizer, which is evaluated before any other guards
+    #[cfg(feature = "authorization")]
+    fn with_authorizer(mut self, authorizer: &Arc<Authorizer>) -> Self {
+        let guard = AuthorizationGuard::new(
+            authorizer.clone(),
+            self.permission.clone(),
+            self.method_permissions.clone(),
+        );
+        self.request_guards.insert(0, Arc::new(guard));
+        self
+    }
+
     fn into_route(self) -> actix_web::Resource {
         let mut resource = web::resource(&self.route);
 
@@ -304,10 +349,14 @@ impl Resource {
         ));
 
         let request_guards = self.request_guards;
+        #[cfg(feature = "metrics")]
+        let route = self.route.clone();
         self.methods
             .into_iter()
             .fold(resource, |resource, (method, handler)| {
                 let guards = request_guards.clone();
+                #[cfg(feature = "metrics")]
+                let (route, method_name) = (route.clone(), method.to_string());
                 let func = move |r: HttpRequest, p: web::Payload| {
                     // This clone satisfies a requirement that this be FnOnce
                     if !guards.is_empty() {
@@ -318,7 +367,21 @@ impl Resource {
                             }
                         }
                     }
-                    (handler)(r, p)
+                    // The body of a signed request must match the digest that was signed
+                    #[cfg(feature = "authorization")]
+                    let p = crate::auth::identity::verify_signed_body(&r, p);
+                    #[cfg(feature = "metrics")]
+                    {
+                        crate::metrics::observe_rest_request(
+                            route.clone(),
+                            method_name.clone(),
+                            (handler)(r, p),
+                        )
+                    }
+                    #[cfg(not(feature = "metrics"))]
+                    {
+                        (handler)(r, p)
+                    }
                 };
                 resource.route(match method {
                     Method::Get => web::get().to_async(func),
@@ -468,7 +531,11 @@ pub struct RestApi {
     #[cfg(feature = "rest-api-cors")]
     whitelist: Option<Vec<String>>,
     #[cfg(feature = "oauth")]
-    oauth_client: Option<OAuthClient>,
+    oauth_resource_provider: Option<OAuthResourceProvider>,
+    #[cfg(feature = "https-bind")]
+    tls_config: Option<RestApiTlsConfig>,
+    #[cfg(feature = "authorization")]
+    authorizer: Arc<Authorizer>,
 }
 
 impl RestApi {
@@ -482,7 +549,16 @@ impl RestApi {
         #[cfg(feature = "rest-api-cors")]
         let whitelist = self.whitelist.to_owned();
         #[cfg(feature = "oauth")]
-        let oauth_resource_provider = self.oauth_client.to_owned().map(OAuthResourceProvider::new);
+        let oauth_resource_provider = self.oauth_resource_provider.to_owned();
+        #[cfg(feature = "authorization")]
+        let authorizer = self.authorizer.clone();
+        // The acceptor is built before the server is started, so that invalid certificates and
+        // keys are reported to the caller
+        #[cfg(feature = "https-bind")]
+        let tls_acceptor = match &self.tls_config {
+            Some(tls_config) => Some(tls_config.acceptor_builder()?),
+            None => None,
+        };
         let join_handle = thread::Builder::new()
             .name("SplinterDRestApi".into())
             .spawn(move || {
@@ -504,17 +580,29 @@ impl RestApi {
                     #[cfg(feature = "oauth")]
                     if let Some(resource_provider) = &oauth_resource_provider {
                         for resource in resource_provider.resources() {
+                            #[cfg(feature = "authorization")]
+                            let resource = resource.with_authorizer(&authorizer);
                             app = app.service(resource.into_route());
                         }
                     }
 
                     for resource in resources.clone() {
+                        #[cfg(feature = "authorization")]
+                        let resource = resource.with_authorizer(&authorizer);
                         app = app.service(resource.into_route());
                     }
                     app
                 });
 
-                server = match server.bind(&bind_url) {
+                #[cfg(feature = "https-bind")]
+                let bind_result = match tls_acceptor {
+                    Some(tls_acceptor) => server.bind_ssl(&bind_url, tls_acceptor),
+                    None => server.bind(&bind_url),
+                };
+                #[cfg(not(feature = "https-bind"))]
+                let bind_result = server.bind(&bind_url);
+
+                server = match bind_result {
                     Ok(server) => server,
                     Err(err) => {
                         let error_msg = format!("Invalid REST API bind {}: {}", bind_url, err);
@@ -579,7 +667,13 @@ pub struct RestApiBuilder {
     #[cfg(feature = "rest-api-cors")]
     whitelist: Option<Vec<String>>,
     #[cfg(feature = "oauth")]
-    oauth_client: Option<OAuthClient>,
+    oauth_resource_provider: Option<OAuthResourceProvider>,
+    #[cfg(feature = "https-bind")]
+    tls_config: Option<RestApiTlsConfig>,
+    #[cfg(feature = "authorization")]
+    identity_providers: Vec<Box<dyn IdentityProvider>>,
+    #[cfg(feature = "authorization")]
+    permission_store: Option<Box<dyn PermissionStore>>,
 }
 
 impl Default for RestApiBuilder {
@@ -590,7 +684,13 @@ impl Default for RestApiBuilder {
             #[cfg(feature = "rest-api-cors")]
             whitelist: None,
             #[cfg(feature = "oauth")]
-            oauth_client: None,
+            oauth_resource_provider: None,
+            #[cfg(feature = "https-bind")]
+            tls_config: None,
+            #[cfg(feature = "authorization")]
+            identity_providers: Vec::new(),
+            #[cfg(feature = "authorization")]
+            permission_store: None,
         }
     }
 }
@@ -621,23 +721,65 @@ impl RestApiBuilder {
         self
     }
 
+    /// Sets the provider of the OAuth login and session endpoints; requests to other resources
+    /// can be restricted to the provider's sessions using its `session_guard`
     #[cfg(feature = "oauth")]
-    pub fn with_oauth_client(mut self, oauth_client: OAuthClient) -> Self {
-        self.oauth_client = Some(oauth_client);
+    pub fn with_oauth_resource_provider(
+        mut self,
+        oauth_resource_provider: OAuthResourceProvider,
+    ) -> Self {
+        self.oauth_resource_provider = Some(oauth_resource_provider);
+        self
+    }
+
+    /// Serves the REST API over HTTPS using the given TLS configuration, instead of plain HTTP
+    #[cfg(feature = "https-bind")]
+    pub fn with_tls_config(mut self, tls_config: RestApiTlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Adds a provider that identifies the callers of requests; providers are consulted in the
+    /// order they are added. The identity provider of the OAuth resource provider, if one is set,
+    /// is added automatically.
+    #[cfg(feature = "authorization")]
+    pub fn add_identity_provider(mut self, identity_provider: Box<dyn IdentityProvider>) -> Self {
+        self.identity_providers.push(identity_provider);
         self
     }
 
-    pub fn build(self) -> Result<RestApi, RestApiServerError> {
+    /// Sets the store of the permissions that are granted to callers. Without a permission store,
+    /// only resources that allow any authenticated or unauthenticated caller can be called.
+    #[cfg(feature = "authorization")]
+    pub fn with_permission_store(mut self, permission_store: Box<dyn PermissionStore>) -> Self {
+        self.permission_store = Some(permission_store);
+        self
+    }
+
+    // Allowing unused_mut because self must be mutable if feature `authorization` is enabled
+    #[allow(unused_mut)]
+    pub fn build(mut self) -> Result<RestApi, RestApiServerError> {
         let bind = self
             .bind
             .ok_or_else(|| RestApiServerError::MissingField("bind".to_string()))?;
 
+        #[cfg(all(feature = "authorization", feature = "oauth"))]
+        if let Some(oauth_resource_provider) = &self.oauth_resource_provider {
+            self.identity_providers
+                .push(Box::new(oauth_resource_provider.identity_provider()));
+        }
+
         #[cfg(feature = "auth")]
         {
             let mut authentication_configured = false;
 
+            #[cfg(feature = "authorization")]
+            if !self.identity_providers.is_empty() {
+                authentication_configured = true;
+            }
+
             #[cfg(feature = "oauth")]
-            if self.oauth_client.is_some() {
+            if self.oauth_resource_provider.is_some() {
                 authentication_configured = true;
             }
 
@@ -654,7 +796,14 @@ impl RestApiBuilder {
             #[cfg(feature = "rest-api-cors")]
             whitelist: self.whitelist,
             #[cfg(feature = "oauth")]
-            oauth_client: self.oauth_client,
+            oauth_resource_provider: self.oauth_resource_provider,
+            #[cfg(feature = "https-bind")]
+            tls_config: self.tls_config,
+            #[cfg(feature = "authorization")]
+            authorizer: Arc::new(Authorizer::new(
+                self.identity_providers,
+                self.permission_store,
+            )),
         })
     }
 }
@@ -719,6 +868,13 @@ mod test {
     use actix_http::Response;
     use futures::IntoFuture;
 
+    #[cfg(feature = "oauth")]
+    use crate::auth::oauth::{rest_api::generate_token_encryption_key, OAuthClient};
+    #[cfg(all(feature = "oauth", feature = "biome-credentials"))]
+    use crate::biome::MemoryCredentialsStore;
+    #[cfg(feature = "oauth")]
+    use crate::biome::{MemoryOAuthUserStore, MemoryUserStore};
+
     #[test]
     fn test_resource() {
         Resource::build("/test")
@@ -753,18 +909,31 @@ mod test {
         #[cfg(feature = "oauth")]
         assert!(RestApiBuilder::new()
             .with_bind("test")
-            .with_oauth_client(
-                OAuthClient::new(
+            .with_oauth_resource_provider(OAuthResourceProvider::new(
+                OAuthClient::new_github(
                     "client_id".into(),
                     "client_secret".into(),
-                    "https://provider.com/auth".into(),
                     "https://localhost/oauth/callback".into(),
-                    "https://provider.com/token".into(),
-                    vec![],
                 )
-                .expect("Failed to create OAuth client")
-            )
+                .expect("Failed to create OAuth client"),
+                Arc::new(MemoryOAuthUserStore::new()),
+                Arc::new(memory_user_store()),
+                Arc::new(secrets::AutoSecretManager::default()),
+                &generate_token_encryption_key(),
+            ))
             .build()
             .is_ok())
     }
+
+    #[cfg(feature = "oauth")]
+    fn memory_user_store() -> MemoryUserStore {
+        #[cfg(feature = "biome-credentials")]
+        {
+            MemoryUserStore::new(MemoryCredentialsStore::new())
+        }
+        #[cfg(not(feature = "biome-credentials"))]
+        {
+            MemoryUserStore::new()
+        }
+    }
 }
@@ -18,7 +18,11 @@ mod claims;
 mod error;
 mod token_issuer;
 
-#[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+#[cfg(any(
+    feature = "biome-key-management",
+    feature = "biome-credentials",
+    feature = "oauth"
+))]
 use jsonwebtoken::Validation;
 use serde::Serialize;
 
@@ -26,7 +30,11 @@ pub use claims::{Claims, ClaimsBuilder};
 pub use error::{ClaimsBuildError, TokenIssuerError, TokenValidationError};
 pub use token_issuer::AccessTokenIssuer;
 
-#[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+#[cfg(any(
+    feature = "biome-key-management",
+    feature = "biome-credentials",
+    feature = "oauth"
+))]
 const DEFAULT_LEEWAY: i64 = 10; // default leeway in seconds.
 
 /// Implementers can issue JWT tokens
@@ -38,7 +46,11 @@ pub trait TokenIssuer<T: Serialize> {
     fn issue_refresh_token_with_claims(&self, claims: T) -> Result<String, TokenIssuerError>;
 }
 
-#[cfg(any(feature = "biome-key-management", feature = "biome-credentials",))]
+#[cfg(any(
+    feature = "biome-key-management",
+    feature = "biome-credentials",
+    feature = "oauth"
+))]
 pub(crate) fn default_validation(issuer: &str) -> Validation {
     let mut validation = Validation::default();
     validation.leeway = DEFAULT_LEEWAY;
@@ -47,7 +59,7 @@ pub(crate) fn default_validation(issuer: &str) -> Validation {
 }
 
 /// Validates authorization token but ignores the expiration date
-#[cfg(feature = "biome-credentials")]
+#[cfg(any(feature = "biome-credentials", feature = "oauth"))]
 pub(crate) fn ignore_exp_validation(issuer: &str) -> Validation {
     let mut validation = Validation::default();
     validation.leeway = DEFAULT_LEEWAY;
@@ -0,0 +1,181 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TLS configuration for serving the REST API over HTTPS.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use openssl::ssl::{
+    SslAcceptor, SslAcceptorBuilder, SslFiletype, SslMethod, SslVerifyMode, SslVersion,
+};
+
+use super::RestApiServerError;
+
+/// The minimum version of TLS that the REST API will negotiate with clients.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TlsVersion {
+    Tls1_2,
+    Tls1_3,
+}
+
+impl TlsVersion {
+    fn ssl_version(self) -> SslVersion {
+        match self {
+            TlsVersion::Tls1_2 => SslVersion::TLS1_2,
+            TlsVersion::Tls1_3 => SslVersion::TLS1_3,
+        }
+    }
+}
+
+impl Default for TlsVersion {
+    fn default() -> Self {
+        TlsVersion::Tls1_2
+    }
+}
+
+impl FromStr for TlsVersion {
+    type Err = RestApiServerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1.2" => Ok(TlsVersion::Tls1_2),
+            "1.3" => Ok(TlsVersion::Tls1_3),
+            _ => Err(RestApiServerError::InvalidTlsConfig(format!(
+                "unsupported TLS version: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Configuration for serving the REST API over HTTPS.
+///
+/// The certificate and private key must be PEM encoded. If a client certificate authority is
+/// configured, clients must present a certificate signed by it (mutual TLS); otherwise client
+/// certificates are not requested.
+#[derive(Clone, Debug)]
+pub struct RestApiTlsConfig {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    client_ca_path: Option<PathBuf>,
+    min_version: TlsVersion,
+}
+
+impl RestApiTlsConfig {
+    /// Creates a new `RestApiTlsConfig` with the server's certificate chain and private key files.
+    /// TLS 1.2 is the minimum version accepted by default.
+    pub fn new(cert_path: &str, key_path: &str) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            client_ca_path: None,
+            min_version: TlsVersion::default(),
+        }
+    }
+
+    /// Requires clients to present a certificate signed by one of the certificate authorities in
+    /// the given file.
+    pub fn with_client_ca(mut self, client_ca_path: &str) -> Self {
+        self.client_ca_path = Some(client_ca_path.into());
+        self
+    }
+
+    /// Sets the minimum version of TLS accepted from clients.
+    pub fn with_min_version(mut self, min_version: TlsVersion) -> Self {
+        self.min_version = min_version;
+        self
+    }
+
+    /// Builds the acceptor for HTTPS connections, verifying that the configured files are valid.
+    pub(super) fn acceptor_builder(&self) -> Result<SslAcceptorBuilder, RestApiServerError> {
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls())
+            .map_err(|err| RestApiServerError::InvalidTlsConfig(err.to_string()))?;
+
+        acceptor
+            .set_min_proto_version(Some(self.min_version.ssl_version()))
+            .map_err(|err| {
+                RestApiServerError::InvalidTlsConfig(format!(
+                    "unable to set minimum TLS version: {}",
+                    err
+                ))
+            })?;
+        acceptor
+            .set_private_key_file(&self.key_path, SslFiletype::PEM)
+            .map_err(|err| {
+                RestApiServerError::InvalidTlsConfig(format!(
+                    "invalid private key {}: {}",
+                    self.key_path.display(),
+                    err
+                ))
+            })?;
+        acceptor
+            .set_certificate_chain_file(&self.cert_path)
+            .map_err(|err| {
+                RestApiServerError::InvalidTlsConfig(format!(
+                    "invalid certificate {}: {}",
+                    self.cert_path.display(),
+                    err
+                ))
+            })?;
+        acceptor.check_private_key().map_err(|err| {
+            RestApiServerError::InvalidTlsConfig(format!(
+                "private key does not match certificate: {}",
+                err
+            ))
+        })?;
+
+        if let Some(client_ca_path) = &self.client_ca_path {
+            acceptor.set_ca_file(client_ca_path).map_err(|err| {
+                RestApiServerError::InvalidTlsConfig(format!(
+                    "invalid client certificate authority {}: {}",
+                    client_ca_path.display(),
+                    err
+                ))
+            })?;
+            acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        } else {
+            acceptor.set_verify(SslVerifyMode::NONE);
+        }
+
+        Ok(acceptor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that the supported minimum TLS versions are parsed, and that other versions are
+    /// rejected.
+    #[test]
+    fn parse_tls_version() {
+        assert_eq!("1.2".parse::<TlsVersion>().ok(), Some(TlsVersion::Tls1_2));
+        assert_eq!("1.3".parse::<TlsVersion>().ok(), Some(TlsVersion::Tls1_3));
+        assert!(matches!(
+            "1.1".parse::<TlsVersion>(),
+            Err(RestApiServerError::InvalidTlsConfig(_))
+        ));
+    }
+
+    /// Verifies that an acceptor cannot be built when the configured files do not exist.
+    #[test]
+    fn acceptor_builder_missing_files() {
+        let config = RestApiTlsConfig::new("/nonexistent/server.crt", "/nonexistent/server.key");
+        assert!(matches!(
+            config.acceptor_builder(),
+            Err(RestApiServerError::InvalidTlsConfig(_))
+        ));
+    }
+}
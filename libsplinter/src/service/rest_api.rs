@@ -17,6 +17,8 @@
 use std::sync::Arc;
 
 use crate::actix_web::{web, Error as ActixError, HttpRequest, HttpResponse};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
 use crate::futures::Future;
 use crate::rest_api::{Continuation, Method, RequestGuard};
 
@@ -46,6 +48,9 @@ pub struct ServiceEndpoint {
     pub handler: Handler,
     /// Guards for this endpoint
     pub request_guards: Vec<Box<dyn ServiceRequestGuard>>,
+    /// The permission that is required to call this endpoint
+    #[cfg(feature = "authorization")]
+    pub permission: Permission,
 }
 
 /// This trait enforces that the Request guard is Clone.
@@ -0,0 +1,622 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed durable sets, powered by [`Diesel`](https://crates.io/crates/diesel).
+//!
+//! This module contains the [`DieselDurableOrderedSet`], which provides implementations of the
+//! [`DurableSet`] and [`DurableOrderedSet`] traits. Any number of named sets may share a single
+//! database; items are converted to and from their stored form via the [`DatabaseSetItem`]
+//! trait, and the size of each set may be limited with a [`RetentionPolicy`].
+//!
+//! [`DieselDurableOrderedSet`]: struct.DieselDurableOrderedSet.html
+//! [`DurableSet`]: ../trait.DurableSet.html
+//! [`DurableOrderedSet`]: ../trait.DurableOrderedSet.html
+//! [`DatabaseSetItem`]: trait.DatabaseSetItem.html
+//! [`RetentionPolicy`]: struct.RetentionPolicy.html
+
+pub mod migrations;
+mod models;
+mod operations;
+mod schema;
+
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
+
+use super::{DurableOrderedSet, DurableRange, DurableSet, DurableSetError};
+
+use models::DurableSetEntryModel;
+use operations::add_entry::DurableSetAddEntryOperation as _;
+use operations::count_entries::DurableSetCountEntriesOperation as _;
+use operations::fetch_entry::DurableSetFetchEntryOperation as _;
+use operations::list_entries::DurableSetListEntriesOperation as _;
+use operations::purge_entries::DurableSetPurgeEntriesOperation as _;
+use operations::remove_entry::DurableSetRemoveEntryOperation as _;
+use operations::DurableSetOperations;
+
+/// An item that may be stored in a `DieselDurableOrderedSet`.
+///
+/// Items are stored as bytes, keyed by an integer derived from their index value. The key must
+/// preserve the ordering of the index values, as the set relies on it to order its items.
+pub trait DatabaseSetItem<Index>: Sized {
+    /// Returns the database key for the given index value.
+    fn index_key(index: &Index) -> Result<i64, DurableSetError>;
+
+    /// Serializes the item into the bytes that are stored in the database.
+    fn to_bytes(&self) -> Result<Vec<u8>, DurableSetError>;
+
+    /// Deserializes an item from the bytes stored in the database.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DurableSetError>;
+}
+
+/// Limits on the items retained by a `DieselDurableOrderedSet`.
+///
+/// The policy is applied each time an item is added to the set. Items older than the maximum age
+/// are removed first, then the items with the lowest index values are removed until the set
+/// contains no more than the maximum number of items. By default, a set is unbounded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetentionPolicy {
+    max_items: Option<u64>,
+    max_age: Option<Duration>,
+}
+
+impl RetentionPolicy {
+    /// Creates a new, unbounded retention policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of items the set will retain.
+    pub fn with_max_items(mut self, max_items: u64) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Sets the maximum amount of time an item will be retained after it has been added.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+}
+
+/// A database-backed durable ordered set, powered by [`Diesel`](https://crates.io/crates/diesel).
+///
+/// Each set is identified by a name, so multiple sets may be stored in the same database.
+pub struct DieselDurableOrderedSet<C: diesel::Connection + 'static, V, Index> {
+    connection_pool: Pool<ConnectionManager<C>>,
+    set_name: String,
+    retention_policy: RetentionPolicy,
+    _items: PhantomData<fn() -> (V, Index)>,
+}
+
+impl<C: diesel::Connection, V, Index> DieselDurableOrderedSet<C, V, Index> {
+    /// Creates a new, unbounded `DieselDurableOrderedSet`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    ///  * `set_name`: the name that identifies this set's items in the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>, set_name: &str) -> Self {
+        Self {
+            connection_pool,
+            set_name: set_name.into(),
+            retention_policy: RetentionPolicy::default(),
+            _items: PhantomData,
+        }
+    }
+
+    /// Sets the retention policy that is applied when items are added to the set.
+    pub fn with_retention_policy(mut self, retention_policy: RetentionPolicy) -> Self {
+        self.retention_policy = retention_policy;
+        self
+    }
+
+    fn get_connection(&self) -> Result<PooledConnection<ConnectionManager<C>>, DurableSetError> {
+        self.connection_pool.get().map_err(|err| {
+            DurableSetError::with_source("Failed to get database connection", Box::new(err))
+        })
+    }
+}
+
+impl<C, V, Index> DieselDurableOrderedSet<C, V, Index>
+where
+    C: diesel::Connection,
+    V: DatabaseSetItem<Index> + Borrow<Index>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<diesel::sql_types::Binary, C::Backend>,
+{
+    fn to_model(&self, item: &V) -> Result<DurableSetEntryModel, DurableSetError> {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| {
+                DurableSetError::with_source("System time is before the Unix epoch", Box::new(err))
+            })?
+            .as_secs() as i64;
+
+        Ok(DurableSetEntryModel {
+            set_name: self.set_name.clone(),
+            item_key: V::index_key(item.borrow())?,
+            item_value: item.to_bytes()?,
+            created_at,
+        })
+    }
+
+    fn apply_retention_policy(&self) -> Result<(), DurableSetError> {
+        let created_before = match self.retention_policy.max_age {
+            Some(max_age) => {
+                let cutoff = SystemTime::now()
+                    .checked_sub(max_age)
+                    .and_then(|cutoff| cutoff.duration_since(UNIX_EPOCH).ok())
+                    .map(|cutoff| cutoff.as_secs() as i64)
+                    .unwrap_or(0);
+                Some(cutoff)
+            }
+            None => None,
+        };
+
+        if created_before.is_none() && self.retention_policy.max_items.is_none() {
+            return Ok(());
+        }
+
+        DurableSetOperations::new(&*self.get_connection()?).purge_entries(
+            &self.set_name,
+            created_before,
+            self.retention_policy.max_items,
+        )
+    }
+
+    fn remove_item(&self, item: &V) -> Result<Option<V>, DurableSetError> {
+        DurableSetOperations::new(&*self.get_connection()?)
+            .remove_entry(&self.set_name, V::index_key(item.borrow())?)?
+            .map(|bytes| V::from_bytes(&bytes))
+            .transpose()
+    }
+
+    fn fetch_item(&self, index: &Index) -> Result<Option<V>, DurableSetError> {
+        DurableSetOperations::new(&*self.get_connection()?)
+            .fetch_entry(&self.set_name, V::index_key(index)?)?
+            .map(|bytes| V::from_bytes(&bytes))
+            .transpose()
+    }
+
+    fn fetch_first_item(&self) -> Result<Option<V>, DurableSetError> {
+        DurableSetOperations::new(&*self.get_connection()?)
+            .fetch_first_entry(&self.set_name)?
+            .map(|bytes| V::from_bytes(&bytes))
+            .transpose()
+    }
+
+    fn fetch_last_item(&self) -> Result<Option<V>, DurableSetError> {
+        DurableSetOperations::new(&*self.get_connection()?)
+            .fetch_last_entry(&self.set_name)?
+            .map(|bytes| V::from_bytes(&bytes))
+            .transpose()
+    }
+
+    fn list_items(&self, range: DurableRange<&Index>) -> Result<Vec<V>, DurableSetError> {
+        // The range is converted to an inclusive range of keys; a range that excludes the
+        // minimum or maximum key is empty.
+        let start_key = match range.start {
+            Bound::Included(index) => V::index_key(index)?,
+            Bound::Excluded(index) => match V::index_key(index)?.checked_add(1) {
+                Some(key) => key,
+                None => return Ok(vec![]),
+            },
+            Bound::Unbounded => std::i64::MIN,
+        };
+        let end_key = match range.end {
+            Bound::Included(index) => V::index_key(index)?,
+            Bound::Excluded(index) => match V::index_key(index)?.checked_sub(1) {
+                Some(key) => key,
+                None => return Ok(vec![]),
+            },
+            Bound::Unbounded => std::i64::MAX,
+        };
+
+        DurableSetOperations::new(&*self.get_connection()?)
+            .list_entries(&self.set_name, start_key, end_key)?
+            .iter()
+            .map(|bytes| V::from_bytes(bytes))
+            .collect()
+    }
+
+    fn count_items(&self) -> Result<u64, DurableSetError> {
+        DurableSetOperations::new(&*self.get_connection()?).count_entries(&self.set_name)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<V, Index> Clone for DieselDurableOrderedSet<diesel::pg::PgConnection, V, Index> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+            set_name: self.set_name.clone(),
+            retention_policy: self.retention_policy.clone(),
+            _items: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<V, Index> Clone for DieselDurableOrderedSet<diesel::sqlite::SqliteConnection, V, Index> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+            set_name: self.set_name.clone(),
+            retention_policy: self.retention_policy.clone(),
+            _items: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<V, Index> DurableSet for DieselDurableOrderedSet<diesel::pg::PgConnection, V, Index>
+where
+    V: DatabaseSetItem<Index> + Borrow<Index> + Ord + Send + 'static,
+    Index: Ord + Send + 'static,
+{
+    type Item = V;
+
+    fn add(&mut self, item: Self::Item) -> Result<(), DurableSetError> {
+        let entry = self.to_model(&item)?;
+        DurableSetOperations::new(&*self.get_connection()?).add_entry(entry)?;
+        self.apply_retention_policy()
+    }
+
+    fn remove(&mut self, item: &Self::Item) -> Result<Option<Self::Item>, DurableSetError> {
+        self.remove_item(item)
+    }
+
+    fn contains(&self, item: &Self::Item) -> Result<bool, DurableSetError> {
+        self.fetch_item(item.borrow()).map(|item| item.is_some())
+    }
+
+    fn iter<'a>(&'a self) -> Result<Box<(dyn Iterator<Item = Self::Item> + 'a)>, DurableSetError> {
+        Ok(Box::new(self.list_items((..).into())?.into_iter()))
+    }
+
+    fn len(&self) -> Result<u64, DurableSetError> {
+        self.count_items()
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<V, Index> DurableOrderedSet<V, Index>
+    for DieselDurableOrderedSet<diesel::pg::PgConnection, V, Index>
+where
+    V: DatabaseSetItem<Index> + Borrow<Index> + Ord + Send + 'static,
+    Index: Ord + Send + 'static,
+{
+    fn get_by_index(&self, index_value: &Index) -> Result<Option<Self::Item>, DurableSetError> {
+        self.fetch_item(index_value)
+    }
+
+    fn contains_by_index(&self, index_value: &Index) -> Result<bool, DurableSetError> {
+        self.fetch_item(index_value).map(|item| item.is_some())
+    }
+
+    fn range_iter<'a>(
+        &'a self,
+        range: DurableRange<&Index>,
+    ) -> Result<Box<(dyn Iterator<Item = Self::Item> + 'a)>, DurableSetError> {
+        Ok(Box::new(self.list_items(range)?.into_iter()))
+    }
+
+    fn first(&self) -> Result<Option<Self::Item>, DurableSetError> {
+        self.fetch_first_item()
+    }
+
+    fn last(&self) -> Result<Option<Self::Item>, DurableSetError> {
+        self.fetch_last_item()
+    }
+
+    fn clone_boxed_ordered_set(&self) -> Box<dyn DurableOrderedSet<V, Index>> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<V, Index> DurableSet for DieselDurableOrderedSet<diesel::sqlite::SqliteConnection, V, Index>
+where
+    V: DatabaseSetItem<Index> + Borrow<Index> + Ord + Send + 'static,
+    Index: Ord + Send + 'static,
+{
+    type Item = V;
+
+    fn add(&mut self, item: Self::Item) -> Result<(), DurableSetError> {
+        let entry = self.to_model(&item)?;
+        DurableSetOperations::new(&*self.get_connection()?).add_entry(entry)?;
+        self.apply_retention_policy()
+    }
+
+    fn remove(&mut self, item: &Self::Item) -> Result<Option<Self::Item>, DurableSetError> {
+        self.remove_item(item)
+    }
+
+    fn contains(&self, item: &Self::Item) -> Result<bool, DurableSetError> {
+        self.fetch_item(item.borrow()).map(|item| item.is_some())
+    }
+
+    fn iter<'a>(&'a self) -> Result<Box<(dyn Iterator<Item = Self::Item> + 'a)>, DurableSetError> {
+        Ok(Box::new(self.list_items((..).into())?.into_iter()))
+    }
+
+    fn len(&self) -> Result<u64, DurableSetError> {
+        self.count_items()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<V, Index> DurableOrderedSet<V, Index>
+    for DieselDurableOrderedSet<diesel::sqlite::SqliteConnection, V, Index>
+where
+    V: DatabaseSetItem<Index> + Borrow<Index> + Ord + Send + 'static,
+    Index: Ord + Send + 'static,
+{
+    fn get_by_index(&self, index_value: &Index) -> Result<Option<Self::Item>, DurableSetError> {
+        self.fetch_item(index_value)
+    }
+
+    fn contains_by_index(&self, index_value: &Index) -> Result<bool, DurableSetError> {
+        self.fetch_item(index_value).map(|item| item.is_some())
+    }
+
+    fn range_iter<'a>(
+        &'a self,
+        range: DurableRange<&Index>,
+    ) -> Result<Box<(dyn Iterator<Item = Self::Item> + 'a)>, DurableSetError> {
+        Ok(Box::new(self.list_items(range)?.into_iter()))
+    }
+
+    fn first(&self) -> Result<Option<Self::Item>, DurableSetError> {
+        self.fetch_first_item()
+    }
+
+    fn last(&self) -> Result<Option<Self::Item>, DurableSetError> {
+        self.fetch_last_item()
+    }
+
+    fn clone_boxed_ordered_set(&self) -> Box<dyn DurableOrderedSet<V, Index>> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use migrations::run_sqlite_migrations;
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+    struct TestItem {
+        id: i64,
+        value: String,
+    }
+
+    impl TestItem {
+        fn new(id: i64, value: &str) -> Self {
+            Self {
+                id,
+                value: value.into(),
+            }
+        }
+    }
+
+    impl Borrow<i64> for TestItem {
+        fn borrow(&self) -> &i64 {
+            &self.id
+        }
+    }
+
+    impl DatabaseSetItem<i64> for TestItem {
+        fn index_key(index: &i64) -> Result<i64, DurableSetError> {
+            Ok(*index)
+        }
+
+        fn to_bytes(&self) -> Result<Vec<u8>, DurableSetError> {
+            serde_json::to_vec(self).map_err(|err| {
+                DurableSetError::with_source("Unable to serialize item", Box::new(err))
+            })
+        }
+
+        fn from_bytes(bytes: &[u8]) -> Result<Self, DurableSetError> {
+            serde_json::from_slice(bytes).map_err(|err| {
+                DurableSetError::with_source("Unable to deserialize item", Box::new(err))
+            })
+        }
+    }
+
+    /// Verify that items can be added to, queried from and removed from the set:
+    ///
+    /// 1. Add three items and verify the length of the set
+    /// 2. Verify that the items are found by value and by index, and that an unknown index is not
+    /// 3. Add an item with an existing index and verify that an error is returned and the original
+    ///    item is retained
+    /// 4. Remove an item and verify that it is returned and is no longer in the set
+    /// 5. Remove the item again and verify that nothing is returned
+    #[test]
+    fn add_and_remove() {
+        let pool = create_connection_pool_and_migrate();
+        let mut set = DieselDurableOrderedSet::<SqliteConnection, TestItem, i64>::new(pool, "test");
+
+        set.add(TestItem::new(1, "one"))
+            .expect("Unable to add item");
+        set.add(TestItem::new(2, "two"))
+            .expect("Unable to add item");
+        set.add(TestItem::new(3, "three"))
+            .expect("Unable to add item");
+        assert_eq!(3, set.len().expect("Unable to get length"));
+
+        assert!(set
+            .contains(&TestItem::new(2, "two"))
+            .expect("Unable to check item"));
+        assert_eq!(
+            Some(TestItem::new(3, "three")),
+            set.get_by_index(&3).expect("Unable to get item")
+        );
+        assert!(!set.contains_by_index(&4).expect("Unable to check index"));
+
+        assert!(set.add(TestItem::new(1, "uno")).is_err());
+        assert_eq!(3, set.len().expect("Unable to get length"));
+        assert_eq!(
+            Some(TestItem::new(1, "one")),
+            set.get_by_index(&1).expect("Unable to get item")
+        );
+
+        assert_eq!(
+            Some(TestItem::new(2, "two")),
+            set.remove(&TestItem::new(2, "two"))
+                .expect("Unable to remove item")
+        );
+        assert!(!set.contains_by_index(&2).expect("Unable to check index"));
+        assert_eq!(2, set.len().expect("Unable to get length"));
+
+        assert_eq!(
+            None,
+            set.remove(&TestItem::new(2, "two"))
+                .expect("Unable to remove item")
+        );
+    }
+
+    /// Verify that the set's items are ordered by index:
+    ///
+    /// 1. Add items out of order and verify that `iter` returns them in order
+    /// 2. Verify that `first` and `last` return the lowest and highest items
+    /// 3. Verify that `range_iter` honors inclusive, exclusive and unbounded ranges
+    #[test]
+    fn ordering_and_ranges() {
+        let pool = create_connection_pool_and_migrate();
+        let mut set = DieselDurableOrderedSet::<SqliteConnection, TestItem, i64>::new(pool, "test");
+
+        for id in &[4, 1, 5, 2, 3] {
+            set.add(TestItem::new(*id, &id.to_string()))
+                .expect("Unable to add item");
+        }
+
+        assert_eq!(
+            vec![1, 2, 3, 4, 5],
+            ids(set.iter().expect("Unable to iterate"))
+        );
+        assert_eq!(
+            Some(1),
+            set.first()
+                .expect("Unable to get first")
+                .map(|item| item.id)
+        );
+        assert_eq!(
+            Some(5),
+            set.last().expect("Unable to get last").map(|item| item.id)
+        );
+
+        assert_eq!(
+            vec![2, 3],
+            ids(set.range_iter((&2..&4).into()).expect("Unable to iterate"))
+        );
+        assert_eq!(
+            vec![2, 3, 4],
+            ids(set.range_iter((&2..=&4).into()).expect("Unable to iterate"))
+        );
+        assert_eq!(
+            vec![4, 5],
+            ids(set
+                .range_iter((Bound::Excluded(&3), Bound::Unbounded).into())
+                .expect("Unable to iterate"))
+        );
+        assert_eq!(
+            vec![1, 2],
+            ids(set.range_iter((..&3).into()).expect("Unable to iterate"))
+        );
+    }
+
+    /// Verify that a retention policy limits the number of items in the set, removing the
+    /// lowest items first.
+    #[test]
+    fn retention_max_items() {
+        let pool = create_connection_pool_and_migrate();
+        let mut set = DieselDurableOrderedSet::<SqliteConnection, TestItem, i64>::new(pool, "test")
+            .with_retention_policy(RetentionPolicy::new().with_max_items(3));
+
+        for id in 1..=5 {
+            set.add(TestItem::new(id, &id.to_string()))
+                .expect("Unable to add item");
+        }
+
+        assert_eq!(3, set.len().expect("Unable to get length"));
+        assert_eq!(
+            vec![3, 4, 5],
+            set.iter()
+                .expect("Unable to iterate")
+                .map(|item| item.id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// Verify that sets with different names in the same database do not share items, and that
+    /// a cloned set operates on the same items as the original.
+    #[test]
+    fn named_sets_are_isolated() {
+        let pool = create_connection_pool_and_migrate();
+        let mut set_a =
+            DieselDurableOrderedSet::<SqliteConnection, TestItem, i64>::new(pool.clone(), "a");
+        let mut set_b = DieselDurableOrderedSet::<SqliteConnection, TestItem, i64>::new(pool, "b");
+
+        set_a
+            .add(TestItem::new(1, "a"))
+            .expect("Unable to add item");
+        set_b
+            .add(TestItem::new(1, "b"))
+            .expect("Unable to add item");
+        set_b
+            .add(TestItem::new(2, "b"))
+            .expect("Unable to add item");
+
+        assert_eq!(1, set_a.len().expect("Unable to get length"));
+        assert_eq!(2, set_b.len().expect("Unable to get length"));
+        assert_eq!(
+            Some(TestItem::new(1, "a")),
+            set_a.get_by_index(&1).expect("Unable to get item")
+        );
+
+        let set_a_clone = set_a.clone_boxed_ordered_set();
+        set_a
+            .add(TestItem::new(2, "a"))
+            .expect("Unable to add item");
+        assert_eq!(2, set_a_clone.len().expect("Unable to get length"));
+    }
+
+    fn ids(items: Box<dyn Iterator<Item = TestItem> + '_>) -> Vec<i64> {
+        items.map(|item| item.id).collect()
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection insures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
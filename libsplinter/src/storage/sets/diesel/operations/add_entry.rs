@@ -0,0 +1,73 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add entry" operation for the `DieselDurableOrderedSet`.
+
+use diesel::{
+    dsl::insert_into, prelude::*, query_builder::InsertStatement, query_dsl::methods::ExecuteDsl,
+};
+
+use crate::storage::sets::diesel::{models::DurableSetEntryModel, schema::durable_set_entries};
+use crate::storage::sets::DurableSetError;
+
+use super::DurableSetOperations;
+
+pub(in crate::storage::sets::diesel) trait DurableSetAddEntryOperation {
+    /// Adds the entry to its set.
+    ///
+    /// Returns an error if the set already contains an entry with the same key; the existing
+    /// entry is left unchanged.
+    fn add_entry(&self, entry: DurableSetEntryModel) -> Result<(), DurableSetError>;
+}
+
+impl<'a, C> DurableSetAddEntryOperation for DurableSetOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    InsertStatement<
+        durable_set_entries::table,
+        <DurableSetEntryModel as Insertable<durable_set_entries::table>>::Values,
+    >: ExecuteDsl<C>,
+{
+    fn add_entry(&self, entry: DurableSetEntryModel) -> Result<(), DurableSetError> {
+        let inserted = self
+            .conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                let exists = durable_set_entries::table
+                    .find((&entry.set_name, entry.item_key))
+                    .select(durable_set_entries::item_key)
+                    .first::<i64>(self.conn)
+                    .optional()?
+                    .is_some();
+                if exists {
+                    return Ok(false);
+                }
+                insert_into(durable_set_entries::table)
+                    .values(entry)
+                    .execute(self.conn)?;
+                Ok(true)
+            })
+            .map_err(|err| {
+                DurableSetError::with_source("Failed to add set entry", Box::new(err))
+            })?;
+
+        if inserted {
+            Ok(())
+        } else {
+            Err(DurableSetError::new(
+                "The set already contains an entry with the same index",
+            ))
+        }
+    }
+}
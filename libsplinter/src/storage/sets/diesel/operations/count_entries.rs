@@ -0,0 +1,43 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "count entries" operation for the `DieselDurableOrderedSet`.
+
+use diesel::prelude::*;
+
+use crate::storage::sets::diesel::schema::durable_set_entries;
+use crate::storage::sets::DurableSetError;
+
+use super::DurableSetOperations;
+
+pub(in crate::storage::sets::diesel) trait DurableSetCountEntriesOperation {
+    fn count_entries(&self, set_name: &str) -> Result<u64, DurableSetError>;
+}
+
+impl<'a, C> DurableSetCountEntriesOperation for DurableSetOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn count_entries(&self, set_name: &str) -> Result<u64, DurableSetError> {
+        durable_set_entries::table
+            .filter(durable_set_entries::set_name.eq(set_name))
+            .count()
+            .get_result::<i64>(self.conn)
+            .map(|count| count as u64)
+            .map_err(|err| {
+                DurableSetError::with_source("Failed to count set entries", Box::new(err))
+            })
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "fetch entry" operations for the `DieselDurableOrderedSet`.
+
+use diesel::prelude::*;
+
+use crate::storage::sets::diesel::schema::durable_set_entries;
+use crate::storage::sets::DurableSetError;
+
+use super::DurableSetOperations;
+
+pub(in crate::storage::sets::diesel) trait DurableSetFetchEntryOperation {
+    /// Returns the value of the entry with the given key.
+    fn fetch_entry(
+        &self,
+        set_name: &str,
+        item_key: i64,
+    ) -> Result<Option<Vec<u8>>, DurableSetError>;
+
+    /// Returns the value of the entry with the lowest key.
+    fn fetch_first_entry(&self, set_name: &str) -> Result<Option<Vec<u8>>, DurableSetError>;
+
+    /// Returns the value of the entry with the highest key.
+    fn fetch_last_entry(&self, set_name: &str) -> Result<Option<Vec<u8>>, DurableSetError>;
+}
+
+impl<'a, C> DurableSetFetchEntryOperation for DurableSetOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<diesel::sql_types::Binary, C::Backend>,
+{
+    fn fetch_entry(
+        &self,
+        set_name: &str,
+        item_key: i64,
+    ) -> Result<Option<Vec<u8>>, DurableSetError> {
+        durable_set_entries::table
+            .find((set_name, item_key))
+            .select(durable_set_entries::item_value)
+            .first::<Vec<u8>>(self.conn)
+            .optional()
+            .map_err(|err| DurableSetError::with_source("Failed to fetch set entry", Box::new(err)))
+    }
+
+    fn fetch_first_entry(&self, set_name: &str) -> Result<Option<Vec<u8>>, DurableSetError> {
+        durable_set_entries::table
+            .filter(durable_set_entries::set_name.eq(set_name))
+            .order(durable_set_entries::item_key.asc())
+            .select(durable_set_entries::item_value)
+            .first::<Vec<u8>>(self.conn)
+            .optional()
+            .map_err(|err| {
+                DurableSetError::with_source("Failed to fetch first set entry", Box::new(err))
+            })
+    }
+
+    fn fetch_last_entry(&self, set_name: &str) -> Result<Option<Vec<u8>>, DurableSetError> {
+        durable_set_entries::table
+            .filter(durable_set_entries::set_name.eq(set_name))
+            .order(durable_set_entries::item_key.desc())
+            .select(durable_set_entries::item_value)
+            .first::<Vec<u8>>(self.conn)
+            .optional()
+            .map_err(|err| {
+                DurableSetError::with_source("Failed to fetch last set entry", Box::new(err))
+            })
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list entries" operation for the `DieselDurableOrderedSet`.
+
+use diesel::prelude::*;
+
+use crate::storage::sets::diesel::schema::durable_set_entries;
+use crate::storage::sets::DurableSetError;
+
+use super::DurableSetOperations;
+
+pub(in crate::storage::sets::diesel) trait DurableSetListEntriesOperation {
+    /// Lists the values of all entries with keys in the inclusive range `[start_key, end_key]`,
+    /// ordered by key.
+    fn list_entries(
+        &self,
+        set_name: &str,
+        start_key: i64,
+        end_key: i64,
+    ) -> Result<Vec<Vec<u8>>, DurableSetError>;
+}
+
+impl<'a, C> DurableSetListEntriesOperation for DurableSetOperations<'a, C>
+where
+    C: diesel::Connection,
+    Vec<u8>: diesel::deserialize::FromSql<diesel::sql_types::Binary, C::Backend>,
+{
+    fn list_entries(
+        &self,
+        set_name: &str,
+        start_key: i64,
+        end_key: i64,
+    ) -> Result<Vec<Vec<u8>>, DurableSetError> {
+        durable_set_entries::table
+            .filter(
+                durable_set_entries::set_name
+                    .eq(set_name)
+                    .and(durable_set_entries::item_key.between(start_key, end_key)),
+            )
+            .order(durable_set_entries::item_key.asc())
+            .select(durable_set_entries::item_value)
+            .load::<Vec<u8>>(self.conn)
+            .map_err(|err| {
+                DurableSetError::with_source("Failed to list set entries", Box::new(err))
+            })
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "purge entries" operation for the `DieselDurableOrderedSet`.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::storage::sets::diesel::schema::durable_set_entries;
+use crate::storage::sets::DurableSetError;
+
+use super::DurableSetOperations;
+
+pub(in crate::storage::sets::diesel) trait DurableSetPurgeEntriesOperation {
+    /// Removes all entries created before `created_before` (if provided), then removes the
+    /// entries with the lowest keys until at most `max_items` (if provided) remain.
+    fn purge_entries(
+        &self,
+        set_name: &str,
+        created_before: Option<i64>,
+        max_items: Option<u64>,
+    ) -> Result<(), DurableSetError>;
+}
+
+impl<'a, C> DurableSetPurgeEntriesOperation for DurableSetOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn purge_entries(
+        &self,
+        set_name: &str,
+        created_before: Option<i64>,
+        max_items: Option<u64>,
+    ) -> Result<(), DurableSetError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                if let Some(created_before) = created_before {
+                    delete(
+                        durable_set_entries::table.filter(
+                            durable_set_entries::set_name
+                                .eq(set_name)
+                                .and(durable_set_entries::created_at.lt(created_before)),
+                        ),
+                    )
+                    .execute(self.conn)?;
+                }
+
+                if let Some(max_items) = max_items {
+                    // Find the highest key that falls outside of the retained items; it and every
+                    // key below it are removed.
+                    let cutoff = durable_set_entries::table
+                        .filter(durable_set_entries::set_name.eq(set_name))
+                        .order(durable_set_entries::item_key.desc())
+                        .select(durable_set_entries::item_key)
+                        .offset(max_items as i64)
+                        .first::<i64>(self.conn)
+                        .optional()?;
+
+                    if let Some(cutoff) = cutoff {
+                        delete(
+                            durable_set_entries::table.filter(
+                                durable_set_entries::set_name
+                                    .eq(set_name)
+                                    .and(durable_set_entries::item_key.le(cutoff)),
+                            ),
+                        )
+                        .execute(self.conn)?;
+                    }
+                }
+
+                Ok(())
+            })
+            .map_err(|err| {
+                DurableSetError::with_source("Failed to purge set entries", Box::new(err))
+            })
+    }
+}
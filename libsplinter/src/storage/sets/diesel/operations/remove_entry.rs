@@ -0,0 +1,60 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove entry" operation for the `DieselDurableOrderedSet`.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::storage::sets::diesel::schema::durable_set_entries;
+use crate::storage::sets::DurableSetError;
+
+use super::DurableSetOperations;
+
+pub(in crate::storage::sets::diesel) trait DurableSetRemoveEntryOperation {
+    /// Removes the entry with the given key, returning its value if it existed.
+    fn remove_entry(
+        &self,
+        set_name: &str,
+        item_key: i64,
+    ) -> Result<Option<Vec<u8>>, DurableSetError>;
+}
+
+impl<'a, C> DurableSetRemoveEntryOperation for DurableSetOperations<'a, C>
+where
+    C: diesel::Connection,
+    Vec<u8>: diesel::deserialize::FromSql<diesel::sql_types::Binary, C::Backend>,
+{
+    fn remove_entry(
+        &self,
+        set_name: &str,
+        item_key: i64,
+    ) -> Result<Option<Vec<u8>>, DurableSetError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                let value = durable_set_entries::table
+                    .find((set_name, item_key))
+                    .select(durable_set_entries::item_value)
+                    .first::<Vec<u8>>(self.conn)
+                    .optional()?;
+                if value.is_some() {
+                    delete(durable_set_entries::table.find((set_name, item_key)))
+                        .execute(self.conn)?;
+                }
+                Ok(value)
+            })
+            .map_err(|err| {
+                DurableSetError::with_source("Failed to remove set entry", Box::new(err))
+            })
+    }
+}
@@ -14,6 +14,8 @@
 
 //! Durable sets, both ordered and unordered. Implementations of these sets must be thread-safe.
 
+#[cfg(feature = "durable-set-database")]
+pub mod diesel;
 pub mod mem;
 
 use std::borrow::Borrow;
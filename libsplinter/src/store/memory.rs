@@ -12,15 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "admin-service-event-store")]
+use std::time::SystemTime;
+
 #[cfg(feature = "biome-credentials")]
 use crate::biome::{
     CredentialsStore, MemoryCredentialsStore, MemoryRefreshTokenStore, RefreshTokenStore,
 };
 #[cfg(feature = "biome-key-management")]
 use crate::biome::{KeyStore, MemoryKeyStore};
+#[cfg(feature = "biome-notifications")]
+use crate::biome::{MemoryNotificationStore, NotificationStore};
+#[cfg(feature = "oauth")]
+use crate::biome::{MemoryOAuthUserStore, OAuthUserStore};
+#[cfg(feature = "biome")]
 use crate::biome::{MemoryUserStore, UserStore};
+#[cfg(feature = "admin-service-event-store")]
+use crate::{
+    admin::service::EventEntry,
+    storage::sets::{diesel::RetentionPolicy, DurableOrderedSet},
+};
 
-use super::StoreFactory;
+use super::{StoreFactory, StoreMigrationError};
 
 /// A `StoryFactory` backed by memory.
 #[derive(Default)]
@@ -29,8 +42,13 @@ pub struct MemoryStoreFactory {
     biome_credentials_store: MemoryCredentialsStore,
     #[cfg(feature = "biome-key-management")]
     biome_key_store: MemoryKeyStore,
+    #[cfg(feature = "biome-notifications")]
+    biome_notification_store: MemoryNotificationStore,
+    #[cfg(feature = "oauth")]
+    biome_oauth_user_store: MemoryOAuthUserStore,
     #[cfg(feature = "biome-credentials")]
     biome_refresh_token_store: MemoryRefreshTokenStore,
+    #[cfg(feature = "biome")]
     biome_user_store: MemoryUserStore,
 }
 
@@ -46,7 +64,7 @@ impl MemoryStoreFactory {
 
         #[cfg(feature = "biome-credentials")]
         let biome_user_store = MemoryUserStore::new(biome_credentials_store.clone());
-        #[cfg(not(feature = "biome-credentials"))]
+        #[cfg(all(feature = "biome", not(feature = "biome-credentials")))]
         let biome_user_store = MemoryUserStore::new();
 
         Self {
@@ -54,14 +72,34 @@ impl MemoryStoreFactory {
             biome_credentials_store,
             #[cfg(feature = "biome-key-management")]
             biome_key_store,
+            #[cfg(feature = "biome-notifications")]
+            biome_notification_store: MemoryNotificationStore::new(),
+            #[cfg(feature = "oauth")]
+            biome_oauth_user_store: MemoryOAuthUserStore::new(),
             #[cfg(feature = "biome-credentials")]
             biome_refresh_token_store: MemoryRefreshTokenStore::new(),
+            #[cfg(feature = "biome")]
             biome_user_store,
         }
     }
 }
 
 impl StoreFactory for MemoryStoreFactory {
+    #[cfg(feature = "admin-service-store")]
+    fn get_admin_service_store(&self) -> Option<Box<dyn crate::admin::store::AdminServiceStore>> {
+        None
+    }
+
+    /// The admin service keeps its events in memory by default, so no store is provided
+    #[cfg(feature = "admin-service-event-store")]
+    fn get_admin_event_store(
+        &self,
+        _set_name: &str,
+        _retention_policy: RetentionPolicy,
+    ) -> Option<Box<dyn DurableOrderedSet<EventEntry, SystemTime>>> {
+        None
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn get_biome_credentials_store(&self) -> Box<dyn CredentialsStore> {
         Box::new(self.biome_credentials_store.clone())
@@ -72,12 +110,32 @@ impl StoreFactory for MemoryStoreFactory {
         Box::new(self.biome_key_store.clone())
     }
 
+    #[cfg(feature = "biome-notifications")]
+    fn get_biome_notification_store(&self) -> Box<dyn NotificationStore> {
+        Box::new(self.biome_notification_store.clone())
+    }
+
+    #[cfg(feature = "oauth")]
+    fn get_biome_oauth_user_store(&self) -> Box<dyn OAuthUserStore> {
+        Box::new(self.biome_oauth_user_store.clone())
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn get_biome_refresh_token_store(&self) -> Box<dyn RefreshTokenStore> {
         Box::new(self.biome_refresh_token_store.clone())
     }
 
+    #[cfg(feature = "biome")]
     fn get_biome_user_store(&self) -> Box<dyn UserStore> {
         Box::new(self.biome_user_store.clone())
     }
+
+    #[cfg(feature = "registry-database")]
+    fn get_registry_store(&self) -> Option<Box<dyn crate::registry::RwRegistry>> {
+        None
+    }
+
+    fn run_migrations(&self) -> Result<(), StoreMigrationError> {
+        Ok(())
+    }
 }
@@ -19,12 +19,32 @@ pub mod postgres;
 pub mod sqlite;
 
 use std::str::FromStr;
+#[cfg(feature = "admin-service-event-store")]
+use std::time::SystemTime;
 
 #[cfg(feature = "diesel")]
 use diesel::r2d2::{ConnectionManager, Pool};
 
+#[cfg(feature = "admin-service-event-store")]
+use crate::admin::service::EventEntry;
+#[cfg(feature = "admin-service-event-store")]
+use crate::storage::sets::{diesel::RetentionPolicy, DurableOrderedSet};
+
 /// An abstract factory for creating Splinter stores backed by the same storage
 pub trait StoreFactory {
+    /// Get a new `AdminServiceStore`, or `None` if the factory's storage is not persistent
+    #[cfg(feature = "admin-service-store")]
+    fn get_admin_service_store(&self) -> Option<Box<dyn crate::admin::store::AdminServiceStore>>;
+
+    /// Get a new `DurableOrderedSet` that stores the admin service's events under the given set
+    /// name, or `None` if the factory's storage is not persistent
+    #[cfg(feature = "admin-service-event-store")]
+    fn get_admin_event_store(
+        &self,
+        set_name: &str,
+        retention_policy: RetentionPolicy,
+    ) -> Option<Box<dyn DurableOrderedSet<EventEntry, SystemTime>>>;
+
     /// Get a new `CredentialsStore`
     #[cfg(feature = "biome-credentials")]
     fn get_biome_credentials_store(&self) -> Box<dyn crate::biome::CredentialsStore>;
@@ -33,12 +53,31 @@ pub trait StoreFactory {
     #[cfg(feature = "biome-key-management")]
     fn get_biome_key_store(&self) -> Box<dyn crate::biome::KeyStore>;
 
+    /// Get a new `NotificationStore`; subscribers added to the store are only guaranteed to be
+    /// sent the notifications that are added through the same instance
+    #[cfg(feature = "biome-notifications")]
+    fn get_biome_notification_store(&self) -> Box<dyn crate::biome::NotificationStore>;
+
+    /// Get a new `OAuthUserStore`
+    #[cfg(feature = "oauth")]
+    fn get_biome_oauth_user_store(&self) -> Box<dyn crate::biome::OAuthUserStore>;
+
     /// Get a new `RefreshTokenStore`
     #[cfg(feature = "biome-credentials")]
     fn get_biome_refresh_token_store(&self) -> Box<dyn crate::biome::RefreshTokenStore>;
 
     /// Get a new `UserStore`
+    #[cfg(feature = "biome")]
     fn get_biome_user_store(&self) -> Box<dyn crate::biome::UserStore>;
+
+    /// Get a new `RwRegistry`, or `None` if the factory's storage is not persistent
+    #[cfg(feature = "registry-database")]
+    fn get_registry_store(&self) -> Option<Box<dyn crate::registry::RwRegistry>>;
+
+    /// Runs the database migrations of the non-Biome stores created by this factory: the admin
+    /// service store, the admin service's event store and the registry; Biome's migrations are
+    /// run by `splinter database migrate`
+    fn run_migrations(&self) -> Result<(), StoreMigrationError>;
 }
 
 /// Creates a `StoreFactory` backed by the given connection
@@ -79,6 +118,30 @@ pub fn create_store_factory(
     }
 }
 
+/// Checks that the storage identified by the given connection URI can be reached, by
+/// establishing and then closing a single connection to it
+///
+/// # Arguments
+///
+/// * `connection_uri` - The identifier of the storage connection to check
+pub fn check_connection(connection_uri: &ConnectionUri) -> Result<(), StoreConnectionError> {
+    match connection_uri {
+        ConnectionUri::Memory => Ok(()),
+        #[cfg(feature = "postgres")]
+        ConnectionUri::Postgres(url) => {
+            <diesel::pg::PgConnection as diesel::Connection>::establish(url)
+                .map(|_| ())
+                .map_err(|err| StoreConnectionError(err.to_string()))
+        }
+        #[cfg(feature = "sqlite")]
+        ConnectionUri::Sqlite(conn_str) => {
+            <diesel::sqlite::SqliteConnection as diesel::Connection>::establish(conn_str)
+                .map(|_| ())
+                .map_err(|err| StoreConnectionError(err.to_string()))
+        }
+    }
+}
+
 /// Errors raised by trying to create a `StoreFactory`
 #[derive(Debug)]
 pub struct StoreFactoryCreationError(pub String);
@@ -91,6 +154,30 @@ impl std::fmt::Display for StoreFactoryCreationError {
     }
 }
 
+/// Errors raised by trying to run the database migrations of a `StoreFactory`'s stores
+#[derive(Debug)]
+pub struct StoreMigrationError(pub String);
+
+impl std::error::Error for StoreMigrationError {}
+
+impl std::fmt::Display for StoreMigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Unable to run store migrations: {}", self.0)
+    }
+}
+
+/// Errors raised by trying to connect to the storage backing a `StoreFactory`
+#[derive(Debug)]
+pub struct StoreConnectionError(pub String);
+
+impl std::error::Error for StoreConnectionError {}
+
+impl std::fmt::Display for StoreConnectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Unable to connect to store: {}", self.0)
+    }
+}
+
 /// The possible connection types and identifiers for a `StoreFactory`
 pub enum ConnectionUri {
     Memory,
@@ -12,12 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "admin-service-event-store")]
+use std::time::SystemTime;
+
 use diesel::{
     r2d2::{ConnectionManager, Pool},
     sqlite::SqliteConnection,
 };
 
-use super::StoreFactory;
+#[cfg(feature = "admin-service-event-store")]
+use crate::{
+    admin::service::EventEntry,
+    storage::sets::{
+        diesel::{DieselDurableOrderedSet, RetentionPolicy},
+        DurableOrderedSet,
+    },
+};
+
+use super::{StoreFactory, StoreMigrationError};
 
 /// A `StoryFactory` backed by a SQLite database.
 pub struct SqliteStoreFactory {
@@ -31,6 +43,25 @@ impl SqliteStoreFactory {
 }
 
 impl StoreFactory for SqliteStoreFactory {
+    #[cfg(feature = "admin-service-store")]
+    fn get_admin_service_store(&self) -> Option<Box<dyn crate::admin::store::AdminServiceStore>> {
+        Some(Box::new(
+            crate::admin::store::diesel::DieselAdminServiceStore::new(self.pool.clone()),
+        ))
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    fn get_admin_event_store(
+        &self,
+        set_name: &str,
+        retention_policy: RetentionPolicy,
+    ) -> Option<Box<dyn DurableOrderedSet<EventEntry, SystemTime>>> {
+        Some(Box::new(
+            DieselDurableOrderedSet::<_, EventEntry, SystemTime>::new(self.pool.clone(), set_name)
+                .with_retention_policy(retention_policy),
+        ))
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn get_biome_credentials_store(&self) -> Box<dyn crate::biome::CredentialsStore> {
         Box::new(crate::biome::DieselCredentialsStore::new(self.pool.clone()))
@@ -41,6 +72,18 @@ impl StoreFactory for SqliteStoreFactory {
         Box::new(crate::biome::DieselKeyStore::new(self.pool.clone()))
     }
 
+    #[cfg(feature = "biome-notifications")]
+    fn get_biome_notification_store(&self) -> Box<dyn crate::biome::NotificationStore> {
+        Box::new(crate::biome::DieselNotificationStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "oauth")]
+    fn get_biome_oauth_user_store(&self) -> Box<dyn crate::biome::OAuthUserStore> {
+        Box::new(crate::biome::DieselOAuthUserStore::new(self.pool.clone()))
+    }
+
     #[cfg(feature = "biome-credentials")]
     fn get_biome_refresh_token_store(&self) -> Box<dyn crate::biome::RefreshTokenStore> {
         Box::new(crate::biome::DieselRefreshTokenStore::new(
@@ -48,7 +91,37 @@ impl StoreFactory for SqliteStoreFactory {
         ))
     }
 
+    #[cfg(feature = "biome")]
     fn get_biome_user_store(&self) -> Box<dyn crate::biome::UserStore> {
         Box::new(crate::biome::DieselUserStore::new(self.pool.clone()))
     }
+
+    #[cfg(feature = "registry-database")]
+    fn get_registry_store(&self) -> Option<Box<dyn crate::registry::RwRegistry>> {
+        Some(Box::new(crate::registry::DieselRegistry::new(
+            self.pool.clone(),
+        )))
+    }
+
+    fn run_migrations(&self) -> Result<(), StoreMigrationError> {
+        #[cfg(any(
+            feature = "admin-service-store",
+            feature = "durable-set-database",
+            feature = "registry-database"
+        ))]
+        let connection = self
+            .pool
+            .get()
+            .map_err(|err| StoreMigrationError(err.to_string()))?;
+        #[cfg(feature = "admin-service-store")]
+        crate::admin::store::diesel::migrations::run_sqlite_migrations(&connection)
+            .map_err(|err| StoreMigrationError(err.to_string()))?;
+        #[cfg(feature = "durable-set-database")]
+        crate::storage::sets::diesel::migrations::run_sqlite_migrations(&connection)
+            .map_err(|err| StoreMigrationError(err.to_string()))?;
+        #[cfg(feature = "registry-database")]
+        crate::registry::run_sqlite_migrations(&connection)
+            .map_err(|err| StoreMigrationError(err.to_string()))?;
+        Ok(())
+    }
 }
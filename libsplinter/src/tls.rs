@@ -0,0 +1,99 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for clients of a Splinter REST API that is served over HTTPS and requires client
+//! certificates.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::PKey;
+use openssl::x509::X509;
+
+/// Loads a client identity from the given PEM certificate and private key files.
+///
+/// The identity is returned as a DER-encoded PKCS #12 archive with an empty password, which is the
+/// format that TLS backends such as native-tls accept.
+///
+/// # Arguments
+///
+/// * `cert` - The path to the client certificate
+/// * `key` - The path to the client certificate's private key
+pub fn load_client_identity(cert: &Path, key: &Path) -> Result<Vec<u8>, ClientIdentityError> {
+    let cert = X509::from_pem(&read_file(cert)?).map_err(|err| {
+        ClientIdentityError::new(
+            &format!("invalid client certificate {}", cert.display()),
+            err,
+        )
+    })?;
+    let key = PKey::private_key_from_pem(&read_file(key)?).map_err(|err| {
+        ClientIdentityError::new(&format!("invalid client key {}", key.display()), err)
+    })?;
+
+    Pkcs12::builder()
+        .build("", "splinter-client", &key, &cert)
+        .and_then(|pkcs12| pkcs12.to_der())
+        .map_err(|err| ClientIdentityError::new("failed to convert client identity", err))
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, ClientIdentityError> {
+    fs::read(path)
+        .map_err(|err| ClientIdentityError::new(&format!("failed to read {}", path.display()), err))
+}
+
+/// An error that occurs when a client identity cannot be loaded
+#[derive(Debug)]
+pub struct ClientIdentityError {
+    context: String,
+    source: Box<dyn Error>,
+}
+
+impl ClientIdentityError {
+    fn new<E: Error + 'static>(context: &str, source: E) -> Self {
+        Self {
+            context: context.into(),
+            source: Box::new(source),
+        }
+    }
+}
+
+impl Error for ClientIdentityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+impl fmt::Display for ClientIdentityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that loading a client identity fails if the certificate or key cannot be read.
+    #[test]
+    fn missing_files() {
+        assert!(load_client_identity(
+            Path::new("/nonexistent/client.crt"),
+            Path::new("/nonexistent/client.key")
+        )
+        .is_err());
+    }
+}
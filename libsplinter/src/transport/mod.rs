@@ -39,6 +39,8 @@ mod error;
 pub mod inproc;
 pub(crate) mod matrix;
 pub mod multi;
+#[cfg(feature = "quic-transport")]
+pub mod quic;
 #[deprecated(since = "0.3.14", note = "please use splinter::transport::socket")]
 pub mod raw;
 pub mod socket;
@@ -0,0 +1,398 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, VecDeque};
+use std::net::{SocketAddr, UdpSocket};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use byteorder::{BigEndian, ByteOrder};
+use mio::{Evented, Poll, PollOpt, Ready, Registration, SetReadiness, Token};
+
+use crate::transport::{Connection, DisconnectError, RecvError, SendError};
+
+/// The maximum size of the UDP datagrams sent by this transport.
+pub(super) const MAX_DATAGRAM_SIZE: usize = 1350;
+
+// Each message is prefixed with its length as a 4-byte, big-endian integer.
+const FRAME_HEADER_LEN: usize = 4;
+
+// The maximum length of a received message; a peer that declares a longer message is disconnected
+// rather than buffering it.
+const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+// The number of received messages that may be waiting for `recv` before the streams are no longer
+// read. The unread data then holds back the peer through flow control.
+const MAX_RECEIVED_MESSAGES: usize = 1024;
+
+// The amount of unsent data that may be buffered before `send` returns `WouldBlock`.
+const MAX_PENDING_SEND: usize = 4 * 1024 * 1024;
+
+// The upper bound on the amount of time a driver waits for datagrams before checking timers.
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Stream 0 is the first client-initiated bidirectional stream and stream 1 is the first
+// server-initiated bidirectional stream.
+const CLIENT_STREAM_ID: u64 = 0;
+const SERVER_STREAM_ID: u64 = 1;
+
+// The application error codes sent when a connection is closed locally.
+const NO_ERROR: u64 = 0;
+const MESSAGE_TOO_LARGE: u64 = 1;
+
+struct QuicState {
+    conn: Pin<Box<quiche::Connection>>,
+    peer_addr: SocketAddr,
+    send_stream_id: u64,
+    pending_send: Vec<u8>,
+    recv_buffers: HashMap<u64, Vec<u8>>,
+    messages: VecDeque<Vec<u8>>,
+}
+
+impl QuicState {
+    /// Writes as much of the pending outgoing data as flow control allows to the send stream.
+    fn write_pending(&mut self) {
+        if self.pending_send.is_empty() || !self.conn.is_established() {
+            return;
+        }
+
+        match self
+            .conn
+            .stream_send(self.send_stream_id, &self.pending_send, false)
+        {
+            Ok(written) => {
+                self.pending_send.drain(..written);
+            }
+            Err(quiche::Error::Done) => (),
+            Err(err) => {
+                error!("Unable to write to QUIC stream: {}", err);
+                self.close();
+            }
+        }
+    }
+
+    /// Reads the readable streams, splitting the received data into messages.
+    ///
+    /// Reading stops while `MAX_RECEIVED_MESSAGES` messages are waiting for `recv`, and the
+    /// connection is closed if the peer declares a message longer than `MAX_MESSAGE_LEN`.
+    fn read_streams(&mut self) {
+        let mut buf = [0; 65535];
+        let readable: Vec<u64> = self.conn.readable().collect();
+        for stream_id in readable {
+            while self.messages.len() < MAX_RECEIVED_MESSAGES {
+                match self.conn.stream_recv(stream_id, &mut buf) {
+                    Ok((len, _fin)) => self
+                        .recv_buffers
+                        .entry(stream_id)
+                        .or_insert_with(Vec::new)
+                        .extend_from_slice(&buf[..len]),
+                    Err(quiche::Error::Done) => break,
+                    Err(err) => {
+                        error!("Unable to read from QUIC stream {}: {}", stream_id, err);
+                        break;
+                    }
+                }
+
+                if let Err(len) = self.split_messages(stream_id) {
+                    error!(
+                        "QUIC peer {} sent a message of {} bytes, which exceeds the maximum of {} \
+                         bytes; closing connection",
+                        self.peer_addr, len, MAX_MESSAGE_LEN
+                    );
+                    self.recv_buffers.clear();
+                    self.close_with_error(MESSAGE_TOO_LARGE, b"message too large");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Moves the complete messages in the stream's buffer to the received messages. Returns the
+    /// declared length of the next message as an error if it exceeds `MAX_MESSAGE_LEN`.
+    fn split_messages(&mut self, stream_id: u64) -> Result<(), usize> {
+        if let Some(buffer) = self.recv_buffers.get_mut(&stream_id) {
+            while buffer.len() >= FRAME_HEADER_LEN {
+                let len = BigEndian::read_u32(&buffer[..FRAME_HEADER_LEN]) as usize;
+                if len > MAX_MESSAGE_LEN {
+                    return Err(len);
+                }
+                if buffer.len() < FRAME_HEADER_LEN + len {
+                    break;
+                }
+                let message = buffer[FRAME_HEADER_LEN..FRAME_HEADER_LEN + len].to_vec();
+                buffer.drain(..FRAME_HEADER_LEN + len);
+                self.messages.push_back(message);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.close_with_error(NO_ERROR, b"")
+    }
+
+    fn close_with_error(&mut self, error_code: u64, reason: &[u8]) {
+        match self.conn.close(true, error_code, reason) {
+            Ok(()) | Err(quiche::Error::Done) => (),
+            Err(err) => error!("Unable to close QUIC connection: {}", err),
+        }
+    }
+}
+
+/// The state of a QUIC connection, shared between the `QuicConnection` and the thread which
+/// receives datagrams on its behalf.
+pub(super) struct QuicConnectionShared {
+    state: Mutex<QuicState>,
+    socket: Arc<UdpSocket>,
+    set_readiness: SetReadiness,
+}
+
+impl QuicConnectionShared {
+    /// Processes a datagram received from the given address.
+    ///
+    /// The address of the peer is updated to the source of the datagram, which allows the
+    /// connection to survive a change of the peer's address.
+    pub fn recv_datagram(&self, buf: &mut [u8], from: SocketAddr) -> Result<(), quiche::Error> {
+        let mut state = mutex_lock_unwrap!(self.state);
+        let result = state.conn.recv(buf).map(|_| ());
+        if result.is_ok() {
+            state.peer_addr = from;
+        }
+
+        state.read_streams();
+        state.write_pending();
+        self.flush_state(&mut state);
+        self.update_readiness(&state);
+
+        result
+    }
+
+    /// Processes any expired timers, such as retransmission and idle timers.
+    pub fn on_timeout(&self) {
+        let mut state = mutex_lock_unwrap!(self.state);
+        state.conn.on_timeout();
+        state.write_pending();
+        self.flush_state(&mut state);
+        self.update_readiness(&state);
+    }
+
+    /// Sends all packets that are ready to be sent.
+    pub fn flush(&self) {
+        let mut state = mutex_lock_unwrap!(self.state);
+        self.flush_state(&mut state);
+    }
+
+    /// Closes the connection, notifying the peer.
+    pub fn close(&self) {
+        let mut state = mutex_lock_unwrap!(self.state);
+        state.close();
+        self.flush_state(&mut state);
+        self.update_readiness(&state);
+    }
+
+    /// Returns the amount of time to wait for datagrams before calling `on_timeout`.
+    pub fn poll_interval(&self) -> Duration {
+        let state = mutex_lock_unwrap!(self.state);
+        state
+            .conn
+            .timeout()
+            .map(|timeout| timeout.min(MAX_POLL_INTERVAL))
+            .unwrap_or(MAX_POLL_INTERVAL)
+            // A zero duration is not a valid socket read timeout.
+            .max(Duration::from_millis(1))
+    }
+
+    pub fn is_established(&self) -> bool {
+        mutex_lock_unwrap!(self.state).conn.is_established()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        mutex_lock_unwrap!(self.state).conn.is_closed()
+    }
+
+    fn flush_state(&self, state: &mut QuicState) {
+        let mut out = [0; MAX_DATAGRAM_SIZE];
+        loop {
+            match state.conn.send(&mut out) {
+                Ok(len) => {
+                    if let Err(err) = self.socket.send_to(&out[..len], state.peer_addr) {
+                        debug!("Unable to send QUIC packet to {}: {}", state.peer_addr, err);
+                        break;
+                    }
+                }
+                Err(quiche::Error::Done) => break,
+                Err(err) => {
+                    error!("Unable to create QUIC packet: {}", err);
+                    state.close();
+                    break;
+                }
+            }
+        }
+    }
+
+    fn update_readiness(&self, state: &QuicState) {
+        let mut ready = Ready::empty();
+        // A closed connection is reported as readable so that `recv` reports the disconnect.
+        if !state.messages.is_empty() || state.conn.is_closed() {
+            ready |= Ready::readable();
+        }
+        if state.pending_send.len() < MAX_PENDING_SEND {
+            ready |= Ready::writable();
+        }
+
+        if let Err(err) = self.set_readiness.set_readiness(ready) {
+            error!("Unable to set QUIC connection readiness: {}", err);
+        }
+    }
+}
+
+pub(super) struct QuicConnection {
+    shared: Arc<QuicConnectionShared>,
+    registration: Registration,
+    remote_endpoint: String,
+    local_endpoint: String,
+}
+
+impl QuicConnection {
+    pub fn new(
+        conn: Pin<Box<quiche::Connection>>,
+        is_server: bool,
+        peer_addr: SocketAddr,
+        socket: Arc<UdpSocket>,
+        remote_endpoint: String,
+        local_endpoint: String,
+    ) -> Self {
+        let send_stream_id = if is_server {
+            SERVER_STREAM_ID
+        } else {
+            CLIENT_STREAM_ID
+        };
+        let (registration, set_readiness) = Registration::new2();
+
+        QuicConnection {
+            shared: Arc::new(QuicConnectionShared {
+                state: Mutex::new(QuicState {
+                    conn,
+                    peer_addr,
+                    send_stream_id,
+                    pending_send: vec![],
+                    recv_buffers: HashMap::new(),
+                    messages: VecDeque::new(),
+                }),
+                socket,
+                set_readiness,
+            }),
+            registration,
+            remote_endpoint,
+            local_endpoint,
+        }
+    }
+
+    pub fn shared(&self) -> Arc<QuicConnectionShared> {
+        self.shared.clone()
+    }
+}
+
+impl Connection for QuicConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        let mut state = mutex_lock_unwrap!(self.shared.state);
+        if state.conn.is_closed() {
+            return Err(SendError::Disconnected);
+        }
+        if state.pending_send.len() >= MAX_PENDING_SEND {
+            return Err(SendError::WouldBlock);
+        }
+
+        let mut header = [0; FRAME_HEADER_LEN];
+        BigEndian::write_u32(&mut header, message.len() as u32);
+        state.pending_send.extend_from_slice(&header);
+        state.pending_send.extend_from_slice(message);
+
+        state.write_pending();
+        self.shared.flush_state(&mut state);
+        self.shared.update_readiness(&state);
+
+        Ok(())
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        let mut state = mutex_lock_unwrap!(self.shared.state);
+        let throttled = state.messages.len() >= MAX_RECEIVED_MESSAGES;
+        let result = match state.messages.pop_front() {
+            Some(message) => Ok(message),
+            None if state.conn.is_closed() => Err(RecvError::Disconnected),
+            None => Err(RecvError::WouldBlock),
+        };
+        // Resume reading the streams that were left unread while the received messages were at
+        // their limit, and send the peer the resulting flow control updates
+        if throttled && state.messages.len() < MAX_RECEIVED_MESSAGES {
+            state.read_streams();
+            self.shared.flush_state(&mut state);
+        }
+        self.shared.update_readiness(&state);
+
+        result
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.remote_endpoint.clone()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        self.shared.close();
+        Ok(())
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        self
+    }
+}
+
+impl Drop for QuicConnection {
+    fn drop(&mut self) {
+        self.shared.close();
+    }
+}
+
+impl Evented for QuicConnection {
+    fn register(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> std::io::Result<()> {
+        self.registration.register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> std::io::Result<()> {
+        self.registration.reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &Poll) -> std::io::Result<()> {
+        self.registration.deregister(poll)
+    }
+}
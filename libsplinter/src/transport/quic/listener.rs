@@ -0,0 +1,248 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::transport::{AcceptError, Connection, ListenError, Listener};
+
+use super::connection::{QuicConnection, QuicConnectionShared, MAX_DATAGRAM_SIZE};
+use super::transport::{is_timeout, new_connection_id, QUIC_PROTOCOL_PREFIX};
+
+// The upper bound on the amount of time the listener waits for datagrams before checking the
+// timers of its connections.
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// The maximum number of connections that may be completing the handshake at once. New connections
+// are refused above this limit, so that a flood of Initial packets cannot exhaust the listener's
+// memory.
+const MAX_HANDSHAKING_CONNECTIONS: usize = 128;
+
+/// A `Listener` for QUIC connections.
+///
+/// All connections accepted by a listener share its UDP socket. A background thread receives the
+/// datagrams on the socket and routes them to the connection with the matching connection ID.
+/// Connections accepted by a listener stop receiving datagrams once the listener is dropped.
+pub(super) struct QuicListener {
+    local_endpoint: String,
+    receiver: Receiver<QuicConnection>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl QuicListener {
+    pub fn new(
+        socket: UdpSocket,
+        local_endpoint: String,
+        server_config: quiche::Config,
+    ) -> Result<Self, ListenError> {
+        let (sender, receiver) = channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let mut dispatcher = QuicDispatcher {
+            socket: Arc::new(socket),
+            server_config,
+            sender,
+            shutdown: shutdown.clone(),
+            connections: HashMap::new(),
+            handshaking: HashMap::new(),
+        };
+
+        thread::Builder::new()
+            .name(format!("QuicListener {}", local_endpoint))
+            .spawn(move || dispatcher.run())
+            .map_err(|err| {
+                ListenError::IoError("Unable to start QUIC listener thread".into(), err)
+            })?;
+
+        Ok(QuicListener {
+            local_endpoint,
+            receiver,
+            shutdown,
+        })
+    }
+}
+
+impl Listener for QuicListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        self.receiver
+            .recv()
+            .map(|connection| Box::new(connection) as Box<dyn Connection>)
+            .map_err(|_| AcceptError::ProtocolError("QUIC listener has shut down".into()))
+    }
+
+    fn endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+}
+
+impl Drop for QuicListener {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+    }
+}
+
+struct QuicDispatcher {
+    socket: Arc<UdpSocket>,
+    server_config: quiche::Config,
+    sender: Sender<QuicConnection>,
+    shutdown: Arc<AtomicBool>,
+    // All connections, keyed by each of the connection IDs a peer may use to address them.
+    connections: HashMap<Vec<u8>, Arc<QuicConnectionShared>>,
+    // Connections which have not yet completed the handshake, keyed by local connection ID.
+    handshaking: HashMap<Vec<u8>, QuicConnection>,
+}
+
+impl QuicDispatcher {
+    fn run(&mut self) {
+        let mut buf = [0; 65535];
+        while !self.shutdown.load(Ordering::SeqCst) {
+            let poll_interval = self
+                .connections
+                .values()
+                .map(|shared| shared.poll_interval())
+                .min()
+                .unwrap_or(MAX_POLL_INTERVAL)
+                .min(MAX_POLL_INTERVAL);
+            if let Err(err) = self.socket.set_read_timeout(Some(poll_interval)) {
+                error!("Unable to set QUIC socket read timeout: {}", err);
+                break;
+            }
+
+            match self.socket.recv_from(&mut buf) {
+                Ok((len, from)) => self.handle_datagram(&mut buf[..len], from),
+                Err(ref err) if is_timeout(err) => (),
+                Err(err) => {
+                    error!("Unable to receive on QUIC socket: {}", err);
+                    break;
+                }
+            }
+
+            self.process_connections();
+        }
+
+        // Dropping the connections that were never accepted closes them.
+        self.handshaking.clear();
+    }
+
+    fn handle_datagram(&mut self, packet: &mut [u8], from: std::net::SocketAddr) {
+        let header = match quiche::Header::from_slice(packet, quiche::MAX_CONN_ID_LEN) {
+            Ok(header) => header,
+            Err(err) => {
+                debug!("Received invalid QUIC packet from {}: {}", from, err);
+                return;
+            }
+        };
+
+        let shared = match self.connections.get(&header.dcid) {
+            Some(shared) => shared.clone(),
+            None => {
+                if header.ty != quiche::Type::Initial {
+                    debug!("Dropping QUIC packet for unknown connection from {}", from);
+                    return;
+                }
+
+                if !quiche::version_is_supported(header.version) {
+                    let mut out = [0; MAX_DATAGRAM_SIZE];
+                    match quiche::negotiate_version(&header.scid, &header.dcid, &mut out) {
+                        Ok(len) => {
+                            if let Err(err) = self.socket.send_to(&out[..len], from) {
+                                debug!("Unable to send QUIC version negotiation: {}", err);
+                            }
+                        }
+                        Err(err) => debug!("Unable to create QUIC version negotiation: {}", err),
+                    }
+                    return;
+                }
+
+                if self.handshaking.len() >= MAX_HANDSHAKING_CONNECTIONS {
+                    debug!(
+                        "Dropping QUIC Initial packet from {}: {} connections are already \
+                         handshaking",
+                        from, MAX_HANDSHAKING_CONNECTIONS
+                    );
+                    return;
+                }
+
+                let scid = new_connection_id();
+                let conn = match quiche::accept(&scid, None, &mut self.server_config) {
+                    Ok(conn) => conn,
+                    Err(err) => {
+                        error!("Unable to accept QUIC connection from {}: {}", from, err);
+                        return;
+                    }
+                };
+
+                let local_endpoint = match self.socket.local_addr() {
+                    Ok(addr) => format!("{}{}", QUIC_PROTOCOL_PREFIX, addr),
+                    Err(err) => {
+                        error!("Unable to get local address: {}", err);
+                        return;
+                    }
+                };
+                let connection = QuicConnection::new(
+                    conn,
+                    true,
+                    from,
+                    self.socket.clone(),
+                    format!("{}{}", QUIC_PROTOCOL_PREFIX, from),
+                    local_endpoint,
+                );
+                let shared = connection.shared();
+
+                // The peer continues to use its original destination connection ID until it
+                // receives the first response, so the connection is addressable by both.
+                self.connections.insert(scid.to_vec(), shared.clone());
+                self.connections.insert(header.dcid.clone(), shared.clone());
+                self.handshaking.insert(scid.to_vec(), connection);
+
+                shared
+            }
+        };
+
+        if let Err(err) = shared.recv_datagram(packet, from) {
+            debug!("Failed to process QUIC packet from {}: {}", from, err);
+        }
+    }
+
+    fn process_connections(&mut self) {
+        for shared in self.connections.values() {
+            shared.on_timeout();
+        }
+
+        self.connections.retain(|_, shared| !shared.is_closed());
+
+        let established: Vec<Vec<u8>> = self
+            .handshaking
+            .iter()
+            .filter(|(_, connection)| connection.shared().is_established())
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in established {
+            if let Some(connection) = self.handshaking.remove(&id) {
+                if self.sender.send(connection).is_err() {
+                    debug!("QUIC listener dropped before connection was accepted");
+                }
+            }
+        }
+
+        let connections = &self.connections;
+        self.handshaking
+            .retain(|id, _| connections.contains_key(id));
+    }
+}
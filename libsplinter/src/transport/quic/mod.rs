@@ -0,0 +1,141 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A QUIC-based transport implementation.
+//!
+//! The `splinter::transport::quic` module provides a `Transport` implementation on top of QUIC,
+//! using UDP datagrams as the underlying socket. Both sides of a connection are authenticated
+//! with mutual TLS, using the same certificates and keys as the TLS transport.
+//!
+//! Each side of a connection sends its messages on a single, locally-initiated bidirectional
+//! stream, so messages are received in the order in which they were sent.
+
+mod connection;
+mod listener;
+mod transport;
+
+pub use transport::{QuicInitError, QuicTransport};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::transport::tests;
+    use crate::transport::tls::tests::{make_ca_cert, make_ca_signed_cert};
+    use crate::transport::tls::{TlsConfig, TlsConfigBuilder};
+    use crate::transport::Transport;
+
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use tempdir::TempDir;
+
+    fn write_file(mut temp_dir: PathBuf, file_name: &str, bytes: &[u8]) -> String {
+        temp_dir.push(file_name);
+        let path = temp_dir.to_str().unwrap().to_string();
+        let mut file = File::create(path.to_string()).unwrap();
+        file.write_all(bytes).unwrap();
+
+        path
+    }
+
+    fn create_test_tls_config(temp_dir: &TempDir, insecure: bool) -> TlsConfig {
+        let mut builder = TlsConfigBuilder::new();
+
+        // Generate Certificate Authority keys and certificate
+        let (ca_key, ca_cert) = make_ca_cert();
+
+        let temp_dir_path = temp_dir.path();
+
+        if !insecure {
+            let ca_path_file = write_file(
+                temp_dir_path.to_path_buf(),
+                "ca.cert",
+                &ca_cert.to_pem().unwrap(),
+            );
+            builder = builder.with_ca_certs_file(ca_path_file);
+        }
+
+        // Generate client and server keys and certificates
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let client_cert_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "client.cert",
+            &client_cert.to_pem().unwrap(),
+        );
+
+        let client_key_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "client.key",
+            &client_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+
+        let server_cert_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "server.cert",
+            &server_cert.to_pem().unwrap(),
+        );
+
+        let server_key_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "server.key",
+            &server_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+
+        builder
+            .with_server_cert_file(server_cert_file)
+            .with_server_private_key_file(server_key_file)
+            .with_client_cert_file(client_cert_file)
+            .with_client_private_key_file(client_key_file)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_quic_accepts() {
+        let temp_dir = TempDir::new("test-quic-accepts").unwrap();
+        let config = create_test_tls_config(&temp_dir, false);
+        let transport = QuicTransport::new(&config).unwrap();
+        assert!(transport.accepts("quic://127.0.0.1:18090"));
+        assert!(transport.accepts("quic://somewhere.example.com:18090"));
+        assert!(!transport.accepts("tcps://127.0.0.1:18090"));
+        assert!(!transport.accepts("127.0.0.1:18090"));
+    }
+
+    #[test]
+    fn test_quic_transport() {
+        let temp_dir = TempDir::new("test-quic-transport").unwrap();
+        let config = create_test_tls_config(&temp_dir, false);
+        let transport = QuicTransport::new(&config).unwrap();
+        tests::test_transport(transport, "quic://127.0.0.1:0");
+    }
+
+    #[test]
+    fn test_quic_transport_insecure() {
+        let temp_dir = TempDir::new("test-quic-transport-insecure").unwrap();
+        let config = create_test_tls_config(&temp_dir, true);
+        let transport = QuicTransport::new(&config).unwrap();
+        tests::test_transport(transport, "quic://127.0.0.1:0");
+    }
+
+    #[test]
+    fn test_quic_poll() {
+        let temp_dir = TempDir::new("test-quic-poll").unwrap();
+        let config = create_test_tls_config(&temp_dir, false);
+        let transport = QuicTransport::new(&config).unwrap();
+        tests::test_poll(transport, "quic://127.0.0.1:0");
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use url::{ParseError, Url};
+
+use crate::transport::tls::TlsConfig;
+use crate::transport::{
+    AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
+    SendError, Transport,
+};
+
+use super::connection::{QuicConnection, QuicConnectionShared, MAX_DATAGRAM_SIZE};
+use super::listener::QuicListener;
+
+pub(super) const QUIC_PROTOCOL_PREFIX: &str = "quic://";
+
+// The ALPN protocol identifier, as a length-prefixed list with a single entry.
+const APPLICATION_PROTOCOL: &[u8] = b"\x08splinter";
+
+// The amount of time a connection may be idle before it is closed. Splinter peers send
+// heartbeats well within this window.
+const MAX_IDLE_TIMEOUT_MILLIS: u64 = 30_000;
+
+// The amount of time allowed for the QUIC handshake to complete when connecting.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+const INITIAL_MAX_DATA: u64 = 16 * 1024 * 1024;
+const INITIAL_MAX_STREAM_DATA: u64 = 8 * 1024 * 1024;
+const INITIAL_MAX_STREAMS_BIDI: u64 = 4;
+
+/// A QUIC-based `Transport`.
+///
+/// Supports endpoints of the format `quic://ip_or_host:port`.
+///
+/// Connections are authenticated with mutual TLS, configured using the same `TlsConfig` as the
+/// other TLS-based transports. If the `TlsConfig` does not include a CA certificates file, the
+/// peer's certificate is not verified.
+///
+/// # Examples
+///
+/// To connect to the a remote endpoint, send a message, and receive a reply message:
+///
+/// ```rust,no_run
+/// use splinter::transport::Transport as _;
+/// use splinter::transport::quic::QuicTransport;
+/// use splinter::transport::tls::TlsConfigBuilder;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let config = TlsConfigBuilder::new()
+///         .with_ca_certs_file("certs/ca.pem".into())
+///         .with_client_cert_file("certs/client.crt".into())
+///         .with_client_private_key_file("certs/client.key".into())
+///         .with_server_cert_file("certs/server.crt".into())
+///         .with_server_private_key_file("certs/server.key".into())
+///         .build()?;
+///
+///     let mut transport = QuicTransport::new(&config)?;
+///
+///     // Connect to a remote endpoint starting wtih `quic://`.
+///     let mut connection = transport.connect("quic://127.0.0.1:5555")?;
+///
+///     // Send some bytes
+///     connection.send(b"hello world")?;
+///
+///     // Receive a response
+///     let msg = connection.recv()?;
+///
+///     // Disconnect
+///     connection.disconnect()?;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct QuicTransport {
+    tls_config: TlsConfig,
+    client_config: quiche::Config,
+}
+
+impl QuicTransport {
+    /// Creates a new `QuicTransport` from the given TLS configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `QuicInitError` if the certificates or keys could not be loaded.
+    pub fn new(config: &TlsConfig) -> Result<Self, QuicInitError> {
+        let client_config = build_client_config(config)?;
+        // Build a server configuration to validate the server certificate and key up front,
+        // rather than on the first call to listen.
+        build_server_config(config)?;
+
+        Ok(QuicTransport {
+            tls_config: config.clone(),
+            client_config,
+        })
+    }
+}
+
+impl Transport for QuicTransport {
+    fn accepts(&self, address: &str) -> bool {
+        address.starts_with(QUIC_PROTOCOL_PREFIX)
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        if !self.accepts(endpoint) {
+            return Err(ConnectError::ProtocolError(format!(
+                "Invalid protocol: {}",
+                endpoint
+            )));
+        }
+
+        let address = &endpoint[QUIC_PROTOCOL_PREFIX.len()..];
+        let remote_addr = address.to_socket_addrs()?.next().ok_or_else(|| {
+            ConnectError::ParseError(format!("Unable to resolve address: {}", address))
+        })?;
+
+        let bind_addr = match remote_addr {
+            SocketAddr::V4(_) => "0.0.0.0:0",
+            SocketAddr::V6(_) => "[::]:0",
+        };
+        let socket = Arc::new(UdpSocket::bind(bind_addr)?);
+
+        let server_name = endpoint_to_server_name(address)?;
+        let scid = new_connection_id();
+        let conn = quiche::connect(Some(&server_name), &scid, &mut self.client_config)?;
+
+        let remote_endpoint = format!("{}{}", QUIC_PROTOCOL_PREFIX, remote_addr);
+        let local_endpoint = format!("{}{}", QUIC_PROTOCOL_PREFIX, socket.local_addr()?);
+
+        let connection = QuicConnection::new(
+            conn,
+            false,
+            remote_addr,
+            socket.clone(),
+            remote_endpoint,
+            local_endpoint,
+        );
+        let shared = connection.shared();
+
+        // Complete the handshake before handing the connection to the caller.
+        let mut buf = [0; 65535];
+        let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+        shared.flush();
+        while !shared.is_established() {
+            if shared.is_closed() {
+                return Err(ConnectError::ProtocolError(format!(
+                    "QUIC handshake with {} failed",
+                    endpoint
+                )));
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(ConnectError::ProtocolError(format!(
+                    "QUIC handshake with {} timed out",
+                    endpoint
+                )));
+            }
+
+            socket.set_read_timeout(Some(shared.poll_interval().min(deadline - now)))?;
+            match socket.recv_from(&mut buf) {
+                Ok((len, from)) => {
+                    if let Err(err) = shared.recv_datagram(&mut buf[..len], from) {
+                        debug!("Failed to process QUIC packet from {}: {}", from, err);
+                    }
+                }
+                Err(ref err) if is_timeout(err) => (),
+                Err(err) => return Err(ConnectError::from(err)),
+            }
+            shared.on_timeout();
+        }
+
+        thread::Builder::new()
+            .name(format!("QuicConnection {}", endpoint))
+            .spawn(move || drive_connection(&shared, &socket))?;
+
+        Ok(Box::new(connection))
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        if !self.accepts(bind) {
+            return Err(ListenError::ProtocolError(format!(
+                "Invalid protocol: {}",
+                bind
+            )));
+        }
+
+        let address = &bind[QUIC_PROTOCOL_PREFIX.len()..];
+        let socket = UdpSocket::bind(address)
+            .map_err(|err| ListenError::IoError(format!("Failed to bind to {}", address), err))?;
+        let local_endpoint = format!(
+            "{}{}",
+            QUIC_PROTOCOL_PREFIX,
+            socket
+                .local_addr()
+                .map_err(|err| ListenError::IoError("Failed to get local address".into(), err))?
+        );
+
+        let server_config = build_server_config(&self.tls_config)
+            .map_err(|err| ListenError::ProtocolError(err.to_string()))?;
+
+        Ok(Box::new(QuicListener::new(
+            socket,
+            local_endpoint,
+            server_config,
+        )?))
+    }
+}
+
+/// Receives and processes datagrams for a client connection until the connection is closed.
+fn drive_connection(shared: &QuicConnectionShared, socket: &UdpSocket) {
+    let mut buf = [0; 65535];
+    while !shared.is_closed() {
+        if let Err(err) = socket.set_read_timeout(Some(shared.poll_interval())) {
+            error!("Unable to set QUIC socket read timeout: {}", err);
+            break;
+        }
+
+        match socket.recv_from(&mut buf) {
+            Ok((len, from)) => {
+                if let Err(err) = shared.recv_datagram(&mut buf[..len], from) {
+                    debug!("Failed to process QUIC packet from {}: {}", from, err);
+                }
+            }
+            Err(ref err) if is_timeout(err) => (),
+            Err(err) => {
+                error!("Unable to receive on QUIC socket: {}", err);
+                break;
+            }
+        }
+        shared.on_timeout();
+    }
+
+    shared.close();
+}
+
+/// Generates a random connection ID of the maximum length supported by QUIC.
+pub(super) fn new_connection_id() -> [u8; quiche::MAX_CONN_ID_LEN] {
+    let mut id = [0; quiche::MAX_CONN_ID_LEN];
+    rand::thread_rng().fill(&mut id[..]);
+    id
+}
+
+pub(super) fn is_timeout(err: &std::io::Error) -> bool {
+    match err.kind() {
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => true,
+        _ => false,
+    }
+}
+
+fn endpoint_to_server_name(endpoint: &str) -> Result<String, ParseError> {
+    let mut address = String::from("https://");
+    address.push_str(endpoint);
+    let url = Url::parse(&address)?;
+    let server_name = match url.host_str() {
+        Some(host) if host.parse::<IpAddr>().is_ok() => "localhost",
+        Some(host) if host.starts_with('[') => "localhost",
+        Some(host) => host,
+        None => "localhost",
+    };
+    Ok(String::from(server_name))
+}
+
+fn build_base_config(tls_config: &TlsConfig) -> Result<quiche::Config, quiche::Error> {
+    let mut config = quiche::Config::new(quiche::PROTOCOL_VERSION)?;
+
+    config.set_application_protos(APPLICATION_PROTOCOL)?;
+    config.set_max_idle_timeout(MAX_IDLE_TIMEOUT_MILLIS);
+    config.set_max_udp_payload_size(MAX_DATAGRAM_SIZE as u64);
+    config.set_initial_max_data(INITIAL_MAX_DATA);
+    config.set_initial_max_stream_data_bidi_local(INITIAL_MAX_STREAM_DATA);
+    config.set_initial_max_stream_data_bidi_remote(INITIAL_MAX_STREAM_DATA);
+    config.set_initial_max_streams_bidi(INITIAL_MAX_STREAMS_BIDI);
+
+    if let Some(ca_certs_file) = tls_config.ca_certs_file() {
+        config.load_verify_locations_from_file(ca_certs_file)?;
+        config.verify_peer(true);
+    } else {
+        config.verify_peer(false);
+    }
+
+    Ok(config)
+}
+
+fn build_client_config(tls_config: &TlsConfig) -> Result<quiche::Config, QuicInitError> {
+    let mut config = build_base_config(tls_config)?;
+
+    config
+        .load_cert_chain_from_pem_file(tls_config.client_cert_file())
+        .map_err(|err| {
+            QuicInitError::CertificateError(format!(
+                "Unable to load client certificate {}: {}",
+                tls_config.client_cert_file(),
+                err
+            ))
+        })?;
+    config
+        .load_priv_key_from_pem_file(tls_config.client_private_key_file())
+        .map_err(|err| {
+            QuicInitError::CertificateError(format!(
+                "Unable to load client private key {}: {}",
+                tls_config.client_private_key_file(),
+                err
+            ))
+        })?;
+
+    Ok(config)
+}
+
+fn build_server_config(tls_config: &TlsConfig) -> Result<quiche::Config, QuicInitError> {
+    let mut config = build_base_config(tls_config)?;
+
+    config
+        .load_cert_chain_from_pem_file(tls_config.server_cert_file())
+        .map_err(|err| {
+            QuicInitError::CertificateError(format!(
+                "Unable to load server certificate {}: {}",
+                tls_config.server_cert_file(),
+                err
+            ))
+        })?;
+    config
+        .load_priv_key_from_pem_file(tls_config.server_private_key_file())
+        .map_err(|err| {
+            QuicInitError::CertificateError(format!(
+                "Unable to load server private key {}: {}",
+                tls_config.server_private_key_file(),
+                err
+            ))
+        })?;
+
+    Ok(config)
+}
+
+impl From<quiche::Error> for ConnectError {
+    fn from(err: quiche::Error) -> Self {
+        ConnectError::ProtocolError(format!("QUIC error: {}", err))
+    }
+}
+
+impl From<quiche::Error> for AcceptError {
+    fn from(err: quiche::Error) -> Self {
+        AcceptError::ProtocolError(format!("QUIC error: {}", err))
+    }
+}
+
+impl From<quiche::Error> for SendError {
+    fn from(err: quiche::Error) -> Self {
+        SendError::ProtocolError(format!("QUIC error: {}", err))
+    }
+}
+
+impl From<quiche::Error> for RecvError {
+    fn from(err: quiche::Error) -> Self {
+        RecvError::ProtocolError(format!("QUIC error: {}", err))
+    }
+}
+
+impl From<quiche::Error> for DisconnectError {
+    fn from(err: quiche::Error) -> Self {
+        DisconnectError::ProtocolError(format!("QUIC error: {}", err))
+    }
+}
+
+/// Errors that may occur when creating a `QuicTransport`.
+#[derive(Debug)]
+pub enum QuicInitError {
+    CertificateError(String),
+    ProtocolError(String),
+}
+
+impl std::error::Error for QuicInitError {}
+
+impl std::fmt::Display for QuicInitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            QuicInitError::CertificateError(msg) => {
+                write!(f, "Unable to initialize QUIC TLS: {}", msg)
+            }
+            QuicInitError::ProtocolError(msg) => write!(f, "Unable to initialize QUIC: {}", msg),
+        }
+    }
+}
+
+impl From<quiche::Error> for QuicInitError {
+    fn from(err: quiche::Error) -> Self {
+        QuicInitError::ProtocolError(err.to_string())
+    }
+}
@@ -19,6 +19,13 @@ use std::time::Duration;
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
 const HEADER_LENGTH: usize = 6;
+// The version 2 header adds a byte which indicates the compression of the payload.
+const V2_HEADER_LENGTH: usize = 7;
+
+// The maximum size of a decompressed payload. This protects against payloads which expand to an
+// unreasonable size when decompressed.
+#[cfg(feature = "socket-compression")]
+const MAX_DECOMPRESSED_LENGTH: u64 = 256 * 1024 * 1024;
 
 /// An error that may be returned during frame-related operations
 #[derive(Debug)]
@@ -27,6 +34,8 @@ pub enum FrameError {
     InvalidChecksum,
     InvalidHeaderLength(usize),
     UnsupportedVersion,
+    UnsupportedCompression(u8),
+    CompressionError(String),
     HandshakeFailure(String),
 }
 
@@ -35,12 +44,14 @@ impl std::fmt::Display for FrameError {
         match self {
             FrameError::IoError(err) => f.write_str(&err.to_string()),
             FrameError::InvalidChecksum => f.write_str("Invalid checksum in frame header"),
-            FrameError::InvalidHeaderLength(n) => write!(
-                f,
-                "Invalid header length expected {} but was {}",
-                HEADER_LENGTH, n
-            ),
+            FrameError::InvalidHeaderLength(n) => {
+                write!(f, "Invalid header length; received {} bytes", n)
+            }
             FrameError::UnsupportedVersion => f.write_str("Unsupported frame version"),
+            FrameError::UnsupportedCompression(code) => {
+                write!(f, "Unsupported frame compression: {}", code)
+            }
+            FrameError::CompressionError(msg) => f.write_str(&msg),
             FrameError::HandshakeFailure(msg) => f.write_str(&msg),
         }
     }
@@ -53,6 +64,8 @@ impl std::error::Error for FrameError {
             FrameError::InvalidChecksum => None,
             FrameError::InvalidHeaderLength(_) => None,
             FrameError::UnsupportedVersion => None,
+            FrameError::UnsupportedCompression(_) => None,
+            FrameError::CompressionError(_) => None,
             FrameError::HandshakeFailure(_) => None,
         }
     }
@@ -71,6 +84,8 @@ impl From<io::Error> for FrameError {
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum FrameVersion {
     V1 = 1,
+    /// Version 2 adds support for compressed payloads.
+    V2 = 2,
 }
 
 impl std::fmt::Display for FrameVersion {
@@ -79,6 +94,162 @@ impl std::fmt::Display for FrameVersion {
     }
 }
 
+/// The compression algorithm applied to a frame's payload.
+///
+/// Compression is only available with frame version 2 or greater.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Compression {
+    None = 0,
+    #[cfg(feature = "socket-compression")]
+    Deflate = 1,
+    #[cfg(feature = "socket-compression")]
+    Zstd = 2,
+}
+
+impl Compression {
+    fn from_code(code: u8) -> Result<Self, FrameError> {
+        match code {
+            0 => Ok(Compression::None),
+            #[cfg(feature = "socket-compression")]
+            1 => Ok(Compression::Deflate),
+            #[cfg(feature = "socket-compression")]
+            2 => Ok(Compression::Zstd),
+            _ => Err(FrameError::UnsupportedCompression(code)),
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> Result<Vec<u8>, FrameError> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            #[cfg(feature = "socket-compression")]
+            Compression::Deflate => {
+                let mut encoder = flate2::write::DeflateEncoder::new(
+                    Vec::with_capacity(data.len()),
+                    flate2::Compression::default(),
+                );
+                encoder.write_all(data)?;
+                Ok(encoder.finish()?)
+            }
+            #[cfg(feature = "socket-compression")]
+            Compression::Zstd => zstd::stream::encode_all(data, 0).map_err(|err| {
+                FrameError::CompressionError(format!("Unable to compress payload: {}", err))
+            }),
+        }
+    }
+
+    fn decompress(self, data: Vec<u8>) -> Result<Vec<u8>, FrameError> {
+        match self {
+            Compression::None => Ok(data),
+            #[cfg(feature = "socket-compression")]
+            Compression::Deflate => {
+                let decoder = flate2::read::DeflateDecoder::new(&data[..]);
+                read_decompressed(decoder)
+            }
+            #[cfg(feature = "socket-compression")]
+            Compression::Zstd => {
+                let decoder = zstd::stream::read::Decoder::new(&data[..]).map_err(|err| {
+                    FrameError::CompressionError(format!("Unable to decompress payload: {}", err))
+                })?;
+                read_decompressed(decoder)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compression::None => f.write_str("none"),
+            #[cfg(feature = "socket-compression")]
+            Compression::Deflate => f.write_str("deflate"),
+            #[cfg(feature = "socket-compression")]
+            Compression::Zstd => f.write_str("zstd"),
+        }
+    }
+}
+
+impl std::str::FromStr for Compression {
+    type Err = FrameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Compression::None),
+            #[cfg(feature = "socket-compression")]
+            "deflate" => Ok(Compression::Deflate),
+            #[cfg(feature = "socket-compression")]
+            "zstd" => Ok(Compression::Zstd),
+            _ => Err(FrameError::CompressionError(format!(
+                "Unsupported compression algorithm: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Read the output of a decoder, up to the maximum decompressed payload length.
+#[cfg(feature = "socket-compression")]
+fn read_decompressed<R: Read>(decoder: R) -> Result<Vec<u8>, FrameError> {
+    let mut buffer = vec![];
+    decoder
+        .take(MAX_DECOMPRESSED_LENGTH + 1)
+        .read_to_end(&mut buffer)
+        .map_err(|err| {
+            FrameError::CompressionError(format!("Unable to decompress payload: {}", err))
+        })?;
+
+    if buffer.len() as u64 > MAX_DECOMPRESSED_LENGTH {
+        return Err(FrameError::CompressionError(format!(
+            "Decompressed payload exceeds the maximum length of {} bytes",
+            MAX_DECOMPRESSED_LENGTH
+        )));
+    }
+
+    Ok(buffer)
+}
+
+/// The compression settings for a transport.
+///
+/// The algorithms are listed in order of preference. Payloads smaller than the threshold, in
+/// bytes, are sent uncompressed.
+#[derive(Debug, Clone)]
+pub struct CompressionConfig {
+    algorithms: Vec<Compression>,
+    threshold: usize,
+}
+
+impl CompressionConfig {
+    /// Construct a new compression configuration.
+    pub fn new(algorithms: Vec<Compression>, threshold: usize) -> Self {
+        Self {
+            algorithms,
+            threshold,
+        }
+    }
+
+    /// The supported compression algorithms, in order of preference.
+    pub fn algorithms(&self) -> &[Compression] {
+        &self.algorithms
+    }
+
+    /// The minimum payload size, in bytes, which will be compressed.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the highest frame version required to use this configuration.
+    pub(super) fn max_frame_version(&self) -> FrameVersion {
+        if self
+            .algorithms
+            .iter()
+            .any(|algorithm| *algorithm != Compression::None)
+        {
+            FrameVersion::V2
+        } else {
+            FrameVersion::V1
+        }
+    }
+}
+
 /// A complete Frame of transmitted data.
 ///
 /// This struct owns the data that has been transmitted.  It is essentially a receiving frame.
@@ -114,35 +285,46 @@ impl Frame {
         };
 
         match frame_header {
-            FrameHeader::V1 { length } => {
-                let mut buffer = vec![0; length as usize];
-                let mut remaining = &mut buffer[..];
-
-                while !remaining.is_empty() {
-                    match reader.read(remaining) {
-                        Ok(0) => break,
-                        Ok(n) => {
-                            let tmp = remaining;
-                            remaining = &mut tmp[n..];
-                        }
-                        Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                        Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                            thread::sleep(Duration::from_millis(100));
-                        }
-                        Err(e) => return Err(FrameError::IoError(e)),
-                    }
-                }
-                if !remaining.is_empty() {
-                    Err(FrameError::IoError(io::Error::new(
-                        io::ErrorKind::UnexpectedEof,
-                        "Could not receive complete frame",
-                    )))
-                } else {
-                    Ok(Self { data: buffer })
-                }
+            FrameHeader::V1 { length } => Ok(Self {
+                data: read_payload(reader, length)?,
+            }),
+            FrameHeader::V2 {
+                length,
+                compression,
+            } => Ok(Self {
+                data: compression.decompress(read_payload(reader, length)?)?,
+            }),
+        }
+    }
+}
+
+/// Read a payload of the given length from the reader.
+fn read_payload<R: Read>(reader: &mut R, length: u32) -> Result<Vec<u8>, FrameError> {
+    let mut buffer = vec![0; length as usize];
+    let mut remaining = &mut buffer[..];
+
+    while !remaining.is_empty() {
+        match reader.read(remaining) {
+            Ok(0) => break,
+            Ok(n) => {
+                let tmp = remaining;
+                remaining = &mut tmp[n..];
             }
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => return Err(FrameError::IoError(e)),
         }
     }
+    if !remaining.is_empty() {
+        Err(FrameError::IoError(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Could not receive complete frame",
+        )))
+    } else {
+        Ok(buffer)
+    }
 }
 
 /// A Frame of referenced data to be transmitted using a specified version.
@@ -151,13 +333,30 @@ impl Frame {
 pub struct FrameRef<'a> {
     version: FrameVersion,
     data: &'a [u8],
+    compression: Compression,
+    compression_threshold: usize,
 }
 
 impl<'a> FrameRef<'a> {
     /// Construct a FrameRef for the given byte slice, which will be transmitted using the given
     /// frame version.
     pub fn new<'b: 'a>(version: FrameVersion, data: &'b [u8]) -> FrameRef<'a> {
-        Self { version, data }
+        Self {
+            version,
+            data,
+            compression: Compression::None,
+            compression_threshold: 0,
+        }
+    }
+
+    /// Compress the data with the given algorithm, if it is at least `threshold` bytes long.
+    ///
+    /// Compression is ignored for frame versions which do not support it. The data is sent
+    /// uncompressed if compressing it does not reduce its size.
+    pub fn with_compression(mut self, compression: Compression, threshold: usize) -> Self {
+        self.compression = compression;
+        self.compression_threshold = threshold;
+        self
     }
 
     /// Write the frame to the given writer.
@@ -166,8 +365,32 @@ impl<'a> FrameRef<'a> {
     ///
     /// Returns a FrameError if an IO error occurs.
     pub fn write<W: Write>(self, writer: &mut W) -> Result<(), FrameError> {
-        let frame_header = match self.version {
-            FrameVersion::V1 => FrameHeader::v1(self.data.len() as u32),
+        let compressed;
+        let (frame_header, data) = match self.version {
+            FrameVersion::V1 => (FrameHeader::v1(self.data.len() as u32), self.data),
+            FrameVersion::V2 => {
+                if self.compression != Compression::None
+                    && self.data.len() >= self.compression_threshold
+                {
+                    compressed = self.compression.compress(self.data)?;
+                    if compressed.len() < self.data.len() {
+                        (
+                            FrameHeader::v2(compressed.len() as u32, self.compression),
+                            &compressed[..],
+                        )
+                    } else {
+                        (
+                            FrameHeader::v2(self.data.len() as u32, Compression::None),
+                            self.data,
+                        )
+                    }
+                } else {
+                    (
+                        FrameHeader::v2(self.data.len() as u32, Compression::None),
+                        self.data,
+                    )
+                }
+            }
         };
         loop {
             match frame_header.write(writer) {
@@ -180,7 +403,7 @@ impl<'a> FrameRef<'a> {
             }
         }
 
-        let mut buffer = &self.data[..];
+        let mut buffer = data;
         while !buffer.is_empty() {
             match writer.write(buffer) {
                 Ok(0) => {
@@ -207,7 +430,13 @@ impl<'a> FrameRef<'a> {
 /// Each variant corresponds to the implementation for a given version.
 #[derive(Debug, PartialEq)]
 enum FrameHeader {
-    V1 { length: u32 },
+    V1 {
+        length: u32,
+    },
+    V2 {
+        length: u32,
+        compression: Compression,
+    },
 }
 
 impl FrameHeader {
@@ -216,6 +445,14 @@ impl FrameHeader {
         FrameHeader::V1 { length }
     }
 
+    /// Construct a version 2 frame header.
+    fn v2(length: u32, compression: Compression) -> Self {
+        FrameHeader::V2 {
+            length,
+            compression,
+        }
+    }
+
     /// Read a FrameHeader from the given reader.
     ///
     /// This function uses the first 2 bytes of the stream to read the version, and constructs the
@@ -252,6 +489,30 @@ impl FrameHeader {
                     length: cursor.read_u32::<BigEndian>()?,
                 })
             }
+            2 => {
+                // Header length + checksum byte
+                let mut buffer = [0u8; V2_HEADER_LENGTH + 1];
+                let mut cursor = Cursor::new(&mut buffer[..]);
+                cursor.write_u16::<BigEndian>(2u16)?;
+
+                let n = reader.read(&mut cursor.get_mut()[std::mem::size_of::<u16>()..])?;
+                if n != V2_HEADER_LENGTH + 1 - std::mem::size_of::<u16>() {
+                    return Err(FrameError::InvalidHeaderLength(n));
+                }
+
+                let checksum = compute_checksum(&cursor.get_ref()[..V2_HEADER_LENGTH]);
+                if checksum != cursor.get_ref()[V2_HEADER_LENGTH] {
+                    return Err(FrameError::InvalidChecksum);
+                }
+
+                let length = cursor.read_u32::<BigEndian>()?;
+                let compression = Compression::from_code(cursor.read_u8()?)?;
+
+                Ok(FrameHeader::V2 {
+                    length,
+                    compression,
+                })
+            }
             _ => Err(FrameError::UnsupportedVersion),
         }
     }
@@ -279,6 +540,22 @@ impl FrameHeader {
 
                 writer.write_all(&cursor.into_inner()[..])?;
             }
+            FrameHeader::V2 {
+                length,
+                compression,
+            } => {
+                let mut header_bytes = [0u8; V2_HEADER_LENGTH + 1];
+                let mut cursor = Cursor::new(&mut header_bytes[..]);
+
+                cursor.write_u16::<BigEndian>(2)?;
+                cursor.write_u32::<BigEndian>(length)?;
+                cursor.write_u8(compression as u8)?;
+
+                cursor.get_mut()[V2_HEADER_LENGTH] =
+                    compute_checksum(&cursor.get_ref()[..V2_HEADER_LENGTH]);
+
+                writer.write_all(&cursor.into_inner()[..])?;
+            }
         }
 
         Ok(())
@@ -313,8 +590,8 @@ pub enum FrameNegotiation {
         min: FrameVersion,
         max: FrameVersion,
     },
-    /// The Inbound variant transmits receives the min and max and decides if it should send its
-    /// version or `0`, depending on whether or not it falls in the range.
+    /// The Inbound variant receives the min and max and sends the highest version, up to and
+    /// including its own, which falls in that range, or `0` if there is no such version.
     Inbound { version: FrameVersion },
 }
 
@@ -325,6 +602,8 @@ impl FrameNegotiation {
     }
 
     /// Construct the inbound side of a negotiation with the given version.
+    ///
+    /// All versions up to and including the given version are supported.
     pub fn inbound(version: FrameVersion) -> Self {
         FrameNegotiation::Inbound { version }
     }
@@ -349,23 +628,39 @@ impl FrameNegotiation {
 
                 let frame_version = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
 
-                match frame_version {
-                    0 => Err(FrameError::UnsupportedVersion),
-                    1 => Ok(FrameVersion::V1),
-                    _ => Err(FrameError::UnsupportedVersion),
+                let frame_version = match frame_version {
+                    0 => return Err(FrameError::UnsupportedVersion),
+                    1 => FrameVersion::V1,
+                    2 => FrameVersion::V2,
+                    _ => return Err(FrameError::UnsupportedVersion),
+                };
+
+                if frame_version as u16 >= min as u16 && frame_version as u16 <= max as u16 {
+                    Ok(frame_version)
+                } else {
+                    Err(FrameError::UnsupportedVersion)
                 }
             }
             FrameNegotiation::Inbound { version } => {
                 let min = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
                 let max = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
-                if min > version as u16 || max < version as u16 {
+                let selected = std::cmp::min(version as u16, max);
+                let selected_version = match selected {
+                    1 => FrameVersion::V1,
+                    2 => FrameVersion::V2,
+                    _ => {
+                        stream.write_u16::<BigEndian>(0).map_err(Self::map_io_err)?;
+                        return Err(FrameError::UnsupportedVersion);
+                    }
+                };
+                if selected < min {
                     stream.write_u16::<BigEndian>(0).map_err(Self::map_io_err)?;
                     Err(FrameError::UnsupportedVersion)
                 } else {
                     stream
-                        .write_u16::<BigEndian>(version as u16)
+                        .write_u16::<BigEndian>(selected)
                         .map_err(Self::map_io_err)?;
-                    Ok(version)
+                    Ok(selected_version)
                 }
             }
         }
@@ -384,6 +679,102 @@ impl FrameNegotiation {
     }
 }
 
+/// Negotiate the payload compression for a given socket connection.
+///
+/// Compression negotiation follows the frame version negotiation, and only takes place if the
+/// negotiated version supports compression.
+pub enum CompressionNegotiation<'a> {
+    /// The Outbound variant transmits its supported algorithms, in order of preference, and
+    /// expects to receive one of those algorithms, or `0` for no compression.
+    Outbound { algorithms: &'a [Compression] },
+    /// The Inbound variant receives the other end's supported algorithms and sends the first of
+    /// those which it also supports, or `0` if there is no such algorithm.
+    Inbound { algorithms: &'a [Compression] },
+}
+
+impl<'a> CompressionNegotiation<'a> {
+    /// Construct the outbound side of a negotiation with the given algorithms.
+    pub fn outbound(algorithms: &'a [Compression]) -> Self {
+        CompressionNegotiation::Outbound { algorithms }
+    }
+
+    /// Construct the inbound side of a negotiation with the given algorithms.
+    pub fn inbound(algorithms: &'a [Compression]) -> Self {
+        CompressionNegotiation::Inbound { algorithms }
+    }
+
+    /// Negotiate the compression to use for future communications over the given stream, using
+    /// the given frame version.
+    ///
+    /// If the frame version does not support compression, `Compression::None` is returned
+    /// without communicating with the other end.
+    ///
+    /// # Errors
+    ///
+    /// Returns a FrameError if:
+    ///
+    /// - the other end selects an algorithm that was not offered
+    /// - an IO error, if one occurs
+    pub fn negotiate<S: Read + Write>(
+        self,
+        version: FrameVersion,
+        stream: &mut S,
+    ) -> Result<Compression, FrameError> {
+        if version == FrameVersion::V1 {
+            return Ok(Compression::None);
+        }
+
+        match self {
+            CompressionNegotiation::Outbound { algorithms } => {
+                let offered: Vec<Compression> = algorithms
+                    .iter()
+                    .filter(|algorithm| **algorithm != Compression::None)
+                    .cloned()
+                    .collect();
+
+                stream
+                    .write_u8(offered.len() as u8)
+                    .map_err(FrameNegotiation::map_io_err)?;
+                for algorithm in &offered {
+                    stream
+                        .write_u8(*algorithm as u8)
+                        .map_err(FrameNegotiation::map_io_err)?;
+                }
+
+                let code = stream.read_u8().map_err(FrameNegotiation::map_io_err)?;
+                let compression = Compression::from_code(code)?;
+                if compression == Compression::None || offered.contains(&compression) {
+                    Ok(compression)
+                } else {
+                    Err(FrameError::UnsupportedCompression(code))
+                }
+            }
+            CompressionNegotiation::Inbound { algorithms } => {
+                let count = stream.read_u8().map_err(FrameNegotiation::map_io_err)?;
+                let mut offered = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    offered.push(stream.read_u8().map_err(FrameNegotiation::map_io_err)?);
+                }
+
+                // Unknown algorithms are ignored
+                let compression = offered
+                    .into_iter()
+                    .filter_map(|code| Compression::from_code(code).ok())
+                    .find(|algorithm| {
+                        *algorithm != Compression::None && algorithms.contains(algorithm)
+                    })
+                    .unwrap_or(Compression::None);
+
+                stream
+                    .write_u8(compression as u8)
+                    .map_err(FrameNegotiation::map_io_err)?;
+
+                Ok(compression)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -498,10 +889,53 @@ mod tests {
             .expect("Unable to write frame header");
 
         header_cursor.set_position(0);
-        let FrameHeader::V1 { length } =
-            FrameHeader::read(&mut header_cursor).expect("Unable to read header");
+        let frame_header = FrameHeader::read(&mut header_cursor).expect("Unable to read header");
+
+        assert_eq!(FrameHeader::v1(100), frame_header);
+    }
+
+    /// Test a round-trip write and read of a v2 FrameHeader.  Construct a valid FrameHeader, and
+    /// write it to bytes.  Read a new FrameHeader from the bytes and verify that they are equal.
+    #[test]
+    fn round_trip_v2() {
+        let header_bytes = vec![0u8; V2_HEADER_LENGTH + 1];
+        let mut header_cursor = Cursor::new(header_bytes);
+
+        let frame_header = FrameHeader::v2(100, Compression::None);
+
+        frame_header
+            .write(&mut header_cursor)
+            .expect("Unable to write frame header");
+
+        header_cursor.set_position(0);
+        let frame_header = FrameHeader::read(&mut header_cursor).expect("Unable to read header");
 
-        assert_eq!(100, length);
+        assert_eq!(FrameHeader::v2(100, Compression::None), frame_header);
+    }
+
+    /// Test that a v2 frame header with an unknown compression code returns an error when read.
+    #[test]
+    fn read_version_2_unknown_compression() {
+        let header_bytes = vec![0u8; V2_HEADER_LENGTH + 1];
+        let mut header_cursor = Cursor::new(header_bytes);
+
+        header_cursor
+            .write_u16::<BigEndian>(2)
+            .expect("Could not write version to cursor");
+        header_cursor
+            .write_u32::<BigEndian>(2)
+            .expect("Could not write length to cursor");
+        header_cursor
+            .write_u8(200)
+            .expect("Could not write compression to cursor");
+        header_cursor.get_mut()[V2_HEADER_LENGTH] =
+            compute_checksum(&header_cursor.get_ref()[..V2_HEADER_LENGTH]);
+        header_cursor.set_position(0);
+
+        match FrameHeader::read(&mut header_cursor) {
+            Err(FrameError::UnsupportedCompression(200)) => (),
+            res => panic!("Unexpected result: {:?}", res),
+        }
     }
 
     /// Test that outbound frame version negotiation works:
@@ -537,6 +971,158 @@ mod tests {
         assert_eq!(FrameVersion::V1, remote_res);
     }
 
+    /// Test that an outbound negotiation which supports v2 agrees on v1 with a peer which only
+    /// supports v1:
+    /// 1. Create a stream pair
+    /// 2. Send one end to a thread, to act as a v1-only inbound receiver
+    /// 3. Create an outbound negotiation for v1 to v2 and execute it on the stream
+    /// 4. Verify that both ends agree on v1.
+    #[test]
+    fn outbound_v2_with_v1_peer() {
+        let (mut tx, mut rx) = stream::byte_stream_pair();
+
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        let join_handle = thread::spawn(move || {
+            let res = FrameNegotiation::inbound(FrameVersion::V1)
+                .negotiate(&mut rx)
+                .expect("Should have successfully negotiated");
+
+            done_rx.recv().unwrap();
+
+            res
+        });
+
+        let version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V2)
+            .negotiate(&mut tx)
+            .expect("Unable to negotiate a valid version");
+
+        assert_eq!(FrameVersion::V1, version);
+
+        done_tx.send(1u8).expect("unable to send stop signal");
+
+        let remote_res = join_handle.join().expect("Unable to join thread");
+
+        assert_eq!(FrameVersion::V1, remote_res);
+    }
+
+    /// Test that an inbound negotiation which supports v2 agrees on v1 with a peer which only
+    /// supports v1:
+    /// 1. Create a stream pair
+    /// 2. Send one end to a thread, to act as the v1-only outbound end
+    /// 3. Create an inbound negotiation for v2 and execute it on the stream
+    /// 4. Verify that both ends agree on v1.
+    #[test]
+    fn inbound_v2_with_v1_peer() {
+        let (mut tx, mut rx) = stream::byte_stream_pair();
+
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        let join_handle = thread::spawn(move || {
+            let res = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+                .negotiate(&mut rx)
+                .expect("Should have successfully negotiated");
+
+            done_rx.recv().unwrap();
+
+            res
+        });
+
+        let version = FrameNegotiation::inbound(FrameVersion::V2)
+            .negotiate(&mut tx)
+            .expect("Unable to negotiate a valid version");
+
+        assert_eq!(FrameVersion::V1, version);
+
+        done_tx.send(1u8).expect("unable to send stop signal");
+
+        let remote_res = join_handle.join().expect("Unable to join thread");
+
+        assert_eq!(FrameVersion::V1, remote_res);
+    }
+
+    /// Test that compression negotiation does not communicate with the other end when the frame
+    /// version does not support compression.
+    #[test]
+    fn compression_negotiation_v1() {
+        let mut cursor = Cursor::new(vec![]);
+
+        let compression = CompressionNegotiation::outbound(&[Compression::None])
+            .negotiate(FrameVersion::V1, &mut cursor)
+            .expect("Unable to negotiate compression");
+
+        assert_eq!(Compression::None, compression);
+        assert!(cursor.get_ref().is_empty());
+    }
+
+    /// Test that compression negotiation selects no compression when the inbound end does not
+    /// support any of the offered algorithms:
+    /// 1. Create a stream pair
+    /// 2. Send one end to a thread, to act as the inbound receiver, which supports no algorithms
+    /// 3. Create an outbound negotiation and execute it on the stream
+    /// 4. Verify that both ends agree on no compression.
+    #[test]
+    fn compression_negotiation_none_supported() {
+        let (mut tx, mut rx) = stream::byte_stream_pair();
+
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        let join_handle = thread::spawn(move || {
+            let res = CompressionNegotiation::inbound(&[])
+                .negotiate(FrameVersion::V2, &mut rx)
+                .expect("Should have successfully negotiated");
+
+            done_rx.recv().unwrap();
+
+            res
+        });
+
+        let compression = CompressionNegotiation::outbound(&[Compression::None])
+            .negotiate(FrameVersion::V2, &mut tx)
+            .expect("Unable to negotiate compression");
+
+        assert_eq!(Compression::None, compression);
+
+        done_tx.send(1u8).expect("unable to send stop signal");
+
+        let remote_res = join_handle.join().expect("Unable to join thread");
+
+        assert_eq!(Compression::None, remote_res);
+    }
+
+    /// Test that compression negotiation selects the first of the outbound end's algorithms that
+    /// is supported by the inbound end:
+    /// 1. Create a stream pair
+    /// 2. Send one end to a thread, to act as the inbound receiver, which supports deflate
+    /// 3. Create an outbound negotiation which prefers zstd to deflate and execute it
+    /// 4. Verify that both ends agree on deflate.
+    #[cfg(feature = "socket-compression")]
+    #[test]
+    fn compression_negotiation() {
+        let (mut tx, mut rx) = stream::byte_stream_pair();
+
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        let join_handle = thread::spawn(move || {
+            let res = CompressionNegotiation::inbound(&[Compression::Deflate])
+                .negotiate(FrameVersion::V2, &mut rx)
+                .expect("Should have successfully negotiated");
+
+            done_rx.recv().unwrap();
+
+            res
+        });
+
+        let compression =
+            CompressionNegotiation::outbound(&[Compression::Zstd, Compression::Deflate])
+                .negotiate(FrameVersion::V2, &mut tx)
+                .expect("Unable to negotiate compression");
+
+        assert_eq!(Compression::Deflate, compression);
+
+        done_tx.send(1u8).expect("unable to send stop signal");
+
+        let remote_res = join_handle.join().expect("Unable to join thread");
+
+        assert_eq!(Compression::Deflate, remote_res);
+    }
+
     /// Test that outbound frame version negotiation works:
     /// 1. Create a stream pair
     /// 2. Send one end to a thread, to act as the inbound receiver - this stream will return no
@@ -676,6 +1262,62 @@ mod tests {
         assert_eq!(input.to_vec(), frame.data);
     }
 
+    /// Write a v2 frame whose data is below the compression threshold and verify that it is sent
+    /// uncompressed and an equivalent frame is read back from the stream.
+    #[test]
+    fn frame_round_trip_v2_below_threshold() {
+        let input = b"hello world";
+        let frame_ref = FrameRef::new(FrameVersion::V2, input)
+            .with_compression(Compression::None, input.len() + 1);
+
+        let mut cursor = Cursor::new(vec![]);
+
+        frame_ref.write(&mut cursor).expect("Unable to write data");
+
+        cursor.set_position(0);
+        assert_eq!(
+            FrameHeader::v2(input.len() as u32, Compression::None),
+            FrameHeader::read(&mut cursor).expect("Unable to read header")
+        );
+
+        cursor.set_position(0);
+        let frame = Frame::read(&mut cursor).expect("Unable to read frame");
+
+        assert_eq!(input.to_vec(), frame.data);
+    }
+
+    /// Write compressed v2 frames to a stream and verify that the payloads were compressed and
+    /// that equivalent frames are read back from the stream.
+    #[cfg(feature = "socket-compression")]
+    #[test]
+    fn frame_round_trip_v2_compressed() {
+        let input = vec![7u8; 4096];
+
+        for compression in &[Compression::Deflate, Compression::Zstd] {
+            let frame_ref =
+                FrameRef::new(FrameVersion::V2, &input).with_compression(*compression, 1024);
+
+            let mut cursor = Cursor::new(vec![]);
+
+            frame_ref.write(&mut cursor).expect("Unable to write data");
+            assert!(cursor.get_ref().len() < input.len());
+
+            cursor.set_position(0);
+            match FrameHeader::read(&mut cursor).expect("Unable to read header") {
+                FrameHeader::V2 {
+                    compression: header_compression,
+                    ..
+                } => assert_eq!(*compression, header_compression),
+                header => panic!("Unexpected header: {:?}", header),
+            }
+
+            cursor.set_position(0);
+            let frame = Frame::read(&mut cursor).expect("Unable to read frame");
+
+            assert_eq!(input, frame.data);
+        }
+    }
+
     #[cfg(not(target_os = "unix"))]
     mod stream {
         use std::io::{Error as IoError, Read, Write};
@@ -16,6 +16,7 @@ mod frame;
 mod tcp;
 mod tls;
 
+pub use frame::{Compression, CompressionConfig};
 pub use tcp::TcpTransport;
 pub use tls::{TlsConnection, TlsInitError, TlsTransport};
 
@@ -21,12 +21,40 @@ use crate::transport::{
     SendError, Transport,
 };
 
-use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+use super::frame::{
+    Compression, CompressionConfig, CompressionNegotiation, Frame, FrameError, FrameNegotiation,
+    FrameRef, FrameVersion,
+};
 
 const PROTOCOL_PREFIX: &str = "tcp://";
 
 #[derive(Default)]
-pub struct TcpTransport {}
+pub struct TcpTransport {
+    compression: Option<CompressionConfig>,
+}
+
+impl TcpTransport {
+    /// Compress message payloads using the given configuration, for connections where the
+    /// remote end also supports compression.
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    fn compression_algorithms(&self) -> &[Compression] {
+        self.compression
+            .as_ref()
+            .map(|config| config.algorithms())
+            .unwrap_or(&[])
+    }
+
+    fn compression_threshold(&self) -> usize {
+        self.compression
+            .as_ref()
+            .map(|config| config.threshold())
+            .unwrap_or(0)
+    }
+}
 
 impl Transport for TcpTransport {
     fn accepts(&self, address: &str) -> bool {
@@ -49,7 +77,13 @@ impl Transport for TcpTransport {
         // Connect a std::net::TcpStream to make sure connect() block
         let mut stream = TcpStream::connect(address)?;
 
-        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+        let max_version = self
+            .compression
+            .as_ref()
+            .map(|config| config.max_frame_version())
+            .unwrap_or(FrameVersion::V1);
+
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, max_version)
             .negotiate(&mut stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => ConnectError::ProtocolError(
@@ -59,9 +93,18 @@ impl Transport for TcpTransport {
                 e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
             })?;
 
+        let compression = CompressionNegotiation::outbound(self.compression_algorithms())
+            .negotiate(frame_version, &mut stream)
+            .map_err(|err| match err {
+                FrameError::IoError(err) => ConnectError::from(err),
+                e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
+            })?;
+
         let mio_stream = MioTcpStream::from_stream(stream)?;
         Ok(Box::new(TcpConnection {
             frame_version,
+            compression,
+            compression_threshold: self.compression_threshold(),
             stream: mio_stream,
         }))
     }
@@ -84,32 +127,52 @@ impl Transport for TcpTransport {
             listener: StdTcpListener::bind(address).map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?,
+            compression: self.compression.clone(),
         }))
     }
 }
 
 struct TcpListener {
     listener: StdTcpListener,
+    compression: Option<CompressionConfig>,
 }
 
 impl Listener for TcpListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (mut stream, _) = self.listener.accept()?;
 
-        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+        let frame_version = FrameNegotiation::inbound(FrameVersion::V2)
             .negotiate(&mut stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
                     "Local {} protocol version {} not supported by remote",
                     PROTOCOL_PREFIX,
-                    FrameVersion::V1
+                    FrameVersion::V2
                 )),
                 FrameError::IoError(err) => AcceptError::from(err),
                 err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
             })?;
 
+        let algorithms = self
+            .compression
+            .as_ref()
+            .map(|config| config.algorithms())
+            .unwrap_or(&[]);
+        let compression = CompressionNegotiation::inbound(algorithms)
+            .negotiate(frame_version, &mut stream)
+            .map_err(|err| match err {
+                FrameError::IoError(err) => AcceptError::from(err),
+                err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
+            })?;
+
         let connection = TcpConnection {
             frame_version,
+            compression,
+            compression_threshold: self
+                .compression
+                .as_ref()
+                .map(|config| config.threshold())
+                .unwrap_or(0),
             stream: MioTcpStream::from_stream(stream)?,
         };
         Ok(Box::new(connection))
@@ -122,12 +185,17 @@ impl Listener for TcpListener {
 
 struct TcpConnection {
     frame_version: FrameVersion,
+    compression: Compression,
+    compression_threshold: usize,
     stream: MioTcpStream,
 }
 
 impl Connection for TcpConnection {
     fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
-        match FrameRef::new(self.frame_version, message).write(&mut self.stream) {
+        match FrameRef::new(self.frame_version, message)
+            .with_compression(self.compression, self.compression_threshold)
+            .write(&mut self.stream)
+        {
             Err(FrameError::IoError(e)) => Err(SendError::from(e)),
             Err(err) => Err(SendError::ProtocolError(err.to_string())),
             Ok(_) => Ok(()),
@@ -196,4 +264,23 @@ mod tests {
         let transport = TcpTransport::default();
         tests::test_poll(transport, "127.0.0.1:0");
     }
+
+    #[cfg(feature = "socket-compression")]
+    #[test]
+    fn test_transport_compression() {
+        let transport = TcpTransport::default().with_compression(CompressionConfig::new(
+            vec![Compression::Zstd, Compression::Deflate],
+            0,
+        ));
+
+        tests::test_transport(transport, "tcp://127.0.0.1:0");
+    }
+
+    #[cfg(feature = "socket-compression")]
+    #[test]
+    fn test_poll_compression() {
+        let transport = TcpTransport::default()
+            .with_compression(CompressionConfig::new(vec![Compression::Deflate], 0));
+        tests::test_poll(transport, "127.0.0.1:0");
+    }
 }
@@ -32,7 +32,10 @@ use crate::transport::{
     SendError, Transport,
 };
 
-use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+use super::frame::{
+    Compression, CompressionConfig, CompressionNegotiation, Frame, FrameError, FrameNegotiation,
+    FrameRef, FrameVersion,
+};
 
 /// tls:// is deprecated, tcps:// should be used instead
 const DEPRECATED_PROTOCOL_PREFIX: &str = "tls://";
@@ -41,6 +44,7 @@ const PROTOCOL_PREFIX: &str = "tcps://";
 pub struct TlsTransport {
     connector: SslConnector,
     acceptor: SslAcceptor,
+    compression: Option<CompressionConfig>,
 }
 
 impl TlsTransport {
@@ -91,8 +95,16 @@ impl TlsTransport {
         Ok(TlsTransport {
             connector,
             acceptor,
+            compression: None,
         })
     }
+
+    /// Compress message payloads using the given configuration, for connections where the
+    /// remote end also supports compression.
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.compression = Some(compression);
+        self
+    }
 }
 
 fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
@@ -136,7 +148,13 @@ impl Transport for TlsTransport {
         let stream = TcpStream::connect(address)?;
         let mut tls_stream = self.connector.connect(&dns_name, stream)?;
 
-        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+        let max_version = self
+            .compression
+            .as_ref()
+            .map(|config| config.max_frame_version())
+            .unwrap_or(FrameVersion::V1);
+
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, max_version)
             .negotiate(&mut tls_stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => ConnectError::ProtocolError(
@@ -146,9 +164,27 @@ impl Transport for TlsTransport {
                 e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
             })?;
 
+        let algorithms = self
+            .compression
+            .as_ref()
+            .map(|config| config.algorithms())
+            .unwrap_or(&[]);
+        let compression = CompressionNegotiation::outbound(algorithms)
+            .negotiate(frame_version, &mut tls_stream)
+            .map_err(|err| match err {
+                FrameError::IoError(err) => ConnectError::from(err),
+                e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
+            })?;
+
         tls_stream.get_ref().set_nonblocking(true)?;
         let connection = TlsConnection {
             frame_version,
+            compression,
+            compression_threshold: self
+                .compression
+                .as_ref()
+                .map(|config| config.threshold())
+                .unwrap_or(0),
             stream: tls_stream,
         };
         Ok(Box::new(connection))
@@ -175,6 +211,7 @@ impl Transport for TlsTransport {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?,
             acceptor: self.acceptor.clone(),
+            compression: self.compression.clone(),
         }))
     }
 }
@@ -182,6 +219,7 @@ impl Transport for TlsTransport {
 pub struct TlsListener {
     listener: TcpListener,
     acceptor: SslAcceptor,
+    compression: Option<CompressionConfig>,
 }
 
 impl Listener for TlsListener {
@@ -189,21 +227,39 @@ impl Listener for TlsListener {
         let (stream, _) = self.listener.accept()?;
         let mut tls_stream = self.acceptor.accept(stream)?;
 
-        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+        let frame_version = FrameNegotiation::inbound(FrameVersion::V2)
             .negotiate(&mut tls_stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
                     "Local {} protocol version {} not supported by remote",
                     PROTOCOL_PREFIX,
-                    FrameVersion::V1
+                    FrameVersion::V2
                 )),
                 FrameError::IoError(err) => AcceptError::from(err),
                 err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
             })?;
 
+        let algorithms = self
+            .compression
+            .as_ref()
+            .map(|config| config.algorithms())
+            .unwrap_or(&[]);
+        let compression = CompressionNegotiation::inbound(algorithms)
+            .negotiate(frame_version, &mut tls_stream)
+            .map_err(|err| match err {
+                FrameError::IoError(err) => AcceptError::from(err),
+                err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
+            })?;
+
         tls_stream.get_ref().set_nonblocking(true)?;
         let connection = TlsConnection {
             frame_version,
+            compression,
+            compression_threshold: self
+                .compression
+                .as_ref()
+                .map(|config| config.threshold())
+                .unwrap_or(0),
             stream: tls_stream,
         };
         Ok(Box::new(connection))
@@ -216,12 +272,17 @@ impl Listener for TlsListener {
 
 pub struct TlsConnection {
     frame_version: FrameVersion,
+    compression: Compression,
+    compression_threshold: usize,
     stream: SslStream<TcpStream>,
 }
 
 impl Connection for TlsConnection {
     fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
-        match FrameRef::new(self.frame_version, message).write(&mut self.stream) {
+        match FrameRef::new(self.frame_version, message)
+            .with_compression(self.compression, self.compression_threshold)
+            .write(&mut self.stream)
+        {
             Err(FrameError::IoError(e)) => Err(SendError::from(e)),
             Err(err) => Err(SendError::ProtocolError(err.to_string())),
             Ok(_) => Ok(()),
@@ -264,6 +325,8 @@ impl TlsConnection {
     pub fn new(stream: SslStream<TcpStream>) -> Self {
         TlsConnection {
             frame_version: FrameVersion::V1,
+            compression: Compression::None,
+            compression_threshold: 0,
             stream,
         }
     }
@@ -444,6 +507,16 @@ pub(crate) mod tests {
         tests::test_transport(transport, "tls://127.0.0.1:0");
     }
 
+    #[cfg(feature = "socket-compression")]
+    #[test]
+    fn test_transport_compression() {
+        let transport = create_test_tls_transport(true).with_compression(CompressionConfig::new(
+            vec![Compression::Zstd, Compression::Deflate],
+            0,
+        ));
+        tests::test_transport(transport, "tcps://127.0.0.1:0");
+    }
+
     #[cfg(not(unix))]
     #[test]
     fn test_poll() {
@@ -20,6 +20,7 @@ use openssl::error::ErrorStack;
 #[cfg(feature = "ws-transport")]
 use openssl::ssl::{SslAcceptor, SslConnector, SslFiletype, SslMethod, SslVerifyMode};
 
+#[derive(Clone)]
 pub struct TlsConfig {
     ca_certs_file: Option<String>,
     server_cert_file: String,
@@ -0,0 +1,60 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Forwards admin service events to webhooks.
+
+use std::time::SystemTime;
+
+use crate::admin::messages::AdminServiceEvent;
+use crate::admin::service::{AdminServiceEventSubscriber, AdminSubscriberError};
+
+use super::{WebhookDispatcher, WebhookEvent};
+
+/// An admin service event subscriber that dispatches each event to the subscribed webhooks.
+///
+/// The webhook event type is the admin event's type, such as `ProposalSubmitted` or
+/// `CircuitReady`, and the payload is the event's message.
+pub struct WebhookAdminEventSubscriber {
+    dispatcher: WebhookDispatcher,
+}
+
+impl WebhookAdminEventSubscriber {
+    pub fn new(dispatcher: WebhookDispatcher) -> Self {
+        Self { dispatcher }
+    }
+}
+
+impl AdminServiceEventSubscriber for WebhookAdminEventSubscriber {
+    fn handle_event(
+        &self,
+        admin_service_event: &AdminServiceEvent,
+        timestamp: &SystemTime,
+    ) -> Result<(), AdminSubscriberError> {
+        let mut value = serde_json::to_value(admin_service_event)
+            .map_err(|err| AdminSubscriberError::UnableToHandleEvent(err.to_string()))?;
+        let event_type = value
+            .get("eventType")
+            .and_then(|event_type| event_type.as_str())
+            .map(String::from)
+            .ok_or_else(|| AdminSubscriberError::UnableToHandleEvent("event has no type".into()))?;
+        let payload = value
+            .get_mut("message")
+            .map(serde_json::Value::take)
+            .unwrap_or(serde_json::Value::Null);
+
+        self.dispatcher
+            .dispatch(&WebhookEvent::new(&event_type, *timestamp, payload))
+            .map_err(|err| AdminSubscriberError::UnableToHandleEvent(err.to_string()))
+    }
+}
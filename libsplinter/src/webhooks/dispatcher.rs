@@ -0,0 +1,926 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The webhook dispatcher and its delivery workers.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use uuid::Uuid;
+
+use super::{
+    sign_payload, Webhook, WebhookError, WebhookEvent, DELIVERY_ID_HEADER, EVENT_TYPE_HEADER,
+    SIGNATURE_HEADER,
+};
+
+/// The number of delivery attempts kept in the delivery log.
+const MAX_DELIVERY_RECORDS: usize = 1000;
+/// The number of failed deliveries kept in the dead-letter list.
+const MAX_DEAD_LETTERS: usize = 1000;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// The number of threads making deliveries. Each webhook has at most one delivery in flight, so a
+/// slow webhook only occupies a single worker.
+const DELIVERY_WORKERS: usize = 4;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(300);
+
+/// Controls how failed deliveries are retried.
+///
+/// The delay before each retry doubles, starting at the initial delay and capped at the maximum
+/// delay. A delivery that fails `max_attempts` times is moved to the dead-letter list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy; `max_attempts` is at least 1.
+    pub fn new(max_attempts: u32, initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_delay,
+            max_delay,
+        }
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay to wait after the given (1-based) failed attempt.
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        self.initial_delay
+            .checked_mul(1 << exponent)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_MAX_ATTEMPTS,
+            DEFAULT_INITIAL_DELAY,
+            DEFAULT_MAX_DELAY,
+        )
+    }
+}
+
+/// The outcome of a single delivery attempt.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// The webhook responded with a success status.
+    Delivered,
+    /// The attempt failed and the delivery will be retried.
+    Retrying,
+    /// The attempt failed and the delivery has been moved to the dead-letter list.
+    DeadLettered,
+}
+
+/// An entry in the delivery log, recording a single delivery attempt.
+#[derive(Clone, Debug, Serialize)]
+pub struct DeliveryRecord {
+    delivery_id: String,
+    webhook_id: String,
+    event_type: String,
+    attempt: u32,
+    status: DeliveryStatus,
+    status_code: Option<u16>,
+    error: Option<String>,
+    timestamp: u64,
+}
+
+impl DeliveryRecord {
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    pub fn webhook_id(&self) -> &str {
+        &self.webhook_id
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    pub fn status(&self) -> DeliveryStatus {
+        self.status
+    }
+
+    /// The HTTP status returned by the webhook, if a response was received.
+    pub fn status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// The time of the attempt, in milliseconds since the Unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+/// A delivery that exhausted its retry attempts.
+#[derive(Clone, Debug, Serialize)]
+pub struct DeadLetter {
+    delivery_id: String,
+    webhook_id: String,
+    event_type: String,
+    attempts: u32,
+    last_error: String,
+    failed_at: u64,
+    #[serde(skip)]
+    body: Vec<u8>,
+}
+
+impl DeadLetter {
+    pub fn delivery_id(&self) -> &str {
+        &self.delivery_id
+    }
+
+    pub fn webhook_id(&self) -> &str {
+        &self.webhook_id
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn last_error(&self) -> &str {
+        &self.last_error
+    }
+
+    /// The time of the final attempt, in milliseconds since the Unix epoch.
+    pub fn failed_at(&self) -> u64 {
+        self.failed_at
+    }
+}
+
+#[derive(Serialize)]
+struct DeliveryBody<'a> {
+    delivery_id: &'a str,
+    event_type: &'a str,
+    timestamp: u64,
+    payload: &'a serde_json::Value,
+}
+
+struct PendingDelivery {
+    delivery_id: String,
+    webhook_id: String,
+    event_type: String,
+    body: Vec<u8>,
+    attempts: u32,
+    due: Instant,
+}
+
+// Ordered so that the `BinaryHeap` of pending deliveries yields the earliest due first.
+impl Ord for PendingDelivery {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .due
+            .cmp(&self.due)
+            .then_with(|| other.delivery_id.cmp(&self.delivery_id))
+    }
+}
+
+impl PartialOrd for PendingDelivery {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PendingDelivery {
+    fn eq(&self, other: &Self) -> bool {
+        self.due == other.due && self.delivery_id == other.delivery_id
+    }
+}
+
+impl Eq for PendingDelivery {}
+
+enum DispatcherMessage {
+    Deliver(PendingDelivery),
+    /// A worker has finished an attempt for the webhook, returning the delivery if it should be
+    /// retried.
+    Attempted {
+        webhook_id: String,
+        retry: Option<PendingDelivery>,
+    },
+    Shutdown,
+}
+
+#[derive(Default)]
+struct DispatcherState {
+    webhooks: BTreeMap<String, Webhook>,
+    deliveries: VecDeque<DeliveryRecord>,
+    dead_letters: VecDeque<DeadLetter>,
+}
+
+impl DispatcherState {
+    fn record(&mut self, record: DeliveryRecord) {
+        if self.deliveries.len() >= MAX_DELIVERY_RECORDS {
+            self.deliveries.pop_front();
+        }
+        self.deliveries.push_back(record);
+    }
+
+    fn add_dead_letter(&mut self, dead_letter: DeadLetter) {
+        if self.dead_letters.len() >= MAX_DEAD_LETTERS {
+            self.dead_letters.pop_front();
+        }
+        self.dead_letters.push_back(dead_letter);
+    }
+}
+
+/// Delivers events to registered webhooks.
+///
+/// Deliveries are made by a fixed pool of background threads, so `dispatch` never blocks on the
+/// network. The deliveries for each webhook are made one at a time, so a slow or unresponsive
+/// webhook does not hold up the others. The dispatcher may be cloned freely; all clones share the
+/// same webhooks and delivery log.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    state: Arc<Mutex<DispatcherState>>,
+    sender: Sender<DispatcherMessage>,
+}
+
+impl WebhookDispatcher {
+    /// Starts a dispatcher with the given retry policy and no webhooks.
+    pub fn start(
+        retry_policy: RetryPolicy,
+    ) -> Result<(Self, WebhookDispatcherShutdownHandle), WebhookError> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| {
+                WebhookError::internal_with_source("Unable to create webhook HTTP client", err)
+            })?;
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (job_sender, job_receiver) = crossbeam_channel::bounded(DELIVERY_WORKERS);
+        let state = Arc::new(Mutex::new(DispatcherState::default()));
+
+        for i in 0..DELIVERY_WORKERS {
+            let worker_client = client.clone();
+            let worker_state = state.clone();
+            let worker_receiver = job_receiver.clone();
+            let worker_sender = sender.clone();
+            let worker_retry_policy = retry_policy.clone();
+            thread::Builder::new()
+                .name(format!("WebhookDeliveryWorker-{}", i))
+                .spawn(move || {
+                    run_worker(
+                        &worker_client,
+                        &worker_state,
+                        &worker_receiver,
+                        &worker_sender,
+                        &worker_retry_policy,
+                    )
+                })
+                .map_err(|err| {
+                    WebhookError::internal_with_source("Unable to start webhook worker", err)
+                })?;
+        }
+
+        thread::Builder::new()
+            .name("WebhookDispatcher".into())
+            .spawn(move || run_scheduler(&receiver, job_sender))
+            .map_err(|err| {
+                WebhookError::internal_with_source("Unable to start webhook dispatcher", err)
+            })?;
+
+        let shutdown_handle = WebhookDispatcherShutdownHandle {
+            sender: sender.clone(),
+        };
+
+        Ok((Self { state, sender }, shutdown_handle))
+    }
+
+    /// Registers a webhook, replacing any existing webhook with the same ID.
+    pub fn add_webhook(&self, webhook: Webhook) -> Result<(), WebhookError> {
+        self.lock_state()?
+            .webhooks
+            .insert(webhook.id().to_string(), webhook);
+        Ok(())
+    }
+
+    /// Removes a webhook; pending retries for it are dropped.
+    pub fn remove_webhook(&self, webhook_id: &str) -> Result<(), WebhookError> {
+        self.lock_state()?
+            .webhooks
+            .remove(webhook_id)
+            .map(|_| ())
+            .ok_or_else(|| WebhookError::NotFound(format!("webhook {}", webhook_id)))
+    }
+
+    pub fn get_webhook(&self, webhook_id: &str) -> Result<Option<Webhook>, WebhookError> {
+        Ok(self.lock_state()?.webhooks.get(webhook_id).cloned())
+    }
+
+    pub fn list_webhooks(&self) -> Result<Vec<Webhook>, WebhookError> {
+        Ok(self.lock_state()?.webhooks.values().cloned().collect())
+    }
+
+    /// Queues the event for delivery to every webhook subscribed to its type.
+    pub fn dispatch(&self, event: &WebhookEvent) -> Result<(), WebhookError> {
+        let webhook_ids = self
+            .lock_state()?
+            .webhooks
+            .values()
+            .filter(|webhook| webhook.accepts(event.event_type()))
+            .map(|webhook| webhook.id().to_string())
+            .collect::<Vec<_>>();
+
+        let timestamp = millis_since_epoch(event.timestamp());
+        for webhook_id in webhook_ids {
+            let delivery_id = Uuid::new_v4().to_string();
+            let body = serde_json::to_vec(&DeliveryBody {
+                delivery_id: &delivery_id,
+                event_type: event.event_type(),
+                timestamp,
+                payload: event.payload(),
+            })
+            .map_err(|err| {
+                WebhookError::internal_with_source("Unable to serialize webhook event", err)
+            })?;
+
+            self.send(PendingDelivery {
+                delivery_id,
+                webhook_id,
+                event_type: event.event_type().to_string(),
+                body,
+                attempts: 0,
+                due: Instant::now(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists the delivery log, oldest first, optionally restricted to a single webhook.
+    pub fn list_deliveries(
+        &self,
+        webhook_id: Option<&str>,
+    ) -> Result<Vec<DeliveryRecord>, WebhookError> {
+        Ok(self
+            .lock_state()?
+            .deliveries
+            .iter()
+            .filter(|record| webhook_id.map_or(true, |id| record.webhook_id == id))
+            .cloned()
+            .collect())
+    }
+
+    /// Lists the deliveries that exhausted their retry attempts, oldest first.
+    pub fn list_dead_letters(&self) -> Result<Vec<DeadLetter>, WebhookError> {
+        Ok(self.lock_state()?.dead_letters.iter().cloned().collect())
+    }
+
+    /// Removes a delivery from the dead-letter list and queues it for delivery again, with a
+    /// fresh set of retry attempts.
+    pub fn retry_dead_letter(&self, delivery_id: &str) -> Result<(), WebhookError> {
+        let dead_letter = {
+            let mut state = self.lock_state()?;
+            let index = state
+                .dead_letters
+                .iter()
+                .position(|dead_letter| dead_letter.delivery_id == delivery_id)
+                .ok_or_else(|| WebhookError::NotFound(format!("dead letter {}", delivery_id)))?;
+            if !state
+                .webhooks
+                .contains_key(&state.dead_letters[index].webhook_id)
+            {
+                return Err(WebhookError::NotFound(format!(
+                    "webhook {}",
+                    state.dead_letters[index].webhook_id
+                )));
+            }
+            state
+                .dead_letters
+                .remove(index)
+                .ok_or_else(|| WebhookError::internal("Dead letter index out of bounds"))?
+        };
+
+        self.send(PendingDelivery {
+            delivery_id: dead_letter.delivery_id,
+            webhook_id: dead_letter.webhook_id,
+            event_type: dead_letter.event_type,
+            body: dead_letter.body,
+            attempts: 0,
+            due: Instant::now(),
+        })
+    }
+
+    fn send(&self, delivery: PendingDelivery) -> Result<(), WebhookError> {
+        self.sender
+            .send(DispatcherMessage::Deliver(delivery))
+            .map_err(|_| WebhookError::internal("Webhook dispatcher has shut down"))
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<DispatcherState>, WebhookError> {
+        lock(&self.state)
+    }
+}
+
+/// Handle for signaling the `WebhookDispatcher` to shut down.
+///
+/// Deliveries that are still pending when the dispatcher shuts down are discarded.
+#[derive(Clone)]
+pub struct WebhookDispatcherShutdownHandle {
+    sender: Sender<DispatcherMessage>,
+}
+
+impl WebhookDispatcherShutdownHandle {
+    pub fn shutdown(&self) {
+        if self.sender.send(DispatcherMessage::Shutdown).is_err() {
+            debug!("Webhook dispatcher has already shut down");
+        }
+    }
+}
+
+/// Hands due deliveries to the workers, at most one at a time for each webhook, and holds the
+/// deliveries that are waiting for their retry delay or for their webhook's previous delivery.
+fn run_scheduler(receiver: &Receiver<DispatcherMessage>, job_sender: Sender<PendingDelivery>) {
+    let mut pending: BinaryHeap<PendingDelivery> = BinaryHeap::new();
+    // The due deliveries for each webhook that has a delivery in flight, oldest first
+    let mut in_flight: HashMap<String, VecDeque<PendingDelivery>> = HashMap::new();
+    loop {
+        let next = match pending.peek() {
+            // All of the workers are busy, so due deliveries wait for one to finish
+            Some(_) if in_flight.len() >= DELIVERY_WORKERS => {
+                receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            }
+            Some(delivery) => {
+                receiver.recv_timeout(delivery.due.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match next {
+            Ok(DispatcherMessage::Deliver(delivery)) => pending.push(delivery),
+            Ok(DispatcherMessage::Attempted { webhook_id, retry }) => {
+                if let Some(retry) = retry {
+                    pending.push(retry);
+                }
+                // The webhook's waiting deliveries are already due, so the oldest is handed out
+                // next and the rest wait for it again
+                if let Some(waiting) = in_flight.remove(&webhook_id) {
+                    pending.extend(waiting);
+                }
+            }
+            Ok(DispatcherMessage::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => (),
+        }
+
+        let now = Instant::now();
+        while in_flight.len() < DELIVERY_WORKERS
+            && pending.peek().map_or(false, |delivery| delivery.due <= now)
+        {
+            if let Some(delivery) = pending.pop() {
+                if let Some(waiting) = in_flight.get_mut(&delivery.webhook_id) {
+                    waiting.push_back(delivery);
+                    continue;
+                }
+                in_flight.insert(delivery.webhook_id.clone(), VecDeque::new());
+                if job_sender.send(delivery).is_err() {
+                    error!("Webhook dispatcher stopping: all delivery workers have stopped");
+                    return;
+                }
+            }
+        }
+    }
+
+    let discarded = pending.len() + in_flight.values().map(VecDeque::len).sum::<usize>();
+    if discarded > 0 {
+        warn!(
+            "Discarding {} pending webhook deliveries on shutdown",
+            discarded
+        );
+    }
+    debug!("Webhook dispatcher shut down");
+}
+
+/// Makes the deliveries handed out by the scheduler until it shuts down.
+fn run_worker(
+    client: &Client,
+    state: &Mutex<DispatcherState>,
+    receiver: &Receiver<PendingDelivery>,
+    sender: &Sender<DispatcherMessage>,
+    retry_policy: &RetryPolicy,
+) {
+    for delivery in receiver {
+        let webhook_id = delivery.webhook_id.clone();
+        let retry = match attempt_delivery(client, state, retry_policy, delivery) {
+            Ok(retry) => retry,
+            Err(err) => {
+                error!("Webhook delivery failed: {}", err);
+                None
+            }
+        };
+        if sender
+            .send(DispatcherMessage::Attempted { webhook_id, retry })
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Makes one attempt at a delivery, returning the delivery if it should be retried.
+fn attempt_delivery(
+    client: &Client,
+    state: &Mutex<DispatcherState>,
+    retry_policy: &RetryPolicy,
+    mut delivery: PendingDelivery,
+) -> Result<Option<PendingDelivery>, WebhookError> {
+    let webhook = match lock(state)?.webhooks.get(&delivery.webhook_id).cloned() {
+        Some(webhook) => webhook,
+        None => {
+            debug!(
+                "Dropping delivery {}; webhook {} has been removed",
+                delivery.delivery_id, delivery.webhook_id
+            );
+            return Ok(None);
+        }
+    };
+
+    delivery.attempts += 1;
+    let result = post(client, &webhook, &delivery);
+
+    let mut state = lock(state)?;
+    let (status, status_code, error) = match result {
+        Ok(status_code) => (DeliveryStatus::Delivered, Some(status_code), None),
+        Err((status_code, error)) if delivery.attempts >= retry_policy.max_attempts() => {
+            (DeliveryStatus::DeadLettered, status_code, Some(error))
+        }
+        Err((status_code, error)) => (DeliveryStatus::Retrying, status_code, Some(error)),
+    };
+    let timestamp = millis_since_epoch(SystemTime::now());
+
+    state.record(DeliveryRecord {
+        delivery_id: delivery.delivery_id.clone(),
+        webhook_id: delivery.webhook_id.clone(),
+        event_type: delivery.event_type.clone(),
+        attempt: delivery.attempts,
+        status,
+        status_code,
+        error: error.clone(),
+        timestamp,
+    });
+
+    match status {
+        DeliveryStatus::Delivered => Ok(None),
+        DeliveryStatus::Retrying => {
+            delivery.due = Instant::now() + retry_policy.delay_after(delivery.attempts);
+            Ok(Some(delivery))
+        }
+        DeliveryStatus::DeadLettered => {
+            warn!(
+                "Delivery {} to webhook {} failed after {} attempts",
+                delivery.delivery_id, delivery.webhook_id, delivery.attempts
+            );
+            state.add_dead_letter(DeadLetter {
+                delivery_id: delivery.delivery_id,
+                webhook_id: delivery.webhook_id,
+                event_type: delivery.event_type,
+                attempts: delivery.attempts,
+                last_error: error.unwrap_or_default(),
+                failed_at: timestamp,
+                body: delivery.body,
+            });
+            Ok(None)
+        }
+    }
+}
+
+/// Posts the delivery, returning the response status on success, or the response status (if
+/// any) and a description of the failure.
+fn post(
+    client: &Client,
+    webhook: &Webhook,
+    delivery: &PendingDelivery,
+) -> Result<u16, (Option<u16>, String)> {
+    let signature =
+        sign_payload(webhook.secret(), &delivery.body).map_err(|err| (None, err.to_string()))?;
+
+    let response = client
+        .post(webhook.url())
+        .header(CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, signature)
+        .header(EVENT_TYPE_HEADER, delivery.event_type.as_str())
+        .header(DELIVERY_ID_HEADER, delivery.delivery_id.as_str())
+        .body(delivery.body.clone())
+        .send()
+        .map_err(|err| (None, err.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        Err((
+            Some(status.as_u16()),
+            format!("webhook responded with status {}", status),
+        ))
+    }
+}
+
+fn lock(state: &Mutex<DispatcherState>) -> Result<MutexGuard<DispatcherState>, WebhookError> {
+    state
+        .lock()
+        .map_err(|_| WebhookError::internal("Webhook dispatcher lock poisoned"))
+}
+
+fn millis_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::webhooks::testing::LocalWebhookReceiver;
+    use crate::webhooks::verify_signature;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    fn fast_retries(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new(
+            max_attempts,
+            Duration::from_millis(10),
+            Duration::from_millis(40),
+        )
+    }
+
+    fn event(event_type: &str) -> WebhookEvent {
+        WebhookEvent::new(
+            event_type,
+            SystemTime::now(),
+            serde_json::json!({ "circuit_id": "abcde-01234" }),
+        )
+    }
+
+    fn wait_until<F: Fn() -> bool>(condition: F) -> bool {
+        let deadline = Instant::now() + WAIT;
+        while Instant::now() < deadline {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+
+    /// Verify that the backoff delay doubles after each attempt and is capped at the maximum.
+    #[test]
+    fn retry_policy_backoff() {
+        let policy = RetryPolicy::new(0, Duration::from_secs(1), Duration::from_secs(5));
+        assert_eq!(policy.max_attempts(), 1);
+        assert_eq!(policy.delay_after(1), Duration::from_secs(1));
+        assert_eq!(policy.delay_after(2), Duration::from_secs(2));
+        assert_eq!(policy.delay_after(3), Duration::from_secs(4));
+        assert_eq!(policy.delay_after(4), Duration::from_secs(5));
+        assert_eq!(policy.delay_after(100), Duration::from_secs(5));
+    }
+
+    /// Verify that an event is delivered, signed, only to webhooks subscribed to its type, and
+    /// that the delivery is logged.
+    #[test]
+    fn delivers_signed_events() {
+        let receiver = LocalWebhookReceiver::start().expect("Unable to start receiver");
+        let (dispatcher, shutdown_handle) =
+            WebhookDispatcher::start(fast_retries(3)).expect("Unable to start dispatcher");
+        dispatcher
+            .add_webhook(
+                Webhook::new(
+                    "hook",
+                    &receiver.url(),
+                    "secret",
+                    vec!["CircuitReady".into()],
+                )
+                .expect("invalid webhook"),
+            )
+            .expect("Unable to add webhook");
+
+        dispatcher
+            .dispatch(&event("ProposalSubmitted"))
+            .expect("Unable to dispatch");
+        dispatcher
+            .dispatch(&event("CircuitReady"))
+            .expect("Unable to dispatch");
+
+        assert!(wait_until(|| dispatcher
+            .list_deliveries(Some("hook"))
+            .expect("Unable to list deliveries")
+            .len()
+            == 1));
+
+        let received = receiver.received();
+        assert_eq!(received.len(), 1);
+        let request = &received[0];
+        assert_eq!(request.header(EVENT_TYPE_HEADER), Some("CircuitReady"));
+        let signature = request
+            .header(SIGNATURE_HEADER)
+            .expect("Missing signature header");
+        assert!(verify_signature("secret", request.body(), signature));
+
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body()).expect("Unable to parse body");
+        assert_eq!(body["event_type"], "CircuitReady");
+        assert_eq!(body["payload"]["circuit_id"], "abcde-01234");
+        assert_eq!(
+            body["delivery_id"].as_str(),
+            request.header(DELIVERY_ID_HEADER)
+        );
+
+        let deliveries = dispatcher
+            .list_deliveries(None)
+            .expect("Unable to list deliveries");
+        assert_eq!(deliveries[0].status(), DeliveryStatus::Delivered);
+        assert_eq!(deliveries[0].status_code(), Some(200));
+
+        shutdown_handle.shutdown();
+    }
+
+    /// Verify that a webhook that does not respond does not hold up the deliveries to other
+    /// webhooks.
+    #[test]
+    fn slow_webhook_does_not_block_others() {
+        // Connections to the listener are accepted by the OS, but never answered
+        let unresponsive = std::net::TcpListener::bind("127.0.0.1:0")
+            .expect("Unable to bind unresponsive listener");
+        let unresponsive_url = format!(
+            "http://{}/",
+            unresponsive
+                .local_addr()
+                .expect("Unable to get listener address")
+        );
+        let receiver = LocalWebhookReceiver::start().expect("Unable to start receiver");
+        let (dispatcher, shutdown_handle) =
+            WebhookDispatcher::start(fast_retries(1)).expect("Unable to start dispatcher");
+        dispatcher
+            .add_webhook(
+                Webhook::new("slow", &unresponsive_url, "secret", vec![]).expect("invalid webhook"),
+            )
+            .expect("Unable to add webhook");
+        dispatcher
+            .add_webhook(
+                Webhook::new("hook", &receiver.url(), "secret", vec![]).expect("invalid webhook"),
+            )
+            .expect("Unable to add webhook");
+
+        for _ in 0..DELIVERY_WORKERS + 1 {
+            dispatcher
+                .dispatch(&event("CircuitReady"))
+                .expect("Unable to dispatch");
+        }
+
+        let received = receiver.wait_for_requests(DELIVERY_WORKERS + 1, WAIT);
+        assert_eq!(received.len(), DELIVERY_WORKERS + 1);
+
+        shutdown_handle.shutdown();
+    }
+
+    /// Verify that failed deliveries are retried with the same delivery ID until they succeed.
+    #[test]
+    fn retries_failed_deliveries() {
+        let receiver = LocalWebhookReceiver::start().expect("Unable to start receiver");
+        receiver.respond_with(&[500, 503]);
+        let (dispatcher, shutdown_handle) =
+            WebhookDispatcher::start(fast_retries(5)).expect("Unable to start dispatcher");
+        dispatcher
+            .add_webhook(
+                Webhook::new("hook", &receiver.url(), "secret", vec![]).expect("invalid webhook"),
+            )
+            .expect("Unable to add webhook");
+
+        dispatcher
+            .dispatch(&event("CircuitReady"))
+            .expect("Unable to dispatch");
+
+        assert!(wait_until(|| dispatcher
+            .list_deliveries(None)
+            .expect("Unable to list deliveries")
+            .len()
+            == 3));
+
+        let statuses = dispatcher
+            .list_deliveries(None)
+            .expect("Unable to list deliveries")
+            .iter()
+            .map(|record| (record.attempt(), record.status(), record.status_code()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, DeliveryStatus::Retrying, Some(500)),
+                (2, DeliveryStatus::Retrying, Some(503)),
+                (3, DeliveryStatus::Delivered, Some(200)),
+            ]
+        );
+
+        let delivery_ids = receiver
+            .received()
+            .iter()
+            .map(|request| request.header(DELIVERY_ID_HEADER).map(String::from))
+            .collect::<Vec<_>>();
+        assert_eq!(delivery_ids.len(), 3);
+        assert!(delivery_ids.iter().all(|id| id == &delivery_ids[0]));
+        assert!(dispatcher
+            .list_dead_letters()
+            .expect("Unable to list dead letters")
+            .is_empty());
+
+        shutdown_handle.shutdown();
+    }
+
+    /// Verify that a delivery is dead-lettered after its attempts are exhausted, and that it can
+    /// be retried from the dead-letter list.
+    #[test]
+    fn dead_letters_and_manual_retry() {
+        let receiver = LocalWebhookReceiver::start().expect("Unable to start receiver");
+        receiver.respond_with(&[500, 500]);
+        let (dispatcher, shutdown_handle) =
+            WebhookDispatcher::start(fast_retries(2)).expect("Unable to start dispatcher");
+        dispatcher
+            .add_webhook(
+                Webhook::new("hook", &receiver.url(), "secret", vec![]).expect("invalid webhook"),
+            )
+            .expect("Unable to add webhook");
+
+        dispatcher
+            .dispatch(&event("CircuitReady"))
+            .expect("Unable to dispatch");
+
+        assert!(wait_until(|| !dispatcher
+            .list_dead_letters()
+            .expect("Unable to list dead letters")
+            .is_empty()));
+
+        let dead_letters = dispatcher
+            .list_dead_letters()
+            .expect("Unable to list dead letters");
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].webhook_id(), "hook");
+        assert_eq!(dead_letters[0].attempts(), 2);
+
+        assert!(matches!(
+            dispatcher.retry_dead_letter("unknown"),
+            Err(WebhookError::NotFound(_))
+        ));
+        dispatcher
+            .retry_dead_letter(dead_letters[0].delivery_id())
+            .expect("Unable to retry dead letter");
+
+        let received = receiver.wait_for_requests(3, WAIT);
+        assert_eq!(received.len(), 3);
+        assert_eq!(
+            received[2].header(DELIVERY_ID_HEADER),
+            Some(dead_letters[0].delivery_id())
+        );
+        assert!(wait_until(|| dispatcher
+            .list_deliveries(None)
+            .expect("Unable to list deliveries")
+            .last()
+            .map_or(false, |record| record.status()
+                == DeliveryStatus::Delivered)));
+        assert!(dispatcher
+            .list_dead_letters()
+            .expect("Unable to list dead letters")
+            .is_empty());
+
+        shutdown_handle.shutdown();
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+/// Errors that may occur while managing or delivering webhooks.
+#[derive(Debug)]
+pub enum WebhookError {
+    /// A webhook definition or request was invalid.
+    InvalidArgument(String),
+    /// The requested webhook or delivery does not exist.
+    NotFound(String),
+    /// An internal error occurred; the message and optional source describe it.
+    InternalError(String, Option<Box<dyn Error + Send>>),
+}
+
+impl WebhookError {
+    pub(crate) fn internal(context: &str) -> Self {
+        WebhookError::InternalError(context.into(), None)
+    }
+
+    pub(crate) fn internal_with_source<E: Error + Send + 'static>(context: &str, err: E) -> Self {
+        WebhookError::InternalError(context.into(), Some(Box::new(err)))
+    }
+}
+
+impl Error for WebhookError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WebhookError::InvalidArgument(_) => None,
+            WebhookError::NotFound(_) => None,
+            WebhookError::InternalError(_, Some(err)) => Some(&**err),
+            WebhookError::InternalError(_, None) => None,
+        }
+    }
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WebhookError::InvalidArgument(msg) => write!(f, "invalid argument: {}", msg),
+            WebhookError::NotFound(msg) => write!(f, "not found: {}", msg),
+            WebhookError::InternalError(msg, Some(err)) => write!(f, "{}: {}", msg, err),
+            WebhookError::InternalError(msg, None) => f.write_str(msg),
+        }
+    }
+}
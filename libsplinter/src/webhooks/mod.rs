@@ -0,0 +1,306 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Outbound webhooks for Splinter events.
+//!
+//! A [`WebhookDispatcher`] holds a set of registered [`Webhook`]s and delivers each
+//! [`WebhookEvent`] it is given to every webhook subscribed to the event's type. Deliveries are
+//! JSON `POST` requests signed with the webhook's shared secret (see [`SIGNATURE_HEADER`]).
+//! Failed deliveries are retried with exponential backoff; deliveries that exhaust their
+//! attempts are moved to a dead-letter list from which they may be retried manually.
+//!
+//! [`WebhookDispatcher`]: struct.WebhookDispatcher.html
+//! [`Webhook`]: struct.Webhook.html
+//! [`WebhookEvent`]: struct.WebhookEvent.html
+//! [`SIGNATURE_HEADER`]: constant.SIGNATURE_HEADER.html
+
+mod admin;
+mod dispatcher;
+mod error;
+#[cfg(feature = "rest-api")]
+mod rest_api;
+pub mod testing;
+
+use std::fs::File;
+use std::path::Path;
+use std::time::SystemTime;
+
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+
+use crate::hex::{parse_hex, to_hex};
+
+pub use admin::WebhookAdminEventSubscriber;
+pub use dispatcher::{
+    DeadLetter, DeliveryRecord, DeliveryStatus, RetryPolicy, WebhookDispatcher,
+    WebhookDispatcherShutdownHandle,
+};
+pub use error::WebhookError;
+#[cfg(all(feature = "authorization", feature = "rest-api"))]
+pub use rest_api::{WEBHOOK_READ_PERMISSION, WEBHOOK_WRITE_PERMISSION};
+
+/// The header carrying the signature of a delivery's body, in the form `sha256=<hex HMAC>`.
+pub const SIGNATURE_HEADER: &str = "X-Splinter-Signature";
+/// The header carrying the type of the delivered event.
+pub const EVENT_TYPE_HEADER: &str = "X-Splinter-Event";
+/// The header carrying the unique ID of a delivery; retries of a delivery share its ID.
+pub const DELIVERY_ID_HEADER: &str = "X-Splinter-Delivery";
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// A registered endpoint that receives events.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Webhook {
+    id: String,
+    url: String,
+    #[serde(skip_serializing)]
+    secret: String,
+    event_types: Vec<String>,
+}
+
+impl Webhook {
+    /// Creates a new webhook.
+    ///
+    /// An empty `event_types` list subscribes the webhook to every event type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookError::InvalidArgument` if the ID or secret is empty, or if the URL is not
+    /// an `http` or `https` URL.
+    pub fn new(
+        id: &str,
+        url: &str,
+        secret: &str,
+        event_types: Vec<String>,
+    ) -> Result<Self, WebhookError> {
+        if id.is_empty() {
+            return Err(WebhookError::InvalidArgument(
+                "webhook ID must not be empty".into(),
+            ));
+        }
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(WebhookError::InvalidArgument(format!(
+                "webhook URL must be an http or https URL: {}",
+                url
+            )));
+        }
+        if secret.is_empty() {
+            return Err(WebhookError::InvalidArgument(
+                "webhook secret must not be empty".into(),
+            ));
+        }
+
+        Ok(Self {
+            id: id.into(),
+            url: url.into(),
+            secret: secret.into(),
+            event_types,
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn event_types(&self) -> &[String] {
+        &self.event_types
+    }
+
+    /// Returns whether or not this webhook is subscribed to the given event type.
+    pub fn accepts(&self, event_type: &str) -> bool {
+        self.event_types.is_empty() || self.event_types.iter().any(|t| t == event_type)
+    }
+}
+
+/// An event to be delivered to the webhooks subscribed to its type.
+#[derive(Clone, Debug)]
+pub struct WebhookEvent {
+    event_type: String,
+    timestamp: SystemTime,
+    payload: serde_json::Value,
+}
+
+impl WebhookEvent {
+    pub fn new(event_type: &str, timestamp: SystemTime, payload: serde_json::Value) -> Self {
+        Self {
+            event_type: event_type.into(),
+            timestamp,
+            payload,
+        }
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    pub fn payload(&self) -> &serde_json::Value {
+        &self.payload
+    }
+}
+
+/// Computes the value of the signature header for the given body and secret.
+pub fn sign_payload(secret: &str, body: &[u8]) -> Result<String, WebhookError> {
+    Ok(format!(
+        "{}{}",
+        SIGNATURE_PREFIX,
+        to_hex(&hmac(secret, body)?)
+    ))
+}
+
+/// Checks a signature header value against the given body and secret.
+///
+/// Receivers should use this, or an equivalent constant-time comparison, to authenticate
+/// deliveries.
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    if !signature.starts_with(SIGNATURE_PREFIX) {
+        return false;
+    }
+    let provided = match parse_hex(&signature[SIGNATURE_PREFIX.len()..]) {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+
+    match hmac(secret, body) {
+        Ok(expected) => expected.len() == provided.len() && memcmp::eq(&expected, &provided),
+        Err(_) => false,
+    }
+}
+
+fn hmac(secret: &str, body: &[u8]) -> Result<Vec<u8>, WebhookError> {
+    let key = PKey::hmac(secret.as_bytes())
+        .map_err(|err| WebhookError::internal_with_source("Unable to create HMAC key", err))?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)
+        .map_err(|err| WebhookError::internal_with_source("Unable to create signer", err))?;
+    signer
+        .update(body)
+        .map_err(|err| WebhookError::internal_with_source("Unable to sign payload", err))?;
+    signer
+        .sign_to_vec()
+        .map_err(|err| WebhookError::internal_with_source("Unable to sign payload", err))
+}
+
+#[derive(Deserialize)]
+struct WebhookDefinition {
+    id: String,
+    url: String,
+    secret: String,
+    #[serde(default)]
+    event_types: Vec<String>,
+}
+
+/// Reads a list of webhooks from a YAML file.
+///
+/// The file contains a sequence of entries with `id`, `url`, `secret` and an optional list of
+/// `event_types`.
+pub fn read_webhooks_file<P: AsRef<Path>>(path: P) -> Result<Vec<Webhook>, WebhookError> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|err| {
+        WebhookError::internal_with_source(
+            &format!("Unable to open webhooks file {}", path.display()),
+            err,
+        )
+    })?;
+    let definitions: Vec<WebhookDefinition> = serde_yaml::from_reader(file).map_err(|err| {
+        WebhookError::InvalidArgument(format!(
+            "unable to parse webhooks file {}: {}",
+            path.display(),
+            err
+        ))
+    })?;
+
+    definitions
+        .into_iter()
+        .map(|def| Webhook::new(&def.id, &def.url, &def.secret, def.event_types))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+
+    use tempdir::TempDir;
+
+    /// Verify that a signature produced by `sign_payload` is accepted by `verify_signature` and
+    /// that a modified body or wrong secret is rejected.
+    #[test]
+    fn sign_and_verify() {
+        let signature = sign_payload("secret", b"{\"a\":1}").expect("Unable to sign");
+        assert!(signature.starts_with("sha256="));
+
+        assert!(verify_signature("secret", b"{\"a\":1}", &signature));
+        assert!(!verify_signature("secret", b"{\"a\":2}", &signature));
+        assert!(!verify_signature("other", b"{\"a\":1}", &signature));
+        assert!(!verify_signature("secret", b"{\"a\":1}", "sha256=zz"));
+    }
+
+    /// Verify that webhooks are validated and that an empty event type list accepts everything.
+    #[test]
+    fn webhook_validation_and_filtering() {
+        assert!(Webhook::new("", "http://localhost", "s", vec![]).is_err());
+        assert!(Webhook::new("hook", "ftp://localhost", "s", vec![]).is_err());
+        assert!(Webhook::new("hook", "http://localhost", "", vec![]).is_err());
+
+        let all = Webhook::new("all", "http://localhost", "s", vec![]).expect("invalid webhook");
+        assert!(all.accepts("CircuitReady"));
+
+        let some = Webhook::new(
+            "some",
+            "https://localhost",
+            "s",
+            vec!["ProposalSubmitted".into()],
+        )
+        .expect("invalid webhook");
+        assert!(some.accepts("ProposalSubmitted"));
+        assert!(!some.accepts("CircuitReady"));
+    }
+
+    /// Verify that webhooks are read from a YAML file and that the secret is not serialized.
+    #[test]
+    fn read_file() {
+        let temp_dir = TempDir::new("read_webhooks_file").expect("Unable to create temp dir");
+        let path = temp_dir.path().join("webhooks.yaml");
+        let mut file = File::create(&path).expect("Unable to create file");
+        writeln!(
+            file,
+            "- id: hook1\n  url: http://localhost:8000/events\n  secret: abc\n  \
+             event_types: [CircuitReady]\n- id: hook2\n  url: https://example.com\n  secret: def"
+        )
+        .expect("Unable to write file");
+
+        let webhooks = read_webhooks_file(&path).expect("Unable to read file");
+        assert_eq!(webhooks.len(), 2);
+        assert_eq!(webhooks[0].id(), "hook1");
+        assert_eq!(webhooks[0].event_types(), &["CircuitReady".to_string()]);
+        assert!(webhooks[1].event_types().is_empty());
+
+        let json = serde_json::to_value(&webhooks[0]).expect("Unable to serialize");
+        assert!(json.get("secret").is_none());
+    }
+}
@@ -0,0 +1,214 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /webhooks` for listing webhooks
+//! * `POST /webhooks` for registering a webhook
+//! * `GET /webhooks/dead_letters` for listing dead-lettered deliveries
+//! * `POST /webhooks/dead_letters/{delivery_id}` for retrying a dead-lettered delivery
+//! * `GET /webhooks/{id}` for fetching a webhook
+//! * `DELETE /webhooks/{id}` for removing a webhook
+//! * `GET /webhooks/{id}/deliveries` for listing a webhook's delivery log
+
+use crate::actix_web::{web, Error, HttpRequest, HttpResponse};
+#[cfg(feature = "authorization")]
+use crate::auth::authorization::Permission;
+use crate::futures::{future::IntoFuture, stream::Stream, Future};
+use crate::protocol;
+use crate::rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource};
+use crate::webhooks::{Webhook, WebhookDispatcher, WebhookError};
+
+#[cfg(feature = "authorization")]
+use super::{WEBHOOK_READ_PERMISSION, WEBHOOK_WRITE_PERMISSION};
+
+#[derive(Serialize)]
+struct ListResponse<T: serde::Serialize> {
+    data: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct NewWebhook {
+    id: String,
+    url: String,
+    secret: String,
+    #[serde(default)]
+    event_types: Vec<String>,
+}
+
+pub fn make_webhooks_resource(dispatcher: WebhookDispatcher) -> Resource {
+    let dispatcher1 = dispatcher.clone();
+    build_resource("/webhooks")
+        .add_method(Method::Get, move |_, _| list_webhooks(&dispatcher))
+        .add_method(Method::Post, move |_, p| {
+            add_webhook(p, dispatcher1.clone())
+        })
+}
+
+pub fn make_webhook_resource(dispatcher: WebhookDispatcher) -> Resource {
+    let dispatcher1 = dispatcher.clone();
+    build_resource("/webhooks/{id}")
+        .add_method(Method::Get, move |r, _| fetch_webhook(r, &dispatcher))
+        .add_method(Method::Delete, move |r, _| remove_webhook(r, &dispatcher1))
+}
+
+pub fn make_webhook_deliveries_resource(dispatcher: WebhookDispatcher) -> Resource {
+    build_resource("/webhooks/{id}/deliveries")
+        .add_method(Method::Get, move |r, _| list_deliveries(r, &dispatcher))
+}
+
+pub fn make_dead_letters_resource(dispatcher: WebhookDispatcher) -> Resource {
+    build_resource("/webhooks/dead_letters")
+        .add_method(Method::Get, move |_, _| list_dead_letters(&dispatcher))
+}
+
+pub fn make_dead_letter_retry_resource(dispatcher: WebhookDispatcher) -> Resource {
+    build_resource("/webhooks/dead_letters/{delivery_id}")
+        .add_method(Method::Post, move |r, _| retry_dead_letter(r, &dispatcher))
+}
+
+fn build_resource(path: &str) -> Resource {
+    let resource = Resource::build(path);
+    #[cfg(feature = "authorization")]
+    let resource = resource
+        .with_permission(Permission::Check(WEBHOOK_WRITE_PERMISSION))
+        .with_method_permission(Method::Get, Permission::Check(WEBHOOK_READ_PERMISSION));
+    resource.add_request_guard(ProtocolVersionRangeGuard::new(
+        protocol::WEBHOOKS_PROTOCOL_MIN,
+        protocol::WEBHOOKS_PROTOCOL_VERSION,
+    ))
+}
+
+fn list_webhooks(
+    dispatcher: &WebhookDispatcher,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let response = match dispatcher.list_webhooks() {
+        Ok(data) => HttpResponse::Ok().json(ListResponse { data }),
+        Err(err) => error_response("Unable to list webhooks", err),
+    };
+    Box::new(response.into_future())
+}
+
+fn add_webhook(
+    payload: web::Payload,
+    dispatcher: WebhookDispatcher,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let webhook = match serde_json::from_slice::<NewWebhook>(&body) {
+                    Ok(new) => Webhook::new(&new.id, &new.url, &new.secret, new.event_types),
+                    Err(err) => Err(WebhookError::InvalidArgument(err.to_string())),
+                };
+                let response = match webhook {
+                    Ok(webhook) => match dispatcher.get_webhook(webhook.id()) {
+                        Ok(Some(_)) => HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                            &format!("Webhook {} already exists", webhook.id()),
+                        )),
+                        Ok(None) => match dispatcher.add_webhook(webhook.clone()) {
+                            Ok(()) => HttpResponse::Ok().json(webhook),
+                            Err(err) => error_response("Unable to add webhook", err),
+                        },
+                        Err(err) => error_response("Unable to add webhook", err),
+                    },
+                    Err(err) => error_response("Unable to add webhook", err),
+                };
+                Ok(response)
+            }),
+    )
+}
+
+fn fetch_webhook(
+    request: HttpRequest,
+    dispatcher: &WebhookDispatcher,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let id = request.match_info().get("id").unwrap_or("");
+    let response = match dispatcher.get_webhook(id) {
+        Ok(Some(webhook)) => HttpResponse::Ok().json(webhook),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse::not_found("Webhook not found")),
+        Err(err) => error_response("Unable to fetch webhook", err),
+    };
+    Box::new(response.into_future())
+}
+
+fn remove_webhook(
+    request: HttpRequest,
+    dispatcher: &WebhookDispatcher,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let id = request.match_info().get("id").unwrap_or("");
+    let response = match dispatcher.remove_webhook(id) {
+        Ok(()) => HttpResponse::Ok().finish(),
+        Err(err) => error_response("Unable to remove webhook", err),
+    };
+    Box::new(response.into_future())
+}
+
+fn list_deliveries(
+    request: HttpRequest,
+    dispatcher: &WebhookDispatcher,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let id = request.match_info().get("id").unwrap_or("");
+    let response = match dispatcher.get_webhook(id) {
+        Ok(Some(_)) => match dispatcher.list_deliveries(Some(id)) {
+            Ok(data) => HttpResponse::Ok().json(ListResponse { data }),
+            Err(err) => error_response("Unable to list deliveries", err),
+        },
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse::not_found("Webhook not found")),
+        Err(err) => error_response("Unable to list deliveries", err),
+    };
+    Box::new(response.into_future())
+}
+
+fn list_dead_letters(
+    dispatcher: &WebhookDispatcher,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let response = match dispatcher.list_dead_letters() {
+        Ok(data) => HttpResponse::Ok().json(ListResponse { data }),
+        Err(err) => error_response("Unable to list dead letters", err),
+    };
+    Box::new(response.into_future())
+}
+
+fn retry_dead_letter(
+    request: HttpRequest,
+    dispatcher: &WebhookDispatcher,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let delivery_id = request.match_info().get("delivery_id").unwrap_or("");
+    let response = match dispatcher.retry_dead_letter(delivery_id) {
+        Ok(()) => HttpResponse::Accepted().finish(),
+        Err(err) => error_response("Unable to retry dead letter", err),
+    };
+    Box::new(response.into_future())
+}
+
+fn error_response(context: &str, err: WebhookError) -> HttpResponse {
+    match err {
+        WebhookError::InvalidArgument(msg) => {
+            HttpResponse::BadRequest().json(ErrorResponse::bad_request(&msg))
+        }
+        WebhookError::NotFound(msg) => {
+            HttpResponse::NotFound().json(ErrorResponse::not_found(&format!("{} not found", msg)))
+        }
+        err => {
+            error!("{}: {}", context, err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        }
+    }
+}
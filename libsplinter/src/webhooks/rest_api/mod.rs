@@ -0,0 +1,69 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for managing webhooks.
+
+#[cfg(feature = "rest-api-actix")]
+mod actix;
+
+use crate::rest_api::{Resource, RestResourceProvider};
+
+use super::WebhookDispatcher;
+
+/// The permission that is required to list and fetch webhooks, deliveries and dead letters
+#[cfg(feature = "authorization")]
+pub const WEBHOOK_READ_PERMISSION: &str = "webhook.read";
+/// The permission that is required to add and remove webhooks and to retry dead letters
+#[cfg(feature = "authorization")]
+pub const WEBHOOK_WRITE_PERMISSION: &str = "webhook.write";
+
+/// The `WebhookDispatcher` provides the following endpoints as REST API resources:
+///
+/// * `GET /webhooks` - List the registered webhooks
+/// * `POST /webhooks` - Register a webhook
+/// * `GET /webhooks/dead_letters` - List the deliveries that exhausted their retry attempts
+/// * `POST /webhooks/dead_letters/{delivery_id}` - Retry a dead-lettered delivery
+/// * `GET /webhooks/{id}` - Fetch a webhook
+/// * `DELETE /webhooks/{id}` - Remove a webhook
+/// * `GET /webhooks/{id}/deliveries` - List the logged delivery attempts for a webhook
+///
+/// If the `authorization` feature is enabled, the `GET` endpoints require the `webhook.read`
+/// permission and the others require the `webhook.write` permission.
+///
+/// These endpoints are only available if the following REST API backend feature is enabled:
+///
+/// * `rest-api-actix`
+impl RestResourceProvider for WebhookDispatcher {
+    fn resources(&self) -> Vec<Resource> {
+        // Allowing unused_mut because resources must be mutable if feature rest-api-actix is
+        // enabled
+        #[allow(unused_mut)]
+        let mut resources = Vec::new();
+
+        // The dead letter resources must be added before the webhook ID resources, which would
+        // otherwise match their paths
+        #[cfg(feature = "rest-api-actix")]
+        {
+            resources.append(&mut vec![
+                actix::make_dead_letters_resource(self.clone()),
+                actix::make_dead_letter_retry_resource(self.clone()),
+                actix::make_webhooks_resource(self.clone()),
+                actix::make_webhook_resource(self.clone()),
+                actix::make_webhook_deliveries_resource(self.clone()),
+            ]);
+        }
+
+        resources
+    }
+}
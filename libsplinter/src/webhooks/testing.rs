@@ -0,0 +1,201 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A local HTTP endpoint for testing webhook deliveries.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::WebhookError;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A request received by a `LocalWebhookReceiver`.
+#[derive(Clone, Debug)]
+pub struct ReceivedRequest {
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl ReceivedRequest {
+    /// Returns the value of a header; header names are case-insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+#[derive(Default)]
+struct ReceiverState {
+    responses: VecDeque<u16>,
+    received: Vec<ReceivedRequest>,
+}
+
+/// A minimal HTTP server on the loopback interface that records every request it receives.
+///
+/// The receiver responds with `200 OK` unless other statuses are queued with `respond_with`. It
+/// shuts down when dropped.
+pub struct LocalWebhookReceiver {
+    address: SocketAddr,
+    state: Arc<Mutex<ReceiverState>>,
+    running: Arc<AtomicBool>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl LocalWebhookReceiver {
+    /// Starts a receiver on an ephemeral port.
+    pub fn start() -> Result<Self, WebhookError> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|err| WebhookError::internal_with_source("Unable to bind receiver", err))?;
+        listener.set_nonblocking(true).map_err(|err| {
+            WebhookError::internal_with_source("Unable to configure receiver", err)
+        })?;
+        let address = listener.local_addr().map_err(|err| {
+            WebhookError::internal_with_source("Unable to get receiver address", err)
+        })?;
+
+        let state = Arc::new(Mutex::new(ReceiverState::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_state = state.clone();
+        let thread_running = running.clone();
+        let join_handle = thread::Builder::new()
+            .name("LocalWebhookReceiver".into())
+            .spawn(move || {
+                while thread_running.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            if let Err(err) = handle_connection(stream, &thread_state) {
+                                error!("Local webhook receiver failed to handle request: {}", err);
+                            }
+                        }
+                        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            thread::sleep(POLL_INTERVAL)
+                        }
+                        Err(err) => {
+                            error!("Local webhook receiver failed: {}", err);
+                            break;
+                        }
+                    }
+                }
+            })
+            .map_err(|err| WebhookError::internal_with_source("Unable to start receiver", err))?;
+
+        Ok(Self {
+            address,
+            state,
+            running,
+            join_handle: Some(join_handle),
+        })
+    }
+
+    /// The URL to register as a webhook's endpoint.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.address)
+    }
+
+    /// Queues response statuses for the next requests; once they are used up, the receiver
+    /// responds with `200 OK` again.
+    pub fn respond_with(&self, statuses: &[u16]) {
+        mutex_lock_unwrap!(self.state)
+            .responses
+            .extend(statuses.iter().copied());
+    }
+
+    /// Returns every request received so far.
+    pub fn received(&self) -> Vec<ReceivedRequest> {
+        mutex_lock_unwrap!(self.state).received.clone()
+    }
+
+    /// Waits until at least `count` requests have been received or the timeout elapses, then
+    /// returns the received requests.
+    pub fn wait_for_requests(&self, count: usize, timeout: Duration) -> Vec<ReceivedRequest> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let received = self.received();
+            if received.len() >= count || Instant::now() >= deadline {
+                return received;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+impl Drop for LocalWebhookReceiver {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<ReceiverState>) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    // Skip the request line; only the headers and body are recorded.
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let mut headers = HashMap::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(index) = header.find(':') {
+            headers.insert(
+                header[..index].trim().to_ascii_lowercase(),
+                header[index + 1..].trim().to_string(),
+            );
+        }
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let status = {
+        let mut state = mutex_lock_unwrap!(state);
+        state.received.push(ReceivedRequest { headers, body });
+        state.responses.pop_front().unwrap_or(200)
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status,
+        if status < 400 { "OK" } else { "Error" }
+    )?;
+    stream.flush()
+}
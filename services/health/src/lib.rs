@@ -12,28 +12,121 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! The health service reports the liveness and readiness of a Splinter node.
+//!
+//! The health service runs on the admin circuit, so that it can exchange pings with the health
+//! services of the other members of each of the node's circuits. Along with the results of these
+//! pings, it reports on the node's peer connections, circuits and services, admin service,
+//! database and remote registries.
+
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+mod ping;
+mod status;
+
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::Permission;
 use splinter::{
     actix_web::HttpResponse,
+    admin::service::AdminCommands,
+    circuit::SplinterState,
     futures::IntoFuture,
+    orchestrator::ServiceLister,
+    peer::PeerManagerConnector,
+    registry::RemoteYamlStatusHandle,
     rest_api::{Method, Resource, RestResourceProvider},
     service::{
         error::{ServiceDestroyError, ServiceError, ServiceStartError, ServiceStopError},
-        Service, ServiceMessageContext, ServiceNetworkRegistry,
+        Service, ServiceMessageContext, ServiceNetworkRegistry, ServiceNetworkSender,
     },
 };
-use std::any::Any;
+
+use crate::ping::{HealthMessage, PingTracker, PING_INTERVAL};
+use crate::status::HealthSources;
+pub use crate::status::{DatabaseCheck, HealthStatus};
+
+const HEALTH_SERVICE_ID_PREFIX: &str = "health::";
+/// The permission that is required to read the detailed health status of the node
+#[cfg(feature = "authorization")]
+pub const HEALTH_READ_PERMISSION: &str = "health.read";
 
 pub struct HealthService {
     service_id: String,
+    node_id: String,
+    sources: Arc<Mutex<HealthSources>>,
+    pings: Arc<Mutex<PingTracker>>,
+    network_sender: Option<Box<dyn ServiceNetworkSender>>,
+    running: Arc<AtomicBool>,
+    ping_thread: Option<JoinHandle<()>>,
 }
 
 impl HealthService {
     pub fn new(node_id: &str) -> Self {
         Self {
-            service_id: format!("health::{}", node_id),
+            service_id: health_service_id(node_id),
+            node_id: node_id.to_string(),
+            sources: Arc::new(Mutex::new(HealthSources::default())),
+            pings: Arc::new(Mutex::new(PingTracker::new())),
+            network_sender: None,
+            running: Arc::new(AtomicBool::new(false)),
+            ping_thread: None,
+        }
+    }
+
+    /// Report on the node's peer connections using the given peer manager connector.
+    pub fn with_peer_connector(self, peer_connector: PeerManagerConnector) -> Self {
+        self.update_sources(|sources| sources.peer_connector = Some(peer_connector));
+        self
+    }
+
+    /// Report on, and ping the members of, the circuits in the given state.
+    pub fn with_splinter_state(self, splinter_state: SplinterState) -> Self {
+        self.update_sources(|sources| sources.splinter_state = Some(splinter_state));
+        self
+    }
+
+    /// Report which of the node's services are running using the orchestrator's service lister.
+    pub fn with_service_lister(self, service_lister: ServiceLister) -> Self {
+        self.update_sources(|sources| sources.service_lister = Some(service_lister));
+        self
+    }
+
+    /// Report on the status of the admin service.
+    pub fn with_admin_commands(self, admin_commands: Box<dyn AdminCommands>) -> Self {
+        self.update_sources(|sources| sources.admin_commands = Some(admin_commands));
+        self
+    }
+
+    /// Report on when the given remote registries were last refreshed.
+    pub fn with_registry_status_handles(
+        self,
+        registry_status_handles: Vec<RemoteYamlStatusHandle>,
+    ) -> Self {
+        self.update_sources(|sources| sources.registry_status_handles = registry_status_handles);
+        self
+    }
+
+    /// Report whether or not the node's database can be reached using the given check.
+    pub fn with_database_check(self, database_check: DatabaseCheck) -> Self {
+        self.update_sources(|sources| sources.database_check = Some(database_check));
+        self
+    }
+
+    fn update_sources<F: FnOnce(&mut HealthSources)>(&self, update: F) {
+        match self.sources.lock() {
+            Ok(mut sources) => update(&mut sources),
+            Err(_) => error!("Health service sources lock poisoned"),
         }
     }
 }
@@ -49,17 +142,63 @@ impl Service for HealthService {
 
     fn start(
         &mut self,
-        _service_registry: &dyn ServiceNetworkRegistry,
+        service_registry: &dyn ServiceNetworkRegistry,
     ) -> Result<(), ServiceStartError> {
+        if self.network_sender.is_some() {
+            return Err(ServiceStartError::AlreadyStarted);
+        }
+
         info!("Starting health service");
+        let network_sender = service_registry.connect(&self.service_id)?;
+
+        let splinter_state = self
+            .sources
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("sources lock poisoned".into()))?
+            .splinter_state
+            .clone();
+
+        // Circuit members can only be pinged if the circuits are known
+        if let Some(splinter_state) = splinter_state {
+            self.running.store(true, Ordering::SeqCst);
+
+            let pinger = Pinger {
+                node_id: self.node_id.clone(),
+                splinter_state,
+                network_sender: network_sender.clone_box(),
+                pings: self.pings.clone(),
+                running: self.running.clone(),
+            };
+            let ping_thread = thread::Builder::new()
+                .name("HealthServicePinger".into())
+                .spawn(move || pinger.run())
+                .map_err(|err| ServiceStartError::Internal(Box::new(err)))?;
+            self.ping_thread = Some(ping_thread);
+        }
+
+        self.network_sender = Some(network_sender);
+
         Ok(())
     }
 
     fn stop(
         &mut self,
-        _service_registry: &dyn ServiceNetworkRegistry,
+        service_registry: &dyn ServiceNetworkRegistry,
     ) -> Result<(), ServiceStopError> {
+        if self.network_sender.take().is_none() {
+            return Err(ServiceStopError::NotStarted);
+        }
+
         info!("Stopping health service");
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(ping_thread) = self.ping_thread.take() {
+            if ping_thread.join().is_err() {
+                error!("Health service ping thread panicked");
+            }
+        }
+
+        service_registry.disconnect(&self.service_id)?;
+
         Ok(())
     }
 
@@ -70,10 +209,38 @@ impl Service for HealthService {
 
     fn handle_message(
         &self,
-        _message_bytes: &[u8],
-        _: &ServiceMessageContext,
+        message_bytes: &[u8],
+        message_context: &ServiceMessageContext,
     ) -> Result<(), ServiceError> {
-        info!("Handling a messge");
+        let message: HealthMessage = serde_json::from_slice(message_bytes)
+            .map_err(|err| ServiceError::InvalidMessageFormat(Box::new(err)))?;
+
+        match message {
+            HealthMessage::Ping { id } => {
+                trace!("Received ping {} from {}", id, message_context.sender);
+                let network_sender = self
+                    .network_sender
+                    .as_ref()
+                    .ok_or(ServiceError::NotStarted)?;
+                let pong = serde_json::to_vec(&HealthMessage::Pong { id })
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                network_sender
+                    .reply(message_context, &pong)
+                    .map_err(|err| ServiceError::UnableToSendMessage(Box::new(err)))?;
+            }
+            HealthMessage::Pong { id } => {
+                let node_id = self
+                    .pings
+                    .lock()
+                    .map_err(|_| ServiceError::PoisonedLock("pings lock poisoned".into()))?
+                    .pong_received(&id);
+                match node_id {
+                    Some(node_id) => trace!("Received pong {} from {}", id, node_id),
+                    None => debug!("Received pong for unknown ping {}", id),
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -84,12 +251,177 @@ impl Service for HealthService {
 
 impl RestResourceProvider for HealthService {
     fn resources(&self) -> Vec<Resource> {
-        vec![make_status_resource()]
+        vec![
+            make_status_resource(
+                self.node_id.clone(),
+                self.sources.clone(),
+                self.pings.clone(),
+            ),
+            make_live_resource(),
+            make_ready_resource(
+                self.node_id.clone(),
+                self.sources.clone(),
+                self.pings.clone(),
+            ),
+        ]
     }
 }
 
-fn make_status_resource() -> Resource {
-    Resource::build("/health/status").add_method(Method::Get, move |_, _| {
-        Box::new(HttpResponse::Ok().finish().into_future())
+fn make_status_resource(
+    node_id: String,
+    sources: Arc<Mutex<HealthSources>>,
+    pings: Arc<Mutex<PingTracker>>,
+) -> Resource {
+    let resource = Resource::build("/health/status");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::Check(HEALTH_READ_PERMISSION));
+    resource.add_method(Method::Get, move |_, _| {
+        match gather_status(&node_id, &sources, &pings) {
+            Some(status) => Box::new(HttpResponse::Ok().json(status).into_future()),
+            None => Box::new(HttpResponse::InternalServerError().finish().into_future()),
+        }
     })
 }
+
+fn make_live_resource() -> Resource {
+    let resource = Resource::build("/health/live");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource.add_method(Method::Get, move |_, _| {
+        Box::new(
+            HttpResponse::Ok()
+                .json(json!({ "live": true }))
+                .into_future(),
+        )
+    })
+}
+
+fn make_ready_resource(
+    node_id: String,
+    sources: Arc<Mutex<HealthSources>>,
+    pings: Arc<Mutex<PingTracker>>,
+) -> Resource {
+    let resource = Resource::build("/health/ready");
+    #[cfg(feature = "authorization")]
+    let resource = resource.with_permission(Permission::AllowUnauthenticated);
+    resource.add_method(Method::Get, move |_, _| {
+        match gather_status(&node_id, &sources, &pings) {
+            Some(status) if status.ready => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({ "ready": true }))
+                    .into_future(),
+            ),
+            Some(status) => Box::new(
+                HttpResponse::ServiceUnavailable()
+                    .json(json!({
+                        "ready": false,
+                        "unready_reasons": status.unready_reasons,
+                    }))
+                    .into_future(),
+            ),
+            None => Box::new(HttpResponse::InternalServerError().finish().into_future()),
+        }
+    })
+}
+
+fn gather_status(
+    node_id: &str,
+    sources: &Mutex<HealthSources>,
+    pings: &Mutex<PingTracker>,
+) -> Option<HealthStatus> {
+    let sources = match sources.lock() {
+        Ok(sources) => sources,
+        Err(_) => {
+            error!("Health service sources lock poisoned");
+            return None;
+        }
+    };
+    let pings = match pings.lock() {
+        Ok(pings) => pings,
+        Err(_) => {
+            error!("Health service pings lock poisoned");
+            return None;
+        }
+    };
+
+    Some(HealthStatus::gather(node_id, &sources, &pings))
+}
+
+fn health_service_id(node_id: &str) -> String {
+    format!("{}{}", HEALTH_SERVICE_ID_PREFIX, node_id)
+}
+
+/// Periodically pings the health services of the other members of the node's circuits.
+struct Pinger {
+    node_id: String,
+    splinter_state: SplinterState,
+    network_sender: Box<dyn ServiceNetworkSender>,
+    pings: Arc<Mutex<PingTracker>>,
+    running: Arc<AtomicBool>,
+}
+
+impl Pinger {
+    fn run(self) {
+        let mut ping_count: u64 = 0;
+        while self.running.load(Ordering::SeqCst) {
+            let members = self.circuit_members();
+
+            match self.pings.lock() {
+                Ok(mut pings) => pings.retain_nodes(&members),
+                Err(_) => {
+                    error!("Health service pings lock poisoned");
+                    break;
+                }
+            }
+
+            for member in members {
+                ping_count += 1;
+                let id = format!("{}-{}", self.node_id, ping_count);
+                if let Err(err) = self.send_ping(&member, &id) {
+                    debug!("Unable to ping {}: {}", member, err);
+                }
+            }
+
+            // Wait for the next round, checking for shutdown every second
+            let next_round = Instant::now() + PING_INTERVAL;
+            while self.running.load(Ordering::SeqCst) && Instant::now() < next_round {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+
+    /// Returns the other nodes which are members of at least one of this node's circuits.
+    fn circuit_members(&self) -> Vec<String> {
+        let circuits = match self.splinter_state.circuits() {
+            Ok(circuits) => circuits,
+            Err(err) => {
+                error!("Unable to list circuits: {}", err);
+                return vec![];
+            }
+        };
+
+        let mut members = circuits
+            .values()
+            .filter(|circuit| circuit.members().contains(&self.node_id))
+            .flat_map(|circuit| circuit.members().to_vec())
+            .filter(|member| member != &self.node_id)
+            .collect::<Vec<_>>();
+        members.sort();
+        members.dedup();
+        members
+    }
+
+    fn send_ping(&self, node_id: &str, id: &str) -> Result<(), String> {
+        let ping = serde_json::to_vec(&HealthMessage::Ping { id: id.to_string() })
+            .map_err(|err| err.to_string())?;
+
+        self.pings
+            .lock()
+            .map_err(|_| "pings lock poisoned".to_string())?
+            .ping_sent(node_id, id);
+
+        self.network_sender
+            .send(&health_service_id(node_id), &ping)
+            .map_err(|err| err.to_string())
+    }
+}
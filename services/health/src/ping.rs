@@ -0,0 +1,160 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pings exchanged between the health services of different nodes.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The amount of time between rounds of pings to the members of this node's circuits.
+pub const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A node is considered reachable if it has replied to a ping within this amount of time.
+const REACHABLE_WINDOW: Duration = Duration::from_secs(30);
+
+/// Messages sent between health services.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HealthMessage {
+    Ping { id: String },
+    Pong { id: String },
+}
+
+/// The results of pinging a single node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PingStatus {
+    pub last_ping: Option<SystemTime>,
+    pub last_pong: Option<SystemTime>,
+    pub round_trip_time: Option<Duration>,
+}
+
+/// Keeps track of the pings sent to other nodes and the replies received from them.
+#[derive(Default)]
+pub struct PingTracker {
+    // Pings that have not been answered yet, keyed by ping ID
+    outstanding: HashMap<String, (String, Instant)>,
+    nodes: HashMap<String, PingStatus>,
+}
+
+impl PingTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a ping with the given ID was sent to the given node.
+    pub fn ping_sent(&mut self, node_id: &str, ping_id: &str) {
+        let now = Instant::now();
+        // Pings that have gone unanswered for longer than the reachable window are abandoned
+        self.outstanding
+            .retain(|_, (_, sent)| now.duration_since(*sent) < REACHABLE_WINDOW);
+        self.outstanding
+            .insert(ping_id.to_string(), (node_id.to_string(), now));
+
+        self.nodes
+            .entry(node_id.to_string())
+            .or_insert_with(PingStatus::default)
+            .last_ping = Some(SystemTime::now());
+    }
+
+    /// Records a reply to the ping with the given ID. Returns the node that replied, or `None` if
+    /// the ping is unknown.
+    pub fn pong_received(&mut self, ping_id: &str) -> Option<String> {
+        let (node_id, sent) = self.outstanding.remove(ping_id)?;
+
+        let status = self
+            .nodes
+            .entry(node_id.clone())
+            .or_insert_with(PingStatus::default);
+        status.last_pong = Some(SystemTime::now());
+        status.round_trip_time = Some(sent.elapsed());
+
+        Some(node_id)
+    }
+
+    /// Returns the results of pinging the given node, if it has been pinged.
+    pub fn status(&self, node_id: &str) -> Option<PingStatus> {
+        self.nodes.get(node_id).cloned()
+    }
+
+    /// Returns whether or not the given node has replied to a ping recently.
+    pub fn is_reachable(&self, node_id: &str) -> bool {
+        self.nodes
+            .get(node_id)
+            .and_then(|status| status.last_pong)
+            .and_then(|last_pong| last_pong.elapsed().ok())
+            .map(|elapsed| elapsed < REACHABLE_WINDOW)
+            .unwrap_or(false)
+    }
+
+    /// Forgets the nodes that are not in the given list, such as when they no longer share a
+    /// circuit with this node.
+    pub fn retain_nodes(&mut self, node_ids: &[String]) {
+        self.nodes.retain(|node_id, _| node_ids.contains(node_id));
+        self.outstanding
+            .retain(|_, (node_id, _)| node_ids.contains(node_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that a pong is matched to the ping it answers and marks the node as reachable.
+    #[test]
+    fn test_ping_and_pong() {
+        let mut tracker = PingTracker::new();
+
+        tracker.ping_sent("node_a", "ping_1");
+        assert!(!tracker.is_reachable("node_a"));
+        let status = tracker.status("node_a").expect("No status for node_a");
+        assert!(status.last_ping.is_some());
+        assert!(status.last_pong.is_none());
+
+        assert_eq!(tracker.pong_received("ping_1"), Some("node_a".to_string()));
+        assert!(tracker.is_reachable("node_a"));
+        let status = tracker.status("node_a").expect("No status for node_a");
+        assert!(status.last_pong.is_some());
+        assert!(status.round_trip_time.is_some());
+
+        // A second reply to the same ping is unknown
+        assert_eq!(tracker.pong_received("ping_1"), None);
+        assert!(!tracker.is_reachable("node_b"));
+    }
+
+    /// Test that nodes which are no longer retained are forgotten.
+    #[test]
+    fn test_retain_nodes() {
+        let mut tracker = PingTracker::new();
+        tracker.ping_sent("node_a", "ping_1");
+        tracker.ping_sent("node_b", "ping_2");
+
+        tracker.retain_nodes(&["node_a".to_string()]);
+
+        assert!(tracker.status("node_a").is_some());
+        assert!(tracker.status("node_b").is_none());
+        assert_eq!(tracker.pong_received("ping_2"), None);
+    }
+
+    /// Test that health messages are serialized with a type tag.
+    #[test]
+    fn test_health_message_serialization() {
+        let ping = HealthMessage::Ping { id: "1".into() };
+        let json = serde_json::to_string(&ping).expect("Failed to serialize ping");
+        assert_eq!(json, r#"{"type":"ping","id":"1"}"#);
+        assert_eq!(
+            serde_json::from_str::<HealthMessage>(&json).expect("Failed to deserialize ping"),
+            ping
+        );
+    }
+}
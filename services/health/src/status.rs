@@ -0,0 +1,295 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The health status report of a node.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use splinter::admin::service::{AdminCommands, AdminServiceStatus};
+use splinter::circuit::SplinterState;
+use splinter::orchestrator::ServiceLister;
+use splinter::peer::PeerManagerConnector;
+use splinter::registry::RemoteYamlStatusHandle;
+
+use crate::ping::PingTracker;
+
+/// A check of whether or not the node's database can be reached.
+pub type DatabaseCheck = Box<dyn Fn() -> Result<(), String> + Send>;
+
+/// The components of the node which the health service reports on. Any component that is not
+/// provided is left out of the report.
+#[derive(Default)]
+pub struct HealthSources {
+    pub peer_connector: Option<PeerManagerConnector>,
+    pub splinter_state: Option<SplinterState>,
+    pub service_lister: Option<ServiceLister>,
+    pub admin_commands: Option<Box<dyn AdminCommands>>,
+    pub registry_status_handles: Vec<RemoteYamlStatusHandle>,
+    pub database_check: Option<DatabaseCheck>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthStatus {
+    pub node_id: String,
+    pub live: bool,
+    pub ready: bool,
+    pub unready_reasons: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_service: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<DatabaseStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peers: Option<PeersStatus>,
+    pub registries: Vec<RegistryStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuits: Option<Vec<CircuitStatus>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseStatus {
+    pub reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeersStatus {
+    pub connected: Vec<String>,
+    pub unreferenced: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegistryStatus {
+    pub url: String,
+    pub last_refresh_successful: bool,
+    pub last_successful_refresh: Option<u64>,
+    pub seconds_since_last_successful_refresh: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_refresh_error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CircuitStatus {
+    pub circuit_id: String,
+    pub members: Vec<MemberStatus>,
+    pub services: Vec<ServiceStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemberStatus {
+    pub node_id: String,
+    pub connected: bool,
+    pub reachable: bool,
+    pub last_ping: Option<u64>,
+    pub last_pong: Option<u64>,
+    pub round_trip_time_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceStatus {
+    pub service_id: String,
+    pub service_type: String,
+    pub running: bool,
+}
+
+impl HealthStatus {
+    /// Gathers the health status of the node from the given components.
+    ///
+    /// The node is ready if the admin service is running and the database can be reached.
+    pub fn gather(node_id: &str, sources: &HealthSources, pings: &PingTracker) -> Self {
+        let mut unready_reasons = vec![];
+
+        let admin_service = sources.admin_commands.as_ref().map(|admin_commands| {
+            match admin_commands.admin_service_status() {
+                Ok(AdminServiceStatus::Running) => "running".to_string(),
+                Ok(status) => {
+                    unready_reasons.push("The admin service is not running".to_string());
+                    admin_status_name(status).to_string()
+                }
+                Err(err) => {
+                    unready_reasons.push(format!("Unable to get admin service status: {}", err));
+                    "unknown".to_string()
+                }
+            }
+        });
+
+        let database = sources.database_check.as_ref().map(|check| match check() {
+            Ok(()) => DatabaseStatus {
+                reachable: true,
+                error: None,
+            },
+            Err(err) => {
+                unready_reasons.push("The database cannot be reached".to_string());
+                DatabaseStatus {
+                    reachable: false,
+                    error: Some(err),
+                }
+            }
+        });
+
+        let connected_peers = sources.peer_connector.as_ref().map(|peer_connector| {
+            let connected = peer_connector.list_peers().unwrap_or_else(|err| {
+                error!("Unable to list peers: {}", err);
+                vec![]
+            });
+            let unreferenced = peer_connector
+                .list_unreferenced_peers()
+                .unwrap_or_else(|err| {
+                    error!("Unable to list unreferenced peers: {}", err);
+                    vec![]
+                });
+            PeersStatus {
+                connected,
+                unreferenced,
+            }
+        });
+
+        let registries = sources
+            .registry_status_handles
+            .iter()
+            .filter_map(|handle| match registry_status(handle) {
+                Ok(status) => Some(status),
+                Err(err) => {
+                    error!("Unable to get remote registry status: {}", err);
+                    None
+                }
+            })
+            .collect();
+
+        let circuits = sources.splinter_state.as_ref().map(|state| {
+            circuit_statuses(
+                node_id,
+                state,
+                sources.service_lister.as_ref(),
+                connected_peers.as_ref(),
+                pings,
+            )
+        });
+
+        HealthStatus {
+            node_id: node_id.to_string(),
+            live: true,
+            ready: unready_reasons.is_empty(),
+            unready_reasons,
+            admin_service,
+            database,
+            peers: connected_peers,
+            registries,
+            circuits,
+        }
+    }
+}
+
+fn admin_status_name(status: AdminServiceStatus) -> &'static str {
+    match status {
+        AdminServiceStatus::NotRunning => "not_running",
+        AdminServiceStatus::Running => "running",
+        AdminServiceStatus::ShuttingDown => "shutting_down",
+        AdminServiceStatus::Shutdown => "shutdown",
+    }
+}
+
+fn registry_status(
+    handle: &RemoteYamlStatusHandle,
+) -> Result<RegistryStatus, splinter::registry::RegistryError> {
+    let last_successful_refresh = handle.last_successful_refresh()?;
+    Ok(RegistryStatus {
+        url: handle.url()?,
+        last_refresh_successful: handle.last_refresh_successful()?,
+        last_successful_refresh: last_successful_refresh.and_then(to_unix_seconds),
+        seconds_since_last_successful_refresh: last_successful_refresh
+            .and_then(|time| time.elapsed().ok())
+            .map(|elapsed| elapsed.as_secs()),
+        last_refresh_error: handle.last_refresh_error()?,
+    })
+}
+
+fn circuit_statuses(
+    node_id: &str,
+    state: &SplinterState,
+    service_lister: Option<&ServiceLister>,
+    peers: Option<&PeersStatus>,
+    pings: &PingTracker,
+) -> Vec<CircuitStatus> {
+    let circuits = match state.circuits() {
+        Ok(circuits) => circuits,
+        Err(err) => {
+            error!("Unable to list circuits: {}", err);
+            return vec![];
+        }
+    };
+
+    circuits
+        .into_iter()
+        .map(|(circuit_id, circuit)| {
+            let members = circuit
+                .members()
+                .to_vec()
+                .into_iter()
+                .filter(|member| member != node_id)
+                .map(|member| {
+                    let ping_status = pings.status(&member).unwrap_or_default();
+                    MemberStatus {
+                        connected: peers
+                            .map(|peers| peers.connected.contains(&member))
+                            .unwrap_or(false),
+                        reachable: pings.is_reachable(&member),
+                        last_ping: ping_status.last_ping.and_then(to_unix_seconds),
+                        last_pong: ping_status.last_pong.and_then(to_unix_seconds),
+                        round_trip_time_ms: ping_status
+                            .round_trip_time
+                            .map(|rtt| rtt.as_millis() as u64),
+                        node_id: member,
+                    }
+                })
+                .collect();
+
+            let running_services = service_lister
+                .map(|lister| {
+                    lister
+                        .list_services(vec![circuit_id.clone()], vec![])
+                        .unwrap_or_else(|err| {
+                            error!("Unable to list services of {}: {}", circuit_id, err);
+                            vec![]
+                        })
+                })
+                .unwrap_or_default();
+
+            let services = circuit
+                .roster()
+                .iter()
+                .filter(|service| service.allowed_nodes().iter().any(|node| node == node_id))
+                .map(|service| ServiceStatus {
+                    service_id: service.service_id().to_string(),
+                    service_type: service.service_type().to_string(),
+                    running: running_services
+                        .iter()
+                        .any(|running| running.service_id == service.service_id()),
+                })
+                .collect();
+
+            CircuitStatus {
+                circuit_id,
+                members,
+                services,
+            }
+        })
+        .collect()
+}
+
+fn to_unix_seconds(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs())
+}
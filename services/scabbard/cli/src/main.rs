@@ -18,6 +18,8 @@ extern crate log;
 mod error;
 mod key;
 
+#[cfg(feature = "client-tls")]
+use std::env;
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::PathBuf;
@@ -44,11 +46,20 @@ use sabre_sdk::{
     },
     protos::FromBytes,
 };
+#[cfg(feature = "client-tls")]
+use scabbard::client::ScabbardClientBuilder;
 use scabbard::client::{ScabbardClient, ServiceId};
 use transact::contract::archive::{default_scar_path, SmartContractArchive};
 
 use error::CliError;
 
+#[cfg(feature = "client-tls")]
+const SPLINTER_REST_API_CA_CERT_ENV: &str = "SPLINTER_REST_API_CA_CERT";
+#[cfg(feature = "client-tls")]
+const SPLINTER_REST_API_CLIENT_CERT_ENV: &str = "SPLINTER_REST_API_CLIENT_CERT";
+#[cfg(feature = "client-tls")]
+const SPLINTER_REST_API_CLIENT_KEY_ENV: &str = "SPLINTER_REST_API_CLIENT_KEY";
+
 fn main() {
     if let Err(e) = run() {
         error!("ERROR: {}", e);
@@ -734,7 +745,7 @@ fn run() -> Result<(), CliError> {
         ("contract", Some(matches)) => match matches.subcommand() {
             ("upload", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -785,7 +796,7 @@ fn run() -> Result<(), CliError> {
             }
             ("list", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -835,7 +846,7 @@ fn run() -> Result<(), CliError> {
             }
             ("show", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -880,7 +891,7 @@ fn run() -> Result<(), CliError> {
         },
         ("exec", Some(matches)) => {
             let url = matches.value_of("url").expect("default not set for --url");
-            let client = ScabbardClient::new(url);
+            let client = new_client(url)?;
 
             let full_service_id = matches
                 .value_of("service-id")
@@ -940,7 +951,7 @@ fn run() -> Result<(), CliError> {
         ("ns", Some(matches)) => match matches.subcommand() {
             ("create", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -981,7 +992,7 @@ fn run() -> Result<(), CliError> {
             }
             ("update", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1022,7 +1033,7 @@ fn run() -> Result<(), CliError> {
             }
             ("delete", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1059,7 +1070,7 @@ fn run() -> Result<(), CliError> {
         },
         ("perm", Some(matches)) => {
             let url = matches.value_of("url").expect("default not set for --url");
-            let client = ScabbardClient::new(url);
+            let client = new_client(url)?;
 
             let full_service_id = matches
                 .value_of("service-id")
@@ -1112,7 +1123,7 @@ fn run() -> Result<(), CliError> {
         ("cr", Some(matches)) => match matches.subcommand() {
             ("create", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1153,7 +1164,7 @@ fn run() -> Result<(), CliError> {
             }
             ("update", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1194,7 +1205,7 @@ fn run() -> Result<(), CliError> {
             }
             ("delete", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1232,7 +1243,7 @@ fn run() -> Result<(), CliError> {
         ("sp", Some(matches)) => match matches.subcommand() {
             ("create", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1276,7 +1287,7 @@ fn run() -> Result<(), CliError> {
             }
             ("update", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1320,7 +1331,7 @@ fn run() -> Result<(), CliError> {
             }
             ("delete", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1362,7 +1373,7 @@ fn run() -> Result<(), CliError> {
         ("state", Some(matches)) => match matches.subcommand() {
             ("root", Some(matches)) => {
                 let url = matches.value_of("url").expect("default not set for --url");
-                let client = ScabbardClient::new(url);
+                let client = new_client(url)?;
 
                 let full_service_id = matches
                     .value_of("service-id")
@@ -1381,6 +1392,46 @@ fn run() -> Result<(), CliError> {
     }
 }
 
+/// Creates the client for the scabbard services of the Splinter node at the given URL.
+///
+/// If the REST API is served over HTTPS with a certificate from a private certificate authority,
+/// the authority's certificate (*.pem file) may be set with the `SPLINTER_REST_API_CA_CERT`
+/// environment variable. If the REST API requires client certificates, the client certificate and
+/// its private key (*.pem files) are set with the `SPLINTER_REST_API_CLIENT_CERT` and
+/// `SPLINTER_REST_API_CLIENT_KEY` environment variables.
+#[cfg(feature = "client-tls")]
+fn new_client(url: &str) -> Result<ScabbardClient, CliError> {
+    let mut builder = ScabbardClientBuilder::new().with_url(url);
+
+    if let Ok(ca_cert) = env::var(SPLINTER_REST_API_CA_CERT_ENV) {
+        builder = builder.with_ca_cert(&ca_cert);
+    }
+
+    match (
+        env::var(SPLINTER_REST_API_CLIENT_CERT_ENV),
+        env::var(SPLINTER_REST_API_CLIENT_KEY_ENV),
+    ) {
+        (Ok(cert), Ok(key)) => {
+            builder = builder.with_client_cert(&cert, &key);
+        }
+        (Err(_), Err(_)) => (),
+        _ => {
+            return Err(CliError::InvalidArgument(format!(
+                "{} and {} must be set together",
+                SPLINTER_REST_API_CLIENT_CERT_ENV, SPLINTER_REST_API_CLIENT_KEY_ENV
+            )))
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Creates the client for the scabbard services of the Splinter node at the given URL.
+#[cfg(not(feature = "client-tls"))]
+fn new_client(url: &str) -> Result<ScabbardClient, CliError> {
+    Ok(ScabbardClient::new(url))
+}
+
 fn setup_logging(log_level: log::LevelFilter) -> Result<(), CliError> {
     let mut log_spec_builder = LogSpecBuilder::new();
     log_spec_builder.default(log_level);
@@ -16,12 +16,20 @@
 
 mod error;
 
+#[cfg(feature = "client-tls")]
+use std::fs;
+#[cfg(feature = "client-tls")]
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime};
 
 use reqwest::{
     blocking::{Client, RequestBuilder, Response},
     Url,
 };
+#[cfg(feature = "client-tls")]
+use reqwest::{Certificate, Identity};
+#[cfg(feature = "client-tls")]
+use splinter::tls::load_client_identity;
 use transact::{protocol::batch::Batch, protos::IntoBytes};
 
 use super::hex::parse_hex;
@@ -32,13 +40,17 @@ pub use error::ScabbardClientError;
 /// A client that can be used to interact with scabbard services on a Splinter node.
 pub struct ScabbardClient {
     url: String,
+    client: Client,
 }
 
 impl ScabbardClient {
     /// Create a new `ScabbardClient` with the given base `url`. The URL should be the bind endpoint
     /// of the Splinter REST API; it should not include the path to the scabbard service itself.
     pub fn new(url: &str) -> Self {
-        Self { url: url.into() }
+        Self {
+            url: url.into(),
+            client: Client::new(),
+        }
     }
 
     /// Submit the given `batches` to the scabbard service with the given `service_id`. If a `wait`
@@ -68,7 +80,7 @@ impl ScabbardClient {
         let body = batches.into_bytes()?;
 
         debug!("Submitting batches via {}", url);
-        let request = Client::new().post(url).body(body);
+        let request = self.client.post(url).body(body);
         let response = perform_request(request)?;
 
         let batch_link: Link = response.json().map_err(|err| {
@@ -79,7 +91,7 @@ impl ScabbardClient {
         })?;
 
         if let Some(wait) = wait {
-            wait_for_batches(&self.url, &batch_link.link, wait)
+            wait_for_batches(&self.client, &self.url, &batch_link.link, wait)
         } else {
             Ok(())
         }
@@ -112,7 +124,7 @@ impl ScabbardClient {
         ))
         .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
 
-        let request = Client::new().get(url);
+        let request = self.client.get(url);
         let response = request
             .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
             .send()
@@ -176,7 +188,7 @@ impl ScabbardClient {
             url.set_query(Some(&format!("prefix={}", prefix)))
         }
 
-        let request = Client::new().get(url);
+        let request = self.client.get(url);
         let response = request
             .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
             .send()
@@ -217,7 +229,7 @@ impl ScabbardClient {
         ))
         .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
 
-        let request = Client::new().get(url);
+        let request = self.client.get(url);
         let response = request
             .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
             .send()
@@ -246,8 +258,95 @@ impl ScabbardClient {
     }
 }
 
-/// Using the given `base_url` and `batch_link` to check batch statuses, `wait` the given duration
-/// for the batches (encoded in `batch_link`) to commit.
+/// Builds a `ScabbardClient` for a Splinter REST API that is served over HTTPS, optionally
+/// requiring a client certificate.
+#[cfg(feature = "client-tls")]
+#[derive(Default)]
+pub struct ScabbardClientBuilder {
+    url: Option<String>,
+    ca_cert: Option<PathBuf>,
+    client_cert: Option<(PathBuf, PathBuf)>,
+}
+
+#[cfg(feature = "client-tls")]
+impl ScabbardClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the base URL of the client; see `ScabbardClient::new`.
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Trusts the certificate authority in the given PEM file, in addition to the system's
+    /// certificate authorities, when verifying the REST API's certificate.
+    pub fn with_ca_cert(mut self, ca_cert: &str) -> Self {
+        self.ca_cert = Some(ca_cert.into());
+        self
+    }
+
+    /// Presents the certificate in the given PEM file, with its private key, to REST APIs that
+    /// require client certificates.
+    pub fn with_client_cert(mut self, cert: &str, key: &str) -> Self {
+        self.client_cert = Some((cert.into(), key.into()));
+        self
+    }
+
+    /// Builds the `ScabbardClient`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is not set, or if the certificates or key cannot be loaded.
+    pub fn build(self) -> Result<ScabbardClient, ScabbardClientError> {
+        let url = self
+            .url
+            .ok_or_else(|| ScabbardClientError::new("a URL is required to build the client"))?;
+
+        let mut client_builder = Client::builder();
+        if let Some(ca_cert) = &self.ca_cert {
+            let pem = read_file(ca_cert)?;
+            let certificate = Certificate::from_pem(&pem).map_err(|err| {
+                ScabbardClientError::new_with_source("invalid CA certificate", err.into())
+            })?;
+            client_builder = client_builder.add_root_certificate(certificate);
+        }
+        if let Some((cert, key)) = &self.client_cert {
+            client_builder = client_builder.identity(load_identity(cert, key)?);
+        }
+
+        let client = client_builder.build().map_err(|err| {
+            ScabbardClientError::new_with_source("failed to build HTTP client", err.into())
+        })?;
+
+        Ok(ScabbardClient { url, client })
+    }
+}
+
+/// Loads a client identity from the given PEM certificate and private key files.
+#[cfg(feature = "client-tls")]
+fn load_identity(cert: &Path, key: &Path) -> Result<Identity, ScabbardClientError> {
+    let pkcs12 = load_client_identity(cert, key).map_err(|err| {
+        ScabbardClientError::new_with_source("failed to load client identity", err.into())
+    })?;
+
+    Identity::from_pkcs12_der(&pkcs12, "")
+        .map_err(|err| ScabbardClientError::new_with_source("invalid client identity", err.into()))
+}
+
+#[cfg(feature = "client-tls")]
+fn read_file(path: &Path) -> Result<Vec<u8>, ScabbardClientError> {
+    fs::read(path).map_err(|err| {
+        ScabbardClientError::new_with_source(
+            &format!("failed to read {}", path.display()),
+            err.into(),
+        )
+    })
+}
+
+/// Using the given `client`, `base_url` and `batch_link` to check batch statuses, `wait` the given
+/// duration for the batches (encoded in `batch_link`) to commit.
 ///
 /// # Errors
 ///
@@ -257,6 +356,7 @@ impl ScabbardClient {
 /// * One or more batches were invalid
 /// * The `wait` time has elapsed and the batches have not been committed
 fn wait_for_batches(
+    client: &Client,
     base_url: &str,
     batch_link: &str,
     wait: Duration,
@@ -283,7 +383,7 @@ fn wait_for_batches(
         url_with_query.set_query(Some(&query_string));
 
         debug!("Checking batches via {}", url);
-        let request = Client::new().get(url.clone());
+        let request = client.get(url.clone());
         let response = perform_request(request)?;
 
         let batch_infos: Vec<BatchInfo> = response.json().map_err(|err| {
@@ -327,7 +427,7 @@ fn wait_for_batches(
 fn parse_http_url(url: &str) -> Result<Url, ScabbardClientError> {
     let url = Url::parse(url)
         .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
-    if url.scheme() != "http" {
+    if url.scheme() != "http" && url.scheme() != "https" {
         Err(ScabbardClientError::new(&format!(
             "unsupported scheme ({}) in URL: {}",
             url.scheme(),
@@ -526,6 +626,36 @@ mod tests {
         assert_eq!(service_id.service_id(), "ABCD");
     }
 
+    /// Verify that HTTP and HTTPS URLs are accepted, and that other schemes are rejected.
+    #[test]
+    fn http_url_schemes() {
+        assert!(parse_http_url("http://localhost:8080").is_ok());
+        assert!(parse_http_url("https://localhost:8080").is_ok());
+        assert!(parse_http_url("ftp://localhost:8080").is_err());
+    }
+
+    /// Verify that the `ScabbardClientBuilder` requires a URL, and fails when the configured
+    /// certificates cannot be loaded.
+    #[cfg(feature = "client-tls")]
+    #[test]
+    fn client_builder() {
+        assert!(ScabbardClientBuilder::new().build().is_err());
+        assert!(ScabbardClientBuilder::new()
+            .with_url("https://localhost:8080")
+            .build()
+            .is_ok());
+        assert!(ScabbardClientBuilder::new()
+            .with_url("https://localhost:8080")
+            .with_ca_cert("/nonexistent/ca.pem")
+            .build()
+            .is_err());
+        assert!(ScabbardClientBuilder::new()
+            .with_url("https://localhost:8080")
+            .with_client_cert("/nonexistent/client.crt", "/nonexistent/client.key")
+            .build()
+            .is_err());
+    }
+
     /// Verify the `ScabbardClient::submit` method works properly.
     #[test]
     fn submit() {
@@ -19,6 +19,8 @@ use std::time::Duration;
 
 #[cfg(feature = "service-arg-validation")]
 use splinter::service::validation::{ServiceArgValidationError, ServiceArgValidator};
+#[cfg(feature = "webhooks")]
+use splinter::webhooks::WebhookDispatcher;
 use splinter::{
     service::{FactoryCreateError, Service, ServiceFactory},
     signing::SignatureVerifierFactory,
@@ -27,6 +29,8 @@ use splinter::{
 #[cfg(feature = "service-arg-validation")]
 use crate::hex::parse_hex;
 
+#[cfg(feature = "webhooks")]
+use super::webhooks::WebhookStateSubscriber;
 use super::{Scabbard, SERVICE_TYPE};
 
 const DEFAULT_STATE_DB_DIR: &str = "/var/lib/splinter";
@@ -41,6 +45,8 @@ pub struct ScabbardFactory {
     receipt_db_dir: String,
     receipt_db_size: usize,
     signature_verifier_factory: Box<dyn SignatureVerifierFactory>,
+    #[cfg(feature = "webhooks")]
+    webhook_dispatcher: Option<WebhookDispatcher>,
 }
 
 impl ScabbardFactory {
@@ -58,8 +64,17 @@ impl ScabbardFactory {
             receipt_db_dir: receipt_db_dir.unwrap_or_else(|| DEFAULT_RECEIPT_DB_DIR.into()),
             receipt_db_size: receipt_db_size.unwrap_or(DEFAULT_RECEIPT_DB_SIZE),
             signature_verifier_factory,
+            #[cfg(feature = "webhooks")]
+            webhook_dispatcher: None,
         }
     }
+
+    /// Sends the state change events of every created service to the dispatcher's webhooks.
+    #[cfg(feature = "webhooks")]
+    pub fn with_webhook_dispatcher(mut self, webhook_dispatcher: WebhookDispatcher) -> Self {
+        self.webhook_dispatcher = Some(webhook_dispatcher);
+        self
+    }
 }
 
 #[cfg(feature = "service-arg-validation")]
@@ -175,6 +190,19 @@ impl ServiceFactory for ScabbardFactory {
         )
         .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))?;
 
+        #[cfg(feature = "webhooks")]
+        {
+            if let Some(webhook_dispatcher) = &self.webhook_dispatcher {
+                service
+                    .add_state_subscriber(Box::new(WebhookStateSubscriber::new(
+                        webhook_dispatcher.clone(),
+                        circuit_id,
+                        service.service_id(),
+                    )))
+                    .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))?;
+            }
+        }
+
         Ok(Box::new(service))
     }
 
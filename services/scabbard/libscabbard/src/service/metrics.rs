@@ -0,0 +1,48 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus metrics for scabbard, registered in the same registry as Splinter's own metrics.
+
+use std::time::SystemTime;
+
+use lazy_static::lazy_static;
+use splinter::prometheus::{
+    register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec,
+};
+
+lazy_static! {
+    static ref BATCHES: IntCounterVec = register_int_counter_vec!(
+        "scabbard_batches_total",
+        "Batches processed by scabbard services, by final status",
+        &["status"]
+    )
+    .expect("Unable to register scabbard_batches_total");
+    static ref BATCH_DURATION: HistogramVec = register_histogram_vec!(
+        "scabbard_batch_duration_seconds",
+        "Time from batch submission until the batch was committed or found invalid",
+        &["status"]
+    )
+    .expect("Unable to register scabbard_batch_duration_seconds");
+}
+
+/// Records a batch which reached the given final status, along with the time elapsed since it was
+/// submitted.
+pub fn record_batch(status: &str, submitted: SystemTime) {
+    BATCHES.with_label_values(&[status]).inc();
+    if let Ok(elapsed) = submitted.elapsed() {
+        BATCH_DURATION
+            .with_label_values(&[status])
+            .observe(elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9);
+    }
+}
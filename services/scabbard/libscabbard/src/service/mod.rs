@@ -19,10 +19,14 @@
 mod consensus;
 mod error;
 mod factory;
+#[cfg(feature = "metrics")]
+mod metrics;
 #[cfg(feature = "rest-api")]
 mod rest_api;
 mod shared;
 mod state;
+#[cfg(feature = "webhooks")]
+mod webhooks;
 
 use std::any::Any;
 use std::collections::{HashSet, VecDeque};
@@ -55,6 +59,8 @@ pub use state::{
     BatchInfo, BatchInfoIter, BatchStatus, Events, StateChange, StateChangeEvent, StateIter,
 };
 use state::{ScabbardState, StateSubscriber};
+#[cfg(feature = "webhooks")]
+pub use webhooks::STATE_CHANGE_EVENT_TYPE;
 
 const SERVICE_TYPE: &str = "scabbard";
 
@@ -18,12 +18,16 @@ use std::time::Duration;
 
 use actix_web::{web, HttpResponse};
 use futures::IntoFuture;
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::Permission;
 use splinter::{
     rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
     service::rest_api::ServiceEndpoint,
 };
 
 use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::service::rest_api::SCABBARD_READ_PERMISSION;
 use crate::service::{
     rest_api::resources::batch_statuses::BatchInfoResponse, Scabbard, SERVICE_TYPE,
 };
@@ -120,5 +124,7 @@ pub fn make_get_batch_status_endpoint() -> ServiceEndpoint {
             protocol::SCABBARD_BATCH_STATUSES_PROTOCOL_MIN,
             protocol::SCABBARD_PROTOCOL_VERSION,
         ))],
+        #[cfg(feature = "authorization")]
+        permission: Permission::Check(SCABBARD_READ_PERMISSION),
     }
 }
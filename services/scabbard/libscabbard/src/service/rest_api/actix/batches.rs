@@ -19,12 +19,16 @@ use transact::protos::FromBytes;
 
 use actix_web::{web, Error as ActixError, HttpResponse};
 use futures::{stream::Stream, Future, IntoFuture};
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::Permission;
 use splinter::{
     rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
     service::rest_api::ServiceEndpoint,
 };
 
 use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::service::rest_api::SCABBARD_WRITE_PERMISSION;
 use crate::service::{rest_api::resources::batches::BatchLinkResponse, Scabbard, SERVICE_TYPE};
 
 pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
@@ -87,5 +91,7 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
             protocol::SCABBARD_ADD_BATCHES_PROTOCOL_MIN,
             protocol::SCABBARD_PROTOCOL_VERSION,
         ))],
+        #[cfg(feature = "authorization")]
+        permission: Permission::Check(SCABBARD_WRITE_PERMISSION),
     }
 }
@@ -17,12 +17,16 @@ use std::sync::Arc;
 
 use actix_web::{web, HttpResponse};
 use futures::IntoFuture;
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::Permission;
 use splinter::{
     rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
     service::rest_api::ServiceEndpoint,
 };
 
 use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::service::rest_api::SCABBARD_READ_PERMISSION;
 use crate::service::{rest_api::resources::state::StateEntryResponse, Scabbard, SERVICE_TYPE};
 
 pub fn make_get_state_with_prefix_endpoint() -> ServiceEndpoint {
@@ -88,6 +92,8 @@ pub fn make_get_state_with_prefix_endpoint() -> ServiceEndpoint {
             protocol::SCABBARD_LIST_STATE_PROTOCOL_MIN,
             protocol::SCABBARD_PROTOCOL_VERSION,
         ))],
+        #[cfg(feature = "authorization")]
+        permission: Permission::Check(SCABBARD_READ_PERMISSION),
     }
 }
 
@@ -16,12 +16,16 @@ use std::sync::Arc;
 
 use actix_web::HttpResponse;
 use futures::IntoFuture;
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::Permission;
 use splinter::{
     rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
     service::rest_api::ServiceEndpoint,
 };
 
 use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::service::rest_api::SCABBARD_READ_PERMISSION;
 use crate::service::{Scabbard, SERVICE_TYPE};
 
 pub fn make_get_state_root_endpoint() -> ServiceEndpoint {
@@ -56,6 +60,8 @@ pub fn make_get_state_root_endpoint() -> ServiceEndpoint {
             protocol::SCABBARD_STATE_ROOT_PROTOCOL_MIN,
             protocol::SCABBARD_PROTOCOL_VERSION,
         ))],
+        #[cfg(feature = "authorization")]
+        permission: Permission::Check(SCABBARD_READ_PERMISSION),
     }
 }
 
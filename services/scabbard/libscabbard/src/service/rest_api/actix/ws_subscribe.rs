@@ -17,6 +17,8 @@ use std::sync::Arc;
 
 use actix_web::{web, HttpResponse};
 use futures::IntoFuture;
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::Permission;
 use splinter::{
     rest_api::{
         new_websocket_event_sender, ErrorResponse, EventSender, Method, ProtocolVersionRangeGuard,
@@ -26,6 +28,8 @@ use splinter::{
 };
 
 use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::service::rest_api::SCABBARD_READ_PERMISSION;
 use crate::service::{
     error::StateSubscriberError,
     state::{StateChangeEvent, StateSubscriber},
@@ -135,5 +139,7 @@ pub fn make_subscribe_endpoint() -> ServiceEndpoint {
             protocol::SCABBARD_SUBSCRIBE_PROTOCOL_MIN,
             protocol::SCABBARD_PROTOCOL_VERSION,
         ))],
+        #[cfg(feature = "authorization")]
+        permission: Permission::Check(SCABBARD_READ_PERMISSION),
     }
 }
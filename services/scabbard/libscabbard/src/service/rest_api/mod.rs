@@ -15,3 +15,11 @@
 #[cfg(feature = "rest-api-actix")]
 pub mod actix;
 pub mod resources;
+
+/// The permission that is required to read a scabbard service's state and batch statuses, and to
+/// subscribe to its state changes
+#[cfg(feature = "authorization")]
+pub const SCABBARD_READ_PERMISSION: &str = "scabbard.read";
+/// The permission that is required to submit batches to a scabbard service
+#[cfg(feature = "authorization")]
+pub const SCABBARD_WRITE_PERMISSION: &str = "scabbard.write";
@@ -633,6 +633,13 @@ impl BatchHistory {
     fn update_batch_status(&mut self, signature: &str, status: BatchStatus) {
         let batch_info = self.upsert_batch(signature.into(), status);
 
+        #[cfg(feature = "metrics")]
+        {
+            if let BatchStatus::Invalid(_) = batch_info.status {
+                super::metrics::record_batch("invalid", batch_info.timestamp);
+            }
+        }
+
         match batch_info.status {
             BatchStatus::Invalid(_) | BatchStatus::Valid(_) => {
                 self.send_completed_batch_info_to_subscribers(batch_info)
@@ -646,6 +653,8 @@ impl BatchHistory {
             Some(info) => match info.status.clone() {
                 BatchStatus::Valid(txns) => {
                     info.set_status(BatchStatus::Committed(txns));
+                    #[cfg(feature = "metrics")]
+                    super::metrics::record_batch("committed", info.timestamp);
                 }
                 _ => {
                     error!(
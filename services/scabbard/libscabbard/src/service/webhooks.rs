@@ -0,0 +1,66 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Forwards scabbard state change events to webhooks.
+
+use std::time::SystemTime;
+
+use splinter::webhooks::{WebhookDispatcher, WebhookEvent};
+
+use super::error::StateSubscriberError;
+use super::state::{StateChangeEvent, StateSubscriber};
+
+/// The webhook event type of scabbard state change events
+pub const STATE_CHANGE_EVENT_TYPE: &str = "StateChange";
+
+/// A state subscriber that dispatches a scabbard service's state change events to webhooks.
+///
+/// The payload of each event identifies the circuit and service along with the event itself.
+pub(super) struct WebhookStateSubscriber {
+    dispatcher: WebhookDispatcher,
+    circuit_id: String,
+    service_id: String,
+}
+
+impl WebhookStateSubscriber {
+    pub fn new(dispatcher: WebhookDispatcher, circuit_id: &str, service_id: &str) -> Self {
+        Self {
+            dispatcher,
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+        }
+    }
+}
+
+impl StateSubscriber for WebhookStateSubscriber {
+    fn handle_event(&self, event: StateChangeEvent) -> Result<(), StateSubscriberError> {
+        let payload = serde_json::to_value(&event)
+            .map(|event| {
+                serde_json::json!({
+                    "circuit_id": self.circuit_id,
+                    "service_id": self.service_id,
+                    "event": event,
+                })
+            })
+            .map_err(|err| StateSubscriberError::UnableToHandleEvent(err.to_string()))?;
+
+        self.dispatcher
+            .dispatch(&WebhookEvent::new(
+                STATE_CHANGE_EVENT_TYPE,
+                SystemTime::now(),
+                payload,
+            ))
+            .map_err(|err| StateSubscriberError::UnableToHandleEvent(err.to_string()))
+    }
+}
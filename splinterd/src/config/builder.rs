@@ -138,6 +138,30 @@ impl ConfigBuilder {
                 None => None,
             })
             .ok_or_else(|| ConfigError::MissingValue("network endpoints".to_string()))?;
+        #[cfg(feature = "https-bind")]
+        let tls_rest_api_cert =
+            self.partial_configs
+                .iter()
+                .find_map(|p| match p.tls_rest_api_cert() {
+                    Some(v) => Some((get_tls_file_path(&tls_cert_dir.0, &v), p.source())),
+                    None => None,
+                });
+        #[cfg(feature = "https-bind")]
+        let tls_rest_api_key =
+            self.partial_configs
+                .iter()
+                .find_map(|p| match p.tls_rest_api_key() {
+                    Some(v) => Some((get_tls_file_path(&tls_cert_dir.0, &v), p.source())),
+                    None => None,
+                });
+        #[cfg(feature = "https-bind")]
+        let tls_rest_api_client_ca =
+            self.partial_configs
+                .iter()
+                .find_map(|p| match p.tls_rest_api_client_ca() {
+                    Some(v) => Some((get_tls_file_path(&tls_cert_dir.0, &v), p.source())),
+                    None => None,
+                });
         // Iterates over the list of `PartialConfig` objects to find the first config with a value
         // for the specific field. If no value is found, an error is returned.
         Ok(Config {
@@ -291,6 +315,107 @@ impl ConfigBuilder {
                     None => None,
                 })
                 .ok_or_else(|| ConfigError::MissingValue("enable_biome".to_string()))?,
+            #[cfg(feature = "biome-mfa")]
+            biome_mfa_required: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.biome_mfa_required() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                })
+                .ok_or_else(|| ConfigError::MissingValue("biome_mfa_required".to_string()))?,
+            #[cfg(feature = "admin-service-event-store")]
+            admin_event_max_age: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.admin_event_max_age() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                })
+                .ok_or_else(|| ConfigError::MissingValue("admin_event_max_age".to_string()))?,
+            #[cfg(feature = "admin-service-event-store")]
+            admin_event_max_items: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.admin_event_max_items() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                })
+                .ok_or_else(|| ConfigError::MissingValue("admin_event_max_items".to_string()))?,
+            #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+            biome_admins: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.biome_admins() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                })
+                .ok_or_else(|| ConfigError::MissingValue("biome_admins".to_string()))?,
+            #[cfg(feature = "oauth")]
+            oauth_provider: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.oauth_provider() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }),
+            #[cfg(feature = "oauth")]
+            oauth_client_id: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.oauth_client_id() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }),
+            #[cfg(feature = "oauth")]
+            oauth_client_secret: self.partial_configs.iter().find_map(|p| {
+                match p.oauth_client_secret() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "oauth")]
+            oauth_redirect_url: self.partial_configs.iter().find_map(|p| {
+                match p.oauth_redirect_url() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "oauth")]
+            oauth_openid_url: self.partial_configs.iter().find_map(|p| {
+                match p.oauth_openid_url() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_cert,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_key,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_client_ca,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_min_version: self.partial_configs.iter().find_map(|p| {
+                match p.tls_rest_api_min_version() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "authorization")]
+            rest_api_permissions: self.partial_configs.iter().find_map(|p| {
+                match p.rest_api_permissions() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "webhooks")]
+            webhooks: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.webhooks() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }),
             #[cfg(feature = "rest-api-cors")]
             whitelist: self
                 .partial_configs
@@ -307,6 +432,61 @@ impl ConfigBuilder {
                     None => None,
                 })
                 .ok_or_else(|| ConfigError::MissingValue("strict_ref_counts".to_string()))?,
+            #[cfg(feature = "socket-compression")]
+            compression: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.compression() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                })
+                .ok_or_else(|| ConfigError::MissingValue("compression".to_string()))?,
+            #[cfg(feature = "socket-compression")]
+            compression_threshold: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.compression_threshold() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                })
+                .ok_or_else(|| ConfigError::MissingValue("compression threshold".to_string()))?,
+            #[cfg(feature = "circuit-rate-limit")]
+            peer_message_rate_limit: self.partial_configs.iter().find_map(|p| {
+                match p.peer_message_rate_limit() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "circuit-rate-limit")]
+            peer_byte_rate_limit: self.partial_configs.iter().find_map(|p| {
+                match p.peer_byte_rate_limit() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "circuit-rate-limit")]
+            circuit_message_rate_limit: self.partial_configs.iter().find_map(|p| {
+                match p.circuit_message_rate_limit() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "circuit-rate-limit")]
+            circuit_byte_rate_limit: self.partial_configs.iter().find_map(|p| {
+                match p.circuit_byte_rate_limit() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "registry-remote-signing")]
+            registry_publisher_keys: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.registry_publisher_keys() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                })
+                .ok_or_else(|| ConfigError::MissingValue("registry publisher keys".to_string()))?,
         })
     }
 }
@@ -108,6 +108,78 @@ impl<'a> PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                 });
         }
 
+        #[cfg(feature = "biome-mfa")]
+        {
+            partial_config = partial_config.with_biome_mfa_required(
+                if self.matches.is_present("biome_mfa_required") {
+                    Some(true)
+                } else {
+                    None
+                },
+            );
+        }
+
+        #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+        {
+            partial_config = partial_config.with_biome_admins(
+                self.matches
+                    .values_of("biome_admins")
+                    .map(|values| values.map(String::from).collect::<Vec<String>>()),
+            );
+        }
+
+        #[cfg(feature = "oauth")]
+        {
+            partial_config = partial_config
+                .with_oauth_provider(self.matches.value_of("oauth_provider").map(String::from))
+                .with_oauth_client_id(self.matches.value_of("oauth_client_id").map(String::from))
+                .with_oauth_client_secret(
+                    self.matches
+                        .value_of("oauth_client_secret")
+                        .map(String::from),
+                )
+                .with_oauth_redirect_url(
+                    self.matches
+                        .value_of("oauth_redirect_url")
+                        .map(String::from),
+                )
+                .with_oauth_openid_url(self.matches.value_of("oauth_openid_url").map(String::from));
+        }
+
+        #[cfg(feature = "https-bind")]
+        {
+            partial_config = partial_config
+                .with_tls_rest_api_cert(
+                    self.matches.value_of("tls_rest_api_cert").map(String::from),
+                )
+                .with_tls_rest_api_key(self.matches.value_of("tls_rest_api_key").map(String::from))
+                .with_tls_rest_api_client_ca(
+                    self.matches
+                        .value_of("tls_rest_api_client_ca")
+                        .map(String::from),
+                )
+                .with_tls_rest_api_min_version(
+                    self.matches
+                        .value_of("tls_rest_api_min_version")
+                        .map(String::from),
+                );
+        }
+
+        #[cfg(feature = "authorization")]
+        {
+            partial_config = partial_config.with_rest_api_permissions(
+                self.matches
+                    .value_of("rest_api_permissions")
+                    .map(String::from),
+            );
+        }
+
+        #[cfg(feature = "webhooks")]
+        {
+            partial_config =
+                partial_config.with_webhooks(self.matches.value_of("webhooks").map(String::from));
+        }
+
         #[cfg(feature = "database")]
         {
             partial_config =
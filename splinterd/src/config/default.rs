@@ -39,6 +39,12 @@ const REGISTRY_AUTO_REFRESH: u64 = 600; // 600 seconds = 10 minutes
 const REGISTRY_FORCED_REFRESH: u64 = 10; // 10 seconds
 const HEARTBEAT: u64 = 30; // 30 seconds
 const ADMIN_TIMEOUT: u64 = 30; // 30 seconds
+#[cfg(feature = "socket-compression")]
+const COMPRESSION_THRESHOLD: usize = 1024; // 1 KiB
+#[cfg(feature = "admin-service-event-store")]
+const ADMIN_EVENT_MAX_AGE: u64 = 2_592_000; // 2,592,000 seconds = 30 days
+#[cfg(feature = "admin-service-event-store")]
+const ADMIN_EVENT_MAX_ITEMS: u64 = 10_000;
 
 pub struct DefaultPartialConfigBuilder;
 
@@ -84,12 +90,38 @@ impl PartialConfigBuilder for DefaultPartialConfigBuilder {
         {
             partial_config = partial_config.with_enable_biome(Some(false));
         }
+        #[cfg(feature = "biome-mfa")]
+        {
+            partial_config = partial_config.with_biome_mfa_required(Some(false));
+        }
+        #[cfg(feature = "admin-service-event-store")]
+        {
+            partial_config = partial_config
+                .with_admin_event_max_age(Some(ADMIN_EVENT_MAX_AGE))
+                .with_admin_event_max_items(Some(ADMIN_EVENT_MAX_ITEMS));
+        }
+        #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+        {
+            partial_config = partial_config.with_biome_admins(Some(vec![]));
+        }
 
         #[cfg(feature = "database")]
         {
             partial_config = partial_config.with_database(Some(String::from(DATABASE)));
         }
 
+        #[cfg(feature = "socket-compression")]
+        {
+            partial_config = partial_config
+                .with_compression(Some(vec![]))
+                .with_compression_threshold(Some(COMPRESSION_THRESHOLD));
+        }
+
+        #[cfg(feature = "registry-remote-signing")]
+        {
+            partial_config = partial_config.with_registry_publisher_keys(Some(vec![]));
+        }
+
         Ok(partial_config)
     }
 }
@@ -149,6 +181,20 @@ mod tests {
         assert_eq!(config.no_tls(), Some(false));
         #[cfg(feature = "biome")]
         assert_eq!(config.enable_biome(), Some(false));
+        #[cfg(feature = "biome-mfa")]
+        assert_eq!(config.biome_mfa_required(), Some(false));
+        #[cfg(feature = "admin-service-event-store")]
+        assert_eq!(config.admin_event_max_age(), Some(ADMIN_EVENT_MAX_AGE));
+        #[cfg(feature = "admin-service-event-store")]
+        assert_eq!(config.admin_event_max_items(), Some(ADMIN_EVENT_MAX_ITEMS));
+        #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+        assert_eq!(config.biome_admins(), Some(vec![]));
+        #[cfg(feature = "socket-compression")]
+        assert_eq!(config.compression(), Some(vec![]));
+        #[cfg(feature = "socket-compression")]
+        assert_eq!(config.compression_threshold(), Some(COMPRESSION_THRESHOLD));
+        #[cfg(feature = "registry-remote-signing")]
+        assert_eq!(config.registry_publisher_keys(), Some(vec![]));
         // Assert the source is correctly identified for this `PartialConfig` object.
         assert_eq!(config.source(), ConfigSource::Default);
     }
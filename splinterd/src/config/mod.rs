@@ -68,9 +68,53 @@ pub struct Config {
     no_tls: (bool, ConfigSource),
     #[cfg(feature = "biome")]
     enable_biome: (bool, ConfigSource),
+    #[cfg(feature = "biome-mfa")]
+    biome_mfa_required: (bool, ConfigSource),
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_max_age: (u64, ConfigSource),
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_max_items: (u64, ConfigSource),
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    biome_admins: (Vec<String>, ConfigSource),
+    #[cfg(feature = "oauth")]
+    oauth_provider: Option<(String, ConfigSource)>,
+    #[cfg(feature = "oauth")]
+    oauth_client_id: Option<(String, ConfigSource)>,
+    #[cfg(feature = "oauth")]
+    oauth_client_secret: Option<(String, ConfigSource)>,
+    #[cfg(feature = "oauth")]
+    oauth_redirect_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "oauth")]
+    oauth_openid_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_cert: Option<(String, ConfigSource)>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_key: Option<(String, ConfigSource)>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_client_ca: Option<(String, ConfigSource)>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_min_version: Option<(String, ConfigSource)>,
+    #[cfg(feature = "authorization")]
+    rest_api_permissions: Option<(String, ConfigSource)>,
+    #[cfg(feature = "webhooks")]
+    webhooks: Option<(String, ConfigSource)>,
     #[cfg(feature = "rest-api-cors")]
     whitelist: Option<(Vec<String>, ConfigSource)>,
     strict_ref_counts: (bool, ConfigSource),
+    #[cfg(feature = "socket-compression")]
+    compression: (Vec<String>, ConfigSource),
+    #[cfg(feature = "socket-compression")]
+    compression_threshold: (usize, ConfigSource),
+    #[cfg(feature = "circuit-rate-limit")]
+    peer_message_rate_limit: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limit")]
+    peer_byte_rate_limit: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limit")]
+    circuit_message_rate_limit: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limit")]
+    circuit_byte_rate_limit: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "registry-remote-signing")]
+    registry_publisher_keys: (Vec<String>, ConfigSource),
 }
 
 impl Config {
@@ -185,6 +229,101 @@ impl Config {
         self.enable_biome.0
     }
 
+    #[cfg(feature = "biome-mfa")]
+    pub fn biome_mfa_required(&self) -> bool {
+        self.biome_mfa_required.0
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn admin_event_max_age(&self) -> u64 {
+        self.admin_event_max_age.0
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn admin_event_max_items(&self) -> u64 {
+        self.admin_event_max_items.0
+    }
+
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    pub fn biome_admins(&self) -> &[String] {
+        &self.biome_admins.0
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_provider(&self) -> Option<&str> {
+        self.oauth_provider
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_client_id(&self) -> Option<&str> {
+        self.oauth_client_id
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_client_secret(&self) -> Option<&str> {
+        self.oauth_client_secret
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_redirect_url(&self) -> Option<&str> {
+        self.oauth_redirect_url
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_openid_url(&self) -> Option<&str> {
+        self.oauth_openid_url
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_cert(&self) -> Option<&str> {
+        self.tls_rest_api_cert
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_key(&self) -> Option<&str> {
+        self.tls_rest_api_key
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_client_ca(&self) -> Option<&str> {
+        self.tls_rest_api_client_ca
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_min_version(&self) -> Option<&str> {
+        self.tls_rest_api_min_version
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "authorization")]
+    pub fn rest_api_permissions(&self) -> Option<&str> {
+        self.rest_api_permissions
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "webhooks")]
+    pub fn webhooks(&self) -> Option<&str> {
+        self.webhooks.as_ref().map(|(value, _)| value.as_str())
+    }
+
     #[cfg(feature = "rest-api-cors")]
     pub fn whitelist(&self) -> Option<&[String]> {
         if let Some((list, _)) = &self.whitelist {
@@ -198,6 +337,47 @@ impl Config {
         self.strict_ref_counts.0
     }
 
+    #[cfg(feature = "socket-compression")]
+    pub fn compression(&self) -> &[String] {
+        &self.compression.0
+    }
+
+    #[cfg(feature = "socket-compression")]
+    pub fn compression_threshold(&self) -> usize {
+        self.compression_threshold.0
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn peer_message_rate_limit(&self) -> Option<u64> {
+        self.peer_message_rate_limit
+            .as_ref()
+            .map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn peer_byte_rate_limit(&self) -> Option<u64> {
+        self.peer_byte_rate_limit.as_ref().map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn circuit_message_rate_limit(&self) -> Option<u64> {
+        self.circuit_message_rate_limit
+            .as_ref()
+            .map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn circuit_byte_rate_limit(&self) -> Option<u64> {
+        self.circuit_byte_rate_limit
+            .as_ref()
+            .map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "registry-remote-signing")]
+    pub fn registry_publisher_keys(&self) -> &[String] {
+        &self.registry_publisher_keys.0
+    }
+
     pub fn config_dir_source(&self) -> &ConfigSource {
         &self.config_dir.1
     }
@@ -268,7 +448,7 @@ impl Config {
     }
 
     #[cfg(feature = "database")]
-    fn database_source(&self) -> &ConfigSource {
+    pub fn database_source(&self) -> &ConfigSource {
         &self.database.1
     }
 
@@ -309,6 +489,85 @@ impl Config {
         &self.enable_biome.1
     }
 
+    #[cfg(feature = "biome-mfa")]
+    fn biome_mfa_required_source(&self) -> &ConfigSource {
+        &self.biome_mfa_required.1
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    fn admin_event_max_age_source(&self) -> &ConfigSource {
+        &self.admin_event_max_age.1
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    fn admin_event_max_items_source(&self) -> &ConfigSource {
+        &self.admin_event_max_items.1
+    }
+
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    fn biome_admins_source(&self) -> &ConfigSource {
+        &self.biome_admins.1
+    }
+
+    #[cfg(feature = "oauth")]
+    fn oauth_provider_source(&self) -> Option<&ConfigSource> {
+        self.oauth_provider.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "oauth")]
+    fn oauth_client_id_source(&self) -> Option<&ConfigSource> {
+        self.oauth_client_id.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "oauth")]
+    fn oauth_client_secret_source(&self) -> Option<&ConfigSource> {
+        self.oauth_client_secret.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "oauth")]
+    fn oauth_redirect_url_source(&self) -> Option<&ConfigSource> {
+        self.oauth_redirect_url.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "oauth")]
+    fn oauth_openid_url_source(&self) -> Option<&ConfigSource> {
+        self.oauth_openid_url.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "https-bind")]
+    fn tls_rest_api_cert_source(&self) -> Option<&ConfigSource> {
+        self.tls_rest_api_cert.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "https-bind")]
+    fn tls_rest_api_key_source(&self) -> Option<&ConfigSource> {
+        self.tls_rest_api_key.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "https-bind")]
+    fn tls_rest_api_client_ca_source(&self) -> Option<&ConfigSource> {
+        self.tls_rest_api_client_ca
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "https-bind")]
+    fn tls_rest_api_min_version_source(&self) -> Option<&ConfigSource> {
+        self.tls_rest_api_min_version
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "authorization")]
+    fn rest_api_permissions_source(&self) -> Option<&ConfigSource> {
+        self.rest_api_permissions.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "webhooks")]
+    fn webhooks_source(&self) -> Option<&ConfigSource> {
+        self.webhooks.as_ref().map(|(_, source)| source)
+    }
+
     #[cfg(feature = "rest-api-cors")]
     pub fn whitelist_source(&self) -> Option<&ConfigSource> {
         if let Some((_, source)) = &self.whitelist {
@@ -322,6 +581,47 @@ impl Config {
         &self.strict_ref_counts.1
     }
 
+    #[cfg(feature = "socket-compression")]
+    fn compression_source(&self) -> &ConfigSource {
+        &self.compression.1
+    }
+
+    #[cfg(feature = "socket-compression")]
+    fn compression_threshold_source(&self) -> &ConfigSource {
+        &self.compression_threshold.1
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    fn peer_message_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.peer_message_rate_limit
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    fn peer_byte_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.peer_byte_rate_limit.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    fn circuit_message_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.circuit_message_rate_limit
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    fn circuit_byte_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.circuit_byte_rate_limit
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "registry-remote-signing")]
+    fn registry_publisher_keys_source(&self) -> &ConfigSource {
+        &self.registry_publisher_keys.1
+    }
+
     #[allow(clippy::cognitive_complexity)]
     /// Displays the configuration value along with where the value was sourced from.
     pub fn log_as_debug(&self) {
@@ -449,6 +749,48 @@ impl Config {
             self.enable_biome(),
             self.enable_biome_source()
         );
+        #[cfg(feature = "biome-mfa")]
+        debug!(
+            "Config: biome_mfa_required: {:?} (source: {:?})",
+            self.biome_mfa_required(),
+            self.biome_mfa_required_source()
+        );
+        #[cfg(feature = "admin-service-event-store")]
+        debug!(
+            "Config: admin_event_max_age: {:?} (source: {:?})",
+            self.admin_event_max_age(),
+            self.admin_event_max_age_source()
+        );
+        #[cfg(feature = "admin-service-event-store")]
+        debug!(
+            "Config: admin_event_max_items: {:?} (source: {:?})",
+            self.admin_event_max_items(),
+            self.admin_event_max_items_source()
+        );
+        #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+        debug!(
+            "Config: biome_admins: {:?} (source: {:?})",
+            self.biome_admins(),
+            self.biome_admins_source()
+        );
+        #[cfg(feature = "oauth")]
+        self.log_oauth();
+        #[cfg(feature = "https-bind")]
+        self.log_tls_rest_api();
+        #[cfg(feature = "authorization")]
+        if let (Some(value), Some(source)) = (
+            self.rest_api_permissions(),
+            self.rest_api_permissions_source(),
+        ) {
+            debug!(
+                "Config: rest_api_permissions: {} (source: {:?})",
+                value, source
+            );
+        }
+        #[cfg(feature = "webhooks")]
+        if let (Some(value), Some(source)) = (self.webhooks(), self.webhooks_source()) {
+            debug!("Config: webhooks: {} (source: {:?})", value, source);
+        }
         #[cfg(feature = "rest-api-cors")]
         self.log_whitelist();
         debug!(
@@ -456,6 +798,66 @@ impl Config {
             self.strict_ref_counts(),
             self.strict_ref_counts_source()
         );
+        #[cfg(feature = "socket-compression")]
+        debug!(
+            "Config: compression: {:?} (source: {:?})",
+            self.compression(),
+            self.compression_source()
+        );
+        #[cfg(feature = "socket-compression")]
+        debug!(
+            "Config: compression_threshold: {} (source: {:?})",
+            self.compression_threshold(),
+            self.compression_threshold_source()
+        );
+        #[cfg(feature = "registry-remote-signing")]
+        debug!(
+            "Config: registry_publisher_keys: {:?} (source: {:?})",
+            self.registry_publisher_keys(),
+            self.registry_publisher_keys_source()
+        );
+        #[cfg(feature = "circuit-rate-limit")]
+        self.log_rate_limits();
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    fn log_rate_limits(&self) {
+        if let (Some(limit), Some(source)) = (
+            self.peer_message_rate_limit(),
+            self.peer_message_rate_limit_source(),
+        ) {
+            debug!(
+                "Config: peer_message_rate_limit: {} (source: {:?})",
+                limit, source
+            );
+        }
+        if let (Some(limit), Some(source)) = (
+            self.peer_byte_rate_limit(),
+            self.peer_byte_rate_limit_source(),
+        ) {
+            debug!(
+                "Config: peer_byte_rate_limit: {} (source: {:?})",
+                limit, source
+            );
+        }
+        if let (Some(limit), Some(source)) = (
+            self.circuit_message_rate_limit(),
+            self.circuit_message_rate_limit_source(),
+        ) {
+            debug!(
+                "Config: circuit_message_rate_limit: {} (source: {:?})",
+                limit, source
+            );
+        }
+        if let (Some(limit), Some(source)) = (
+            self.circuit_byte_rate_limit(),
+            self.circuit_byte_rate_limit_source(),
+        ) {
+            debug!(
+                "Config: circuit_byte_rate_limit: {} (source: {:?})",
+                limit, source
+            );
+        }
     }
 
     #[cfg(feature = "rest-api-cors")]
@@ -464,6 +866,71 @@ impl Config {
             debug!("Config: whitelist: {:?} (source: {:?})", list, source,);
         }
     }
+
+    #[cfg(feature = "oauth")]
+    fn log_oauth(&self) {
+        if let (Some(value), Some(source)) = (self.oauth_provider(), self.oauth_provider_source()) {
+            debug!("Config: oauth_provider: {} (source: {:?})", value, source);
+        }
+        if let (Some(value), Some(source)) = (self.oauth_client_id(), self.oauth_client_id_source())
+        {
+            debug!("Config: oauth_client_id: {} (source: {:?})", value, source);
+        }
+        if let Some(source) = self.oauth_client_secret_source() {
+            debug!(
+                "Config: oauth_client_secret: <redacted> (source: {:?})",
+                source
+            );
+        }
+        if let (Some(value), Some(source)) =
+            (self.oauth_redirect_url(), self.oauth_redirect_url_source())
+        {
+            debug!(
+                "Config: oauth_redirect_url: {} (source: {:?})",
+                value, source
+            );
+        }
+        if let (Some(value), Some(source)) =
+            (self.oauth_openid_url(), self.oauth_openid_url_source())
+        {
+            debug!("Config: oauth_openid_url: {} (source: {:?})", value, source);
+        }
+    }
+
+    #[cfg(feature = "https-bind")]
+    fn log_tls_rest_api(&self) {
+        if let (Some(value), Some(source)) =
+            (self.tls_rest_api_cert(), self.tls_rest_api_cert_source())
+        {
+            debug!(
+                "Config: tls_rest_api_cert: {} (source: {:?})",
+                value, source
+            );
+        }
+        if let (Some(value), Some(source)) =
+            (self.tls_rest_api_key(), self.tls_rest_api_key_source())
+        {
+            debug!("Config: tls_rest_api_key: {} (source: {:?})", value, source);
+        }
+        if let (Some(value), Some(source)) = (
+            self.tls_rest_api_client_ca(),
+            self.tls_rest_api_client_ca_source(),
+        ) {
+            debug!(
+                "Config: tls_rest_api_client_ca: {} (source: {:?})",
+                value, source
+            );
+        }
+        if let (Some(value), Some(source)) = (
+            self.tls_rest_api_min_version(),
+            self.tls_rest_api_min_version_source(),
+        ) {
+            debug!(
+                "Config: tls_rest_api_min_version: {} (source: {:?})",
+                value, source
+            );
+        }
+    }
 }
 
 #[cfg(test)]
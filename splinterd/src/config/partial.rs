@@ -61,9 +61,53 @@ pub struct PartialConfig {
     no_tls: Option<bool>,
     #[cfg(feature = "biome")]
     enable_biome: Option<bool>,
+    #[cfg(feature = "biome-mfa")]
+    biome_mfa_required: Option<bool>,
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_max_age: Option<u64>,
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_max_items: Option<u64>,
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    biome_admins: Option<Vec<String>>,
+    #[cfg(feature = "oauth")]
+    oauth_provider: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_client_id: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_client_secret: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_redirect_url: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_openid_url: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_cert: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_key: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_client_ca: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_min_version: Option<String>,
+    #[cfg(feature = "authorization")]
+    rest_api_permissions: Option<String>,
+    #[cfg(feature = "webhooks")]
+    webhooks: Option<String>,
     #[cfg(feature = "rest-api-cors")]
     whitelist: Option<Vec<String>>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "socket-compression")]
+    compression: Option<Vec<String>>,
+    #[cfg(feature = "socket-compression")]
+    compression_threshold: Option<usize>,
+    #[cfg(feature = "circuit-rate-limit")]
+    peer_message_rate_limit: Option<u64>,
+    #[cfg(feature = "circuit-rate-limit")]
+    peer_byte_rate_limit: Option<u64>,
+    #[cfg(feature = "circuit-rate-limit")]
+    circuit_message_rate_limit: Option<u64>,
+    #[cfg(feature = "circuit-rate-limit")]
+    circuit_byte_rate_limit: Option<u64>,
+    #[cfg(feature = "registry-remote-signing")]
+    registry_publisher_keys: Option<Vec<String>>,
 }
 
 impl PartialConfig {
@@ -99,9 +143,53 @@ impl PartialConfig {
             no_tls: None,
             #[cfg(feature = "biome")]
             enable_biome: None,
+            #[cfg(feature = "biome-mfa")]
+            biome_mfa_required: None,
+            #[cfg(feature = "admin-service-event-store")]
+            admin_event_max_age: None,
+            #[cfg(feature = "admin-service-event-store")]
+            admin_event_max_items: None,
+            #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+            biome_admins: None,
+            #[cfg(feature = "oauth")]
+            oauth_provider: None,
+            #[cfg(feature = "oauth")]
+            oauth_client_id: None,
+            #[cfg(feature = "oauth")]
+            oauth_client_secret: None,
+            #[cfg(feature = "oauth")]
+            oauth_redirect_url: None,
+            #[cfg(feature = "oauth")]
+            oauth_openid_url: None,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_cert: None,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_key: None,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_client_ca: None,
+            #[cfg(feature = "https-bind")]
+            tls_rest_api_min_version: None,
+            #[cfg(feature = "authorization")]
+            rest_api_permissions: None,
+            #[cfg(feature = "webhooks")]
+            webhooks: None,
             #[cfg(feature = "rest-api-cors")]
             whitelist: None,
             strict_ref_counts: None,
+            #[cfg(feature = "socket-compression")]
+            compression: None,
+            #[cfg(feature = "socket-compression")]
+            compression_threshold: None,
+            #[cfg(feature = "circuit-rate-limit")]
+            peer_message_rate_limit: None,
+            #[cfg(feature = "circuit-rate-limit")]
+            peer_byte_rate_limit: None,
+            #[cfg(feature = "circuit-rate-limit")]
+            circuit_message_rate_limit: None,
+            #[cfg(feature = "circuit-rate-limit")]
+            circuit_byte_rate_limit: None,
+            #[cfg(feature = "registry-remote-signing")]
+            registry_publisher_keys: None,
         }
     }
 
@@ -212,6 +300,81 @@ impl PartialConfig {
         self.enable_biome
     }
 
+    #[cfg(feature = "biome-mfa")]
+    pub fn biome_mfa_required(&self) -> Option<bool> {
+        self.biome_mfa_required
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn admin_event_max_age(&self) -> Option<u64> {
+        self.admin_event_max_age
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn admin_event_max_items(&self) -> Option<u64> {
+        self.admin_event_max_items
+    }
+
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    pub fn biome_admins(&self) -> Option<Vec<String>> {
+        self.biome_admins.clone()
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_provider(&self) -> Option<String> {
+        self.oauth_provider.clone()
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_client_id(&self) -> Option<String> {
+        self.oauth_client_id.clone()
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_client_secret(&self) -> Option<String> {
+        self.oauth_client_secret.clone()
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_redirect_url(&self) -> Option<String> {
+        self.oauth_redirect_url.clone()
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_openid_url(&self) -> Option<String> {
+        self.oauth_openid_url.clone()
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_cert(&self) -> Option<String> {
+        self.tls_rest_api_cert.clone()
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_key(&self) -> Option<String> {
+        self.tls_rest_api_key.clone()
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_client_ca(&self) -> Option<String> {
+        self.tls_rest_api_client_ca.clone()
+    }
+
+    #[cfg(feature = "https-bind")]
+    pub fn tls_rest_api_min_version(&self) -> Option<String> {
+        self.tls_rest_api_min_version.clone()
+    }
+
+    #[cfg(feature = "authorization")]
+    pub fn rest_api_permissions(&self) -> Option<String> {
+        self.rest_api_permissions.clone()
+    }
+
+    #[cfg(feature = "webhooks")]
+    pub fn webhooks(&self) -> Option<String> {
+        self.webhooks.clone()
+    }
+
     #[cfg(feature = "rest-api-cors")]
     pub fn whitelist(&self) -> Option<Vec<String>> {
         self.whitelist.clone()
@@ -221,6 +384,41 @@ impl PartialConfig {
         self.strict_ref_counts
     }
 
+    #[cfg(feature = "socket-compression")]
+    pub fn compression(&self) -> Option<Vec<String>> {
+        self.compression.clone()
+    }
+
+    #[cfg(feature = "socket-compression")]
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn peer_message_rate_limit(&self) -> Option<u64> {
+        self.peer_message_rate_limit
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn peer_byte_rate_limit(&self) -> Option<u64> {
+        self.peer_byte_rate_limit
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn circuit_message_rate_limit(&self) -> Option<u64> {
+        self.circuit_message_rate_limit
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn circuit_byte_rate_limit(&self) -> Option<u64> {
+        self.circuit_byte_rate_limit
+    }
+
+    #[cfg(feature = "registry-remote-signing")]
+    pub fn registry_publisher_keys(&self) -> Option<Vec<String>> {
+        self.registry_publisher_keys.clone()
+    }
+
     /// Adds a `config_dir` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -508,6 +706,191 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "biome-mfa")]
+    /// Adds a `biome_mfa_required` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `biome_mfa_required` - Require biome users to log in with multi-factor authentication
+    ///
+    pub fn with_biome_mfa_required(mut self, biome_mfa_required: Option<bool>) -> Self {
+        self.biome_mfa_required = biome_mfa_required;
+        self
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    /// Adds an `admin_event_max_age` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_event_max_age` - Number of seconds a stored admin service event is kept
+    ///
+    pub fn with_admin_event_max_age(mut self, admin_event_max_age: Option<u64>) -> Self {
+        self.admin_event_max_age = admin_event_max_age;
+        self
+    }
+
+    #[cfg(feature = "admin-service-event-store")]
+    /// Adds an `admin_event_max_items` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_event_max_items` - Maximum number of admin service events kept in the store
+    ///
+    pub fn with_admin_event_max_items(mut self, admin_event_max_items: Option<u64>) -> Self {
+        self.admin_event_max_items = admin_event_max_items;
+        self
+    }
+
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    /// Adds a `biome_admins` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `biome_admins` - The IDs of the biome users that may administer other users
+    ///
+    pub fn with_biome_admins(mut self, biome_admins: Option<Vec<String>>) -> Self {
+        self.biome_admins = biome_admins;
+        self
+    }
+
+    #[cfg(feature = "oauth")]
+    /// Adds a `oauth_provider` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth_provider` - The OAuth provider used to authenticate users (`github` or `openid`)
+    ///
+    pub fn with_oauth_provider(mut self, oauth_provider: Option<String>) -> Self {
+        self.oauth_provider = oauth_provider;
+        self
+    }
+
+    #[cfg(feature = "oauth")]
+    /// Adds a `oauth_client_id` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth_client_id` - The client ID of the node's OAuth app
+    ///
+    pub fn with_oauth_client_id(mut self, oauth_client_id: Option<String>) -> Self {
+        self.oauth_client_id = oauth_client_id;
+        self
+    }
+
+    #[cfg(feature = "oauth")]
+    /// Adds a `oauth_client_secret` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth_client_secret` - The client secret of the node's OAuth app
+    ///
+    pub fn with_oauth_client_secret(mut self, oauth_client_secret: Option<String>) -> Self {
+        self.oauth_client_secret = oauth_client_secret;
+        self
+    }
+
+    #[cfg(feature = "oauth")]
+    /// Adds a `oauth_redirect_url` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth_redirect_url` - The URL that the OAuth provider redirects to after authorization
+    ///
+    pub fn with_oauth_redirect_url(mut self, oauth_redirect_url: Option<String>) -> Self {
+        self.oauth_redirect_url = oauth_redirect_url;
+        self
+    }
+
+    #[cfg(feature = "oauth")]
+    /// Adds a `oauth_openid_url` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth_openid_url` - The URL of the OpenID Connect provider's discovery document
+    ///
+    pub fn with_oauth_openid_url(mut self, oauth_openid_url: Option<String>) -> Self {
+        self.oauth_openid_url = oauth_openid_url;
+        self
+    }
+
+    #[cfg(feature = "https-bind")]
+    /// Adds a `tls_rest_api_cert` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_rest_api_cert` - The REST API's certificate chain (*.pem file)
+    ///
+    pub fn with_tls_rest_api_cert(mut self, tls_rest_api_cert: Option<String>) -> Self {
+        self.tls_rest_api_cert = tls_rest_api_cert;
+        self
+    }
+
+    #[cfg(feature = "https-bind")]
+    /// Adds a `tls_rest_api_key` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_rest_api_key` - The private key of the REST API's certificate (*.pem file)
+    ///
+    pub fn with_tls_rest_api_key(mut self, tls_rest_api_key: Option<String>) -> Self {
+        self.tls_rest_api_key = tls_rest_api_key;
+        self
+    }
+
+    #[cfg(feature = "https-bind")]
+    /// Adds a `tls_rest_api_client_ca` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_rest_api_client_ca` - Certificate authorities that REST API clients must present a
+    ///   certificate from (*.pem file)
+    ///
+    pub fn with_tls_rest_api_client_ca(mut self, tls_rest_api_client_ca: Option<String>) -> Self {
+        self.tls_rest_api_client_ca = tls_rest_api_client_ca;
+        self
+    }
+
+    #[cfg(feature = "https-bind")]
+    /// Adds a `tls_rest_api_min_version` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_rest_api_min_version` - The minimum TLS version accepted by the REST API (`1.2` or
+    ///   `1.3`)
+    ///
+    pub fn with_tls_rest_api_min_version(
+        mut self,
+        tls_rest_api_min_version: Option<String>,
+    ) -> Self {
+        self.tls_rest_api_min_version = tls_rest_api_min_version;
+        self
+    }
+
+    #[cfg(feature = "authorization")]
+    /// Adds a `rest_api_permissions` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rest_api_permissions` - The YAML file of the permissions granted to REST API callers
+    ///
+    pub fn with_rest_api_permissions(mut self, rest_api_permissions: Option<String>) -> Self {
+        self.rest_api_permissions = rest_api_permissions;
+        self
+    }
+
+    #[cfg(feature = "webhooks")]
+    /// Adds a `webhooks` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `webhooks` - The YAML file of the webhooks that receive admin and scabbard events
+    ///
+    pub fn with_webhooks(mut self, webhooks: Option<String>) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
     #[cfg(feature = "rest-api-cors")]
     /// Adds a `whitelist` value to the `PartialConfig` object.
     ///
@@ -530,4 +913,95 @@ impl PartialConfig {
         self.strict_ref_counts = strict_ref_counts;
         self
     }
+
+    #[cfg(feature = "socket-compression")]
+    /// Adds a `compression` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - Compression algorithms offered to peers, in order of preference
+    ///
+    pub fn with_compression(mut self, compression: Option<Vec<String>>) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    #[cfg(feature = "socket-compression")]
+    /// Adds a `compression_threshold` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression_threshold` - The minimum message size, in bytes, that will be compressed
+    ///
+    pub fn with_compression_threshold(mut self, compression_threshold: Option<usize>) -> Self {
+        self.compression_threshold = compression_threshold;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    /// Adds a `peer_message_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_message_rate_limit` - Maximum messages per second accepted from each peer
+    ///
+    pub fn with_peer_message_rate_limit(mut self, peer_message_rate_limit: Option<u64>) -> Self {
+        self.peer_message_rate_limit = peer_message_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    /// Adds a `peer_byte_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_byte_rate_limit` - Maximum payload bytes per second accepted from each peer
+    ///
+    pub fn with_peer_byte_rate_limit(mut self, peer_byte_rate_limit: Option<u64>) -> Self {
+        self.peer_byte_rate_limit = peer_byte_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    /// Adds a `circuit_message_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_message_rate_limit` - Maximum messages per second accepted on each circuit
+    ///
+    pub fn with_circuit_message_rate_limit(
+        mut self,
+        circuit_message_rate_limit: Option<u64>,
+    ) -> Self {
+        self.circuit_message_rate_limit = circuit_message_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    /// Adds a `circuit_byte_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_byte_rate_limit` - Maximum payload bytes per second accepted on each circuit
+    ///
+    pub fn with_circuit_byte_rate_limit(mut self, circuit_byte_rate_limit: Option<u64>) -> Self {
+        self.circuit_byte_rate_limit = circuit_byte_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "registry-remote-signing")]
+    /// Adds a `registry_publisher_keys` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_publisher_keys` - Public keys of the publishers trusted to sign remote
+    ///   registry files
+    ///
+    pub fn with_registry_publisher_keys(
+        mut self,
+        registry_publisher_keys: Option<Vec<String>>,
+    ) -> Self {
+        self.registry_publisher_keys = registry_publisher_keys;
+        self
+    }
 }
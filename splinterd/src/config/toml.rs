@@ -53,6 +53,50 @@ struct TomlConfig {
     version: Option<String>,
     #[cfg(feature = "rest-api-cors")]
     whitelist: Option<Vec<String>>,
+    #[cfg(feature = "socket-compression")]
+    compression: Option<Vec<String>>,
+    #[cfg(feature = "socket-compression")]
+    compression_threshold: Option<usize>,
+    #[cfg(feature = "circuit-rate-limit")]
+    peer_message_rate_limit: Option<u64>,
+    #[cfg(feature = "circuit-rate-limit")]
+    peer_byte_rate_limit: Option<u64>,
+    #[cfg(feature = "circuit-rate-limit")]
+    circuit_message_rate_limit: Option<u64>,
+    #[cfg(feature = "circuit-rate-limit")]
+    circuit_byte_rate_limit: Option<u64>,
+    #[cfg(feature = "registry-remote-signing")]
+    registry_publisher_keys: Option<Vec<String>>,
+    #[cfg(feature = "biome-mfa")]
+    biome_mfa_required: Option<bool>,
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_max_age: Option<u64>,
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_max_items: Option<u64>,
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    biome_admins: Option<Vec<String>>,
+    #[cfg(feature = "oauth")]
+    oauth_provider: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_client_id: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_client_secret: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_redirect_url: Option<String>,
+    #[cfg(feature = "oauth")]
+    oauth_openid_url: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_cert: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_key: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_client_ca: Option<String>,
+    #[cfg(feature = "https-bind")]
+    tls_rest_api_min_version: Option<String>,
+    #[cfg(feature = "authorization")]
+    rest_api_permissions: Option<String>,
+    #[cfg(feature = "webhooks")]
+    webhooks: Option<String>,
 
     // Deprecated values
     cert_dir: Option<String>,
@@ -149,6 +193,76 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
             partial_config = partial_config.with_whitelist(self.toml_config.whitelist);
         }
 
+        #[cfg(feature = "socket-compression")]
+        {
+            partial_config = partial_config
+                .with_compression(self.toml_config.compression)
+                .with_compression_threshold(self.toml_config.compression_threshold);
+        }
+
+        #[cfg(feature = "circuit-rate-limit")]
+        {
+            partial_config = partial_config
+                .with_peer_message_rate_limit(self.toml_config.peer_message_rate_limit)
+                .with_peer_byte_rate_limit(self.toml_config.peer_byte_rate_limit)
+                .with_circuit_message_rate_limit(self.toml_config.circuit_message_rate_limit)
+                .with_circuit_byte_rate_limit(self.toml_config.circuit_byte_rate_limit);
+        }
+
+        #[cfg(feature = "registry-remote-signing")]
+        {
+            partial_config = partial_config
+                .with_registry_publisher_keys(self.toml_config.registry_publisher_keys);
+        }
+
+        #[cfg(feature = "biome-mfa")]
+        {
+            partial_config =
+                partial_config.with_biome_mfa_required(self.toml_config.biome_mfa_required);
+        }
+
+        #[cfg(feature = "admin-service-event-store")]
+        {
+            partial_config = partial_config
+                .with_admin_event_max_age(self.toml_config.admin_event_max_age)
+                .with_admin_event_max_items(self.toml_config.admin_event_max_items);
+        }
+
+        #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+        {
+            partial_config = partial_config.with_biome_admins(self.toml_config.biome_admins);
+        }
+
+        #[cfg(feature = "oauth")]
+        {
+            partial_config = partial_config
+                .with_oauth_provider(self.toml_config.oauth_provider)
+                .with_oauth_client_id(self.toml_config.oauth_client_id)
+                .with_oauth_client_secret(self.toml_config.oauth_client_secret)
+                .with_oauth_redirect_url(self.toml_config.oauth_redirect_url)
+                .with_oauth_openid_url(self.toml_config.oauth_openid_url);
+        }
+
+        #[cfg(feature = "https-bind")]
+        {
+            partial_config = partial_config
+                .with_tls_rest_api_cert(self.toml_config.tls_rest_api_cert)
+                .with_tls_rest_api_key(self.toml_config.tls_rest_api_key)
+                .with_tls_rest_api_client_ca(self.toml_config.tls_rest_api_client_ca)
+                .with_tls_rest_api_min_version(self.toml_config.tls_rest_api_min_version);
+        }
+
+        #[cfg(feature = "authorization")]
+        {
+            partial_config =
+                partial_config.with_rest_api_permissions(self.toml_config.rest_api_permissions);
+        }
+
+        #[cfg(feature = "webhooks")]
+        {
+            partial_config = partial_config.with_webhooks(self.toml_config.webhooks);
+        }
+
         // deprecated values, only set if the current value was not set
         if partial_config.tls_cert_dir().is_none() {
             partial_config = partial_config.with_tls_cert_dir(self.toml_config.cert_dir)
@@ -16,8 +16,20 @@
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
+#[cfg(any(
+    feature = "biome-mfa",
+    feature = "oauth",
+    feature = "registry-database"
+))]
+use std::fs;
+#[cfg(any(feature = "biome-mfa", feature = "oauth"))]
+use std::io::Write;
+#[cfg(any(feature = "biome-mfa", feature = "oauth"))]
+use std::os::unix::fs::OpenOptionsExt;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "circuit-rate-limit")]
+use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -28,14 +40,41 @@ use health::HealthService;
 use scabbard::service::ScabbardArgValidator;
 use scabbard::service::ScabbardFactory;
 use splinter::admin::rest_api::CircuitResourceProvider;
+#[cfg(any(feature = "biome", feature = "webhooks"))]
+use splinter::admin::service::AdminCommands;
+#[cfg(any(
+    all(feature = "biome-notifications", feature = "biome-key-management"),
+    feature = "webhooks"
+))]
+use splinter::admin::service::ALL_EVENT_TYPES;
 use splinter::admin::service::{admin_service_id, AdminService};
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::{Permission, RoleBasedPermissionStore};
+#[cfg(feature = "authorization")]
+use splinter::auth::identity::SignedRequestIdentityProvider;
+#[cfg(feature = "oauth")]
+use splinter::auth::oauth::{
+    rest_api::{generate_token_encryption_key, OAuthResourceProvider},
+    OAuthClient,
+};
+#[cfg(feature = "biome-mfa")]
+use splinter::biome::credentials::mfa::generate_encryption_key;
+#[cfg(all(feature = "biome-notifications", feature = "biome-key-management"))]
+use splinter::biome::notifications::ProposalNotifier;
 #[cfg(feature = "biome")]
-use splinter::biome::rest_api::{BiomeRestResourceManager, BiomeRestResourceManagerBuilder};
+use splinter::biome::rest_api::{
+    BiomeRestConfig, BiomeRestConfigBuilder, BiomeRestResourceManager,
+    BiomeRestResourceManagerBuilder,
+};
+#[cfg(feature = "biome-notifications")]
+use splinter::biome::NotificationStore;
 use splinter::circuit::directory::CircuitDirectory;
 use splinter::circuit::handlers::{
     AdminDirectMessageHandler, CircuitDirectMessageHandler, CircuitErrorHandler,
     CircuitMessageHandler, ServiceConnectRequestHandler, ServiceDisconnectRequestHandler,
 };
+#[cfg(feature = "circuit-rate-limit")]
+use splinter::circuit::rate_limit::RateLimiter;
 use splinter::circuit::{SplinterState, SplinterStateError};
 use splinter::keys::insecure::AllowAllKeyPermissionManager;
 use splinter::mesh::Mesh;
@@ -51,12 +90,26 @@ use splinter::orchestrator::{NewOrchestratorError, ServiceOrchestrator};
 use splinter::peer::interconnect::NetworkMessageSender;
 use splinter::peer::interconnect::PeerInterconnectBuilder;
 use splinter::peer::PeerManager;
+#[cfg(any(feature = "circuit-rate-limit", feature = "registry-events"))]
+use splinter::peer::PeerManagerConnector;
+#[cfg(feature = "circuit-rate-limit")]
+use splinter::peer::PeerManagerNotification;
 use splinter::protos::circuit::CircuitMessageType;
 use splinter::protos::network::NetworkMessageType;
+#[cfg(feature = "registry-database")]
+use splinter::registry::RegistryWriter;
+#[cfg(feature = "registry-remote-signing")]
+use splinter::registry::RemoteYamlPublisherVerifier;
 use splinter::registry::{
-    LocalYamlRegistry, RegistryReader, RemoteYamlRegistry, RemoteYamlShutdownHandle, RwRegistry,
-    UnifiedRegistry,
+    LocalYamlRegistry, RegistryReader, RemoteYamlRegistry, RemoteYamlShutdownHandle,
+    RemoteYamlStatusHandle, RwRegistry, UnifiedRegistry,
 };
+#[cfg(feature = "registry-events")]
+use splinter::registry::{RegistryEvent, RegistrySubscriberError};
+#[cfg(feature = "oauth")]
+use splinter::rest_api::secrets::AutoSecretManager;
+#[cfg(feature = "https-bind")]
+use splinter::rest_api::RestApiTlsConfig;
 use splinter::rest_api::{
     Method, Resource, RestApiBuilder, RestApiServerError, RestResourceProvider,
 };
@@ -65,10 +118,19 @@ use splinter::service::validation::ServiceArgValidator;
 use splinter::service::{self, ServiceProcessor, ShutdownHandle};
 use splinter::signing::sawtooth::SawtoothSecp256k1SignatureVerifier;
 use splinter::storage::get_storage;
+#[cfg(feature = "admin-service-event-store")]
+use splinter::storage::sets::diesel::RetentionPolicy;
+#[cfg(feature = "database")]
+use splinter::store::StoreFactory;
 use splinter::transport::{
     inproc::InprocTransport, multi::MultiTransport, AcceptError, ConnectError, Connection,
     Incoming, ListenError, Listener, Transport,
 };
+#[cfg(feature = "webhooks")]
+use splinter::webhooks::{
+    RetryPolicy, Webhook, WebhookAdminEventSubscriber, WebhookDispatcher,
+    WebhookDispatcherShutdownHandle,
+};
 
 use crate::routes;
 
@@ -80,6 +142,21 @@ const ADMIN_SERVICE_PROCESSOR_INCOMING_CAPACITY: usize = 8;
 const ADMIN_SERVICE_PROCESSOR_OUTGOING_CAPACITY: usize = 8;
 const ADMIN_SERVICE_PROCESSOR_CHANNEL_CAPACITY: usize = 8;
 
+#[cfg(feature = "admin-service-event-store")]
+const ADMIN_EVENT_SET_NAME: &str = "admin_service_events";
+
+#[cfg(feature = "biome-mfa")]
+const BIOME_MFA_KEY_FILE: &str = "biome_mfa.key";
+#[cfg(feature = "oauth")]
+const OAUTH_TOKEN_KEY_FILE: &str = "oauth_token.key";
+
+/// The permission that is required to read the node's status
+#[cfg(feature = "authorization")]
+const STATUS_READ_PERMISSION: &str = "status.read";
+/// The permission that is required to read the node's metrics
+#[cfg(all(feature = "authorization", feature = "metrics"))]
+const METRICS_READ_PERMISSION: &str = "metrics.read";
+
 #[cfg(feature = "health")]
 const HEALTH_SERVICE_PROCESSOR_INCOMING_CAPACITY: usize = 8;
 #[cfg(feature = "health")]
@@ -102,17 +179,41 @@ pub struct SplinterDaemon {
     rest_api_endpoint: String,
     #[cfg(feature = "database")]
     db_url: Option<String>,
+    #[cfg(feature = "registry-database")]
+    database_registry: bool,
+    #[cfg(feature = "admin-service-event-store")]
+    database_admin_events: bool,
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_retention: RetentionPolicy,
+    #[cfg(feature = "admin-audit-log")]
+    admin_audit_log: bool,
     #[cfg(feature = "biome")]
     enable_biome: bool,
+    #[cfg(feature = "biome-mfa")]
+    biome_mfa_required: bool,
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    biome_admins: Vec<String>,
+    #[cfg(feature = "oauth")]
+    oauth_config: Option<OAuthConfig>,
     registries: Vec<String>,
     registry_auto_refresh: u64,
     registry_forced_refresh: u64,
+    #[cfg(feature = "registry-remote-signing")]
+    registry_publisher_keys: Vec<String>,
     storage_type: String,
     admin_timeout: Duration,
     #[cfg(feature = "rest-api-cors")]
     whitelist: Option<Vec<String>>,
+    #[cfg(feature = "https-bind")]
+    rest_api_tls_config: Option<RestApiTlsConfig>,
+    #[cfg(feature = "authorization")]
+    rest_api_permission_store: Option<RoleBasedPermissionStore>,
     heartbeat: u64,
     strict_ref_counts: bool,
+    #[cfg(feature = "circuit-rate-limit")]
+    rate_limiter: RateLimiter,
+    #[cfg(feature = "webhooks")]
+    webhooks: Vec<Webhook>,
 }
 
 impl SplinterDaemon {
@@ -145,6 +246,8 @@ impl SplinterDaemon {
             .map_err(StartError::StorageError)?;
         let circuit_directory = storage.read().clone();
         let state = SplinterState::new(storage_location, circuit_directory);
+        #[cfg(feature = "circuit-rate-limit")]
+        let state = state.with_rate_limiter(self.rate_limiter.clone());
 
         // set up the listeners on the transport. This will set up listeners for different
         // transports based on the protocol prefix of the endpoint.
@@ -199,7 +302,7 @@ impl SplinterDaemon {
 
         #[cfg(feature = "health")]
         inproc_ids.push((
-            "inproc://health-service".to_string(),
+            "inproc://health_service".to_string(),
             format!("health::{}", &self.node_id),
         ));
 
@@ -291,6 +394,8 @@ impl SplinterDaemon {
             &self.node_id,
             &self.network_endpoints,
             state.clone(),
+            #[cfg(feature = "circuit-rate-limit")]
+            self.rate_limiter.clone(),
         );
         let circuit_dispatch_loop = DispatchLoopBuilder::new()
             .with_dispatcher(circuit_dispatcher)
@@ -364,14 +469,27 @@ impl SplinterDaemon {
             }
         }
 
+        #[cfg(feature = "webhooks")]
+        let (webhook_dispatcher, webhook_dispatcher_shutdown) =
+            start_webhook_dispatcher(&self.webhooks)?;
+
+        // Allowing unused_mut because scabbard_factory must be mutable if feature webhooks is
+        // enabled
+        #[allow(unused_mut)]
+        let mut scabbard_factory = ScabbardFactory::new(
+            None,
+            None,
+            None,
+            None,
+            Box::new(SawtoothSecp256k1SignatureVerifier::new()),
+        );
+        #[cfg(feature = "webhooks")]
+        {
+            scabbard_factory = scabbard_factory.with_webhook_dispatcher(webhook_dispatcher.clone());
+        }
+
         let (orchestrator, orchestator_join_handles) = ServiceOrchestrator::new(
-            vec![Box::new(ScabbardFactory::new(
-                None,
-                None,
-                None,
-                None,
-                Box::new(SawtoothSecp256k1SignatureVerifier::new()),
-            ))],
+            vec![Box::new(scabbard_factory)],
             orchestrator_connection,
             ORCHESTRATOR_INCOMING_CAPACITY,
             ORCHESTRATOR_OUTGOING_CAPACITY,
@@ -381,13 +499,35 @@ impl SplinterDaemon {
 
         let signature_verifier = SawtoothSecp256k1SignatureVerifier::new();
 
-        let (registry, registry_shutdown) = create_registry(
+        #[cfg(feature = "database")]
+        let store_factory = self.create_store_factory()?;
+
+        let (registry, registry_shutdown, registry_status_handles) = create_registry(
             &self.state_dir,
             &self.registries,
             self.registry_auto_refresh,
             self.registry_forced_refresh,
+            #[cfg(feature = "registry-database")]
+            store_factory.as_deref().filter(|_| self.database_registry),
+            #[cfg(feature = "registry-remote-signing")]
+            &self.registry_publisher_keys,
         )?;
 
+        #[cfg(feature = "registry-events")]
+        subscribe_peer_endpoint_updates(&*registry, peer_connector.clone())?;
+
+        #[cfg(feature = "circuit-rate-limit")]
+        if self.rate_limiter.is_enabled() {
+            remove_disconnected_peer_limits(&peer_connector, self.rate_limiter.clone())?;
+        }
+
+        #[cfg(feature = "health")]
+        let health_peer_connector = peer_connector.clone();
+        #[cfg(feature = "health")]
+        let health_splinter_state = state.clone();
+        #[cfg(feature = "health")]
+        let health_service_lister = orchestrator.service_lister();
+
         let (admin_service, admin_notification_join) = AdminService::new(
             &self.node_id,
             orchestrator,
@@ -411,6 +551,61 @@ impl SplinterDaemon {
             StartError::AdminServiceError(format!("unable to create admin service: {}", err))
         })?;
 
+        // The event and audit stores are not available for the in-memory database; the admin
+        // service then keeps its events in memory and does not keep an audit log
+        #[cfg(feature = "admin-service-event-store")]
+        {
+            let event_store = store_factory
+                .as_deref()
+                .filter(|_| self.database_admin_events)
+                .and_then(|store_factory| {
+                    store_factory.get_admin_event_store(
+                        ADMIN_EVENT_SET_NAME,
+                        self.admin_event_retention.clone(),
+                    )
+                });
+            if let Some(event_store) = event_store {
+                admin_service.set_event_store(event_store).map_err(|err| {
+                    StartError::AdminServiceError(format!(
+                        "unable to set admin service event store: {}",
+                        err
+                    ))
+                })?;
+            }
+        }
+
+        #[cfg(feature = "admin-audit-log")]
+        {
+            let audit_store = store_factory
+                .as_deref()
+                .filter(|_| self.admin_audit_log)
+                .and_then(|store_factory| store_factory.get_admin_service_store());
+            if let Some(audit_store) = audit_store {
+                admin_service.set_audit_store(audit_store).map_err(|err| {
+                    StartError::AdminServiceError(format!(
+                        "unable to set admin service audit store: {}",
+                        err
+                    ))
+                })?;
+            }
+        }
+
+        #[cfg(feature = "webhooks")]
+        {
+            admin_service
+                .commands()
+                .add_event_subscriber(
+                    ALL_EVENT_TYPES,
+                    Box::new(WebhookAdminEventSubscriber::new(webhook_dispatcher.clone())),
+                )
+                .map_err(|err| {
+                    StartError::AdminServiceError(format!(
+                        "unable to add webhook event subscriber: {}",
+                        err
+                    ))
+                })?;
+        }
+
         let node_id = self.node_id.clone();
         let display_name = self.display_name.clone();
         #[cfg(feature = "service-endpoint")]
@@ -421,29 +616,74 @@ impl SplinterDaemon {
         let circuit_resource_provider =
             CircuitResourceProvider::new(self.node_id.to_string(), state);
 
+        // The admin, registry and scabbard resources are protected by OAuth sessions, if OAuth is
+        // configured and authorization is not enabled; otherwise, the REST API authorizes requests
+        // to every resource. Allowing unused_mut because protected_resources must be mutable if
+        // feature oauth is enabled
+        #[allow(unused_mut)]
+        let mut protected_resources = registry.resources();
+        protected_resources.append(&mut admin_service.resources());
+        protected_resources.append(&mut circuit_resource_provider.resources());
+        protected_resources.extend(orchestrator_resources);
+        #[cfg(feature = "webhooks")]
+        protected_resources.append(&mut webhook_dispatcher.resources());
+
+        #[cfg(feature = "oauth")]
+        let oauth_resource_provider = match &self.oauth_config {
+            Some(oauth_config) => {
+                let store_factory = store_factory.as_deref().ok_or_else(|| {
+                    StartError::StorageError(
+                        "OAuth was enabled but the builder failed to require the db URL".into(),
+                    )
+                })?;
+                let oauth_resource_provider =
+                    build_oauth_resource_provider(oauth_config, store_factory, &self.state_dir)?;
+                #[cfg(not(feature = "authorization"))]
+                {
+                    let guard = oauth_resource_provider.session_guard();
+                    protected_resources = protected_resources
+                        .into_iter()
+                        .map(|resource| resource.add_request_guard(guard.clone()))
+                        .collect();
+                }
+                Some(oauth_resource_provider)
+            }
+            None => None,
+        };
+
+        let openapi_resource = Resource::build("/openapi.yaml");
+        #[cfg(feature = "authorization")]
+        let openapi_resource = openapi_resource.with_permission(Permission::AllowUnauthenticated);
+
+        let status_resource = Resource::build("/status");
+        #[cfg(feature = "authorization")]
+        let status_resource =
+            status_resource.with_permission(Permission::Check(STATUS_READ_PERMISSION));
+
         // Allowing unused_mut because rest_api_builder must be mutable if feature biome is enabled
         #[allow(unused_mut)]
         let mut rest_api_builder = RestApiBuilder::new()
             .with_bind(&self.rest_api_endpoint)
-            .add_resource(
-                Resource::build("/openapi.yaml").add_method(Method::Get, routes::get_openapi),
-            )
-            .add_resource(
-                Resource::build("/status").add_method(Method::Get, move |_, _| {
-                    routes::get_status(
-                        node_id.clone(),
-                        display_name.clone(),
-                        #[cfg(feature = "service-endpoint")]
-                        service_endpoint.clone(),
-                        network_endpoints.clone(),
-                        advertised_endpoints.clone(),
-                    )
-                }),
-            )
-            .add_resources(registry.resources())
-            .add_resources(admin_service.resources())
-            .add_resources(orchestrator_resources)
-            .add_resources(circuit_resource_provider.resources());
+            .add_resource(openapi_resource.add_method(Method::Get, routes::get_openapi))
+            .add_resource(status_resource.add_method(Method::Get, move |_, _| {
+                routes::get_status(
+                    node_id.clone(),
+                    display_name.clone(),
+                    #[cfg(feature = "service-endpoint")]
+                    service_endpoint.clone(),
+                    network_endpoints.clone(),
+                    advertised_endpoints.clone(),
+                )
+            }))
+            .add_resources(protected_resources);
+
+        #[cfg(feature = "oauth")]
+        {
+            if let Some(oauth_resource_provider) = oauth_resource_provider {
+                rest_api_builder =
+                    rest_api_builder.with_oauth_resource_provider(oauth_resource_provider);
+            }
+        }
 
         #[cfg(feature = "rest-api-cors")]
         {
@@ -453,23 +693,120 @@ impl SplinterDaemon {
             }
         }
 
+        #[cfg(feature = "https-bind")]
+        {
+            if let Some(tls_config) = &self.rest_api_tls_config {
+                debug!("Serving REST API over HTTPS");
+                rest_api_builder = rest_api_builder.with_tls_config(tls_config.clone());
+            }
+        }
+
+        // Requests signed with a private key are identified by the key; Biome and OAuth users are
+        // identified by the providers added below and by the REST API builder, respectively
+        #[cfg(feature = "authorization")]
+        {
+            rest_api_builder = rest_api_builder.add_identity_provider(Box::new(
+                SignedRequestIdentityProvider::new(Box::new(
+                    SawtoothSecp256k1SignatureVerifier::new(),
+                )),
+            ));
+            match &self.rest_api_permission_store {
+                Some(permission_store) => {
+                    rest_api_builder =
+                        rest_api_builder.with_permission_store(Box::new(permission_store.clone()));
+                }
+                None => warn!(
+                    "No REST API permissions are configured; only resources that do not require \
+                     a specific permission may be called"
+                ),
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let metrics_resource = Resource::build("/metrics");
+            #[cfg(feature = "authorization")]
+            let metrics_resource =
+                metrics_resource.with_permission(Permission::Check(METRICS_READ_PERMISSION));
+            rest_api_builder = rest_api_builder
+                .add_resource(metrics_resource.add_method(Method::Get, routes::get_metrics));
+        }
+
         #[cfg(feature = "biome")]
         {
             if self.enable_biome {
-                let db_url = self.db_url.clone().ok_or_else(|| {
+                let store_factory = store_factory.as_deref().ok_or_else(|| {
                     StartError::StorageError(
                         "biome was enabled but the builder failed to require the db URL".into(),
                     )
                 })?;
-                let biome_resources = build_biome_routes(db_url)?;
+                // Allowing unused_mut because the builder must be mutable if feature biome-mfa,
+                // biome-login-lockout or biome-user-admin is enabled
+                #[allow(unused_mut)]
+                let mut biome_rest_config_builder = BiomeRestConfigBuilder::default();
+                #[cfg(feature = "biome-mfa")]
+                {
+                    biome_rest_config_builder = biome_rest_config_builder
+                        .with_mfa_required(self.biome_mfa_required)
+                        .with_mfa_encryption_key(&load_encryption_key(
+                            &self.state_dir,
+                            BIOME_MFA_KEY_FILE,
+                            "biome MFA key",
+                            generate_encryption_key,
+                        )?);
+                }
+                #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+                {
+                    biome_rest_config_builder =
+                        biome_rest_config_builder.with_admin_user_ids(self.biome_admins.clone());
+                }
+                let biome_rest_config = biome_rest_config_builder.build().map_err(|err| {
+                    StartError::RestApiError(format!("Invalid biome configuration: {}", err))
+                })?;
+                let biome_resources = build_biome_routes(
+                    store_factory,
+                    biome_rest_config,
+                    &admin_service.commands(),
+                )?;
                 rest_api_builder = rest_api_builder.add_resources(biome_resources.resources());
+                #[cfg(all(feature = "authorization", feature = "biome-credentials"))]
+                {
+                    rest_api_builder = rest_api_builder
+                        .add_identity_provider(Box::new(biome_resources.identity_provider()));
+                }
             }
         }
 
         let mut health_service_processor_join_handle: Option<_> = None;
         #[cfg(feature = "health")]
         {
-            let health_service = HealthService::new(&self.node_id);
+            // Allowing unused_mut because health_service must be mutable if feature biome is
+            // enabled
+            #[allow(unused_mut)]
+            let mut health_service = HealthService::new(&self.node_id)
+                .with_peer_connector(health_peer_connector)
+                .with_splinter_state(health_splinter_state)
+                .with_service_lister(health_service_lister)
+                .with_admin_commands(Box::new(admin_service.commands()))
+                .with_registry_status_handles(registry_status_handles);
+
+            #[cfg(feature = "biome")]
+            {
+                if let Some(db_url) = &self.db_url {
+                    let connection_uri: splinter::store::ConnectionUri =
+                        db_url.parse().map_err(|err| {
+                            StartError::StorageError(format!(
+                                "Invalid database URL provided: {}",
+                                err
+                            ))
+                        })?;
+                    health_service = health_service.with_database_check(Box::new(move || {
+                        splinter::store::check_connection(&connection_uri)
+                            .map_err(|err| err.to_string())
+                    }));
+                }
+            }
+
             rest_api_builder = rest_api_builder.add_resources(health_service.resources());
 
             health_service_processor_join_handle.replace(start_health_service(
@@ -482,6 +819,7 @@ impl SplinterDaemon {
         #[cfg(not(feature = "health"))]
         {
             health_service_processor_join_handle.replace(());
+            let _ = registry_status_handles;
         }
 
         let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api_builder.build()?.run()?;
@@ -507,6 +845,8 @@ impl SplinterDaemon {
             circuit_dispatcher_shutdown.shutdown();
             network_dispatcher_shutdown.shutdown();
             registry_shutdown.shutdown();
+            #[cfg(feature = "webhooks")]
+            webhook_dispatcher_shutdown.shutdown();
             interconnect_shutdown.shutdown();
         })
         .expect("Error setting Ctrl-C handler");
@@ -635,6 +975,53 @@ impl SplinterDaemon {
             )
         })?
     }
+
+    /// Creates the factory of the stores that are kept in the database, if any of them are
+    /// enabled. All of these stores share the factory's connection pool.
+    #[cfg(feature = "database")]
+    fn create_store_factory(&self) -> Result<Option<Box<dyn StoreFactory>>, StartError> {
+        // Allowing unused_mut because these must be mutable if any of the stores that are
+        // migrated on startup are enabled
+        #[allow(unused_mut)]
+        let mut migrations_required = false;
+        #[cfg(feature = "registry-database")]
+        {
+            migrations_required |= self.database_registry;
+        }
+        #[cfg(feature = "admin-service-event-store")]
+        {
+            migrations_required |= self.database_admin_events;
+        }
+        #[cfg(feature = "admin-audit-log")]
+        {
+            migrations_required |= self.admin_audit_log;
+        }
+        #[allow(unused_mut)]
+        let mut factory_required = migrations_required;
+        #[cfg(feature = "biome")]
+        {
+            factory_required |= self.enable_biome;
+        }
+
+        let db_url = match &self.db_url {
+            Some(db_url) if factory_required => db_url,
+            _ => return Ok(None),
+        };
+        let connection_uri = db_url.parse().map_err(|err| {
+            StartError::StorageError(format!("Invalid database URL provided: {}", err))
+        })?;
+        let store_factory =
+            splinter::store::create_store_factory(connection_uri).map_err(|err| {
+                StartError::StorageError(format!("Failed to initialize store factory: {}", err))
+            })?;
+        if migrations_required {
+            store_factory
+                .run_migrations()
+                .map_err(|err| StartError::StorageError(err.to_string()))?;
+        }
+
+        Ok(Some(store_factory))
+    }
 }
 
 #[cfg(feature = "health")]
@@ -648,7 +1035,7 @@ fn start_health_service(
     > = thread::spawn(move || {
         let mut health_service_processor = ServiceProcessor::new(
             connection,
-            "health".into(),
+            "admin".into(),
             HEALTH_SERVICE_PROCESSOR_INCOMING_CAPACITY,
             HEALTH_SERVICE_PROCESSOR_OUTGOING_CAPACITY,
             HEALTH_SERVICE_PROCESSOR_CHANNEL_CAPACITY,
@@ -688,18 +1075,35 @@ fn start_health_service(
     })?
 }
 
+// Starts the webhook dispatcher with the configured webhooks
+#[cfg(feature = "webhooks")]
+fn start_webhook_dispatcher(
+    webhooks: &[Webhook],
+) -> Result<(WebhookDispatcher, WebhookDispatcherShutdownHandle), StartError> {
+    let (dispatcher, shutdown_handle) = WebhookDispatcher::start(RetryPolicy::default())
+        .map_err(|err| StartError::WebhookError(err.to_string()))?;
+
+    for webhook in webhooks {
+        info!("Adding webhook {} ({})", webhook.id(), webhook.url());
+        dispatcher
+            .add_webhook(webhook.clone())
+            .map_err(|err| StartError::WebhookError(err.to_string()))?;
+    }
+
+    Ok((dispatcher, shutdown_handle))
+}
+
 #[cfg(feature = "biome")]
-fn build_biome_routes(db_url: String) -> Result<BiomeRestResourceManager, StartError> {
+fn build_biome_routes(
+    store_factory: &dyn StoreFactory,
+    rest_config: BiomeRestConfig,
+    admin_commands: &dyn AdminCommands,
+) -> Result<BiomeRestResourceManager, StartError> {
     info!("Adding biome routes");
-    let connection_uri = db_url.parse().map_err(|err| {
-        StartError::StorageError(format!("Invalid database URL provided: {}", err))
-    })?;
-    let store_factory = splinter::store::create_store_factory(connection_uri).map_err(|err| {
-        StartError::StorageError(format!("Failed to initialize store factory: {}", err))
-    })?;
     let mut biome_rest_provider_builder: BiomeRestResourceManagerBuilder = Default::default();
-    biome_rest_provider_builder =
-        biome_rest_provider_builder.with_user_store(store_factory.get_biome_user_store());
+    biome_rest_provider_builder = biome_rest_provider_builder
+        .with_rest_config(rest_config)
+        .with_user_store(store_factory.get_biome_user_store());
     #[cfg(feature = "biome-credentials")]
     {
         biome_rest_provider_builder = biome_rest_provider_builder
@@ -712,6 +1116,42 @@ fn build_biome_routes(db_url: String) -> Result<BiomeRestResourceManager, StartE
         biome_rest_provider_builder =
             biome_rest_provider_builder.with_key_store(store_factory.get_biome_key_store())
     }
+    #[cfg(feature = "biome-notifications")]
+    {
+        // A single store is shared so that the REST API's websocket subscribers are sent the
+        // notifications added for admin service events
+        let notification_store: Arc<dyn NotificationStore> =
+            Arc::from(store_factory.get_biome_notification_store());
+
+        #[cfg(feature = "biome-key-management")]
+        {
+            let proposal_notifier = ProposalNotifier::new(
+                notification_store.clone(),
+                Arc::from(store_factory.get_biome_key_store()),
+            )
+            .map_err(|err| {
+                StartError::AdminServiceError(format!(
+                    "Unable to start biome proposal notifier: {}",
+                    err
+                ))
+            })?;
+            admin_commands
+                .add_event_subscriber(ALL_EVENT_TYPES, Box::new(proposal_notifier))
+                .map_err(|err| {
+                    StartError::AdminServiceError(format!(
+                        "Unable to add biome proposal notifier: {}",
+                        err
+                    ))
+                })?;
+        }
+
+        biome_rest_provider_builder =
+            biome_rest_provider_builder.with_notification_store(notification_store);
+    }
+    #[cfg(not(all(feature = "biome-notifications", feature = "biome-key-management")))]
+    {
+        let _ = admin_commands;
+    }
     let biome_rest_provider = biome_rest_provider_builder.build().map_err(|err| {
         StartError::RestApiError(format!("Unable to build Biome REST routes: {}", err))
     })?;
@@ -719,6 +1159,116 @@ fn build_biome_routes(db_url: String) -> Result<BiomeRestResourceManager, StartE
     Ok(biome_rest_provider)
 }
 
+/// Builds the OAuth login and session endpoints for the configured provider. Users that log in
+/// are stored as biome users in the given factory's stores, and their provider tokens are
+/// encrypted with a key kept in the state directory.
+#[cfg(feature = "oauth")]
+fn build_oauth_resource_provider(
+    oauth_config: &OAuthConfig,
+    store_factory: &dyn StoreFactory,
+    state_dir: &str,
+) -> Result<OAuthResourceProvider, StartError> {
+    info!("Adding OAuth routes");
+
+    let client = match oauth_config {
+        OAuthConfig::GitHub {
+            client_id,
+            client_secret,
+            redirect_url,
+        } => OAuthClient::new_github(
+            client_id.to_string(),
+            client_secret.to_string(),
+            redirect_url.to_string(),
+        ),
+        OAuthConfig::OpenId {
+            client_id,
+            client_secret,
+            redirect_url,
+            discovery_url,
+        } => OAuthClient::new_openid(
+            client_id.to_string(),
+            client_secret.to_string(),
+            redirect_url.to_string(),
+            discovery_url,
+        ),
+    }
+    .map_err(|err| StartError::RestApiError(format!("Invalid OAuth configuration: {}", err)))?;
+
+    Ok(OAuthResourceProvider::new(
+        client,
+        Arc::from(store_factory.get_biome_oauth_user_store()),
+        Arc::from(store_factory.get_biome_user_store()),
+        Arc::new(AutoSecretManager::default()),
+        &load_encryption_key(
+            state_dir,
+            OAUTH_TOKEN_KEY_FILE,
+            "OAuth token key",
+            generate_token_encryption_key,
+        )?,
+    ))
+}
+
+/// Loads an encryption key, such as the key used to encrypt biome users' TOTP secrets, from the
+/// state directory, generating and saving a new key if one does not exist yet.
+#[cfg(any(feature = "biome-mfa", feature = "oauth"))]
+fn load_encryption_key(
+    state_dir: &str,
+    file_name: &str,
+    description: &str,
+    generate_key: fn() -> Vec<u8>,
+) -> Result<Vec<u8>, StartError> {
+    let key_path = Path::new(state_dir).join(file_name);
+
+    if key_path.exists() {
+        return fs::read(&key_path).map_err(|err| {
+            StartError::StorageError(format!(
+                "Unable to read {} from {}: {}",
+                description,
+                key_path.display(),
+                err
+            ))
+        });
+    }
+
+    info!("Generating {} at {}", description, key_path.display());
+    let key = generate_key();
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&key_path)
+        .and_then(|mut file| file.write_all(&key))
+        .map_err(|err| {
+            StartError::StorageError(format!(
+                "Unable to write {} to {}: {}",
+                description,
+                key_path.display(),
+                err
+            ))
+        })?;
+
+    Ok(key)
+}
+
+/// The OAuth provider used to authenticate users of the REST API
+#[cfg(feature = "oauth")]
+pub enum OAuthConfig {
+    /// Authenticate users with GitHub
+    GitHub {
+        client_id: String,
+        client_secret: String,
+        redirect_url: String,
+    },
+    /// Authenticate users with an OpenID Connect provider
+    OpenId {
+        client_id: String,
+        client_secret: String,
+        redirect_url: String,
+        /// The URL of the provider's discovery document
+        discovery_url: String,
+    },
+}
+
 #[derive(Default)]
 pub struct SplinterDaemonBuilder {
     state_dir: Option<String>,
@@ -732,17 +1282,41 @@ pub struct SplinterDaemonBuilder {
     rest_api_endpoint: Option<String>,
     #[cfg(feature = "database")]
     db_url: Option<String>,
+    #[cfg(feature = "registry-database")]
+    database_registry: bool,
+    #[cfg(feature = "admin-service-event-store")]
+    database_admin_events: bool,
+    #[cfg(feature = "admin-service-event-store")]
+    admin_event_retention: RetentionPolicy,
+    #[cfg(feature = "admin-audit-log")]
+    admin_audit_log: bool,
     #[cfg(feature = "biome")]
     enable_biome: bool,
+    #[cfg(feature = "biome-mfa")]
+    biome_mfa_required: bool,
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    biome_admins: Vec<String>,
+    #[cfg(feature = "oauth")]
+    oauth_config: Option<OAuthConfig>,
     registries: Vec<String>,
     registry_auto_refresh: Option<u64>,
     registry_forced_refresh: Option<u64>,
+    #[cfg(feature = "registry-remote-signing")]
+    registry_publisher_keys: Vec<String>,
     storage_type: Option<String>,
     heartbeat: Option<u64>,
     admin_timeout: Duration,
     #[cfg(feature = "rest-api-cors")]
     whitelist: Option<Vec<String>>,
+    #[cfg(feature = "https-bind")]
+    rest_api_tls_config: Option<RestApiTlsConfig>,
+    #[cfg(feature = "authorization")]
+    rest_api_permission_store: Option<RoleBasedPermissionStore>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "circuit-rate-limit")]
+    rate_limiter: Option<RateLimiter>,
+    #[cfg(feature = "webhooks")]
+    webhooks: Vec<Webhook>,
 }
 
 impl SplinterDaemonBuilder {
@@ -797,12 +1371,73 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    /// Sets whether the local registry is stored in the database. If not, the local registry is
+    /// stored in a YAML file in the state directory.
+    #[cfg(feature = "registry-database")]
+    pub fn with_database_registry(mut self, enabled: bool) -> Self {
+        self.database_registry = enabled;
+        self
+    }
+
+    /// Sets whether admin service events are stored in the database. If not, the admin service
+    /// only keeps a limited number of events in memory, which are lost on restart.
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn with_database_admin_events(mut self, enabled: bool) -> Self {
+        self.database_admin_events = enabled;
+        self
+    }
+
+    /// Sets the maximum age of the admin service events kept in the database. Older events are
+    /// removed as new events are added.
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn with_admin_event_max_age(mut self, max_age: Duration) -> Self {
+        self.admin_event_retention = self.admin_event_retention.with_max_age(max_age);
+        self
+    }
+
+    /// Sets the maximum number of admin service events kept in the database. The oldest events
+    /// are removed once the limit is reached.
+    #[cfg(feature = "admin-service-event-store")]
+    pub fn with_admin_event_max_items(mut self, max_items: u64) -> Self {
+        self.admin_event_retention = self.admin_event_retention.with_max_items(max_items);
+        self
+    }
+
+    /// Sets whether the audit log of circuit proposals is kept in the database. If not, the
+    /// circuit history endpoint is unavailable.
+    #[cfg(feature = "admin-audit-log")]
+    pub fn enable_admin_audit_log(mut self, enabled: bool) -> Self {
+        self.admin_audit_log = enabled;
+        self
+    }
+
     #[cfg(feature = "biome")]
     pub fn enable_biome(mut self, enabled: bool) -> Self {
         self.enable_biome = enabled;
         self
     }
 
+    /// Sets whether biome users must log in with multi-factor authentication.
+    #[cfg(feature = "biome-mfa")]
+    pub fn with_biome_mfa_required(mut self, required: bool) -> Self {
+        self.biome_mfa_required = required;
+        self
+    }
+
+    /// Sets the IDs of the biome users that may administer other users.
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    pub fn with_biome_admins(mut self, admins: Vec<String>) -> Self {
+        self.biome_admins = admins;
+        self
+    }
+
+    /// Sets the OAuth provider used to authenticate users of the REST API.
+    #[cfg(feature = "oauth")]
+    pub fn with_oauth_config(mut self, oauth_config: Option<OAuthConfig>) -> Self {
+        self.oauth_config = oauth_config;
+        self
+    }
+
     pub fn with_registries(mut self, registries: Vec<String>) -> Self {
         self.registries = registries;
         self
@@ -818,6 +1453,14 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    /// Sets the public keys of the publishers that are trusted to sign remote registry files. If
+    /// any keys are set, remote registry files must be signed by one of them.
+    #[cfg(feature = "registry-remote-signing")]
+    pub fn with_registry_publisher_keys(mut self, value: Vec<String>) -> Self {
+        self.registry_publisher_keys = value;
+        self
+    }
+
     pub fn with_storage_type(mut self, value: String) -> Self {
         self.storage_type = Some(value);
         self
@@ -839,11 +1482,44 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    /// Sets the TLS configuration used to serve the REST API over HTTPS; if not set, the REST API
+    /// is served over plain HTTP.
+    #[cfg(feature = "https-bind")]
+    pub fn with_rest_api_tls_config(mut self, value: Option<RestApiTlsConfig>) -> Self {
+        self.rest_api_tls_config = value;
+        self
+    }
+
+    /// Sets the permissions granted to REST API callers; if not set, callers may only call the
+    /// resources that do not require a specific permission.
+    #[cfg(feature = "authorization")]
+    pub fn with_rest_api_permission_store(
+        mut self,
+        value: Option<RoleBasedPermissionStore>,
+    ) -> Self {
+        self.rest_api_permission_store = value;
+        self
+    }
+
     pub fn with_strict_ref_counts(mut self, strict_ref_counts: bool) -> Self {
         self.strict_ref_counts = Some(strict_ref_counts);
         self
     }
 
+    #[cfg(feature = "circuit-rate-limit")]
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the webhooks that receive admin and scabbard events on startup; further webhooks may
+    /// be registered through the REST API.
+    #[cfg(feature = "webhooks")]
+    pub fn with_webhooks(mut self, webhooks: Vec<Webhook>) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
     pub fn build(self) -> Result<SplinterDaemon, CreateError> {
         let heartbeat = self.heartbeat.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: heartbeat".to_string())
@@ -896,6 +1572,42 @@ impl SplinterDaemonBuilder {
             }
         }
 
+        #[cfg(feature = "registry-database")]
+        {
+            if self.database_registry && db_url.is_none() {
+                return Err(CreateError::MissingRequiredField(
+                    "db_url is required to store the registry in the database.".to_string(),
+                ));
+            }
+        }
+
+        #[cfg(feature = "admin-service-event-store")]
+        {
+            if self.database_admin_events && db_url.is_none() {
+                return Err(CreateError::MissingRequiredField(
+                    "db_url is required to store admin service events in the database.".to_string(),
+                ));
+            }
+        }
+
+        #[cfg(feature = "admin-audit-log")]
+        {
+            if self.admin_audit_log && db_url.is_none() {
+                return Err(CreateError::MissingRequiredField(
+                    "db_url is required to keep the audit log of circuit proposals.".to_string(),
+                ));
+            }
+        }
+
+        #[cfg(feature = "oauth")]
+        {
+            if self.oauth_config.is_some() && !self.enable_biome {
+                return Err(CreateError::MissingRequiredField(
+                    "biome must be enabled to use OAuth authentication.".to_string(),
+                ));
+            }
+        }
+
         let registry_auto_refresh = self.registry_auto_refresh.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: registry_auto_refresh".to_string())
         })?;
@@ -925,17 +1637,41 @@ impl SplinterDaemonBuilder {
             rest_api_endpoint,
             #[cfg(feature = "database")]
             db_url,
+            #[cfg(feature = "registry-database")]
+            database_registry: self.database_registry,
+            #[cfg(feature = "admin-service-event-store")]
+            database_admin_events: self.database_admin_events,
+            #[cfg(feature = "admin-service-event-store")]
+            admin_event_retention: self.admin_event_retention,
+            #[cfg(feature = "admin-audit-log")]
+            admin_audit_log: self.admin_audit_log,
             #[cfg(feature = "biome")]
             enable_biome: self.enable_biome,
+            #[cfg(feature = "biome-mfa")]
+            biome_mfa_required: self.biome_mfa_required,
+            #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+            biome_admins: self.biome_admins,
+            #[cfg(feature = "oauth")]
+            oauth_config: self.oauth_config,
             registries: self.registries,
             registry_auto_refresh,
             registry_forced_refresh,
+            #[cfg(feature = "registry-remote-signing")]
+            registry_publisher_keys: self.registry_publisher_keys,
             storage_type,
             admin_timeout: self.admin_timeout,
             #[cfg(feature = "rest-api-cors")]
             whitelist: self.whitelist,
+            #[cfg(feature = "https-bind")]
+            rest_api_tls_config: self.rest_api_tls_config,
+            #[cfg(feature = "authorization")]
+            rest_api_permission_store: self.rest_api_permission_store,
             heartbeat,
             strict_ref_counts,
+            #[cfg(feature = "circuit-rate-limit")]
+            rate_limiter: self.rate_limiter.unwrap_or_default(),
+            #[cfg(feature = "webhooks")]
+            webhooks: self.webhooks,
         })
     }
 }
@@ -965,6 +1701,7 @@ fn set_up_circuit_dispatcher(
     node_id: &str,
     endpoints: &[String],
     state: SplinterState,
+    #[cfg(feature = "circuit-rate-limit")] rate_limiter: RateLimiter,
 ) -> Dispatcher<CircuitMessageType> {
     let mut dispatcher = Dispatcher::<CircuitMessageType>::new(Box::new(network_sender));
 
@@ -977,6 +1714,12 @@ fn set_up_circuit_dispatcher(
 
     let direct_message_handler =
         CircuitDirectMessageHandler::new(node_id.to_string(), state.clone());
+    #[cfg(feature = "circuit-rate-limit")]
+    let direct_message_handler = if rate_limiter.is_enabled() {
+        direct_message_handler.with_rate_limiter(rate_limiter)
+    } else {
+        direct_message_handler
+    };
     dispatcher.set_handler(Box::new(direct_message_handler));
 
     let circuit_error_handler = CircuitErrorHandler::new(node_id.to_string(), state.clone());
@@ -994,24 +1737,37 @@ fn create_registry(
     registries: &[String],
     auto_refresh_interval: u64,
     forced_refresh_interval: u64,
-) -> Result<(Box<dyn RwRegistry>, RegistryShutdownHandle), StartError> {
+    #[cfg(feature = "registry-database")] store_factory: Option<&dyn StoreFactory>,
+    #[cfg(feature = "registry-remote-signing")] publisher_keys: &[String],
+) -> Result<
+    (
+        Box<dyn RwRegistry>,
+        RegistryShutdownHandle,
+        Vec<RemoteYamlStatusHandle>,
+    ),
+    StartError,
+> {
     let mut registry_shutdown_handle = RegistryShutdownHandle::new();
+    let mut registry_status_handles = vec![];
 
     let local_registry_path = Path::new(state_dir)
         .join("local_registry.yaml")
         .to_str()
         .expect("path built from &str cannot be invalid")
         .to_string();
-    debug!(
-        "Creating local registry with registry file: {:?}",
-        local_registry_path
-    );
-    let local_registry = Box::new(LocalYamlRegistry::new(&local_registry_path).map_err(|err| {
-        StartError::RegistryError(format!(
-            "Failed to initialize local LocalYamlRegistry: {}",
-            err
-        ))
-    })?);
+
+    #[cfg(feature = "registry-database")]
+    let database_registry = match store_factory {
+        Some(store_factory) => create_database_registry(store_factory, &local_registry_path)?,
+        None => None,
+    };
+    #[cfg(feature = "registry-database")]
+    let local_registry = match database_registry {
+        Some(registry) => registry,
+        None => create_local_yaml_registry(&local_registry_path)?,
+    };
+    #[cfg(not(feature = "registry-database"))]
+    let local_registry = create_local_yaml_registry(&local_registry_path)?;
 
     let read_only_registries = registries
         .iter()
@@ -1050,15 +1806,41 @@ fn create_registry(
                 } else {
                     None
                 };
-                match RemoteYamlRegistry::new(
+                #[cfg(feature = "registry-remote-signing")]
+                let remote_registry = if publisher_keys.is_empty() {
+                    RemoteYamlRegistry::new(
+                        registry,
+                        state_dir,
+                        auto_refresh_interval,
+                        forced_refresh_interval,
+                    )
+                } else {
+                    RemoteYamlPublisherVerifier::new(
+                        publisher_keys,
+                        Box::new(SawtoothSecp256k1SignatureVerifier::new()),
+                    )
+                    .and_then(|publisher_verifier| {
+                        RemoteYamlRegistry::new_with_publisher_verifier(
+                            registry,
+                            state_dir,
+                            auto_refresh_interval,
+                            forced_refresh_interval,
+                            publisher_verifier,
+                        )
+                    })
+                };
+                #[cfg(not(feature = "registry-remote-signing"))]
+                let remote_registry = RemoteYamlRegistry::new(
                     registry,
                     state_dir,
                     auto_refresh_interval,
                     forced_refresh_interval,
-                ) {
+                );
+                match remote_registry {
                     Ok(registry) => {
                         registry_shutdown_handle
                             .add_remote_yaml_shutdown_handle(registry.shutdown_handle());
+                        registry_status_handles.push(registry.status_handle());
                         Some(Box::new(registry) as Box<dyn RegistryReader>)
                     }
                     Err(err) => {
@@ -1081,7 +1863,166 @@ fn create_registry(
 
     let unified_registry = Box::new(UnifiedRegistry::new(local_registry, read_only_registries));
 
-    Ok((unified_registry, registry_shutdown_handle))
+    Ok((
+        unified_registry,
+        registry_shutdown_handle,
+        registry_status_handles,
+    ))
+}
+
+fn create_local_yaml_registry(
+    local_registry_path: &str,
+) -> Result<Box<dyn RwRegistry>, StartError> {
+    debug!(
+        "Creating local registry with registry file: {:?}",
+        local_registry_path
+    );
+    Ok(Box::new(
+        LocalYamlRegistry::new(local_registry_path).map_err(|err| {
+            StartError::RegistryError(format!(
+                "Failed to initialize local LocalYamlRegistry: {}",
+                err
+            ))
+        })?,
+    ))
+}
+
+/// Creates the registry in the given factory's database to be used as the local registry, or
+/// returns `None` if the factory's storage is not persistent. If a local registry YAML file
+/// exists at `local_registry_path`, its nodes are imported into the database and the file is
+/// renamed so the import is only performed once.
+#[cfg(feature = "registry-database")]
+fn create_database_registry(
+    store_factory: &dyn StoreFactory,
+    local_registry_path: &str,
+) -> Result<Option<Box<dyn RwRegistry>>, StartError> {
+    debug!("Creating local registry in database");
+    let registry = match store_factory.get_registry_store() {
+        Some(registry) => registry,
+        None => return Ok(None),
+    };
+
+    if Path::new(local_registry_path).exists() {
+        import_local_yaml_registry(local_registry_path, &*registry)?;
+    }
+
+    Ok(Some(registry))
+}
+
+/// Inserts all nodes from the local registry YAML file into the given registry, then renames the
+/// file with an `.imported` extension. If any node cannot be inserted, none are, and the file is
+/// left in place.
+#[cfg(feature = "registry-database")]
+fn import_local_yaml_registry(
+    local_registry_path: &str,
+    registry: &dyn RwRegistry,
+) -> Result<(), StartError> {
+    info!(
+        "Importing local registry file {} into the database",
+        local_registry_path
+    );
+    let yaml_registry = LocalYamlRegistry::new(local_registry_path).map_err(|err| {
+        StartError::RegistryError(format!(
+            "Failed to read local registry file for import: {}",
+            err
+        ))
+    })?;
+    let nodes = yaml_registry
+        .list_nodes(&[])
+        .map_err(|err| {
+            StartError::RegistryError(format!(
+                "Failed to read nodes from local registry file: {}",
+                err
+            ))
+        })?
+        .collect::<Vec<_>>();
+    let node_count = nodes.len();
+    // The nodes are inserted in a single transaction, so a failed import leaves the database
+    // registry unchanged and the import is attempted again on the next start
+    registry.insert_nodes(nodes).map_err(|err| {
+        StartError::RegistryError(format!("Failed to import nodes into the database: {}", err))
+    })?;
+
+    let imported_path = format!("{}.imported", local_registry_path);
+    fs::rename(local_registry_path, &imported_path).map_err(|err| {
+        StartError::RegistryError(format!(
+            "Imported local registry file, but failed to rename it to {}: {}",
+            imported_path, err
+        ))
+    })?;
+    info!(
+        "Imported {} nodes from the local registry file; file renamed to {}",
+        node_count, imported_path
+    );
+
+    Ok(())
+}
+
+/// Keeps known peers up-to-date with the registry, so peers whose endpoints changed are
+/// reconnected to at their new endpoints, and peers whose keys changed are authorized again.
+#[cfg(feature = "registry-events")]
+fn subscribe_peer_endpoint_updates(
+    registry: &dyn RwRegistry,
+    peer_connector: PeerManagerConnector,
+) -> Result<(), StartError> {
+    registry
+        .add_event_subscriber(Box::new(
+            move |event: &RegistryEvent| -> Result<(), RegistrySubscriberError> {
+                if let RegistryEvent::NodeUpdated { old, new } = event {
+                    // The node may not be a peer, in which case there is nothing to update
+                    if old.keys != new.keys {
+                        if let Err(err) =
+                            peer_connector.reconnect_peer(&new.identity, new.endpoints.clone())
+                        {
+                            debug!("Did not reconnect to peer {}: {}", new.identity, err);
+                        }
+                    } else if old.endpoints != new.endpoints {
+                        if let Err(err) = peer_connector
+                            .update_peer_endpoints(&new.identity, new.endpoints.clone())
+                        {
+                            debug!("Did not update endpoints of peer {}: {}", new.identity, err);
+                        }
+                    }
+                }
+                Ok(())
+            },
+        ))
+        .map_err(|err| {
+            StartError::RegistryError(format!("Failed to subscribe to registry events: {}", err))
+        })
+}
+
+/// Removes the rate limit buckets and statistics of peers once they disconnect.
+#[cfg(feature = "circuit-rate-limit")]
+fn remove_disconnected_peer_limits(
+    peer_connector: &PeerManagerConnector,
+    rate_limiter: RateLimiter,
+) -> Result<(), StartError> {
+    let (sender, receiver) = mpsc::channel();
+    peer_connector.subscribe_sender(sender).map_err(|err| {
+        StartError::NetworkError(format!(
+            "Failed to subscribe to peer notifications: {}",
+            err
+        ))
+    })?;
+
+    thread::Builder::new()
+        .name("RateLimiterPeerCleanup".into())
+        .spawn(move || {
+            // The channel closes once the peer manager has shut down
+            for notification in receiver {
+                if let PeerManagerNotification::Disconnected { peer } = notification {
+                    rate_limiter.remove_peer(&peer);
+                }
+            }
+        })
+        .map(|_| ())
+        .map_err(|err| {
+            StartError::NetworkError(format!(
+                "Unable to start rate limiter peer cleanup thread: {}",
+                err
+            ))
+        })
 }
 
 fn parse_registry_arg(registry: &str) -> Result<(&str, &str), &str> {
@@ -1142,6 +2083,8 @@ pub enum StartError {
     HealthServiceError(String),
     OrchestratorError(String),
     StateError(String),
+    #[cfg(feature = "webhooks")]
+    WebhookError(String),
 }
 
 impl Error for StartError {}
@@ -1166,6 +2109,8 @@ impl fmt::Display for StartError {
                 write!(f, "the orchestrator encountered an error: {}", msg)
             }
             StartError::StateError(msg) => write!(f, "{}", msg),
+            #[cfg(feature = "webhooks")]
+            StartError::WebhookError(msg) => write!(f, "unable to set up webhooks: {}", msg),
         }
     }
 }
@@ -1211,3 +2156,71 @@ impl From<SplinterStateError> for StartError {
         StartError::StateError(err.context())
     }
 }
+
+#[cfg(all(test, feature = "registry-database"))]
+mod tests {
+    use super::*;
+
+    use splinter::registry::Node;
+    use splinter::store::{create_store_factory, ConnectionUri};
+    use tempdir::TempDir;
+
+    /// Verify that a failed import of a local registry file leaves the database registry and the
+    /// file unchanged, and that a successful import inserts all of the file's nodes and renames
+    /// the file.
+    #[test]
+    fn test_import_local_yaml_registry() {
+        let temp_dir =
+            TempDir::new("test_import_local_yaml_registry").expect("Unable to create temp dir");
+        let path = temp_dir
+            .path()
+            .join("nodes.yaml")
+            .to_str()
+            .expect("Path is not valid UTF-8")
+            .to_string();
+
+        let store_factory = create_store_factory(ConnectionUri::Sqlite(":memory:".into()))
+            .expect("Unable to create store factory");
+        store_factory
+            .run_migrations()
+            .expect("Unable to run migrations");
+        let registry = store_factory
+            .get_registry_store()
+            .expect("Store factory did not provide a registry");
+
+        // A node in the database already uses the endpoint of the second node in the file
+        registry
+            .insert_node(node("node-0", "tcp://localhost:8080"))
+            .expect("Unable to insert node");
+
+        let yaml_registry = LocalYamlRegistry::new(&path).expect("Unable to create YAML registry");
+        yaml_registry
+            .insert_node(node("node-1", "tcp://localhost:8081"))
+            .expect("Unable to insert node");
+        yaml_registry
+            .insert_node(node("node-2", "tcp://localhost:8080"))
+            .expect("Unable to insert node");
+
+        assert!(import_local_yaml_registry(&path, &*registry).is_err());
+        assert!(!registry.has_node("node-1").expect("Unable to check node"));
+        assert!(Path::new(&path).exists());
+
+        registry
+            .delete_node("node-0")
+            .expect("Unable to delete node");
+        import_local_yaml_registry(&path, &*registry).expect("Unable to import local registry");
+        assert!(registry.has_node("node-1").expect("Unable to check node"));
+        assert!(registry.has_node("node-2").expect("Unable to check node"));
+        assert!(!Path::new(&path).exists());
+        assert!(Path::new(&format!("{}.imported", path)).exists());
+    }
+
+    fn node(identity: &str, endpoint: &str) -> Node {
+        Node::builder(identity)
+            .with_endpoint(endpoint)
+            .with_display_name(identity)
+            .with_key(format!("{}-key", identity))
+            .build()
+            .expect("Unable to build node")
+    }
+}
@@ -134,6 +134,8 @@ pub enum GetTransportError {
     CertError(String),
     TlsTransportError(TlsInitError),
     IoError(io::Error),
+    #[cfg(feature = "socket-compression")]
+    CompressionError(String),
 }
 
 impl Error for GetTransportError {
@@ -142,6 +144,8 @@ impl Error for GetTransportError {
             GetTransportError::CertError(_) => None,
             GetTransportError::TlsTransportError(err) => Some(err),
             GetTransportError::IoError(err) => Some(err),
+            #[cfg(feature = "socket-compression")]
+            GetTransportError::CompressionError(_) => None,
         }
     }
 }
@@ -158,6 +162,10 @@ impl fmt::Display for GetTransportError {
             GetTransportError::IoError(err) => {
                 write!(f, "unable to get transport due to IoError: {}", err)
             }
+            #[cfg(feature = "socket-compression")]
+            GetTransportError::CompressionError(msg) => {
+                write!(f, "invalid compression configuration: {}", msg)
+            }
         }
     }
 }
@@ -28,11 +28,27 @@ mod transport;
 use flexi_logger::{style, DeferredNow, LogSpecBuilder, Logger};
 use log::Record;
 use rand::{thread_rng, Rng};
-
+#[cfg(feature = "authorization")]
+use splinter::auth::authorization::RoleBasedPermissionStore;
+#[cfg(feature = "circuit-rate-limit")]
+use splinter::circuit::rate_limit::{RateLimit, RateLimiter};
+#[cfg(feature = "https-bind")]
+use splinter::rest_api::{RestApiTlsConfig, TlsVersion};
+#[cfg(feature = "webhooks")]
+use splinter::webhooks::{read_webhooks_file, Webhook};
+
+#[cfg(any(
+    feature = "admin-audit-log",
+    feature = "admin-service-event-store",
+    feature = "registry-database"
+))]
+use crate::config::ConfigSource;
 use crate::config::{
     ClapPartialConfigBuilder, Config, ConfigBuilder, ConfigError, DefaultPartialConfigBuilder,
     EnvPartialConfigBuilder, PartialConfigBuilder, TomlPartialConfigBuilder,
 };
+#[cfg(feature = "oauth")]
+use crate::daemon::OAuthConfig;
 use crate::daemon::SplinterDaemonBuilder;
 use clap::{clap_app, crate_version};
 use clap::{Arg, ArgMatches};
@@ -42,6 +58,8 @@ use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
 use std::thread;
+#[cfg(feature = "admin-service-event-store")]
+use std::time::Duration;
 
 use error::UserError;
 use transport::build_transport;
@@ -142,6 +160,141 @@ fn find_node_id(config: &Config) -> Result<String, UserError> {
     }
 }
 
+// Builds the OAuth configuration from the configured provider, if there is one; the provider's
+// client ID, client secret and redirect URL are required.
+#[cfg(feature = "oauth")]
+fn build_oauth_config(config: &Config) -> Result<Option<OAuthConfig>, UserError> {
+    let provider = match config.oauth_provider() {
+        Some(provider) => provider,
+        None => return Ok(None),
+    };
+
+    let require = |value: Option<&str>, name: &str| {
+        value.map(String::from).ok_or_else(|| {
+            UserError::MissingArgument(format!(
+                "{} is required when an OAuth provider is configured",
+                name
+            ))
+        })
+    };
+    let client_id = require(config.oauth_client_id(), "oauth_client_id")?;
+    let client_secret = require(config.oauth_client_secret(), "oauth_client_secret")?;
+    let redirect_url = require(config.oauth_redirect_url(), "oauth_redirect_url")?;
+
+    match provider {
+        "github" => Ok(Some(OAuthConfig::GitHub {
+            client_id,
+            client_secret,
+            redirect_url,
+        })),
+        "openid" => Ok(Some(OAuthConfig::OpenId {
+            client_id,
+            client_secret,
+            redirect_url,
+            discovery_url: require(config.oauth_openid_url(), "oauth_openid_url")?,
+        })),
+        provider => Err(UserError::InvalidArgument(format!(
+            "unsupported OAuth provider: {}",
+            provider
+        ))),
+    }
+}
+
+// Builds the TLS configuration of the REST API if a certificate is configured; the certificate's
+// private key is required, and the client certificate authority and minimum TLS version may only
+// be set along with them.
+#[cfg(feature = "https-bind")]
+fn build_rest_api_tls_config(config: &Config) -> Result<Option<RestApiTlsConfig>, UserError> {
+    let (cert, key) = match (config.tls_rest_api_cert(), config.tls_rest_api_key()) {
+        (Some(cert), Some(key)) => (cert, key),
+        (None, None) => {
+            if config.tls_rest_api_client_ca().is_some()
+                || config.tls_rest_api_min_version().is_some()
+            {
+                return Err(UserError::MissingArgument(
+                    "tls_rest_api_cert and tls_rest_api_key are required to configure REST API \
+                     TLS"
+                    .to_string(),
+                ));
+            }
+            return Ok(None);
+        }
+        _ => {
+            return Err(UserError::MissingArgument(
+                "tls_rest_api_cert and tls_rest_api_key must be configured together".to_string(),
+            ))
+        }
+    };
+
+    let mut tls_config = RestApiTlsConfig::new(cert, key);
+    if let Some(client_ca) = config.tls_rest_api_client_ca() {
+        tls_config = tls_config.with_client_ca(client_ca);
+    }
+    if let Some(min_version) = config.tls_rest_api_min_version() {
+        let min_version = min_version
+            .parse::<TlsVersion>()
+            .map_err(|err| UserError::InvalidArgument(err.to_string()))?;
+        tls_config = tls_config.with_min_version(min_version);
+    }
+
+    Ok(Some(tls_config))
+}
+
+// Loads the permissions granted to REST API callers, if a permissions file is configured; a
+// relative path is resolved against the config directory.
+#[cfg(feature = "authorization")]
+fn build_rest_api_permission_store(
+    config: &Config,
+) -> Result<Option<RoleBasedPermissionStore>, UserError> {
+    let permissions_file = match config.rest_api_permissions() {
+        Some(permissions_file) => Path::new(config.config_dir()).join(permissions_file),
+        None => return Ok(None),
+    };
+
+    RoleBasedPermissionStore::from_yaml_file(&permissions_file.to_string_lossy())
+        .map(Some)
+        .map_err(|err| UserError::InvalidArgument(err.to_string()))
+}
+
+// Loads the configured webhooks, if a webhooks file is configured; a relative path is resolved
+// against the config directory.
+#[cfg(feature = "webhooks")]
+fn load_webhooks(config: &Config) -> Result<Vec<Webhook>, UserError> {
+    let webhooks_file = match config.webhooks() {
+        Some(webhooks_file) => Path::new(config.config_dir()).join(webhooks_file),
+        None => return Ok(vec![]),
+    };
+
+    read_webhooks_file(&webhooks_file).map_err(|err| UserError::InvalidArgument(err.to_string()))
+}
+
+// Builds the rate limiter for circuit messages from the configured limits. Each limit allows a
+// burst of one second's worth of traffic.
+#[cfg(feature = "circuit-rate-limit")]
+fn build_rate_limiter(config: &Config) -> RateLimiter {
+    let build_limit = |messages: Option<u64>, bytes: Option<u64>| {
+        let mut limit = RateLimit::new();
+        if let Some(rate) = messages {
+            limit = limit.with_messages_per_second(rate, rate);
+        }
+        if let Some(rate) = bytes {
+            limit = limit.with_bytes_per_second(rate, rate);
+        }
+        limit
+    };
+
+    RateLimiter::new(
+        build_limit(
+            config.peer_message_rate_limit(),
+            config.peer_byte_rate_limit(),
+        ),
+        build_limit(
+            config.circuit_message_rate_limit(),
+            config.circuit_byte_rate_limit(),
+        ),
+    )
+}
+
 // format for logs
 pub fn log_format(
     w: &mut dyn std::io::Write,
@@ -319,6 +472,118 @@ fn main() {
             .long_help("Enable the biome subsystem"),
     );
 
+    #[cfg(feature = "biome-mfa")]
+    let app = app.arg(
+        Arg::with_name("biome_mfa_required")
+            .long("biome-mfa-required")
+            .long_help("Require biome users to log in with multi-factor authentication"),
+    );
+
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    let app = app.arg(
+        Arg::with_name("biome_admins")
+            .long("biome-admins")
+            .multiple(true)
+            .takes_value(true)
+            .long_help("IDs of the biome users that may administer other users"),
+    );
+
+    #[cfg(feature = "oauth")]
+    let app = app
+        .arg(
+            Arg::with_name("oauth_provider")
+                .long("oauth-provider")
+                .takes_value(true)
+                .possible_values(&["github", "openid"])
+                .long_help("OAuth provider used to authenticate users of the REST API"),
+        )
+        .arg(
+            Arg::with_name("oauth_client_id")
+                .long("oauth-client-id")
+                .takes_value(true)
+                .long_help("Client ID of the node's OAuth app"),
+        )
+        .arg(
+            Arg::with_name("oauth_client_secret")
+                .long("oauth-client-secret")
+                .takes_value(true)
+                .long_help("Client secret of the node's OAuth app"),
+        )
+        .arg(
+            Arg::with_name("oauth_redirect_url")
+                .long("oauth-redirect-url")
+                .takes_value(true)
+                .long_help(
+                    "URL of the node's `/oauth/callback` endpoint, which the OAuth provider \
+                     redirects to after authorization",
+                ),
+        )
+        .arg(
+            Arg::with_name("oauth_openid_url")
+                .long("oauth-openid-url")
+                .takes_value(true)
+                .long_help(
+                    "URL of the OpenID Connect provider's discovery document; required if the \
+                     OAuth provider is `openid`",
+                ),
+        );
+
+    #[cfg(feature = "https-bind")]
+    let app = app
+        .arg(
+            Arg::with_name("tls_rest_api_cert")
+                .long("tls-rest-api-cert")
+                .takes_value(true)
+                .long_help(
+                    "Certificate chain used to serve the REST API over HTTPS; the REST API is \
+                     served over plain HTTP if not set",
+                ),
+        )
+        .arg(
+            Arg::with_name("tls_rest_api_key")
+                .long("tls-rest-api-key")
+                .takes_value(true)
+                .long_help("Private key of the REST API's certificate"),
+        )
+        .arg(
+            Arg::with_name("tls_rest_api_client_ca")
+                .long("tls-rest-api-client-ca")
+                .takes_value(true)
+                .long_help(
+                    "Certificate authorities that REST API clients must present a certificate \
+                     from; client certificates are not required if not set",
+                ),
+        )
+        .arg(
+            Arg::with_name("tls_rest_api_min_version")
+                .long("tls-rest-api-min-version")
+                .takes_value(true)
+                .possible_values(&["1.2", "1.3"])
+                .long_help("Minimum TLS version accepted by the REST API (default: 1.2)"),
+        );
+
+    #[cfg(feature = "authorization")]
+    let app = app.arg(
+        Arg::with_name("rest_api_permissions")
+            .long("rest-api-permissions")
+            .takes_value(true)
+            .long_help(
+                "YAML file of the roles and permissions granted to REST API callers; relative \
+                 paths are resolved against the config directory",
+            ),
+    );
+
+    #[cfg(feature = "webhooks")]
+    let app = app.arg(
+        Arg::with_name("webhooks")
+            .long("webhooks")
+            .takes_value(true)
+            .long_help(
+                "YAML file of the webhooks that receive admin and scabbard events; relative \
+                 paths are resolved against the config directory",
+            ),
+    );
+
     #[cfg(feature = "rest-api-cors")]
     let app = app.arg(
         Arg::with_name("whitelist")
@@ -435,16 +700,83 @@ fn start_daemon(matches: ArgMatches) -> Result<(), UserError> {
         daemon_builder = daemon_builder.with_db_url(Some(String::from(db_url)));
     }
 
+    // The local registry is only moved into the database if one has been configured explicitly
+    #[cfg(feature = "registry-database")]
+    {
+        daemon_builder = daemon_builder
+            .with_database_registry(config.database_source() != &ConfigSource::Default);
+    }
+
+    // Admin service events are only stored in the database if one has been configured explicitly
+    #[cfg(feature = "admin-service-event-store")]
+    {
+        daemon_builder = daemon_builder
+            .with_database_admin_events(config.database_source() != &ConfigSource::Default)
+            .with_admin_event_max_age(Duration::from_secs(config.admin_event_max_age()))
+            .with_admin_event_max_items(config.admin_event_max_items());
+    }
+
+    // The audit log of circuit proposals is only kept if a database has been configured
+    // explicitly
+    #[cfg(feature = "admin-audit-log")]
+    {
+        daemon_builder = daemon_builder
+            .enable_admin_audit_log(config.database_source() != &ConfigSource::Default);
+    }
+
+    #[cfg(feature = "registry-remote-signing")]
+    {
+        daemon_builder =
+            daemon_builder.with_registry_publisher_keys(config.registry_publisher_keys().to_vec());
+    }
+
     #[cfg(feature = "biome")]
     {
         daemon_builder = daemon_builder.enable_biome(config.enable_biome());
     }
 
+    #[cfg(feature = "biome-mfa")]
+    {
+        daemon_builder = daemon_builder.with_biome_mfa_required(config.biome_mfa_required());
+    }
+
+    #[cfg(any(feature = "biome-login-lockout", feature = "biome-user-admin"))]
+    {
+        daemon_builder = daemon_builder.with_biome_admins(config.biome_admins().to_vec());
+    }
+
+    #[cfg(feature = "oauth")]
+    {
+        daemon_builder = daemon_builder.with_oauth_config(build_oauth_config(&config)?);
+    }
+
     #[cfg(feature = "rest-api-cors")]
     {
         daemon_builder = daemon_builder.with_whitelist(config.whitelist().map(ToOwned::to_owned));
     }
 
+    #[cfg(feature = "https-bind")]
+    {
+        daemon_builder =
+            daemon_builder.with_rest_api_tls_config(build_rest_api_tls_config(&config)?);
+    }
+
+    #[cfg(feature = "authorization")]
+    {
+        daemon_builder = daemon_builder
+            .with_rest_api_permission_store(build_rest_api_permission_store(&config)?);
+    }
+
+    #[cfg(feature = "circuit-rate-limit")]
+    {
+        daemon_builder = daemon_builder.with_rate_limiter(build_rate_limiter(&config));
+    }
+
+    #[cfg(feature = "webhooks")]
+    {
+        daemon_builder = daemon_builder.with_webhooks(load_webhooks(&config)?);
+    }
+
     let mut node = daemon_builder.build().map_err(|err| {
         UserError::daemon_err_with_source("unable to build the Splinter daemon", Box::new(err))
     })?;
@@ -0,0 +1,35 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use splinter::actix_web::{web, Error, HttpRequest, HttpResponse};
+use splinter::futures::{Future, IntoFuture};
+use splinter::metrics;
+
+pub fn get_metrics(
+    _: HttpRequest,
+    _: web::Payload,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match metrics::gather() {
+        Ok(text) => Box::new(
+            HttpResponse::Ok()
+                .content_type(metrics::TEXT_CONTENT_TYPE)
+                .body(text)
+                .into_future(),
+        ),
+        Err(err) => {
+            error!("{}", err);
+            Box::new(HttpResponse::InternalServerError().finish().into_future())
+        }
+    }
+}
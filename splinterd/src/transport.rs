@@ -16,8 +16,12 @@ use std::fs;
 use std::path::Path;
 
 use splinter::transport::multi::MultiTransport;
+#[cfg(feature = "quic-transport")]
+use splinter::transport::quic::QuicTransport;
 use splinter::transport::socket::TcpTransport;
 use splinter::transport::socket::TlsTransport;
+#[cfg(feature = "socket-compression")]
+use splinter::transport::socket::{Compression, CompressionConfig};
 use splinter::transport::tls::{TlsConfig, TlsConfigBuilder};
 #[cfg(feature = "ws-transport")]
 use splinter::transport::ws::WsTransport;
@@ -31,9 +35,15 @@ type SendableTransport = Box<dyn Transport + Send>;
 pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportError> {
     let mut transports: Vec<SendableTransport> = vec![];
 
+    #[cfg(feature = "socket-compression")]
+    let compression = build_compression_config(&config)?;
+
     // add tcp transport
     // this will be default for endpoints without a prefix
-    transports.push(Box::new(TcpTransport::default()));
+    let tcp_transport = TcpTransport::default();
+    #[cfg(feature = "socket-compression")]
+    let tcp_transport = tcp_transport.with_compression(compression.clone());
+    transports.push(Box::new(tcp_transport));
 
     // add web socket transport
 
@@ -43,13 +53,16 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
         validate_tls_config(&tls_config)?;
         print_tls_config(&tls_config)?;
 
-        transports.push(Box::new(TlsTransport::new(
+        let tls_transport = TlsTransport::new(
             tls_config.ca_certs_file().to_owned(),
             tls_config.client_private_key_file().to_string(),
             tls_config.client_cert_file().to_string(),
             tls_config.server_private_key_file().to_string(),
             tls_config.server_cert_file().to_string(),
-        )?));
+        )?;
+        #[cfg(feature = "socket-compression")]
+        let tls_transport = tls_transport.with_compression(compression);
+        transports.push(Box::new(tls_transport));
 
         #[cfg(feature = "ws-transport")]
         transports.push(Box::new(WsTransport::new(Some(&tls_config)).map_err(
@@ -57,6 +70,12 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
                 GetTransportError::CertError(format!("Failed to create WebSocket transport: {}", e))
             },
         )?));
+
+        // QUIC always uses TLS, so it is only available when TLS is enabled
+        #[cfg(feature = "quic-transport")]
+        transports.push(Box::new(QuicTransport::new(&tls_config).map_err(|e| {
+            GetTransportError::CertError(format!("Failed to create QUIC transport: {}", e))
+        })?));
     } else {
         #[cfg(feature = "ws-transport")]
         transports.push(Box::new(WsTransport::default()));
@@ -65,6 +84,21 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
     Ok(MultiTransport::new(transports))
 }
 
+#[cfg(feature = "socket-compression")]
+fn build_compression_config(config: &Config) -> Result<CompressionConfig, GetTransportError> {
+    let algorithms = config
+        .compression()
+        .iter()
+        .map(|algorithm| algorithm.parse::<Compression>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| GetTransportError::CompressionError(err.to_string()))?;
+
+    Ok(CompressionConfig::new(
+        algorithms,
+        config.compression_threshold(),
+    ))
+}
+
 fn build_tls_config(config: &Config) -> Result<TlsConfig, GetTransportError> {
     let mut builder = TlsConfigBuilder::new()
         .with_client_cert_file(config.tls_client_cert().to_string())